@@ -0,0 +1,271 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Append-only log of the owner API calls changing the wallet, kept in the wallet's
+//! directory for compliance reviews
+
+use chrono::{DateTime, Utc};
+use ring::digest;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::libwallet::{Error, ErrorKind};
+use crate::types::READ_ONLY_METHODS;
+use crate::util::to_hex;
+
+/// File of the audit log, in the wallet's top level directory
+pub const AUDIT_LOG_FILE: &str = "owner_audit.log";
+
+/// Methods whose parameters hold secrets such as passwords or seed words, which
+/// aren't hashed as their hash could be brute forced
+const SECRET_PARAMS_METHODS: &[&str] = &[
+	"create_wallet",
+	"open_wallet",
+	"get_mnemonic",
+	"export_view_key",
+	"create_watch_only_wallet",
+	"get_seed_quiz",
+	"verify_seed_words",
+	"change_password",
+	"recover_from_mnemonic",
+	"get_delete_token",
+	"delete_wallet",
+];
+
+/// Methods not logged, besides the read-only ones
+const UNAUDITED_METHODS: &[&str] = &["init_secure_api", "get_audit_log"];
+
+/// A call recorded in the audit log
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+	/// When the call was answered
+	pub timestamp: DateTime<Utc>,
+	/// Method called
+	pub method: String,
+	/// SHA-256 of the call's parameters, without the token, or `None` if they
+	/// hold secrets
+	pub params_hash: Option<String>,
+	/// Fingerprint of the token the call was made with, telling the callers holding
+	/// different tokens apart, if it was made with one
+	pub caller: Option<String>,
+	/// Whether the call succeeded
+	pub success: bool,
+	/// Error the call failed with, if it did
+	pub error: Option<String>,
+}
+
+impl AuditEntry {
+	/// Entry of a JSON-RPC call answered with the reply, if its method is audited.
+	/// Notifications, which aren't answered, are recorded as failed.
+	pub fn from_call(call: &serde_json::Value, reply: Option<&serde_json::Value>) -> Option<Self> {
+		let method = call["method"].as_str()?;
+		if !is_audited(method) {
+			return None;
+		}
+		let params = &call["params"];
+		let token = match params {
+			serde_json::Value::Object(p) => p.get("token"),
+			serde_json::Value::Array(p) => p.first().filter(|t| is_token(t)),
+			_ => None,
+		};
+		let error = match reply {
+			Some(r) if r.get("error").is_some() => Some(match r["error"]["message"].as_str() {
+				Some(m) => m.to_owned(),
+				None => r["error"].to_string(),
+			}),
+			Some(r) if !r["result"]["Err"].is_null() => Some(r["result"]["Err"].to_string()),
+			Some(_) => None,
+			None => Some("Notification, not answered".to_owned()),
+		};
+		Some(AuditEntry {
+			timestamp: Utc::now(),
+			method: method.to_owned(),
+			params_hash: params_hash(method, params),
+			caller: token.and_then(|t| t.as_str()).map(token_fingerprint),
+			success: error.is_none(),
+			error,
+		})
+	}
+}
+
+/// Page of the audit log, oldest entries first
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditLogPage {
+	/// Number of entries in the log
+	pub total: usize,
+	/// Entries of the page
+	pub entries: Vec<AuditEntry>,
+}
+
+/// Whether calls of the method are recorded: all of them but the read-only ones
+pub fn is_audited(method: &str) -> bool {
+	!READ_ONLY_METHODS.contains(&method) && !UNAUDITED_METHODS.contains(&method)
+}
+
+/// Hash of the parameters of a call to the method, leaving out its token
+pub fn params_hash(method: &str, params: &serde_json::Value) -> Option<String> {
+	if SECRET_PARAMS_METHODS.contains(&method) {
+		return None;
+	}
+	let json = serde_json::to_string(&without_token(params)).unwrap_or_default();
+	Some(to_hex(
+		digest::digest(&digest::SHA256, json.as_bytes())
+			.as_ref()
+			.to_vec(),
+	))
+}
+
+/// Parameters of a call to the method as they can be logged, leaving out its token
+/// and any secret
+pub fn redacted_params(method: &str, params: &serde_json::Value) -> serde_json::Value {
+	match SECRET_PARAMS_METHODS.contains(&method) {
+		true => serde_json::Value::String("<redacted>".to_owned()),
+		false => without_token(params),
+	}
+}
+
+/// Parameters of a call without its token
+fn without_token(params: &serde_json::Value) -> serde_json::Value {
+	match params {
+		serde_json::Value::Object(p) => {
+			let mut p = p.clone();
+			p.remove("token");
+			serde_json::Value::Object(p)
+		}
+		// positional tokens come first
+		serde_json::Value::Array(p) if p.first().map_or(false, is_token) => {
+			serde_json::Value::Array(p[1..].to_vec())
+		}
+		p => p.clone(),
+	}
+}
+
+/// Whether a parameter is a token, a keychain mask or none
+fn is_token(param: &serde_json::Value) -> bool {
+	match param {
+		serde_json::Value::Null => true,
+		serde_json::Value::String(s) => s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()),
+		_ => false,
+	}
+}
+
+/// Fingerprint of a token, which can't be used in its place
+pub fn token_fingerprint(token: &str) -> String {
+	let hash = digest::digest(&digest::SHA256, token.as_bytes());
+	to_hex(hash.as_ref()[..8].to_vec())
+}
+
+/// Append an entry to the audit log of the directory
+pub fn append(dir: &Path, entry: &AuditEntry) -> Result<(), Error> {
+	let mut line = serde_json::to_string(entry).map_err(|e| {
+		ErrorKind::GenericError(format!("Unable to serialize the audit entry: {}", e))
+	})?;
+	line.push('\n');
+	let mut file = OpenOptions::new()
+		.create(true)
+		.append(true)
+		.open(dir.join(AUDIT_LOG_FILE))?;
+	file.write_all(line.as_bytes())?;
+	file.sync_data()?;
+	Ok(())
+}
+
+/// Entries of the audit log of the directory, oldest first, read from the file as
+/// they're iterated over. A line which isn't an entry, as left by a write
+/// interrupted by a crash, is skipped.
+pub fn entries(dir: &Path) -> Result<Box<dyn Iterator<Item = AuditEntry> + Send>, Error> {
+	let path = dir.join(AUDIT_LOG_FILE);
+	if !path.exists() {
+		return Ok(Box::new(std::iter::empty()));
+	}
+	let lines = BufReader::new(File::open(path)?).lines();
+	Ok(Box::new(lines.filter_map(|l| {
+		l.ok().and_then(|l| serde_json::from_str(&l).ok())
+	})))
+}
+
+/// Page of the audit log of the directory, its lines which aren't entries skipped
+pub fn read(dir: &Path, offset: usize, limit: Option<usize>) -> Result<AuditLogPage, Error> {
+	let mut total = 0;
+	let mut entries = vec![];
+	for entry in self::entries(dir)? {
+		if total >= offset && limit.map_or(true, |l| entries.len() < l) {
+			entries.push(entry);
+		}
+		total += 1;
+	}
+	Ok(AuditLogPage { total, entries })
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	const TOKEN: &str = "d202964900000000d302964900000000d402964900000000d502964900000000";
+
+	#[test]
+	fn audit_log() {
+		// read-only calls aren't audited
+		let call = json!({"method": "retrieve_summary_info", "params": {"token": TOKEN}});
+		assert!(AuditEntry::from_call(&call, None).is_none());
+
+		// the token isn't part of the parameters hashed
+		let call = json!({"id": 1, "method": "cancel_tx", "params": {"token": TOKEN, "tx_id": 3}});
+		let reply = json!({"id": 1, "result": {"Ok": null}});
+		let entry = AuditEntry::from_call(&call, Some(&reply)).unwrap();
+		assert!(entry.success);
+		assert_eq!(entry.caller, Some(token_fingerprint(TOKEN)));
+		assert_eq!(
+			entry.params_hash,
+			params_hash("cancel_tx", &json!({"tx_id": 3}))
+		);
+		assert_eq!(
+			params_hash("cancel_tx", &json!([TOKEN, 3])),
+			params_hash("cancel_tx", &json!([null, 3]))
+		);
+
+		// secrets aren't hashed, and failures are recorded with their error
+		let call = json!({"id": 2, "method": "open_wallet", "params": [null, "password"]});
+		let reply = json!({"id": 2, "result": {"Err": {"GenericError": "wrong password"}}});
+		let failed = AuditEntry::from_call(&call, Some(&reply)).unwrap();
+		assert_eq!(failed.params_hash, None);
+		assert_eq!(
+			redacted_params("open_wallet", &json!([null, "password"])),
+			json!("<redacted>")
+		);
+		assert_eq!(redacted_params("cancel_tx", &json!([TOKEN, 3])), json!([3]));
+		assert!(!failed.success);
+		assert!(failed.error.unwrap().contains("wrong password"));
+
+		let dir = tempfile::tempdir().unwrap();
+		assert_eq!(read(dir.path(), 0, None).unwrap().total, 0);
+		for _ in 0..3 {
+			append(dir.path(), &entry).unwrap();
+		}
+		let page = read(dir.path(), 1, Some(1)).unwrap();
+		assert_eq!(page.total, 3);
+		assert_eq!(page.entries, vec![entry.clone()]);
+
+		// a line left by an interrupted write is skipped
+		let mut file = OpenOptions::new()
+			.append(true)
+			.open(dir.path().join(AUDIT_LOG_FILE))
+			.unwrap();
+		file.write_all(b"{\"timestamp\":").unwrap();
+		append(dir.path(), &entry).unwrap();
+		assert_eq!(entries(dir.path()).unwrap().count(), 3);
+	}
+}
@@ -0,0 +1,126 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Settings of the wallet's configuration file applied by a reload, while the
+//! listeners keep running
+
+use log::LevelFilter;
+use std::collections::BTreeMap;
+
+use crate::config::{ConfigError, GlobalWalletConfig};
+use crate::util::logger::LoggingConfig;
+use crate::util::LogLevel;
+
+/// Settings a reload applies, the keys ending with a dot standing for all the
+/// settings of their section
+const RELOADED_KEYS: &[&str] = &[
+	"wallet.check_node_api_http_addr",
+	"wallet.node_api_secret_path",
+	"logging.stdout_log_level",
+	"logging.file_log_level",
+	"tor.",
+	"epicbox.",
+];
+
+/// Settings changed by a reload of the configuration
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConfigReload {
+	/// Settings applied, e.g. `wallet.check_node_api_http_addr`
+	pub applied: Vec<String>,
+	/// Settings only applied once the wallet is restarted
+	pub restart_required: Vec<String>,
+}
+
+/// Whether a change of the setting is applied by a reload
+pub fn is_reloaded(key: &str) -> bool {
+	RELOADED_KEYS.iter().any(|k| match k.ends_with('.') {
+		true => key.starts_with(k),
+		false => key == *k,
+	})
+}
+
+/// Keys of the settings whose value differs between the configurations, those
+/// removed included
+pub fn changed_keys(
+	old: &GlobalWalletConfig,
+	new: &GlobalWalletConfig,
+) -> Result<Vec<String>, ConfigError> {
+	let mut old_values: BTreeMap<String, String> = old
+		.effective_values()?
+		.into_iter()
+		.map(|(k, v, _)| (k, v))
+		.collect();
+	let mut changed = vec![];
+	for (k, v, _) in new.effective_values()? {
+		if old_values.remove(&k).as_ref() != Some(&v) {
+			changed.push(k);
+		}
+	}
+	changed.extend(old_values.into_iter().map(|(k, _)| k));
+	Ok(changed)
+}
+
+/// Most verbose level of the logs written by the configuration
+pub fn max_log_level(config: &LoggingConfig) -> LevelFilter {
+	let level = |l: &LogLevel| match l {
+		LogLevel::Error => LevelFilter::Error,
+		LogLevel::Warning => LevelFilter::Warn,
+		LogLevel::Info => LevelFilter::Info,
+		LogLevel::Debug => LevelFilter::Debug,
+		LogLevel::Trace => LevelFilter::Trace,
+	};
+	let mut max = LevelFilter::Off;
+	if config.log_to_stdout {
+		max = max.max(level(&config.stdout_log_level));
+	}
+	if config.log_to_file {
+		max = max.max(level(&config.file_log_level));
+	}
+	max
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn config_reload() {
+		assert!(is_reloaded("wallet.check_node_api_http_addr"));
+		assert!(is_reloaded("epicbox.epicbox_domain"));
+		assert!(!is_reloaded("wallet.api_listen_port"));
+		assert!(!is_reloaded("tor_config"));
+
+		let old = GlobalWalletConfig::default();
+		let mut new = old.clone();
+		{
+			let members = new.members.as_mut().unwrap();
+			members.wallet.check_node_api_http_addr = "http://10.0.0.2:3413".to_owned();
+			members.wallet.api_listen_port = 3420;
+		}
+		let changed = changed_keys(&old, &new).unwrap();
+		assert_eq!(changed.len(), 2);
+		assert!(changed.contains(&"wallet.check_node_api_http_addr".to_owned()));
+		assert!(changed.contains(&"wallet.api_listen_port".to_owned()));
+		assert!(changed_keys(&old, &old).unwrap().is_empty());
+
+		let mut logging = LoggingConfig::default();
+		logging.log_to_stdout = true;
+		logging.stdout_log_level = LogLevel::Warning;
+		logging.log_to_file = true;
+		logging.file_log_level = LogLevel::Debug;
+		assert_eq!(max_log_level(&logging), LevelFilter::Debug);
+		logging.log_to_file = false;
+		assert_eq!(max_log_level(&logging), LevelFilter::Warn);
+	}
+}
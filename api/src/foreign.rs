@@ -17,8 +17,8 @@
 use crate::keychain::Keychain;
 use crate::libwallet::api_impl::foreign;
 use crate::libwallet::{
-	BlockFees, CbData, Error, NodeClient, NodeVersionInfo, Slate, VersionInfo, WalletInst,
-	WalletLCProvider,
+	BlockFees, CbData, Error, NodeClient, NodeVersionInfo, ReceiveHook, Slate, VersionInfo,
+	WalletInst, WalletLCProvider,
 };
 use crate::util::secp::key::SecretKey;
 use crate::util::Mutex;
@@ -68,8 +68,12 @@ where
 	pub doctest_mode: bool,
 	/// foreign check middleware
 	middleware: Option<ForeignCheckMiddleware>,
+	/// check applied to incoming slates before they're signed
+	receive_hook: Option<Arc<dyn ReceiveHook>>,
 	/// Stored keychain mask (in case the stored wallet seed is tokenized)
 	keychain_mask: Option<SecretKey>,
+	/// Whether responses to received slates are signed with the wallet's address key
+	sign_responses: bool,
 }
 
 impl<'a, L, C, K> Foreign<'a, L, C, K>
@@ -167,10 +171,56 @@ where
 			wallet_inst,
 			doctest_mode: false,
 			middleware,
+			receive_hook: None,
 			keychain_mask,
+			sign_responses: false,
 		}
 	}
 
+	/// Set the hook checking incoming slates in
+	/// [`receive_tx`](struct.Foreign.html#method.receive_tx) before the wallet signs them.
+	/// A slate rejected by the hook is returned to the sender as an
+	/// `ErrorKind::ReceiveRejected` error carrying the hook's reason.
+	///
+	/// # Arguments
+	/// * `receive_hook` - An implementation of the
+	/// [`ReceiveHook`](../epic_wallet_libwallet/types/trait.ReceiveHook.html) trait, or `None`
+	/// to accept all slates.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Foreign.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env_foreign!(wallet, wallet_config);
+	///
+	/// let mut api_foreign = Foreign::new(wallet.clone(), None, None);
+	/// api_foreign.set_receive_hook(None);
+	/// ```
+
+	pub fn set_receive_hook(&mut self, receive_hook: Option<Arc<dyn ReceiveHook>>) {
+		self.receive_hook = receive_hook;
+	}
+
+	/// Set whether the slates returned by
+	/// [`receive_tx`](struct.Foreign.html#method.receive_tx) are signed with the key of the
+	/// wallet's payment proof address, so the sender can verify the response came from this
+	/// wallet rather than a relay in between. Disabled by default.
+	///
+	/// # Arguments
+	/// * `sign_responses` - Whether responses are signed
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Foreign.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env_foreign!(wallet, wallet_config);
+	///
+	/// let mut api_foreign = Foreign::new(wallet.clone(), None, None);
+	/// api_foreign.set_sign_responses(true);
+	/// ```
+
+	pub fn set_sign_responses(&mut self, sign_responses: bool) {
+		self.sign_responses = sign_responses;
+	}
+
 	/// Return the version capabilities of the running ForeignApi Node
 	/// # Arguments
 	/// None
@@ -375,6 +425,11 @@ where
 	/// # Remarks
 	///
 	/// * This method will store a partially completed transaction in the wallet's transaction log.
+	/// * The response is kept until the transaction is confirmed or cancelled. Receiving the same
+	/// slate again returns it unchanged, so a sender whose connection dropped before the response
+	/// came back can simply send the slate again.
+	/// * If enabled with [`set_sign_responses`](struct.Foreign.html#method.set_sign_responses),
+	/// the returned slate carries a signature made with the wallet's payment proof address key.
 	///
 	/// # Example
 	/// Set up as in [new](struct.Foreign.html#method.new) method above.
@@ -400,6 +455,11 @@ where
 		dest_acct_name: Option<&str>,
 		message: Option<String>,
 	) -> Result<Slate, Error> {
+		// the hook may run an external command, so the other calls aren't held up
+		// waiting for the wallet meanwhile
+		if let Some(h) = self.receive_hook.as_ref() {
+			h.check(slate)?;
+		}
 		let mut w_lock = self.wallet_inst.lock();
 		let w = w_lock.lc_provider()?.wallet_inst()?;
 		if let Some(m) = self.middleware.as_ref() {
@@ -415,6 +475,7 @@ where
 			slate,
 			dest_acct_name,
 			message,
+			self.sign_responses,
 			self.doctest_mode,
 		)
 	}
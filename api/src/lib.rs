@@ -36,21 +36,41 @@ extern crate serde_json;
 
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate lazy_static;
 
+mod audit_log;
+mod config_reload;
 mod foreign;
 mod foreign_rpc;
 
+mod open_throttle;
 mod owner;
 mod owner_rpc;
 mod owner_rpc_s;
+mod page_snapshots;
+mod rpc_metrics;
+mod scan_jobs;
+
+mod send_queue;
 
 mod types;
 
+pub use crate::audit_log::{AuditEntry, AuditLogPage, AUDIT_LOG_FILE};
+pub use crate::config_reload::ConfigReload;
 pub use crate::foreign::{Foreign, ForeignCheckMiddleware, ForeignCheckMiddlewareFn};
 pub use crate::foreign_rpc::ForeignRpc;
+pub use crate::open_throttle::{
+	OPEN_THROTTLE_DEFAULT_LOCKOUT_SECS, OPEN_THROTTLE_DEFAULT_MAX_ATTEMPTS,
+	OPEN_THROTTLE_DEFAULT_MAX_LOCKOUT_SECS,
+};
 pub use crate::owner::Owner;
 pub use crate::owner_rpc::OwnerRpc;
 pub use crate::owner_rpc_s::OwnerRpcS;
+pub use crate::rpc_metrics::{handle_timed_request, set_slow_rpc_ms, RpcMethodMetrics};
+pub use crate::send_queue::{
+	SEND_QUEUE_DEFAULT_RETRY_DELAY, SEND_QUEUE_DEFAULT_WORKERS, SEND_QUEUE_MAX_ATTEMPTS,
+};
 
 pub use crate::foreign_rpc::foreign_rpc as foreign_rpc_client;
 pub use crate::foreign_rpc::run_doctest_foreign;
@@ -58,5 +78,5 @@ pub use crate::owner_rpc::run_doctest_owner;
 
 pub use types::{
 	ECDHPubkey, EncryptedRequest, EncryptedResponse, EncryptionErrorResponse, PubAddress, RpcId,
-	Token,
+	Token, TokenCall, TokenScope,
 };
@@ -0,0 +1,158 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backoff on failed attempts at opening the wallet, so its password can't be
+//! guessed at the speed of the owner API
+
+use std::time::{Duration, Instant};
+
+use crate::config::OpenThrottleConfig;
+
+/// Consecutive failed attempts allowed before opening is locked out, unless configured otherwise
+pub const OPEN_THROTTLE_DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// Duration (seconds) of the first lockout, unless configured otherwise
+pub const OPEN_THROTTLE_DEFAULT_LOCKOUT_SECS: u64 = 30;
+
+/// Longest lockout (seconds), unless configured otherwise
+pub const OPEN_THROTTLE_DEFAULT_MAX_LOCKOUT_SECS: u64 = 3600;
+
+/// Failed attempts at opening the wallet, and the lockout they led to if any
+pub struct OpenThrottle {
+	max_attempts: u32,
+	lockout: Duration,
+	max_lockout: Duration,
+	failures: u32,
+	locked_until: Option<Instant>,
+}
+
+impl OpenThrottle {
+	/// Throttle with the given settings, defaulting those that aren't set
+	pub fn new(config: &OpenThrottleConfig) -> OpenThrottle {
+		let lockout_secs = config
+			.lockout_secs
+			.unwrap_or(OPEN_THROTTLE_DEFAULT_LOCKOUT_SECS);
+		let max_lockout_secs = config
+			.max_lockout_secs
+			.unwrap_or(OPEN_THROTTLE_DEFAULT_MAX_LOCKOUT_SECS);
+		OpenThrottle {
+			max_attempts: config
+				.max_attempts
+				.unwrap_or(OPEN_THROTTLE_DEFAULT_MAX_ATTEMPTS),
+			lockout: Duration::from_secs(lockout_secs),
+			max_lockout: Duration::from_secs(max_lockout_secs.max(lockout_secs)),
+			failures: 0,
+			locked_until: None,
+		}
+	}
+
+	/// Seconds left, rounded up, before an attempt is allowed at `now`, if locked out
+	pub fn locked_out(&self, now: Instant) -> Option<u64> {
+		match self.locked_until {
+			Some(until) if until > now => {
+				let left = until - now;
+				Some(left.as_secs() + (left.subsec_nanos() > 0) as u64)
+			}
+			_ => None,
+		}
+	}
+
+	/// Record a failed attempt at `now`. Returns the lockout it starts, if any: the
+	/// first one once the allowed attempts failed, then twice as long as the
+	/// previous one with each further failure, up to the longest lockout.
+	pub fn failed(&mut self, now: Instant) -> Option<Duration> {
+		if self.max_attempts == 0 {
+			return None;
+		}
+		self.failures = self.failures.saturating_add(1);
+		if self.failures < self.max_attempts {
+			return None;
+		}
+		let doublings = (self.failures - self.max_attempts).min(31);
+		let lockout = self
+			.lockout
+			.checked_mul(1u32 << doublings)
+			.unwrap_or(self.max_lockout)
+			.min(self.max_lockout);
+		self.locked_until = Some(now + lockout);
+		Some(lockout)
+	}
+
+	/// Record a successful attempt, forgetting the failed ones
+	pub fn succeeded(&mut self) {
+		self.failures = 0;
+		self.locked_until = None;
+	}
+
+	/// Number of consecutive failed attempts
+	pub fn failures(&self) -> u32 {
+		self.failures
+	}
+}
+
+impl Default for OpenThrottle {
+	fn default() -> OpenThrottle {
+		OpenThrottle::new(&OpenThrottleConfig {
+			max_attempts: None,
+			lockout_secs: None,
+			max_lockout_secs: None,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn open_throttle_backoff() {
+		let mut t = OpenThrottle::new(&OpenThrottleConfig {
+			max_attempts: Some(3),
+			lockout_secs: Some(10),
+			max_lockout_secs: Some(35),
+		});
+		let start = Instant::now();
+		assert_eq!(t.failed(start), None);
+		assert_eq!(t.failed(start), None);
+		assert_eq!(t.locked_out(start), None);
+		assert_eq!(t.failed(start), Some(Duration::from_secs(10)));
+		assert_eq!(t.locked_out(start), Some(10));
+		assert_eq!(t.locked_out(start + Duration::from_millis(500)), Some(10));
+		assert_eq!(t.locked_out(start + Duration::from_secs(10)), None);
+
+		let later = start + Duration::from_secs(10);
+		assert_eq!(t.failed(later), Some(Duration::from_secs(20)));
+		assert_eq!(t.failed(later), Some(Duration::from_secs(35)));
+		assert_eq!(t.failed(later), Some(Duration::from_secs(35)));
+		assert_eq!(t.failures(), 6);
+
+		t.succeeded();
+		assert_eq!(t.locked_out(later), None);
+		assert_eq!(t.failed(later), None);
+	}
+
+	#[test]
+	fn open_throttle_disabled() {
+		let mut t = OpenThrottle::new(&OpenThrottleConfig {
+			max_attempts: Some(0),
+			lockout_secs: None,
+			max_lockout_secs: None,
+		});
+		let now = Instant::now();
+		for _ in 0..100 {
+			assert_eq!(t.failed(now), None);
+		}
+		assert_eq!(t.locked_out(now), None);
+	}
+}
@@ -18,28 +18,53 @@ use chrono::prelude::*;
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use uuid::Uuid;
 
-use crate::config::{EpicboxConfig, TorConfig, WalletConfig};
+use crate::audit_log::{self, AuditEntry, AuditLogPage};
+use crate::config::{
+	EpicboxConfig, GlobalWalletConfig, OpenThrottleConfig, PasswordPolicyConfig, TorConfig,
+	WalletConfig,
+};
+use crate::config_reload::{self, ConfigReload};
 use crate::core::core::Transaction;
 use crate::core::global;
-use crate::impls::create_sender;
 use crate::impls::EpicboxChannel;
+use crate::impls::{create_sender, listener_statuses, sender_transport, ListenerStatus};
 use crate::keychain::{Identifier, Keychain};
-use crate::libwallet::api_impl::owner_updater::{start_updater_log_thread, StatusMessage};
+use crate::libwallet::api_impl::owner_updater::{
+	start_updater_log_thread, BalanceAlerts, MaturityWatch, StatusMessage,
+};
 use crate::libwallet::api_impl::{owner, owner_updater};
 use crate::libwallet::{
-	address, AcctPathMapping, EpicboxAddress, Error, ErrorKind, InitTxArgs, IssueInvoiceTxArgs,
-	NodeClient, NodeHeightResult, OutputCommitMapping, PaymentProof, Slate, TxLogEntry, WalletInfo,
-	WalletInst, WalletLCProvider,
+	address, check_password_policy, AccountStatement, AcctPathMapping, ApiVersions,
+	BlockHeaderInfo, Contact, DeduplicatedOutput, EpicboxAddress, Error, ErrorKind, FeePeriod,
+	FeeReport, ImportedOutput, InitTxArgs, InitTxBatchResult, InitTxSendArgs, IssueInvoiceTxArgs,
+	MetadataEntry, MnemonicLanguage, NodeClient, NodeHeightResult, NodeRequestMetrics, OutputClaim,
+	OutputClaimVerification, OutputCommitMapping, OutputImportSummary, OutputLineage, OutputPage,
+	OutputPageQuery, PaymentProof, PaymentProofEntry, PaymentRequest, PinnedKey, ScanJob,
+	ScanJobStatus, ScanPreview, SeedWord, SendJob, SendPriority, SendTemplate, SigningRequest,
+	Slate, StoredEvent, Swap, SwapMessage, SwapStartArgs, SwapStep, SweepPolicy, TransportEvent,
+	TransportMetrics, TxBulkResult, TxEstimate, TxFilter, TxHook, TxLogEntry, TxLogEntryType,
+	TxPage, TxPageQuery, ViewKey, WalletInfo, WalletInitStatus, WalletInst, WalletLCProvider,
 };
+use crate::open_throttle::OpenThrottle;
+use crate::page_snapshots::PageSnapshots;
+use crate::rpc_metrics::{self, RpcMethodMetrics};
+use crate::scan_jobs::ScanJobs;
+use crate::send_queue::{self, QueuedSend, SendQueue};
+use crate::types::{ScopedToken, TokenCall, TokenScope};
 
+use crate::util::file::get_first_line;
 use crate::util::logger::LoggingConfig;
 use crate::util::secp::key::SecretKey;
-use crate::util::{from_hex, static_secp_instance, Mutex, ZeroingString};
+use crate::util::secp::pedersen;
+use crate::util::{from_hex, static_secp_instance, to_hex, Mutex, ZeroingString};
+use rand::{thread_rng, Rng};
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Main interface into all wallet API functions.
 /// Wallet APIs are split into two seperate blocks of functionality
@@ -81,6 +106,73 @@ where
 	tor_config: Mutex<Option<TorConfig>>,
 	/// epicbox configuration, holding epicbox relay server settings
 	epicbox_config: Mutex<Option<EpicboxConfig>>,
+	/// Outstanding confirmation token for `delete_wallet`, with its issue time
+	delete_token: Mutex<Option<(String, Instant)>>,
+	/// Positions of the words asked for by the last `get_seed_quiz`, to be answered
+	/// by `verify_seed_words`
+	seed_quiz: Mutex<Option<Vec<usize>>>,
+	/// Sends queued with `queue_send`, and the status of their jobs
+	send_queue: Arc<SendQueue>,
+	/// Whether transactions are only posted to a node that's caught up with the wallet
+	node_height_guard: AtomicBool,
+	/// Balance thresholds evaluated by the update thread
+	balance_alerts: Arc<Mutex<Option<BalanceAlerts>>>,
+	/// Watch of the outputs maturing, checked by the update thread
+	maturity_watch: Arc<Mutex<Option<MaturityWatch>>>,
+	/// Maximum number of entries returned by the `retrieve_*` queries, if limited
+	max_results: Mutex<Option<usize>>,
+	/// Percentage complete of the initial scan of a restored wallet, while it runs
+	initial_scan: Arc<Mutex<Option<u8>>>,
+	/// Hooks run before sending and after finalizing transactions
+	tx_hook: Mutex<Option<Arc<dyn TxHook>>>,
+	/// Requirements on the passwords wallets are created with or changed to, if any
+	password_policy: Mutex<Option<PasswordPolicyConfig>>,
+	/// Backoff on failed attempts at opening the wallet
+	open_throttle: Mutex<OpenThrottle>,
+	/// Scans started with `start_scan`, and the status of their jobs
+	scan_jobs: Arc<ScanJobs>,
+	/// Outputs and transactions paged through with `retrieve_outputs_page` and
+	/// `retrieve_txs_page`
+	page_snapshots: PageSnapshots,
+	/// Whether sends require the approval of a second operator
+	require_approval: AtomicBool,
+	/// Sends held until approved by their slate id, with how to send them
+	held_sends: Mutex<HashMap<Uuid, (InitTxSendArgs, Slate)>>,
+	/// Time the recipient of a send over http or Tor has to respond, if limited
+	send_timeout: Mutex<Option<Duration>>,
+	/// Tokens issued by `open_wallet` with a scope by their hex
+	scoped_tokens: Mutex<HashMap<String, ScopedToken>>,
+	/// Held while appending to the audit log, so entries aren't interleaved
+	audit_log: Mutex<()>,
+	/// Configuration the wallet was started with, or last reloaded
+	config: Mutex<Option<GlobalWalletConfig>>,
+}
+
+/// How long a token returned by `get_delete_token` can be used for
+const DELETE_TOKEN_VALIDITY: Duration = Duration::from_secs(300);
+
+/// Token returned by `get_delete_token` in doctest mode
+const DOCTEST_DELETE_TOKEN: &str = "e7a4c1ef0b4d5a2d8f6c3b9a1e0d7f42";
+
+/// Token returned by `open_wallet` with a scope in doctest mode
+const DOCTEST_SCOPED_TOKEN: &str =
+	"5c0e7a9d3b1f4e6a8c2d0b9f7e5a3c1d9b7f5e3a1c9d7b5f3e1a9c7d5b3f1e0a";
+
+/// Id of the jobs started by `start_scan` in doctest mode
+const DOCTEST_SCAN_JOB_ID: &str = "1d8b2ab4-7f6a-4c3e-9a51-2e0f6b7c8d90";
+
+/// Id of the snapshots taken by the `retrieve_*_page` queries in doctest mode
+const DOCTEST_PAGE_SNAPSHOT_ID: &str = "5b0f4de2-8c7a-4e19-b3f6-0a9d2c6e1f74";
+
+/// Check the method of send arguments is one `init_send_tx` can send with
+fn check_send_method(method: &str) -> Result<(), Error> {
+	match method {
+		"http" | "keybase" | "epicbox" => Ok(()),
+		_ => {
+			error!("unsupported payment method: {}", method);
+			Err(ErrorKind::ClientCallback("unsupported payment method".to_owned()).into())
+		}
+	}
 }
 
 impl<L, C, K> Owner<L, C, K>
@@ -169,9 +261,13 @@ where
 		custom_channel: Option<Sender<StatusMessage>>,
 	) -> Self {
 		let updater_running = Arc::new(AtomicBool::new(false));
+		let balance_alerts = Arc::new(Mutex::new(None));
+		let maturity_watch = Arc::new(Mutex::new(None));
 		let updater = Arc::new(Mutex::new(owner_updater::Updater::new(
 			wallet_inst.clone(),
 			updater_running.clone(),
+			balance_alerts.clone(),
+			maturity_watch.clone(),
 		)));
 
 		let updater_messages = Arc::new(Mutex::new(vec![]));
@@ -194,9 +290,34 @@ where
 			updater_messages,
 			tor_config: Mutex::new(None),
 			epicbox_config: Mutex::new(None),
+			delete_token: Mutex::new(None),
+			seed_quiz: Mutex::new(None),
+			send_queue: Arc::new(SendQueue::new(send_queue::SEND_QUEUE_DEFAULT_WORKERS)),
+			node_height_guard: AtomicBool::new(true),
+			balance_alerts,
+			maturity_watch,
+			max_results: Mutex::new(None),
+			initial_scan: Arc::new(Mutex::new(None)),
+			tx_hook: Mutex::new(None),
+			password_policy: Mutex::new(None),
+			open_throttle: Mutex::new(OpenThrottle::default()),
+			scan_jobs: Arc::new(ScanJobs::new()),
+			page_snapshots: PageSnapshots::new(),
+			require_approval: AtomicBool::new(false),
+			held_sends: Mutex::new(HashMap::new()),
+			send_timeout: Mutex::new(None),
+			scoped_tokens: Mutex::new(HashMap::new()),
+			audit_log: Mutex::new(()),
+			config: Mutex::new(None),
 		}
 	}
 
+	/// Whether the update thread or the initial scan keep the wallet up to date,
+	/// so queries don't refresh it themselves
+	fn updating_in_background(&self) -> bool {
+		self.updater_running.load(Ordering::Relaxed) || self.initial_scan.lock().is_some()
+	}
+
 	/// Set the TOR configuration for this instance of the OwnerAPI, used during
 	/// `init_send_tx` when send args are present and a TOR address is specified
 	///
@@ -223,6 +344,322 @@ where
 		*lock = epicbox_config;
 	}
 
+	/// Set the maximum number of sends queued with `queue_send` executed at the same
+	/// time by this instance of the OwnerAPI. Sends to the same destination are still
+	/// executed one at a time.
+	///
+	/// # Arguments
+	/// * `workers` - The maximum number of sends executed at the same time, at least 1
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_send_queue_workers(&self, workers: usize) {
+		self.send_queue.set_max_workers(workers);
+	}
+
+	/// Set the delay before a send queued with `queue_send` retries a failed exchange
+	/// with the recipient, doubled on each further retry.
+	///
+	/// # Arguments
+	/// * `delay` - The delay before the first retry, 5 seconds by default
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_send_queue_retry_delay(&self, delay: Duration) {
+		self.send_queue.set_retry_delay(delay);
+	}
+
+	/// Set whether this instance of the OwnerAPI checks the node before posting
+	/// transactions, refusing to post to a node that's behind the height the wallet last
+	/// refreshed to or still syncing. Enabled by default.
+	///
+	/// # Arguments
+	/// * `enabled` - Whether the node is checked before posting
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_node_height_guard(&self, enabled: bool) {
+		self.node_height_guard.store(enabled, Ordering::Relaxed);
+	}
+
+	/// Set the balance thresholds evaluated by the update thread started with
+	/// [`start_updater`](struct.Owner.html#method.start_updater) after each update.
+	/// An alert is raised when the spendable balance of an account moves outside of
+	/// its threshold, as a `BalanceAlert` message returned by
+	/// [`get_updater_messages`](struct.Owner.html#method.get_updater_messages) and
+	/// through the hook of the alerts if any.
+	///
+	/// # Arguments
+	/// * `balance_alerts` - The thresholds to evaluate and their hook, or `None` to
+	/// stop evaluating them
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_balance_alerts(&self, balance_alerts: Option<BalanceAlerts>) {
+		let mut lock = self.balance_alerts.lock();
+		*lock = balance_alerts;
+	}
+
+	/// Set the watch of the wallet's coinbase and height-locked outputs checked by
+	/// the update thread started with
+	/// [`start_updater`](struct.Owner.html#method.start_updater) after each update.
+	/// Once the header cache reaches the lock height of an output, an `OutputMatured`
+	/// message is returned by
+	/// [`get_updater_messages`](struct.Owner.html#method.get_updater_messages) and
+	/// the hook of the watch is notified if any.
+	///
+	/// # Arguments
+	/// * `maturity_watch` - The watch and its hook, or `None` to stop watching
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_maturity_watch(&self, maturity_watch: Option<MaturityWatch>) {
+		let mut lock = self.maturity_watch.lock();
+		*lock = maturity_watch;
+	}
+
+	/// Set the maximum number of entries returned by the output and transaction queries
+	/// of this instance of the OwnerAPI, such as
+	/// [`retrieve_outputs`](struct.Owner.html#method.retrieve_outputs) and
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs). A query matching more
+	/// entries fails with `ErrorKind::ResultLimit` rather than returning them. Unlimited
+	/// by default.
+	///
+	/// # Arguments
+	/// * `max_results` - The maximum number of entries returned, or `None` for no limit
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_max_results(&self, max_results: Option<usize>) {
+		let mut lock = self.max_results.lock();
+		*lock = max_results;
+	}
+
+	/// Set the hooks run by this instance of the OwnerAPI around the transactions it
+	/// sends: before a new slate is returned by
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx), where the hook can veto
+	/// the send, and after a slate is finalized by
+	/// [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	///
+	/// # Arguments
+	/// * `tx_hook` - An implementation of the
+	/// [`TxHook`](../epic_wallet_libwallet/types/trait.TxHook.html) trait, or `None`
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_tx_hook(&self, tx_hook: Option<Arc<dyn TxHook>>) {
+		let mut lock = self.tx_hook.lock();
+		*lock = tx_hook;
+	}
+
+	/// Set the requirements on the passwords wallets are created with by
+	/// [`create_wallet`](struct.Owner.html#method.create_wallet) and
+	/// [`recover_from_mnemonic`](struct.Owner.html#method.recover_from_mnemonic), or
+	/// changed to by [`change_password`](struct.Owner.html#method.change_password).
+	/// A password that doesn't meet them is refused with `ErrorKind::WeakPassword`.
+	/// No requirements by default.
+	///
+	/// # Arguments
+	/// * `password_policy` - The requirements, or `None` to accept any password
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_password_policy(&self, password_policy: Option<PasswordPolicyConfig>) {
+		let mut lock = self.password_policy.lock();
+		*lock = password_policy;
+	}
+
+	/// Require the sends initiated with [`init_send_tx`](struct.Owner.html#method.init_send_tx)
+	/// to be approved by a second operator with
+	/// [`approve_tx`](struct.Owner.html#method.approve_tx) before they're sent to their
+	/// recipient or finalized. The approver is given a token opened with the
+	/// [`TokenScope::Approver`](enum.TokenScope.html) scope, the operators initiating sends
+	/// tokens of other scopes. Sends don't require approval by default.
+	///
+	/// Sends given `send_args` are held by this instance until approved, so are only sent
+	/// by a long-lived one such as the V3 owner API listener's.
+	///
+	/// # Arguments
+	/// * `require_approval` - Whether sends require approval
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_require_approval(&self, require_approval: bool) {
+		self.require_approval
+			.store(require_approval, Ordering::Relaxed);
+	}
+
+	/// Set the time the recipient of a send made with `init_send_tx` over http or Tor
+	/// has to respond. A send timing out is aborted: its outputs are unlocked and its
+	/// transaction is cancelled, with the timeout as the failure reason, so it can be
+	/// made again right away. The exchange isn't limited by default.
+	///
+	/// # Arguments
+	/// * `send_timeout` - The time the recipient has to respond, or `None` for no limit
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_send_timeout(&self, send_timeout: Option<Duration>) {
+		let mut lock = self.send_timeout.lock();
+		*lock = send_timeout;
+	}
+
+	/// Set the backoff on failed attempts at opening the wallet with
+	/// [`open_wallet`](struct.Owner.html#method.open_wallet). Once the allowed number
+	/// of consecutive attempts failed, attempts fail with `ErrorKind::OpenLockedOut`
+	/// without the password being checked until the lockout ends, and an `OpenLockout`
+	/// message is returned by
+	/// [`get_updater_messages`](struct.Owner.html#method.get_updater_messages). Each
+	/// further failure doubles the lockout. Failed attempts are forgotten once the
+	/// wallet is opened. Enabled with the default settings unless set, setting it
+	/// forgets the attempts made so far.
+	///
+	/// # Arguments
+	/// * `open_throttle` - The settings of the backoff, with 0 `max_attempts` to
+	/// disable it
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_open_throttle(&self, open_throttle: &OpenThrottleConfig) {
+		let mut lock = self.open_throttle.lock();
+		*lock = OpenThrottle::new(open_throttle);
+	}
+
+	/// Set the configuration the wallet was started with, which
+	/// [`reload_config`](struct.Owner.html#method.reload_config) reads again from its
+	/// files.
+	///
+	/// # Arguments
+	/// * `config` - The configuration, with the paths of the files it was read from
+	/// # Returns
+	/// * Nothing
+
+	pub fn set_config(&self, config: Option<GlobalWalletConfig>) {
+		let mut lock = self.config.lock();
+		*lock = config;
+	}
+
+	/// Reads the wallet's configuration file again, as set with
+	/// [`set_config`](struct.Owner.html#method.set_config), applying the changes to the
+	/// node's address and API secret, the log levels and the Tor and epicbox settings
+	/// at once. The listeners keep running, and with them the slate exchanges in
+	/// progress. The other settings changed only apply once the wallet is restarted.
+	///
+	/// The log levels can be lowered, but not raised above those the wallet was
+	/// started with.
+	///
+	/// # Returns
+	/// * Ok with the [`ConfigReload`](struct.ConfigReload.html) listing the settings
+	/// changed, those applied and those requiring a restart
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the
+	/// configuration isn't set or can't be read, in which case nothing is changed.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.reload_config();
+	///
+	/// if let Ok(reload) = result {
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn reload_config(&self) -> Result<ConfigReload, Error> {
+		let mut config = self.config.lock();
+		let current = match config.as_ref() {
+			Some(c) => c,
+			None => {
+				let msg = "No configuration file to reload".to_owned();
+				return Err(ErrorKind::GenericError(msg).into());
+			}
+		};
+		let unreadable =
+			|e| ErrorKind::GenericError(format!("Unable to reload the configuration: {}", e));
+		let reloaded = current.reload().map_err(unreadable)?;
+		let changed = config_reload::changed_keys(current, &reloaded).map_err(unreadable)?;
+		let (applied, restart_required): (Vec<String>, Vec<String>) = changed
+			.into_iter()
+			.partition(|k| config_reload::is_reloaded(k));
+
+		let members = reloaded.members.clone().unwrap_or_default();
+		if applied.iter().any(|k| k.starts_with("wallet.")) {
+			let node_api_secret = get_first_line(members.wallet.node_api_secret_path.clone());
+			let mut w_lock = self.wallet_inst.lock();
+			w_lock
+				.lc_provider()?
+				.set_node(&members.wallet.check_node_api_http_addr, node_api_secret)?;
+			warn!(
+				"Node set to {} by the configuration reload",
+				members.wallet.check_node_api_http_addr
+			);
+		}
+		if applied.iter().any(|k| k.starts_with("logging.")) {
+			if let Some(l) = members.logging.as_ref() {
+				log::set_max_level(config_reload::max_log_level(l));
+			}
+		}
+		if applied.iter().any(|k| k.starts_with("tor.")) {
+			self.set_tor_config(members.tor.clone());
+		}
+		if applied.iter().any(|k| k.starts_with("epicbox.")) {
+			self.set_epicbox_config(members.epicbox.clone());
+		}
+		if !restart_required.is_empty() {
+			warn!(
+				"Configuration changes only applied after a restart: {}",
+				restart_required.join(", ")
+			);
+		}
+		*config = Some(reloaded);
+		Ok(ConfigReload {
+			applied,
+			restart_required,
+		})
+	}
+
+	/// Check a password a wallet is about to be encrypted with against the policy
+	fn check_password_policy(&self, password: &str) -> Result<(), Error> {
+		match self.password_policy.lock().as_ref() {
+			Some(p) => check_password_policy(p, password),
+			None => Ok(()),
+		}
+	}
+
+	/// Run the pre-send hook on a new slate, dropping the slate's private context if
+	/// the send is vetoed
+	fn check_pre_send(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<(), Error> {
+		let hook = match self.tx_hook.lock().clone() {
+			Some(h) => h,
+			None => return Ok(()),
+		};
+		if let Err(e) = hook.pre_send(slate) {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let mut batch = w.batch(keychain_mask)?;
+			batch.delete_private_context(slate.id.as_bytes(), 0)?;
+			batch.commit()?;
+			return Err(e);
+		}
+		Ok(())
+	}
+
+	/// Fail if a query matched more entries than allowed by `set_max_results`
+	fn check_result_len<T>(&self, results: Vec<T>) -> Result<Vec<T>, Error> {
+		match *self.max_results.lock() {
+			Some(max) if results.len() > max => {
+				Err(ErrorKind::ResultLimit(results.len(), max).into())
+			}
+			_ => Ok(results),
+		}
+	}
+
 	/// Returns a list of accounts stored in the wallet (i.e. mappings between
 	/// user-specified labels and BIP32 derivation paths.
 	/// # Arguments
@@ -315,6 +752,49 @@ where
 		owner::create_account_path(&mut **w, keychain_mask, label)
 	}
 
+	/// Sets or removes a metadata entry of an account. Accounts can carry arbitrary key/value
+	/// metadata, such as the id of the customer an account is held for, which is stored in
+	/// the wallet and returned along with the account by [`accounts`](struct.Owner.html#method.accounts).
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `label` - The label of the account
+	/// * `key` - The metadata key to set or remove
+	/// * `value` - The value to set, or `None` to remove the entry
+	///
+	/// # Returns
+	/// * Result Containing:
+	/// * The updated [AcctPathMapping](../epic_wallet_libwallet/types/struct.AcctPathMapping.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.set_account_metadata(None, "default", "customer_id", Some("1234"));
+	///
+	/// if let Ok(mapping) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn set_account_metadata(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		label: &str,
+		key: &str,
+		value: Option<&str>,
+	) -> Result<AcctPathMapping, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_account_metadata(&mut **w, keychain_mask, label, key, value)
+	}
+
 	/// Sets the wallet's currently active account. This sets the
 	/// BIP32 parent path used for most key-derivation operations.
 	///
@@ -392,7 +872,10 @@ where
 	/// [OutputCommitMapping](../epic_wallet_libwallet/types/struct.OutputCommitMapping.html)
 	/// of which each element is a mapping between the wallet's internal
 	/// [OutputData](../epic_wallet_libwallet/types/struct.Output.html)
-	/// and the Output commitment as identified in the chain's UTXO set
+	/// and the Output commitment as identified in the chain's UTXO set, along with the
+	/// output's stable `output_id`. The outputs are ordered by child index, then by
+	/// transaction log entry, any remaining tie being broken on the other fields of the
+	/// outputs, so the same outputs are always listed in the same order.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -423,11 +906,11 @@ where
 			let t = self.status_tx.lock();
 			t.clone()
 		};
-		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
+		let refresh_from_node = match self.updating_in_background() {
 			true => false,
 			false => refresh_from_node,
 		};
-		owner::retrieve_outputs(
+		let (validated, outputs) = owner::retrieve_outputs(
 			self.wallet_inst.clone(),
 			keychain_mask,
 			&tx,
@@ -435,7 +918,177 @@ where
 			refresh_from_node,
 			show_full_history,
 			tx_id,
-		)
+		)?;
+		Ok((validated, self.check_result_len(outputs)?))
+	}
+
+	/// Returns a page of the outputs from the active account in the wallet, sorted as
+	/// requested, along with the number of outputs in all pages. Lets a client render a
+	/// large output set a page at a time rather than retrieving all of it at once.
+	///
+	/// The first page is cut from a new snapshot of the outputs, returned along with
+	/// it. Giving the snapshot in the query for the next pages cuts them from the same
+	/// outputs, so the update thread adding or spending outputs in the meantime doesn't
+	/// make a listing skip or repeat them. The other arguments are then ignored, as
+	/// they were applied when the snapshot was taken. Snapshots expire after 10 minutes.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `include_spent` - If `true`, outputs that have been marked as 'spent'
+	/// in the wallet will be returned. If `false`, spent outputs will omitted
+	/// from the results.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node, as in [`retrieve_outputs`](struct.Owner.html#method.retrieve_outputs).
+	/// * `tx_id` - If `Some(i)`, only return the outputs associated with
+	/// the transaction log entry of id `i`.
+	/// * `query` - An [`OutputPageQuery`](../epic_wallet_libwallet/api_impl/types/struct.OutputPageQuery.html)
+	/// giving the order the outputs are sorted in, the page of them returned and the
+	/// snapshot it's cut from
+	///
+	/// # Returns
+	/// * `(bool, OutputPage)` - A tuple:
+	/// * The first `bool` element indicates whether the data was successfully
+	/// refreshed from the node, when the snapshot was taken.
+	/// * The second element is the [`OutputPage`](../epic_wallet_libwallet/api_impl/types/struct.OutputPage.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// e.g. `ErrorKind::UnknownPageSnapshot` if the snapshot has expired.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use epic_wallet_libwallet::{OutputPageQuery, OutputSortField};
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let query = OutputPageQuery {
+	///		offset: 0,
+	///		limit: Some(20),
+	///		sort_by: Some(OutputSortField::Value),
+	///		descending: true,
+	///		snapshot: None,
+	/// };
+	///
+	/// let result = api_owner.retrieve_outputs_page(None, false, true, None, &query);
+	///
+	/// if let Ok((was_updated, page)) = result {
+	///		// the next page, of the same outputs
+	///		let query = OutputPageQuery {
+	///			offset: 20,
+	///			snapshot: Some(page.snapshot),
+	///			..query
+	///		};
+	///		let result = api_owner.retrieve_outputs_page(None, false, true, None, &query);
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_outputs_page(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		include_spent: bool,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		query: &OutputPageQuery,
+	) -> Result<(bool, OutputPage), Error> {
+		let (snapshot, validated, outputs) = match query.snapshot {
+			Some(id) => {
+				{
+					let mut w_lock = self.wallet_inst.lock();
+					let w = w_lock.lc_provider()?.wallet_inst()?;
+					// Test keychain mask, to keep API consistent
+					let _ = w.keychain(keychain_mask)?;
+				}
+				let (validated, outputs) = self.page_snapshots.outputs(&id)?;
+				(id, validated, outputs)
+			}
+			None => {
+				let tx = {
+					let t = self.status_tx.lock();
+					t.clone()
+				};
+				let refresh_from_node = match self.updating_in_background() {
+					true => false,
+					false => refresh_from_node,
+				};
+				let (validated, outputs) = owner::retrieve_outputs(
+					self.wallet_inst.clone(),
+					keychain_mask,
+					&tx,
+					include_spent,
+					refresh_from_node,
+					false,
+					tx_id,
+				)?;
+				let id = self.new_page_snapshot_id();
+				self.page_snapshots
+					.add_outputs(id, validated, outputs.clone());
+				(id, validated, outputs)
+			}
+		};
+		let mut page = query.apply(outputs, snapshot);
+		page.outputs = self.check_result_len(page.outputs)?;
+		Ok((validated, page))
+	}
+
+	/// Returns the outputs of the active account in the wallet for an export, as
+	/// [`retrieve_outputs`](struct.Owner.html#method.retrieve_outputs) without
+	/// refreshing them from the node. The number of outputs isn't limited by
+	/// [`set_max_results`](struct.Owner.html#method.set_max_results), as the owner
+	/// listener streams an export a few outputs at a time rather than in a single
+	/// response.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `include_spent` - If true, outputs that have been marked as 'spent'
+	/// in the wallet will be returned.
+	///
+	/// # Returns
+	/// * Ok with the [`OutputCommitMapping`](../epic_wallet_libwallet/types/struct.OutputCommitMapping.html)
+	/// of every output
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.export_outputs(None, true);
+	///
+	/// if let Ok(outputs) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn export_outputs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		include_spent: bool,
+	) -> Result<Vec<OutputCommitMapping>, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let (_, outputs) = owner::retrieve_outputs(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			include_spent,
+			false,
+			false,
+			None,
+		)?;
+		Ok(outputs)
+	}
+
+	/// Id of a new page snapshot, fixed in doctest mode
+	fn new_page_snapshot_id(&self) -> Uuid {
+		match self.doctest_mode {
+			true => Uuid::parse_str(DOCTEST_PAGE_SNAPSHOT_ID).unwrap(),
+			false => Uuid::new_v4(),
+		}
 	}
 
 	/// Returns a list of [Transaction Log Entries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
@@ -462,7 +1115,12 @@ where
 	/// refreshed from the node (note this may be false even if the `refresh_from_node`
 	/// argument was set to `true`.
 	/// * The second element contains the set of retrieved
-	/// [TxLogEntries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// [TxLogEntries](../epic_wallet_libwallet/types/struct.TxLogEntry.html), ordered by
+	/// creation time, then by account and id. An entry is identified by its account and
+	/// id, which never change. The
+	/// `state` of an entry's payment proof tells whether the recipient has signed it yet
+	/// and whether it can be verified against the chain, see
+	/// [`PaymentProofState`](../epic_wallet_libwallet/types/enum.PaymentProofState.html)
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -489,11 +1147,68 @@ where
 		tx_id: Option<u32>,
 		tx_slate_id: Option<Uuid>,
 	) -> Result<(bool, Vec<TxLogEntry>), Error> {
-		let tx = {
+		let (validated, txs) =
+			self.retrieve_all_txs(keychain_mask, refresh_from_node, tx_id, tx_slate_id)?;
+		Ok((validated, self.check_result_len(txs)?))
+	}
+
+	/// Returns the [Transaction Log Entries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// from the active account in the wallet which carry all of the given tags.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	/// * `tags` - The tags each returned transaction must carry, as set with
+	/// [`update_tx_tags`](struct.Owner.html#method.update_tx_tags). If empty, all transactions
+	/// are returned.
+	///
+	/// # Returns
+	/// * `(bool, Vec<TxLogEntry)` - A tuple as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// // Return all TxLogEntries tagged as mining income
+	/// let result = api_owner.retrieve_txs_by_tag(None, true, &["mining".to_owned()]);
+	///
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_txs_by_tag(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		tags: &[String],
+	) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		let (validated, txs) =
+			self.retrieve_all_txs(keychain_mask, refresh_from_node, None, None)?;
+		let txs = txs.into_iter().filter(|t| t.has_tags(tags)).collect();
+		Ok((validated, self.check_result_len(txs)?))
+	}
+
+	/// Transactions as in `retrieve_txs`, before checking the result limit so they can
+	/// be filtered first
+	fn retrieve_all_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		let tx = {
 			let t = self.status_tx.lock();
 			t.clone()
 		};
-		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
+		let refresh_from_node = match self.updating_in_background() {
 			true => false,
 			false => refresh_from_node,
 		};
@@ -519,222 +1234,193 @@ where
 		Ok(res)
 	}
 
-	/// Returns summary information from the active account in the wallet.
+	/// Returns a page of the [Transaction Log Entries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// from the active account in the wallet, along with the number of transactions in
+	/// all pages.
+	///
+	/// As with [`retrieve_outputs_page`](struct.Owner.html#method.retrieve_outputs_page),
+	/// the first page is cut from a new snapshot of the transactions, and giving the
+	/// snapshot in the query for the next pages cuts them from the same transactions,
+	/// e.g. so an export taken while the wallet is listening has no duplicates or gaps.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
 	/// * `refresh_from_node` - If true, the wallet will attempt to contact
-	/// a node (via the [`NodeClient`](../epic_wallet_libwallet/types/trait.NodeClient.html)
-	/// provided during wallet instantiation). If `false`, the results will
-	/// contain transaction information that may be out-of-date (from the last time
-	/// the wallet's output set was refreshed against the node).
-	/// Note this setting is ignored if the updater process is running via a call to
-	/// [`start_updater`](struct.Owner.html#method.start_updater)
-	/// * `minimum_confirmations` - The minimum number of confirmations an output
-	/// should have before it's included in the 'amount_currently_spendable' total
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs), when taking
+	/// a new snapshot.
+	/// * `query` - A [`TxPageQuery`](../epic_wallet_libwallet/api_impl/types/struct.TxPageQuery.html)
+	/// giving the page of transactions returned and the snapshot it's cut from
 	///
 	/// # Returns
-	/// * (`bool`, [`WalletInfo`](../epic_wallet_libwallet/types/struct.WalletInfo.html)) - A tuple:
+	/// * `(bool, TxPage)` - A tuple:
 	/// * The first `bool` element indicates whether the data was successfully
-	/// refreshed from the node (note this may be false even if the `refresh_from_node`
-	/// argument was set to `true`.
-	/// * The second element contains the Summary [`WalletInfo`](../epic_wallet_libwallet/types/struct.WalletInfo.html)
+	/// refreshed from the node, when the snapshot was taken.
+	/// * The second element is the [`TxPage`](../epic_wallet_libwallet/api_impl/types/struct.TxPage.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// e.g. `ErrorKind::UnknownPageSnapshot` if the snapshot has expired.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use epic_wallet_libwallet::TxPageQuery;
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let update_from_node = true;
-	/// let minimum_confirmations=10;
-	///
-	/// // Return summary info for active account
-	/// let result = api_owner.retrieve_summary_info(None, update_from_node, minimum_confirmations);
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let mut query = TxPageQuery {
+	///		limit: Some(50),
+	///		..Default::default()
+	/// };
 	///
-	/// if let Ok((was_updated, summary_info)) = result {
+	/// // Export all TxLogEntries, 50 at a time
+	/// loop {
+	///		let (_, page) = api_owner.retrieve_txs_page(None, true, &query).unwrap();
+	///		if page.txs.is_empty() {
+	///			break;
+	///		}
 	///		//...
+	///		query.offset += page.txs.len();
+	///		query.snapshot = Some(page.snapshot);
 	/// }
 	/// ```
 
-	pub fn retrieve_summary_info(
+	pub fn retrieve_txs_page(
 		&self,
 		keychain_mask: Option<&SecretKey>,
 		refresh_from_node: bool,
-		minimum_confirmations: u64,
-	) -> Result<(bool, WalletInfo), Error> {
-		let tx = {
-			let t = self.status_tx.lock();
-			t.clone()
-		};
-		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
-			true => false,
-			false => refresh_from_node,
+		query: &TxPageQuery,
+	) -> Result<(bool, TxPage), Error> {
+		let (snapshot, validated, txs) = match query.snapshot {
+			Some(id) => {
+				{
+					let mut w_lock = self.wallet_inst.lock();
+					let w = w_lock.lc_provider()?.wallet_inst()?;
+					// Test keychain mask, to keep API consistent
+					let _ = w.keychain(keychain_mask)?;
+				}
+				let (validated, txs) = self.page_snapshots.txs(&id)?;
+				(id, validated, txs)
+			}
+			None => {
+				let (validated, txs) =
+					self.retrieve_all_txs(keychain_mask, refresh_from_node, None, None)?;
+				let id = self.new_page_snapshot_id();
+				self.page_snapshots.add_txs(id, validated, txs.clone());
+				(id, validated, txs)
+			}
 		};
-		owner::retrieve_summary_info(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			&tx,
-			refresh_from_node,
-			minimum_confirmations,
-		)
+		let mut page = query.apply(txs, snapshot);
+		page.txs = self.check_result_len(page.txs)?;
+		Ok((validated, page))
 	}
 
-	/// Initiates a new transaction as the sender, creating a new
-	/// [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) object containing
-	/// the sender's inputs, change outputs, and public signature data. This slate can
-	/// then be sent to the recipient to continue the transaction via the
-	/// [Foreign API's `receive_tx`](struct.Foreign.html#method.receive_tx) method.
-	///
-	/// When a transaction is created, the wallet must also lock inputs (and create unconfirmed
-	/// outputs) corresponding to the transaction created in the slate, so that the wallet doesn't
-	/// attempt to re-spend outputs that are already included in a transaction before the transaction
-	/// is confirmed. This method also returns a function that will perform that locking, and it is
-	/// up to the caller to decide the best time to call the lock function
-	/// (via the [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs) method).
-	/// If the exchange method is intended to be synchronous (such as via a direct http call,)
-	/// then the lock call can wait until the response is confirmed. If it is asynchronous, (such
-	/// as via file transfer,) the lock call should happen immediately (before the file is sent
-	/// to the recipient).
-	///
-	/// If the `send_args` [`InitTxSendArgs`](../epic_wallet_libwallet/types/struct.InitTxSendArgs.html),
-	/// of the [`args`](../epic_wallet_libwallet/types/struct.InitTxArgs.html), field is Some, this
-	/// function will attempt to perform a synchronous send to the recipient specified in the `dest`
-	/// field according to the `method` field, and will also finalize and post the transaction if
-	/// the `finalize` field is set.
+	/// Returns the transactions of the active account in the wallet for an export, as
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs) without refreshing
+	/// them from the node, and without the limit set by
+	/// [`set_max_results`](struct.Owner.html#method.set_max_results).
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
-	/// transaction initialization arguments. See struct documentation for further detail.
+	/// * `tags` - Only export the transactions carrying all of these tags, as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs)
 	///
 	/// # Returns
-	/// * a result containing:
-	/// * The transaction [Slate](../epic_wallet_libwallet/slate/struct.Slate.html),
-	/// which can be forwarded to the recieving party by any means. Once the caller is relatively
-	/// certain that the transaction has been sent to the recipient, the associated wallet
-	/// transaction outputs should be locked via a call to
-	/// [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs). This must be called before calling
-	/// [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	/// * Ok with every matching [TxLogEntry](../epic_wallet_libwallet/types/struct.TxLogEntry.html),
+	/// in the order of [`retrieve_txs`](struct.Owner.html#method.retrieve_txs)
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
-	/// # Remarks
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// * This method requires an active connection to a node, and will fail with error if a node
-	/// cannot be contacted to refresh output statuses.
-	/// * This method will store a partially completed transaction in the wallet's transaction log,
-	/// which will be updated on the corresponding call to [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.export_txs(None, &[]);
+	///
+	/// if let Ok(txs) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn export_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tags: &[String],
+	) -> Result<Vec<TxLogEntry>, Error> {
+		let (_, txs) = self.retrieve_all_txs(keychain_mask, false, None, None)?;
+		Ok(txs.into_iter().filter(|t| t.has_tags(tags)).collect())
+	}
+
+	/// Returns the [Transaction Log Entries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// from the active account in the wallet which were made with the given address book contact,
+	/// i.e. whose payment proof names the contact's proof address.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	/// * `name` - The name of the contact, as added with
+	/// [`save_contact`](struct.Owner.html#method.save_contact)
+	///
+	/// # Returns
+	/// * `(bool, Vec<TxLogEntry)` - A tuple as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the contact
+	/// is unknown.
 	///
 	/// # Example
-	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// // Attempt to create a transaction using the 'default' account
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 2,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Have some Epics. Love, Yeastplume".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
 	///
-	/// if let Ok(slate) = result {
-	/// 	// Send slate somehow
-	/// 	// ...
-	/// 	// Lock our outputs if we're happy the slate was (or is being) sent
-	/// 	api_owner.tx_lock_outputs(None, &slate, 0);
+	/// let result = api_owner.retrieve_contact_txs(None, true, "alice");
+	///
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn init_send_tx(
+	pub fn retrieve_contact_txs(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		args: InitTxArgs,
-	) -> Result<Slate, Error> {
-		let send_args = args.send_args.clone();
-		let mut slate = {
+		refresh_from_node: bool,
+		name: &str,
+	) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		{
 			let mut w_lock = self.wallet_inst.lock();
 			let w = w_lock.lc_provider()?.wallet_inst()?;
-			owner::init_send_tx(&mut **w, keychain_mask, args, self.doctest_mode)?
-		};
-
-		// Helper functionality. If send arguments exist, attempt to send
-		match send_args {
-			Some(sa) => {
-				//TODO: in case of keybase, the response might take 60s and leave the service hanging
-				match sa.method.as_ref() {
-					"http" | "keybase" | "epicbox" => {}
-					_ => {
-						error!("unsupported payment method: {}", sa.method);
-						return Err(ErrorKind::ClientCallback(
-							"unsupported payment method".to_owned(),
-						)
-						.into());
-					}
-				};
-
-				let tor_config_lock = self.tor_config.lock();
-				let epicbox_config_lock = self.epicbox_config.lock();
-
-				if sa.method == "epicbox" {
-					let epicbox_channel =
-						Box::new(EpicboxChannel::new(&sa.dest, epicbox_config_lock.clone()))
-							.map_err(|e| ErrorKind::GenericError(format!("{}", e)))?;
-					let wallet = self.wallet_inst.clone();
-					let km = match keychain_mask.as_ref() {
-						None => None,
-						Some(&m) => Some(m.to_owned()),
-					};
-					slate = epicbox_channel.send(wallet, km, &slate)?;
-					self.tx_lock_outputs(keychain_mask, &slate, 0)?;
-					return Ok(slate);
-				} else {
-					let comm_adapter = create_sender(&sa.method, &sa.dest, tor_config_lock.clone())
-						.map_err(|e| ErrorKind::GenericError(format!("{}", e)))?;
-					slate = comm_adapter.send_tx(&slate)?;
-				}
-
-				self.tx_lock_outputs(keychain_mask, &slate, 0)?;
-				let slate = match sa.finalize {
-					true => self.finalize_tx(keychain_mask, &slate)?,
-					false => slate,
-				};
-
-				if sa.post_tx {
-					self.post_tx(keychain_mask, &slate.tx, sa.fluff)?;
-				}
-				Ok(slate)
+			if w.get_contact(name)?.is_none() {
+				return Err(ErrorKind::UnknownContact(name.to_owned()).into());
 			}
-			None => Ok(slate),
 		}
+		let (validated, txs) =
+			self.retrieve_all_txs(keychain_mask, refresh_from_node, None, None)?;
+		let txs = txs
+			.into_iter()
+			.filter(|t| t.contact.as_ref().map(|c| c.as_str()) == Some(name))
+			.collect();
+		Ok((validated, self.check_result_len(txs)?))
 	}
 
-	/// Issues a new invoice transaction slate, essentially a `request for payment`.
-	/// The slate created by this function will contain the amount, an output for the amount,
-	/// as well as round 1 of singature creation complete. The slate should then be send
-	/// to the payer, who should add their inputs and signature data and return the slate
-	/// via the [Foreign API's `finalize_invoice_tx`](struct.Foreign.html#method.finalize_invoice_tx) method.
+	/// Returns the [Transaction Log Entries](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// of the transactions received by the active account over the foreign API whose final
+	/// transaction hasn't been seen on chain yet, oldest first. These can be expired with
+	/// [`cancel_txs`](struct.Owner.html#method.cancel_txs), which the listener does
+	/// automatically if a `pending_receive_expiry_hours` window is configured.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `args` - [`IssueInvoiceTxArgs`](../epic_wallet_libwallet/types/struct.IssueInvoiceTxArgs.html),
-	/// invoice transaction initialization arguments. See struct documentation for further detail.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
 	///
 	/// # Returns
-	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
-	/// containing the updated slate.
+	/// * `(bool, Vec<TxLogEntry)` - A tuple as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -742,183 +1428,163 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let api_owner = Owner::new(wallet.clone());
 	///
-	/// let args = IssueInvoiceTxArgs {
-	/// 	amount: 60_000_000_000,
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.issue_invoice_tx(None, args);
+	/// let result = api_owner.retrieve_pending_receives(None, true);
 	///
-	/// if let Ok(slate) = result {
-	///		// if okay, send to the payer to add their inputs
-	///		// . . .
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///		//...
 	/// }
 	/// ```
-	pub fn issue_invoice_tx(
+
+	pub fn retrieve_pending_receives(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		args: IssueInvoiceTxArgs,
-	) -> Result<Slate, Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let w = w_lock.lc_provider()?.wallet_inst()?;
-		owner::issue_invoice_tx(&mut **w, keychain_mask, args, self.doctest_mode)
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		let (validated, txs) =
+			self.retrieve_all_txs(keychain_mask, refresh_from_node, None, None)?;
+		let mut txs: Vec<TxLogEntry> = txs
+			.into_iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxReceived && !t.confirmed)
+			.collect();
+		txs.sort_by_key(|t| t.creation_ts);
+		Ok((validated, self.check_result_len(txs)?))
 	}
 
-	/// Processes an invoice tranaction created by another party, essentially
-	/// a `request for payment`. The incoming slate should contain a requested
-	/// amount, an output created by the invoicer convering the amount, and
-	/// part 1 of signature creation completed. This function will add inputs
-	/// equalling the amount + fees, as well as perform round 1 and 2 of signature
-	/// creation.
-	///
-	/// Callers should note that no prompting of the user will be done by this function
-	/// it is up to the caller to present the request for payment to the user
-	/// and verify that payment should go ahead.
-	///
-	/// This function also stores the final transaction in the user's wallet files for retrieval
-	/// via the [`get_stored_tx`](struct.Owner.html#method.get_stored_tx) function.
+	/// Statements of the transactions each account of the wallet confirmed over a
+	/// period, with the account's opening and closing balances and the fees it paid,
+	/// for bookkeeping. Transactions count towards the period they were confirmed in.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). The
-	/// payer should have filled in round 1 and 2.
-	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
-	/// transaction initialization arguments. See struct documentation for further detail.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	/// * `start` - Start of the period.
+	/// * `end` - End of the period, exclusive.
 	///
 	/// # Returns
-	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
-	/// containing the updated slate.
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * (`bool`, `Vec<`[`AccountStatement`](../epic_wallet_libwallet/api_impl/types/struct.AccountStatement.html)`>`) -
+	/// whether the data was refreshed from the node as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs), and the statement of each
+	/// account, by label.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
+	/// use chrono::{TimeZone, Utc};
 	///
-	/// // . . .
-	/// // The slate has been recieved from the invoicer, somehow
-	/// # let slate = Slate::blank(2);
-	/// let args = InitTxArgs {
-	///		src_acct_name: None,
-	///		amount: slate.amount,
-	///		minimum_confirmations: 2,
-	///		max_outputs: 500,
-	///		num_change_outputs: 1,
-	///		selection_strategy_is_use_all: false,
-	///		..Default::default()
-	///	};
+	/// let api_owner = Owner::new(wallet.clone());
 	///
-	/// let result = api_owner.process_invoice_tx(None, &slate, args);
+	/// // Statements of September 2019
+	/// let start = Utc.with_ymd_and_hms(2019, 9, 1, 0, 0, 0).unwrap();
+	/// let end = Utc.with_ymd_and_hms(2019, 10, 1, 0, 0, 0).unwrap();
+	/// let result = api_owner.account_statements(None, true, start, end);
 	///
-	/// if let Ok(slate) = result {
-	///	// If result okay, send back to the invoicer
-	///	// . . .
-	///	}
+	/// if let Ok((was_updated, statements)) = result {
+	///		//...
+	/// }
 	/// ```
 
-	pub fn process_invoice_tx(
+	pub fn account_statements(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		slate: &Slate,
-		args: InitTxArgs,
-	) -> Result<Slate, Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let w = w_lock.lc_provider()?.wallet_inst()?;
-		owner::process_invoice_tx(&mut **w, keychain_mask, slate, args, self.doctest_mode)
+		refresh_from_node: bool,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> Result<(bool, Vec<AccountStatement>), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updating_in_background() {
+			true => false,
+			false => refresh_from_node,
+		};
+		owner::account_statements(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			start,
+			end,
+		)
 	}
 
-	/// Locks the outputs associated with the inputs to the transaction in the given
-	/// [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html),
-	/// making them unavailable for use in further transactions. This function is called
-	/// by the sender, (or more generally, all parties who have put inputs into the transaction,)
-	/// and must be called before the corresponding call to [`finalize_tx`](struct.Owner.html#method.finalize_tx)
-	/// that completes the transaction.
-	///
-	/// Outputs will generally remain locked until they are removed from the chain,
-	/// at which point they will become `Spent`. It is commonplace for transactions not to complete
-	/// for various reasons over which a particular wallet has no control. For this reason,
-	/// [`cancel_tx`](struct.Owner.html#method.cancel_tx) can be used to manually unlock outputs
-	/// and return them to the `Unspent` state.
+	/// Summarizes the fees paid by the confirmed transactions sent from each account
+	/// of the wallet, in total and over periods of the given length, for cost accounting.
+	/// Transactions count towards the period they were confirmed in.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). All
-	/// * `participant_id` - The participant id, generally 0 for the party putting in funds, 1 for the
-	/// party receiving.
-	/// elements in the `input` vector of the `tx` field that are found in the wallet's currently
-	/// active account will be set to status `Locked`
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node as in [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
+	/// * `period` - The length of the periods fees are summarized over.
 	///
 	/// # Returns
-	/// * Ok(()) if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * (`bool`, [`FeeReport`](../epic_wallet_libwallet/api_impl/types/struct.FeeReport.html)) -
+	/// whether the data was refreshed from the node as in
+	/// [`retrieve_txs`](struct.Owner.html#method.retrieve_txs), and the fees paid from all
+	/// accounts, from each account, and by the transactions paying the largest fees.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 10,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Remember to lock this when we're happy this is sent".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// use epic_wallet_libwallet::FeePeriod;
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		api_owner.tx_lock_outputs(None, &slate, 0);
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// // Fees paid each month
+	/// let result = api_owner.fee_report(None, true, FeePeriod::Month);
+	///
+	/// if let Ok((was_updated, report)) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn tx_lock_outputs(
+	pub fn fee_report(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		slate: &Slate,
-		participant_id: usize,
-	) -> Result<(), Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let w = w_lock.lc_provider()?.wallet_inst()?;
-		owner::tx_lock_outputs(&mut **w, keychain_mask, slate, participant_id)
+		refresh_from_node: bool,
+		period: FeePeriod,
+	) -> Result<(bool, FeeReport), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updating_in_background() {
+			true => false,
+			false => refresh_from_node,
+		};
+		owner::fee_report(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			period,
+		)
 	}
 
-	/// Finalizes a transaction, after all parties
-	/// have filled in both rounds of Slate generation. This step adds
-	/// all participants partial signatures to create the final signature,
-	/// resulting in a final transaction that is ready to post to a node.
-	///
-	/// Note that this function DOES NOT POST the transaction to a node
-	/// for validation. This is done in separately via the
-	/// [`post_tx`](struct.Owner.html#method.post_tx) function.
-	///
-	/// This function also stores the final transaction in the user's wallet files for retrieval
-	/// via the [`get_stored_tx`](struct.Owner.html#method.get_stored_tx) function.
+	/// Adds tags to and removes tags from a transaction, to categorize it for bookkeeping.
+	/// Tags are trimmed and lowercased, and may not be empty or contain commas.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). All
-	/// participants must have filled in both rounds, and the sender should have locked their
-	/// outputs (via the [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs) function).
+	/// * `tx_id` - If present, the transaction log entry id of the transaction to tag.
+	/// * `tx_slate_id` - If `tx_id` is not present, the
+	/// [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) uuid of the transaction.
+	/// * `add` - Tags to add to the transaction. Tags it already carries are ignored.
+	/// * `remove` - Tags to remove from the transaction.
 	///
 	/// # Returns
-	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
-	/// containing the new finalized slate.
+	/// * Ok with the resulting, sorted tags of the transaction
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -926,59 +1592,42 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 10,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Finalize this tx now".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let add = vec!["payroll".to_owned()];
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
-	///		//
-	///		// Retrieve slate back from recipient
-	///		//
-	///		let res = api_owner.finalize_tx(None, &slate);
+	/// let result = api_owner.update_tx_tags(None, Some(1), None, &add, &[]);
+	///
+	/// if let Ok(tags) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn finalize_tx(
+	pub fn update_tx_tags(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		slate: &Slate,
-	) -> Result<Slate, Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let w = w_lock.lc_provider()?.wallet_inst()?;
-		owner::finalize_tx(&mut **w, keychain_mask, &slate)
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+		add: &[String],
+		remove: &[String],
+	) -> Result<Vec<String>, Error> {
+		owner::update_tx_tags(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			tx_id,
+			tx_slate_id,
+			add,
+			remove,
+		)
 	}
 
-	/// Posts a completed transaction to the listening node for validation and inclusion in a block
-	/// for mining.
+	/// Returns all tags used by transactions in the active account.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx` - A completed [`Transaction`](../epic_core/core/transaction/struct.Transaction.html),
-	/// typically the `tx` field in the transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html).
-	/// * `fluff` - Instruct the node whether to use the Dandelion protocol when posting the
-	/// transaction. If `true`, the node should skip the Dandelion phase and broadcast the
-	/// transaction to all peers immediately. If `false`, the node will follow dandelion logic and
-	/// initiate the stem phase.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful
+	/// * Ok with the sorted tags
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -986,71 +1635,35 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 10,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Post this tx".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_tx_tags(None);
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
-	///		//
-	///		// Retrieve slate back from recipient
-	///		//
-	///		let res = api_owner.finalize_tx(None, &slate);
-	///		let res = api_owner.post_tx(None, &slate.tx, true);
+	/// if let Ok(tags) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn post_tx(
+	pub fn retrieve_tx_tags(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx: &Transaction,
-		fluff: bool,
-	) -> Result<(), Error> {
-		let client = {
-			let mut w_lock = self.wallet_inst.lock();
-			let w = w_lock.lc_provider()?.wallet_inst()?;
-			// Test keychain mask, to keep API consistent
-			let _ = w.keychain(keychain_mask)?;
-			w.w2n_client().clone()
-		};
-		owner::post_tx(&client, tx, fluff)
+	) -> Result<Vec<String>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_tx_tags(&mut **w)
 	}
 
-	/// Cancels a transaction. This entails:
-	/// * Setting the transaction status to either `TxSentCancelled` or `TxReceivedCancelled`
-	/// * Deleting all change outputs or recipient outputs associated with the transaction
-	/// * Setting the status of all assocatied inputs from `Locked` to `Spent` so they can be
-	/// used in new transactions.
-	///
-	/// Transactions can be cancelled by transaction log id or slate id (call with either set to
-	/// Some, not both)
+	/// Returns the wallet's address book, sorted by name. Transactions whose payment proof
+	/// names the proof address of a contact are annotated with the contact's name when
+	/// retrieved with [`retrieve_txs`](struct.Owner.html#method.retrieve_txs).
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx_id` - If present, cancel by the [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html) id
-	/// for the transaction.
-	///
-	/// * `tx_slate_id` - If present, cancel by the Slate id.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful
+	/// * Ok with a vector of [`Contact`](../epic_wallet_libwallet/types/struct.Contact.html)
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1058,253 +1671,158 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 10,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Cancel this tx".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_contacts(None);
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
-	///		//
-	///		// We didn't get the slate back, or something else went wrong
-	///		//
-	///		let res = api_owner.cancel_tx(None, None, Some(slate.id.clone()));
+	/// if let Ok(contacts) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn cancel_tx(
+	pub fn retrieve_contacts(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<(), Error> {
-		let tx = {
-			let t = self.status_tx.lock();
-			t.clone()
-		};
-		owner::cancel_tx(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			&tx,
-			tx_id,
-			tx_slate_id,
-		)
-	}
-
-	/// Retrieves the stored transaction associated with a TxLogEntry. Can be used even after the
-	/// transaction has completed.
+	) -> Result<Vec<Contact>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_contacts(&mut **w)
+	}
+
+	/// Adds a contact to the address book, or replaces the addresses of the contact with the
+	/// same name. The proof address must be a valid onion v3 address and the epicbox address,
+	/// if given, a valid epicbox address. The preferred transport, if given, must be one of
+	/// [`CONTACT_TRANSPORTS`](../epic_wallet_libwallet/constant.CONTACT_TRANSPORTS.html), while
+	/// the transport that last worked is kept from the stored contact.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `tx_log_entry` - A [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// * `contact` - The contact to save. Its name is trimmed.
 	///
 	/// # Returns
-	/// * Ok with the stored  [`Transaction`](../epic_core/core/transaction/struct.Transaction.html)
-	/// if successful
+	/// * Ok with the contact as stored
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use epic_wallet_libwallet::Contact;
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let update_from_node = true;
-	/// let tx_id = None;
-	/// let tx_slate_id = None;
-	///
-	/// // Return all TxLogEntries
-	/// let result = api_owner.retrieve_txs(None, update_from_node, tx_id, tx_slate_id);
+	/// let contact = Contact {
+	///		name: "alice".to_owned(),
+	///		proof_address: "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid".to_owned(),
+	///		epicbox_address: None,
+	///		http_address: None,
+	///		preferred_transport: Some("tor".to_owned()),
+	///		last_transport: None,
+	/// };
+	/// let result = api_owner.save_contact(None, &contact);
 	///
-	/// if let Ok((was_updated, tx_log_entries)) = result {
-	///		let stored_tx = api_owner.get_stored_tx(None, &tx_log_entries[0]).unwrap();
+	/// if let Ok(contact) = result {
 	///		//...
 	/// }
 	/// ```
 
-	// TODO: Should be accepting an id, not an entire entry struct
-	pub fn get_stored_tx(
+	pub fn save_contact(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		tx_log_entry: &TxLogEntry,
-	) -> Result<Option<Transaction>, Error> {
+		contact: &Contact,
+	) -> Result<Contact, Error> {
 		let mut w_lock = self.wallet_inst.lock();
 		let w = w_lock.lc_provider()?.wallet_inst()?;
-		// Test keychain mask, to keep API consistent
-		let _ = w.keychain(keychain_mask)?;
-		owner::get_stored_tx(&**w, tx_log_entry)
+		owner::save_contact(&mut **w, keychain_mask, contact)
 	}
 
-	/// Verifies all messages in the slate match their public keys.
-	///
-	/// The optional messages themselves are part of the `participant_data` field within the slate.
-	/// Messages are signed with the same key used to sign for the paricipant's inputs, and can thus be
-	/// verified with the public key found in the `public_blind_excess` field. This function is a
-	/// simple helper to returns whether all signatures in the participant data match their public
-	/// keys.
+	/// Records the transport a sent transaction went through on its log entry, and as the
+	/// transport that last worked for the contact it was sent to, so later payments to the
+	/// contact try it first.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html).
+	/// * `tx_slate_id` - The [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) uuid of
+	/// the sent transaction.
+	/// * `transport` - The transport the slate was sent with, e.g. "epicbox".
+	/// * `contact` - The name of the contact the transaction was sent to, if any.
 	///
 	/// # Returns
-	/// * `Ok(())` if successful and the signatures validate
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok(()) if the transport was recorded
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the transaction
+	/// or the contact is unknown.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let args = InitTxArgs {
-	/// 	src_acct_name: None,
-	/// 	amount: 2_000_000_000,
-	/// 	minimum_confirmations: 10,
-	/// 	max_outputs: 500,
-	/// 	num_change_outputs: 1,
-	/// 	selection_strategy_is_use_all: false,
-	/// 	message: Some("Just verify messages".to_owned()),
-	/// 	..Default::default()
-	/// };
-	/// let result = api_owner.init_send_tx(
-	/// 	None,
-	/// 	args,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let tx_slate_id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.record_tx_transport(None, tx_slate_id, "tor", None);
 	///
-	/// if let Ok(slate) = result {
-	///		// Send slate somehow
-	///		// ...
-	///		// Lock our outputs if we're happy the slate was (or is being) sent
-	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
-	///		//
-	///		// Retrieve slate back from recipient
-	///		//
-	///		let res = api_owner.verify_slate_messages(None, &slate);
+	/// if let Ok(_) = result {
+	///		//...
 	/// }
 	/// ```
-	pub fn verify_slate_messages(
+
+	pub fn record_tx_transport(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		slate: &Slate,
+		tx_slate_id: Uuid,
+		transport: &str,
+		contact: Option<&str>,
 	) -> Result<(), Error> {
-		{
-			let mut w_lock = self.wallet_inst.lock();
-			let w = w_lock.lc_provider()?.wallet_inst()?;
-			// Test keychain mask, to keep API consistent
-			let _ = w.keychain(keychain_mask)?;
-		}
-		owner::verify_slate_messages(slate)
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::record_tx_transport(&mut **w, keychain_mask, tx_slate_id, transport, contact)
 	}
 
-	/// Scans the entire UTXO set from the node, identify which outputs belong to the given wallet
-	/// update the wallet state to be consistent with what's currently in the UTXO set.
-	///
-	/// This function can be used to repair wallet state, particularly by restoring outputs that may
-	/// be missing if the wallet owner has cancelled transactions locally that were then successfully
-	/// posted to the chain.
-	///
-	/// This operation scans the entire chain, and is expected to be time intensive. It is imperative
-	/// that no other processes should be trying to use the wallet at the same time this function is
-	/// running.
-	///
-	/// When an output is found that doesn't exist in the wallet, a corresponding
-	/// [TxLogEntry](../epic_wallet_libwallet/types/struct.TxLogEntry.html) is created.
+	/// Removes a contact from the address book.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `start_height` - If provided, the height of the first block from which to start scanning.
-	/// The scan will start from block 1 if this is not provided.
-	/// * `delete_unconfirmed` - if `false`, the scan process will be non-destructive, and
-	/// mostly limited to restoring missing outputs. It will leave unconfirmed transaction logs entries
-	/// and unconfirmed outputs intact. If `true`, the process will unlock all locked outputs,
-	/// restore all missing outputs, and mark any outputs that have been marked 'Spent' but are still
-	/// in the UTXO set as 'Unspent' (as can happen during a fork). It will also attempt to cancel any
-	/// transaction log entries associated with any locked outputs or outputs incorrectly marked 'Spent'.
-	/// Note this completely removes all outstanding transactions, so users should be very aware what
-	/// will happen if this flag is set. Note that if transactions/outputs are removed that later
-	/// confirm on the chain, another call to this function will restore them.
+	/// * `name` - The name of the contact to remove
 	///
 	/// # Returns
-	/// * `Ok(())` if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
-
+	/// * Ok(()) if the contact was removed
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the contact
+	/// is unknown.
+	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// let mut api_owner = Owner::new(wallet.clone());
-	/// let result = api_owner.scan(
-	/// 	None,
-	/// 	Some(20000),
-	/// 	false,
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.remove_contact(None, "alice");
 	///
 	/// if let Ok(_) = result {
-	///		// Wallet outputs should be consistent with what's on chain
-	///		// ...
+	///		//...
 	/// }
 	/// ```
 
-	pub fn scan(
+	pub fn remove_contact(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		start_height: Option<u64>,
-		delete_unconfirmed: bool,
+		name: &str,
 	) -> Result<(), Error> {
-		let tx = {
-			let t = self.status_tx.lock();
-			t.clone()
-		};
-		owner::scan(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			start_height,
-			delete_unconfirmed,
-			&tx,
-		)
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::remove_contact(&mut **w, keychain_mask, name)
 	}
 
-	/// Retrieves the last known height known by the wallet. This is determined as follows:
-	/// * If the wallet can successfully contact its configured node, the reported node
-	/// height is returned, and the `updated_from_node` field in the response is `true`
-	/// * If the wallet cannot contact the node, this function returns the maximum height
-	/// of all outputs contained within the wallet, and the `updated_from_node` fields
-	/// in the response is set to false.
-	///
-	/// Clients should generally ensure the `updated_from_node` field is returned as
-	/// `true` before assuming the height for any operation.
+	/// Returns the saved send templates, sorted by name.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
 	///
 	/// # Returns
-	/// * Ok with a  [`NodeHeightResult`](../epic_wallet_libwallet/types/struct.NodeHeightResult.html)
-	/// if successful. If the height result was obtained from the configured node,
-	/// `updated_from_node` will be set to `true`
+	/// * Ok with a vector of [`SendTemplate`](../epic_wallet_libwallet/types/struct.SendTemplate.html)
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1313,56 +1831,35 @@ where
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let result = api_owner.node_height(None);
+	/// let result = api_owner.retrieve_send_templates(None);
 	///
-	/// if let Ok(node_height_result) = result {
-	///		if node_height_result.updated_from_node {
-	///			//we can assume node_height_result.height is relatively safe to use
-	///
-	///		}
+	/// if let Ok(templates) = result {
 	///		//...
 	/// }
 	/// ```
 
-	pub fn node_height(
+	pub fn retrieve_send_templates(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-	) -> Result<NodeHeightResult, Error> {
-		{
-			let mut w_lock = self.wallet_inst.lock();
-			let w = w_lock.lc_provider()?.wallet_inst()?;
-			// Test keychain mask, to keep API consistent
-			let _ = w.keychain(keychain_mask)?;
-		}
-		let mut res = owner::node_height(self.wallet_inst.clone(), keychain_mask)?;
-		if self.doctest_mode {
-			// return a consistent hash for doctest
-			res.header_hash =
-				"d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d".to_owned();
-		}
-		Ok(res)
+	) -> Result<Vec<SendTemplate>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_send_templates(&mut **w)
 	}
 
-	// LIFECYCLE FUNCTIONS
-
-	/// Retrieve the top-level directory for the wallet. This directory should contain the
-	/// `epic-wallet.toml` file and the `wallet_data` directory that contains the wallet
-	/// seed + data files. Future versions of the wallet API will support multiple wallets
-	/// within the top level directory.
-	///
-	/// The top level directory defaults to (in order of precedence):
-	///
-	/// 1) The current directory, from which `epic-wallet` or the main process was run, if it
-	/// contains a `epic-wallet.toml` file.
-	/// 2) ~/.epic/<chaintype>/ otherwise
+	/// Returns the send template with the given name.
 	///
 	/// # Arguments
-	///
-	/// * None
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `name` - The name of the template
 	///
 	/// # Returns
-	/// * Ok with a String value representing the full path to the top level wallet dierctory
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok with the [`SendTemplate`](../epic_wallet_libwallet/types/struct.SendTemplate.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the template
+	/// is unknown.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -1370,154 +1867,4107 @@ where
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let result = api_owner.get_top_level_directory();
+	/// let result = api_owner.get_send_template(None, "payroll-john");
 	///
-	/// if let Ok(dir) = result {
-	///		println!("Top level directory is: {}", dir);
+	/// if let Ok(template) = result {
 	///		//...
 	/// }
 	/// ```
 
-	pub fn get_top_level_directory(&self) -> Result<String, Error> {
+	pub fn get_send_template(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		name: &str,
+	) -> Result<SendTemplate, Error> {
 		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		if self.doctest_mode {
-			Ok("/doctest/dir".to_owned())
-		} else {
-			lc.get_top_level_directory()
-		}
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::get_send_template(&mut **w, name)
 	}
 
-	/// Set the top-level directory for the wallet. This directory can be empty, and will be created
-	/// during a subsequent calls to [`create_config`](struct.Owner.html#method.create_config)
-	///
-	/// Set [`get_top_level_directory`](struct.Owner.html#method.get_top_level_directory) for a
-	/// description of the top level directory and default paths.
+	/// Saves a send template, replacing the template with the same name. The method must be
+	/// one of [`SEND_TEMPLATE_METHODS`](../epic_wallet_libwallet/constant.SEND_TEMPLATE_METHODS.html),
+	/// the destination and the amount must be given, and the account, if given, must exist.
+	/// The template is executed with `send --template`.
 	///
 	/// # Arguments
-	///
-	/// * `dir`: The new top-level directory path (either relative to current directory or
-	/// absolute.
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `template` - The template to save. Its name, method and destination are trimmed.
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * Ok with the template as stored
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
-	///
-	/// let dir = "path/to/wallet/dir";
-	///
-	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
-	/// # let dir = dir
-	/// # 	.path()
-	/// # 	.to_str()
-	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
-	/// # 	.unwrap();
+	/// use epic_wallet_libwallet::SendTemplate;
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let result = api_owner.set_top_level_directory(dir);
+	/// let template = SendTemplate {
+	///		name: "payroll-john".to_owned(),
+	///		method: "http".to_owned(),
+	///		dest: "http://127.0.0.1:3415".to_owned(),
+	///		amount: 2_000_000_000,
+	///		src_acct_name: None,
+	///		message: Some("Salary".to_owned()),
+	/// };
+	/// let result = api_owner.save_send_template(None, &template);
 	///
-	/// if let Ok(dir) = result {
+	/// if let Ok(template) = result {
 	///		//...
 	/// }
 	/// ```
 
-	pub fn set_top_level_directory(&self, dir: &str) -> Result<(), Error> {
+	pub fn save_send_template(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		template: &SendTemplate,
+	) -> Result<SendTemplate, Error> {
 		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.set_top_level_directory(dir)
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::save_send_template(&mut **w, keychain_mask, template)
 	}
 
-	/// Create a `epic-wallet.toml` configuration file in the top-level directory for the
-	/// specified chain type.
-	/// A custom [`WalletConfig`](../epic_wallet_config/types/struct.WalletConfig.html)
-	/// and/or epic `LoggingConfig` may optionally be provided, otherwise defaults will be used.
-	///
-	/// Paths in the configuration file will be updated to reflect the top level directory, so
-	/// path-related values in the optional configuration structs will be ignored.
+	/// Removes a send template.
 	///
 	/// # Arguments
-	///
-	/// * `chain_type`: The chain type to use in creation of the configuration file. This can be
-	///     * `AutomatedTesting`
-	///     * `UserTesting`
-	///     * `Floonet`
-	///     * `Mainnet`
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `name` - The name of the template to remove
 	///
 	/// # Returns
-	/// * Ok if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok(()) if the template was removed
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the template
+	/// is unknown.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// let dir = "path/to/wallet/dir";
-	///
-	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
-	/// # let dir = dir
-	/// # 	.path()
-	/// # 	.to_str()
-	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
-	/// # 	.unwrap();
-	///
-	/// let api_owner = Owner::new(wallet.clone());
-	/// let _ = api_owner.set_top_level_directory(dir);
-	///
-	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.remove_send_template(None, "payroll-john");
 	///
 	/// if let Ok(_) = result {
 	///		//...
 	/// }
 	/// ```
 
-	pub fn create_config(
+	pub fn remove_send_template(
 		&self,
-		chain_type: &global::ChainTypes,
-		wallet_config: Option<WalletConfig>,
-		logging_config: Option<LoggingConfig>,
-		tor_config: Option<TorConfig>,
-		epicbox_config: Option<EpicboxConfig>,
+		keychain_mask: Option<&SecretKey>,
+		name: &str,
 	) -> Result<(), Error> {
 		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.create_config(
-			chain_type,
-			"epic-wallet.toml",
-			wallet_config,
-			logging_config,
-			tor_config,
-			epicbox_config,
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::remove_send_template(&mut **w, keychain_mask, name)
+	}
+
+	/// Returns summary information from the active account in the wallet.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node (via the [`NodeClient`](../epic_wallet_libwallet/types/trait.NodeClient.html)
+	/// provided during wallet instantiation). If `false`, the results will
+	/// contain transaction information that may be out-of-date (from the last time
+	/// the wallet's output set was refreshed against the node).
+	/// Note this setting is ignored if the updater process is running via a call to
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
+	/// * `minimum_confirmations` - The minimum number of confirmations an output
+	/// should have before it's included in the 'amount_currently_spendable' total
+	///
+	/// # Returns
+	/// * (`bool`, [`WalletInfo`](../epic_wallet_libwallet/types/struct.WalletInfo.html)) - A tuple:
+	/// * The first `bool` element indicates whether the data was successfully
+	/// refreshed from the node (note this may be false even if the `refresh_from_node`
+	/// argument was set to `true`.
+	/// * The second element contains the Summary [`WalletInfo`](../epic_wallet_libwallet/types/struct.WalletInfo.html)
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let update_from_node = true;
+	/// let minimum_confirmations=10;
+	///
+	/// // Return summary info for active account
+	/// let result = api_owner.retrieve_summary_info(None, update_from_node, minimum_confirmations);
+	///
+	/// if let Ok((was_updated, summary_info)) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_summary_info(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		minimum_confirmations: u64,
+	) -> Result<(bool, WalletInfo), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updating_in_background() {
+			true => false,
+			false => refresh_from_node,
+		};
+		owner::retrieve_summary_info(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			minimum_confirmations,
 		)
 	}
 
-	/// Creates a new wallet seed and empty wallet database in the `wallet_data` directory of
-	/// the top level directory.
+	/// Initiates a new transaction as the sender, creating a new
+	/// [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) object containing
+	/// the sender's inputs, change outputs, and public signature data. This slate can
+	/// then be sent to the recipient to continue the transaction via the
+	/// [Foreign API's `receive_tx`](struct.Foreign.html#method.receive_tx) method.
 	///
-	/// Paths in the configuration file will be updated to reflect the top level directory, so
-	/// path-related values in the optional configuration structs will be ignored.
+	/// When a transaction is created, the wallet must also lock inputs (and create unconfirmed
+	/// outputs) corresponding to the transaction created in the slate, so that the wallet doesn't
+	/// attempt to re-spend outputs that are already included in a transaction before the transaction
+	/// is confirmed. This method also returns a function that will perform that locking, and it is
+	/// up to the caller to decide the best time to call the lock function
+	/// (via the [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs) method).
+	/// If the exchange method is intended to be synchronous (such as via a direct http call,)
+	/// then the lock call can wait until the response is confirmed. If it is asynchronous, (such
+	/// as via file transfer,) the lock call should happen immediately (before the file is sent
+	/// to the recipient).
 	///
-	/// The wallet files must not already exist, and ~The `epic-wallet.toml` file must exist
-	/// in the top level directory (can be created via a call to
-	/// [`create_config`](struct.Owner.html#method.create_config))
+	/// If the `send_args` [`InitTxSendArgs`](../epic_wallet_libwallet/types/struct.InitTxSendArgs.html),
+	/// of the [`args`](../epic_wallet_libwallet/types/struct.InitTxArgs.html), field is Some, this
+	/// function will attempt to perform a synchronous send to the recipient specified in the `dest`
+	/// field according to the `method` field, and will also finalize and post the transaction if
+	/// the `finalize` field is set.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments. See struct documentation for further detail.
+	///
+	/// # Returns
+	/// * a result containing:
+	/// * The transaction [Slate](../epic_wallet_libwallet/slate/struct.Slate.html),
+	/// which can be forwarded to the recieving party by any means. Once the caller is relatively
+	/// certain that the transaction has been sent to the recipient, the associated wallet
+	/// transaction outputs should be locked via a call to
+	/// [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs). This must be called before calling
+	/// [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Remarks
+	///
+	/// * This method requires an active connection to a node, and will fail with error if a node
+	/// cannot be contacted to refresh output statuses.
+	/// * This method will store a partially completed transaction in the wallet's transaction log,
+	/// which will be updated on the corresponding call to [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	/// * The pre-send hook set with [`set_tx_hook`](struct.Owner.html#method.set_tx_hook), if any,
+	/// is run on the new slate, failing with `ErrorKind::SendRejected` if it vetoes the send.
+	/// * If sends require approval (see [`set_require_approval`](struct.Owner.html#method.set_require_approval)),
+	/// the transaction awaits approval and can't be finalized until it's given with
+	/// [`approve_tx`](struct.Owner.html#method.approve_tx). A slate given `send_args` isn't
+	/// sent either: its outputs are locked and it's held until approved, then sent,
+	/// finalized and posted as requested. The unsent slate is returned.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// // Attempt to create a transaction using the 'default' account
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Have some Epics. Love, Yeastplume".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	/// 	// Send slate somehow
+	/// 	// ...
+	/// 	// Lock our outputs if we're happy the slate was (or is being) sent
+	/// 	api_owner.tx_lock_outputs(None, &slate, 0);
+	/// }
+	/// ```
+
+	pub fn init_send_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: InitTxArgs,
+	) -> Result<Slate, Error> {
+		let send_args = args.send_args.clone();
+		let estimate_only = args.estimate_only == Some(true);
+		let slate = {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::init_send_tx(&mut **w, keychain_mask, args, self.doctest_mode)?
+		};
+		let requires_approval = !estimate_only && self.require_approval.load(Ordering::Relaxed);
+		if !estimate_only {
+			self.check_pre_send(keychain_mask, &slate)?;
+		}
+		if requires_approval {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::request_tx_approval(&mut **w, keychain_mask, slate.id)?;
+		}
+
+		// Helper functionality. If send arguments exist, attempt to send
+		match send_args {
+			// held until approved, its outputs locked meanwhile
+			Some(sa) if requires_approval => {
+				check_send_method(&sa.method)?;
+				self.tx_lock_outputs(keychain_mask, &slate, 0)?;
+				self.held_sends.lock().insert(slate.id, (sa, slate.clone()));
+				Ok(slate)
+			}
+			Some(sa) => {
+				check_send_method(&sa.method)?;
+				let slate = self.exchange_slate(keychain_mask, &sa, &slate)?;
+				self.tx_lock_outputs(keychain_mask, &slate, 0)?;
+				self.complete_send(keychain_mask, &sa, slate)
+			}
+			None => Ok(slate),
+		}
+	}
+
+	/// Send the slate to its recipient, returning their response. A send timing out is
+	/// failed, so its outputs are unlocked.
+	fn exchange_slate(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		sa: &InitTxSendArgs,
+		slate: &Slate,
+	) -> Result<Slate, Error> {
+		match self.send_slate(keychain_mask, sa, slate) {
+			Ok(s) => Ok(s),
+			Err(e) => {
+				if let ErrorKind::SendTimeout(reason) = e.kind() {
+					let wallet = self.wallet_inst.clone();
+					if let Err(f) = owner::fail_send_tx(wallet, keychain_mask, slate, &reason) {
+						warn!("Unable to cancel the timed out send: {}", f);
+					}
+				}
+				Err(e)
+			}
+		}
+	}
+
+	/// Estimates the transaction [`init_send_tx`](struct.Owner.html#method.init_send_tx)
+	/// would create with the same arguments, returning the inputs it would select, its
+	/// fee and its change. Nothing is saved or locked, and no rangeproofs are built, so
+	/// this is much faster than initiating the transaction, e.g. for showing the fee of
+	/// a send before confirming it. The `estimate_only` and `send_args` fields of the
+	/// arguments are ignored.
+	///
+	/// `init_send_tx` with `estimate_only` set runs this same estimate, but keeps returning
+	/// a [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) holding only the total
+	/// and the fee, as its existing callers expect, so the full estimate is returned here
+	/// instead. Neither saves a private context nor locks outputs, this one being allowed
+	/// for read-only tokens; only the statuses of the outputs are refreshed from the node.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments, as for `init_send_tx`.
+	///
+	/// # Returns
+	/// * Ok with the [`TxEstimate`](../epic_wallet_libwallet/api_impl/types/struct.TxEstimate.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// e.g. `ErrorKind::NotEnoughFunds` if the wallet can't cover the amount and the fee.
+	///
+	/// # Remarks
+	///
+	/// * This method requires an active connection to a node, and will fail with error if a node
+	/// cannot be contacted to refresh output statuses.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.estimate_send_tx(None, &args);
+	///
+	/// if let Ok(estimate) = result {
+	/// 	// Show the fee, and initiate the transaction if it's accepted
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn estimate_send_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: &InitTxArgs,
+	) -> Result<TxEstimate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::estimate_send_tx(&mut **w, keychain_mask, args)
+	}
+
+	/// Builds the signing request of a send from a watch-only wallet (see
+	/// [`create_watch_only_wallet`](struct.Owner.html#method.create_watch_only_wallet)):
+	/// an unsigned slate with the inputs selected as
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) would, its amount, fee and
+	/// height, but no outputs nor participant data, along with the outputs it spends. The
+	/// send is then signed on the host holding the wallet's keys with
+	/// [`sign_tx_offline`](struct.Owner.html#method.sign_tx_offline), or initiated there
+	/// with the slate's inputs given in the `inputs` field of the arguments. Nothing is
+	/// saved or locked.
+	///
+	/// # Arguments
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments, as for `init_send_tx`.
+	///
+	/// # Returns
+	/// * Ok with the [`SigningRequest`](../epic_wallet_libwallet/api_impl/types/struct.SigningRequest.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::WatchOnly` if the wallet isn't watch-only.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_unsigned_send_tx(args);
+	///
+	/// if let Ok(request) = result {
+	/// 	// Take the request to the signing host
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn init_unsigned_send_tx(&self, args: InitTxArgs) -> Result<SigningRequest, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		if lc.view_key(None)?.is_none() {
+			let msg = "unsigned slates are only built by watch-only wallets".to_owned();
+			return Err(ErrorKind::WatchOnly(msg).into());
+		}
+		let w = lc.wallet_inst()?;
+		owner::init_unsigned_send_tx(&mut **w, args)
+	}
+
+	/// Signs the send of a watch-only wallet's signing request, built by
+	/// [`init_unsigned_send_tx`](struct.Owner.html#method.init_unsigned_send_tx), on the
+	/// host holding the wallet's keys. The send is initiated as by
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) from the request's inputs,
+	/// which must be outputs of the active account, without contacting the node, so the
+	/// wallet can be kept offline. Inputs the wallet doesn't know of yet are added to it.
+	///
+	/// The returned slate then goes through the usual exchange: its outputs are locked with
+	/// [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs), it's sent to the
+	/// recipient and finalized with [`finalize_tx`](struct.Owner.html#method.finalize_tx),
+	/// still offline. The finalized transaction is posted by the watch-only wallet.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `request` - The [`SigningRequest`](../epic_wallet_libwallet/api_impl/types/struct.SigningRequest.html)
+	/// of the watch-only wallet.
+	///
+	/// # Returns
+	/// * Ok with the [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html) of the send,
+	/// to be sent to the recipient
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::SigningRequest` if the request doesn't describe a send from the wallet's
+	/// active account, or its fee isn't the one the wallet computes.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	..Default::default()
+	/// };
+	/// // Built by the watch-only wallet and taken to the signing host
+	/// if let Ok(request) = api_owner.init_unsigned_send_tx(args) {
+	/// 	let result = api_owner.sign_tx_offline(None, &request);
+	///
+	/// 	if let Ok(slate) = result {
+	/// 		// Lock our outputs and send the slate to the recipient
+	/// 		// ...
+	/// 		api_owner.tx_lock_outputs(None, &slate, 0);
+	/// 	}
+	/// }
+	/// ```
+
+	pub fn sign_tx_offline(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		request: &SigningRequest,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::sign_tx_offline(&mut **w, keychain_mask, request, self.doctest_mode)
+	}
+
+	/// Initiates a transaction to each of several recipients in a single call, e.g. for a
+	/// payout. Each transaction is initiated as in
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) with its own arguments. The
+	/// inputs of all of them are selected in a single pass over the wallet's outputs, smallest
+	/// first, so no two transactions spend the same output, and their outputs are locked
+	/// together. The caller therefore doesn't need to call
+	/// [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs) on the returned slates,
+	/// unless they were only estimated. Outputs aren't consolidated by the transactions.
+	///
+	/// The batch is all or nothing: if a transaction can't be initiated, e.g. because the
+	/// remaining outputs don't cover it, or a slate given `send_args` can't be sent to its
+	/// recipient, the transactions to all recipients are cancelled and their outputs unlocked.
+	/// The result of the recipient whose transaction failed gives the error. Finalizing and
+	/// posting the sent slates isn't undone if it fails for one of them, as their recipients
+	/// have already signed them.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html) of
+	/// the transaction to each recipient, in the order they are initiated.
+	///
+	/// # Returns
+	/// * Ok with an [`InitTxBatchResult`](../epic_wallet_libwallet/api_impl/types/struct.InitTxBatchResult.html)
+	/// for each recipient, in the order of the arguments
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx_batch(None, vec![args.clone(), args]);
+	///
+	/// if let Ok(results) = result {
+	/// 	// send each initiated slate to its recipient
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn init_send_tx_batch(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: Vec<InitTxArgs>,
+	) -> Result<Vec<InitTxBatchResult>, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		for sa in args.iter().filter_map(|a| a.send_args.as_ref()) {
+			check_send_method(&sa.method)?;
+		}
+		let sends: Vec<(u64, Option<InitTxSendArgs>, bool)> = args
+			.iter()
+			.map(|a| (a.amount, a.send_args.clone(), a.estimate_only == Some(true)))
+			.collect();
+		let requires_approval = self.require_approval.load(Ordering::Relaxed);
+		let results = {
+			let tx_hook = self.tx_hook.lock().clone();
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::init_send_tx_batch(
+				&mut **w,
+				keychain_mask,
+				args,
+				tx_hook.as_ref().map(|h| h.as_ref()),
+				requires_approval,
+				self.doctest_mode,
+			)?
+		};
+		if results.iter().any(|r| r.is_err()) {
+			return Ok(sends
+				.iter()
+				.zip(results)
+				.map(|((amount, _, _), res)| InitTxBatchResult::new(*amount, res))
+				.collect());
+		}
+		let slates: Vec<Slate> = results.into_iter().filter_map(Result::ok).collect();
+
+		// the slates given send arguments are all sent, or held until approved
+		let mut responses = vec![];
+		for (i, (slate, (_, sa, estimate_only))) in slates.iter().zip(sends.iter()).enumerate() {
+			let sa = match sa {
+				Some(sa) if !estimate_only => sa,
+				_ => continue,
+			};
+			if requires_approval {
+				self.held_sends
+					.lock()
+					.insert(slate.id, (sa.clone(), slate.clone()));
+				continue;
+			}
+			match self.send_slate(keychain_mask, sa, slate) {
+				Ok(s) => responses.push((i, s)),
+				Err(e) => return Ok(self.fail_batch(keychain_mask, &slates, &sends, i, e)),
+			}
+		}
+		let mut results: Vec<Result<Slate, Error>> = slates.into_iter().map(Ok).collect();
+		for (i, response) in responses {
+			let sa = sends[i].1.as_ref().unwrap();
+			results[i] = self.complete_send(keychain_mask, sa, response);
+		}
+		Ok(sends
+			.iter()
+			.zip(results)
+			.map(|((amount, _, _), res)| InitTxBatchResult::new(*amount, res))
+			.collect())
+	}
+
+	/// Cancel the sends of a batch, unlocking their outputs, as the slate of one of them
+	/// couldn't be sent to its recipient, returning the result of each
+	fn fail_batch(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slates: &[Slate],
+		sends: &[(u64, Option<InitTxSendArgs>, bool)],
+		failed: usize,
+		error: Error,
+	) -> Vec<InitTxBatchResult> {
+		let reason = format!("the send to recipient {} of its batch failed", failed);
+		for (slate, (_, _, estimate_only)) in slates.iter().zip(sends.iter()) {
+			if *estimate_only {
+				continue;
+			}
+			let wallet = self.wallet_inst.clone();
+			if let Err(e) = owner::fail_send_tx(wallet, keychain_mask, slate, &reason) {
+				warn!("Unable to cancel the send of slate {}: {}", slate.id, e);
+			}
+		}
+		let mut error = Some(error);
+		sends
+			.iter()
+			.enumerate()
+			.map(|(i, (amount, _, _))| {
+				let res = match i == failed {
+					true => Err(error.take().unwrap()),
+					false => Err(ErrorKind::GenericError(reason.clone()).into()),
+				};
+				InitTxBatchResult::new(*amount, res)
+			})
+			.collect()
+	}
+
+	/// Approves a send awaiting approval, initiated with
+	/// [`init_send_tx`](struct.Owner.html#method.init_send_tx) while sends require a second
+	/// operator's approval (see [`set_require_approval`](struct.Owner.html#method.set_require_approval)).
+	/// A send given `send_args` is then sent to its recipient, finalized and posted as
+	/// requested, others can be finalized with [`finalize_tx`](struct.Owner.html#method.finalize_tx).
+	///
+	/// # Arguments
+	/// * `approval_token` - The approving operator's token, issued by
+	/// [`open_wallet`](struct.Owner.html#method.open_wallet) with the
+	/// [`TokenScope::Approver`](enum.TokenScope.html) scope
+	/// * `tx_id` - The id of the transaction in the wallet's transaction log
+	///
+	/// # Returns
+	/// * Ok with the approved [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::Approval` if sends don't require approval, the token isn't an approver's or the
+	/// transaction isn't awaiting approval.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// api_owner.set_require_approval(true);
+	/// let pw = ZeroingString::from("");
+	/// let approver = api_owner.open_wallet(None, pw, true, Some(api::TokenScope::Approver));
+	///
+	/// if let Ok(Some(token)) = approver {
+	/// 	let result = api_owner.approve_tx(&token, 1);
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn approve_tx(&self, approval_token: &SecretKey, tx_id: u32) -> Result<TxLogEntry, Error> {
+		if !self.require_approval.load(Ordering::Relaxed) {
+			let msg = "sends don't require approval".to_owned();
+			return Err(ErrorKind::Approval(msg).into());
+		}
+		let keychain_mask = match self.token_scope(&to_hex(approval_token.0.to_vec())) {
+			Some((TokenScope::Approver, mask)) => mask,
+			_ => {
+				let msg = "invalid approval token".to_owned();
+				return Err(ErrorKind::Approval(msg).into());
+			}
+		};
+		let keychain_mask = keychain_mask.as_ref();
+		let tx = {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::approve_tx(&mut **w, keychain_mask, tx_id)?
+		};
+
+		// release a send held until approved, its outputs already locked
+		let held = match tx.tx_slate_id {
+			Some(id) => self.held_sends.lock().remove(&id),
+			None => None,
+		};
+		if let Some((sa, slate)) = held {
+			let slate = self.exchange_slate(keychain_mask, &sa, &slate)?;
+			self.complete_send(keychain_mask, &sa, slate)?;
+		}
+		Ok(tx)
+	}
+
+	/// Queues a send, returning immediately with the id of its job. The send is executed in
+	/// the background by a worker as [`init_send_tx`](struct.Owner.html#method.init_send_tx)
+	/// would with the same arguments: the slate is created and its outputs locked, it is
+	/// exchanged with the recipient over the method given by the `send_args`, then finalized
+	/// and posted as requested. Callers such as payout processors therefore don't wait on slow
+	/// transports.
+	///
+	/// Waiting sends are started by priority, and in the order queued for the same priority.
+	/// Up to [`set_send_queue_workers`](struct.Owner.html#method.set_send_queue_workers) sends
+	/// are executed at the same time, only one of them to each destination. Outputs are selected
+	/// and locked by one send at a time, so sends executed together never spend the same outputs.
+	///
+	/// An exchange which fails is retried, up to
+	/// [`SEND_QUEUE_MAX_ATTEMPTS`](constant.SEND_QUEUE_MAX_ATTEMPTS.html) attempts
+	/// with a growing delay in between, starting from the one set with
+	/// [`set_send_queue_retry_delay`](struct.Owner.html#method.set_send_queue_retry_delay);
+	/// the same slate is sent on each attempt. If all attempts
+	/// fail the transaction is cancelled, releasing its outputs. Failures after the exchange
+	/// aren't retried.
+	///
+	/// The state of the job can be queried with
+	/// [`get_send_job`](struct.Owner.html#method.get_send_job), and each change of state is
+	/// also returned by [`get_updater_messages`](struct.Owner.html#method.get_updater_messages).
+	/// Jobs are kept in memory by this instance of the Owner API, and are lost if the wallet is
+	/// restarted before they run.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html), which must
+	/// include the `send_args`.
+	/// * `priority` - [`SendPriority`](../epic_wallet_libwallet/api_impl/types/enum.SendPriority.html)
+	/// of the send in the queue.
+	///
+	/// # Returns
+	/// * Ok with the id of the queued job
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the send arguments
+	/// are missing or their method isn't supported, or `ErrorKind::Approval` if sends require
+	/// approval.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use epic_wallet_libwallet::{InitTxSendArgs, SendPriority};
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	send_args: Some(InitTxSendArgs {
+	/// 		method: "http".to_owned(),
+	/// 		dest: "http://127.0.0.1:3415".to_owned(),
+	/// 		finalize: true,
+	/// 		post_tx: true,
+	/// 		fluff: false,
+	/// 	}),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.queue_send(None, args, SendPriority::Normal);
+	///
+	/// if let Ok(id) = result {
+	/// 	// check on the send later
+	/// 	let job = api_owner.get_send_job(None, id);
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn queue_send(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: InitTxArgs,
+		priority: SendPriority,
+	) -> Result<Uuid, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		match args.send_args.as_ref() {
+			Some(sa) => check_send_method(&sa.method)?,
+			None => {
+				let msg = "send arguments are required to queue a send".to_owned();
+				return Err(ErrorKind::GenericError(msg).into());
+			}
+		}
+		if self.require_approval.load(Ordering::Relaxed) {
+			let msg = "sends awaiting approval can't be queued".to_owned();
+			return Err(ErrorKind::Approval(msg).into());
+		}
+
+		let id = Uuid::new_v4();
+		let send = QueuedSend {
+			id,
+			priority,
+			keychain_mask: keychain_mask.cloned(),
+			args,
+			tor_config: self.tor_config.lock().clone(),
+			epicbox_config: self.epicbox_config.lock().clone(),
+		};
+		for _ in 0..self.send_queue.push(send) {
+			let status_tx = self.status_tx.lock().clone();
+			let mut worker = Owner::new(self.wallet_inst.clone(), status_tx.clone());
+			worker.doctest_mode = self.doctest_mode;
+			worker.set_node_height_guard(self.node_height_guard.load(Ordering::Relaxed));
+			send_queue::start_send_worker(worker, self.send_queue.clone(), status_tx)?;
+		}
+		Ok(id)
+	}
+
+	/// Returns the state of a send queued with [`queue_send`](struct.Owner.html#method.queue_send).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - The id of the job, as returned by `queue_send`.
+	///
+	/// # Returns
+	/// * Ok with the [`SendJob`](../epic_wallet_libwallet/api_impl/types/struct.SendJob.html), or
+	/// `None` if no job with that id was queued on this instance
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.get_send_job(None, id);
+	///
+	/// if let Ok(Some(job)) = result {
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn get_send_job(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: Uuid,
+	) -> Result<Option<SendJob>, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		Ok(self.send_queue.job(&id))
+	}
+
+	/// Returns the state of all sends queued with
+	/// [`queue_send`](struct.Owner.html#method.queue_send) on this instance, oldest first,
+	/// including the most recent finished ones.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`SendJob`](../epic_wallet_libwallet/api_impl/types/struct.SendJob.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_send_jobs(None);
+	///
+	/// if let Ok(jobs) = result {
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn retrieve_send_jobs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<SendJob>, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		Ok(self.send_queue.jobs())
+	}
+
+	/// Exchange the slate with the recipient over the method given by the send arguments,
+	/// returning the recipient's response. Doesn't lock any outputs, so may be repeated
+	/// after a failure.
+	pub(crate) fn send_slate(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		sa: &InitTxSendArgs,
+		slate: &Slate,
+	) -> Result<Slate, Error> {
+		//TODO: in case of keybase, the response might take 60s and leave the service hanging
+		let tor_config_lock = self.tor_config.lock();
+		let epicbox_config_lock = self.epicbox_config.lock();
+
+		if sa.method == "epicbox" {
+			let epicbox_channel =
+				Box::new(EpicboxChannel::new(&sa.dest, epicbox_config_lock.clone()))
+					.map_err(|e| ErrorKind::GenericError(format!("{}", e)))?;
+			let wallet = self.wallet_inst.clone();
+			let km = match keychain_mask.as_ref() {
+				None => None,
+				Some(&m) => Some(m.to_owned()),
+			};
+			return epicbox_channel.send(wallet, km, slate);
+		}
+
+		let send_timeout = *self.send_timeout.lock();
+		let comm_adapter =
+			create_sender(&sa.method, &sa.dest, tor_config_lock.clone(), send_timeout)
+				.map_err(|e| ErrorKind::GenericError(format!("{}", e)))?;
+		let transport = sender_transport(&sa.method, &sa.dest);
+		let res = comm_adapter.send_tx(slate);
+		let event = match res {
+			Ok(_) => TransportEvent::slate_sent(slate),
+			Err(_) => TransportEvent::Failure,
+		};
+		if let Err(e) = owner::record_transport_event(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&transport,
+			event,
+		) {
+			warn!("Unable to record transport metrics: {}", e);
+		}
+		res
+	}
+
+	/// Finalize and post a slate sent with `send_slate` as requested by the send arguments,
+	/// once its outputs are locked. Epicbox responses are finalized by the listener.
+	pub(crate) fn complete_send(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		sa: &InitTxSendArgs,
+		slate: Slate,
+	) -> Result<Slate, Error> {
+		if sa.method == "epicbox" {
+			return Ok(slate);
+		}
+		let slate = match sa.finalize {
+			true => self.finalize_tx(keychain_mask, &slate)?,
+			false => slate,
+		};
+
+		if sa.post_tx {
+			self.post_tx(keychain_mask, &slate.tx, sa.fluff)?;
+		}
+		Ok(slate)
+	}
+
+	/// Issues a new invoice transaction slate, essentially a `request for payment`.
+	/// The slate created by this function will contain the amount, an output for the amount,
+	/// as well as round 1 of singature creation complete. The slate should then be send
+	/// to the payer, who should add their inputs and signature data and return the slate
+	/// via the [Foreign API's `finalize_invoice_tx`](struct.Foreign.html#method.finalize_invoice_tx) method.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`IssueInvoiceTxArgs`](../epic_wallet_libwallet/types/struct.IssueInvoiceTxArgs.html),
+	/// invoice transaction initialization arguments. See struct documentation for further detail.
+	///
+	/// # Returns
+	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
+	/// containing the updated slate.
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	///
+	/// let args = IssueInvoiceTxArgs {
+	/// 	amount: 60_000_000_000,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.issue_invoice_tx(None, args);
+	///
+	/// if let Ok(slate) = result {
+	///		// if okay, send to the payer to add their inputs
+	///		// . . .
+	/// }
+	/// ```
+	pub fn issue_invoice_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: IssueInvoiceTxArgs,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::issue_invoice_tx(&mut **w, keychain_mask, args, self.doctest_mode)
+	}
+
+	/// Processes an invoice tranaction created by another party, essentially
+	/// a `request for payment`. The incoming slate should contain a requested
+	/// amount, an output created by the invoicer convering the amount, and
+	/// part 1 of signature creation completed. This function will add inputs
+	/// equalling the amount + fees, as well as perform round 1 and 2 of signature
+	/// creation.
+	///
+	/// Callers should note that no prompting of the user will be done by this function
+	/// it is up to the caller to present the request for payment to the user
+	/// and verify that payment should go ahead.
+	///
+	/// This function also stores the final transaction in the user's wallet files for retrieval
+	/// via the [`get_stored_tx`](struct.Owner.html#method.get_stored_tx) function.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). The
+	/// payer should have filled in round 1 and 2.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments. See struct documentation for further detail.
+	///
+	/// # Returns
+	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
+	/// containing the updated slate.
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	///
+	/// // . . .
+	/// // The slate has been recieved from the invoicer, somehow
+	/// # let slate = Slate::blank(2);
+	/// let args = InitTxArgs {
+	///		src_acct_name: None,
+	///		amount: slate.amount,
+	///		minimum_confirmations: 2,
+	///		max_outputs: 500,
+	///		num_change_outputs: 1,
+	///		selection_strategy_is_use_all: false,
+	///		..Default::default()
+	///	};
+	///
+	/// let result = api_owner.process_invoice_tx(None, &slate, args);
+	///
+	/// if let Ok(slate) = result {
+	///	// If result okay, send back to the invoicer
+	///	// . . .
+	///	}
+	/// ```
+
+	pub fn process_invoice_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+		args: InitTxArgs,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::process_invoice_tx(&mut **w, keychain_mask, slate, args, self.doctest_mode)
+	}
+
+	/// Locks the outputs associated with the inputs to the transaction in the given
+	/// [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html),
+	/// making them unavailable for use in further transactions. This function is called
+	/// by the sender, (or more generally, all parties who have put inputs into the transaction,)
+	/// and must be called before the corresponding call to [`finalize_tx`](struct.Owner.html#method.finalize_tx)
+	/// that completes the transaction.
+	///
+	/// Outputs will generally remain locked until they are removed from the chain,
+	/// at which point they will become `Spent`. It is commonplace for transactions not to complete
+	/// for various reasons over which a particular wallet has no control. For this reason,
+	/// [`cancel_tx`](struct.Owner.html#method.cancel_tx) can be used to manually unlock outputs
+	/// and return them to the `Unspent` state.
+	///
+	/// The transaction log entry created when locking records the estimated weight of the
+	/// complete transaction in its `tx_size`, which is updated once the transaction is finalized.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). All
+	/// * `participant_id` - The participant id, generally 0 for the party putting in funds, 1 for the
+	/// party receiving.
+	/// elements in the `input` vector of the `tx` field that are found in the wallet's currently
+	/// active account will be set to status `Locked`
+	///
+	/// # Returns
+	/// * Ok(()) if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Remember to lock this when we're happy this is sent".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		api_owner.tx_lock_outputs(None, &slate, 0);
+	/// }
+	/// ```
+
+	pub fn tx_lock_outputs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+		participant_id: usize,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::tx_lock_outputs(&mut **w, keychain_mask, slate, participant_id)
+	}
+
+	/// Finalizes a transaction, after all parties
+	/// have filled in both rounds of Slate generation. This step adds
+	/// all participants partial signatures to create the final signature,
+	/// resulting in a final transaction that is ready to post to a node.
+	///
+	/// Note that this function DOES NOT POST the transaction to a node
+	/// for validation. This is done in separately via the
+	/// [`post_tx`](struct.Owner.html#method.post_tx) function.
+	///
+	/// This function also stores the final transaction in the user's wallet files for retrieval
+	/// via the [`get_stored_tx`](struct.Owner.html#method.get_stored_tx) function.
+	///
+	/// The weight, number of kernels and serialized size of the final transaction are recorded in
+	/// the `tx_size` of its [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html).
+	///
+	/// If the recipient signed its response, the signature is verified, and must be made with the
+	/// key of the payment proof recipient address if any. A signed response is required if a
+	/// `response_address` was given in the [`InitTxArgs`](../epic_wallet_libwallet/api_impl/types/struct.InitTxArgs.html),
+	/// failing with `ErrorKind::ResponseSignature` otherwise.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html). All
+	/// participants must have filled in both rounds, and the sender should have locked their
+	/// outputs (via the [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs) function).
+	///
+	/// # Returns
+	/// * ``Ok([`slate`](../epic_wallet_libwallet/slate/struct.Slate.html))` if successful,
+	/// containing the new finalized slate.
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Finalize this tx now".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
+	///		//
+	///		// Retrieve slate back from recipient
+	///		//
+	///		let res = api_owner.finalize_tx(None, &slate);
+	/// }
+	/// ```
+
+	pub fn finalize_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<Slate, Error> {
+		let slate = {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::finalize_tx(&mut **w, keychain_mask, &slate)?
+		};
+		if let Some(h) = self.tx_hook.lock().clone() {
+			if let Err(e) = h.post_finalize(&slate) {
+				warn!(
+					"Post-finalize hook failed for transaction {}: {}",
+					slate.id, e
+				);
+			}
+		}
+		Ok(slate)
+	}
+
+	/// Initiates a transaction creating an output shared with the recipient: an output of the
+	/// amount whose blinding factor is split between both wallets, so it can only be spent by
+	/// both of them together, e.g. as an escrow. The sender's inputs and change are selected as
+	/// in [`init_send_tx`](struct.Owner.html#method.init_send_tx), which the returned slate
+	/// should be locked the same way as, with
+	/// [`tx_lock_outputs`](struct.Owner.html#method.tx_lock_outputs).
+	///
+	/// The slate then goes back and forth through
+	/// [`receive_multisig`](struct.Owner.html#method.receive_multisig): first the recipient's,
+	/// then the sender's, then the recipient's again, who ends up with the final transaction
+	/// to post. The shared output isn't part of either wallet's balance. It's spent by sending
+	/// it whole to the other party with [`init_send_tx`](struct.Owner.html#method.init_send_tx),
+	/// giving its commitment as the only input.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`InitTxArgs`](../epic_wallet_libwallet/api_impl/types/struct.InitTxArgs.html),
+	/// transaction initialization arguments, as for `init_send_tx`. Estimates, decoy outputs and
+	/// payment proofs aren't supported.
+	///
+	/// # Returns
+	/// * a result containing:
+	/// * The transaction [Slate](../epic_wallet_libwallet/slate/struct.Slate.html), to be sent to
+	/// the recipient
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 2,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_multisig_output(None, args);
+	///
+	/// if let Ok(slate) = result {
+	/// 	// Lock our outputs and send the slate to the recipient
+	/// 	// ...
+	/// 	api_owner.tx_lock_outputs(None, &slate, 0);
+	/// }
+	/// ```
+
+	pub fn init_multisig_output(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: InitTxArgs,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::init_multisig_output(&mut **w, keychain_mask, args, self.doctest_mode)
+	}
+
+	/// Takes this wallet's next step building the shared output of a slate initiated with
+	/// [`init_multisig_output`](struct.Owner.html#method.init_multisig_output). Which step it
+	/// is depends on what the wallet stored for the slate:
+	///
+	/// * The recipient joins the output with its share, adding its rounds of the output's range
+	/// proof and its public excess and nonce.
+	/// * The sender adds its partial signature and its rounds of the range proof.
+	/// * The recipient completes the range proof and the transaction, which is returned ready
+	/// to post.
+	///
+	/// Both wallets record the shared output once they hold their share of it.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html),
+	/// as received from the other party.
+	///
+	/// # Returns
+	/// * Ok with the [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html), to be sent back
+	/// to the other party or, once complete, posted
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::Multisig` if the slate doesn't create a shared output or its parties don't
+	/// check out.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// # let slate = Slate::blank(2);
+	/// // Received from the other party
+	/// // ...
+	/// let result = api_owner.receive_multisig(None, &slate);
+	///
+	/// if let Ok(slate) = result {
+	/// 	// Send the slate back, or post its transaction once complete
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn receive_multisig(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<Slate, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::receive_multisig(&mut **w, keychain_mask, slate, self.doctest_mode)
+	}
+
+	/// Starts an atomic swap of epics for bitcoins or litecoins as the seller. The epics are
+	/// locked in an output shared with the buyer, built as in
+	/// [`init_multisig_output`](struct.Owner.html#method.init_multisig_output), whose inputs
+	/// are locked right away. The buyer signs the seller's refund of the shared output, valid
+	/// from `refund_blocks` blocks on, before the seller signs the lock transaction.
+	///
+	/// The buyer then locks the secondary currency in the `secondary_lock_script` of the swap,
+	/// redeemable by the seller with its redeem key along with a secret of the buyer's, or by
+	/// the buyer once `secondary_lock_hours` passed. Redeeming the locked epics reveals the
+	/// secret to the seller: the buyer gives its partial signature of the redeem transaction
+	/// plus the secret before the seller signs it.
+	///
+	/// Each party then takes its steps with [`swap_process`](struct.Owner.html#method.swap_process),
+	/// given the other party's last message, until the swap is `Redeemed`, or gives up with
+	/// [`swap_cancel`](struct.Owner.html#method.swap_cancel).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `args` - [`SwapStartArgs`](../epic_wallet_libwallet/api_impl/types/struct.SwapStartArgs.html),
+	/// the terms of the swap.
+	///
+	/// # Returns
+	/// * a result containing:
+	/// * The [`SwapStep`](../epic_wallet_libwallet/api_impl/types/struct.SwapStep.html), with
+	/// the stored swap and the offer to send to the buyer
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::Swap` if the terms of the swap don't check out.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let args = SwapStartArgs {
+	/// 	amount: 2_000_000_000,
+	/// 	secondary_currency: SwapCurrency::Btc,
+	/// 	secondary_amount: 1_000_000,
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.swap_start(None, args);
+	///
+	/// if let Ok(step) = result {
+	/// 	// Send the offer to the buyer
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn swap_start(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		args: SwapStartArgs,
+	) -> Result<SwapStep, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::swap_start(&mut **w, keychain_mask, args, self.doctest_mode)
+	}
+
+	/// Takes this wallet's next step of a swap started with
+	/// [`swap_start`](struct.Owner.html#method.swap_start), given the other party's last
+	/// message if it sent one. Which step it is depends on the swap's role and status:
+	///
+	/// * The buyer accepts an offer, joining the shared output and signing the refund.
+	/// * The seller signs the lock transaction, which the buyer completes and posts.
+	/// * Once the lock transaction is confirmed, the seller starts the redeem transaction, to
+	/// which the buyer adds its output and its adaptor signature. The buyer should then lock
+	/// the secondary currency in the swap's `secondary_lock_script`.
+	/// * Once the secondary currency is locked, the seller signs the redeem transaction, which
+	/// the buyer completes and posts.
+	/// * Once the redeem transaction is confirmed, the seller takes the secret out of it,
+	/// stored as the swap's `secondary_secret` along with its `secondary_key`, to redeem the
+	/// secondary currency.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - The id of the swap.
+	/// * `message` - The other party's last [`SwapMessage`](../epic_wallet_libwallet/api_impl/types/struct.SwapMessage.html),
+	/// if the step waits for one.
+	///
+	/// # Returns
+	/// * Ok with the [`SwapStep`](../epic_wallet_libwallet/api_impl/types/struct.SwapStep.html),
+	/// the updated swap and the message to send to the other party, if any
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::Swap` if the message isn't the expected one or doesn't check out.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// # let id = Uuid::new_v4();
+	/// // Message received from the other party
+	/// // ...
+	/// let result = api_owner.swap_process(None, id, None);
+	///
+	/// if let Ok(step) = result {
+	/// 	// Send step.message to the other party, if any
+	/// 	// ...
+	/// }
+	/// ```
+
+	pub fn swap_process(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: Uuid,
+		message: Option<SwapMessage>,
+	) -> Result<SwapStep, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::swap_process(&mut **w, keychain_mask, id, message, self.doctest_mode)
+	}
+
+	/// Cancels a swap. Before its epics are locked, the swap is just dropped. The seller
+	/// refunds the locked epics once the chain reaches the swap's `refund_height`, posting the
+	/// refund. The buyer gives up a swap whose secondary currency was locked once its
+	/// `secondary_lock_time` passed, the swap's `secondary_key` then refunding it.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - The id of the swap.
+	///
+	/// # Returns
+	/// * Ok with the cancelled [`Swap`](../epic_wallet_libwallet/types/struct.Swap.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::Swap` if the swap is complete or can't be refunded yet.
+	///
+	/// # Example
+	/// Set up as in [new](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// # let id = Uuid::new_v4();
+	/// let result = api_owner.swap_cancel(None, id);
+	/// ```
+
+	pub fn swap_cancel(&self, keychain_mask: Option<&SecretKey>, id: Uuid) -> Result<Swap, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::swap_cancel(&mut **w, keychain_mask, id)
+	}
+
+	/// Posts a completed transaction to the listening node for validation and inclusion in a block
+	/// for mining.
+	///
+	/// Unless disabled with [`set_node_height_guard`](struct.Owner.html#method.set_node_height_guard),
+	/// the transaction isn't posted to a node whose tip is behind the height the wallet last
+	/// refreshed to, or that's still syncing, failing with `ErrorKind::NodeNotReady`.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx` - A completed [`Transaction`](../epic_core/core/transaction/struct.Transaction.html),
+	/// typically the `tx` field in the transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html).
+	/// * `fluff` - Instruct the node whether to use the Dandelion protocol when posting the
+	/// transaction. If `true`, the node should skip the Dandelion phase and broadcast the
+	/// transaction to all peers immediately. If `false`, the node will follow dandelion logic and
+	/// initiate the stem phase.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Post this tx".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
+	///		//
+	///		// Retrieve slate back from recipient
+	///		//
+	///		let res = api_owner.finalize_tx(None, &slate);
+	///		let res = api_owner.post_tx(None, &slate.tx, true);
+	/// }
+	/// ```
+
+	pub fn post_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx: &Transaction,
+		fluff: bool,
+	) -> Result<(), Error> {
+		let client = {
+			let mut w_lock = self.wallet_inst.lock();
+			let lc = w_lock.lc_provider()?;
+			// a watch-only wallet posts the transactions signed offline
+			let watch_only = lc.view_key(None)?.is_some();
+			let w = lc.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			if !watch_only {
+				let _ = w.keychain(keychain_mask)?;
+			}
+			if self.node_height_guard.load(Ordering::Relaxed) {
+				owner::check_node_height(&mut **w)?;
+			}
+			w.w2n_client().clone()
+		};
+		owner::post_tx(&client, tx, fluff)
+	}
+
+	/// Cancels a transaction. This entails:
+	/// * Setting the transaction status to either `TxSentCancelled` or `TxReceivedCancelled`
+	/// * Deleting all change outputs or recipient outputs associated with the transaction
+	/// * Setting the status of all assocatied inputs from `Locked` to `Spent` so they can be
+	/// used in new transactions.
+	///
+	/// Transactions can be cancelled by transaction log id or slate id (call with either set to
+	/// Some, not both)
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx_id` - If present, cancel by the [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html) id
+	/// for the transaction.
+	///
+	/// * `tx_slate_id` - If present, cancel by the Slate id.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Cancel this tx".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
+	///		//
+	///		// We didn't get the slate back, or something else went wrong
+	///		//
+	///		let res = api_owner.cancel_tx(None, None, Some(slate.id.clone()));
+	/// }
+	/// ```
+
+	pub fn cancel_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<(), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::cancel_tx(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			tx_id,
+			tx_slate_id,
+		)
+	}
+
+	/// Cancels all unconfirmed sent and received transactions matching the given filter in a
+	/// single call, e.g. all unconfirmed sent transactions older than a given number of hours.
+	/// Each matching transaction is cancelled as in
+	/// [`cancel_tx`](struct.Owner.html#method.cancel_tx), and a failure to cancel one
+	/// transaction does not prevent the others from being cancelled.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `filter` - A [`TxFilter`](../epic_wallet_libwallet/api_impl/types/struct.TxFilter.html)
+	/// selecting which unconfirmed transactions to cancel.
+	///
+	/// # Returns
+	/// * Ok with a [`TxBulkResult`](../epic_wallet_libwallet/api_impl/types/struct.TxBulkResult.html)
+	/// for each matching transaction if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let filter = TxFilter {
+	/// 	tx_type: Some(TxLogEntryType::TxSent),
+	/// 	min_age_hours: Some(24),
+	/// };
+	///
+	/// let result = api_owner.cancel_txs(None, &filter);
+	///
+	/// if let Ok(results) = result {
+	///		// check which transactions were cancelled
+	///		//...
+	/// }
+	/// ```
+
+	pub fn cancel_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		filter: &TxFilter,
+	) -> Result<Vec<TxBulkResult>, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::cancel_txs(self.wallet_inst.clone(), keychain_mask, &tx, filter)
+	}
+
+	/// Reposts the stored transactions of all unconfirmed sent and received transactions
+	/// matching the given filter in a single call, cancelled transactions being left out.
+	/// Transactions without stored transaction data are reported as failed, and a failure to
+	/// post one transaction does not prevent the others from being posted.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `filter` - A [`TxFilter`](../epic_wallet_libwallet/api_impl/types/struct.TxFilter.html)
+	/// selecting which unconfirmed transactions to repost.
+	/// * `fluff` - Instruct the node whether to use the Dandelion protocol when posting the
+	/// transactions. If `true`, the node should skip the Dandelion phase and broadcast the
+	/// transactions to all peers immediately.
+	///
+	/// # Returns
+	/// * Ok with a [`TxBulkResult`](../epic_wallet_libwallet/api_impl/types/struct.TxBulkResult.html)
+	/// for each matching transaction if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let filter = TxFilter {
+	/// 	tx_type: Some(TxLogEntryType::TxSent),
+	/// 	min_age_hours: Some(1),
+	/// };
+	///
+	/// let result = api_owner.repost_txs(None, &filter, false);
+	///
+	/// if let Ok(results) = result {
+	///		// check which transactions were reposted
+	///		//...
+	/// }
+	/// ```
+
+	pub fn repost_txs(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		filter: &TxFilter,
+		fluff: bool,
+	) -> Result<Vec<TxBulkResult>, Error> {
+		if self.node_height_guard.load(Ordering::Relaxed) {
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			owner::check_node_height(&mut **w)?;
+		}
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::repost_txs(self.wallet_inst.clone(), keychain_mask, &tx, filter, fluff)
+	}
+
+	/// Sweeps the spendable balance of a hot account above the policy's threshold to a cold
+	/// account in the same wallet. The transfer is created, received, finalized and posted in
+	/// one go, and both of its transaction log entries are tagged with
+	/// [`SWEEP_TX_TAG`](../epic_wallet_libwallet/api_impl/types/constant.SWEEP_TX_TAG.html) so
+	/// they can be told apart from external payments. The wallet state is refreshed from the
+	/// node first, and nothing is swept if that fails.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `policy` - A [`SweepPolicy`](../epic_wallet_libwallet/api_impl/types/struct.SweepPolicy.html)
+	/// naming the hot and cold accounts and the balance to leave in the hot account.
+	///
+	/// # Returns
+	/// * Ok with the posted [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html), or `None`
+	/// if the hot account's spendable balance doesn't exceed the threshold
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let policy = SweepPolicy {
+	///     hot_account: "default".to_owned(),
+	///     cold_account: "cold".to_owned(),
+	///     max_hot_balance: 10_000_000_000,
+	///     minimum_confirmations: 10,
+	/// };
+	///
+	/// let result = api_owner.sweep_hot_account(None, &policy);
+	///
+	/// if let Ok(Some(slate)) = result {
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn sweep_hot_account(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		policy: &SweepPolicy,
+	) -> Result<Option<Slate>, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::sweep_hot_account(self.wallet_inst.clone(), keychain_mask, &tx, policy)
+	}
+
+	/// Retrieves the stored transaction associated with a TxLogEntry. Can be used even after the
+	/// transaction has completed.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `tx_log_entry` - A [`TxLogEntry`](../epic_wallet_libwallet/types/struct.TxLogEntry.html)
+	///
+	/// # Returns
+	/// * Ok with the stored  [`Transaction`](../epic_core/core/transaction/struct.Transaction.html)
+	/// if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let update_from_node = true;
+	/// let tx_id = None;
+	/// let tx_slate_id = None;
+	///
+	/// // Return all TxLogEntries
+	/// let result = api_owner.retrieve_txs(None, update_from_node, tx_id, tx_slate_id);
+	///
+	/// if let Ok((was_updated, tx_log_entries)) = result {
+	///		let stored_tx = api_owner.get_stored_tx(None, &tx_log_entries[0]).unwrap();
+	///		//...
+	/// }
+	/// ```
+
+	// TODO: Should be accepting an id, not an entire entry struct
+	pub fn get_stored_tx(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		tx_log_entry: &TxLogEntry,
+	) -> Result<Option<Transaction>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::get_stored_tx(&**w, tx_log_entry)
+	}
+
+	/// Verifies all messages in the slate match their public keys.
+	///
+	/// The optional messages themselves are part of the `participant_data` field within the slate.
+	/// Messages are signed with the same key used to sign for the paricipant's inputs, and can thus be
+	/// verified with the public key found in the `public_blind_excess` field. This function is a
+	/// simple helper to returns whether all signatures in the participant data match their public
+	/// keys.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `slate` - The transaction [`Slate`](../epic_wallet_libwallet/slate/struct.Slate.html).
+	///
+	/// # Returns
+	/// * `Ok(())` if successful and the signatures validate
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let args = InitTxArgs {
+	/// 	src_acct_name: None,
+	/// 	amount: 2_000_000_000,
+	/// 	minimum_confirmations: 10,
+	/// 	max_outputs: 500,
+	/// 	num_change_outputs: 1,
+	/// 	selection_strategy_is_use_all: false,
+	/// 	message: Some("Just verify messages".to_owned()),
+	/// 	..Default::default()
+	/// };
+	/// let result = api_owner.init_send_tx(
+	/// 	None,
+	/// 	args,
+	/// );
+	///
+	/// if let Ok(slate) = result {
+	///		// Send slate somehow
+	///		// ...
+	///		// Lock our outputs if we're happy the slate was (or is being) sent
+	///		let res = api_owner.tx_lock_outputs(None, &slate, 0);
+	///		//
+	///		// Retrieve slate back from recipient
+	///		//
+	///		let res = api_owner.verify_slate_messages(None, &slate);
+	/// }
+	/// ```
+	pub fn verify_slate_messages(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		slate: &Slate,
+	) -> Result<(), Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		owner::verify_slate_messages(slate)
+	}
+
+	/// Scans the entire UTXO set from the node, identify which outputs belong to the given wallet
+	/// update the wallet state to be consistent with what's currently in the UTXO set.
+	///
+	/// This function can be used to repair wallet state, particularly by restoring outputs that may
+	/// be missing if the wallet owner has cancelled transactions locally that were then successfully
+	/// posted to the chain.
+	///
+	/// This operation scans the entire chain, and is expected to be time intensive. It is imperative
+	/// that no other processes should be trying to use the wallet at the same time this function is
+	/// running.
+	///
+	/// When an output is found that doesn't exist in the wallet, a corresponding
+	/// [TxLogEntry](../epic_wallet_libwallet/types/struct.TxLogEntry.html) is created.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `start_height` - If provided, the height of the first block from which to start scanning.
+	/// The scan will start from block 1 if this is not provided.
+	/// * `delete_unconfirmed` - if `false`, the scan process will be non-destructive, and
+	/// mostly limited to restoring missing outputs. It will leave unconfirmed transaction logs entries
+	/// and unconfirmed outputs intact. If `true`, the process will unlock all locked outputs,
+	/// restore all missing outputs, and mark any outputs that have been marked 'Spent' but are still
+	/// in the UTXO set as 'Unspent' (as can happen during a fork). It will also attempt to cancel any
+	/// transaction log entries associated with any locked outputs or outputs incorrectly marked 'Spent'.
+	/// Note this completely removes all outstanding transactions, so users should be very aware what
+	/// will happen if this flag is set. Note that if transactions/outputs are removed that later
+	/// confirm on the chain, another call to this function will restore them.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.scan(
+	/// 	None,
+	/// 	Some(20000),
+	/// 	false,
+	/// );
+	///
+	/// if let Ok(_) = result {
+	///		// Wallet outputs should be consistent with what's on chain
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn scan(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::scan(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			start_height,
+			delete_unconfirmed,
+			&tx,
+			None,
+		)
+	}
+
+	/// A [`scan`](struct.Owner.html#method.scan) only restoring and repairing the outputs
+	/// of one account, much quicker to settle for a wallet of many accounts. The outputs of
+	/// the other accounts found on the chain are left as they are, and the wallet's last
+	/// scanned block isn't changed.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `account` - Label of the account scanned for.
+	/// * `start_height` - If provided, the height of the first block from which to start scanning.
+	/// The scan will start from block 1 if this is not provided.
+	/// * `delete_unconfirmed` - as for [`scan`](struct.Owner.html#method.scan), limited to the
+	/// outputs of the account.
+	///
+	/// # Returns
+	/// * `Ok(())` if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// such as an unknown account.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.scan_account(None, "default", Some(20000), false);
+	///
+	/// if let Ok(_) = result {
+	///		// The outputs of the default account should be consistent with what's on chain
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn scan_account(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		account: &str,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::scan_account(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			account,
+			start_height,
+			delete_unconfirmed,
+			&tx,
+			None,
+		)
+	}
+
+	/// Refreshes the outputs and transactions of one account from the node, checking all
+	/// the account's outputs. Unlike the refresh of the retrieve methods, the recent blocks
+	/// aren't scanned for new outputs, nor the other accounts checked.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `account` - Label of the account refreshed.
+	///
+	/// # Returns
+	/// * `Ok(true)` if the account was refreshed, `Ok(false)` if the node couldn't be reached
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.refresh_account(None, "default");
+	///
+	/// if let Ok(refreshed) = result {
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn refresh_account(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		account: &str,
+	) -> Result<bool, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::refresh_account(self.wallet_inst.clone(), keychain_mask, account, &tx)
+	}
+
+	/// Starts a [`scan`](struct.Owner.html#method.scan) with the same arguments in the
+	/// background, returning at once with the id of its job. The progress of the scan, the
+	/// percentage complete and the height of the last output checked, is returned by
+	/// [`get_job_status`](struct.Owner.html#method.get_job_status), and the scan can be
+	/// stopped with [`cancel_job`](struct.Owner.html#method.cancel_job).
+	///
+	/// Only one scan started this way runs at a time. Jobs are kept in memory by this
+	/// instance of the Owner API.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `start_height` - If provided, the height of the first block from which to start scanning.
+	/// The scan will start from block 1 if this is not provided.
+	/// * `delete_unconfirmed` - As in [`scan`](struct.Owner.html#method.scan).
+	///
+	/// # Returns
+	/// * Ok with the id of the scan's job
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if another scan
+	/// is running.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.start_scan(None, Some(20000), false);
+	///
+	/// if let Ok(id) = result {
+	/// 	// check on the scan later
+	/// 	let job = api_owner.get_job_status(None, id);
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn start_scan(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<Uuid, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		let id = match self.doctest_mode {
+			true => Uuid::parse_str(DOCTEST_SCAN_JOB_ID).unwrap(),
+			false => Uuid::new_v4(),
+		};
+		let cancel = self
+			.scan_jobs
+			.add(id, start_height.unwrap_or(1), delete_unconfirmed)?;
+
+		let wallet_inst = self.wallet_inst.clone();
+		let jobs = self.scan_jobs.clone();
+		let tx = self.status_tx.lock().clone();
+		let keychain_mask = keychain_mask.cloned();
+		let res = thread::Builder::new()
+			.name("wallet-scan".to_string())
+			.spawn(move || {
+				let progress = |percent: u8, height: Option<u64>| {
+					jobs.update(&id, &|j| {
+						j.percent_complete = percent;
+						j.current_height = height;
+					});
+					!cancel.load(Ordering::Relaxed)
+				};
+				let res = owner::scan(
+					wallet_inst,
+					keychain_mask.as_ref(),
+					start_height,
+					delete_unconfirmed,
+					&tx,
+					Some(&progress),
+				);
+				match res {
+					Ok(_) => jobs.update(&id, &|j| {
+						j.status = ScanJobStatus::Completed;
+						j.percent_complete = 100;
+					}),
+					Err(e) => match e.kind() {
+						ErrorKind::ScanCancelled => {
+							jobs.update(&id, &|j| j.status = ScanJobStatus::Cancelled)
+						}
+						_ => {
+							error!("Scan of the wallet failed with error: {}", e);
+							let msg = format!("{}", e);
+							jobs.update(&id, &|j| {
+								j.status = ScanJobStatus::Failed;
+								j.error = Some(msg.clone());
+							})
+						}
+					},
+				}
+			});
+		if let Err(e) = res {
+			self.scan_jobs.update(&id, &|j| {
+				j.status = ScanJobStatus::Failed;
+				j.error = Some(format!("{}", e));
+			});
+			return Err(ErrorKind::GenericError(format!("Unable to start scan: {}", e)).into());
+		}
+		Ok(id)
+	}
+
+	/// Returns the state of a scan started with
+	/// [`start_scan`](struct.Owner.html#method.start_scan).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - The id of the job, as returned by `start_scan`.
+	///
+	/// # Returns
+	/// * Ok with the [`ScanJob`](../epic_wallet_libwallet/api_impl/types/struct.ScanJob.html), or
+	/// `None` if no job with that id was started on this instance
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.get_job_status(None, id);
+	///
+	/// if let Ok(Some(job)) = result {
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn get_job_status(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		id: Uuid,
+	) -> Result<Option<ScanJob>, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		Ok(self.scan_jobs.job(&id))
+	}
+
+	/// Stops a scan started with [`start_scan`](struct.Owner.html#method.start_scan). The
+	/// scan stops after the batch of outputs it's checking, and its job is then
+	/// `Cancelled`. Once the scan started repairing the wallet with the outputs found it
+	/// completes regardless.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `id` - The id of the job, as returned by `start_scan`.
+	///
+	/// # Returns
+	/// * Ok with `true` if the scan was running and was told to stop, `false` if no
+	/// running job with that id was started on this instance
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let id = Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap();
+	/// let result = api_owner.cancel_job(None, id);
+	///
+	/// if let Ok(false) = result {
+	/// 	//...
+	/// }
+	/// ```
+
+	pub fn cancel_job(&self, keychain_mask: Option<&SecretKey>, id: Uuid) -> Result<bool, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		Ok(self.scan_jobs.cancel(&id))
+	}
+
+	/// Reports how many unspent outputs belonging to the wallet's seed, and how much value,
+	/// a [`scan`](struct.Owner.html#method.scan) of the given range of blocks would find,
+	/// and how many of them the wallet doesn't know of yet. Nothing is written to the wallet,
+	/// so this can be used to quickly check a recovered seed against a small range of blocks
+	/// before scanning the whole chain.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `start_height` - If provided, the height of the first block of the range, otherwise 1.
+	/// * `end_height` - If provided, the height of the last block of the range, otherwise the
+	/// height of the chain tip.
+	///
+	/// # Returns
+	/// * Ok with a [`ScanPreview`](../epic_wallet_libwallet/api_impl/types/struct.ScanPreview.html)
+	/// if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let mut api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.scan_preview(
+	/// 	None,
+	/// 	Some(20000),
+	/// 	Some(30000),
+	/// );
+	///
+	/// if let Ok(preview) = result {
+	///		// preview.num_missing outputs would be restored by a scan of the range
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn scan_preview(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		start_height: Option<u64>,
+		end_height: Option<u64>,
+	) -> Result<ScanPreview, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		owner::scan_preview(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			start_height,
+			end_height,
+			&tx,
+		)
+	}
+
+	/// Retrieves the last known height known by the wallet. This is determined as follows:
+	/// * If the wallet can successfully contact its configured node, the reported node
+	/// height is returned, and the `updated_from_node` field in the response is `true`
+	/// * If the wallet cannot contact the node, this function returns the height of the
+	/// most recent block header cached by the wallet during its last update, or the maximum
+	/// height of all outputs contained within the wallet if no headers are cached, and the
+	/// `updated_from_node` fields in the response is set to false.
+	///
+	/// Clients should generally ensure the `updated_from_node` field is returned as
+	/// `true` before assuming the height for any operation.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a  [`NodeHeightResult`](../epic_wallet_libwallet/types/struct.NodeHeightResult.html)
+	/// if successful. If the height result was obtained from the configured node,
+	/// `updated_from_node` will be set to `true`
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.node_height(None);
+	///
+	/// if let Ok(node_height_result) = result {
+	///		if node_height_result.updated_from_node {
+	///			//we can assume node_height_result.height is relatively safe to use
+	///
+	///		}
+	///		//...
+	/// }
+	/// ```
+
+	pub fn node_height(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<NodeHeightResult, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			// Test keychain mask, to keep API consistent
+			let _ = w.keychain(keychain_mask)?;
+		}
+		let mut res = owner::node_height(self.wallet_inst.clone(), keychain_mask)?;
+		if self.doctest_mode {
+			// return a consistent hash for doctest
+			res.header_hash =
+				"d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d".to_owned();
+		}
+		Ok(res)
+	}
+
+	/// Returns the header (height, hash and timestamp) of the block at the given height. Headers
+	/// are kept in a cache in the wallet database, which is filled with the chain tip on each
+	/// wallet update and with any header looked up through this function, so repeated lookups
+	/// don't require a round trip to the node. On a cache miss the header is retrieved from
+	/// the configured node and cached.
+	///
+	/// The cache is checked against the node's chain on each update; cached headers which are no
+	/// longer part of the chain after a reorg are removed.
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `height` - The height of the block.
+	///
+	/// # Returns
+	/// * Ok with the [`BlockHeaderInfo`](../epic_wallet_libwallet/types/struct.BlockHeaderInfo.html)
+	/// of the block, or `None` if the node's chain has no block at that height
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_block_header(None, 1);
+	///
+	/// if let Ok(Some(header)) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_block_header(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		height: u64,
+	) -> Result<Option<BlockHeaderInfo>, Error> {
+		owner::get_block_header(self.wallet_inst.clone(), keychain_mask, height)
+	}
+
+	/// Returns the owner API versions served by the wallet, which methods of each version are
+	/// deprecated or have been removed, and the version of the wallet build. Client applications
+	/// can check this on connecting, rather than finding out about removed methods by calling
+	/// them after the wallet is upgraded. Doesn't require an open wallet.
+	///
+	/// # Returns
+	/// * Ok with the [`ApiVersions`](../epic_wallet_libwallet/api_impl/types/struct.ApiVersions.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_api_versions();
+	///
+	/// if let Ok(versions) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_api_versions(&self) -> Result<ApiVersions, Error> {
+		Ok(owner::api_versions())
+	}
+
+	/// Returns the usage counters of each slate transport (http, tor, epicbox) used by this
+	/// wallet, i.e. the number and total size of slates received and sent, and the number of
+	/// failures. Counters are persisted in the wallet database and survive restarts.
+	///
+	/// Note that slates received by the TOR listener are forwarded to the HTTP listener, and
+	/// are therefore counted under `http`.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`TransportMetrics`](../epic_wallet_libwallet/types/struct.TransportMetrics.html),
+	/// one for each transport that has been used
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.retrieve_transport_metrics(None);
+	///
+	/// if let Ok(metrics) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_transport_metrics(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<TransportMetrics>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_transport_metrics(&mut **w)
+	}
+
+	/// Returns the latency statistics of the requests made to the node since the wallet
+	/// was started, for each kind of request, so a slow node can be told apart from a
+	/// slow wallet. Requests taking longer than the `node_slow_request_ms` setting of
+	/// the configuration are also logged. The statistics are kept in memory only.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`NodeRequestMetrics`](../epic_wallet_libwallet/types/struct.NodeRequestMetrics.html),
+	/// one for each kind of request made, ordered by the method of the node client
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.retrieve_node_metrics(None);
+	///
+	/// if let Ok(metrics) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_node_metrics(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<NodeRequestMetrics>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_node_metrics(&mut **w)
+	}
+
+	/// Returns the call counts, errors and latency percentiles of each method of the
+	/// owner and foreign APIs served by the listeners of the process since the wallet was
+	/// started, along with the node's in
+	/// [`retrieve_node_metrics`](struct.Owner.html#method.retrieve_node_metrics). Calls
+	/// taking longer than the `rpc_slow_call_ms` setting of the configuration are also
+	/// logged, with their token and any secret left out of their parameters. The
+	/// statistics are kept in memory only.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`RpcMethodMetrics`](struct.RpcMethodMetrics.html), one for
+	/// each method called, ordered by API and method
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.retrieve_rpc_metrics(None);
+	///
+	/// if let Ok(metrics) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_rpc_metrics(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<RpcMethodMetrics>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		Ok(rpc_metrics::rpc_metrics())
+	}
+
+	/// Returns the status of the tor hidden services of the listeners running in the
+	/// process. Each is checked at an interval, asking tor over its control port whether
+	/// it published the service's descriptor, and tor is restarted if it died or hasn't
+	/// published the service for a few checks in a row.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`ListenerStatus`](../epic_wallet_impls/struct.ListenerStatus.html),
+	/// one for each hidden service, empty if no listener runs over tor
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.get_listener_status(None);
+	///
+	/// if let Ok(statuses) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_listener_status(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<ListenerStatus>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		Ok(listener_statuses())
+	}
+
+	/// Returns the epicbox keys pinned for known contacts. The epicbox key of a contact,
+	/// identified by the payment proof address of their wallet, is pinned the first time a
+	/// slate with a payment proof is exchanged with them over epicbox. Slates from or to a
+	/// different key are then refused, as the key may have been substituted by the relay.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with a vector of [`PinnedKey`](../epic_wallet_libwallet/types/struct.PinnedKey.html),
+	/// one for each known contact
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.retrieve_pinned_keys(None);
+	///
+	/// if let Ok(keys) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_pinned_keys(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Vec<PinnedKey>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_pinned_keys(&mut **w)
+	}
+
+	/// Forgets the epicbox key pinned for a contact, e.g. after they've confirmed out of band
+	/// that their key changed. The key of the next slate exchanged with the contact is pinned
+	/// in its place.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `contact` - The payment proof address of the contact, as listed by
+	/// [`retrieve_pinned_keys`](struct.Owner.html#method.retrieve_pinned_keys)
+	///
+	/// # Returns
+	/// * Ok(true) if a key was pinned for the contact, Ok(false) otherwise
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	///
+	/// let result = api_owner.unpin_key(
+	///		None,
+	///		"fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd",
+	/// );
+	///
+	/// if let Ok(was_pinned) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn unpin_key(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		contact: &str,
+	) -> Result<bool, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::unpin_key(&mut **w, keychain_mask, contact)
+	}
+
+	/// Returns a value from the wallet's metadata store, which applications such as GUI
+	/// wallets can use to keep user preferences and annotations in the wallet database,
+	/// so they're backed up along with it. Entries are grouped in namespaces, one per
+	/// application.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `namespace` - Namespace of the entry, 1 to 64 ASCII letters, digits, '-', '_' or '.'
+	/// * `key` - Key of the entry within the namespace, 1 to 256 bytes
+	///
+	/// # Returns
+	/// * Ok with the [`MetadataEntry`](../epic_wallet_libwallet/types/struct.MetadataEntry.html)
+	/// if one is stored under the namespace and key, None otherwise
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_metadata(None, "my-gui", "theme");
+	///
+	/// if let Ok(Some(entry)) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_metadata(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		namespace: &str,
+		key: &str,
+	) -> Result<Option<MetadataEntry>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::get_metadata(&mut **w, namespace, key)
+	}
+
+	/// Returns all entries of a namespace of the metadata store, sorted by key. See
+	/// [`get_metadata`](struct.Owner.html#method.get_metadata).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `namespace` - Namespace of the entries
+	///
+	/// # Returns
+	/// * Ok with a vector of [`MetadataEntry`](../epic_wallet_libwallet/types/struct.MetadataEntry.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_metadata(None, "my-gui");
+	///
+	/// if let Ok(entries) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn retrieve_metadata(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		namespace: &str,
+	) -> Result<Vec<MetadataEntry>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::retrieve_metadata(&mut **w, namespace)
+	}
+
+	/// Returns the events of the wallet kept in its store, so clients which were offline,
+	/// such as GUIs or webhook consumers reconnecting, can catch up on the events they
+	/// missed. The store keeps the last
+	/// [`MAX_STORED_EVENTS`](../epic_wallet_libwallet/constant.MAX_STORED_EVENTS.html)
+	/// transactions received or confirmed, outputs spent, reorgs detected and listeners
+	/// started or stopped.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `since_id` - Id of the last event the client saw, only the later events being
+	/// returned. All the events kept if None.
+	///
+	/// # Returns
+	/// * Ok with the [`StoredEvent`](../epic_wallet_libwallet/struct.StoredEvent.html)s,
+	/// oldest first
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_events(None, Some(42));
+	///
+	/// if let Ok(events) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_events(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		since_id: Option<u64>,
+	) -> Result<Vec<StoredEvent>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		// Test keychain mask, to keep API consistent
+		let _ = w.keychain(keychain_mask)?;
+		owner::get_events(&mut **w, since_id)
+	}
+
+	/// Appends an entry to the audit log kept in the wallet's top level directory, as the
+	/// owner listener does for every call changing the wallet. Entries can't be changed or
+	/// removed through the API.
+	///
+	/// # Arguments
+	/// * `entry` - The call to record
+	///
+	/// # Returns
+	/// * Ok(()) if the entry was written to the log
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+
+	pub fn record_audit_entry(&self, entry: &AuditEntry) -> Result<(), Error> {
+		let dir = {
+			let mut w_lock = self.wallet_inst.lock();
+			w_lock.lc_provider()?.get_top_level_directory()?
+		};
+		let _lock = self.audit_log.lock();
+		audit_log::append(Path::new(&dir), entry)
+	}
+
+	/// Returns a page of the audit log of the calls changing the wallet made through the
+	/// owner listener, oldest first, each with its method, the hash of its parameters, the
+	/// fingerprint of the token it was made with and its outcome. The parameters of the
+	/// calls holding passwords or seed words aren't hashed.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `offset` - Number of entries skipped
+	/// * `limit` - Maximum number of entries returned, or `None` for all the entries after `offset`
+	///
+	/// # Returns
+	/// * Ok with the [`AuditLogPage`](struct.AuditLogPage.html), with the number of entries
+	/// in the whole log
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_audit_log(None, 0, Some(100));
+	///
+	/// if let Ok(page) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_audit_log(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		offset: usize,
+		limit: Option<usize>,
+	) -> Result<AuditLogPage, Error> {
+		let dir = {
+			let mut w_lock = self.wallet_inst.lock();
+			let lc = w_lock.lc_provider()?;
+			let dir = lc.get_top_level_directory()?;
+			// Test keychain mask, to keep API consistent
+			let _ = lc.wallet_inst()?.keychain(keychain_mask)?;
+			dir
+		};
+		let _lock = self.audit_log.lock();
+		audit_log::read(Path::new(&dir), offset, limit)
+	}
+
+	/// Returns the entries of the audit log for an export, oldest first, read from the
+	/// log as they're iterated over rather than all at once as in
+	/// [`get_audit_log`](struct.Owner.html#method.get_audit_log). Entries appended
+	/// while iterating may or may not be returned.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	///
+	/// # Returns
+	/// * Ok with an iterator over the [`AuditEntry`](struct.AuditEntry.html) of the log
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.export_audit_log(None);
+	///
+	/// if let Ok(entries) = result {
+	///		for entry in entries {
+	///			//...
+	///		}
+	/// }
+	/// ```
+
+	pub fn export_audit_log(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<Box<dyn Iterator<Item = AuditEntry> + Send>, Error> {
+		let dir = {
+			let mut w_lock = self.wallet_inst.lock();
+			let lc = w_lock.lc_provider()?;
+			let dir = lc.get_top_level_directory()?;
+			// Test keychain mask, to keep API consistent
+			let _ = lc.wallet_inst()?.keychain(keychain_mask)?;
+			dir
+		};
+		audit_log::entries(Path::new(&dir))
+	}
+
+	/// Stores a value in the wallet's metadata store, replacing the value already stored under
+	/// the same namespace and key. See [`get_metadata`](struct.Owner.html#method.get_metadata).
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `namespace` - Namespace of the entry, 1 to 64 ASCII letters, digits, '-', '_' or '.'
+	/// * `key` - Key of the entry within the namespace, 1 to 256 bytes
+	/// * `value` - The value to store, at most 64 KiB
+	///
+	/// # Returns
+	/// * Ok(()) if the value was stored
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the namespace,
+	/// key or value is invalid, or the namespace already holds 1000 entries.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.set_metadata(None, "my-gui", "theme", "dark");
+	///
+	/// if let Ok(_) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn set_metadata(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		namespace: &str,
+		key: &str,
+		value: &str,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_metadata(&mut **w, keychain_mask, namespace, key, value)
+	}
+
+	/// Removes an entry from the wallet's metadata store.
+	///
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `namespace` - Namespace of the entry
+	/// * `key` - Key of the entry within the namespace
+	///
+	/// # Returns
+	/// * Ok(true) if an entry was stored under the namespace and key, Ok(false) otherwise
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.delete_metadata(None, "my-gui", "theme");
+	///
+	/// if let Ok(was_stored) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn delete_metadata(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		namespace: &str,
+		key: &str,
+	) -> Result<bool, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::delete_metadata(&mut **w, keychain_mask, namespace, key)
+	}
+
+	// LIFECYCLE FUNCTIONS
+
+	/// Retrieve the top-level directory for the wallet. This directory should contain the
+	/// `epic-wallet.toml` file and the `wallet_data` directory that contains the wallet
+	/// seed + data files. Future versions of the wallet API will support multiple wallets
+	/// within the top level directory.
+	///
+	/// The top level directory defaults to (in order of precedence):
+	///
+	/// 1) The current directory, from which `epic-wallet` or the main process was run, if it
+	/// contains a `epic-wallet.toml` file.
+	/// 2) ~/.epic/<chaintype>/ otherwise
+	///
+	/// # Arguments
+	///
+	/// * None
+	///
+	/// # Returns
+	/// * Ok with a String value representing the full path to the top level wallet dierctory
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.get_top_level_directory();
+	///
+	/// if let Ok(dir) = result {
+	///		println!("Top level directory is: {}", dir);
+	///		//...
+	/// }
+	/// ```
+
+	pub fn get_top_level_directory(&self) -> Result<String, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		if self.doctest_mode {
+			Ok("/doctest/dir".to_owned())
+		} else {
+			lc.get_top_level_directory()
+		}
+	}
+
+	/// Set the top-level directory for the wallet. This directory can be empty, and will be created
+	/// during a subsequent calls to [`create_config`](struct.Owner.html#method.create_config)
+	///
+	/// Set [`get_top_level_directory`](struct.Owner.html#method.get_top_level_directory) for a
+	/// description of the top level directory and default paths.
+	///
+	/// # Arguments
+	///
+	/// * `dir`: The new top-level directory path (either relative to current directory or
+	/// absolute.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// # 	.path()
+	/// # 	.to_str()
+	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// # 	.unwrap();
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.set_top_level_directory(dir);
+	///
+	/// if let Ok(dir) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn set_top_level_directory(&self, dir: &str) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.set_top_level_directory(dir)
+	}
+
+	/// Create a `epic-wallet.toml` configuration file in the top-level directory for the
+	/// specified chain type.
+	/// A custom [`WalletConfig`](../epic_wallet_config/types/struct.WalletConfig.html)
+	/// and/or epic `LoggingConfig` may optionally be provided, otherwise defaults will be used.
+	///
+	/// Paths in the configuration file will be updated to reflect the top level directory, so
+	/// path-related values in the optional configuration structs will be ignored.
+	///
+	/// # Arguments
+	///
+	/// * `chain_type`: The chain type to use in creation of the configuration file. This can be
+	///     * `AutomatedTesting`
+	///     * `UserTesting`
+	///     * `Floonet`
+	///     * `Mainnet`
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// # 	.path()
+	/// # 	.to_str()
+	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// # 	.unwrap();
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let _ = api_owner.set_top_level_directory(dir);
+	///
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	///
+	/// if let Ok(_) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn create_config(
+		&self,
+		chain_type: &global::ChainTypes,
+		wallet_config: Option<WalletConfig>,
+		logging_config: Option<LoggingConfig>,
+		tor_config: Option<TorConfig>,
+		epicbox_config: Option<EpicboxConfig>,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.create_config(
+			chain_type,
+			"epic-wallet.toml",
+			wallet_config,
+			logging_config,
+			tor_config,
+			epicbox_config,
+		)
+	}
+
+	/// Creates a new wallet seed and empty wallet database in the `wallet_data` directory of
+	/// the top level directory.
+	///
+	/// Paths in the configuration file will be updated to reflect the top level directory, so
+	/// path-related values in the optional configuration structs will be ignored.
+	///
+	/// The wallet files must not already exist, and ~The `epic-wallet.toml` file must exist
+	/// in the top level directory (can be created via a call to
+	/// [`create_config`](struct.Owner.html#method.create_config))
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `mnemonic`: If present, restore the wallet seed from the given mnemonic instead of creating
+	/// a new random seed.
+	/// * `mnemonic_length`: Desired length of mnemonic in bytes (16 or 32, either 12 or 24 words).
+	/// Use 0 if mnemonic isn't being used.
+	/// * `language`: Language of the recovery phrase, one of the standard BIP39 wordlists. If
+	/// `None`, the language of the given mnemonic is detected, and a new seed's phrase is in English.
+	/// * `password`: The password used to encrypt/decrypt the `wallet.seed` file
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// note that the WalletInst struct does not necessarily need to contain an
+	///	// instantiated wallet
+	///
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// # 	.path()
+	/// # 	.to_str()
+	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// # 	.unwrap();
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let _ = api_owner.set_top_level_directory(dir);
+	///
+	/// // Create configuration
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	///
+	///	// create new wallet wirh random seed
+	///	let pw = ZeroingString::from("my_password");
+	/// let result = api_owner.create_wallet(None, None, 0, None, pw);
+	///
+	/// if let Ok(r) = result {
+	///		//...
+	/// }
+	/// ```
+
+	pub fn create_wallet(
+		&self,
+		name: Option<&str>,
+		mnemonic: Option<ZeroingString>,
+		mnemonic_length: u32,
+		language: Option<MnemonicLanguage>,
+		password: ZeroingString,
+	) -> Result<(), Error> {
+		self.check_password_policy(&password)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.create_wallet(
+			name,
+			mnemonic,
+			mnemonic_length as usize,
+			language,
+			password,
+			self.doctest_mode,
+		)
+	}
+
+	/// `Opens` a wallet, populating the internal keychain with the encrypted seed, and optionally
+	/// returning a `keychain_mask` token to the caller to provide in all future calls.
+	/// If using a mask, the seed will be stored in-memory XORed against the `keychain_mask`, and
+	/// will not be useable if the mask is not provided.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password to use to open the wallet
+	/// a new random seed.
+	/// * `use_mask`: Whether to create and return a mask which much be provided in all future
+	/// API calls.
+	/// * `scope`: If given and not [`TokenScope::Full`](enum.TokenScope.html), a token standing
+	/// for the mask is returned in its place, which the V3 owner API listener only allows to
+	/// call the methods of the scope, e.g. to hand a monitoring dashboard a token which can
+	/// retrieve the summary info but never send. The tokens are valid until the wallet is
+	/// closed, opening it again standing them for the new mask.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// `ErrorKind::OpenLockedOut` without the password being checked while too many attempts
+	/// failed in a row (see [`set_open_throttle`](struct.Owner.html#method.set_open_throttle)).
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// note that the WalletInst struct does not necessarily need to contain an
+	///	// instantiated wallet
+	/// let dir = "path/to/wallet/dir";
+	///
+	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
+	/// # let dir = dir
+	/// # 	.path()
+	/// # 	.to_str()
+	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
+	/// # 	.unwrap();
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let _ = api_owner.set_top_level_directory(dir);
+	///
+	/// // Create configuration
+	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	///
+	///	// create new wallet wirh random seed
+	///	let pw = ZeroingString::from("my_password");
+	/// let _ = api_owner.create_wallet(None, None, 0, None, pw.clone());
+	///
+	/// let result = api_owner.open_wallet(None, pw, true, None);
+	///
+	/// if let Ok(m) = result {
+	///		// use this mask in all subsequent calls
+	///		let mask = m;
+	/// }
+	/// ```
+
+	pub fn open_wallet(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		use_mask: bool,
+		scope: Option<TokenScope>,
+	) -> Result<Option<SecretKey>, Error> {
+		let scope = scope.filter(|s| *s != TokenScope::Full);
+		// just return a representative string for doctest mode
+		if self.doctest_mode {
+			let secp_inst = static_secp_instance();
+			let secp = secp_inst.lock();
+			let token = match scope {
+				Some(_) => DOCTEST_SCOPED_TOKEN,
+				None => "d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868",
+			};
+			return Ok(Some(SecretKey::from_slice(
+				&secp,
+				&from_hex(token.to_owned()).unwrap(),
+			)?));
+		}
+		let mut throttle = self.open_throttle.lock();
+		if let Some(secs) = throttle.locked_out(Instant::now()) {
+			return Err(ErrorKind::OpenLockedOut(secs).into());
+		}
+		let res = {
+			let mut w_lock = self.wallet_inst.lock();
+			let lc = w_lock.lc_provider()?;
+			lc.open_wallet(name, password, use_mask, self.doctest_mode)
+		};
+		match &res {
+			Ok(mask) => {
+				throttle.succeeded();
+				// the issued tokens stood for the previous mask
+				let mut tokens = self.scoped_tokens.lock();
+				for t in tokens.values_mut() {
+					t.mask = mask.clone();
+				}
+				if let Some(scope) = scope {
+					let secp_inst = static_secp_instance();
+					let secp = secp_inst.lock();
+					let token = SecretKey::new(&secp, &mut thread_rng());
+					tokens.insert(
+						to_hex(token.0.to_vec()),
+						ScopedToken::new(scope, mask.clone()),
+					);
+					return Ok(Some(token));
+				}
+			}
+			Err(_) => {
+				// logged by the status thread
+				if let Some(lockout) = throttle.failed(Instant::now()) {
+					let msg = StatusMessage::OpenLockout(throttle.failures(), lockout.as_secs());
+					if let Some(tx) = self.status_tx.lock().as_ref() {
+						let _ = tx.send(msg);
+					}
+				}
+			}
+		}
+		res
+	}
+
+	/// `Close` a wallet, removing the master seed from memory.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	/// let res = api_owner.close_wallet(None);
+	///
+	/// if let Ok(_) = res {
+	///		// ...
+	/// }
+	/// ```
+
+	pub fn close_wallet(&self, name: Option<&str>) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.close_wallet(name)?;
+		self.page_snapshots.clear();
+		self.scoped_tokens.lock().clear();
+		Ok(())
+	}
+
+	/// Scope of a token issued by [`open_wallet`](struct.Owner.html#method.open_wallet),
+	/// given in hex, and the keychain mask it stands for. `None` for any other token.
+	pub fn token_scope(&self, token: &str) -> Option<(TokenScope, Option<SecretKey>)> {
+		self.scoped_tokens
+			.lock()
+			.get(token)
+			.map(|t| (t.scope.clone(), t.mask.clone()))
+	}
+
+	/// Checks a call made with a token issued by
+	/// [`open_wallet`](struct.Owner.html#method.open_wallet), given in hex, against the
+	/// token's scope. `None` for any other token, else whether the call is allowed and the
+	/// keychain mask the token stands for. The amount of an allowed send only counts
+	/// against a send-limited token's limit once the send succeeded, see
+	/// [`spend_token_limit`](struct.Owner.html#method.spend_token_limit).
+	pub fn check_token_call(
+		&self,
+		token: &str,
+		call: &TokenCall,
+	) -> Option<(bool, Option<SecretKey>)> {
+		let tokens = self.scoped_tokens.lock();
+		let t = tokens.get(token)?;
+		Some((t.allows(call), t.mask.clone()))
+	}
+
+	/// Deducts the amount of a successful send, or finalization of a slate, made with a
+	/// token issued by [`open_wallet`](struct.Owner.html#method.open_wallet) from what's
+	/// left of its limit if it's send-limited. A slate's amount is only deducted once,
+	/// and the transaction it was finalized into can then be posted with the token.
+	///
+	/// # Arguments
+	///
+	/// * `token`: The token, given in hex
+	/// * `amount`: The amount sent
+	/// * `slate_id`: Id of the slate sent or finalized, if known
+	/// * `kernels`: Excesses of the kernels of the transaction finalized, if any
+	pub fn spend_token_limit(
+		&self,
+		token: &str,
+		amount: u64,
+		slate_id: Option<Uuid>,
+		kernels: Vec<String>,
+	) {
+		if let Some(t) = self.scoped_tokens.lock().get_mut(token) {
+			t.spend(amount, slate_id, kernels);
+		}
+	}
+
+	/// Return the BIP39 mnemonic for the given wallet. This function will decrypt
+	/// the wallet's seed file with the given password, and thus does not need the
+	/// wallet to be open.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password used to encrypt the seed file.
+	///
+	/// # Returns
+	/// * Ok(BIP-39 mneminc) if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.get_mnemonic(None, pw);
+	///
+	/// if let Ok(mne) = res {
+	///		// ...
+	/// }
+	/// ```
+	pub fn get_mnemonic(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+	) -> Result<ZeroingString, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.get_mnemonic(name, password)
+	}
+
+	/// Exports the view key of an account of the wallet, from which a watch-only wallet is
+	/// created with [`create_watch_only_wallet`](struct.Owner.html#method.create_watch_only_wallet).
+	/// The wallet's seed file is decrypted with the given password, and the wallet must be
+	/// open to look up the account.
+	///
+	/// Anyone with the view key can see the outputs of the account and their amounts, but
+	/// can't spend them.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password used to encrypt the seed file.
+	/// * `account`: The label of the account, or `None` for the active account.
+	///
+	/// # Returns
+	/// * Ok([`ViewKey`](../epic_wallet_libwallet/struct.ViewKey.html)) if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.export_view_key(None, pw, None);
+	///
+	/// if let Ok(view_key) = res {
+	///		// ...
+	/// }
+	/// ```
+	pub fn export_view_key(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		account: Option<String>,
+	) -> Result<ViewKey, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		let parent_key_id = {
+			let w = lc.wallet_inst()?;
+			match account {
+				Some(label) => match w.get_acct_path(label.clone())? {
+					Some(m) => m.path,
+					None => return Err(ErrorKind::UnknownAccountLabel(label).into()),
+				},
+				None => w.parent_key_id(),
+			}
+		};
+		lc.export_view_key(name, password, &parent_key_id)
+	}
+
+	/// Creates a watch-only wallet in the `wallet_data` directory of the top level directory,
+	/// from the view key of an account exported by
+	/// [`export_view_key`](struct.Owner.html#method.export_view_key). It has no seed file
+	/// nor password: it's opened with any password, and scans the chain for the outputs of
+	/// the account on its first update. It tracks the outputs and balance of the account and
+	/// builds unsigned slates with
+	/// [`init_unsigned_send_tx`](struct.Owner.html#method.init_unsigned_send_tx), but any
+	/// call that needs the wallet's keys fails with `ErrorKind::KeychainDoesntExist`.
+	///
+	/// Spends of the account's outputs are only seen as the outputs leaving the UTXO set,
+	/// without any transaction log entry.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `view_key`: The view key of the account.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered,
+	/// e.g. if a wallet already exists in the directory.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// if let Ok(view_key) = api_owner.export_view_key(None, pw, None) {
+	///		// on the watching host
+	///		let res = api_owner.create_watch_only_wallet(None, view_key);
+	/// }
+	/// ```
+	pub fn create_watch_only_wallet(
+		&self,
+		name: Option<&str>,
+		view_key: ViewKey,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.create_watch_only_wallet(name, view_key)
+	}
+
+	/// Starts a check that the user has backed up the wallet's recovery phrase, returning
+	/// the positions of `count` randomly chosen words of the phrase to ask the user for.
+	/// The answers are then checked with
+	/// [`verify_seed_words`](struct.Owner.html#method.verify_seed_words), which only
+	/// accepts the words at the positions of the last quiz.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password used to encrypt the seed file.
+	/// * `count`: The number of words to ask for, at most the length of the phrase.
+	///
+	/// # Returns
+	/// * Ok(positions) of the words in the phrase, starting from 1, in ascending order
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.get_seed_quiz(None, pw, 4);
+	///
+	/// if let Ok(positions) = res {
+	///		// ask the user for the words at these positions
+	/// }
+	/// ```
+	pub fn get_seed_quiz(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		count: usize,
+	) -> Result<Vec<usize>, Error> {
+		let mnemonic = self.get_mnemonic(name, password)?;
+		let positions = owner::seed_quiz(&mnemonic, count)?;
+		// keep doctest output stable
+		if self.doctest_mode {
+			return Ok((1..=count).collect());
+		}
+		*self.seed_quiz.lock() = Some(positions.clone());
+		Ok(positions)
+	}
+
+	/// Checks words given by the user against the wallet's recovery phrase. If they're
+	/// the words at all the positions returned by the last call to
+	/// [`get_seed_quiz`](struct.Owner.html#method.get_seed_quiz), and only those, the
+	/// wallet records that its recovery phrase has been backed up, as returned by
+	/// [`seed_verified`](struct.Owner.html#method.seed_verified). A quiz can only be
+	/// answered once, whether or not the answer is right.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password used to encrypt the seed file.
+	/// * `words`: The words given by the user at the positions returned by
+	/// [`get_seed_quiz`](struct.Owner.html#method.get_seed_quiz).
+	///
+	/// # Returns
+	/// * Ok(true) if the words answer the quiz and match the recovery phrase, Ok(false)
+	/// otherwise
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	/// use epic_wallet_libwallet::SeedWord;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let positions = api_owner.get_seed_quiz(None, pw.clone(), 1);
+	///
+	///	let words = vec![SeedWord {
+	///		position: 1,
+	///		word: "fat".to_owned(),
+	///	}];
+	/// let res = api_owner.verify_seed_words(None, pw, words);
+	///
+	/// if let Ok(true) = res {
+	///		// ...
+	/// }
+	/// ```
+	pub fn verify_seed_words(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		words: Vec<SeedWord>,
+	) -> Result<bool, Error> {
+		let positions = match self.doctest_mode {
+			true => Some((1..=words.len()).collect()),
+			false => self.seed_quiz.lock().take(),
+		};
+		let positions = match positions {
+			Some(p) => p,
+			None => return Ok(false),
+		};
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		let mnemonic = lc.get_mnemonic(name, password)?;
+		if !owner::check_seed_words(&mnemonic, &positions, &words) {
+			return Ok(false);
+		}
+		lc.set_seed_verified(name, true)?;
+		Ok(true)
+	}
+
+	/// Returns whether the user has confirmed a backup of the wallet's recovery phrase via
+	/// [`verify_seed_words`](struct.Owner.html#method.verify_seed_words). Recovering the
+	/// wallet from a different phrase resets this status.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	///
+	/// # Returns
+	/// * Ok(true) if the recovery phrase has been verified
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	/// let res = api_owner.seed_verified(None);
+	///
+	/// if let Ok(false) = res {
+	///		// remind the user to back up the recovery phrase
+	/// }
+	/// ```
+	pub fn seed_verified(&self, name: Option<&str>) -> Result<bool, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.seed_verified(name)
+	}
+
+	/// Changes a wallet's password, meaning the old seed file is decrypted with the old password,
+	/// and a new seed file is created with the same mnemonic and encrypted with the new password.
+	///
+	/// This function temporarily backs up the old seed file until a test-decryption of the new
+	/// file is confirmed to contain the same seed as the original seed file, at which point the
+	/// backup is deleted. If this operation fails for an unknown reason, the backup file will still
+	/// exist in the wallet's data directory encrypted with the old password.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `old`: The password used to encrypt the existing seed file (i.e. old password)
+	/// * `new`: The password to be used to encrypt the new seed file
+	///
+	/// # Returns
+	/// * Ok(()) if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let old = ZeroingString::from("my_password");
+	///	let new = ZeroingString::from("new_password");
+	/// let res = api_owner.change_password(None, old, new);
+	///
+	/// if let Ok(mne) = res {
+	///		// ...
+	/// }
+	/// ```
+	pub fn change_password(
+		&self,
+		name: Option<&str>,
+		old: ZeroingString,
+		new: ZeroingString,
+	) -> Result<(), Error> {
+		self.check_password_policy(&new)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.change_password(name, old, new)
+	}
+
+	/// Replaces the seed of an existing wallet with the seed restored from the given BIP39
+	/// mnemonic, encrypted with the given password. The existing seed file is backed up in
+	/// the wallet's data directory before being replaced, and the wallet database is kept.
+	///
+	/// The wallet should be closed first via the `close_wallet` function, and a
+	/// [`scan`](struct.Owner.html#method.scan) performed after opening it again to restore
+	/// the outputs belonging to the recovered seed.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `mnemonic`: The BIP39 mnemonic to restore the seed from.
+	/// * `password`: The password used to encrypt the new `wallet.seed` file.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let mnemonic = ZeroingString::from("fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch");
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.recover_from_mnemonic(None, mnemonic, pw);
+	///
+	/// if let Ok(_) = res {
+	///		// ...
+	/// }
+	/// ```
+	pub fn recover_from_mnemonic(
+		&self,
+		_name: Option<&str>,
+		mnemonic: ZeroingString,
+		password: ZeroingString,
+	) -> Result<(), Error> {
+		self.check_password_policy(&password)?;
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.validate_mnemonic(mnemonic.clone())?;
+		lc.recover_from_mnemonic(mnemonic, password)
+	}
+
+	/// Returns a single use token which must be passed to
+	/// [`delete_wallet`](struct.Owner.html#method.delete_wallet) within 5 minutes. The
+	/// wallet password is checked against the seed file before the token is issued, so a
+	/// caller can't delete a wallet it isn't able to open.
+	///
+	/// Requesting a new token invalidates any previously issued one.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `password`: The password used to encrypt the seed file.
+	///
+	/// # Returns
+	/// * Ok(token) if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let res = api_owner.get_delete_token(None, pw);
+	///
+	/// if let Ok(token) = res {
+	///		// confirm with the user, then pass the token to `delete_wallet`
+	/// }
+	/// ```
+	pub fn get_delete_token(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+	) -> Result<String, Error> {
+		if self.doctest_mode {
+			return Ok(DOCTEST_DELETE_TOKEN.to_owned());
+		}
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let lc = w_lock.lc_provider()?;
+			lc.get_mnemonic(name, password)?;
+		}
+		let token = to_hex(thread_rng().gen::<[u8; 16]>().to_vec());
+		let mut lock = self.delete_token.lock();
+		*lock = Some((token.clone(), Instant::now()));
+		Ok(token)
+	}
+
+	/// Deletes a wallet, removing the config file, seed file and all data files.
+	/// Obviously, use with extreme caution and plenty of user warning
+	///
+	/// Highly recommended that the wallet be explicitly closed first via the `close_wallet`
+	/// function.
+	///
+	/// # Arguments
+	///
+	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `confirmation_token`: The token returned by the last call to
+	/// [`get_delete_token`](struct.Owner.html#method.get_delete_token).
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// An `InvalidConfirmationToken` error is returned if the token doesn't match or has expired.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	///	// Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	///	let pw = ZeroingString::from("my_password");
+	/// let token = api_owner.get_delete_token(None, pw);
+	///
+	/// if let Ok(t) = token {
+	///		let res = api_owner.delete_wallet(None, &t);
+	/// }
+	/// ```
+
+	pub fn delete_wallet(&self, name: Option<&str>, confirmation_token: &str) -> Result<(), Error> {
+		let valid = if self.doctest_mode {
+			confirmation_token == DOCTEST_DELETE_TOKEN
+		} else {
+			// tokens are single use, whether or not they match
+			match self.delete_token.lock().take() {
+				Some((t, issued)) => {
+					t == confirmation_token && issued.elapsed() < DELETE_TOKEN_VALIDITY
+				}
+				None => false,
+			}
+		};
+		if !valid {
+			return Err(ErrorKind::InvalidConfirmationToken.into());
+		}
+		let mut w_lock = self.wallet_inst.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.delete_wallet(name)
+	}
+
+	/// Starts a background wallet update thread, which performs the wallet update process
+	/// automatically at the frequency specified.
+	///
+	/// The updater process is as follows:
+	///
+	/// * Reconcile the wallet outputs against the node's current UTXO set, confirming
+	/// transactions if needs be.
+	/// * Look up transactions by kernel in cases where it's necessary (for instance, when
+	/// there are no change outputs for a transaction and transaction status can't be
+	/// inferred from the output state.
+	/// * Incrementally perform a scan of the UTXO set, correcting outputs and transactions
+	/// where their local state differs from what's on-chain. The wallet stores the last
+	/// position scanned, and will scan back 100 blocks worth of UTXOs on each update, to
+	/// correct any differences due to forks or otherwise.
+	///
+	/// Note that an update process can take a long time, particularly when the entire
+	/// UTXO set is being scanned for correctness. The wallet status can be determined by
+	/// calling the [`get_updater_messages`](struct.Owner.html#method.get_updater_messages).
+	///
+	/// # Arguments
+	///
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `frequency`: The frequency at which to call the update process. Note this is
+	/// time elapsed since the last successful update process. If calling via the JSON-RPC
+	/// api, this represents milliseconds.
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	/// use std::time::Duration;
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
+	///
+	/// if let Ok(_) = res {
+	///   // ...
+	/// }
+	/// ```
+
+	pub fn start_updater(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		frequency: Duration,
+	) -> Result<(), Error> {
+		let updater_inner = self.updater.clone();
+		let tx_inner = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let keychain_mask = match keychain_mask {
+			Some(m) => Some(m.clone()),
+			None => None,
+		};
+		let _ = thread::Builder::new()
+			.name("wallet-updater".to_string())
+			.spawn(move || {
+				let u = updater_inner.lock();
+				if let Err(e) = u.run(frequency, keychain_mask, &tx_inner) {
+					error!("Wallet state updater failed with error: {:?}", e);
+				}
+			})?;
+		Ok(())
+	}
+
+	/// Stops the background update thread. If the updater is currently updating, the
+	/// thread will stop after the next update
+	///
+	/// # Arguments
+	///
+	/// * None
+	///
+	/// # Returns
+	/// * Ok if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	/// use std::time::Duration;
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
+	///
+	/// if let Ok(_) = res {
+	///   // ...
+	/// }
+	///
+	/// let res = api_owner.stop_updater();
+	/// ```
+
+	pub fn stop_updater(&self) -> Result<(), Error> {
+		self.updater_running.store(false, Ordering::Relaxed);
+		Ok(())
+	}
+
+	/// Retrieve messages from the updater thread, up to `count` number of messages.
+	/// The resulting array will be ordered newest messages first. The updater will
+	/// store a maximum of 10,000 messages, after which it will start removing the oldest
+	/// messages as newer ones are created.
+	///
+	/// Messages retrieved via this method are removed from the internal queue, so calling
+	/// this function at a specified interval should result in a complete message history.
+	///
+	/// # Arguments
+	///
+	/// * `count` - The number of messages to retrieve.
+	///
+	/// # Returns
+	/// * Ok with a Vec of [`StatusMessage`](../epic_wallet_libwallet/api_impl/owner_updater/enum.StatusMessage.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// use epic_core::global::ChainTypes;
+	///
+	/// use std::time::Duration;
+	///
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
+	///
+	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
+	///
+	/// let messages = api_owner.get_updater_messages(10000);
+	///
+	/// if let Ok(_) = res {
+	///   // ...
+	/// }
+	///
+	/// ```
+
+	pub fn get_updater_messages(&self, count: usize) -> Result<Vec<StatusMessage>, Error> {
+		let mut q = self.updater_messages.lock();
+		let index = q.len().saturating_sub(count);
+		Ok(q.split_off(index))
+	}
+
+	/// Starts the first scan of a wallet restored from its recovery phrase in a background
+	/// thread, if the wallet hasn't been scanned yet. Until the scan completes, queries don't
+	/// refresh the wallet from the node and the update thread waits for it, while its
+	/// progress is given by [`initial_scan_progress`](struct.Owner.html#method.initial_scan_progress)
+	/// and its messages are returned by
+	/// [`get_updater_messages`](struct.Owner.html#method.get_updater_messages).
 	///
 	/// # Arguments
-	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `mnemonic`: If present, restore the wallet seed from the given mnemonic instead of creating
-	/// a new random seed.
-	/// * `mnemonic_length`: Desired length of mnemonic in bytes (16 or 32, either 12 or 24 words).
-	/// Use 0 if mnemonic isn't being used.
-	/// * `password`: The password used to encrypt/decrypt the `wallet.seed` file
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * Ok(true) if the scan was started, Ok(false) if the wallet doesn't need it or it's
+	/// already running
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1525,67 +5975,128 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	///	// note that the WalletInst struct does not necessarily need to contain an
-	///	// instantiated wallet
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.start_initial_scan(None);
 	///
-	/// let dir = "path/to/wallet/dir";
+	/// if let Ok(true) = result {
+	///		let progress = api_owner.initial_scan_progress();
+	///		//...
+	/// }
+	/// ```
+
+	pub fn start_initial_scan(&self, keychain_mask: Option<&SecretKey>) -> Result<bool, Error> {
+		{
+			let mut w_lock = self.wallet_inst.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			match w.init_status()? {
+				WalletInitStatus::InitNeedsScanning => {}
+				_ => return Ok(false),
+			}
+		}
+		{
+			let mut progress = self.initial_scan.lock();
+			if progress.is_some() {
+				return Ok(false);
+			}
+			*progress = Some(0);
+		}
+
+		let (tx, rx) = channel();
+		let status_tx = self.status_tx.lock().clone();
+		let progress = self.initial_scan.clone();
+		let _ = thread::Builder::new()
+			.name("wallet-initial-scan-status".to_string())
+			.spawn(move || {
+				while let Ok(m) = rx.recv() {
+					if let StatusMessage::Scanning(_, percent) = m {
+						let mut p = progress.lock();
+						if p.is_some() {
+							*p = Some(percent);
+						}
+					}
+					if let Some(ref s) = status_tx {
+						let _ = s.send(m);
+					}
+				}
+			})?;
+
+		let wallet_inst = self.wallet_inst.clone();
+		let updater = self.updater.clone();
+		let progress = self.initial_scan.clone();
+		let keychain_mask = keychain_mask.cloned();
+		let _ = thread::Builder::new()
+			.name("wallet-initial-scan".to_string())
+			.spawn(move || {
+				// keeps the update thread from scanning at the same time
+				let _u = updater.lock();
+				let tx = Some(tx);
+				let res =
+					owner::update_wallet_state(wallet_inst, keychain_mask.as_ref(), &tx, false);
+				if let Err(e) = res {
+					error!("Initial scan of the wallet failed with error: {}", e);
+				}
+				*progress.lock() = None;
+			})?;
+		Ok(true)
+	}
+
+	/// Returns how far the initial scan started by
+	/// [`start_initial_scan`](struct.Owner.html#method.start_initial_scan) got.
 	///
-	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
-	/// # let dir = dir
-	/// # 	.path()
-	/// # 	.to_str()
-	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
-	/// # 	.unwrap();
-	/// let api_owner = Owner::new(wallet.clone());
-	/// let _ = api_owner.set_top_level_directory(dir);
+	/// # Arguments
+	/// * None
 	///
-	/// // Create configuration
-	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	/// # Returns
+	/// * The percentage complete of the scan while it runs, `None` once it completed or
+	/// if it isn't running
 	///
-	///	// create new wallet wirh random seed
-	///	let pw = ZeroingString::from("my_password");
-	/// let result = api_owner.create_wallet(None, None, 0, pw);
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// if let Ok(r) = result {
-	///		//...
-	/// }
+	/// let api_owner = Owner::new(wallet.clone());
+	/// assert_eq!(api_owner.initial_scan_progress(), None);
 	/// ```
 
-	pub fn create_wallet(
-		&self,
-		name: Option<&str>,
-		mnemonic: Option<ZeroingString>,
-		mnemonic_length: u32,
-		password: ZeroingString,
-	) -> Result<(), Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.create_wallet(
-			name,
-			mnemonic,
-			mnemonic_length as usize,
-			password,
-			self.doctest_mode,
-		)
+	pub fn initial_scan_progress(&self) -> Option<u8> {
+		*self.initial_scan.lock()
 	}
 
-	/// `Opens` a wallet, populating the internal keychain with the encrypted seed, and optionally
-	/// returning a `keychain_mask` token to the caller to provide in all future calls.
-	/// If using a mask, the seed will be stored in-memory XORed against the `keychain_mask`, and
-	/// will not be useable if the mask is not provided.
+	/// Retrieve the public "addresses" associated with the active account at the
+	/// given derivation path.
+	///
+	/// In this case, an "address" means a Dalek ed25519 public key corresponding to
+	/// a private key derived as follows:
+	///
+	/// e.g. The default parent account is at
+	///
+	/// `m/0/0`
+	///
+	/// With output blinding factors created as
+	///
+	/// `m/0/0/0`
+	/// `m/0/0/1` etc...
+	///
+	/// The corresponding public address derivation path would be at:
+	///
+	/// `m/0/1`
+	///
+	/// With addresses created as:
+	///
+	/// `m/0/1/0`
+	/// `m/0/1/1` etc...
+	///
+	/// Note that these addresses correspond to the public keys used in the addresses
+	/// of TOR hidden services configured by the wallet listener.
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `password`: The password to use to open the wallet
-	/// a new random seed.
-	/// * `use_mask`: Whether to create and return a mask which much be provided in all future
-	/// API calls.
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// * `derivation_index` - The index along the derivation path to retrieve an address for
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * Ok with a DalekPublicKey representing the address
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1595,65 +6106,61 @@ where
 	///
 	/// use epic_core::global::ChainTypes;
 	///
-	///	// note that the WalletInst struct does not necessarily need to contain an
-	///	// instantiated wallet
-	/// let dir = "path/to/wallet/dir";
-	///
-	/// # let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
-	/// # let dir = dir
-	/// # 	.path()
-	/// # 	.to_str()
-	/// # 	.ok_or("Failed to convert tmpdir path to string.".to_owned())
-	/// # 	.unwrap();
-	/// let api_owner = Owner::new(wallet.clone());
-	/// let _ = api_owner.set_top_level_directory(dir);
-	///
-	/// // Create configuration
-	/// let result = api_owner.create_config(&ChainTypes::Mainnet, None, None, None, None);
+	/// use std::time::Duration;
 	///
-	///	// create new wallet wirh random seed
-	///	let pw = ZeroingString::from("my_password");
-	/// let _ = api_owner.create_wallet(None, None, 0, pw.clone());
+	/// // Set up as above
+	/// # let api_owner = Owner::new(wallet.clone());
 	///
-	/// let result = api_owner.open_wallet(None, pw, true);
+	/// let res = api_owner.get_public_address(None, 0);
 	///
-	/// if let Ok(m) = result {
-	///		// use this mask in all subsequent calls
-	///		let mask = m;
+	/// if let Ok(_) = res {
+	///   // ...
 	/// }
+	///
 	/// ```
 
-	pub fn open_wallet(
+	pub fn get_public_address(
 		&self,
-		name: Option<&str>,
-		password: ZeroingString,
-		use_mask: bool,
-	) -> Result<Option<SecretKey>, Error> {
-		// just return a representative string for doctest mode
-		if self.doctest_mode {
-			let secp_inst = static_secp_instance();
-			let secp = secp_inst.lock();
-			return Ok(Some(SecretKey::from_slice(
-				&secp,
-				&from_hex(
-					"d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868".to_owned(),
-				)
-				.unwrap(),
-			)?));
-		}
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.open_wallet(name, password, use_mask, self.doctest_mode)
+		keychain_mask: Option<&SecretKey>,
+		derivation_index: u32,
+	) -> Result<EpicboxAddress, Error> {
+		owner::get_public_address(self.wallet_inst.clone(), keychain_mask, derivation_index)
 	}
 
-	/// `Close` a wallet, removing the master seed from memory.
+	/// Retrieve the public proof "addresses" associated with the active account at the
+	/// given derivation path.
+	///
+	/// In this case, an "address" means a Dalek ed25519 public key corresponding to
+	/// a private key derived as follows:
+	///
+	/// e.g. The default parent account is at
+	///
+	/// `m/0/0`
+	///
+	/// With output blinding factors created as
+	///
+	/// `m/0/0/0`
+	/// `m/0/0/1` etc...
+	///
+	/// The corresponding public address derivation path would be at:
+	///
+	/// `m/0/1`
+	///
+	/// With addresses created as:
+	///
+	/// `m/0/1/0`
+	/// `m/0/1/1` etc...
+	///
+	/// Note that these addresses correspond to the public keys used in the addresses
+	/// of TOR hidden services configured by the wallet listener.
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// * `derivation_index` - The index along the derivation path to retrieve an address for
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * Ok with a DalekPublicKey representing the address
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1663,34 +6170,38 @@ where
 	///
 	/// use epic_core::global::ChainTypes;
 	///
-	///	// Set up as above
+	/// use std::time::Duration;
+	///
+	/// // Set up as above
 	/// # let api_owner = Owner::new(wallet.clone());
 	///
-	/// let res = api_owner.close_wallet(None);
+	/// let res = api_owner.get_public_proof_address(None, 0);
 	///
 	/// if let Ok(_) = res {
-	///		// ...
+	///   // ...
 	/// }
+	///
 	/// ```
 
-	pub fn close_wallet(&self, name: Option<&str>) -> Result<(), Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.close_wallet(name)
+	pub fn get_public_proof_address(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		derivation_index: u32,
+	) -> Result<DalekPublicKey, Error> {
+		owner::get_public_proof_address(self.wallet_inst.clone(), keychain_mask, derivation_index)
 	}
 
-	/// Return the BIP39 mnemonic for the given wallet. This function will decrypt
-	/// the wallet's seed file with the given password, and thus does not need the
-	/// wallet to be open.
+	/// Helper function to convert an Onion v3 address to a payment proof address (essentially
+	/// exctacting and verifying the public key)
 	///
 	/// # Arguments
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `password`: The password used to encrypt the seed file.
+	/// * `address_v3` - An V3 Onion address
 	///
 	/// # Returns
-	/// * Ok(BIP-39 mneminc) if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok(DalekPublicKey) representing the public key associated with the address, if successful
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered
+	/// or the address provided is invalid
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -1699,139 +6210,174 @@ where
 	///
 	/// use epic_core::global::ChainTypes;
 	///
-	///	// Set up as above
+	/// use std::time::Duration;
+	///
+	/// // Set up as above
 	/// # let api_owner = Owner::new(wallet.clone());
 	///
-	///	let pw = ZeroingString::from("my_password");
-	/// let res = api_owner.get_mnemonic(None, pw);
+	/// let res = api_owner.proof_address_from_onion_v3(
+	///  "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid"
+	/// );
 	///
-	/// if let Ok(mne) = res {
-	///		// ...
+	/// if let Ok(_) = res {
+	///   // ...
 	/// }
+	///
+	/// let res = api_owner.stop_updater();
 	/// ```
-	pub fn get_mnemonic(
-		&self,
-		name: Option<&str>,
-		password: ZeroingString,
-	) -> Result<ZeroingString, Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.get_mnemonic(name, password)
+
+	pub fn proof_address_from_onion_v3(&self, address_v3: &str) -> Result<DalekPublicKey, Error> {
+		address::pubkey_from_onion_v3(address_v3)
 	}
 
-	/// Changes a wallet's password, meaning the old seed file is decrypted with the old password,
-	/// and a new seed file is created with the same mnemonic and encrypted with the new password.
+	/// Returns a single, exportable [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
+	/// from a completed transaction within the wallet.
 	///
-	/// This function temporarily backs up the old seed file until a test-decryption of the new
-	/// file is confirmed to contain the same seed as the original seed file, at which point the
-	/// backup is deleted. If this operation fails for an unknown reason, the backup file will still
-	/// exist in the wallet's data directory encrypted with the old password.
+	/// The transaction must have been created with a payment proof, and the transaction must be
+	/// complete in order for a payment proof to be returned. Either the `tx_id` or `tx_slate_id`
+	/// argument must be provided, or the function will return an error.
 	///
 	/// # Arguments
-	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
-	/// * `old`: The password used to encrypt the existing seed file (i.e. old password)
-	/// * `new`: The password to be used to encrypt the new seed file
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node (via the [`NodeClient`](../grin_wallet_libwallet/types/trait.NodeClient.html)
+	/// provided during wallet instantiation). If `false`, the results will
+	/// contain transaction information that may be out-of-date (from the last time
+	/// the wallet's output set was refreshed against the node).
+	/// Note this setting is ignored if the updater process is running via a call to
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
+	/// * `tx_id` - If `Some(i)` return the proof associated with the transaction with id `i`
+	/// * `tx_slate_id` - If `Some(uuid)`, return the proof associated with the transaction with the
+	/// given `uuid`
 	///
 	/// # Returns
-	/// * Ok(()) if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok([PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)) if successful
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
+	/// or the proof is not present or complete
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	///	// Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let update_from_node = true;
+	/// let tx_id = None;
+	/// let tx_slate_id = Some(Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap());
 	///
-	///	let old = ZeroingString::from("my_password");
-	///	let new = ZeroingString::from("new_password");
-	/// let res = api_owner.change_password(None, old, new);
+	/// // Return all TxLogEntries
+	/// let result = api_owner.retrieve_payment_proof(None, update_from_node, tx_id, tx_slate_id);
 	///
-	/// if let Ok(mne) = res {
-	///		// ...
+	/// if let Ok(p) = result {
+	///		//...
 	/// }
 	/// ```
-	pub fn change_password(
+
+	pub fn retrieve_payment_proof(
 		&self,
-		name: Option<&str>,
-		old: ZeroingString,
-		new: ZeroingString,
-	) -> Result<(), Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.change_password(name, old, new)
+		keychain_mask: Option<&SecretKey>,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<PaymentProof, Error> {
+		let tx = {
+			let t = self.status_tx.lock();
+			t.clone()
+		};
+		let refresh_from_node = match self.updating_in_background() {
+			true => false,
+			false => refresh_from_node,
+		};
+		owner::retrieve_payment_proof(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+			tx_id,
+			tx_slate_id,
+		)
 	}
 
-	/// Deletes a wallet, removing the config file, seed file and all data files.
-	/// Obviously, use with extreme caution and plenty of user warning
+	/// Verifies a [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
+	/// This process entails:
 	///
-	/// Highly recommended that the wallet be explicitly closed first via the `close_wallet`
-	/// function.
+	/// * Ensuring the kernel identified by the proof's stored excess commitment exists in the kernel set
+	/// * Reproducing the signed message `amount|kernel_commitment|sender_address`
+	/// * Validating the proof's `recipient_sig` against the message using the recipient's
+	/// address as the public key and
+	/// * Validating the proof's `sender_sig` against the message using the senders's
+	/// address as the public key
 	///
-	/// # Arguments
+	/// This function also checks whether the sender or recipient address belongs to the currently
+	/// open wallet, and returns 2 booleans indicating whether the address belongs to the sender and
+	/// whether the address belongs to the recipient respectively
 	///
-	/// * `name`: Reserved for future use, use `None` for the time being.
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `proof` A [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html))
 	///
 	/// # Returns
-	/// * Ok if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok((bool, bool)) if the proof is valid. The first boolean indicates whether the sender
+	/// address belongs to this wallet, the second whether the recipient address belongs to this
+	/// wallet
+	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
+	/// or the proof is not present or complete
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let update_from_node = true;
+	/// let tx_id = None;
+	/// let tx_slate_id = Some(Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap());
 	///
-	///	// Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
+	/// // Return all TxLogEntries
+	/// let result = api_owner.retrieve_payment_proof(None, update_from_node, tx_id, tx_slate_id);
 	///
-	/// let res = api_owner.delete_wallet(None);
+	/// // The proof will likely be exported as JSON to be provided to another party
 	///
-	/// if let Ok(_) = res {
-	///		// ...
+	/// if let Ok(p) = result {
+	///		let valid = api_owner.verify_payment_proof(None, &p);
+	///		if let Ok(_) = valid {
+	///		  //...
+	///		}
 	/// }
 	/// ```
 
-	pub fn delete_wallet(&self, name: Option<&str>) -> Result<(), Error> {
-		let mut w_lock = self.wallet_inst.lock();
-		let lc = w_lock.lc_provider()?;
-		lc.delete_wallet(name)
+	pub fn verify_payment_proof(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		proof: &PaymentProof,
+	) -> Result<(bool, bool), Error> {
+		owner::verify_payment_proof(self.wallet_inst.clone(), keychain_mask, proof)
 	}
 
-	/// Starts a background wallet update thread, which performs the wallet update process
-	/// automatically at the frequency specified.
-	///
-	/// The updater process is as follows:
-	///
-	/// * Reconcile the wallet outputs against the node's current UTXO set, confirming
-	/// transactions if needs be.
-	/// * Look up transactions by kernel in cases where it's necessary (for instance, when
-	/// there are no change outputs for a transaction and transaction status can't be
-	/// inferred from the output state.
-	/// * Incrementally perform a scan of the UTXO set, correcting outputs and transactions
-	/// where their local state differs from what's on-chain. The wallet stores the last
-	/// position scanned, and will scan back 100 blocks worth of UTXOs on each update, to
-	/// correct any differences due to forks or otherwise.
-	///
-	/// Note that an update process can take a long time, particularly when the entire
-	/// UTXO set is being scanned for correctness. The wallet status can be determined by
-	/// calling the [`get_updater_messages`](struct.Owner.html#method.get_updater_messages).
+	/// Returns the [PaymentProof](../epic_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
+	/// of every transaction of the active account whose proof is complete, so they can be
+	/// archived in one go. Proofs still waiting for the recipient's signature are left out.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `frequency`: The frequency at which to call the update process. Note this is
-	/// time elapsed since the last successful update process. If calling via the JSON-RPC
-	/// api, this represents milliseconds.
+	/// * `refresh_from_node` - If true, the wallet will attempt to contact
+	/// a node (via the [`NodeClient`](../epic_wallet_libwallet/types/trait.NodeClient.html)
+	/// provided during wallet instantiation). If `false`, the results will
+	/// contain transaction information that may be out-of-date (from the last time
+	/// the wallet's output set was refreshed against the node).
+	/// Note this setting is ignored if the updater process is running via a call to
+	/// [`start_updater`](struct.Owner.html#method.start_updater)
 	///
 	/// # Returns
-	/// * Ok if successful
+	/// * `(bool, Vec<PaymentProofEntry>)` - A tuple:
+	/// * The first `bool` element indicates whether the data was successfully
+	/// refreshed from the node (note this may be false even if the `refresh_from_node`
+	/// argument was set to `true`.
+	/// * The second element contains the proofs, each with the id and slate id of its
+	/// transaction
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -1839,160 +6385,229 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let update_from_node = true;
 	///
-	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
+	/// let result = api_owner.retrieve_payment_proofs(None, update_from_node);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok((was_updated, proofs)) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn start_updater(
+	pub fn retrieve_payment_proofs(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		frequency: Duration,
-	) -> Result<(), Error> {
-		let updater_inner = self.updater.clone();
-		let tx_inner = {
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<PaymentProofEntry>), Error> {
+		let tx = {
 			let t = self.status_tx.lock();
 			t.clone()
 		};
-		let keychain_mask = match keychain_mask {
-			Some(m) => Some(m.clone()),
-			None => None,
+		let refresh_from_node = match self.updating_in_background() {
+			true => false,
+			false => refresh_from_node,
 		};
-		let _ = thread::Builder::new()
-			.name("wallet-updater".to_string())
-			.spawn(move || {
-				let u = updater_inner.lock();
-				if let Err(e) = u.run(frequency, keychain_mask, &tx_inner) {
-					error!("Wallet state updater failed with error: {:?}", e);
-				}
-			})?;
-		Ok(())
+		owner::retrieve_payment_proofs(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			&tx,
+			refresh_from_node,
+		)
 	}
 
-	/// Stops the background update thread. If the updater is currently updating, the
-	/// thread will stop after the next update
+	/// Checks the signatures of a [PaymentProof](../epic_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
+	/// against the message `amount|kernel_commitment|sender_address`, as
+	/// [`verify_payment_proof`](struct.Owner.html#method.verify_payment_proof) does, but
+	/// without contacting the node or using the open wallet, so archived proofs can be
+	/// validated offline. It doesn't check the proof's kernel is on chain.
 	///
 	/// # Arguments
-	///
-	/// * None
+	/// * `proof` A [PaymentProof](../epic_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
 	///
 	/// # Returns
-	/// * Ok if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok(()) if both signatures are valid
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an address or
+	/// signature is invalid
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
-	///
-	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let tx_slate_id = Some(Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap());
+	/// let result = api_owner.retrieve_payment_proof(None, true, None, tx_slate_id);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok(p) = result {
+	///		let valid = api_owner.verify_payment_proof_offline(&p);
+	///		if let Ok(_) = valid {
+	///		  //...
+	///		}
 	/// }
-	///
-	/// let res = api_owner.stop_updater();
 	/// ```
 
-	pub fn stop_updater(&self) -> Result<(), Error> {
-		self.updater_running.store(false, Ordering::Relaxed);
-		Ok(())
+	pub fn verify_payment_proof_offline(&self, proof: &PaymentProof) -> Result<(), Error> {
+		owner::verify_payment_proof_signatures(proof)
 	}
 
-	/// Retrieve messages from the updater thread, up to `count` number of messages.
-	/// The resulting array will be ordered newest messages first. The updater will
-	/// store a maximum of 10,000 messages, after which it will start removing the oldest
-	/// messages as newer ones are created.
+	/// Creates a request for a payment of the given amount to this wallet, which can be shared
+	/// with the sender over any channel, e.g. a chat. The request is signed with the payment
+	/// proof address of the active account, so the amount, memo and expiry can't be altered
+	/// without the sender noticing when checking it with
+	/// [`verify_payment_request`](struct.Owner.html#method.verify_payment_request).
 	///
-	/// Messages retrieved via this method are removed from the internal queue, so calling
-	/// this function at a specified interval should result in a complete message history.
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `amount` - The requested amount, in nanoepics
+	/// * `memo` - A memo describing the payment, at most 256 bytes. The sender sends it as their
+	/// message.
+	/// * `expiry` - Time after which the request is no longer valid, if any. It's rounded down to
+	/// whole seconds.
+	///
+	/// # Returns
+	/// * Ok with the signed [`PaymentRequest`](../epic_wallet_libwallet/api_impl/types/struct.PaymentRequest.html)
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the amount is zero,
+	/// the memo too long or the expiry already passed.
+	///
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	///
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.create_payment_request(
+	///		None,
+	///		2_000_000_000,
+	///		Some("Invoice 42".to_owned()),
+	///		None,
+	/// );
+	///
+	/// if let Ok(request) = result {
+	///		// share the request as JSON with the sender
+	/// }
+	/// ```
+
+	pub fn create_payment_request(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		amount: u64,
+		memo: Option<String>,
+		expiry: Option<DateTime<Utc>>,
+	) -> Result<PaymentRequest, Error> {
+		owner::create_payment_request(
+			self.wallet_inst.clone(),
+			keychain_mask,
+			amount,
+			memo,
+			expiry,
+		)
+	}
+
+	/// Checks a payment request received from another wallet, as created by
+	/// [`create_payment_request`](struct.Owner.html#method.create_payment_request), and returns
+	/// the arguments to pay it with [`init_send_tx`](struct.Owner.html#method.init_send_tx).
+	/// The arguments carry the requested amount, the memo as message, and the request's
+	/// address as payment proof recipient, so the payment proof can only be completed by the
+	/// wallet which signed the request. The remaining arguments are the defaults and may be
+	/// adjusted before sending; the address is also the Tor address of the requesting wallet.
 	///
 	/// # Arguments
-	///
-	/// * `count` - The number of messages to retrieve.
+	/// * `request` - The [`PaymentRequest`](../epic_wallet_libwallet/api_impl/types/struct.PaymentRequest.html)
 	///
 	/// # Returns
-	/// * Ok with a Vec of [`StatusMessage`](../epic_wallet_libwallet/api_impl/owner_updater/enum.StatusMessage.html)
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok with the [`InitTxArgs`](../epic_wallet_libwallet/types/struct.InitTxArgs.html) if the
+	/// request's signature is valid and it hasn't expired
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) otherwise.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
-	///
-	/// let res = api_owner.start_updater(None, Duration::from_secs(60));
-	///
-	/// let messages = api_owner.get_updater_messages(10000);
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.create_payment_request(None, 2_000_000_000, None, None);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok(request) = result {
+	///		if let Ok(args) = api_owner.verify_payment_request(&request) {
+	///			let slate = api_owner.init_send_tx(None, args);
+	///			//...
+	///		}
 	/// }
-	///
 	/// ```
 
-	pub fn get_updater_messages(&self, count: usize) -> Result<Vec<StatusMessage>, Error> {
-		let mut q = self.updater_messages.lock();
-		let index = q.len().saturating_sub(count);
-		Ok(q.split_off(index))
+	pub fn verify_payment_request(&self, request: &PaymentRequest) -> Result<InitTxArgs, Error> {
+		owner::verify_payment_request(request)
 	}
 
-	/// Retrieve the public "addresses" associated with the active account at the
-	/// given derivation path.
-	///
-	/// In this case, an "address" means a Dalek ed25519 public key corresponding to
-	/// a private key derived as follows:
-	///
-	/// e.g. The default parent account is at
-	///
-	/// `m/0/0`
-	///
-	/// With output blinding factors created as
+	/// Checks a counterparty's claim about an output, such as a payment they say they made or an
+	/// output they are selling, without having to trust their word or a screenshot. The claim
+	/// gives the output's commitment along with the value and blinding factor it was created
+	/// with; the claim holds if the value and blinding factor open the commitment, and the
+	/// output is unspent on the node's chain with a valid rangeproof.
 	///
-	/// `m/0/0/0`
-	/// `m/0/0/1` etc...
+	/// Note that revealing the blinding factor of an output gives away the ability to spend it,
+	/// so claims should only be made about outputs which are spent immediately after, or for
+	/// which the counterparty is already trusted with the funds.
 	///
-	/// The corresponding public address derivation path would be at:
+	/// # Arguments
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `claim` - The [`OutputClaim`](../epic_wallet_libwallet/api_impl/types/struct.OutputClaim.html)
 	///
-	/// `m/0/1`
+	/// # Returns
+	/// * Ok with the [`OutputClaimVerification`](../epic_wallet_libwallet/api_impl/types/struct.OutputClaimVerification.html),
+	/// whose `is_valid` is true if the claim holds
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the node can't be
+	/// contacted or another error is encountered.
 	///
-	/// With addresses created as:
+	/// # Example
+	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
+	/// ```
+	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use epic_wallet_libwallet::OutputClaim;
 	///
-	/// `m/0/1/0`
-	/// `m/0/1/1` etc...
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let claim: OutputClaim = serde_json::from_str(r#"{
+	///		"commit": "090000000000000000000000000000000000000000000000000000000000000001",
+	///		"value": "2000000000",
+	///		"blinding_factor": "0000000000000000000000000000000000000000000000000000000000000000"
+	/// }"#).unwrap();
+	/// let result = api_owner.verify_output_claim(None, &claim);
+	///
+	/// if let Ok(verification) = result {
+	///		if verification.is_valid() {
+	///			//...
+	///		}
+	/// }
+	/// ```
+
+	pub fn verify_output_claim(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		claim: &OutputClaim,
+	) -> Result<OutputClaimVerification, Error> {
+		owner::verify_output_claim(self.wallet_inst.clone(), keychain_mask, claim)
+	}
+
+	/// Traces where an output of the wallet came from and where it went, from the wallet's
+	/// own records: the transaction that created it (a receive, a coinbase, or the send
+	/// it's the change of) and the one that spent it, along with the outputs spent to create
+	/// it and the change created when spending it. Following the parents of an output, traced
+	/// in turn, gives its history back to the receives and coinbases it originates from.
 	///
-	/// Note that these addresses correspond to the public keys used in the addresses
-	/// of TOR hidden services configured by the wallet listener.
+	/// Works from the output history the wallet keeps, so outputs restored from the seed
+	/// only show the transactions recorded since.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
-	/// * `derivation_index` - The index along the derivation path to retrieve an address for
+	/// being used.
+	/// * `commit` - Commitment of the output
 	///
 	/// # Returns
-	/// * Ok with a DalekPublicKey representing the address
+	/// * Ok with the [`OutputLineage`](../epic_wallet_libwallet/api_impl/types/struct.OutputLineage.html)
+	/// of the output, or `None` if the output isn't one of the wallet's
 	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
@@ -2000,226 +6615,172 @@ where
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
-	///
-	/// let res = api_owner.get_public_address(None, 0);
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_outputs(None, false, false, false, None);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok((_, outputs)) = result {
+	///		for o in outputs {
+	///			let lineage = api_owner.trace_output(None, &o.commit);
+	///			//...
+	///		}
 	/// }
-	///
 	/// ```
 
-	pub fn get_public_address(
+	pub fn trace_output(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		derivation_index: u32,
-	) -> Result<EpicboxAddress, Error> {
-		owner::get_public_address(self.wallet_inst.clone(), keychain_mask, derivation_index)
+		commit: &pedersen::Commitment,
+	) -> Result<Option<OutputLineage>, Error> {
+		owner::trace_output(self.wallet_inst.clone(), keychain_mask, commit)
 	}
 
-	/// Retrieve the public proof "addresses" associated with the active account at the
-	/// given derivation path.
-	///
-	/// In this case, an "address" means a Dalek ed25519 public key corresponding to
-	/// a private key derived as follows:
-	///
-	/// e.g. The default parent account is at
-	///
-	/// `m/0/0`
-	///
-	/// With output blinding factors created as
-	///
-	/// `m/0/0/0`
-	/// `m/0/0/1` etc...
-	///
-	/// The corresponding public address derivation path would be at:
-	///
-	/// `m/0/1`
-	///
-	/// With addresses created as:
-	///
-	/// `m/0/1/0`
-	/// `m/0/1/1` etc...
-	///
-	/// Note that these addresses correspond to the public keys used in the addresses
-	/// of TOR hidden services configured by the wallet listener.
+	/// Locks an output of the wallet against spending: coin selection leaves it out of the
+	/// transactions the wallet builds, and it can't be given as one of their inputs, until
+	/// it's unlocked with [`unlock_output`](struct.Owner.html#method.unlock_output). This
+	/// keeps e.g. the outputs of a cold reserve from being spent by automated payouts. The
+	/// lock is kept in the wallet's store, apart from the status of the output, and isn't
+	/// reflected in the wallet's balances.
 	///
 	/// # Arguments
-	///
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
-	/// * `derivation_index` - The index along the derivation path to retrieve an address for
+	/// being used.
+	/// * `commit` - Commitment of the output
 	///
 	/// # Returns
-	/// * Ok with a DalekPublicKey representing the address
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
+	/// * Ok(()) if the output is locked, or was locked already
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the output
+	/// isn't one of the wallet's or is spent already.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
-	///
-	/// let res = api_owner.get_public_proof_address(None, 0);
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_outputs(None, false, false, false, None);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok((_, outputs)) = result {
+	///		for o in outputs {
+	///			let result = api_owner.lock_output(None, &o.commit);
+	///			//...
+	///		}
 	/// }
-	///
 	/// ```
 
-	pub fn get_public_proof_address(
+	pub fn lock_output(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		derivation_index: u32,
-	) -> Result<DalekPublicKey, Error> {
-		owner::get_public_proof_address(self.wallet_inst.clone(), keychain_mask, derivation_index)
+		commit: &pedersen::Commitment,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_output_frozen(&mut **w, keychain_mask, commit, true)
 	}
 
-	/// Helper function to convert an Onion v3 address to a payment proof address (essentially
-	/// exctacting and verifying the public key)
+	/// Unlocks an output locked with [`lock_output`](struct.Owner.html#method.lock_output),
+	/// making it available to coin selection again as soon as it's spendable.
 	///
 	/// # Arguments
-	///
-	/// * `address_v3` - An V3 Onion address
+	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
+	/// being used.
+	/// * `commit` - Commitment of the output
 	///
 	/// # Returns
-	/// * Ok(DalekPublicKey) representing the public key associated with the address, if successful
-	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered
-	/// or the address provided is invalid
+	/// * Ok(()) if the output is unlocked, or wasn't locked
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if the output
+	/// isn't one of the wallet's.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
-	/// use epic_core::global::ChainTypes;
-	///
-	/// use std::time::Duration;
-	///
-	/// // Set up as above
-	/// # let api_owner = Owner::new(wallet.clone());
-	///
-	/// let res = api_owner.proof_address_from_onion_v3(
-	///  "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid"
-	/// );
+	/// let api_owner = Owner::new(wallet.clone());
+	/// let result = api_owner.retrieve_outputs(None, false, false, false, None);
 	///
-	/// if let Ok(_) = res {
-	///   // ...
+	/// if let Ok((_, outputs)) = result {
+	///		for o in outputs {
+	///			let result = api_owner.unlock_output(None, &o.commit);
+	///			//...
+	///		}
 	/// }
-	///
-	/// let res = api_owner.stop_updater();
 	/// ```
 
-	pub fn proof_address_from_onion_v3(&self, address_v3: &str) -> Result<DalekPublicKey, Error> {
-		address::pubkey_from_onion_v3(address_v3)
+	pub fn unlock_output(
+		&self,
+		keychain_mask: Option<&SecretKey>,
+		commit: &pedersen::Commitment,
+	) -> Result<(), Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::set_output_frozen(&mut **w, keychain_mask, commit, false)
 	}
 
-	/// Returns a single, exportable [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
-	/// from a completed transaction within the wallet.
-	///
-	/// The transaction must have been created with a payment proof, and the transaction must be
-	/// complete in order for a payment proof to be returned. Either the `tx_id` or `tx_slate_id`
-	/// argument must be provided, or the function will return an error.
+	/// Imports outputs exported by another wallet along with their key derivation, e.g. the
+	/// output records of a `db export`, so a wallet can be migrated without scanning the
+	/// whole chain. The commitment of each output is derived from this wallet's seed and
+	/// looked up in the node's UTXO set, and the unspent outputs the wallet doesn't hold yet
+	/// are restored, each with a transaction log entry, as a [`scan`](struct.Owner.html#method.scan)
+	/// would. With only the seed of the other wallet, recover this wallet from it and scan instead.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `refresh_from_node` - If true, the wallet will attempt to contact
-	/// a node (via the [`NodeClient`](../grin_wallet_libwallet/types/trait.NodeClient.html)
-	/// provided during wallet instantiation). If `false`, the results will
-	/// contain transaction information that may be out-of-date (from the last time
-	/// the wallet's output set was refreshed against the node).
-	/// Note this setting is ignored if the updater process is running via a call to
-	/// [`start_updater`](struct.Owner.html#method.start_updater)
-	/// * `tx_id` - If `Some(i)` return the proof associated with the transaction with id `i`
-	/// * `tx_slate_id` - If `Some(uuid)`, return the proof associated with the transaction with the
-	/// given `uuid`
+	/// * `outputs` - The exported outputs, see
+	/// [`ImportedOutput`](../epic_wallet_libwallet/api_impl/types/struct.ImportedOutput.html)
 	///
 	/// # Returns
-	/// * Ok([PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)) if successful
-	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
-	/// or the proof is not present or complete
+	/// * Ok with an [`OutputImportSummary`](../epic_wallet_libwallet/api_impl/types/struct.OutputImportSummary.html)
+	/// of the outputs imported and skipped
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
 	/// ```
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
+	/// use libwallet::ImportedOutput;
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let update_from_node = true;
-	/// let tx_id = None;
-	/// let tx_slate_id = Some(Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap());
-	///
-	/// // Return all TxLogEntries
-	/// let result = api_owner.retrieve_payment_proof(None, update_from_node, tx_id, tx_slate_id);
-	///
-	/// if let Ok(p) = result {
-	///		//...
+	/// let outputs: Vec<ImportedOutput> = serde_json::from_str(r#"[{
+	///		"key_id": "0300000000000000000000000000000000",
+	///		"value": "60000000000",
+	///		"is_coinbase": false
+	/// }]"#).unwrap();
+	/// let result = api_owner.import_outputs(None, outputs);
+	///
+	/// if let Ok(summary) = result {
+	///		// summary.num_imported outputs were restored
+	///		// ...
 	/// }
 	/// ```
 
-	pub fn retrieve_payment_proof(
+	pub fn import_outputs(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		refresh_from_node: bool,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<PaymentProof, Error> {
+		outputs: Vec<ImportedOutput>,
+	) -> Result<OutputImportSummary, Error> {
 		let tx = {
 			let t = self.status_tx.lock();
 			t.clone()
 		};
-		let refresh_from_node = match self.updater_running.load(Ordering::Relaxed) {
-			true => false,
-			false => refresh_from_node,
-		};
-		owner::retrieve_payment_proof(
-			self.wallet_inst.clone(),
-			keychain_mask,
-			&tx,
-			refresh_from_node,
-			tx_id,
-			tx_slate_id,
-		)
+		owner::import_outputs(self.wallet_inst.clone(), keychain_mask, outputs, &tx)
 	}
 
-	/// Verifies a [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html)
-	/// This process entails:
-	///
-	/// * Ensuring the kernel identified by the proof's stored excess commitment exists in the kernel set
-	/// * Reproducing the signed message `amount|kernel_commitment|sender_address`
-	/// * Validating the proof's `recipient_sig` against the message using the recipient's
-	/// address as the public key and
-	/// * Validating the proof's `sender_sig` against the message using the senders's
-	/// address as the public key
-	///
-	/// This function also checks whether the sender or recipient address belongs to the currently
-	/// open wallet, and returns 2 booleans indicating whether the address belongs to the sender and
-	/// whether the address belongs to the recipient respectively
+	/// Removes the extra records of the outputs recorded more than once with the same
+	/// commitment across the wallet's accounts, which restores can leave behind and which
+	/// would otherwise be counted twice. Of the records of such an output, the one kept is
+	/// in the account the output's key was derived in, then with its MMR index known, then
+	/// linked to a transaction, then locked or spent rather than unspent. Wallet updates
+	/// and scans do the same, reporting the outputs deduplicated in their status messages.
 	///
 	/// # Arguments
 	/// * `keychain_mask` - Wallet secret mask to XOR against the stored wallet seed before using, if
 	/// being used.
-	/// * `proof` A [PaymentProof](../grin_wallet_libwallet/api_impl/types/struct.PaymentProof.html))
 	///
 	/// # Returns
-	/// * Ok((bool, bool)) if the proof is valid. The first boolean indicates whether the sender
-	/// address belongs to this wallet, the second whether the recipient address belongs to this
-	/// wallet
-	/// * or [`libwallet::Error`](../grin_wallet_libwallet/struct.Error.html) if an error is encountered
-	/// or the proof is not present or complete
+	/// * Ok with a [`DeduplicatedOutput`](../epic_wallet_libwallet/api_impl/types/struct.DeduplicatedOutput.html)
+	/// for each output which was recorded more than once, with the records kept and removed
+	/// * or [`libwallet::Error`](../epic_wallet_libwallet/struct.Error.html) if an error is encountered.
 	///
 	/// # Example
 	/// Set up as in [`new`](struct.Owner.html#method.new) method above.
@@ -2227,29 +6788,20 @@ where
 	/// # epic_wallet_api::doctest_helper_setup_doc_env!(wallet, wallet_config);
 	///
 	/// let api_owner = Owner::new(wallet.clone());
-	/// let update_from_node = true;
-	/// let tx_id = None;
-	/// let tx_slate_id = Some(Uuid::parse_str("0436430c-2b02-624c-2032-570501212b00").unwrap());
-	///
-	/// // Return all TxLogEntries
-	/// let result = api_owner.retrieve_payment_proof(None, update_from_node, tx_id, tx_slate_id);
-	///
-	/// // The proof will likely be exported as JSON to be provided to another party
+	/// let result = api_owner.deduplicate_outputs(None);
 	///
-	/// if let Ok(p) = result {
-	///		let valid = api_owner.verify_payment_proof(None, &p);
-	///		if let Ok(_) = valid {
-	///		  //...
-	///		}
+	/// if let Ok(dups) = result {
+	///		//...
 	/// }
 	/// ```
 
-	pub fn verify_payment_proof(
+	pub fn deduplicate_outputs(
 		&self,
 		keychain_mask: Option<&SecretKey>,
-		proof: &PaymentProof,
-	) -> Result<(bool, bool), Error> {
-		owner::verify_payment_proof(self.wallet_inst.clone(), keychain_mask, proof)
+	) -> Result<Vec<DeduplicatedOutput>, Error> {
+		let mut w_lock = self.wallet_inst.lock();
+		let w = w_lock.lc_provider()?.wallet_inst()?;
+		owner::deduplicate_outputs(&mut **w, keychain_mask)
 	}
 }
 
@@ -2274,7 +6826,10 @@ macro_rules! doctest_helper_setup_doc_env {
 		use api::{Foreign, Owner};
 		use config::WalletConfig;
 		use impls::{DefaultLCProvider, DefaultWalletImpl, HTTPNodeClient};
-		use libwallet::{BlockFees, InitTxArgs, IssueInvoiceTxArgs, Slate, WalletInst};
+		use libwallet::{
+			BlockFees, InitTxArgs, IssueInvoiceTxArgs, Slate, SwapCurrency, SwapStartArgs,
+			SweepPolicy, TxFilter, TxLogEntryType, WalletInst,
+		};
 		use uuid::Uuid;
 		let dir = tempdir().map_err(|e| format!("{:#?}", e)).unwrap();
 		let dir = dir
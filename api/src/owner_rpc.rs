@@ -19,9 +19,9 @@ use crate::core::core::Transaction;
 use crate::keychain::{Identifier, Keychain};
 use crate::libwallet::slate_versions::v3::TransactionV3;
 use crate::libwallet::{
-	AcctPathMapping, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient, NodeHeightResult,
-	OutputCommitMapping, Slate, SlateVersion, TxLogEntry, VersionedSlate, WalletInfo,
-	WalletLCProvider,
+	AcctPathMapping, ApiVersions, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient,
+	NodeHeightResult, OutputCommitMapping, Slate, SlateVersion, TxLogEntry, VersionedSlate,
+	WalletInfo, WalletLCProvider,
 };
 use crate::util::{from_hex, Mutex};
 use crate::{Owner, OwnerRpcS};
@@ -168,7 +168,8 @@ pub trait OwnerRpc: Sync + Send {
 							"status": "Unspent",
 							"tx_log_entry": 0,
 							"value": "1457920000"
-						}
+						},
+						"output_id": "0482d9c44a063f139214baa66c1438ac"
 					},
 					{
 						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
@@ -184,7 +185,8 @@ pub trait OwnerRpc: Sync + Send {
 							"status": "Unspent",
 							"tx_log_entry": 1,
 							"value": "1457920000"
-						}
+						},
+						"output_id": "588d369f8f21b520b9a186cbaf64f780"
 					}
 				]
 			]
@@ -1269,6 +1271,64 @@ pub trait OwnerRpc: Sync + Send {
 	```
 	 */
 	fn node_height(&self) -> Result<NodeHeightResult, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_api_versions](struct.Owner.html#method.get_api_versions).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_api_versions",
+		"params": [],
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"build_version": "3.4.0",
+				"foreign_api_version": 2,
+				"owner_api_versions": [
+					{
+						"deprecated_methods": [
+							{
+								"method": "check_repair",
+								"replacement": "scan",
+								"status": "Removed"
+							},
+							{
+								"method": "restore",
+								"replacement": "scan",
+								"status": "Removed"
+							}
+						],
+						"endpoint": "/v2/owner",
+						"status": "Deprecated",
+						"version": 2
+					},
+					{
+						"deprecated_methods": [],
+						"endpoint": "/v3/owner",
+						"status": "Supported",
+						"version": 3
+					}
+				]
+			}
+		}
+	}
+	# "#
+	# , false, 0, false, false, false, false);
+	```
+	 */
+	fn get_api_versions(&self) -> Result<ApiVersions, ErrorKind>;
 }
 
 impl<'a, L, C, K> OwnerRpc for Owner<L, C, K>
@@ -1381,6 +1441,10 @@ where
 	fn node_height(&self) -> Result<NodeHeightResult, ErrorKind> {
 		Owner::node_height(self, None).map_err(|e| e.kind())
 	}
+
+	fn get_api_versions(&self) -> Result<ApiVersions, ErrorKind> {
+		Owner::get_api_versions(self).map_err(|e| e.kind())
+	}
 }
 
 /// helper to set up a real environment to run integrated doctests
@@ -1444,8 +1508,15 @@ pub fn run_doctest_owner(
 			>;
 	let lc = wallet1.lc_provider().unwrap();
 	let _ = lc.set_top_level_directory(&format!("{}/wallet1", test_dir));
-	lc.create_wallet(None, Some(rec_phrase_1), 32, empty_string.clone(), false)
-		.unwrap();
+	lc.create_wallet(
+		None,
+		Some(rec_phrase_1),
+		32,
+		None,
+		empty_string.clone(),
+		false,
+	)
+	.unwrap();
 	let mask1 = lc
 		.open_wallet(None, empty_string.clone(), use_token, true)
 		.unwrap();
@@ -1479,8 +1550,15 @@ pub fn run_doctest_owner(
 			>;
 	let lc = wallet2.lc_provider().unwrap();
 	let _ = lc.set_top_level_directory(&format!("{}/wallet2", test_dir));
-	lc.create_wallet(None, Some(rec_phrase_2), 32, empty_string.clone(), false)
-		.unwrap();
+	lc.create_wallet(
+		None,
+		Some(rec_phrase_2),
+		32,
+		None,
+		empty_string.clone(),
+		false,
+	)
+	.unwrap();
 	let mask2 = lc
 		.open_wallet(None, empty_string.clone(), use_token, true)
 		.unwrap();
@@ -1566,6 +1644,7 @@ pub fn run_doctest_owner(
 				&slate,
 				None,
 				None,
+				false,
 				true,
 			)
 			.unwrap();
@@ -13,22 +13,34 @@
 // limitations under the License.
 
 //! JSON-RPC Stub generation for the Owner API
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::config::{EpicboxConfig, TorConfig, WalletConfig};
 use crate::core::core::Transaction;
 use crate::core::global;
+use crate::impls::ListenerStatus;
 use crate::keychain::{Identifier, Keychain};
 use crate::libwallet::slate_versions::v3::TransactionV3;
 use crate::libwallet::{
-	AcctPathMapping, EpicboxAddress, ErrorKind, InitTxArgs, IssueInvoiceTxArgs, NodeClient,
-	NodeHeightResult, OutputCommitMapping, PaymentProof, Slate, SlateVersion, StatusMessage,
-	TxLogEntry, VersionedSlate, WalletInfo, WalletLCProvider,
+	AcctPathMapping, ApiVersions, BlockHeaderInfo, Contact, DeduplicatedOutput, EpicboxAddress,
+	ErrorKind, FeePeriod, FeeReport, ImportedOutput, InitTxArgs, InitTxBatchResult,
+	IssueInvoiceTxArgs, MetadataEntry, MnemonicLanguage, NodeClient, NodeHeightResult,
+	NodeRequestMetrics, OutputClaim, OutputClaimVerification, OutputCommitMapping,
+	OutputImportSummary, OutputLineage, OutputPage, OutputPageQuery, PaymentProof,
+	PaymentProofEntry, PaymentRequest, PinnedKey, ScanJob, SeedWord, SendJob, SendPriority,
+	SendTemplate, SigningRequest, Slate, SlateVersion, StatusMessage, StoredEvent, Swap,
+	SwapMessage, SwapStartArgs, SwapStep, SweepPolicy, TransportMetrics, TxBulkResult, TxEstimate,
+	TxFilter, TxLogEntry, TxPage, TxPageQuery, VersionedSlate, ViewKey, WalletInfo,
+	WalletLCProvider,
 };
 use crate::util::logger::LoggingConfig;
 use crate::util::secp::key::{PublicKey, SecretKey};
-use crate::util::{static_secp_instance, ZeroingString};
-use crate::{ECDHPubkey, Owner, PubAddress, Token};
+use crate::util::secp::pedersen;
+use crate::util::{from_hex, static_secp_instance, ZeroingString};
+use crate::{
+	AuditLogPage, ConfigReload, ECDHPubkey, Owner, PubAddress, RpcMethodMetrics, Token, TokenScope,
+};
 use easy_jsonrpc_mw;
 use rand::thread_rng;
 use std::time::Duration;
@@ -110,6 +122,53 @@ pub trait OwnerRpcS {
 	 */
 	fn create_account_path(&self, token: Token, label: &String) -> Result<Identifier, ErrorKind>;
 
+	/**
+	Networked version of [Owner::set_account_metadata](struct.Owner.html#method.set_account_metadata).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_account_metadata",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"label": "default",
+			"key": "customer_id",
+			"value": "1234"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"label": "default",
+				"path": "0200000000000000000000000000000000",
+				"metadata": {
+					"customer_id": "1234"
+				}
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,true, 0, false, false, false, false);
+	```
+	 */
+	fn set_account_metadata(
+		&self,
+		token: Token,
+		label: &String,
+		key: &String,
+		value: Option<String>,
+	) -> Result<AcctPathMapping, ErrorKind>;
+
 	/**
 	Networked version of [Owner::set_active_account](struct.Owner.html#method.set_active_account).
 
@@ -186,7 +245,8 @@ pub trait OwnerRpcS {
 							"status": "Unspent",
 							"tx_log_entry": 0,
 							"value": "1457920000"
-						}
+						},
+						"output_id": "0482d9c44a063f139214baa66c1438ac"
 					},
 					{
 						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
@@ -202,7 +262,8 @@ pub trait OwnerRpcS {
 							"status": "Unspent",
 							"tx_log_entry": 1,
 							"value": "1457920000"
-						}
+						},
+						"output_id": "588d369f8f21b520b9a186cbaf64f780"
 					}
 				]
 			]
@@ -220,6 +281,80 @@ pub trait OwnerRpcS {
 		tx_id: Option<u32>,
 	) -> Result<(bool, Vec<OutputCommitMapping>), ErrorKind>;
 
+	/**
+	Networked version of [Owner::retrieve_outputs_page](struct.Owner.html#method.retrieve_outputs_page).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_outputs_page",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"include_spent": false,
+			"refresh_from_node": true,
+			"tx_id": null,
+			"query": {
+				"offset": 0,
+				"limit": 1,
+				"sort_by": "Height",
+				"descending": true,
+				"snapshot": null
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": [
+				true,
+				{
+					"total": 2,
+					"outputs": [
+						{
+							"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
+							"output": {
+								"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
+								"height": "2",
+								"is_coinbase": true,
+								"key_id": "0300000000000000000000000100000000",
+								"lock_height": "5",
+								"mmr_index": null,
+								"n_child": 1,
+								"root_key_id": "0200000000000000000000000000000000",
+								"status": "Unspent",
+								"tx_log_entry": 1,
+								"value": "1457920000"
+							},
+							"output_id": "588d369f8f21b520b9a186cbaf64f780"
+						}
+					],
+					"snapshot": "5b0f4de2-8c7a-4e19-b3f6-0a9d2c6e1f74"
+				}
+			]
+		}
+	}
+	# "#
+	# , true, 2, false, false, false, false);
+	```
+	*/
+	fn retrieve_outputs_page(
+		&self,
+		token: Token,
+		include_spent: bool,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		query: OutputPageQuery,
+	) -> Result<(bool, OutputPage), ErrorKind>;
+
 	/**
 	Networked version of [Owner::retrieve_txs](struct.Owner.html#method.retrieve_txs).
 
@@ -307,7 +442,7 @@ pub trait OwnerRpcS {
 	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind>;
 
 	/**
-	Networked version of [Owner::retrieve_summary_info](struct.Owner.html#method.retrieve_summary_info).
+	Networked version of [Owner::retrieve_txs_page](struct.Owner.html#method.retrieve_txs_page).
 
 	# Json rpc example
 
@@ -316,11 +451,16 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "retrieve_summary_info",
+		"method": "retrieve_txs_page",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
 			"refresh_from_node": true,
-			"minimum_confirmations": 1
+			"query": {
+				"offset": 0,
+				"limit": 1,
+				"descending": true,
+				"snapshot": null
+			}
 		},
 		"id": 1
 	}
@@ -328,39 +468,53 @@ pub trait OwnerRpcS {
 	# ,
 	# r#"
 	{
-	"id": 1,
+		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
 			"Ok": [
 				true,
 				{
-					"amount_awaiting_confirmation": "0",
-					"amount_awaiting_finalization": "0",
-					"amount_currently_spendable": "1457920000",
-					"amount_immature": "4373760000",
-					"amount_locked": "0",
-					"last_confirmed_height": "4",
-					"minimum_confirmations": "1",
-					"total": "5831680000"
+					"total": 2,
+					"txs": [
+						{
+							"amount_credited": "1457920000",
+							"amount_debited": "0",
+							"confirmation_ts": "2019-01-15T16:01:26Z",
+							"confirmed": true,
+							"creation_ts": "2019-01-15T16:01:26Z",
+							"fee": null,
+							"id": 1,
+							"kernel_excess": "08bae42ff7d5fa5aca058fd0889dd1e40df16bf3ee2eea6e5db720c0a6d638a7f8",
+							"kernel_lookup_min_height": 2,
+							"messages": null,
+							"num_inputs": 0,
+							"num_outputs": 1,
+							"parent_key_id": "0200000000000000000000000000000000",
+							"stored_tx": null,
+							"ttl_cutoff_height": null,
+							"payment_proof": null,
+							"tx_slate_id": null,
+							"tx_type": "ConfirmedCoinbase"
+						}
+					],
+					"snapshot": "5b0f4de2-8c7a-4e19-b3f6-0a9d2c6e1f74"
 				}
-
 			]
 		}
 	}
 	# "#
-	# ,true, 4, false, false, false, false);
+	# , true, 2, false, false, false, false);
 	```
-	 */
-
-	fn retrieve_summary_info(
+	*/
+	fn retrieve_txs_page(
 		&self,
 		token: Token,
 		refresh_from_node: bool,
-		minimum_confirmations: u64,
-	) -> Result<(bool, WalletInfo), ErrorKind>;
+		query: TxPageQuery,
+	) -> Result<(bool, TxPage), ErrorKind>;
 
 	/**
-	Networked version of [Owner::init_send_tx](struct.Owner.html#method.init_send_tx).
+	Networked version of [Owner::retrieve_txs_by_tag](struct.Owner.html#method.retrieve_txs_by_tag).
 
 	# Json rpc example
 
@@ -369,22 +523,11 @@ pub trait OwnerRpcS {
 		# r#"
 		{
 			"jsonrpc": "2.0",
-			"method": "init_send_tx",
+			"method": "retrieve_txs_by_tag",
 			"params": {
 				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-				"args": {
-					"src_acct_name": null,
-					"amount": "60000000",
-					"minimum_confirmations": 2,
-					"max_outputs": 500,
-					"num_change_outputs": 1,
-					"selection_strategy_is_use_all": true,
-					"message": "my message",
-					"target_slate_version": null,
-					"payment_proof_recipient_address": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb",
-					"ttl_blocks": null,
-					"send_args": null
-				}
+				"refresh_from_node": true,
+				"tags": ["mining"]
 			},
 			"id": 1
 		}
@@ -392,77 +535,29 @@ pub trait OwnerRpcS {
 		# ,
 		# r#"
 		{
-	  "id": 1,
-	  "jsonrpc": "2.0",
-	  "result": {
-		"Ok": {
-		  "amount": "60000000",
-		  "fee": "800000",
-		  "height": "4",
-		  "id": "0436430c-2b02-624c-2032-570501212b00",
-		  "lock_height": "0",
-		  "num_participants": 2,
-
-		  "participant_data": [
-			{
-			  "id": "0",
-			  "message": "my message",
-			  "message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bea065fb74c27c31d611427ac5fa1459d1db340d7475e2967f19e2fa95687d88c",
-			  "part_sig": null,
-			  "public_blind_excess": "039fbac4782fa1600aa704c38073eece85e3a085a90446ded19a9fec90e432b330",
-			  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": [
+					true,
+					[]
+				]
 			}
-		  ],
-		  "payment_proof": {
-			  "receiver_address": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb",
-			  "receiver_signature": null,
-			  "sender_address": "32cdd63928854f8b2628b1dce4626ddcdf35d56cb7cfdf7d64cca5822b78d4d3"
-			},
-			"ttl_cutoff_height": null,
-		  "tx": {
-			"body": {
-			"inputs": [
-			  {
-				"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
-				"features": "Coinbase"
-			  }
-			],
-			"kernels": [
-			  {
-				"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-				"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-				"features": "Plain",
-				"fee": "800000",
-				"lock_height": "0"
-			  }
-			],
-			"outputs": [
-			  {
-				"commit": "0832ca73c2049ee0c8f555c6297aa3658eb3f8ce711dfd63d6d5234cf3191c7756",
-				"features": "Plain",
-				"proof": "1393386202b2ba345e131efb1dfc3730c000ee6b6f3cb8d56e3d0680796b11940abeaed75e18458b544d28765b530a7ca2ed170b42c8bd5e07d4451b0cfa59c20fee43c9107091a328a9f745a255702ea17ac50f5e92b2daf51a7851e8ffde5e11a6837b5b6058aee5ab1e68bcd756646f36b38c6262aa20ff9c194d2e3e155b608216186fee66ef8ca269d31a015c63cca3cf5f0d40e4cccd13d390ed1aeed7a7c7d0709e9b509844462098d97e983ba6963ee3a68955d5317ecec47caeb8911784598be71d319ba8441c36388bf4a10ba2538f4f54f648b22a8f1e1f713dec36376936100b0fc5fb5ad4f51811ec96a76b564c3ee08305f5a2ad79a80152a03eb86d4dcd854a23818621d80771ceb169e45a692b45db77667beecd545e08b8afe8f8a3d049ae18e1cee5522769cd6b0131e036ee81d70df4cd679959fd82684bf9e1f4784325ef271eec2fb73ef4a9569fd76f7b1e55d8e2e87a5daa5ad5357cb401af13c2c695afc4b6a8a2004da1b0f5ebe7cb70cb2e15f0f3ca41eaca969abcb452f7a15fe9d004e66ff646e423366713632f1dedcb33bac1abbc47f1cf2b280f04cf85a7291bb4ecb2c1c252d65e933f5819ba4984b1018ec11ae36d2445af56900b9b6e746f84ddd6b06baab9d7c8f82f0b0bc7a61ade6eabe762ac0d3afe4b2102518361a9e54a4d9d51e4a25ccf1d40c36f6444d2271d03d91eb0f1f6895345c8758a7375926cf0ab75212ef7b4a0efa59a31decd995be2933e3da51efec22365521b8942f997789f9618cbbb422607c2414fc64bc558eca27df5fe7156954b98335a5cc63e6bfe7e076149c93e2314dd626f48bf6721b506b81962b6ca81bff28c7e216f49fcbf989045f97452f3b4ccdcaa7ca5a4ce0bd3f5e16440c6c0b73a42bfa6cfe8e31265b73b81b81c2d54e4f7aefb16ebfa1273adbfd57c08a6"
-			  }
-			]
-			},
-			"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-		  },
-		  "version_info": {
-				"orig_version": 3,
-				"version": 3,
-				"block_header_version": 6
-		  }
 		}
-	  }
-	}
 		# "#
-		# ,true, 4, false, false, false, false);
+		# , true, 2, false, false, false, false);
 	```
 	*/
 
-	fn init_send_tx(&self, token: Token, args: InitTxArgs) -> Result<VersionedSlate, ErrorKind>;
+	fn retrieve_txs_by_tag(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		tags: Vec<String>,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind>;
 
 	/**
-	Networked version of [Owner::issue_invoice_tx](struct.Owner.html#method.issue_invoice_tx).
+	Networked version of [Owner::update_tx_tags](struct.Owner.html#method.update_tx_tags).
 
 	# Json rpc example
 
@@ -471,15 +566,13 @@ pub trait OwnerRpcS {
 		# r#"
 		{
 			"jsonrpc": "2.0",
-			"method": "issue_invoice_tx",
+			"method": "update_tx_tags",
 			"params": {
 				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-				"args": {
-					"amount": "60000000",
-					"message": "Please give me your epics",
-					"dest_acct_name": null,
-					"target_slate_version": null
-				}
+				"tx_id": 1,
+				"tx_slate_id": null,
+				"add": ["Mining", "payroll"],
+				"remove": []
 			},
 			"id": 1
 		}
@@ -490,68 +583,25 @@ pub trait OwnerRpcS {
 			"id": 1,
 			"jsonrpc": "2.0",
 			"result": {
-				"Ok": {
-					"amount": "60000000",
-					"fee": "0",
-					"height": "4",
-					"id": "0436430c-2b02-624c-2032-570501212b00",
-					"lock_height": "0",
-					"ttl_cutoff_height": null,
-					"num_participants": 2,
-					"payment_proof": null,
-					"participant_data": [
-						{
-							"id": "1",
-							"message": "Please give me your epics",
-							"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bb06e3894e0db51e6015d2181f101d06722094128dbc316f7186b57edd68731cb",
-							"part_sig": null,
-							"public_blind_excess": "035ca9d2d82e0e31bc2add7ef9200066b257d9f72cb16c0e57455277b90e2b3503",
-							"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-						}
-					],
-					"tx": {
-						"body": {
-							"inputs": [],
-							"kernels": [
-								{
-									"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-									"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-									"features": "Plain",
-									"fee": "0",
-									"lock_height": "0"
-								}
-							],
-							"outputs": [
-								{
-									"commit": "083fe68fb96a0941f70e9412b4483621326abee9eccf10e7a7efef2a9b4e97df25",
-									"features": "Plain",
-									"proof": "2df8ba188baa701a7c07e23de108e8318797ba029319b4557db3c1c8af917f6361d9871520e33423131d5771b06566aba2469f1e9fcc8eda8203a5c241759e45041af26939c57946372bf330d09670eb30a08cae27b3015dac2efb518fbe2325487d8417f4014cb854aec9e4e1770d2f5d7a6e32e2bc904245505ebd952eda10b5c401f315a8cb969da4cc2dc1e656f33d870ca07ffbc45f58cae9f28b836d4bf3c1786b805ba9d8789cc211998981a8b4115aa7383ddbd10e656fc0a590c3e2cf8dd07e414217d9a1d1af32bcbd933448d0a89033cb93ba2eb0d3b973136d61ee7f109d0476ed3475b7328eff5e9d3362b5db4621682a443a382f7ef09304f9bff422885d23f62f9d7d1a9bbf888e5ba5678e347182770cbbf41cfb3002269607f085881ce0f0df01f34f34433ef04dd6008f9a0c13e47e6e386d62151386dfd20bdf812ae2fb580edc38f38bfc9cc543d1023889ee646d4e75a7382caa3bb00b970062ffdc1fc643ce56d25e2e73b556162c8441d5a667b36b840cc244f69395b46900dd1edc562ed741c239804588e94c071b621766b55f738802c376012fa577e0d82bdf7bf2f229a867d91ed177bacde44faadb6901066f84e21a5fb0b73ed7ef9ef4a1e2c65e6a28a0ae834a99ed1694889d885fc8e90c8e7507078603a9705cc3c57b8b0125ad385cb5ec564f9ca69b530307d91ef2c6bb49a39e30d9e68f2f67d99915d87d1a7776f4c0b61913ea661ebe320b8e99919c69d9dbdc527e787d46e772da9ab9f9cc60e43b41fb0981b6b882ed7a535451158c711210fe25e68d12719192c3d332aea9e047a0f7a292b8e6f13fd76ed47afbadf070392cc3f4a4ebb8ec9853587e30ad9b9794717c87bf962e2ab99ec543f5a24efda0cfc2bf51f23c8132aee6058189925febe1d9d3a145f580ef9835db3c1f3b6e97bd36331e"
-								}
-							]
-						},
-						"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-					},
-					"version_info": {
-						"orig_version": 3,
-						"version": 3,
-						"block_header_version": 6
-					}
-				}
+				"Ok": ["mining", "payroll"]
 			}
 		}
 		# "#
-		# ,true, 4, false, false, false, false);
+		# , true, 2, false, false, false, false);
 	```
 	*/
 
-	fn issue_invoice_tx(
+	fn update_tx_tags(
 		&self,
 		token: Token,
-		args: IssueInvoiceTxArgs,
-	) -> Result<VersionedSlate, ErrorKind>;
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+		add: Vec<String>,
+		remove: Vec<String>,
+	) -> Result<Vec<String>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::process_invoice_tx](struct.Owner.html#method.process_invoice_tx).
+	Networked version of [Owner::retrieve_tx_tags](struct.Owner.html#method.retrieve_tx_tags).
 
 	# Json rpc example
 
@@ -560,69 +610,85 @@ pub trait OwnerRpcS {
 		# r#"
 		{
 			"jsonrpc": "2.0",
-			"method": "process_invoice_tx",
+			"method": "retrieve_tx_tags",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": []
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_tx_tags(&self, token: Token) -> Result<Vec<String>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_contact_txs](struct.Owner.html#method.retrieve_contact_txs).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "retrieve_contact_txs",
 			"params": {
 				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-				"slate": {
-					"amount": "60000000",
-					"fee": "0",
-					"height": "4",
-					"id": "0436430c-2b02-624c-2032-570501212b00",
-					"lock_height": "0",
-					"ttl_cutoff_height": null,
-					"num_participants": 2,
-					"payment_proof": null,
-					"participant_data": [
-						{
-							"id": "1",
-							"message": "Please give me your epics",
-							"message_sig": "1b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078fd2599ab38942986602e943f684a85992893a6d34367dc7cc2b403a5dcfcdbcd9",
-							"part_sig": null,
-							"public_blind_excess": "028e95921cc0d5be5922362265d352c9bdabe51a9e1502a3f0d4a10387f1893f40",
-							"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-						}
-					],
-					"tx": {
-						"body": {
-							"inputs": [],
-							"kernels": [
-								{
-									"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-									"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-									"features": "Plain",
-									"fee": "0",
-									"lock_height": "0"
-								}
-							],
-							"outputs": [
-								{
-									"commit": "09cf47204446c326e361a1a92f34b174deff732daaedb80d7339fbe3db5ca2f6ba",
-									"features": "Plain",
-									"proof": "8f511614315626b5f39224482351d766f5a8ef136262befc050d839be8479b0a13470cd88f4436346d213d83847a4055c6e0ac63681556470349a1aab47034a3015eb64d8163955998e2dd4165dd24386b1e279974b05deb5d46ba2bc321f7000c0784f8f10690605ffe717119d045e02b141ed12d8fc6d20930483a8af889ef533495eb442fcff36d98ebc104f13fc645c28431b3296e4a11f7c991ff97f9abbc2f8886762d7f29fdacb31d52c6850e6ccf5386117d89e8ea4ca3071c56c218dd5d3bcd65f6c06ed9f51f848507ca1d594f41796d1cf99f68a5c3f0c5dd9873602284cff31269b102fcc6c68607565faaf0adb04ed4ff3ea5d41f3b5235ac6cb90e4046c808c9c48c27172c891b20085c56a99913ef47fd8b3dc4920cef50534b9319a7cefe0df10a0206a634ac837e11da92df83ff58b1a14de81313400988aa48b946fcbe1b81f0e79e13f7c6c639b1c10983b424bda08d0ce593a20f1f47e0aa01473e7144f116b76d9ebc60599053d8f1542d60747793d99064e51fce8f8866390325d48d6e8e3bbdbc1822c864303451525c6cb4c6902f105a70134186fb32110d8192fc2528a9483fc8a4001f4bdeab1dd7b3d1ccb9ae2e746a78013ef74043f0b2436f0ca49627af1768b7c791c669bd331fd18c16ef88ad0a29861db70f2f76f3e74fde5accb91b73573e31333333223693d6fbc786e740c085e4fc6e7bde0a3f54e9703f816c54f012d3b1f41ec4d253d9337af61e7f1f1383bd929421ac346e3d2771dfee0b60503b33938e7c83eb37af3b6bf66041a3519a2b4cb557b34e3b9afcf95524f9a011425a34d32e7b6e9f255291094930acae26e8f7a1e4e6bc405d0f88e919f354f3ba85356a34f1aba5f7da1fad88e2692f4129cc1fb80a2122b2d996c6ccf7f08d8248e511d92af9ce49039de728848a2dc74101f4e94a"
-								}
-							]
-						},
-						"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-					},
-					"version_info": {
-						"orig_version": 3,
-						"version": 3,
-						"block_header_version": 6
-					}
-				},
-				"args": {
-					"src_acct_name": null,
-					"amount": "0",
-					"minimum_confirmations": 2,
-					"max_outputs": 500,
-					"num_change_outputs": 1,
-					"selection_strategy_is_use_all": true,
-					"message": "Ok, here are your epics",
-					"target_slate_version": null,
-					"payment_proof_recipient_address": null,
-					"ttl_blocks": null,
-					"send_args": null
+				"refresh_from_node": true,
+				"name": "alice"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Err": {
+					"UnknownContact": "alice"
 				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_contact_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		name: String,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_pending_receives](struct.Owner.html#method.retrieve_pending_receives).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "retrieve_pending_receives",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"refresh_from_node": false
 			},
 			"id": 1
 		}
@@ -630,90 +696,3112 @@ pub trait OwnerRpcS {
 		# ,
 		# r#"
 		{
-		"id": 1,
-		"jsonrpc": "2.0",
-		"result": {
-			"Ok": {
-				"amount": "60000000",
-				"fee": "800000",
-				"height": "4",
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"lock_height": "0",
-				"ttl_cutoff_height": null,
-				"num_participants": 2,
-				"payment_proof": null,
-				"participant_data": [
-					{
-						"id": "1",
-						"message": "Please give me your epics",
-						"message_sig": "1b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078fd2599ab38942986602e943f684a85992893a6d34367dc7cc2b403a5dcfcdbcd9",
-						"part_sig": null,
-						"public_blind_excess": "028e95921cc0d5be5922362265d352c9bdabe51a9e1502a3f0d4a10387f1893f40",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-					},
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": [
+					false,
+					[]
+				]
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_pending_receives(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind>;
+
+	/**
+	Networked version of [Owner::fee_report](struct.Owner.html#method.fee_report).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "fee_report",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"refresh_from_node": false,
+				"period": "Month"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": [
+					false,
 					{
-						"id": "0",
-						"message": "Ok, here are your epics",
-						"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bec8c1cac6cb5770a3c62c9bb95063581cc08bfccd72dac72be8ec4ba5374a9f3",
-						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bcec20532cbe7ce0a3152b61566785684fea3534b7f834f02f733fa524123ee54",
-						"public_blind_excess": "02802124f21ba02769a3f05ecfe9662e8783fa0bd1a7b7d63cf3aea0ebc0d7af3a",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+						"accounts": [],
+						"fees": {
+							"average_fee": "0",
+							"largest_fee": "0",
+							"num_txs": 0,
+							"total_fees": "0"
+						},
+						"largest": [],
+						"period": "Month"
 					}
-				],
-				"tx": {
-					"body": {
-						"inputs": [
-							{
-								"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
-								"features": "Coinbase"
-							}
-						],
-						"kernels": [
-							{
-								"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-								"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-								"features": "Plain",
-								"fee": "800000",
-								"lock_height": "0"
-							}
-						],
-						"outputs": [
-							{
-								"commit": "0832ca73c2049ee0c8f555c6297aa3658eb3f8ce711dfd63d6d5234cf3191c7756",
-								"features": "Plain",
-								"proof": "1393386202b2ba345e131efb1dfc3730c000ee6b6f3cb8d56e3d0680796b11940abeaed75e18458b544d28765b530a7ca2ed170b42c8bd5e07d4451b0cfa59c20fee43c9107091a328a9f745a255702ea17ac50f5e92b2daf51a7851e8ffde5e11a6837b5b6058aee5ab1e68bcd756646f36b38c6262aa20ff9c194d2e3e155b608216186fee66ef8ca269d31a015c63cca3cf5f0d40e4cccd13d390ed1aeed7a7c7d0709e9b509844462098d97e983ba6963ee3a68955d5317ecec47caeb8911784598be71d319ba8441c36388bf4a10ba2538f4f54f648b22a8f1e1f713dec36376936100b0fc5fb5ad4f51811ec96a76b564c3ee08305f5a2ad79a80152a03eb86d4dcd854a23818621d80771ceb169e45a692b45db77667beecd545e08b8afe8f8a3d049ae18e1cee5522769cd6b0131e036ee81d70df4cd679959fd82684bf9e1f4784325ef271eec2fb73ef4a9569fd76f7b1e55d8e2e87a5daa5ad5357cb401af13c2c695afc4b6a8a2004da1b0f5ebe7cb70cb2e15f0f3ca41eaca969abcb452f7a15fe9d004e66ff646e423366713632f1dedcb33bac1abbc47f1cf2b280f04cf85a7291bb4ecb2c1c252d65e933f5819ba4984b1018ec11ae36d2445af56900b9b6e746f84ddd6b06baab9d7c8f82f0b0bc7a61ade6eabe762ac0d3afe4b2102518361a9e54a4d9d51e4a25ccf1d40c36f6444d2271d03d91eb0f1f6895345c8758a7375926cf0ab75212ef7b4a0efa59a31decd995be2933e3da51efec22365521b8942f997789f9618cbbb422607c2414fc64bc558eca27df5fe7156954b98335a5cc63e6bfe7e076149c93e2314dd626f48bf6721b506b81962b6ca81bff28c7e216f49fcbf989045f97452f3b4ccdcaa7ca5a4ce0bd3f5e16440c6c0b73a42bfa6cfe8e31265b73b81b81c2d54e4f7aefb16ebfa1273adbfd57c08a6"
-							},
-							{
-								"commit": "09cf47204446c326e361a1a92f34b174deff732daaedb80d7339fbe3db5ca2f6ba",
-								"features": "Plain",
-								"proof": "8f511614315626b5f39224482351d766f5a8ef136262befc050d839be8479b0a13470cd88f4436346d213d83847a4055c6e0ac63681556470349a1aab47034a3015eb64d8163955998e2dd4165dd24386b1e279974b05deb5d46ba2bc321f7000c0784f8f10690605ffe717119d045e02b141ed12d8fc6d20930483a8af889ef533495eb442fcff36d98ebc104f13fc645c28431b3296e4a11f7c991ff97f9abbc2f8886762d7f29fdacb31d52c6850e6ccf5386117d89e8ea4ca3071c56c218dd5d3bcd65f6c06ed9f51f848507ca1d594f41796d1cf99f68a5c3f0c5dd9873602284cff31269b102fcc6c68607565faaf0adb04ed4ff3ea5d41f3b5235ac6cb90e4046c808c9c48c27172c891b20085c56a99913ef47fd8b3dc4920cef50534b9319a7cefe0df10a0206a634ac837e11da92df83ff58b1a14de81313400988aa48b946fcbe1b81f0e79e13f7c6c639b1c10983b424bda08d0ce593a20f1f47e0aa01473e7144f116b76d9ebc60599053d8f1542d60747793d99064e51fce8f8866390325d48d6e8e3bbdbc1822c864303451525c6cb4c6902f105a70134186fb32110d8192fc2528a9483fc8a4001f4bdeab1dd7b3d1ccb9ae2e746a78013ef74043f0b2436f0ca49627af1768b7c791c669bd331fd18c16ef88ad0a29861db70f2f76f3e74fde5accb91b73573e31333333223693d6fbc786e740c085e4fc6e7bde0a3f54e9703f816c54f012d3b1f41ec4d253d9337af61e7f1f1383bd929421ac346e3d2771dfee0b60503b33938e7c83eb37af3b6bf66041a3519a2b4cb557b34e3b9afcf95524f9a011425a34d32e7b6e9f255291094930acae26e8f7a1e4e6bc405d0f88e919f354f3ba85356a34f1aba5f7da1fad88e2692f4129cc1fb80a2122b2d996c6ccf7f08d8248e511d92af9ce49039de728848a2dc74101f4e94a"
-							}
-						]
-					},
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-				},
-				"version_info": {
-					"orig_version": 3,
-					"version": 3,
-					"block_header_version": 6
-				}
+				]
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn fee_report(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		period: FeePeriod,
+	) -> Result<(bool, FeeReport), ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_contacts](struct.Owner.html#method.retrieve_contacts).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "retrieve_contacts",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": []
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_contacts(&self, token: Token) -> Result<Vec<Contact>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::save_contact](struct.Owner.html#method.save_contact).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "save_contact",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"contact": {
+					"name": " alice ",
+					"proof_address": "2A6AT2OBTO3UVKPKITQP4WXCG6U36QF534EUCBSKQCITURCZZC5SUYID",
+					"epicbox_address": null
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": {
+					"name": "alice",
+					"proof_address": "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid",
+					"epicbox_address": null
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn save_contact(&self, token: Token, contact: Contact) -> Result<Contact, ErrorKind>;
+
+	/**
+	Networked version of [Owner::remove_contact](struct.Owner.html#method.remove_contact).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "remove_contact",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"name": "alice"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Err": {
+					"UnknownContact": "alice"
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn remove_contact(&self, token: Token, name: String) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_send_templates](struct.Owner.html#method.retrieve_send_templates).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "retrieve_send_templates",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": []
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_send_templates(&self, token: Token) -> Result<Vec<SendTemplate>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_send_template](struct.Owner.html#method.get_send_template).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "get_send_template",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"name": "payroll-john"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Err": {
+					"UnknownSendTemplate": "payroll-john"
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_send_template(&self, token: Token, name: String) -> Result<SendTemplate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::save_send_template](struct.Owner.html#method.save_send_template).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "save_send_template",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"template": {
+					"name": " payroll-john ",
+					"method": "HTTP",
+					"dest": "http://127.0.0.1:3415",
+					"amount": "2000000000",
+					"message": "Salary"
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": {
+					"name": "payroll-john",
+					"method": "http",
+					"dest": "http://127.0.0.1:3415",
+					"amount": "2000000000",
+					"src_acct_name": null,
+					"message": "Salary"
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn save_send_template(
+		&self,
+		token: Token,
+		template: SendTemplate,
+	) -> Result<SendTemplate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::remove_send_template](struct.Owner.html#method.remove_send_template).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "remove_send_template",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"name": "payroll-john"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Err": {
+					"UnknownSendTemplate": "payroll-john"
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn remove_send_template(&self, token: Token, name: String) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_summary_info](struct.Owner.html#method.retrieve_summary_info).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_summary_info",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"refresh_from_node": true,
+			"minimum_confirmations": 1
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+	"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": [
+				true,
+				{
+					"amount_awaiting_confirmation": "0",
+					"amount_awaiting_finalization": "0",
+					"amount_currently_spendable": "1457920000",
+					"amount_immature": "4373760000",
+					"amount_locked": "0",
+					"last_confirmed_height": "4",
+					"minimum_confirmations": "1",
+					"total": "5831680000"
+				}
+
+			]
+		}
+	}
+	# "#
+	# ,true, 4, false, false, false, false);
+	```
+	 */
+
+	fn retrieve_summary_info(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		minimum_confirmations: u64,
+	) -> Result<(bool, WalletInfo), ErrorKind>;
+
+	/**
+	Networked version of [Owner::init_send_tx](struct.Owner.html#method.init_send_tx).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "init_send_tx",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"args": {
+					"src_acct_name": null,
+					"amount": "60000000",
+					"minimum_confirmations": 2,
+					"max_outputs": 500,
+					"num_change_outputs": 1,
+					"selection_strategy_is_use_all": true,
+					"message": "my message",
+					"target_slate_version": null,
+					"payment_proof_recipient_address": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb",
+					"ttl_blocks": null,
+					"send_args": null
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+	  "id": 1,
+	  "jsonrpc": "2.0",
+	  "result": {
+		"Ok": {
+		  "amount": "60000000",
+		  "fee": "800000",
+		  "height": "4",
+		  "id": "0436430c-2b02-624c-2032-570501212b00",
+		  "lock_height": "0",
+		  "num_participants": 2,
+
+		  "participant_data": [
+			{
+			  "id": "0",
+			  "message": "my message",
+			  "message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bea065fb74c27c31d611427ac5fa1459d1db340d7475e2967f19e2fa95687d88c",
+			  "part_sig": null,
+			  "public_blind_excess": "039fbac4782fa1600aa704c38073eece85e3a085a90446ded19a9fec90e432b330",
+			  "public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+			}
+		  ],
+		  "payment_proof": {
+			  "receiver_address": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb",
+			  "receiver_signature": null,
+			  "sender_address": "32cdd63928854f8b2628b1dce4626ddcdf35d56cb7cfdf7d64cca5822b78d4d3"
+			},
+			"ttl_cutoff_height": null,
+		  "tx": {
+			"body": {
+			"inputs": [
+			  {
+				"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+				"features": "Coinbase"
+			  }
+			],
+			"kernels": [
+			  {
+				"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+				"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+				"features": "Plain",
+				"fee": "800000",
+				"lock_height": "0"
+			  }
+			],
+			"outputs": [
+			  {
+				"commit": "0832ca73c2049ee0c8f555c6297aa3658eb3f8ce711dfd63d6d5234cf3191c7756",
+				"features": "Plain",
+				"proof": "1393386202b2ba345e131efb1dfc3730c000ee6b6f3cb8d56e3d0680796b11940abeaed75e18458b544d28765b530a7ca2ed170b42c8bd5e07d4451b0cfa59c20fee43c9107091a328a9f745a255702ea17ac50f5e92b2daf51a7851e8ffde5e11a6837b5b6058aee5ab1e68bcd756646f36b38c6262aa20ff9c194d2e3e155b608216186fee66ef8ca269d31a015c63cca3cf5f0d40e4cccd13d390ed1aeed7a7c7d0709e9b509844462098d97e983ba6963ee3a68955d5317ecec47caeb8911784598be71d319ba8441c36388bf4a10ba2538f4f54f648b22a8f1e1f713dec36376936100b0fc5fb5ad4f51811ec96a76b564c3ee08305f5a2ad79a80152a03eb86d4dcd854a23818621d80771ceb169e45a692b45db77667beecd545e08b8afe8f8a3d049ae18e1cee5522769cd6b0131e036ee81d70df4cd679959fd82684bf9e1f4784325ef271eec2fb73ef4a9569fd76f7b1e55d8e2e87a5daa5ad5357cb401af13c2c695afc4b6a8a2004da1b0f5ebe7cb70cb2e15f0f3ca41eaca969abcb452f7a15fe9d004e66ff646e423366713632f1dedcb33bac1abbc47f1cf2b280f04cf85a7291bb4ecb2c1c252d65e933f5819ba4984b1018ec11ae36d2445af56900b9b6e746f84ddd6b06baab9d7c8f82f0b0bc7a61ade6eabe762ac0d3afe4b2102518361a9e54a4d9d51e4a25ccf1d40c36f6444d2271d03d91eb0f1f6895345c8758a7375926cf0ab75212ef7b4a0efa59a31decd995be2933e3da51efec22365521b8942f997789f9618cbbb422607c2414fc64bc558eca27df5fe7156954b98335a5cc63e6bfe7e076149c93e2314dd626f48bf6721b506b81962b6ca81bff28c7e216f49fcbf989045f97452f3b4ccdcaa7ca5a4ce0bd3f5e16440c6c0b73a42bfa6cfe8e31265b73b81b81c2d54e4f7aefb16ebfa1273adbfd57c08a6"
+			  }
+			]
+			},
+			"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		  },
+		  "version_info": {
+				"orig_version": 3,
+				"version": 3,
+				"block_header_version": 6
+		  }
+		}
+	  }
+	}
+		# "#
+		# ,true, 4, false, false, false, false);
+	```
+	*/
+
+	fn init_send_tx(&self, token: Token, args: InitTxArgs) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::estimate_send_tx](struct.Owner.html#method.estimate_send_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "estimate_send_tx",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "60000000",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": false,
+				"message": null,
+				"target_slate_version": null,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"amount": "60000000",
+				"fee": "800000",
+				"inputs": [
+					{
+						"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+						"output": {
+							"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+							"height": "1",
+							"is_coinbase": true,
+							"key_id": "0300000000000000000000000000000000",
+							"lock_height": "4",
+							"mmr_index": null,
+							"n_child": 0,
+							"root_key_id": "0200000000000000000000000000000000",
+							"status": "Unspent",
+							"tx_log_entry": 0,
+							"value": "1457920000"
+						},
+						"output_id": "0482d9c44a063f139214baa66c1438ac"
+					}
+				],
+				"total": "1457920000",
+				"change": "1397120000",
+				"num_change_outputs": 1,
+				"input_composition": {
+					"coinbase_inputs": 1,
+					"coinbase_amount": "1457920000",
+					"plain_inputs": 0,
+					"plain_amount": "0"
+				}
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn estimate_send_tx(&self, token: Token, args: InitTxArgs) -> Result<TxEstimate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::init_unsigned_send_tx](struct.Owner.html#method.init_unsigned_send_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "init_unsigned_send_tx",
+		"params": {
+			"args": {
+				"src_acct_name": null,
+				"amount": "60000000",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": false,
+				"message": null,
+				"target_slate_version": null,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"WatchOnly": "unsigned slates are only built by watch-only wallets"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn init_unsigned_send_tx(&self, args: InitTxArgs) -> Result<SigningRequest, ErrorKind>;
+
+	/**
+	Networked version of [Owner::sign_tx_offline](struct.Owner.html#method.sign_tx_offline).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "sign_tx_offline",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"request": {
+				"slate": {
+					"amount": "1457920000",
+					"fee": "8000000",
+					"height": "4",
+					"id": "0436430c-2b02-624c-2032-570501212b00",
+					"lock_height": "0",
+					"ttl_cutoff_height": null,
+					"num_participants": 2,
+					"payment_proof": null,
+					"participant_data": [
+					{
+						"id": "0",
+						"message": "my message",
+						"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
+						"part_sig": null,
+						"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+					}
+					],
+					"tx": {
+						"body": {
+							"inputs": [
+							{
+								"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
+								"features": "Coinbase"
+							}
+							],
+							"kernels": [
+							{
+								"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+								"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+								"features": "Plain",
+								"fee": "8000000",
+								"lock_height": "0"
+							}
+							],
+							"outputs": []
+						},
+						"offset": "0000000000000000000000000000000000000000000000000000000000000000"
+					},
+					"version_info": {
+						"orig_version": 3,
+						"version": 3,
+						"block_header_version": 6
+					}
+				},
+				"inputs": [],
+				"num_change_outputs": 1,
+				"next_child_index": 0,
+				"message": null
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"SigningRequest": "the slate was signed already"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn sign_tx_offline(
+		&self,
+		token: Token,
+		request: SigningRequest,
+	) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::init_multisig_output](struct.Owner.html#method.init_multisig_output).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "init_multisig_output",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "60000000",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": true,
+				"message": "my message",
+				"target_slate_version": 2,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Compatibility": "shared outputs require slate version 3, but slate version 2 was requested"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn init_multisig_output(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+	) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::receive_multisig](struct.Owner.html#method.receive_multisig).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "receive_multisig",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"slate": {
+				"amount": "1457920000",
+				"fee": "8000000",
+				"height": "4",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "0",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"payment_proof": null,
+				"participant_data": [
+				{
+					"id": "0",
+					"message": "my message",
+					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
+					"part_sig": null,
+					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
+					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+				],
+				"tx": {
+					"body": {
+						"inputs": [
+						{
+							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
+							"features": "Coinbase"
+						}
+						],
+						"kernels": [
+						{
+							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+							"features": "Plain",
+							"fee": "8000000",
+							"lock_height": "0"
+						}
+						],
+						"outputs": []
+					},
+					"offset": "0000000000000000000000000000000000000000000000000000000000000000"
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 6
+				}
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Multisig": "the slate doesn't create a shared output"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn receive_multisig(
+		&self,
+		token: Token,
+		slate: VersionedSlate,
+	) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_start](struct.Owner.html#method.swap_start).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_start",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "2000000000",
+				"secondary_currency": "Btc",
+				"secondary_amount": "1000000",
+				"minimum_confirmations": "2",
+				"refund_blocks": "720",
+				"secondary_lock_hours": "12"
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Swap": "the secondary lock must last at least twice as long as the refund blocks"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn swap_start(&self, token: Token, args: SwapStartArgs) -> Result<SwapStep, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_process](struct.Owner.html#method.swap_process).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_process",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00",
+			"message": null
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Swap": "no swap 0436430c-2b02-624c-2032-570501212b00"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn swap_process(
+		&self,
+		token: Token,
+		id: Uuid,
+		message: Option<SwapMessage>,
+	) -> Result<SwapStep, ErrorKind>;
+
+	/**
+	Networked version of [Owner::swap_cancel](struct.Owner.html#method.swap_cancel).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "swap_cancel",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00"
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Swap": "no swap 0436430c-2b02-624c-2032-570501212b00"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn swap_cancel(&self, token: Token, id: Uuid) -> Result<Swap, ErrorKind>;
+
+	/**
+	Networked version of [Owner::init_send_tx_batch](struct.Owner.html#method.init_send_tx_batch).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "init_send_tx_batch",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": []
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn init_send_tx_batch(
+		&self,
+		token: Token,
+		args: Vec<InitTxArgs>,
+	) -> Result<Vec<InitTxBatchResult>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::approve_tx](struct.Owner.html#method.approve_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "approve_tx",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"tx_id": 1
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"Approval": "sends don't require approval"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn approve_tx(&self, token: Token, tx_id: u32) -> Result<TxLogEntry, ErrorKind>;
+
+	/**
+	Networked version of [Owner::queue_send](struct.Owner.html#method.queue_send).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "queue_send",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"args": {
+				"src_acct_name": null,
+				"amount": "60000000",
+				"minimum_confirmations": 2,
+				"max_outputs": 500,
+				"num_change_outputs": 1,
+				"selection_strategy_is_use_all": true,
+				"message": "my message",
+				"target_slate_version": null,
+				"payment_proof_recipient_address": null,
+				"ttl_blocks": null,
+				"send_args": null
+			},
+			"priority": "High"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"GenericError": "send arguments are required to queue a send"
+			}
+		}
+	}
+	# "#
+	# , true, 4, false, false, false, false);
+	```
+	*/
+	fn queue_send(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		priority: SendPriority,
+	) -> Result<Uuid, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_send_job](struct.Owner.html#method.get_send_job).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_send_job",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn get_send_job(&self, token: Token, id: Uuid) -> Result<Option<SendJob>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_send_jobs](struct.Owner.html#method.retrieve_send_jobs).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_send_jobs",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn retrieve_send_jobs(&self, token: Token) -> Result<Vec<SendJob>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::issue_invoice_tx](struct.Owner.html#method.issue_invoice_tx).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "issue_invoice_tx",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"args": {
+					"amount": "60000000",
+					"message": "Please give me your epics",
+					"dest_acct_name": null,
+					"target_slate_version": null
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": {
+					"amount": "60000000",
+					"fee": "0",
+					"height": "4",
+					"id": "0436430c-2b02-624c-2032-570501212b00",
+					"lock_height": "0",
+					"ttl_cutoff_height": null,
+					"num_participants": 2,
+					"payment_proof": null,
+					"participant_data": [
+						{
+							"id": "1",
+							"message": "Please give me your epics",
+							"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bb06e3894e0db51e6015d2181f101d06722094128dbc316f7186b57edd68731cb",
+							"part_sig": null,
+							"public_blind_excess": "035ca9d2d82e0e31bc2add7ef9200066b257d9f72cb16c0e57455277b90e2b3503",
+							"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+						}
+					],
+					"tx": {
+						"body": {
+							"inputs": [],
+							"kernels": [
+								{
+									"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+									"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+									"features": "Plain",
+									"fee": "0",
+									"lock_height": "0"
+								}
+							],
+							"outputs": [
+								{
+									"commit": "083fe68fb96a0941f70e9412b4483621326abee9eccf10e7a7efef2a9b4e97df25",
+									"features": "Plain",
+									"proof": "2df8ba188baa701a7c07e23de108e8318797ba029319b4557db3c1c8af917f6361d9871520e33423131d5771b06566aba2469f1e9fcc8eda8203a5c241759e45041af26939c57946372bf330d09670eb30a08cae27b3015dac2efb518fbe2325487d8417f4014cb854aec9e4e1770d2f5d7a6e32e2bc904245505ebd952eda10b5c401f315a8cb969da4cc2dc1e656f33d870ca07ffbc45f58cae9f28b836d4bf3c1786b805ba9d8789cc211998981a8b4115aa7383ddbd10e656fc0a590c3e2cf8dd07e414217d9a1d1af32bcbd933448d0a89033cb93ba2eb0d3b973136d61ee7f109d0476ed3475b7328eff5e9d3362b5db4621682a443a382f7ef09304f9bff422885d23f62f9d7d1a9bbf888e5ba5678e347182770cbbf41cfb3002269607f085881ce0f0df01f34f34433ef04dd6008f9a0c13e47e6e386d62151386dfd20bdf812ae2fb580edc38f38bfc9cc543d1023889ee646d4e75a7382caa3bb00b970062ffdc1fc643ce56d25e2e73b556162c8441d5a667b36b840cc244f69395b46900dd1edc562ed741c239804588e94c071b621766b55f738802c376012fa577e0d82bdf7bf2f229a867d91ed177bacde44faadb6901066f84e21a5fb0b73ed7ef9ef4a1e2c65e6a28a0ae834a99ed1694889d885fc8e90c8e7507078603a9705cc3c57b8b0125ad385cb5ec564f9ca69b530307d91ef2c6bb49a39e30d9e68f2f67d99915d87d1a7776f4c0b61913ea661ebe320b8e99919c69d9dbdc527e787d46e772da9ab9f9cc60e43b41fb0981b6b882ed7a535451158c711210fe25e68d12719192c3d332aea9e047a0f7a292b8e6f13fd76ed47afbadf070392cc3f4a4ebb8ec9853587e30ad9b9794717c87bf962e2ab99ec543f5a24efda0cfc2bf51f23c8132aee6058189925febe1d9d3a145f580ef9835db3c1f3b6e97bd36331e"
+								}
+							]
+						},
+						"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+					},
+					"version_info": {
+						"orig_version": 3,
+						"version": 3,
+						"block_header_version": 6
+					}
+				}
+			}
+		}
+		# "#
+		# ,true, 4, false, false, false, false);
+	```
+	*/
+
+	fn issue_invoice_tx(
+		&self,
+		token: Token,
+		args: IssueInvoiceTxArgs,
+	) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::process_invoice_tx](struct.Owner.html#method.process_invoice_tx).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "process_invoice_tx",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"slate": {
+					"amount": "60000000",
+					"fee": "0",
+					"height": "4",
+					"id": "0436430c-2b02-624c-2032-570501212b00",
+					"lock_height": "0",
+					"ttl_cutoff_height": null,
+					"num_participants": 2,
+					"payment_proof": null,
+					"participant_data": [
+						{
+							"id": "1",
+							"message": "Please give me your epics",
+							"message_sig": "1b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078fd2599ab38942986602e943f684a85992893a6d34367dc7cc2b403a5dcfcdbcd9",
+							"part_sig": null,
+							"public_blind_excess": "028e95921cc0d5be5922362265d352c9bdabe51a9e1502a3f0d4a10387f1893f40",
+							"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+						}
+					],
+					"tx": {
+						"body": {
+							"inputs": [],
+							"kernels": [
+								{
+									"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+									"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+									"features": "Plain",
+									"fee": "0",
+									"lock_height": "0"
+								}
+							],
+							"outputs": [
+								{
+									"commit": "09cf47204446c326e361a1a92f34b174deff732daaedb80d7339fbe3db5ca2f6ba",
+									"features": "Plain",
+									"proof": "8f511614315626b5f39224482351d766f5a8ef136262befc050d839be8479b0a13470cd88f4436346d213d83847a4055c6e0ac63681556470349a1aab47034a3015eb64d8163955998e2dd4165dd24386b1e279974b05deb5d46ba2bc321f7000c0784f8f10690605ffe717119d045e02b141ed12d8fc6d20930483a8af889ef533495eb442fcff36d98ebc104f13fc645c28431b3296e4a11f7c991ff97f9abbc2f8886762d7f29fdacb31d52c6850e6ccf5386117d89e8ea4ca3071c56c218dd5d3bcd65f6c06ed9f51f848507ca1d594f41796d1cf99f68a5c3f0c5dd9873602284cff31269b102fcc6c68607565faaf0adb04ed4ff3ea5d41f3b5235ac6cb90e4046c808c9c48c27172c891b20085c56a99913ef47fd8b3dc4920cef50534b9319a7cefe0df10a0206a634ac837e11da92df83ff58b1a14de81313400988aa48b946fcbe1b81f0e79e13f7c6c639b1c10983b424bda08d0ce593a20f1f47e0aa01473e7144f116b76d9ebc60599053d8f1542d60747793d99064e51fce8f8866390325d48d6e8e3bbdbc1822c864303451525c6cb4c6902f105a70134186fb32110d8192fc2528a9483fc8a4001f4bdeab1dd7b3d1ccb9ae2e746a78013ef74043f0b2436f0ca49627af1768b7c791c669bd331fd18c16ef88ad0a29861db70f2f76f3e74fde5accb91b73573e31333333223693d6fbc786e740c085e4fc6e7bde0a3f54e9703f816c54f012d3b1f41ec4d253d9337af61e7f1f1383bd929421ac346e3d2771dfee0b60503b33938e7c83eb37af3b6bf66041a3519a2b4cb557b34e3b9afcf95524f9a011425a34d32e7b6e9f255291094930acae26e8f7a1e4e6bc405d0f88e919f354f3ba85356a34f1aba5f7da1fad88e2692f4129cc1fb80a2122b2d996c6ccf7f08d8248e511d92af9ce49039de728848a2dc74101f4e94a"
+								}
+							]
+						},
+						"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+					},
+					"version_info": {
+						"orig_version": 3,
+						"version": 3,
+						"block_header_version": 6
+					}
+				},
+				"args": {
+					"src_acct_name": null,
+					"amount": "0",
+					"minimum_confirmations": 2,
+					"max_outputs": 500,
+					"num_change_outputs": 1,
+					"selection_strategy_is_use_all": true,
+					"message": "Ok, here are your epics",
+					"target_slate_version": null,
+					"payment_proof_recipient_address": null,
+					"ttl_blocks": null,
+					"send_args": null
+				}
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"amount": "60000000",
+				"fee": "800000",
+				"height": "4",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "0",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"payment_proof": null,
+				"participant_data": [
+					{
+						"id": "1",
+						"message": "Please give me your epics",
+						"message_sig": "1b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078fd2599ab38942986602e943f684a85992893a6d34367dc7cc2b403a5dcfcdbcd9",
+						"part_sig": null,
+						"public_blind_excess": "028e95921cc0d5be5922362265d352c9bdabe51a9e1502a3f0d4a10387f1893f40",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+					},
+					{
+						"id": "0",
+						"message": "Ok, here are your epics",
+						"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bec8c1cac6cb5770a3c62c9bb95063581cc08bfccd72dac72be8ec4ba5374a9f3",
+						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841bcec20532cbe7ce0a3152b61566785684fea3534b7f834f02f733fa524123ee54",
+						"public_blind_excess": "02802124f21ba02769a3f05ecfe9662e8783fa0bd1a7b7d63cf3aea0ebc0d7af3a",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+					}
+				],
+				"tx": {
+					"body": {
+						"inputs": [
+							{
+								"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+								"features": "Coinbase"
+							}
+						],
+						"kernels": [
+							{
+								"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+								"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+								"features": "Plain",
+								"fee": "800000",
+								"lock_height": "0"
+							}
+						],
+						"outputs": [
+							{
+								"commit": "0832ca73c2049ee0c8f555c6297aa3658eb3f8ce711dfd63d6d5234cf3191c7756",
+								"features": "Plain",
+								"proof": "1393386202b2ba345e131efb1dfc3730c000ee6b6f3cb8d56e3d0680796b11940abeaed75e18458b544d28765b530a7ca2ed170b42c8bd5e07d4451b0cfa59c20fee43c9107091a328a9f745a255702ea17ac50f5e92b2daf51a7851e8ffde5e11a6837b5b6058aee5ab1e68bcd756646f36b38c6262aa20ff9c194d2e3e155b608216186fee66ef8ca269d31a015c63cca3cf5f0d40e4cccd13d390ed1aeed7a7c7d0709e9b509844462098d97e983ba6963ee3a68955d5317ecec47caeb8911784598be71d319ba8441c36388bf4a10ba2538f4f54f648b22a8f1e1f713dec36376936100b0fc5fb5ad4f51811ec96a76b564c3ee08305f5a2ad79a80152a03eb86d4dcd854a23818621d80771ceb169e45a692b45db77667beecd545e08b8afe8f8a3d049ae18e1cee5522769cd6b0131e036ee81d70df4cd679959fd82684bf9e1f4784325ef271eec2fb73ef4a9569fd76f7b1e55d8e2e87a5daa5ad5357cb401af13c2c695afc4b6a8a2004da1b0f5ebe7cb70cb2e15f0f3ca41eaca969abcb452f7a15fe9d004e66ff646e423366713632f1dedcb33bac1abbc47f1cf2b280f04cf85a7291bb4ecb2c1c252d65e933f5819ba4984b1018ec11ae36d2445af56900b9b6e746f84ddd6b06baab9d7c8f82f0b0bc7a61ade6eabe762ac0d3afe4b2102518361a9e54a4d9d51e4a25ccf1d40c36f6444d2271d03d91eb0f1f6895345c8758a7375926cf0ab75212ef7b4a0efa59a31decd995be2933e3da51efec22365521b8942f997789f9618cbbb422607c2414fc64bc558eca27df5fe7156954b98335a5cc63e6bfe7e076149c93e2314dd626f48bf6721b506b81962b6ca81bff28c7e216f49fcbf989045f97452f3b4ccdcaa7ca5a4ce0bd3f5e16440c6c0b73a42bfa6cfe8e31265b73b81b81c2d54e4f7aefb16ebfa1273adbfd57c08a6"
+							},
+							{
+								"commit": "09cf47204446c326e361a1a92f34b174deff732daaedb80d7339fbe3db5ca2f6ba",
+								"features": "Plain",
+								"proof": "8f511614315626b5f39224482351d766f5a8ef136262befc050d839be8479b0a13470cd88f4436346d213d83847a4055c6e0ac63681556470349a1aab47034a3015eb64d8163955998e2dd4165dd24386b1e279974b05deb5d46ba2bc321f7000c0784f8f10690605ffe717119d045e02b141ed12d8fc6d20930483a8af889ef533495eb442fcff36d98ebc104f13fc645c28431b3296e4a11f7c991ff97f9abbc2f8886762d7f29fdacb31d52c6850e6ccf5386117d89e8ea4ca3071c56c218dd5d3bcd65f6c06ed9f51f848507ca1d594f41796d1cf99f68a5c3f0c5dd9873602284cff31269b102fcc6c68607565faaf0adb04ed4ff3ea5d41f3b5235ac6cb90e4046c808c9c48c27172c891b20085c56a99913ef47fd8b3dc4920cef50534b9319a7cefe0df10a0206a634ac837e11da92df83ff58b1a14de81313400988aa48b946fcbe1b81f0e79e13f7c6c639b1c10983b424bda08d0ce593a20f1f47e0aa01473e7144f116b76d9ebc60599053d8f1542d60747793d99064e51fce8f8866390325d48d6e8e3bbdbc1822c864303451525c6cb4c6902f105a70134186fb32110d8192fc2528a9483fc8a4001f4bdeab1dd7b3d1ccb9ae2e746a78013ef74043f0b2436f0ca49627af1768b7c791c669bd331fd18c16ef88ad0a29861db70f2f76f3e74fde5accb91b73573e31333333223693d6fbc786e740c085e4fc6e7bde0a3f54e9703f816c54f012d3b1f41ec4d253d9337af61e7f1f1383bd929421ac346e3d2771dfee0b60503b33938e7c83eb37af3b6bf66041a3519a2b4cb557b34e3b9afcf95524f9a011425a34d32e7b6e9f255291094930acae26e8f7a1e4e6bc405d0f88e919f354f3ba85356a34f1aba5f7da1fad88e2692f4129cc1fb80a2122b2d996c6ccf7f08d8248e511d92af9ce49039de728848a2dc74101f4e94a"
+							}
+						]
+					},
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 6
+				}
+			}
+		}
+	}
+	# "#
+	# ,true, 4, false, false, false, false);
+	```
+	*/
+
+	fn process_invoice_tx(
+		&self,
+		token: Token,
+		slate: VersionedSlate,
+		args: InitTxArgs,
+	) -> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::tx_lock_outputs](struct.Owner.html#method.tx_lock_outputs).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "tx_lock_outputs",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"slate": {
+				"amount": "1457920000",
+				"fee": "8000000",
+				"height": "4",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "4",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"payment_proof": null,
+				"participant_data": [
+				{
+					"id": "0",
+					"message": "my message",
+					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
+					"part_sig": null,
+					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
+					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+				],
+				"tx": {
+					"body": {
+						"inputs": [
+						{
+							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
+							"features": "Coinbase"
+						}
+						],
+						"kernels": [
+						{
+							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+							"features": "HeightLocked",
+							"fee": "8000000",
+							"lock_height": "4"
+						}
+						],
+						"outputs": [
+						{
+							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
+							"features": "Plain",
+							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
+						}
+						]
+					},
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 6
+				}
+			},
+			"participant_id": 0
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# ,true, 5 ,true, false, false, false);
+
+	```
+	 */
+	fn tx_lock_outputs(
+		&self,
+		token: Token,
+		slate: VersionedSlate,
+		participant_id: usize,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::finalize_tx](struct.Owner.html#method.finalize_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "finalize_tx",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"slate": {
+				"version_info": {
+					"version": 3,
+					"orig_version": 3,
+					"block_header_version": 6
+				},
+				"num_participants": 2,
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"payment_proof": null,
+				"tx": {
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
+					"body": {
+						"inputs": [
+							{
+								"features": "Coinbase",
+								"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e"
+							},
+							{
+								"features": "Coinbase",
+								"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e"
+							}
+						],
+						"outputs": [
+							{
+								"features": "Plain",
+								"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
+								"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
+							},
+							{
+								"features": "Plain",
+								"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
+								"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
+							}
+						],
+						"kernels": [
+							{
+								"features": "Plain",
+								"fee": "700000",
+								"lock_height": "0",
+								"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+								"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+							}
+						]
+					}
+				},
+				"amount": "600000000",
+				"fee": "700000",
+				"height": "5",
+				"lock_height": "0",
+				"ttl_cutoff_height": null,
+				"participant_data": [
+					{
+						"id": "0",
+						"public_blind_excess": "028e1bbb43e6038efc42054778d0a1aa184b2cf4d51acb40a2a8dc049788d97bd2",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f",
+						"part_sig": null,
+						"message": null,
+						"message_sig": null
+					},
+					{
+						"id": "1",
+						"public_blind_excess": "03e14bacb4cfeda43edf6c1b0ffced9a358a119c7936bc68af724477eb91d9e4eb",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f",
+						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b129340313ed7db02f6fd9a16c23ae8d5801af4fdc2ea580e2dec26e3821d5c17",
+						"message": null,
+						"message_sig": null
+					}
+				]
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"result": {
+		"Ok": {
+				"amount": "600000000",
+				"fee": "700000",
+				"height": "5",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "0",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"payment_proof": null,
+				"participant_data": [
+					{
+						"id": "0",
+						"message": null,
+						"message_sig": null,
+						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b93f888685e13250c5cb6b830ff898264ce247c73d3ab47845c01bcc6455ecbe5",
+						"public_blind_excess": "028e1bbb43e6038efc42054778d0a1aa184b2cf4d51acb40a2a8dc049788d97bd2",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+					},
+					{
+						"id": "1",
+						"message": null,
+						"message_sig": null,
+						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b129340313ed7db02f6fd9a16c23ae8d5801af4fdc2ea580e2dec26e3821d5c17",
+						"public_blind_excess": "03e14bacb4cfeda43edf6c1b0ffced9a358a119c7936bc68af724477eb91d9e4eb",
+						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+					}
+				],
+				"tx": {
+					"body": {
+					"inputs": [
+						{
+							"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
+							"features": "Coinbase"
+						},
+						{
+							"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+							"features": "Coinbase"
+						}
+					],
+					"kernels": [
+						{
+							"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+							"excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4da58bc9999cea000f52b45347c1c46a3a4f3f70719696a09289ede2a9c87b27fd",
+							"features": "Plain",
+							"fee": "700000",
+							"lock_height": "0"
+						}
+					],
+					"outputs": [
+						{
+							"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
+							"features": "Plain",
+							"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
+						},
+						{
+							"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
+							"features": "Plain",
+							"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
+						}
+					]
+					},
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 6
+				}
+			}
+		}
+	}
+	# "#
+	# , true, 5, true, true, false, false);
+	```
+	 */
+	fn finalize_tx(&self, token: Token, slate: VersionedSlate)
+		-> Result<VersionedSlate, ErrorKind>;
+
+	/**
+	Networked version of [Owner::post_tx](struct.Owner.html#method.post_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "post_tx",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"tx": {
+			"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"body": {
+				"inputs": [
+					{
+						"features": "Coinbase",
+						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e"
+					},
+					{
+						"features": "Coinbase",
+						"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e"
+					}
+				],
+				"kernels": [
+					{
+						"features": "Plain",
+						"fee": "700000",
+						"lock_height": "0",
+						"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+						"excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4da58bc9999cea000f52b45347c1c46a3a4f3f70719696a09289ede2a9c87b27fd"
+					}
+				],
+				"outputs": [
+					{
+						"features": "Plain",
+						"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
+						"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
+					},
+					{
+						"features": "Plain",
+						"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
+						"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
+					}
+				]
+
+			}
+		},
+		"fluff": false
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, true, true, true, false);
+	```
+	 */
+
+	fn post_tx(&self, token: Token, tx: TransactionV3, fluff: bool) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::cancel_tx](struct.Owner.html#method.cancel_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "cancel_tx",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"tx_id": null,
+			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, true, true, false, false);
+	```
+	 */
+	fn cancel_tx(
+		&self,
+		token: Token,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::cancel_txs](struct.Owner.html#method.cancel_txs).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "cancel_txs",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"filter": {
+				"tx_type": "TxSent",
+				"min_age_hours": 87600
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 5, true, true, false, false);
+	```
+	 */
+	fn cancel_txs(&self, token: Token, filter: TxFilter) -> Result<Vec<TxBulkResult>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::repost_txs](struct.Owner.html#method.repost_txs).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "repost_txs",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"filter": {
+				"tx_type": "TxSent",
+				"min_age_hours": 87600
+			},
+			"fluff": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 5, true, true, false, false);
+	```
+	 */
+	fn repost_txs(
+		&self,
+		token: Token,
+		filter: TxFilter,
+		fluff: bool,
+	) -> Result<Vec<TxBulkResult>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::sweep_hot_account](struct.Owner.html#method.sweep_hot_account).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "sweep_hot_account",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"policy": {
+				"hot_account": "default",
+				"cold_account": "cold",
+				"max_hot_balance": "10000000000",
+				"minimum_confirmations": "10"
+			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"UnknownAccountLabel": "cold"
+			}
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false);
+	```
+	*/
+	fn sweep_hot_account(
+		&self,
+		token: Token,
+		policy: SweepPolicy,
+	) -> Result<Option<VersionedSlate>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_stored_tx](struct.Owner.html#method.get_stored_tx).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_stored_tx",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"tx": {
+				"amount_credited": "59993000000",
+				"amount_debited": "120000000000",
+				"confirmation_ts": "2019-01-15T16:01:26Z",
+				"confirmed": false,
+				"creation_ts": "2019-01-15T16:01:26Z",
+				"fee": "7000000",
+				"id": 5,
+				"messages": {
+					"messages": [
+						{
+							"id": "0",
+							"message": null,
+							"message_sig": null,
+							"public_key": "033ac2158fa0077f087de60c19d8e431753baa5b63b6e1477f05a2a6e7190d4592"
+						},
+						{
+							"id": "1",
+							"message": null,
+							"message_sig": null,
+							"public_key": "024f9bc78c984c78d6e916d3a00746aa30fa1172124c8dbc0cbddcb7b486719bc7"
+						}
+					]
+				},
+				"num_inputs": 2,
+				"num_outputs": 1,
+				"parent_key_id": "0200000000000000000000000000000000",
+				"stored_tx": "0436430c-2b02-624c-2032-570501212b00.epictx",
+				"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00",
+				"tx_type": "TxSent",
+				"kernel_excess": null,
+				"kernel_lookup_min_height": null
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"result": {
+			"Ok": {
+				"body": {
+				"inputs": [
+					{
+						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
+						"features": "Coinbase"
+					},
+					{
+						"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
+						"features": "Coinbase"
+					}
+				],
+				"kernels": [
+					{
+						"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+						"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+						"features": "Plain",
+						"fee": "700000",
+						"lock_height": "0"
+					}
+				],
+				"outputs": [
+					{
+						"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
+						"features": "Plain",
+						"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
+					},
+					{
+						"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
+						"features": "Plain",
+						"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
+					}
+				]
+				},
+				"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			}
+		}
+	}
+	# "#
+	# , true, 5, true, true, false, false);
+	```
+	 */
+	fn get_stored_tx(
+		&self,
+		token: Token,
+		tx: &TxLogEntry,
+	) -> Result<Option<TransactionV3>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::verify_slate_messages](struct.Owner.html#method.verify_slate_messages).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "verify_slate_messages",
+		"id": 1,
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"slate": {
+				"amount": "1457920000",
+				"fee": "8000000",
+				"height": "4",
+				"id": "0436430c-2b02-624c-2032-570501212b00",
+				"lock_height": "4",
+				"ttl_cutoff_height": null,
+				"num_participants": 2,
+				"participant_data": [
+				{
+					"id": "0",
+					"message": "my message",
+					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
+					"part_sig": null,
+					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
+					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
+				}
+				],
+				"tx": {
+					"body": {
+						"inputs": [
+						{
+							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
+							"features": "Coinbase"
+						}
+						],
+						"kernels": [
+						{
+							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
+							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+							"features": "HeightLocked",
+							"fee": "8000000",
+							"lock_height": "4"
+						}
+						],
+						"outputs": [
+						{
+							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
+							"features": "Plain",
+							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
+						}
+						]
+					},
+					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
+					"payment_proof": null
+				},
+				"version_info": {
+					"orig_version": 3,
+					"version": 3,
+					"block_header_version": 6
+				}
+			}
+		}
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"id": 1,
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# ,true, 0 ,false, false, false, false);
+	```
+	*/
+	fn verify_slate_messages(&self, token: Token, slate: VersionedSlate) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::scan](struct.Owner.html#method.scan).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "scan",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"start_height": 1,
+			"delete_unconfirmed": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false);
+	```
+	 */
+	fn scan(
+		&self,
+		token: Token,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::scan_account](struct.Owner.html#method.scan_account).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "scan_account",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"account": "default",
+			"start_height": 1,
+			"delete_unconfirmed": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false);
+	```
+	 */
+	fn scan_account(
+		&self,
+		token: Token,
+		account: String,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::refresh_account](struct.Owner.html#method.refresh_account).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "refresh_account",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"account": "default"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": true
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false);
+	```
+	 */
+	fn refresh_account(&self, token: Token, account: String) -> Result<bool, ErrorKind>;
+
+	/**
+	Networked version of [Owner::start_scan](struct.Owner.html#method.start_scan).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "start_scan",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"start_height": 1,
+			"delete_unconfirmed": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "1d8b2ab4-7f6a-4c3e-9a51-2e0f6b7c8d90"
+		}
+	}
+	# "#
+	# , true, 1, false, false, false, false);
+	```
+	 */
+	fn start_scan(
+		&self,
+		token: Token,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<Uuid, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_job_status](struct.Owner.html#method.get_job_status).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_job_status",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn get_job_status(&self, token: Token, id: Uuid) -> Result<Option<ScanJob>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::cancel_job](struct.Owner.html#method.cancel_job).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "cancel_job",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"id": "0436430c-2b02-624c-2032-570501212b00"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": false
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn cancel_job(&self, token: Token, id: Uuid) -> Result<bool, ErrorKind>;
+
+	/**
+	Networked version of [Owner::node_height](struct.Owner.html#method.node_height).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "node_height",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"header_hash": "d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d",
+				"height": "5",
+				"updated_from_node": true
+			}
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false);
+	```
+	 */
+	fn node_height(&self, token: Token) -> Result<NodeHeightResult, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_block_header](struct.Owner.html#method.get_block_header).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_block_header",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"height": 1000
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false);
+	```
+	 */
+	fn get_block_header(
+		&self,
+		token: Token,
+		height: u64,
+	) -> Result<Option<BlockHeaderInfo>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_transport_metrics](struct.Owner.html#method.retrieve_transport_metrics).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_transport_metrics",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn retrieve_transport_metrics(&self, token: Token) -> Result<Vec<TransportMetrics>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_node_metrics](struct.Owner.html#method.retrieve_node_metrics).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_node_metrics",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn retrieve_node_metrics(&self, token: Token) -> Result<Vec<NodeRequestMetrics>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_rpc_metrics](struct.Owner.html#method.retrieve_rpc_metrics).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_rpc_metrics",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn retrieve_rpc_metrics(&self, token: Token) -> Result<Vec<RpcMethodMetrics>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_listener_status](struct.Owner.html#method.get_listener_status).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_listener_status",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn get_listener_status(&self, token: Token) -> Result<Vec<ListenerStatus>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_pinned_keys](struct.Owner.html#method.retrieve_pinned_keys).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "retrieve_pinned_keys",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": []
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn retrieve_pinned_keys(&self, token: Token) -> Result<Vec<PinnedKey>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::unpin_key](struct.Owner.html#method.unpin_key).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "unpin_key",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"contact": "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": false
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	 */
+	fn unpin_key(&self, token: Token, contact: String) -> Result<bool, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_metadata](struct.Owner.html#method.get_metadata).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "get_metadata",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"namespace": "my-gui",
+				"key": "theme"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": null
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+	) -> Result<Option<MetadataEntry>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::retrieve_metadata](struct.Owner.html#method.retrieve_metadata).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "retrieve_metadata",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"namespace": "my-gui"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": []
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn retrieve_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+	) -> Result<Vec<MetadataEntry>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_events](struct.Owner.html#method.get_events).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "get_events",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"since_id": 42
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": []
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_events(
+		&self,
+		token: Token,
+		since_id: Option<u64>,
+	) -> Result<Vec<StoredEvent>, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_audit_log](struct.Owner.html#method.get_audit_log).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "get_audit_log",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"offset": 0,
+				"limit": 100
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": {
+					"entries": [],
+					"total": 0
+				}
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_audit_log(
+		&self,
+		token: Token,
+		offset: usize,
+		limit: Option<usize>,
+	) -> Result<AuditLogPage, ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_metadata](struct.Owner.html#method.set_metadata).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "set_metadata",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"namespace": "my-gui",
+				"key": "theme",
+				"value": "dark"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": null
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn set_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+		value: String,
+	) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::delete_metadata](struct.Owner.html#method.delete_metadata).
+
+	# Json rpc example
+
+	```
+		# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+		# r#"
+		{
+			"jsonrpc": "2.0",
+			"method": "delete_metadata",
+			"params": {
+				"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+				"namespace": "my-gui",
+				"key": "theme"
+			},
+			"id": 1
+		}
+		# "#
+		# ,
+		# r#"
+		{
+			"id": 1,
+			"jsonrpc": "2.0",
+			"result": {
+				"Ok": false
+			}
+		}
+		# "#
+		# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn delete_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+	) -> Result<bool, ErrorKind>;
+
+	/**
+		Initializes the secure JSON-RPC API. This function must be called and a shared key
+		established before any other OwnerAPI JSON-RPC function can be called.
+
+		The shared key will be derived using ECDH with the provided public key on the secp256k1 curve. This
+		function will return its public key used in the derivation, which the caller should multiply by its
+		private key to derive the shared key.
+
+		Once the key is established, all further requests and responses are encrypted and decrypted with the
+		following parameters:
+		* AES-256 in GCM mode with 128-bit tags and 96 bit nonces
+		* 12 byte nonce which must be included in each request/response to use on the decrypting side
+		* Empty vector for additional data
+		* Suffix length = AES-256 GCM mode tag length = 16 bytes
+		*
+
+		Fully-formed JSON-RPC requests (as documented) should be encrypted using these parameters, encoded
+		into base64 and included with the one-time nonce in a request for the `encrypted_request_v3` method
+		as follows:
+
+		```
+		# let s = r#"
+		{
+			 "jsonrpc": "2.0",
+			 "method": "encrypted_request_v3",
+			 "id": "1",
+			 "params": {
+					"nonce": "ef32...",
+					"body_enc": "e0bcd..."
+			 }
+		}
+		# "#;
+		```
+
+		With a typical response being:
+
+		```
+		# let s = r#"{
+		{
+			 "jsonrpc": "2.0",
+			 "method": "encrypted_response_v3",
+			 "id": "1",
+			 "Ok": {
+					"nonce": "340b...",
+					"body_enc": "3f09c..."
+			 }
+		}
+		# }"#;
+		```
+
+	*/
+
+	fn init_secure_api(&self, ecdh_pubkey: ECDHPubkey) -> Result<ECDHPubkey, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_top_level_directory](struct.Owner.html#method.get_top_level_directory).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_top_level_directory",
+		"params": {
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "/doctest/dir"
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false);
+	```
+	*/
+
+	fn get_top_level_directory(&self) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::reload_config](struct.Owner.html#method.reload_config).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "reload_config",
+		"params": {
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Err": {
+				"GenericError": "No configuration file to reload"
+			}
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false);
+	```
+	*/
+
+	fn reload_config(&self) -> Result<ConfigReload, ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_api_versions](struct.Owner.html#method.get_api_versions).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_api_versions",
+		"params": {
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": {
+				"build_version": "3.4.0",
+				"foreign_api_version": 2,
+				"owner_api_versions": [
+					{
+						"deprecated_methods": [
+							{
+								"method": "check_repair",
+								"replacement": "scan",
+								"status": "Removed"
+							},
+							{
+								"method": "restore",
+								"replacement": "scan",
+								"status": "Removed"
+							}
+						],
+						"endpoint": "/v2/owner",
+						"status": "Deprecated",
+						"version": 2
+					},
+					{
+						"deprecated_methods": [],
+						"endpoint": "/v3/owner",
+						"status": "Supported",
+						"version": 3
+					}
+				]
+			}
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_api_versions(&self) -> Result<ApiVersions, ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_top_level_directory](struct.Owner.html#method.set_top_level_directory).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_top_level_directory",
+		"params": {
+			"dir": "/home/wallet_user/my_wallet_dir"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 5, false, false, false, false);
+	```
+	*/
+
+	fn set_top_level_directory(&self, dir: String) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::create_config](struct.Owner.html#method.create_config).
+
+	# Json rpc example
+
+	Both the `wallet_config` and `logging_config` parameters can be `null`, the examples
+	below are for illustration. Note that the values provided for `log_file_path` and `data_file_dir`
+	will be ignored and replaced with the actual values based on the value of `get_top_level_directory`
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "create_config",
+		"params": {
+			"chain_type": "Mainnet",
+			"wallet_config": {
+				"epicbox_domain": "epicbox.io",
+				"epicbox_address_index": 0,
+				"chain_type": null,
+				"api_listen_interface": "127.0.0.1",
+				"api_listen_port": 3415,
+				"owner_api_listen_port": 3420,
+				"api_secret_path": null,
+				"node_api_secret_path": null,
+				"check_node_api_http_addr": "http://127.0.0.1:3413",
+				"owner_api_include_foreign": false,
+				"data_file_dir": "/path/to/data/file/dir",
+				"no_commit_cache": null,
+				"tls_certificate_file": null,
+				"tls_certificate_key": null,
+				"dark_background_color_scheme": null,
+				"keybase_notify_ttl": null
+			},
+			"logging_config": {
+				"log_to_stdout": false,
+				"stdout_log_level": "Info",
+				"log_to_file": true,
+				"file_log_level": "Debug",
+				"log_file_path": "/path/to/log/file",
+				"log_file_append": true,
+				"log_max_size": null,
+				"log_max_files": null,
+				"tui_running": null
+			},
+			"tor_config" : {
+				"use_tor_listener": true,
+				"socks_proxy_addr": "127.0.0.1:9050",
+				"send_config_dir": "."
+			},
+			"epicbox_config" : {
+				"epicbox_domain": "epicbox.io",
+				"epicbox_port": 443,
+				"epicbox_protocol_unsecure": false,
+				"epicbox_address_index": 0
 			}
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
 	}
 	# "#
-	# ,true, 4, false, false, false, false);
+	# , true, 5, false, false, false, false);
 	```
 	*/
-
-	fn process_invoice_tx(
+	fn create_config(
 		&self,
-		token: Token,
-		slate: VersionedSlate,
-		args: InitTxArgs,
-	) -> Result<VersionedSlate, ErrorKind>;
+		chain_type: global::ChainTypes,
+		wallet_config: Option<WalletConfig>,
+		logging_config: Option<LoggingConfig>,
+		tor_config: Option<TorConfig>,
+		epicbox_config: Option<EpicboxConfig>,
+	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::tx_lock_outputs](struct.Owner.html#method.tx_lock_outputs).
+	Networked version of [Owner::create_wallet](struct.Owner.html#method.create_wallet).
 
 	# Json rpc example
 
@@ -722,89 +3810,45 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "tx_lock_outputs",
-		"id": 1,
+		"method": "create_wallet",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"slate": {
-				"amount": "1457920000",
-				"fee": "8000000",
-				"height": "4",
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"lock_height": "4",
-				"ttl_cutoff_height": null,
-				"num_participants": 2,
-				"payment_proof": null,
-				"participant_data": [
-				{
-					"id": "0",
-					"message": "my message",
-					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
-					"part_sig": null,
-					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
-					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-				}
-				],
-				"tx": {
-					"body": {
-						"inputs": [
-						{
-							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
-							"features": "Coinbase"
-						}
-						],
-						"kernels": [
-						{
-							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-							"features": "HeightLocked",
-							"fee": "8000000",
-							"lock_height": "4"
-						}
-						],
-						"outputs": [
-						{
-							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
-							"features": "Plain",
-							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
-						}
-						]
-					},
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-				},
-				"version_info": {
-					"orig_version": 3,
-					"version": 3,
-					"block_header_version": 6
-				}
-			},
-			"participant_id": 0
-		}
+			"name": null,
+			"mnemonic": null,
+			"mnemonic_length": 0,
+			"language": null,
+			"password": "my_secret_password"
+		},
+		"id": 1
 	}
 	# "#
 	# ,
 	# r#"
 	{
-		"jsonrpc": "2.0",
 		"id": 1,
+		"jsonrpc": "2.0",
 		"result": {
 			"Ok": null
 		}
 	}
 	# "#
-	# ,true, 5 ,true, false, false, false);
-
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn tx_lock_outputs(
+	*/
+
+	fn create_wallet(
 		&self,
-		token: Token,
-		slate: VersionedSlate,
-		participant_id: usize,
+		name: Option<String>,
+		mnemonic: Option<String>,
+		mnemonic_length: u32,
+		language: Option<MnemonicLanguage>,
+		password: String,
 	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::finalize_tx](struct.Owner.html#method.finalize_tx).
+	Networked version of [Owner::open_wallet](struct.Owner.html#method.open_wallet).
+	Given a `scope` such as `"read_only"` or `{"send_limited": {"max_amount": 1000000000}}`,
+	the token returned is only allowed to call the methods of the scope, the listener
+	refusing the others with error -32004.
 
 	# Json rpc example
 
@@ -813,168 +3857,147 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "finalize_tx",
+		"method": "open_wallet",
+		"params": {
+			"name": null,
+			"password": "my_secret_password",
+			"scope": null
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
 		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868"
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn open_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		scope: Option<TokenScope>,
+	) -> Result<Token, ErrorKind>;
+
+	/**
+	Networked version of [Owner::close_wallet](struct.Owner.html#method.close_wallet).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "close_wallet",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"slate": {
-				"version_info": {
-					"version": 3,
-					"orig_version": 3,
-					"block_header_version": 6
-				},
-				"num_participants": 2,
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"payment_proof": null,
-				"tx": {
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
-					"body": {
-						"inputs": [
-							{
-								"features": "Coinbase",
-								"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e"
-							},
-							{
-								"features": "Coinbase",
-								"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e"
-							}
-						],
-						"outputs": [
-							{
-								"features": "Plain",
-								"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
-								"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
-							},
-							{
-								"features": "Plain",
-								"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
-								"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
-							}
-						],
-						"kernels": [
-							{
-								"features": "Plain",
-								"fee": "700000",
-								"lock_height": "0",
-								"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-								"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
-							}
-						]
-					}
-				},
-				"amount": "600000000",
-				"fee": "700000",
-				"height": "5",
-				"lock_height": "0",
-				"ttl_cutoff_height": null,
-				"participant_data": [
-					{
-						"id": "0",
-						"public_blind_excess": "028e1bbb43e6038efc42054778d0a1aa184b2cf4d51acb40a2a8dc049788d97bd2",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f",
-						"part_sig": null,
-						"message": null,
-						"message_sig": null
-					},
-					{
-						"id": "1",
-						"public_blind_excess": "03e14bacb4cfeda43edf6c1b0ffced9a358a119c7936bc68af724477eb91d9e4eb",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f",
-						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b129340313ed7db02f6fd9a16c23ae8d5801af4fdc2ea580e2dec26e3821d5c17",
-						"message": null,
-						"message_sig": null
-					}
-				]
-			}
+			"name": null
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
 	}
 	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn close_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::get_mnemonic](struct.Owner.html#method.get_mnemonic).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "get_mnemonic",
+		"params": {
+			"name": null,
+			"password": ""
+		},
+		"id": 1
+	}
+	# "#
 	# ,
 	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": "fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch"
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+
+	fn get_mnemonic(&self, name: Option<String>, password: String) -> Result<String, ErrorKind>;
+
+	/**
+	Networked version of [Owner::export_view_key](struct.Owner.html#method.export_view_key).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
 	{
 		"jsonrpc": "2.0",
+		"method": "export_view_key",
+		"params": {
+			"name": null,
+			"password": "",
+			"account": "savings"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
 		"id": 1,
+		"jsonrpc": "2.0",
 		"result": {
-		"Ok": {
-				"amount": "600000000",
-				"fee": "700000",
-				"height": "5",
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"lock_height": "0",
-				"ttl_cutoff_height": null,
-				"num_participants": 2,
-				"payment_proof": null,
-				"participant_data": [
-					{
-						"id": "0",
-						"message": null,
-						"message_sig": null,
-						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b93f888685e13250c5cb6b830ff898264ce247c73d3ab47845c01bcc6455ecbe5",
-						"public_blind_excess": "028e1bbb43e6038efc42054778d0a1aa184b2cf4d51acb40a2a8dc049788d97bd2",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-					},
-					{
-						"id": "1",
-						"message": null,
-						"message_sig": null,
-						"part_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b129340313ed7db02f6fd9a16c23ae8d5801af4fdc2ea580e2dec26e3821d5c17",
-						"public_blind_excess": "03e14bacb4cfeda43edf6c1b0ffced9a358a119c7936bc68af724477eb91d9e4eb",
-						"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-					}
-				],
-				"tx": {
-					"body": {
-					"inputs": [
-						{
-							"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
-							"features": "Coinbase"
-						},
-						{
-							"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
-							"features": "Coinbase"
-						}
-					],
-					"kernels": [
-						{
-							"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
-							"excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4da58bc9999cea000f52b45347c1c46a3a4f3f70719696a09289ede2a9c87b27fd",
-							"features": "Plain",
-							"fee": "700000",
-							"lock_height": "0"
-						}
-					],
-					"outputs": [
-						{
-							"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
-							"features": "Plain",
-							"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
-						},
-						{
-							"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
-							"features": "Plain",
-							"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
-						}
-					]
-					},
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-				},
-				"version_info": {
-					"orig_version": 3,
-					"version": 3,
-					"block_header_version": 6
-				}
+			"Err": {
+				"UnknownAccountLabel": "savings"
 			}
 		}
 	}
 	# "#
-	# , true, 5, true, true, false, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn finalize_tx(&self, token: Token, slate: VersionedSlate)
-		-> Result<VersionedSlate, ErrorKind>;
+	*/
+
+	fn export_view_key(
+		&self,
+		name: Option<String>,
+		password: String,
+		account: Option<String>,
+	) -> Result<ViewKey, ErrorKind>;
 
 	/**
-	Networked version of [Owner::post_tx](struct.Owner.html#method.post_tx).
+	Networked version of [Owner::create_watch_only_wallet](struct.Owner.html#method.create_watch_only_wallet).
 
 	# Json rpc example
 
@@ -983,49 +4006,19 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"id": 1,
-		"method": "post_tx",
+		"method": "create_watch_only_wallet",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx": {
-			"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"body": {
-				"inputs": [
-					{
-						"features": "Coinbase",
-						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e"
-					},
-					{
-						"features": "Coinbase",
-						"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e"
-					}
-				],
-				"kernels": [
-					{
-						"features": "Plain",
-						"fee": "700000",
-						"lock_height": "0",
-						"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
-						"excess_sig": "66074d25a751c4743342c90ad8ead9454daa00d9b9aed29bca321036d16c4b4da58bc9999cea000f52b45347c1c46a3a4f3f70719696a09289ede2a9c87b27fd"
-					}
-				],
-				"outputs": [
-					{
-						"features": "Plain",
-						"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
-						"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
-					},
-					{
-						"features": "Plain",
-						"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
-						"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
-					}
-				]
-
+			"name": null,
+			"view_key": {
+				"is_floo": false,
+				"parent_key_id": "0200000000000000000000000000000000",
+				"public_key": "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+				"switch_public_key": "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+				"chain_code": "5d4a3b1f9e8c7a6b5d4a3b1f9e8c7a6b5d4a3b1f9e8c7a6b5d4a3b1f9e8c7a6b",
+				"rewind_hash": "0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0"
 			}
 		},
-		"fluff": false
-		}
+		"id": 1
 	}
 	# "#
 	# ,
@@ -1034,18 +4027,24 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Err": {
+				"WalletSeedExists": "A wallet already exists in the top level directory"
+			}
 		}
 	}
 	# "#
-	# , true, 5, true, true, true, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
+	*/
 
-	fn post_tx(&self, token: Token, tx: TransactionV3, fluff: bool) -> Result<(), ErrorKind>;
+	fn create_watch_only_wallet(
+		&self,
+		name: Option<String>,
+		view_key: ViewKey,
+	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::cancel_tx](struct.Owner.html#method.cancel_tx).
+	Networked version of [Owner::get_seed_quiz](struct.Owner.html#method.get_seed_quiz).
 
 	# Json rpc example
 
@@ -1054,11 +4053,11 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "cancel_tx",
+		"method": "get_seed_quiz",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx_id": null,
-			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
+			"name": null,
+			"password": "",
+			"count": 3
 		},
 		"id": 1
 	}
@@ -1069,22 +4068,22 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok": [1, 2, 3]
 		}
 	}
 	# "#
-	# , true, 5, true, true, false, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn cancel_tx(
+	*/
+	fn get_seed_quiz(
 		&self,
-		token: Token,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<(), ErrorKind>;
+		name: Option<String>,
+		password: String,
+		count: u32,
+	) -> Result<Vec<usize>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_stored_tx](struct.Owner.html#method.get_stored_tx).
+	Networked version of [Owner::verify_seed_words](struct.Owner.html#method.verify_seed_words).
 
 	# Json rpc example
 
@@ -1093,102 +4092,78 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_stored_tx",
-		"id": 1,
+		"method": "verify_seed_words",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"tx": {
-				"amount_credited": "59993000000",
-				"amount_debited": "120000000000",
-				"confirmation_ts": "2019-01-15T16:01:26Z",
-				"confirmed": false,
-				"creation_ts": "2019-01-15T16:01:26Z",
-				"fee": "7000000",
-				"id": 5,
-				"messages": {
-					"messages": [
-						{
-							"id": "0",
-							"message": null,
-							"message_sig": null,
-							"public_key": "033ac2158fa0077f087de60c19d8e431753baa5b63b6e1477f05a2a6e7190d4592"
-						},
-						{
-							"id": "1",
-							"message": null,
-							"message_sig": null,
-							"public_key": "024f9bc78c984c78d6e916d3a00746aa30fa1172124c8dbc0cbddcb7b486719bc7"
-						}
-					]
+			"name": null,
+			"password": "",
+			"words": [
+				{
+					"position": 1,
+					"word": "fat"
 				},
-				"num_inputs": 2,
-				"num_outputs": 1,
-				"parent_key_id": "0200000000000000000000000000000000",
-				"stored_tx": "0436430c-2b02-624c-2032-570501212b00.epictx",
-				"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00",
-				"tx_type": "TxSent",
-				"kernel_excess": null,
-				"kernel_lookup_min_height": null
-			}
+				{
+					"position": 2,
+					"word": "twenty"
+				}
+			]
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": true
 		}
 	}
 	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn verify_seed_words(
+		&self,
+		name: Option<String>,
+		password: String,
+		words: Vec<SeedWord>,
+	) -> Result<bool, ErrorKind>;
+
+	/**
+	Networked version of [Owner::seed_verified](struct.Owner.html#method.seed_verified).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "seed_verified",
+		"params": {
+			"name": null
+		},
+		"id": 1
+	}
+	# "#
 	# ,
 	# r#"
 	{
-		"jsonrpc": "2.0",
 		"id": 1,
+		"jsonrpc": "2.0",
 		"result": {
-			"Ok": {
-				"body": {
-				"inputs": [
-					{
-						"commit": "09d8836ffd38ffca42567ef965fdcf1f35b05aeb357664d70cd482438ca0ca0c9e",
-						"features": "Coinbase"
-					},
-					{
-						"commit": "089be87c488db1e7c783b19272a83b23bce56a5263163554b345c6f7ffedac517e",
-						"features": "Coinbase"
-					}
-				],
-				"kernels": [
-					{
-						"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-						"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-						"features": "Plain",
-						"fee": "700000",
-						"lock_height": "0"
-					}
-				],
-				"outputs": [
-					{
-						"commit": "091454e23b4dbc71f546a41035d69f4c87d0f6efb5ceb119cc0d2eef80ba1928d7",
-						"features": "Plain",
-						"proof": "1a32a93de1dad833b4ae66d042784c435f60ac452f769d2d778772b3e2f2ca9fb16191636222b35866f273935f657ff37e1d38b877e12b7bcce98b1aa71f47a701b9ed8c648e2d6ab18ac0f8f7cf4a7c0aebb2c15681a684ec6f4d385e5db20e7bf9e6f3d8554ada1b82ac2fa9b77cb0a4c4c6b6c740d938fc0c6031a1cc0c0839701e6dab439c4dcdb32ca87d510b582efbabe8f8b783a330bc2c4451d1c2949a6ad901d40f7abc6103fadebba22016a955eaec4a0215398afbc7d22a4ad5bf3103446f4fe5440ded3bd9db607a69b8ca7c005c09e82fa367febc532b8d5c573e2bcc65a972bf76cea98943d9baaf209c84b4b70d56444c22cd334c7299000122de110f957b7af1f4d7f3816e053c94731113fd098bd2c0ccbe4c19152dd07a8d137b453e5a9d19cca576b494f448c5673babf9122297e4d2f4bd4a5a768c4da040527816d6ff91edb57da4053df167a44d2d5cf194bf30a47bcdd4ff541638b3db02e8ac882fb00767bf50fe5bf1b6077c8ad4f163ce75f21c99f708a9bcc0676034351e5ca68894550fcca5ee868d3d9d87e164612f09c79f2676a4acd8a8266e0f794c49318f8a1595ee1ff4e55e9cf5f3361cc473a032bd3bbd36a085f0c03f9b451b472d6a6a7ea9d858fd42a49c2e68c25bf8f18dd8e691168fe6f10602c6ec04cbc2601afa479294da84ecb79bc9b225d8758f682a2df52882c586ead779711258a9443e43365df9d326ca9052615ce33efac4bd0452a18d5b294b9fcf86e860786a692bfbd84a8bf3a751adedd978b969177cd8897871c43cd28df40a4beefcc86b10e6822ba18673f396294c799e756c8a5f03c92499127ec567e9f5b794442c63be7119ce741e4e056f502ca4809f7c76dd6dad754a1b31201ca2e2540e125637e1da5d16c61e3bea90ded06892076268893c167e0faed26172f304900e"
-					},
-					{
-						"commit": "09414416856d650cd42abad97943f8ea32ff19e7d5d10201ff790d1ca941f578ed",
-						"features": "Plain",
-						"proof": "bdd12075099d53912b42073fd9c2841f2e21dff01656e7f909e1bbd30ada9a18b2f645128676ecddaecbffdcce43e9ff0e850acbce0f9a1e3fc525a7424d09040da752a8db0c8173f31ec4696bf007bf76801f63cedeadc66f4198836494de20a3d48150776c819d2e0a8ef376622d8a1cef78cd6928b3aa38883f51594fa50c3a772c539071c1c05ac4fce08768076618e2d5c7b3d46e28f1459f84f143a943957a4294011b093caf0e077020caf0668b379525df35f626641be6e81d7b711f1b32a98596c1829b9671d574f793e7f9f08c9118bdda60577053456caace5071cc14b10a67205e1c263bb53990fcf4fbcaea9cae652bd9e7ad6c1573ff96cd9271ecf0fabb895cea13b80d59bf7093fa03907911f526cb60df2bf0d3e2d4b81be4bbae55c466d6b221fa70cb145e6550e37856d080304e104fb23be97ae1499b4d3a2f7a4550545a03c20d374c081ac4f592477e23a20f418bcc59d9b02f665b898400a74350b88d793d383a0dc57618d58711e85e221383abb170c4a7f1640f30f2fc8258074f882b56453befecf3a61ed194a8ad98d1f6ab38c565b7cde60a7bb258066d9c5363c6bd618a9b3473b70a516ad4a67c2571e62fec4970eb4df902143aa130d333825f0a4cde9f93d8249c32f26bfadb26be8a5ceb6b5b6cdd076baa1cbde1973d83e64a1b35075dba69682e51cedfb82484276d56cf9e0601a272c0148ce070c6019ab2882405900164871f6b59d2c2a9f5d92674fe58cd9e036752eae8fb58e0fc29e3d59330ac92c1f263988f67add07a22770c381f29a602785244dbd46e4416ca56f25fe0cdd21714bcdf58c28329e22124247416b8de61297b6bd1630b93692a3a81c3107689f35cf4be5a8472b31552973ef2bcee5a298a858a768eefd0e31a3936790dd1c6e1379fffa0235c188b2c0f8b8b41abb84c32c608"
-					}
-				]
-				},
-				"offset": "d202964900000000d302964900000000d402964900000000d502964900000000"
-			}
+			"Ok": false
 		}
 	}
 	# "#
-	# , true, 5, true, true, false, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn get_stored_tx(
-		&self,
-		token: Token,
-		tx: &TxLogEntry,
-	) -> Result<Option<TransactionV3>, ErrorKind>;
+	*/
+	fn seed_verified(&self, name: Option<String>) -> Result<bool, ErrorKind>;
 
 	/**
-	Networked version of [Owner::verify_slate_messages](struct.Owner.html#method.verify_slate_messages).
+	Networked version of [Owner::change_password](struct.Owner.html#method.change_password).
 
 	# Json rpc example
 
@@ -1197,82 +4172,37 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "verify_slate_messages",
-		"id": 1,
+		"method": "change_password",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"slate": {
-				"amount": "1457920000",
-				"fee": "8000000",
-				"height": "4",
-				"id": "0436430c-2b02-624c-2032-570501212b00",
-				"lock_height": "4",
-				"ttl_cutoff_height": null,
-				"num_participants": 2,
-				"participant_data": [
-				{
-					"id": "0",
-					"message": "my message",
-					"message_sig": "8f07ddd5e9f5179cff19486034181ed76505baaad53e5d994064127b56c5841b1d4c1358be398f801eb90d933774b5218fa7e769b11c4c640402253353656f75",
-					"part_sig": null,
-					"public_blind_excess": "034b4df2f0558b73ea72a1ca5c4ab20217c66bbe0829056fca7abe76888e9349ee",
-					"public_nonce": "031b84c5567b126440995d3ed5aaba0565d71e1834604819ff9c17f5e9d5dd078f"
-				}
-				],
-				"tx": {
-					"body": {
-						"inputs": [
-						{
-							"commit": "08e1da9e6dc4d6e808a718b2f110a991dd775d65ce5ae408a4e1f002a4961aa9e7",
-							"features": "Coinbase"
-						}
-						],
-						"kernels": [
-						{
-							"excess": "000000000000000000000000000000000000000000000000000000000000000000",
-							"excess_sig": "00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-							"features": "HeightLocked",
-							"fee": "8000000",
-							"lock_height": "4"
-						}
-						],
-						"outputs": [
-						{
-							"commit": "094be57c91787fc2033d5d97fae099f1a6ddb37ea48370f1a138f09524c767fdd3",
-							"features": "Plain",
-							"proof": "2a42e9e902b70ce44e1fccb14de87ee0a97100bddf12c6bead1b9c5f4eb60300f29c13094fa12ffeee238fb4532b18f6b61cf51b23c1c7e1ad2e41560dc27edc0a2b9e647a0b3e4e806fced5b65e61d0f1f5197d3e2285c632d359e27b6b9206b2caffea4f67e0c7a2812e7a22c134b98cf89bd43d9f28b8bec25cce037a0ac5b1ae8f667e54e1250813a5263004486b4465ad4e641ab2b535736ea26535a11013564f08f483b7dab1c2bcc3ee38eadf2f7850eff7e3459a4bbabf9f0cf6c50d0c0a4120565cd4a2ce3e354c11721cd695760a24c70e0d5a0dfc3c5dcd51dfad6de2c237a682f36dc0b271f21bb3655e5333016aaa42c2efa1446e5f3c0a79ec417c4d30f77556951cb0f05dbfafb82d9f95951a9ea241fda2a6388f73ace036b98acce079f0e4feebccc96290a86dcc89118a901210b245f2d114cf94396e4dbb461e82aa26a0581389707957968c7cdc466213bb1cd417db207ef40c05842ab67a01a9b96eb1430ebc26e795bb491258d326d5174ad549401059e41782121e506744af8af9d8e493644a87d613600888541cbbe538c625883f3eb4aa3102c5cfcc25de8e97af8927619ce6a731b3b8462d51d993066b935b0648d2344ad72e4fd70f347fbd81041042e5ea31cc7b2e3156a920b80ecba487b950ca32ca95fae85b759c936246ecf441a9fdd95e8fee932d6782cdec686064018c857efc47fb4b2a122600d5fdd79af2486f44df7e629184e1c573bc0a9b3feb40b190ef2861a1ab45e2ac2201b9cd42e495deea247269820ed32389a2810ad6c0f9a296d2a2d9c54089fed50b7f5ecfcd33ab9954360e1d7f5598c32128cfcf2a1d8bf14616818da8a5343bfa88f0eedf392e9d4ab1ace1b60324129cd4852c2e27813a9cf71a6ae6229a4fcecc1a756b3e664c5f50af333082616815a3bec8fc0b75b8e4e767d719"
-						}
-						]
-					},
-					"offset": "d202964900000000d302964900000000d402964900000000d502964900000000",
-					"payment_proof": null
-				},
-				"version_info": {
-					"orig_version": 3,
-					"version": 3,
-					"block_header_version": 6
-				}
-			}
-		}
+			"name": null,
+			"old": "",
+			"new": "new_password"
+		},
+		"id": 1
 	}
 	# "#
 	# ,
 	# r#"
 	{
-		"jsonrpc": "2.0",
 		"id": 1,
+		"jsonrpc": "2.0",
 		"result": {
 			"Ok": null
 		}
 	}
 	# "#
-	# ,true, 0 ,false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
 	*/
-	fn verify_slate_messages(&self, token: Token, slate: VersionedSlate) -> Result<(), ErrorKind>;
+	fn change_password(
+		&self,
+		name: Option<String>,
+		old: String,
+		new: String,
+	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::scan](struct.Owner.html#method.scan).
+	Networked version of [Owner::recover_from_mnemonic](struct.Owner.html#method.recover_from_mnemonic).
 
 	# Json rpc example
 
@@ -1281,11 +4211,11 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "scan",
+		"method": "recover_from_mnemonic",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"start_height": 1,
-			"delete_unconfirmed": false
+			"name": null,
+			"mnemonic": "fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch",
+			"password": "new_password"
 		},
 		"id": 1
 	}
@@ -1300,18 +4230,18 @@ pub trait OwnerRpcS {
 		}
 	}
 	# "#
-	# , true, 1, false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn scan(
+	*/
+	fn recover_from_mnemonic(
 		&self,
-		token: Token,
-		start_height: Option<u64>,
-		delete_unconfirmed: bool,
+		name: Option<String>,
+		mnemonic: String,
+		password: String,
 	) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::node_height](struct.Owner.html#method.node_height).
+	Networked version of [Owner::get_delete_token](struct.Owner.html#method.get_delete_token).
 
 	# Json rpc example
 
@@ -1320,9 +4250,10 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "node_height",
+		"method": "get_delete_token",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
+			"name": null,
+			"password": ""
 		},
 		"id": 1
 	}
@@ -1333,75 +4264,89 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": {
-				"header_hash": "d4b3d3c40695afd8c7760f8fc423565f7d41310b7a4e1c4a4a7950a66f16240d",
-				"height": "5",
-				"updated_from_node": true
-			}
+			"Ok": "e7a4c1ef0b4d5a2d8f6c3b9a1e0d7f42"
 		}
 	}
 	# "#
-	# , true, 5, false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
-	 */
-	fn node_height(&self, token: Token) -> Result<NodeHeightResult, ErrorKind>;
+	*/
+	fn get_delete_token(&self, name: Option<String>, password: String)
+		-> Result<String, ErrorKind>;
 
 	/**
-		Initializes the secure JSON-RPC API. This function must be called and a shared key
-		established before any other OwnerAPI JSON-RPC function can be called.
-
-		The shared key will be derived using ECDH with the provided public key on the secp256k1 curve. This
-		function will return its public key used in the derivation, which the caller should multiply by its
-		private key to derive the shared key.
-
-		Once the key is established, all further requests and responses are encrypted and decrypted with the
-		following parameters:
-		* AES-256 in GCM mode with 128-bit tags and 96 bit nonces
-		* 12 byte nonce which must be included in each request/response to use on the decrypting side
-		* Empty vector for additional data
-		* Suffix length = AES-256 GCM mode tag length = 16 bytes
-		*
+	Networked version of [Owner::delete_wallet](struct.Owner.html#method.delete_wallet).
 
-		Fully-formed JSON-RPC requests (as documented) should be encrypted using these parameters, encoded
-		into base64 and included with the one-time nonce in a request for the `encrypted_request_v3` method
-		as follows:
+	# Json rpc example
 
-		```
-		# let s = r#"
-		{
-			 "jsonrpc": "2.0",
-			 "method": "encrypted_request_v3",
-			 "id": "1",
-			 "params": {
-					"nonce": "ef32...",
-					"body_enc": "e0bcd..."
-			 }
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "delete_wallet",
+		"params": {
+			"name": null,
+			"confirmation_token": "e7a4c1ef0b4d5a2d8f6c3b9a1e0d7f42"
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
-		# "#;
-		```
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn delete_wallet(
+		&self,
+		name: Option<String>,
+		confirmation_token: String,
+	) -> Result<(), ErrorKind>;
 
-		With a typical response being:
+	/**
+	Networked version of [Owner::start_updated](struct.Owner.html#method.start_updater).
 
-		```
-		# let s = r#"{
-		{
-			 "jsonrpc": "2.0",
-			 "method": "encrypted_response_v3",
-			 "id": "1",
-			 "Ok": {
-					"nonce": "340b...",
-					"body_enc": "3f09c..."
-			 }
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "start_updater",
+		"params": {
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"frequency": 30000
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
 		}
-		# }"#;
-		```
-
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
 	*/
 
-	fn init_secure_api(&self, ecdh_pubkey: ECDHPubkey) -> Result<ECDHPubkey, ErrorKind>;
+	fn start_updater(&self, token: Token, frequency: u32) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_top_level_directory](struct.Owner.html#method.get_top_level_directory).
+	Networked version of [Owner::stop_updater](struct.Owner.html#method.stop_updater).
 
 	# Json rpc example
 
@@ -1410,9 +4355,8 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_top_level_directory",
-		"params": {
-		},
+		"method": "stop_updater",
+		"params": null,
 		"id": 1
 	}
 	# "#
@@ -1422,18 +4366,17 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": "/doctest/dir"
+			"Ok": null
 		}
 	}
 	# "#
-	# , true, 5, false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
 	*/
-
-	fn get_top_level_directory(&self) -> Result<String, ErrorKind>;
+	fn stop_updater(&self) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::set_top_level_directory](struct.Owner.html#method.set_top_level_directory).
+	Networked version of [Owner::get_updater_messages](struct.Owner.html#method.get_updater_messages).
 
 	# Json rpc example
 
@@ -1442,9 +4385,9 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "set_top_level_directory",
+		"method": "get_updater_messages",
 		"params": {
-			"dir": "/home/wallet_user/my_wallet_dir"
+			"count": 1
 		},
 		"id": 1
 	}
@@ -1455,73 +4398,30 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok": []
 		}
 	}
 	# "#
-	# , true, 5, false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
 	*/
 
-	fn set_top_level_directory(&self, dir: String) -> Result<(), ErrorKind>;
+	fn get_updater_messages(&self, count: u32) -> Result<Vec<StatusMessage>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::create_config](struct.Owner.html#method.create_config).
+	Networked version of [Owner::get_public_address](struct.Owner.html#method.get_public_address).
 
 	# Json rpc example
 
-	Both the `wallet_config` and `logging_config` parameters can be `null`, the examples
-	below are for illustration. Note that the values provided for `log_file_path` and `data_file_dir`
-	will be ignored and replaced with the actual values based on the value of `get_top_level_directory`
-
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "create_config",
+		"method": "get_public_address",
 		"params": {
-			"chain_type": "Mainnet",
-			"wallet_config": {
-				"epicbox_domain": "epicbox.io",
-				"epicbox_address_index": 0,
-				"chain_type": null,
-				"api_listen_interface": "127.0.0.1",
-				"api_listen_port": 3415,
-				"owner_api_listen_port": 3420,
-				"api_secret_path": null,
-				"node_api_secret_path": null,
-				"check_node_api_http_addr": "http://127.0.0.1:3413",
-				"owner_api_include_foreign": false,
-				"data_file_dir": "/path/to/data/file/dir",
-				"no_commit_cache": null,
-				"tls_certificate_file": null,
-				"tls_certificate_key": null,
-				"dark_background_color_scheme": null,
-				"keybase_notify_ttl": null
-			},
-			"logging_config": {
-				"log_to_stdout": false,
-				"stdout_log_level": "Info",
-				"log_to_file": true,
-				"file_log_level": "Debug",
-				"log_file_path": "/path/to/log/file",
-				"log_file_append": true,
-				"log_max_size": null,
-				"log_max_files": null,
-				"tui_running": null
-			},
-			"tor_config" : {
-				"use_tor_listener": true,
-				"socks_proxy_addr": "127.0.0.1:9050",
-				"send_config_dir": "."
-			},
-			"epicbox_config" : {
-				"epicbox_domain": "epicbox.io",
-				"epicbox_port": 443,
-				"epicbox_protocol_unsecure": false,
-				"epicbox_address_index": 0
-			}
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"derivation_index": 0
 		},
 		"id": 1
 	}
@@ -1532,24 +4432,26 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok":  {
+				"domain": "",
+				"port": 0,
+				"public_key": "esWVpwMwUyYoxta4EpGPQQEBYdm3wBqCcggVswNyquoLHaLjFdwq"
+			}
 		}
 	}
 	# "#
-	# , true, 5, false, false, false, false);
+	# , true, 0, false, false, false, false);
 	```
 	*/
-	fn create_config(
+
+	fn get_public_address(
 		&self,
-		chain_type: global::ChainTypes,
-		wallet_config: Option<WalletConfig>,
-		logging_config: Option<LoggingConfig>,
-		tor_config: Option<TorConfig>,
-		epicbox_config: Option<EpicboxConfig>,
-	) -> Result<(), ErrorKind>;
+		token: Token,
+		derivation_index: u32,
+	) -> Result<EpicboxAddress, ErrorKind>;
 
 	/**
-	Networked version of [Owner::create_wallet](struct.Owner.html#method.create_wallet).
+	Networked version of [Owner::get_public_proof_address](struct.Owner.html#method.get_public_proof_address).
 
 	# Json rpc example
 
@@ -1558,12 +4460,10 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "create_wallet",
+		"method": "get_public_proof_address",
 		"params": {
-			"name": null,
-			"mnemonic": null,
-			"mnemonic_length": 0,
-			"password": "my_secret_password"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"derivation_index": 0
 		},
 		"id": 1
 	}
@@ -1574,7 +4474,7 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok": "32cdd63928854f8b2628b1dce4626ddcdf35d56cb7cfdf7d64cca5822b78d4d3"
 		}
 	}
 	# "#
@@ -1582,16 +4482,14 @@ pub trait OwnerRpcS {
 	```
 	*/
 
-	fn create_wallet(
+	fn get_public_proof_address(
 		&self,
-		name: Option<String>,
-		mnemonic: Option<String>,
-		mnemonic_length: u32,
-		password: String,
-	) -> Result<(), ErrorKind>;
+		token: Token,
+		derivation_index: u32,
+	) -> Result<PubAddress, ErrorKind>;
 
 	/**
-	Networked version of [Owner::open_wallet](struct.Owner.html#method.open_wallet).
+	Networked version of [Owner::proof_address_from_onion_v3](struct.Owner.html#method.proof_address_from_onion_v3).
 
 	# Json rpc example
 
@@ -1600,10 +4498,9 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "open_wallet",
+		"method": "proof_address_from_onion_v3",
 		"params": {
-			"name": null,
-			"password": "my_secret_password"
+			"address_v3": "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid"
 		},
 		"id": 1
 	}
@@ -1614,7 +4511,7 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": "d096b3cb75986b3b13f80b8f5243a9edf0af4c74ac37578c5a12cfb5b59b1868"
+			"Ok": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb"
 		}
 	}
 	# "#
@@ -1622,21 +4519,21 @@ pub trait OwnerRpcS {
 	```
 	*/
 
-	fn open_wallet(&self, name: Option<String>, password: String) -> Result<Token, ErrorKind>;
+	fn proof_address_from_onion_v3(&self, address_v3: String) -> Result<PubAddress, ErrorKind>;
 
 	/**
-	Networked version of [Owner::close_wallet](struct.Owner.html#method.close_wallet).
-
-	# Json rpc example
-
+	Networked version of [Owner::retrieve_payment_proof](struct.Owner.html#method.retrieve_payment_proof).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "close_wallet",
+		"method": "retrieve_payment_proof",
 		"params": {
-			"name": null
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"refresh_from_node": true,
+			"tx_id": null,
+			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
 		},
 		"id": 1
 	}
@@ -1647,30 +4544,47 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok": {
+				"amount": "600000000",
+				"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+				"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
+				"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
+				"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
+				"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
+			}
 		}
 	}
 	# "#
-	# , true, 0, false, false, false, false);
+	# , true, 5, true, true, true, true);
 	```
 	*/
 
-	fn close_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
+	fn retrieve_payment_proof(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+	) -> Result<PaymentProof, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_mnemonic](struct.Owner.html#method.get_mnemonic).
-
-	# Json rpc example
-
+	Networked version of [Owner::verify_payment_proof](struct.Owner.html#method.verify_payment_proof).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_mnemonic",
+		"method": "verify_payment_proof",
 		"params": {
-			"name": null,
-			"password": ""
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"proof": {
+				"amount": "600000000",
+				"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+				"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
+				"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
+				"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
+				"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
+			}
 		},
 		"id": 1
 	}
@@ -1681,31 +4595,34 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": "fat twenty mean degree forget shell check candy immense awful flame next during february bulb bike sun wink theory day kiwi embrace peace lunch"
+			"Ok": [
+				true,
+				false
+			]
 		}
 	}
 	# "#
-	# , true, 0, false, false, false, false);
+	# , true, 5, true, true, true, true);
 	```
 	*/
 
-	fn get_mnemonic(&self, name: Option<String>, password: String) -> Result<String, ErrorKind>;
+	fn verify_payment_proof(
+		&self,
+		token: Token,
+		proof: PaymentProof,
+	) -> Result<(bool, bool), ErrorKind>;
 
 	/**
-	Networked version of [Owner::change_password](struct.Owner.html#method.change_password).
-
-	# Json rpc example
-
+	Networked version of [Owner::retrieve_payment_proofs](struct.Owner.html#method.retrieve_payment_proofs).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "change_password",
+		"method": "retrieve_payment_proofs",
 		"params": {
-			"name": null,
-			"old": "",
-			"new": "new_password"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"refresh_from_node": true
 		},
 		"id": 1
 	}
@@ -1716,33 +4633,53 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Ok": [
+				true,
+				[
+					{
+						"tx_id": 5,
+						"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00",
+						"proof": {
+							"amount": "600000000",
+							"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+							"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
+							"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
+							"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
+							"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
+						}
+					}
+				]
+			]
 		}
 	}
 	# "#
-	# , true, 0, false, false, false, false);
+	# , true, 5, true, true, true, true);
 	```
 	*/
-	fn change_password(
+
+	fn retrieve_payment_proofs(
 		&self,
-		name: Option<String>,
-		old: String,
-		new: String,
-	) -> Result<(), ErrorKind>;
+		token: Token,
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<PaymentProofEntry>), ErrorKind>;
 
 	/**
-	Networked version of [Owner::delete_wallet](struct.Owner.html#method.delete_wallet).
-
-	# Json rpc example
-
+	Networked version of [Owner::verify_payment_proof_offline](struct.Owner.html#method.verify_payment_proof_offline).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "delete_wallet",
+		"method": "verify_payment_proof_offline",
 		"params": {
-			"name": null
+			"proof": {
+				"amount": "600000000",
+				"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
+				"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
+				"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
+				"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
+				"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
+			}
 		},
 		"id": 1
 	}
@@ -1759,23 +4696,23 @@ pub trait OwnerRpcS {
 	# "#
 	# , true, 0, false, false, false, false);
 	```
-	*/
-	fn delete_wallet(&self, name: Option<String>) -> Result<(), ErrorKind>;
-
-	/**
-	Networked version of [Owner::start_updated](struct.Owner.html#method.start_updater).
+	*/
 
-	# Json rpc example
+	fn verify_payment_proof_offline(&self, proof: PaymentProof) -> Result<(), ErrorKind>;
 
+	/**
+	Networked version of [Owner::create_payment_request](struct.Owner.html#method.create_payment_request).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "start_updater",
+		"method": "create_payment_request",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"frequency": 30000
+			"amount": 0,
+			"memo": "Invoice 42",
+			"expiry": null
 		},
 		"id": 1
 	}
@@ -1786,7 +4723,9 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Err": {
+				"InvalidPaymentRequest": "amount must be greater than zero"
+			}
 		}
 	}
 	# "#
@@ -1794,20 +4733,31 @@ pub trait OwnerRpcS {
 	```
 	*/
 
-	fn start_updater(&self, token: Token, frequency: u32) -> Result<(), ErrorKind>;
+	fn create_payment_request(
+		&self,
+		token: Token,
+		amount: u64,
+		memo: Option<String>,
+		expiry: Option<DateTime<Utc>>,
+	) -> Result<PaymentRequest, ErrorKind>;
 
 	/**
-	Networked version of [Owner::stop_updater](struct.Owner.html#method.stop_updater).
-
-	# Json rpc example
-
+	Networked version of [Owner::verify_payment_request](struct.Owner.html#method.verify_payment_request).
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "stop_updater",
-		"params": null,
+		"method": "verify_payment_request",
+		"params": {
+			"request": {
+				"address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
+				"amount": "2000000000",
+				"memo": "Invoice 42",
+				"expiry": null,
+				"signature": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406"
+			}
+		},
 		"id": 1
 	}
 	# "#
@@ -1817,17 +4767,20 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": null
+			"Err": {
+				"InvalidPaymentRequest": "invalid signature"
+			}
 		}
 	}
 	# "#
 	# , true, 0, false, false, false, false);
 	```
 	*/
-	fn stop_updater(&self) -> Result<(), ErrorKind>;
+
+	fn verify_payment_request(&self, request: PaymentRequest) -> Result<InitTxArgs, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_updater_messages](struct.Owner.html#method.get_updater_messages).
+	Networked version of [Owner::verify_output_claim](struct.Owner.html#method.verify_output_claim).
 
 	# Json rpc example
 
@@ -1836,9 +4789,14 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_updater_messages",
+		"method": "verify_output_claim",
 		"params": {
-			"count": 1
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"claim": {
+				"commit": "090000000000000000000000000000000000000000000000000000000000000001",
+				"value": "2000000000",
+				"blinding_factor": "0000000000000000000000000000000000000000000000000000000000000000"
+			}
 		},
 		"id": 1
 	}
@@ -1849,18 +4807,28 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": []
+			"Ok": {
+				"height": null,
+				"is_coinbase": null,
+				"on_chain": false,
+				"opening_valid": false,
+				"range_proof_valid": false
+			}
 		}
 	}
 	# "#
 	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn get_updater_messages(&self, count: u32) -> Result<Vec<StatusMessage>, ErrorKind>;
+	 */
+	fn verify_output_claim(
+		&self,
+		token: Token,
+		claim: OutputClaim,
+	) -> Result<OutputClaimVerification, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_public_address](struct.Owner.html#method.get_public_address).
+	Networked version of [Owner::trace_output](struct.Owner.html#method.trace_output),
+	taking the commitment of the output in hex.
 
 	# Json rpc example
 
@@ -1869,10 +4837,10 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_public_address",
+		"method": "trace_output",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"derivation_index": 0
+			"commit": "090000000000000000000000000000000000000000000000000000000000000001"
 		},
 		"id": 1
 	}
@@ -1883,26 +4851,22 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok":  {
-				"domain": "",
-				"port": 0,
-				"public_key": "esWVpwMwUyYoxta4EpGPQQEBYdm3wBqCcggVswNyquoLHaLjFdwq"
-			}
+			"Ok": null
 		}
 	}
 	# "#
 	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn get_public_address(
+	 */
+	fn trace_output(
 		&self,
 		token: Token,
-		derivation_index: u32,
-	) -> Result<EpicboxAddress, ErrorKind>;
+		commit: String,
+	) -> Result<Option<OutputLineage>, ErrorKind>;
 
 	/**
-	Networked version of [Owner::get_public_proof_address](struct.Owner.html#method.get_public_proof_address).
+	Networked version of [Owner::lock_output](struct.Owner.html#method.lock_output),
+	taking the commitment of the output in hex.
 
 	# Json rpc example
 
@@ -1911,10 +4875,10 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "get_public_proof_address",
+		"method": "lock_output",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"derivation_index": 0
+			"commit": "090000000000000000000000000000000000000000000000000000000000000001"
 		},
 		"id": 1
 	}
@@ -1925,22 +4889,20 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": "32cdd63928854f8b2628b1dce4626ddcdf35d56cb7cfdf7d64cca5822b78d4d3"
+			"Err": {
+				"UnknownOutput": "090000000000000000000000000000000000000000000000000000000000000001"
+			}
 		}
 	}
 	# "#
 	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn get_public_proof_address(
-		&self,
-		token: Token,
-		derivation_index: u32,
-	) -> Result<PubAddress, ErrorKind>;
+	 */
+	fn lock_output(&self, token: Token, commit: String) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::proof_address_from_onion_v3](struct.Owner.html#method.proof_address_from_onion_v3).
+	Networked version of [Owner::unlock_output](struct.Owner.html#method.unlock_output),
+	taking the commitment of the output in hex.
 
 	# Json rpc example
 
@@ -1949,9 +4911,10 @@ pub trait OwnerRpcS {
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "proof_address_from_onion_v3",
+		"method": "unlock_output",
 		"params": {
-			"address_v3": "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid"
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
+			"commit": "090000000000000000000000000000000000000000000000000000000000000001"
 		},
 		"id": 1
 	}
@@ -1962,29 +4925,31 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": "d03c09e9c19bb74aa9ea44e0fe5ae237a9bf40bddf0941064a80913a4459c8bb"
+			"Err": {
+				"UnknownOutput": "090000000000000000000000000000000000000000000000000000000000000001"
+			}
 		}
 	}
 	# "#
 	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn proof_address_from_onion_v3(&self, address_v3: String) -> Result<PubAddress, ErrorKind>;
+	 */
+	fn unlock_output(&self, token: Token, commit: String) -> Result<(), ErrorKind>;
 
 	/**
-	Networked version of [Owner::retrieve_payment_proof](struct.Owner.html#method.retrieve_payment_proof).
+	Networked version of [Owner::import_outputs](struct.Owner.html#method.import_outputs).
+
+	# Json rpc example
+
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "retrieve_payment_proof",
+		"method": "import_outputs",
 		"params": {
 			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"refresh_from_node": true,
-			"tx_id": null,
-			"tx_slate_id": "0436430c-2b02-624c-2032-570501212b00"
+			"outputs": []
 		},
 		"id": 1
 	}
@@ -1996,46 +4961,37 @@ pub trait OwnerRpcS {
 		"jsonrpc": "2.0",
 		"result": {
 			"Ok": {
-				"amount": "600000000",
-				"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
-				"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
-				"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
-				"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
-				"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
+				"imported_value": "0",
+				"num_imported": 0,
+				"num_known": 0,
+				"num_mismatched": 0,
+				"num_not_found": 0
 			}
 		}
 	}
 	# "#
-	# , true, 5, true, true, true, true);
+	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn retrieve_payment_proof(
+	 */
+	fn import_outputs(
 		&self,
 		token: Token,
-		refresh_from_node: bool,
-		tx_id: Option<u32>,
-		tx_slate_id: Option<Uuid>,
-	) -> Result<PaymentProof, ErrorKind>;
+		outputs: Vec<ImportedOutput>,
+	) -> Result<OutputImportSummary, ErrorKind>;
 
 	/**
-	Networked version of [Owner::verify_payment_proof](struct.Owner.html#method.verify_payment_proof).
+	Networked version of [Owner::deduplicate_outputs](struct.Owner.html#method.deduplicate_outputs).
+
+	# Json rpc example
+
 	```
 	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
 	# r#"
 	{
 		"jsonrpc": "2.0",
-		"method": "verify_payment_proof",
+		"method": "deduplicate_outputs",
 		"params": {
-			"token": "d202964900000000d302964900000000d402964900000000d502964900000000",
-			"proof": {
-				"amount": "600000000",
-				"excess": "08d09187cb93cf5d6b97b28e8ca529912bf35ec8773d3e9af9b3c174a270dc7f05",
-				"recipient_address": "pa7wkkdgs5bkteha7lykl7ff2wztgdrxxo442xdcq2lnaphe5aidd4id",
-				"recipient_sig": "b9ac5e18fd13ce72923cc47796bd5af09b5247c52da3634c9b934d4e111a43f53f1c55e3f3be36a79450e18f8989d81a0c21c4b2c16c208753a9971a5ffee406",
-				"sender_address": "glg5mojiqvhywjriwhooiytn3tptlvlmw7h567lezssyek3y2tjzznad",
-				"sender_sig": "d26fa48e9a32058b4dc9e9098edd3b98bf2e5286024adc5f7555aa4804acdb1c5506412dfae7d087c138d727da427e14c6c5b7dc2008fc7ed55ab95e8bac3e06"
-			}
+			"token": "d202964900000000d302964900000000d402964900000000d502964900000000"
 		},
 		"id": 1
 	}
@@ -2046,22 +5002,14 @@ pub trait OwnerRpcS {
 		"id": 1,
 		"jsonrpc": "2.0",
 		"result": {
-			"Ok": [
-				true,
-				false
-			]
+			"Ok": []
 		}
 	}
 	# "#
-	# , true, 5, true, true, true, true);
+	# , true, 0, false, false, false, false);
 	```
-	*/
-
-	fn verify_payment_proof(
-		&self,
-		token: Token,
-		proof: PaymentProof,
-	) -> Result<(bool, bool), ErrorKind>;
+	 */
+	fn deduplicate_outputs(&self, token: Token) -> Result<Vec<DeduplicatedOutput>, ErrorKind>;
 
 	/**
 	Networked version of [Owner::set_tor_config](struct.Owner.html#method.set_tor_config).
@@ -2135,6 +5083,38 @@ pub trait OwnerRpcS {
 	```
 	*/
 	fn set_epicbox_config(&self, epicbox_config: Option<EpicboxConfig>) -> Result<(), ErrorKind>;
+
+	/**
+	Networked version of [Owner::set_node_height_guard](struct.Owner.html#method.set_node_height_guard).
+
+	# Json rpc example
+
+	```
+	# epic_wallet_api::doctest_helper_json_rpc_owner_assert_response!(
+	# r#"
+	{
+		"jsonrpc": "2.0",
+		"method": "set_node_height_guard",
+		"params": {
+			"enabled": false
+		},
+		"id": 1
+	}
+	# "#
+	# ,
+	# r#"
+	{
+		"id": 1,
+		"jsonrpc": "2.0",
+		"result": {
+			"Ok": null
+		}
+	}
+	# "#
+	# , true, 0, false, false, false, false);
+	```
+	*/
+	fn set_node_height_guard(&self, enabled: bool) -> Result<(), ErrorKind>;
 }
 
 impl<L, C, K> OwnerRpcS for Owner<L, C, K>
@@ -2152,6 +5132,23 @@ where
 			.map_err(|e| e.kind())
 	}
 
+	fn set_account_metadata(
+		&self,
+		token: Token,
+		label: &String,
+		key: &String,
+		value: Option<String>,
+	) -> Result<AcctPathMapping, ErrorKind> {
+		Owner::set_account_metadata(
+			self,
+			(&token.keychain_mask).as_ref(),
+			label,
+			key,
+			value.as_ref().map(|v| v.as_str()),
+		)
+		.map_err(|e| e.kind())
+	}
+
 	fn set_active_account(&self, token: Token, label: &String) -> Result<(), ErrorKind> {
 		Owner::set_active_account(self, (&token.keychain_mask).as_ref(), label)
 			.map_err(|e| e.kind())
@@ -2175,6 +5172,25 @@ where
 		.map_err(|e| e.kind())
 	}
 
+	fn retrieve_outputs_page(
+		&self,
+		token: Token,
+		include_spent: bool,
+		refresh_from_node: bool,
+		tx_id: Option<u32>,
+		query: OutputPageQuery,
+	) -> Result<(bool, OutputPage), ErrorKind> {
+		Owner::retrieve_outputs_page(
+			self,
+			(&token.keychain_mask).as_ref(),
+			include_spent,
+			refresh_from_node,
+			tx_id,
+			&query,
+		)
+		.map_err(|e| e.kind())
+	}
+
 	fn retrieve_txs(
 		&self,
 		token: Token,
@@ -2192,6 +5208,132 @@ where
 		.map_err(|e| e.kind())
 	}
 
+	fn retrieve_txs_page(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		query: TxPageQuery,
+	) -> Result<(bool, TxPage), ErrorKind> {
+		Owner::retrieve_txs_page(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			&query,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn retrieve_txs_by_tag(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		tags: Vec<String>,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind> {
+		Owner::retrieve_txs_by_tag(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			&tags,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn update_tx_tags(
+		&self,
+		token: Token,
+		tx_id: Option<u32>,
+		tx_slate_id: Option<Uuid>,
+		add: Vec<String>,
+		remove: Vec<String>,
+	) -> Result<Vec<String>, ErrorKind> {
+		Owner::update_tx_tags(
+			self,
+			(&token.keychain_mask).as_ref(),
+			tx_id,
+			tx_slate_id,
+			&add,
+			&remove,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn retrieve_tx_tags(&self, token: Token) -> Result<Vec<String>, ErrorKind> {
+		Owner::retrieve_tx_tags(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn retrieve_contact_txs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		name: String,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind> {
+		Owner::retrieve_contact_txs(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			&name,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn retrieve_pending_receives(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<TxLogEntry>), ErrorKind> {
+		Owner::retrieve_pending_receives(self, (&token.keychain_mask).as_ref(), refresh_from_node)
+			.map_err(|e| e.kind())
+	}
+
+	fn fee_report(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+		period: FeePeriod,
+	) -> Result<(bool, FeeReport), ErrorKind> {
+		Owner::fee_report(
+			self,
+			(&token.keychain_mask).as_ref(),
+			refresh_from_node,
+			period,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn retrieve_contacts(&self, token: Token) -> Result<Vec<Contact>, ErrorKind> {
+		Owner::retrieve_contacts(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn save_contact(&self, token: Token, contact: Contact) -> Result<Contact, ErrorKind> {
+		Owner::save_contact(self, (&token.keychain_mask).as_ref(), &contact).map_err(|e| e.kind())
+	}
+
+	fn remove_contact(&self, token: Token, name: String) -> Result<(), ErrorKind> {
+		Owner::remove_contact(self, (&token.keychain_mask).as_ref(), &name).map_err(|e| e.kind())
+	}
+
+	fn retrieve_send_templates(&self, token: Token) -> Result<Vec<SendTemplate>, ErrorKind> {
+		Owner::retrieve_send_templates(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn get_send_template(&self, token: Token, name: String) -> Result<SendTemplate, ErrorKind> {
+		Owner::get_send_template(self, (&token.keychain_mask).as_ref(), &name).map_err(|e| e.kind())
+	}
+
+	fn save_send_template(
+		&self,
+		token: Token,
+		template: SendTemplate,
+	) -> Result<SendTemplate, ErrorKind> {
+		Owner::save_send_template(self, (&token.keychain_mask).as_ref(), &template)
+			.map_err(|e| e.kind())
+	}
+
+	fn remove_send_template(&self, token: Token, name: String) -> Result<(), ErrorKind> {
+		Owner::remove_send_template(self, (&token.keychain_mask).as_ref(), &name)
+			.map_err(|e| e.kind())
+	}
+
 	fn retrieve_summary_info(
 		&self,
 		token: Token,
@@ -2214,6 +5356,100 @@ where
 		Ok(VersionedSlate::into_version(slate, version))
 	}
 
+	fn estimate_send_tx(&self, token: Token, args: InitTxArgs) -> Result<TxEstimate, ErrorKind> {
+		Owner::estimate_send_tx(self, (&token.keychain_mask).as_ref(), &args).map_err(|e| e.kind())
+	}
+
+	fn init_unsigned_send_tx(&self, args: InitTxArgs) -> Result<SigningRequest, ErrorKind> {
+		Owner::init_unsigned_send_tx(self, args).map_err(|e| e.kind())
+	}
+
+	fn sign_tx_offline(
+		&self,
+		token: Token,
+		request: SigningRequest,
+	) -> Result<VersionedSlate, ErrorKind> {
+		let slate = Owner::sign_tx_offline(self, (&token.keychain_mask).as_ref(), &request)
+			.map_err(|e| e.kind())?;
+		Ok(VersionedSlate::into_version(slate, SlateVersion::V3))
+	}
+
+	fn init_multisig_output(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+	) -> Result<VersionedSlate, ErrorKind> {
+		let slate = Owner::init_multisig_output(self, (&token.keychain_mask).as_ref(), args)
+			.map_err(|e| e.kind())?;
+		Ok(VersionedSlate::into_version(slate, SlateVersion::V3))
+	}
+
+	fn receive_multisig(
+		&self,
+		token: Token,
+		in_slate: VersionedSlate,
+	) -> Result<VersionedSlate, ErrorKind> {
+		let slate = Owner::receive_multisig(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&Slate::from(in_slate),
+		)
+		.map_err(|e| e.kind())?;
+		Ok(VersionedSlate::into_version(slate, SlateVersion::V3))
+	}
+
+	fn swap_start(&self, token: Token, args: SwapStartArgs) -> Result<SwapStep, ErrorKind> {
+		Owner::swap_start(self, (&token.keychain_mask).as_ref(), args).map_err(|e| e.kind())
+	}
+
+	fn swap_process(
+		&self,
+		token: Token,
+		id: Uuid,
+		message: Option<SwapMessage>,
+	) -> Result<SwapStep, ErrorKind> {
+		Owner::swap_process(self, (&token.keychain_mask).as_ref(), id, message)
+			.map_err(|e| e.kind())
+	}
+
+	fn swap_cancel(&self, token: Token, id: Uuid) -> Result<Swap, ErrorKind> {
+		Owner::swap_cancel(self, (&token.keychain_mask).as_ref(), id).map_err(|e| e.kind())
+	}
+
+	fn init_send_tx_batch(
+		&self,
+		token: Token,
+		args: Vec<InitTxArgs>,
+	) -> Result<Vec<InitTxBatchResult>, ErrorKind> {
+		Owner::init_send_tx_batch(self, (&token.keychain_mask).as_ref(), args).map_err(|e| e.kind())
+	}
+
+	fn approve_tx(&self, token: Token, tx_id: u32) -> Result<TxLogEntry, ErrorKind> {
+		// the approver's own token, passed through by the listener
+		match token.keychain_mask {
+			Some(t) => Owner::approve_tx(self, &t, tx_id).map_err(|e| e.kind()),
+			None => Err(ErrorKind::Approval("invalid approval token".to_owned())),
+		}
+	}
+
+	fn queue_send(
+		&self,
+		token: Token,
+		args: InitTxArgs,
+		priority: SendPriority,
+	) -> Result<Uuid, ErrorKind> {
+		Owner::queue_send(self, (&token.keychain_mask).as_ref(), args, priority)
+			.map_err(|e| e.kind())
+	}
+
+	fn get_send_job(&self, token: Token, id: Uuid) -> Result<Option<SendJob>, ErrorKind> {
+		Owner::get_send_job(self, (&token.keychain_mask).as_ref(), id).map_err(|e| e.kind())
+	}
+
+	fn retrieve_send_jobs(&self, token: Token) -> Result<Vec<SendJob>, ErrorKind> {
+		Owner::retrieve_send_jobs(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
 	fn issue_invoice_tx(
 		&self,
 		token: Token,
@@ -2282,6 +5518,31 @@ where
 			.map_err(|e| e.kind())
 	}
 
+	fn cancel_txs(&self, token: Token, filter: TxFilter) -> Result<Vec<TxBulkResult>, ErrorKind> {
+		Owner::cancel_txs(self, (&token.keychain_mask).as_ref(), &filter).map_err(|e| e.kind())
+	}
+
+	fn repost_txs(
+		&self,
+		token: Token,
+		filter: TxFilter,
+		fluff: bool,
+	) -> Result<Vec<TxBulkResult>, ErrorKind> {
+		Owner::repost_txs(self, (&token.keychain_mask).as_ref(), &filter, fluff)
+			.map_err(|e| e.kind())
+	}
+
+	fn sweep_hot_account(
+		&self,
+		token: Token,
+		policy: SweepPolicy,
+	) -> Result<Option<VersionedSlate>, ErrorKind> {
+		let slate = Owner::sweep_hot_account(self, (&token.keychain_mask).as_ref(), &policy)
+			.map_err(|e| e.kind())?;
+		let version = SlateVersion::V3;
+		Ok(slate.map(|s| VersionedSlate::into_version(s, version)))
+	}
+
 	fn get_stored_tx(
 		&self,
 		token: Token,
@@ -2322,10 +5583,152 @@ where
 		.map_err(|e| e.kind())
 	}
 
+	fn scan_account(
+		&self,
+		token: Token,
+		account: String,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<(), ErrorKind> {
+		Owner::scan_account(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&account,
+			start_height,
+			delete_unconfirmed,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn refresh_account(&self, token: Token, account: String) -> Result<bool, ErrorKind> {
+		Owner::refresh_account(self, (&token.keychain_mask).as_ref(), &account)
+			.map_err(|e| e.kind())
+	}
+
+	fn start_scan(
+		&self,
+		token: Token,
+		start_height: Option<u64>,
+		delete_unconfirmed: bool,
+	) -> Result<Uuid, ErrorKind> {
+		Owner::start_scan(
+			self,
+			(&token.keychain_mask).as_ref(),
+			start_height,
+			delete_unconfirmed,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn get_job_status(&self, token: Token, id: Uuid) -> Result<Option<ScanJob>, ErrorKind> {
+		Owner::get_job_status(self, (&token.keychain_mask).as_ref(), id).map_err(|e| e.kind())
+	}
+
+	fn cancel_job(&self, token: Token, id: Uuid) -> Result<bool, ErrorKind> {
+		Owner::cancel_job(self, (&token.keychain_mask).as_ref(), id).map_err(|e| e.kind())
+	}
+
 	fn node_height(&self, token: Token) -> Result<NodeHeightResult, ErrorKind> {
 		Owner::node_height(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
 	}
 
+	fn get_block_header(
+		&self,
+		token: Token,
+		height: u64,
+	) -> Result<Option<BlockHeaderInfo>, ErrorKind> {
+		Owner::get_block_header(self, (&token.keychain_mask).as_ref(), height).map_err(|e| e.kind())
+	}
+
+	fn retrieve_transport_metrics(&self, token: Token) -> Result<Vec<TransportMetrics>, ErrorKind> {
+		Owner::retrieve_transport_metrics(self, (&token.keychain_mask).as_ref())
+			.map_err(|e| e.kind())
+	}
+
+	fn retrieve_node_metrics(&self, token: Token) -> Result<Vec<NodeRequestMetrics>, ErrorKind> {
+		Owner::retrieve_node_metrics(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn retrieve_rpc_metrics(&self, token: Token) -> Result<Vec<RpcMethodMetrics>, ErrorKind> {
+		Owner::retrieve_rpc_metrics(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn get_listener_status(&self, token: Token) -> Result<Vec<ListenerStatus>, ErrorKind> {
+		Owner::get_listener_status(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn retrieve_pinned_keys(&self, token: Token) -> Result<Vec<PinnedKey>, ErrorKind> {
+		Owner::retrieve_pinned_keys(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
+
+	fn unpin_key(&self, token: Token, contact: String) -> Result<bool, ErrorKind> {
+		Owner::unpin_key(self, (&token.keychain_mask).as_ref(), &contact).map_err(|e| e.kind())
+	}
+
+	fn get_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+	) -> Result<Option<MetadataEntry>, ErrorKind> {
+		Owner::get_metadata(self, (&token.keychain_mask).as_ref(), &namespace, &key)
+			.map_err(|e| e.kind())
+	}
+
+	fn retrieve_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+	) -> Result<Vec<MetadataEntry>, ErrorKind> {
+		Owner::retrieve_metadata(self, (&token.keychain_mask).as_ref(), &namespace)
+			.map_err(|e| e.kind())
+	}
+
+	fn get_events(
+		&self,
+		token: Token,
+		since_id: Option<u64>,
+	) -> Result<Vec<StoredEvent>, ErrorKind> {
+		Owner::get_events(self, (&token.keychain_mask).as_ref(), since_id).map_err(|e| e.kind())
+	}
+
+	fn get_audit_log(
+		&self,
+		token: Token,
+		offset: usize,
+		limit: Option<usize>,
+	) -> Result<AuditLogPage, ErrorKind> {
+		Owner::get_audit_log(self, (&token.keychain_mask).as_ref(), offset, limit)
+			.map_err(|e| e.kind())
+	}
+
+	fn set_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+		value: String,
+	) -> Result<(), ErrorKind> {
+		Owner::set_metadata(
+			self,
+			(&token.keychain_mask).as_ref(),
+			&namespace,
+			&key,
+			&value,
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn delete_metadata(
+		&self,
+		token: Token,
+		namespace: String,
+		key: String,
+	) -> Result<bool, ErrorKind> {
+		Owner::delete_metadata(self, (&token.keychain_mask).as_ref(), &namespace, &key)
+			.map_err(|e| e.kind())
+	}
+
 	fn init_secure_api(&self, ecdh_pubkey: ECDHPubkey) -> Result<ECDHPubkey, ErrorKind> {
 		let secp_inst = static_secp_instance();
 		let secp = secp_inst.lock();
@@ -2356,6 +5759,14 @@ where
 		Owner::get_top_level_directory(self).map_err(|e| e.kind())
 	}
 
+	fn reload_config(&self) -> Result<ConfigReload, ErrorKind> {
+		Owner::reload_config(self).map_err(|e| e.kind())
+	}
+
+	fn get_api_versions(&self) -> Result<ApiVersions, ErrorKind> {
+		Owner::get_api_versions(self).map_err(|e| e.kind())
+	}
+
 	fn set_top_level_directory(&self, dir: String) -> Result<(), ErrorKind> {
 		Owner::set_top_level_directory(self, &dir).map_err(|e| e.kind())
 	}
@@ -2384,6 +5795,7 @@ where
 		name: Option<String>,
 		mnemonic: Option<String>,
 		mnemonic_length: u32,
+		language: Option<MnemonicLanguage>,
 		password: String,
 	) -> Result<(), ErrorKind> {
 		let n = name.as_ref().map(|s| s.as_str());
@@ -2391,13 +5803,25 @@ where
 			Some(s) => Some(ZeroingString::from(s)),
 			None => None,
 		};
-		Owner::create_wallet(self, n, m, mnemonic_length, ZeroingString::from(password))
-			.map_err(|e| e.kind())
+		Owner::create_wallet(
+			self,
+			n,
+			m,
+			mnemonic_length,
+			language,
+			ZeroingString::from(password),
+		)
+		.map_err(|e| e.kind())
 	}
 
-	fn open_wallet(&self, name: Option<String>, password: String) -> Result<Token, ErrorKind> {
+	fn open_wallet(
+		&self,
+		name: Option<String>,
+		password: String,
+		scope: Option<TokenScope>,
+	) -> Result<Token, ErrorKind> {
 		let n = name.as_ref().map(|s| s.as_str());
-		let sec_key = Owner::open_wallet(self, n, ZeroingString::from(password), true)
+		let sec_key = Owner::open_wallet(self, n, ZeroingString::from(password), true, scope)
 			.map_err(|e| e.kind())?;
 		Ok(Token {
 			keychain_mask: sec_key,
@@ -2416,6 +5840,53 @@ where
 		Ok(format!("{}", &*res))
 	}
 
+	fn export_view_key(
+		&self,
+		name: Option<String>,
+		password: String,
+		account: Option<String>,
+	) -> Result<ViewKey, ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::export_view_key(self, n, ZeroingString::from(password), account)
+			.map_err(|e| e.kind())
+	}
+
+	fn create_watch_only_wallet(
+		&self,
+		name: Option<String>,
+		view_key: ViewKey,
+	) -> Result<(), ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::create_watch_only_wallet(self, n, view_key).map_err(|e| e.kind())
+	}
+
+	fn get_seed_quiz(
+		&self,
+		name: Option<String>,
+		password: String,
+		count: u32,
+	) -> Result<Vec<usize>, ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::get_seed_quiz(self, n, ZeroingString::from(password), count as usize)
+			.map_err(|e| e.kind())
+	}
+
+	fn verify_seed_words(
+		&self,
+		name: Option<String>,
+		password: String,
+		words: Vec<SeedWord>,
+	) -> Result<bool, ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::verify_seed_words(self, n, ZeroingString::from(password), words)
+			.map_err(|e| e.kind())
+	}
+
+	fn seed_verified(&self, name: Option<String>) -> Result<bool, ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::seed_verified(self, n).map_err(|e| e.kind())
+	}
+
 	fn change_password(
 		&self,
 		name: Option<String>,
@@ -2427,9 +5898,38 @@ where
 			.map_err(|e| e.kind())
 	}
 
-	fn delete_wallet(&self, name: Option<String>) -> Result<(), ErrorKind> {
+	fn recover_from_mnemonic(
+		&self,
+		name: Option<String>,
+		mnemonic: String,
+		password: String,
+	) -> Result<(), ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::recover_from_mnemonic(
+			self,
+			n,
+			ZeroingString::from(mnemonic),
+			ZeroingString::from(password),
+		)
+		.map_err(|e| e.kind())
+	}
+
+	fn get_delete_token(
+		&self,
+		name: Option<String>,
+		password: String,
+	) -> Result<String, ErrorKind> {
+		let n = name.as_ref().map(|s| s.as_str());
+		Owner::get_delete_token(self, n, ZeroingString::from(password)).map_err(|e| e.kind())
+	}
+
+	fn delete_wallet(
+		&self,
+		name: Option<String>,
+		confirmation_token: String,
+	) -> Result<(), ErrorKind> {
 		let n = name.as_ref().map(|s| s.as_str());
-		Owner::delete_wallet(self, n).map_err(|e| e.kind())
+		Owner::delete_wallet(self, n, &confirmation_token).map_err(|e| e.kind())
 	}
 
 	fn start_updater(&self, token: Token, frequency: u32) -> Result<(), ErrorKind> {
@@ -2497,6 +5997,98 @@ where
 		Owner::verify_payment_proof(self, (&token.keychain_mask).as_ref(), &proof)
 			.map_err(|e| e.kind())
 	}
+
+	fn retrieve_payment_proofs(
+		&self,
+		token: Token,
+		refresh_from_node: bool,
+	) -> Result<(bool, Vec<PaymentProofEntry>), ErrorKind> {
+		Owner::retrieve_payment_proofs(self, (&token.keychain_mask).as_ref(), refresh_from_node)
+			.map_err(|e| e.kind())
+	}
+
+	fn verify_payment_proof_offline(&self, proof: PaymentProof) -> Result<(), ErrorKind> {
+		Owner::verify_payment_proof_offline(self, &proof).map_err(|e| e.kind())
+	}
+
+	fn create_payment_request(
+		&self,
+		token: Token,
+		amount: u64,
+		memo: Option<String>,
+		expiry: Option<DateTime<Utc>>,
+	) -> Result<PaymentRequest, ErrorKind> {
+		Owner::create_payment_request(self, (&token.keychain_mask).as_ref(), amount, memo, expiry)
+			.map_err(|e| e.kind())
+	}
+
+	fn verify_payment_request(&self, request: PaymentRequest) -> Result<InitTxArgs, ErrorKind> {
+		Owner::verify_payment_request(self, &request).map_err(|e| e.kind())
+	}
+
+	fn verify_output_claim(
+		&self,
+		token: Token,
+		claim: OutputClaim,
+	) -> Result<OutputClaimVerification, ErrorKind> {
+		Owner::verify_output_claim(self, (&token.keychain_mask).as_ref(), &claim)
+			.map_err(|e| e.kind())
+	}
+
+	fn trace_output(
+		&self,
+		token: Token,
+		commit: String,
+	) -> Result<Option<OutputLineage>, ErrorKind> {
+		let commit = match from_hex(commit.clone()) {
+			Ok(c) => pedersen::Commitment::from_vec(c),
+			Err(_) => {
+				return Err(ErrorKind::GenericError(format!(
+					"Invalid commitment {}",
+					commit
+				)))
+			}
+		};
+		Owner::trace_output(self, (&token.keychain_mask).as_ref(), &commit).map_err(|e| e.kind())
+	}
+
+	fn lock_output(&self, token: Token, commit: String) -> Result<(), ErrorKind> {
+		let commit = match from_hex(commit.clone()) {
+			Ok(c) => pedersen::Commitment::from_vec(c),
+			Err(_) => {
+				return Err(ErrorKind::GenericError(format!(
+					"Invalid commitment {}",
+					commit
+				)))
+			}
+		};
+		Owner::lock_output(self, (&token.keychain_mask).as_ref(), &commit).map_err(|e| e.kind())
+	}
+
+	fn unlock_output(&self, token: Token, commit: String) -> Result<(), ErrorKind> {
+		let commit = match from_hex(commit.clone()) {
+			Ok(c) => pedersen::Commitment::from_vec(c),
+			Err(_) => {
+				return Err(ErrorKind::GenericError(format!(
+					"Invalid commitment {}",
+					commit
+				)))
+			}
+		};
+		Owner::unlock_output(self, (&token.keychain_mask).as_ref(), &commit).map_err(|e| e.kind())
+	}
+
+	fn import_outputs(
+		&self,
+		token: Token,
+		outputs: Vec<ImportedOutput>,
+	) -> Result<OutputImportSummary, ErrorKind> {
+		Owner::import_outputs(self, (&token.keychain_mask).as_ref(), outputs).map_err(|e| e.kind())
+	}
+
+	fn deduplicate_outputs(&self, token: Token) -> Result<Vec<DeduplicatedOutput>, ErrorKind> {
+		Owner::deduplicate_outputs(self, (&token.keychain_mask).as_ref()).map_err(|e| e.kind())
+	}
 	fn proof_address_from_onion_v3(&self, address_v3: String) -> Result<PubAddress, ErrorKind> {
 		let address =
 			Owner::proof_address_from_onion_v3(self, &address_v3).map_err(|e| e.kind())?;
@@ -2511,4 +6103,9 @@ where
 		Owner::set_epicbox_config(self, epicbox_config);
 		Ok(())
 	}
+
+	fn set_node_height_guard(&self, enabled: bool) -> Result<(), ErrorKind> {
+		Owner::set_node_height_guard(self, enabled);
+		Ok(())
+	}
 }
@@ -0,0 +1,146 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots of the outputs and transactions paged through with
+//! `retrieve_outputs_page` and `retrieve_txs_page`, so all the pages of a listing
+//! show the wallet at the same point in time while the updater keeps writing to it
+
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::libwallet::{Error, ErrorKind, OutputCommitMapping, TxLogEntry};
+use crate::util::Mutex;
+
+/// How long a snapshot can be paged through after it's taken
+const PAGE_SNAPSHOT_VALIDITY: Duration = Duration::from_secs(600);
+
+/// Number of snapshots kept, the oldest being dropped first
+const PAGE_SNAPSHOTS_MAX: usize = 32;
+
+/// What a snapshot was taken of
+#[derive(Clone)]
+enum SnapshotEntries {
+	Outputs(Vec<OutputCommitMapping>),
+	Txs(Vec<TxLogEntry>),
+}
+
+/// Entries as retrieved for the first page of a listing
+struct Snapshot {
+	id: Uuid,
+	taken: Instant,
+	/// Whether the entries were refreshed from the node
+	validated: bool,
+	entries: SnapshotEntries,
+}
+
+/// Page snapshots of an owner API instance
+pub(crate) struct PageSnapshots {
+	snapshots: Mutex<Vec<Snapshot>>,
+}
+
+impl PageSnapshots {
+	/// No snapshots yet
+	pub fn new() -> PageSnapshots {
+		PageSnapshots {
+			snapshots: Mutex::new(vec![]),
+		}
+	}
+
+	/// Keep the outputs for the next pages
+	pub fn add_outputs(&self, id: Uuid, validated: bool, outputs: Vec<OutputCommitMapping>) {
+		self.add(id, validated, SnapshotEntries::Outputs(outputs));
+	}
+
+	/// Keep the transactions for the next pages
+	pub fn add_txs(&self, id: Uuid, validated: bool, txs: Vec<TxLogEntry>) {
+		self.add(id, validated, SnapshotEntries::Txs(txs));
+	}
+
+	/// The outputs of the snapshot with the given id, and whether they were refreshed
+	/// from the node
+	pub fn outputs(&self, id: &Uuid) -> Result<(bool, Vec<OutputCommitMapping>), Error> {
+		match self.entries(id)? {
+			(validated, SnapshotEntries::Outputs(outputs)) => Ok((validated, outputs)),
+			_ => Err(ErrorKind::UnknownPageSnapshot(id.to_string()).into()),
+		}
+	}
+
+	/// The transactions of the snapshot with the given id, and whether they were
+	/// refreshed from the node
+	pub fn txs(&self, id: &Uuid) -> Result<(bool, Vec<TxLogEntry>), Error> {
+		match self.entries(id)? {
+			(validated, SnapshotEntries::Txs(txs)) => Ok((validated, txs)),
+			_ => Err(ErrorKind::UnknownPageSnapshot(id.to_string()).into()),
+		}
+	}
+
+	/// Drop all the snapshots, once the wallet they were taken of is closed
+	pub fn clear(&self) {
+		self.snapshots.lock().clear();
+	}
+
+	fn add(&self, id: Uuid, validated: bool, entries: SnapshotEntries) {
+		let mut snapshots = self.snapshots.lock();
+		snapshots.retain(|s| s.taken.elapsed() < PAGE_SNAPSHOT_VALIDITY);
+		let excess = (snapshots.len() + 1).saturating_sub(PAGE_SNAPSHOTS_MAX);
+		snapshots.drain(..excess);
+		snapshots.push(Snapshot {
+			id,
+			taken: Instant::now(),
+			validated,
+			entries,
+		});
+	}
+
+	fn entries(&self, id: &Uuid) -> Result<(bool, SnapshotEntries), Error> {
+		let snapshots = self.snapshots.lock();
+		match snapshots
+			.iter()
+			.find(|s| s.id == *id && s.taken.elapsed() < PAGE_SNAPSHOT_VALIDITY)
+		{
+			Some(s) => Ok((s.validated, s.entries.clone())),
+			None => Err(ErrorKind::UnknownPageSnapshot(id.to_string()).into()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn page_snapshots_kept_by_kind() {
+		let snapshots = PageSnapshots::new();
+		let outputs = Uuid::new_v4();
+		let txs = Uuid::new_v4();
+		snapshots.add_outputs(outputs, true, vec![]);
+		snapshots.add_txs(txs, false, vec![]);
+
+		assert!(snapshots.outputs(&outputs).unwrap().0);
+		assert!(!snapshots.txs(&txs).unwrap().0);
+		assert!(snapshots.txs(&outputs).is_err());
+		assert!(snapshots.outputs(&txs).is_err());
+		assert!(snapshots.outputs(&Uuid::new_v4()).is_err());
+
+		// the oldest snapshots make way for new ones
+		for _ in 0..PAGE_SNAPSHOTS_MAX - 1 {
+			snapshots.add_txs(Uuid::new_v4(), false, vec![]);
+		}
+		assert!(snapshots.outputs(&outputs).is_err());
+		assert!(snapshots.txs(&txs).is_ok());
+
+		snapshots.clear();
+		assert!(snapshots.txs(&txs).is_err());
+	}
+}
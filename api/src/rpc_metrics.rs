@@ -0,0 +1,251 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timing of the calls made to the owner and foreign APIs, logging the slow ones
+//! with their parameters redacted and keeping latency statistics over the latest
+//! calls of each method
+
+use easy_jsonrpc_mw::{Handler, MaybeReply};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::audit_log;
+use crate::util::Mutex;
+
+/// Calls slower than this are logged, unless configured otherwise
+const DEFAULT_SLOW_CALL_MS: u64 = 5000;
+
+/// Number of latest calls of a method the latency statistics are computed over
+const LATENCY_WINDOW: usize = 100;
+
+/// Longest parameters logged with a slow call, in characters, as slates are large
+const MAX_LOGGED_PARAMS_LEN: usize = 1000;
+
+/// Calls made to a method of an API
+struct MethodSamples {
+	calls: u64,
+	errors: u64,
+	slow_calls: u64,
+	/// Durations of the latest calls, in milliseconds, oldest first
+	latencies: VecDeque<u64>,
+}
+
+/// Calls made to the APIs of the process, by API and method
+struct RpcMetrics {
+	slow_call_ms: u64,
+	methods: HashMap<(&'static str, String), MethodSamples>,
+}
+
+lazy_static! {
+	static ref RPC_METRICS: Mutex<RpcMetrics> = Mutex::new(RpcMetrics {
+		slow_call_ms: DEFAULT_SLOW_CALL_MS,
+		methods: HashMap::new(),
+	});
+}
+
+/// Calls made to a method of the owner or foreign API since the wallet was started,
+/// kept in memory. Latencies are over the latest 100 calls.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RpcMethodMetrics {
+	/// API the method is part of, `owner` or `foreign`
+	pub api: String,
+	/// Method called
+	pub method: String,
+	/// Number of calls made
+	pub calls: u64,
+	/// Number of calls answered with an error
+	pub errors: u64,
+	/// Number of calls slower than the configured threshold
+	pub slow_calls: u64,
+	/// Average duration, in milliseconds
+	pub avg_ms: u64,
+	/// Median of the durations, in milliseconds
+	pub p50_ms: u64,
+	/// 95th percentile of the durations, in milliseconds
+	pub p95_ms: u64,
+	/// 99th percentile of the durations, in milliseconds
+	pub p99_ms: u64,
+	/// Longest duration, in milliseconds
+	pub max_ms: u64,
+}
+
+/// Set the duration above which calls are logged, in milliseconds
+pub fn set_slow_rpc_ms(ms: u64) {
+	RPC_METRICS.lock().slow_call_ms = ms;
+}
+
+/// Handle a JSON-RPC request made to an API, timing each of its calls. The calls
+/// of a batch are handled one after the other, as the handler would.
+pub fn handle_timed_request<H>(
+	api: &'static str,
+	handler: &H,
+	request: serde_json::Value,
+) -> MaybeReply
+where
+	H: Handler + ?Sized,
+{
+	match request {
+		serde_json::Value::Array(calls) if !calls.is_empty() => {
+			let replies: Vec<serde_json::Value> = calls
+				.into_iter()
+				.filter_map(|c| match timed_call(api, handler, c) {
+					MaybeReply::Reply(r) => Some(r),
+					MaybeReply::DontReply => None,
+				})
+				.collect();
+			match replies.is_empty() {
+				true => MaybeReply::DontReply,
+				false => MaybeReply::Reply(serde_json::Value::Array(replies)),
+			}
+		}
+		request => timed_call(api, handler, request),
+	}
+}
+
+fn timed_call<H>(api: &'static str, handler: &H, call: serde_json::Value) -> MaybeReply
+where
+	H: Handler + ?Sized,
+{
+	let method = match call["method"].as_str() {
+		Some(m) => m.to_owned(),
+		None => return handler.handle_request(call),
+	};
+	let params = call["params"].clone();
+	let start = Instant::now();
+	let reply = handler.handle_request(call);
+	let ok = match &reply {
+		MaybeReply::Reply(r) => r.get("error").is_none() && r["result"]["Err"].is_null(),
+		MaybeReply::DontReply => true,
+	};
+	record(api, &method, start.elapsed(), ok, &params);
+	reply
+}
+
+/// Record a call made to a method of an API
+fn record(api: &'static str, method: &str, took: Duration, ok: bool, params: &serde_json::Value) {
+	let ms = took.as_millis() as u64;
+	let mut metrics = RPC_METRICS.lock();
+	let slow = ms > metrics.slow_call_ms;
+	if slow {
+		let params = audit_log::redacted_params(method, params).to_string();
+		warn!(
+			"Slow {} API call: {} took {} ms{}, with parameters {}",
+			api,
+			method,
+			ms,
+			if ok { "" } else { " and failed" },
+			params
+				.chars()
+				.take(MAX_LOGGED_PARAMS_LEN)
+				.collect::<String>()
+		);
+	}
+	let samples = metrics
+		.methods
+		.entry((api, method.to_owned()))
+		.or_insert_with(|| MethodSamples {
+			calls: 0,
+			errors: 0,
+			slow_calls: 0,
+			latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+		});
+	samples.calls += 1;
+	if !ok {
+		samples.errors += 1;
+	}
+	if slow {
+		samples.slow_calls += 1;
+	}
+	if samples.latencies.len() == LATENCY_WINDOW {
+		samples.latencies.pop_front();
+	}
+	samples.latencies.push_back(ms);
+}
+
+/// Statistics of each method called so far, ordered by API and method
+pub fn rpc_metrics() -> Vec<RpcMethodMetrics> {
+	let metrics = RPC_METRICS.lock();
+	let mut retval: Vec<RpcMethodMetrics> = metrics
+		.methods
+		.iter()
+		.map(|((api, method), s)| {
+			let mut latencies: Vec<u64> = s.latencies.iter().cloned().collect();
+			latencies.sort();
+			// nearest rank
+			let percentile = |p: usize| latencies[((latencies.len() * p + 99) / 100).max(1) - 1];
+			RpcMethodMetrics {
+				api: api.to_string(),
+				method: method.clone(),
+				calls: s.calls,
+				errors: s.errors,
+				slow_calls: s.slow_calls,
+				avg_ms: latencies.iter().sum::<u64>() / latencies.len() as u64,
+				p50_ms: percentile(50),
+				p95_ms: percentile(95),
+				p99_ms: percentile(99),
+				max_ms: latencies[latencies.len() - 1],
+			}
+		})
+		.collect();
+	retval.sort_by(|a, b| (&a.api, &a.method).cmp(&(&b.api, &b.method)));
+	retval
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn rpc_metrics_rolling_latencies() {
+		set_slow_rpc_ms(150);
+		for ms in 1..=200 {
+			record(
+				"owner",
+				"retrieve_txs",
+				Duration::from_millis(ms),
+				true,
+				&json!({}),
+			);
+		}
+		record(
+			"foreign",
+			"receive_tx",
+			Duration::from_millis(10),
+			false,
+			&json!([]),
+		);
+
+		let metrics = rpc_metrics();
+		assert_eq!(metrics.len(), 2);
+		let receive = &metrics[0];
+		assert_eq!(receive.api, "foreign");
+		assert_eq!(receive.calls, 1);
+		assert_eq!(receive.errors, 1);
+		assert_eq!(receive.p50_ms, 10);
+		assert_eq!(receive.p99_ms, 10);
+
+		let txs = &metrics[1];
+		assert_eq!(txs.method, "retrieve_txs");
+		assert_eq!(txs.calls, 200);
+		assert_eq!(txs.errors, 0);
+		assert_eq!(txs.slow_calls, 50);
+		// only the latest 100 calls, of 101 to 200 ms, are in the statistics
+		assert_eq!(txs.avg_ms, 150);
+		assert_eq!(txs.p50_ms, 150);
+		assert_eq!(txs.p95_ms, 195);
+		assert_eq!(txs.p99_ms, 199);
+		assert_eq!(txs.max_ms, 200);
+	}
+}
@@ -0,0 +1,138 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scans of the chain started with `start_scan`, running in the background so
+//! callers can follow their progress and cancel them
+
+use chrono::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::libwallet::{Error, ErrorKind, ScanJob, ScanJobStatus};
+use crate::util::Mutex;
+
+/// Number of finished jobs kept for status queries
+const SCAN_JOBS_MAX_FINISHED: usize = 100;
+
+/// A job, with the flag telling its scan to stop
+struct TrackedJob {
+	job: ScanJob,
+	cancel: Arc<AtomicBool>,
+}
+
+/// Scan jobs of an owner API instance
+pub(crate) struct ScanJobs {
+	jobs: Mutex<Vec<TrackedJob>>,
+}
+
+impl ScanJobs {
+	/// No jobs yet
+	pub fn new() -> ScanJobs {
+		ScanJobs {
+			jobs: Mutex::new(vec![]),
+		}
+	}
+
+	/// Add a running job, returning the flag its scan should stop on. Only one scan
+	/// runs at a time, as concurrent scans would repair the wallet over each other.
+	pub fn add(
+		&self,
+		id: Uuid,
+		start_height: u64,
+		delete_unconfirmed: bool,
+	) -> Result<Arc<AtomicBool>, Error> {
+		let mut jobs = self.jobs.lock();
+		if let Some(t) = jobs.iter().find(|t| t.job.status == ScanJobStatus::Running) {
+			let msg = format!("scan {} is already running", t.job.id);
+			return Err(ErrorKind::GenericError(msg).into());
+		}
+		let now = Utc::now();
+		let cancel = Arc::new(AtomicBool::new(false));
+		jobs.push(TrackedJob {
+			job: ScanJob {
+				id,
+				status: ScanJobStatus::Running,
+				start_height,
+				delete_unconfirmed,
+				percent_complete: 0,
+				current_height: None,
+				error: None,
+				created_ts: now,
+				updated_ts: now,
+			},
+			cancel: cancel.clone(),
+		});
+
+		let finished = jobs.len() - 1;
+		let excess = finished.saturating_sub(SCAN_JOBS_MAX_FINISHED);
+		jobs.drain(..excess);
+		Ok(cancel)
+	}
+
+	/// The job with the given id
+	pub fn job(&self, id: &Uuid) -> Option<ScanJob> {
+		self.jobs
+			.lock()
+			.iter()
+			.find(|t| t.job.id == *id)
+			.map(|t| t.job.clone())
+	}
+
+	/// Change the job with the given id
+	pub fn update(&self, id: &Uuid, f: &dyn Fn(&mut ScanJob)) {
+		let mut jobs = self.jobs.lock();
+		if let Some(t) = jobs.iter_mut().find(|t| t.job.id == *id) {
+			f(&mut t.job);
+			t.job.updated_ts = Utc::now();
+		}
+	}
+
+	/// Tell the scan of the job with the given id to stop. Returns whether the job is
+	/// still running, so it'll stop.
+	pub fn cancel(&self, id: &Uuid) -> bool {
+		let jobs = self.jobs.lock();
+		match jobs.iter().find(|t| t.job.id == *id) {
+			Some(t) if t.job.status == ScanJobStatus::Running => {
+				t.cancel.store(true, Ordering::Relaxed);
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn scan_jobs_one_at_a_time() {
+		let jobs = ScanJobs::new();
+		let first = Uuid::new_v4();
+		let cancel = jobs.add(first, 1, false).unwrap();
+		assert!(jobs.add(Uuid::new_v4(), 1, false).is_err());
+
+		assert!(jobs.cancel(&first));
+		assert!(cancel.load(Ordering::Relaxed));
+		assert!(!jobs.cancel(&Uuid::new_v4()));
+
+		jobs.update(&first, &|j| j.status = ScanJobStatus::Cancelled);
+		assert!(!jobs.cancel(&first));
+		let second = Uuid::new_v4();
+		jobs.add(second, 10, true).unwrap();
+		assert_eq!(jobs.job(&second).unwrap().start_height, 10);
+		assert_eq!(jobs.job(&first).unwrap().status, ScanJobStatus::Cancelled);
+	}
+}
@@ -0,0 +1,411 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Background workers executing sends queued through the owner API, so callers
+//! don't wait on slow transports
+
+use chrono::prelude::*;
+use std::collections::HashSet;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex as StdMutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::config::{EpicboxConfig, TorConfig};
+use crate::keychain::Keychain;
+use crate::libwallet::api_impl::owner_updater::StatusMessage;
+use crate::libwallet::{
+	Error, ErrorKind, InitTxArgs, NodeClient, SendJob, SendJobStatus, SendPriority,
+	WalletLCProvider,
+};
+use crate::util::secp::key::SecretKey;
+use crate::util::Mutex;
+use crate::Owner;
+
+/// Number of exchanges with the recipient attempted before a queued send fails
+pub const SEND_QUEUE_MAX_ATTEMPTS: u32 = 5;
+
+/// Number of workers executing queued sends at the same time, unless configured otherwise
+pub const SEND_QUEUE_DEFAULT_WORKERS: usize = 1;
+
+/// Delay before the first retry of a failed exchange, doubled on each further retry,
+/// unless configured otherwise
+pub const SEND_QUEUE_DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Number of finished jobs kept for status queries
+const SEND_QUEUE_MAX_FINISHED: usize = 1000;
+
+/// A send waiting for a worker, with everything needed to execute it
+pub(crate) struct QueuedSend {
+	pub id: Uuid,
+	pub priority: SendPriority,
+	pub keychain_mask: Option<SecretKey>,
+	pub args: InitTxArgs,
+	pub tor_config: Option<TorConfig>,
+	pub epicbox_config: Option<EpicboxConfig>,
+}
+
+impl QueuedSend {
+	/// Destination of the send, only one send runs at a time for each
+	fn dest(&self) -> String {
+		match self.args.send_args.as_ref() {
+			Some(sa) => format!("{}:{}", sa.method, sa.dest),
+			None => String::new(),
+		}
+	}
+}
+
+/// Waiting sends and the workers taking them
+struct QueueState {
+	/// Sends not yet taken by a worker, in the order queued
+	pending: Vec<QueuedSend>,
+	/// Destinations of the sends being executed
+	busy: HashSet<String>,
+	/// Number of running workers
+	workers: usize,
+	/// Maximum number of running workers
+	max_workers: usize,
+}
+
+/// Queue of sends shared by an owner API instance and its workers
+pub(crate) struct SendQueue {
+	state: StdMutex<QueueState>,
+	/// Signalled when a destination becomes free or the number of workers changes
+	changed: Condvar,
+	/// Held by a worker while it selects and locks the outputs of a send, so sends
+	/// executed at the same time never select the same outputs
+	select_lock: Mutex<()>,
+	/// Status of all jobs queued
+	jobs: Mutex<Vec<SendJob>>,
+	/// Delay before the first retry of a failed exchange
+	retry_delay: Mutex<Duration>,
+}
+
+impl SendQueue {
+	/// Create an empty queue, executed by at most the given number of workers
+	pub fn new(max_workers: usize) -> SendQueue {
+		SendQueue {
+			state: StdMutex::new(QueueState {
+				pending: vec![],
+				busy: HashSet::new(),
+				workers: 0,
+				max_workers: max_workers.max(1),
+			}),
+			changed: Condvar::new(),
+			select_lock: Mutex::new(()),
+			jobs: Mutex::new(vec![]),
+			retry_delay: Mutex::new(SEND_QUEUE_DEFAULT_RETRY_DELAY),
+		}
+	}
+
+	/// Set the delay before the first retry of a failed exchange
+	pub fn set_retry_delay(&self, delay: Duration) {
+		*self.retry_delay.lock() = delay;
+	}
+
+	/// Set the maximum number of workers. Workers beyond it stop once their current
+	/// send is done
+	pub fn set_max_workers(&self, max_workers: usize) {
+		let mut state = self.state();
+		state.max_workers = max_workers.max(1);
+		self.changed.notify_all();
+	}
+
+	/// All jobs queued, oldest first
+	pub fn jobs(&self) -> Vec<SendJob> {
+		self.jobs.lock().clone()
+	}
+
+	/// The job with the given id
+	pub fn job(&self, id: &Uuid) -> Option<SendJob> {
+		self.jobs.lock().iter().find(|j| j.id == *id).cloned()
+	}
+
+	/// Add a send to the queue, returning the number of workers to start for it.
+	/// These are counted as running from now on.
+	pub fn push(&self, send: QueuedSend) -> usize {
+		self.add_job(&send);
+		let mut state = self.state();
+		state.pending.push(send);
+		let start = state.max_workers.saturating_sub(state.workers).min(1);
+		state.workers += start;
+		self.changed.notify_all();
+		start
+	}
+
+	/// Add the job of a send, dropping the oldest finished jobs beyond the number kept
+	fn add_job(&self, send: &QueuedSend) {
+		let now = Utc::now();
+		let (method, dest) = match send.args.send_args.as_ref() {
+			Some(sa) => (sa.method.clone(), sa.dest.clone()),
+			None => (String::new(), String::new()),
+		};
+		let job = SendJob {
+			id: send.id,
+			status: SendJobStatus::Queued,
+			method,
+			dest,
+			amount: send.args.amount,
+			priority: send.priority,
+			attempts: 0,
+			slate_id: None,
+			error: None,
+			created_ts: now,
+			updated_ts: now,
+		};
+
+		let mut jobs = self.jobs.lock();
+		jobs.push(job);
+		let finished = jobs.iter().filter(|j| is_finished(j)).count();
+		let mut excess = finished.saturating_sub(SEND_QUEUE_MAX_FINISHED);
+		jobs.retain(|j| {
+			if is_finished(j) && excess > 0 {
+				excess -= 1;
+				return false;
+			}
+			true
+		});
+	}
+
+	/// Take the next send for a worker: the highest priority send waiting whose
+	/// destination has no send running, waiting for one if all are busy. Returns
+	/// `None` if the worker should stop, as nothing is waiting or there are more
+	/// workers than allowed.
+	fn take(&self) -> Option<QueuedSend> {
+		let mut state = self.state();
+		loop {
+			if state.pending.is_empty() || state.workers > state.max_workers {
+				state.workers -= 1;
+				return None;
+			}
+			let mut next: Option<usize> = None;
+			for (i, s) in state.pending.iter().enumerate() {
+				if state.busy.contains(&s.dest()) {
+					continue;
+				}
+				if next.map_or(true, |n| s.priority > state.pending[n].priority) {
+					next = Some(i);
+				}
+			}
+			if let Some(i) = next {
+				let send = state.pending.remove(i);
+				state.busy.insert(send.dest());
+				return Some(send);
+			}
+			state = match self.changed.wait(state) {
+				Ok(s) => s,
+				Err(e) => e.into_inner(),
+			};
+		}
+	}
+
+	/// Mark the destination of a send taken with `take` as free again
+	fn done(&self, send: &QueuedSend) {
+		let mut state = self.state();
+		state.busy.remove(&send.dest());
+		self.changed.notify_all();
+	}
+
+	fn state(&self) -> MutexGuard<QueueState> {
+		match self.state.lock() {
+			Ok(s) => s,
+			Err(e) => e.into_inner(),
+		}
+	}
+}
+
+fn is_finished(job: &SendJob) -> bool {
+	job.status == SendJobStatus::Completed || job.status == SendJobStatus::Failed
+}
+
+/// Start a worker thread, executing queued sends one at a time with the given owner
+/// API instance and reporting job changes on the status channel. The worker stops
+/// once no sends are waiting.
+pub(crate) fn start_send_worker<L, C, K>(
+	owner: Owner<L, C, K>,
+	queue: Arc<SendQueue>,
+	status_tx: Option<Sender<StatusMessage>>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let q = queue.clone();
+	let res = thread::Builder::new()
+		.name("wallet-send-queue".to_string())
+		.spawn(move || {
+			while let Some(send) = queue.take() {
+				let update = |f: &dyn Fn(&mut SendJob)| {
+					let mut jobs = queue.jobs.lock();
+					if let Some(j) = jobs.iter_mut().find(|j| j.id == send.id) {
+						f(j);
+						j.updated_ts = Utc::now();
+						if let Some(ref s) = status_tx {
+							let _ = s.send(StatusMessage::SendJobUpdate(j.clone()));
+						}
+					}
+				};
+				update(&|j: &mut SendJob| j.status = SendJobStatus::Running);
+				match execute_send(&owner, &queue, &send, &update) {
+					Ok(_) => update(&|j: &mut SendJob| {
+						j.status = SendJobStatus::Completed;
+						j.error = None;
+					}),
+					Err(e) => {
+						let msg = format!("{}", e);
+						update(&|j: &mut SendJob| {
+							j.status = SendJobStatus::Failed;
+							j.error = Some(msg.clone());
+						})
+					}
+				}
+				queue.done(&send);
+			}
+		});
+	if let Err(e) = res {
+		// the send stays queued for the other workers, or the next one started
+		q.state().workers -= 1;
+		return Err(ErrorKind::GenericError(format!("Unable to start send worker: {}", e)).into());
+	}
+	Ok(())
+}
+
+/// Create the slate and lock its outputs, then exchange it with the recipient,
+/// retrying the exchange with a growing delay, and finalize and post it as
+/// requested. The transaction is cancelled if the exchange keeps failing, releasing
+/// its outputs.
+fn execute_send<L, C, K>(
+	owner: &Owner<L, C, K>,
+	queue: &SendQueue,
+	send: &QueuedSend,
+	update: &dyn Fn(&dyn Fn(&mut SendJob)),
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let keychain_mask = send.keychain_mask.as_ref();
+	owner.set_tor_config(send.tor_config.clone());
+	owner.set_epicbox_config(send.epicbox_config.clone());
+
+	let mut args = send.args.clone();
+	let sa = match args.send_args.take() {
+		Some(sa) => sa,
+		None => return Ok(()),
+	};
+	let slate = {
+		let _select = queue.select_lock.lock();
+		let slate = owner.init_send_tx(keychain_mask, args)?;
+		owner.tx_lock_outputs(keychain_mask, &slate, 0)?;
+		slate
+	};
+	update(&|j: &mut SendJob| j.slate_id = Some(slate.id));
+
+	let mut attempt = 0;
+	let mut delay = *queue.retry_delay.lock();
+	let response = loop {
+		attempt += 1;
+		update(&|j: &mut SendJob| j.attempts = attempt);
+		match owner.send_slate(keychain_mask, &sa, &slate) {
+			Ok(s) => break s,
+			Err(e) => {
+				if attempt >= SEND_QUEUE_MAX_ATTEMPTS {
+					if let Err(c) = owner.cancel_tx(keychain_mask, None, Some(slate.id)) {
+						warn!("Unable to cancel queued send {}: {}", send.id, c);
+					}
+					return Err(e);
+				}
+				let msg = format!("{}", e);
+				warn!(
+					"Queued send {} attempt {} failed, retrying in {}ms: {}",
+					send.id,
+					attempt,
+					delay.as_millis(),
+					msg
+				);
+				update(&|j: &mut SendJob| j.error = Some(msg.clone()));
+				thread::sleep(delay);
+				delay *= 2;
+			}
+		}
+	};
+	owner.complete_send(keychain_mask, &sa, response)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::libwallet::InitTxSendArgs;
+
+	fn send(dest: &str, priority: SendPriority) -> QueuedSend {
+		QueuedSend {
+			id: Uuid::new_v4(),
+			priority,
+			keychain_mask: None,
+			args: InitTxArgs {
+				send_args: Some(InitTxSendArgs {
+					method: "http".to_owned(),
+					dest: dest.to_owned(),
+					finalize: true,
+					post_tx: true,
+					fluff: false,
+				}),
+				..Default::default()
+			},
+			tor_config: None,
+			epicbox_config: None,
+		}
+	}
+
+	#[test]
+	fn send_queue_order() {
+		let queue = SendQueue::new(2);
+		let sends = vec![
+			send("a", SendPriority::Low),
+			send("b", SendPriority::Normal),
+			send("a", SendPriority::High),
+			send("c", SendPriority::Normal),
+		];
+		let ids: Vec<Uuid> = sends.iter().map(|s| s.id).collect();
+		let started: usize = sends.into_iter().map(|s| queue.push(s)).sum();
+		assert_eq!(started, 2);
+		assert_eq!(queue.jobs().len(), 4);
+
+		// highest priority first, then in order for destinations not busy
+		let high_a = queue.take().unwrap();
+		assert_eq!(high_a.id, ids[2]);
+		let b = queue.take().unwrap();
+		assert_eq!(b.id, ids[1]);
+		assert_eq!(queue.take().unwrap().id, ids[3]);
+
+		// the other send to a waits for the first
+		queue.done(&high_a);
+		assert_eq!(queue.take().unwrap().id, ids[0]);
+
+		// nothing left, the worker stops
+		assert!(queue.take().is_none());
+		assert_eq!(queue.state().workers, 1);
+
+		// workers beyond the maximum stop
+		queue.push(send("d", SendPriority::Normal));
+		queue.set_max_workers(0);
+		assert!(queue.take().is_none());
+		assert_eq!(queue.state().workers, 1);
+		assert_eq!(queue.take().unwrap().args.send_args.unwrap().dest, "d");
+	}
+}
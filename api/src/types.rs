@@ -23,10 +23,11 @@ use ed25519_dalek::PublicKey as DalekPublicKey;
 use failure::ResultExt;
 
 use serde_json::{self, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use rand::{thread_rng, Rng};
 use ring::aead;
+use uuid::Uuid;
 
 /// Wrapper for API Tokens
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -37,6 +38,161 @@ pub struct Token {
 	pub keychain_mask: Option<SecretKey>,
 }
 
+/// Methods of the owner API a read-only token can call, none of them changing the wallet
+pub(crate) const READ_ONLY_METHODS: &[&str] = &[
+	"accounts",
+	"retrieve_outputs",
+	"retrieve_outputs_page",
+	"retrieve_txs",
+	"retrieve_txs_page",
+	"retrieve_txs_by_tag",
+	"retrieve_tx_tags",
+	"retrieve_contact_txs",
+	"retrieve_pending_receives",
+	"fee_report",
+	"retrieve_contacts",
+	"retrieve_send_templates",
+	"get_send_template",
+	"retrieve_summary_info",
+	"estimate_send_tx",
+	"get_send_job",
+	"retrieve_send_jobs",
+	"get_stored_tx",
+	"verify_slate_messages",
+	"get_job_status",
+	"node_height",
+	"get_block_header",
+	"retrieve_transport_metrics",
+	"retrieve_node_metrics",
+	"retrieve_rpc_metrics",
+	"get_listener_status",
+	"retrieve_pinned_keys",
+	"get_metadata",
+	"retrieve_metadata",
+	"get_events",
+	"get_audit_log",
+	"get_public_address",
+	"get_public_proof_address",
+	"retrieve_payment_proof",
+	"retrieve_payment_proofs",
+	"trace_output",
+];
+
+/// Methods a send-limited token can call besides the read-only ones
+const SEND_METHODS: &[&str] = &[
+	"init_send_tx",
+	"queue_send",
+	"tx_lock_outputs",
+	"finalize_tx",
+	"post_tx",
+];
+
+/// Methods of the owner API a token issued by
+/// [`open_wallet`](struct.Owner.html#method.open_wallet) can call
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenScope {
+	/// Queries only, which don't change the wallet, so without refreshing it from the node
+	ReadOnly,
+	/// Queries, and sends of at most the amount in total
+	SendLimited {
+		/// Maximum amount sent with the token
+		max_amount: u64,
+	},
+	/// Approval of the sends awaiting approval, and queries as with `ReadOnly`
+	Approver,
+	/// All methods, as the keychain mask itself
+	Full,
+}
+
+impl TokenScope {
+	/// Whether the scope allows calling the method, sending the amount if it's a send,
+	/// and refreshing the wallet from the node if asked to
+	pub fn allows(&self, method: &str, amount: Option<u64>, refresh_from_node: bool) -> bool {
+		let read_only = READ_ONLY_METHODS.contains(&method);
+		match self {
+			TokenScope::ReadOnly => read_only && !refresh_from_node,
+			TokenScope::SendLimited { max_amount } => {
+				read_only
+					|| (SEND_METHODS.contains(&method) && amount.map_or(true, |a| a <= *max_amount))
+			}
+			TokenScope::Approver => method == "approve_tx" || (read_only && !refresh_from_node),
+			TokenScope::Full => true,
+		}
+	}
+}
+
+/// A call of an owner API method made with a token issued by
+/// [`open_wallet`](struct.Owner.html#method.open_wallet) with a scope, as checked
+/// against the scope
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenCall {
+	/// Method called
+	pub method: String,
+	/// Amount sent, if the call sends or finalizes a send
+	pub amount: Option<u64>,
+	/// Whether the call refreshes the wallet from the node
+	pub refresh_from_node: bool,
+	/// Id of the slate finalized, if the call finalizes one
+	pub slate_id: Option<Uuid>,
+	/// Excesses of the kernels of the transaction posted, if the call posts one
+	pub kernels: Vec<String>,
+}
+
+/// A token issued by [`open_wallet`](struct.Owner.html#method.open_wallet) with a scope
+pub(crate) struct ScopedToken {
+	/// Scope of the token, a send-limited one holding what's left of its limit
+	pub scope: TokenScope,
+	/// Keychain mask the token stands for
+	pub mask: Option<SecretKey>,
+	/// Slates sent or finalized with the token, their amount deducted from its limit
+	slates: HashSet<Uuid>,
+	/// Excesses of the kernels of the transactions finalized with the token
+	kernels: HashSet<String>,
+}
+
+impl ScopedToken {
+	/// New token of the scope, standing for the mask
+	pub fn new(scope: TokenScope, mask: Option<SecretKey>) -> ScopedToken {
+		ScopedToken {
+			scope,
+			mask,
+			slates: HashSet::new(),
+			kernels: HashSet::new(),
+		}
+	}
+
+	/// Whether the token allows the call. A send-limited token finalizes the slates it
+	/// sent without their amount counting again, and only posts the transactions it
+	/// finalized.
+	pub fn allows(&self, call: &TokenCall) -> bool {
+		if let TokenScope::SendLimited { .. } = self.scope {
+			match call.method.as_str() {
+				"finalize_tx" if call.slate_id.map_or(false, |id| self.slates.contains(&id)) => {
+					return true;
+				}
+				"post_tx" => {
+					return !call.kernels.is_empty()
+						&& call.kernels.iter().all(|k| self.kernels.contains(k));
+				}
+				_ => {}
+			}
+		}
+		self.scope
+			.allows(&call.method, call.amount, call.refresh_from_node)
+	}
+
+	/// Deduct the amount of a successful send or finalization from what's left of a
+	/// send-limited token's limit, unless its slate was already counted
+	pub fn spend(&mut self, amount: u64, slate_id: Option<Uuid>, kernels: Vec<String>) {
+		let counted = slate_id.map_or(false, |id| !self.slates.insert(id));
+		if let (false, TokenScope::SendLimited { max_amount }) = (counted, &mut self.scope) {
+			*max_amount = max_amount.saturating_sub(amount);
+		}
+		self.kernels.extend(kernels);
+	}
+}
+
 /// Wrapper for dalek public keys, used as addresses
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(transparent)]
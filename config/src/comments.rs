@@ -123,6 +123,239 @@ fn comments() -> HashMap<String, String> {
 		.to_string(),
 	);
 
+	retval.insert(
+		"locale".to_string(),
+		"
+#Locale used to format numbers and dates in command line output, e.g. \"en\" or \"de-DE\"
+"
+		.to_string(),
+	);
+	retval.insert(
+		"locale_catalog_path".to_string(),
+		"
+#Optional JSON file mapping command line message keys to translated strings
+#locale_catalog_path = \"\"
+"
+		.to_string(),
+	);
+	retval.insert(
+		"amount_decimals".to_string(),
+		"
+#Number of decimals (0 to 8) displayed for amounts in command line output
+"
+		.to_string(),
+	);
+	retval.insert(
+		"display_unit".to_string(),
+		"
+#Unit in which amounts are displayed and entered on the command line, one of
+#\"epic\", \"milli\" (1/1000 EPIC), \"micro\" (1/1000000 EPIC) or \"raw\" (smallest unit)
+"
+		.to_string(),
+	);
+	retval.insert(
+		"max_tx_weight".to_string(),
+		"
+#Maximum weight of transactions created when sending, unlimited if not set
+#max_tx_weight = 40000
+"
+		.to_string(),
+	);
+	retval.insert(
+		"auto_split_tx".to_string(),
+		"
+#Whether a send exceeding max_tx_weight is split into several sequential
+#transactions instead of failing
+"
+		.to_string(),
+	);
+	retval.insert(
+		"pending_receive_expiry_hours".to_string(),
+		"
+#If set, the listener cancels received transactions the sender hasn't posted
+#to the chain after this many hours
+#pending_receive_expiry_hours = 72
+"
+		.to_string(),
+	);
+	retval.insert(
+		"send_queue_workers".to_string(),
+		"
+#Maximum number of sends queued through the owner API executed at the same
+#time, sends to the same destination are always executed one at a time
+#send_queue_workers = 4
+"
+		.to_string(),
+	);
+	retval.insert(
+		"strict_slate_compat".to_string(),
+		"
+#Whether sent and invoiced slates always use the oldest slate version (V2),
+#without optional fields such as payment proofs or TTLs, for receivers running
+#older wallets
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_max_request_bytes".to_string(),
+		"
+#Maximum size in bytes of a request body accepted by the owner and foreign
+#listeners, larger requests are refused with 413 Payload Too Large
+#api_max_request_bytes = 8388608
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_max_batch_len".to_string(),
+		"
+#Maximum number of calls in a JSON-RPC batch request sent to the listeners
+#api_max_batch_len = 100
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_max_results".to_string(),
+		"
+#Maximum number of outputs or transactions returned by an owner API query,
+#queries matching more fail and should be narrowed down. Unlimited if not set
+#api_max_results = 10000
+"
+		.to_string(),
+	);
+	retval.insert(
+		"sign_foreign_responses".to_string(),
+		"
+#Whether the foreign API signs the slates it returns with the key of the
+#wallet's payment proof address, so senders can check the response came from
+#this wallet rather than a relay or proxy in between
+"
+		.to_string(),
+	);
+	retval.insert(
+		"auto_initial_scan".to_string(),
+		"
+#Whether a wallet restored from its recovery phrase starts scanning the chain
+#for its outputs in the background the first time it's opened, the progress
+#being shown by the info command until the scan completes
+"
+		.to_string(),
+	);
+	retval.insert(
+		"node_slow_request_ms".to_string(),
+		"
+#Requests to the node taking longer than this many milliseconds are logged as
+#warnings. The latencies of all requests are returned by the owner API's
+#retrieve_node_metrics
+#node_slow_request_ms = 5000
+"
+		.to_string(),
+	);
+	retval.insert(
+		"rpc_slow_call_ms".to_string(),
+		"
+#Calls to the owner and foreign APIs taking longer than this many milliseconds
+#are logged as warnings, with their parameters but not their token or secrets.
+#The call counts, errors and latencies of each method are returned by the owner
+#API's retrieve_rpc_metrics
+#rpc_slow_call_ms = 5000
+"
+		.to_string(),
+	);
+	retval.insert(
+		"node_cache_size".to_string(),
+		"
+#The node's responses for outputs and kernels can be cached, so refreshes made
+#in quick succession don't request them again. Number of outputs, and of kernel
+#lookups, cached, 0 to disable the cache
+#node_cache_size = 0
+"
+		.to_string(),
+	);
+	retval.insert(
+		"node_cache_ttl_secs".to_string(),
+		"
+#Seconds a cached response of the node is reused for
+#node_cache_ttl_secs = 10
+"
+		.to_string(),
+	);
+	retval.insert(
+		"avoid_mixed_coinbase".to_string(),
+		"
+#If true, sends spend either coinbase outputs only or plain outputs only, and
+#fail if neither kind covers the amount on its own, so the provenance of mining
+#payouts isn't mixed with other funds
+#avoid_mixed_coinbase = false
+"
+		.to_string(),
+	);
+	retval.insert(
+		"consolidation_fee_threshold".to_string(),
+		"
+#If set, sends also spend the smallest outputs worth less than the amount sent,
+#while each raises the fee by at most this many nanoepics, consolidating them
+#into the change so the number of outputs of the wallet stays down
+#consolidation_fee_threshold = 1000000
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_allowed_origins".to_string(),
+		"
+#Origins of the web frontends allowed to call the owner and foreign APIs from a
+#browser, any origin being allowed if not set
+#api_allowed_origins = [\"https://wallet.example.com\"]
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_exposed_headers".to_string(),
+		"
+#Response headers the web frontends of the allowed origins can read
+#api_exposed_headers = [\"Content-Length\"]
+"
+		.to_string(),
+	);
+	retval.insert(
+		"api_behind_proxy".to_string(),
+		"
+#Whether the listeners are behind a reverse proxy, the address of the clients
+#being read from its X-Forwarded-For header
+#api_behind_proxy = false
+"
+		.to_string(),
+	);
+	retval.insert(
+		"send_timeout_secs".to_string(),
+		"
+#seconds a send over http or Tor waits for the recipient's response. Sends
+#taking longer are aborted, their transaction being cancelled with the timeout
+#as its failure reason, so they can be made again right away
+#send_timeout_secs = 120
+"
+		.to_string(),
+	);
+	retval.insert(
+		"owner_api_client_ca_file".to_string(),
+		"
+#path of the CA certificate the clients of the owner API must present a
+#certificate signed by, connections without one being refused. Requires the
+#TLS certificate of the listener to be set
+#owner_api_client_ca_file = \"\"
+"
+		.to_string(),
+	);
+	retval.insert(
+		"require_send_approval".to_string(),
+		"
+#whether the owner API's sends must be approved with approve_tx, by a second
+#operator holding a token opened with the approver scope, before they're sent
+#to their recipient or finalized
+#require_send_approval = false
+"
+		.to_string(),
+	);
+
 	retval.insert(
 		"[logging]".to_string(),
 		"
@@ -274,6 +507,15 @@ fn comments() -> HashMap<String, String> {
 		.to_string(),
 	);
 
+	retval.insert(
+		"epicbox_receive_workers".to_string(),
+		"
+#Number of incoming slates the listener processes at the same time. Their results
+#are still saved one at a time, in the order the slates were received (default 4)
+"
+		.to_string(),
+	);
+
 	retval
 }
 
@@ -17,6 +17,7 @@
 use dirs;
 use rand::distributions::{Alphanumeric, Distribution};
 use rand::thread_rng;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::{self, File};
 use std::io::prelude::*;
@@ -27,7 +28,7 @@ use toml;
 
 use crate::comments::insert_comments;
 use crate::core::global;
-use crate::types::{ConfigError, GlobalWalletConfig, GlobalWalletConfigMembers};
+use crate::types::{ConfigError, ConfigSource, GlobalWalletConfig, GlobalWalletConfigMembers};
 use crate::types::{EpicboxConfig, TorConfig, WalletConfig};
 use crate::util::logger::LoggingConfig;
 
@@ -41,6 +42,8 @@ pub const EPIC_WALLET_DIR: &'static str = "wallet_data";
 pub const API_SECRET_FILE_NAME: &'static str = ".api_secret";
 /// Owner API secret
 pub const OWNER_API_SECRET_FILE_NAME: &'static str = ".owner_api_secret";
+/// Environment variable giving the path of the system-wide configuration file
+pub const SYSTEM_CONFIG_ENV: &'static str = "EPIC_WALLET_SYSTEM_CONFIG";
 
 fn get_epic_path(chain_type: &global::ChainTypes) -> Result<PathBuf, ConfigError> {
 	// Check if epic dir exists
@@ -57,6 +60,32 @@ fn get_epic_path(chain_type: &global::ChainTypes) -> Result<PathBuf, ConfigError
 	Ok(epic_path)
 }
 
+/// Path of the system-wide configuration file of the chain, holding the values
+/// shared by all the wallets of the host, e.g. `/etc/epic/main/epic-wallet.toml`.
+/// The configuration file of each wallet overrides them.
+pub fn system_config_path(chain_type: &global::ChainTypes) -> PathBuf {
+	if let Some(p) = env::var_os(SYSTEM_CONFIG_ENV) {
+		return PathBuf::from(p);
+	}
+	let mut path = match env::var_os("PROGRAMDATA") {
+		Some(p) if cfg!(windows) => PathBuf::from(p),
+		_ => PathBuf::from("/etc"),
+	};
+	path.push("epic");
+	path.push(chain_type.shortname());
+	path.push(WALLET_CONFIG_FILE_NAME);
+	path
+}
+
+fn existing_system_config(chain_type: &global::ChainTypes) -> Option<PathBuf> {
+	let path = system_config_path(chain_type);
+	if path.exists() {
+		Some(path)
+	} else {
+		None
+	}
+}
+
 fn check_config_current_dir(path: &str) -> Option<PathBuf> {
 	let p = env::current_dir();
 
@@ -73,17 +102,38 @@ fn check_config_current_dir(path: &str) -> Option<PathBuf> {
 	None
 }
 
+fn new_api_secret() -> String {
+	Alphanumeric
+		.sample_iter(&mut thread_rng())
+		.take(20)
+		.collect()
+}
+
 /// Create file with api secret
 pub fn init_api_secret(api_secret_path: &PathBuf) -> Result<(), ConfigError> {
 	let mut api_secret_file = File::create(api_secret_path)?;
-	let api_secret: String = Alphanumeric
-		.sample_iter(&mut thread_rng())
-		.take(20)
-		.collect();
+	let api_secret = new_api_secret();
 	api_secret_file.write_all(api_secret.as_bytes())?;
 	Ok(())
 }
 
+/// Replace the secret in an api secret file with a new one, returning it. The new
+/// file is written beside the old one and moved over it, so readers of the file
+/// see either the old secret or the new one.
+pub fn rotate_api_secret(api_secret_path: &PathBuf) -> Result<String, ConfigError> {
+	let api_secret = new_api_secret();
+	let mut tmp_path = api_secret_path.clone().into_os_string();
+	tmp_path.push(".tmp");
+	let tmp_path = PathBuf::from(tmp_path);
+	{
+		let mut api_secret_file = File::create(&tmp_path)?;
+		api_secret_file.write_all(api_secret.as_bytes())?;
+		api_secret_file.sync_all()?;
+	}
+	fs::rename(&tmp_path, api_secret_path)?;
+	Ok(api_secret)
+}
+
 /// Check if file contains a secret and nothing else
 pub fn check_api_secret(api_secret_path: &PathBuf) -> Result<(), ConfigError> {
 	let api_secret_file = File::open(api_secret_path)?;
@@ -123,9 +173,10 @@ pub fn initial_setup_wallet(
 ) -> Result<GlobalWalletConfig, ConfigError> {
 	check_api_secret_file(chain_type, data_path.clone(), OWNER_API_SECRET_FILE_NAME)?;
 	check_api_secret_file(chain_type, data_path.clone(), API_SECRET_FILE_NAME)?;
+	let system_config = existing_system_config(chain_type);
 	// Use config file if current directory if it exists, .epic home otherwise
 	if let Some(p) = check_config_current_dir(WALLET_CONFIG_FILE_NAME) {
-		GlobalWalletConfig::new(p.to_str().unwrap())
+		GlobalWalletConfig::new_layered(p.to_str().unwrap(), chain_type, system_config)
 	} else {
 		// Check if epic dir exists
 		let epic_path = match data_path {
@@ -143,9 +194,17 @@ pub fn initial_setup_wallet(
 			default_config.config_file_path = Some(config_path);
 			// update paths relative to current dir
 			default_config.update_paths(&epic_path);
+			if let Some(p) = system_config {
+				default_config.apply_layer(&p, ConfigSource::System)?;
+				default_config.system_config_path = Some(p);
+			}
 			Ok(default_config)
 		} else {
-			GlobalWalletConfig::new(config_path.to_str().unwrap())
+			GlobalWalletConfig::new_layered(
+				config_path.to_str().unwrap(),
+				chain_type,
+				system_config,
+			)
 		}
 	}
 }
@@ -165,6 +224,8 @@ impl Default for GlobalWalletConfig {
 	fn default() -> GlobalWalletConfig {
 		GlobalWalletConfig {
 			config_file_path: None,
+			system_config_path: None,
+			sources: BTreeMap::new(),
 			members: Some(GlobalWalletConfigMembers::default()),
 		}
 	}
@@ -210,6 +271,94 @@ impl GlobalWalletConfig {
 		return_value.read_config()
 	}
 
+	/// Read the configuration file at the given path over the system-wide
+	/// configuration file if any, itself over the defaults of the chain. Paths
+	/// configured by neither file are relative to the directory of the wallet's file.
+	pub fn new_layered(
+		file_path: &str,
+		chain_type: &global::ChainTypes,
+		system_config_path: Option<PathBuf>,
+	) -> Result<GlobalWalletConfig, ConfigError> {
+		let system_config_path = match system_config_path {
+			Some(p) => p,
+			None => return GlobalWalletConfig::new(file_path),
+		};
+		let config_file = PathBuf::from(file_path);
+		if !config_file.exists() {
+			return Err(ConfigError::FileNotFoundError(file_path.to_owned()));
+		}
+
+		let mut config = GlobalWalletConfig::for_chain(chain_type);
+		if let Some(dir) = config_file.parent() {
+			config.update_paths(&dir.to_path_buf());
+		}
+		config.apply_layer(&system_config_path, ConfigSource::System)?;
+		config.apply_layer(&config_file, ConfigSource::Wallet)?;
+		config.config_file_path = Some(config_file);
+		config.system_config_path = Some(system_config_path);
+		Ok(config)
+	}
+
+	/// Set the values of the configuration file at the given path over the
+	/// current ones
+	fn apply_layer(&mut self, path: &PathBuf, source: ConfigSource) -> Result<(), ConfigError> {
+		let layer = read_table(path)?;
+		let mut merged = self.members_table()?;
+		merge_layer(&mut merged, layer, source, "", &mut self.sources);
+		let members = toml::Value::Table(merged).try_into().map_err(|e| {
+			ConfigError::ParseError(path.to_string_lossy().into_owned(), format!("{}", e))
+		})?;
+		self.members = Some(members);
+		Ok(())
+	}
+
+	fn members_table(&self) -> Result<toml::value::Table, ConfigError> {
+		let members = self.members.clone().unwrap_or_default();
+		match toml::Value::try_from(&members) {
+			Ok(toml::Value::Table(t)) => Ok(t),
+			Ok(_) => Ok(toml::value::Table::new()),
+			Err(e) => Err(ConfigError::SerializationError(format!("{}", e))),
+		}
+	}
+
+	/// Value of every configuration key, e.g. `wallet.api_listen_port`, in order,
+	/// with the layer it was read from
+	pub fn effective_values(&self) -> Result<Vec<(String, String, ConfigSource)>, ConfigError> {
+		let mut values = vec![];
+		flatten_values(&self.members_table()?, "", &mut values);
+		Ok(values
+			.into_iter()
+			.map(|(k, v)| {
+				let source = self
+					.sources
+					.get(&k)
+					.cloned()
+					.unwrap_or(ConfigSource::Default);
+				(k, v, source)
+			})
+			.collect())
+	}
+
+	/// Read the configuration again from the files it was read from, picking up the
+	/// changes made to them since
+	pub fn reload(&self) -> Result<GlobalWalletConfig, ConfigError> {
+		let path = match self.config_file_path.as_ref() {
+			Some(p) if p.exists() => p.to_string_lossy().into_owned(),
+			Some(p) => return Err(ConfigError::FileNotFoundError(p.display().to_string())),
+			None => {
+				return Err(ConfigError::FileNotFoundError(
+					WALLET_CONFIG_FILE_NAME.to_owned(),
+				))
+			}
+		};
+		let chain_type = self
+			.members
+			.as_ref()
+			.and_then(|m| m.wallet.chain_type.clone())
+			.unwrap_or(global::ChainTypes::Mainnet);
+		GlobalWalletConfig::new_layered(&path, &chain_type, self.system_config_path.clone())
+	}
+
 	/// Read config
 	fn read_config(mut self) -> Result<GlobalWalletConfig, ConfigError> {
 		let mut file = File::open(self.config_file_path.as_mut().unwrap())?;
@@ -219,6 +368,11 @@ impl GlobalWalletConfig {
 		let decoded: Result<GlobalWalletConfigMembers, toml::de::Error> = toml::from_str(&fixed);
 		match decoded {
 			Ok(gc) => {
+				if let Ok(t) = toml::from_str::<toml::value::Table>(&fixed) {
+					for (k, v) in t.iter() {
+						record_sources(k, v, ConfigSource::Wallet, &mut self.sources);
+					}
+				}
 				self.members = Some(gc);
 				return Ok(self);
 			}
@@ -286,9 +440,28 @@ impl GlobalWalletConfig {
 		}
 	}
 
-	/// Write configuration to a file
+	/// Serialize the values of the configuration differing from the ones inherited
+	/// from the system-wide configuration file and the defaults of the chain
+	fn ser_overrides(&mut self, system_config_path: &PathBuf) -> Result<String, ConfigError> {
+		let chain_type = self
+			.members
+			.as_ref()
+			.and_then(|m| m.wallet.chain_type.clone())
+			.unwrap_or(global::ChainTypes::Mainnet);
+		let mut inherited = GlobalWalletConfig::for_chain(&chain_type);
+		inherited.apply_layer(system_config_path, ConfigSource::System)?;
+		let overrides = table_overrides(self.members_table()?, &inherited.members_table()?);
+		toml::to_string(&toml::Value::Table(overrides))
+			.map_err(|e| ConfigError::SerializationError(format!("{}", e)))
+	}
+
+	/// Write configuration to a file. When layered over a system-wide configuration
+	/// file, only the values overriding it are written.
 	pub fn write_to_file(&mut self, name: &str) -> Result<(), ConfigError> {
-		let conf_out = self.ser_config()?;
+		let conf_out = match self.system_config_path.clone() {
+			Some(p) => self.ser_overrides(&p)?,
+			None => self.ser_config()?,
+		};
 		let fixed_config = GlobalWalletConfig::fix_log_level(conf_out);
 		let commented_config = insert_comments(fixed_config);
 		let mut file = File::create(name)?;
@@ -310,3 +483,162 @@ impl GlobalWalletConfig {
 			.replace("ERROR", "Error")
 	}
 }
+
+/// Read a configuration file as a TOML table
+fn read_table(path: &PathBuf) -> Result<toml::value::Table, ConfigError> {
+	let mut contents = String::new();
+	File::open(path)?.read_to_string(&mut contents)?;
+	let fixed = GlobalWalletConfig::fix_warning_level(contents);
+	toml::from_str(&fixed)
+		.map_err(|e| ConfigError::ParseError(path.to_string_lossy().into_owned(), format!("{}", e)))
+}
+
+/// Record `source` as the layer of the value at `key`, and of all the values
+/// within it
+fn record_sources(
+	key: &str,
+	value: &toml::Value,
+	source: ConfigSource,
+	sources: &mut BTreeMap<String, ConfigSource>,
+) {
+	let prefix = format!("{}.", key);
+	let replaced: Vec<String> = sources
+		.keys()
+		.filter(|k| k.starts_with(&prefix))
+		.cloned()
+		.collect();
+	for k in replaced {
+		sources.remove(&k);
+	}
+	match value {
+		toml::Value::Table(t) => {
+			for (k, v) in t.iter() {
+				record_sources(&format!("{}{}", prefix, k), v, source, sources);
+			}
+		}
+		_ => {
+			sources.insert(key.to_owned(), source);
+		}
+	}
+}
+
+/// Set the values of `layer` over the ones of `base`, tables being merged key by key
+fn merge_layer(
+	base: &mut toml::value::Table,
+	layer: toml::value::Table,
+	source: ConfigSource,
+	prefix: &str,
+	sources: &mut BTreeMap<String, ConfigSource>,
+) {
+	for (k, v) in layer {
+		let key = format!("{}{}", prefix, k);
+		match (base.remove(&k), v) {
+			(Some(toml::Value::Table(mut b)), toml::Value::Table(t)) => {
+				merge_layer(&mut b, t, source, &format!("{}.", key), sources);
+				base.insert(k, toml::Value::Table(b));
+			}
+			(_, v) => {
+				record_sources(&key, &v, source, sources);
+				base.insert(k, v);
+			}
+		}
+	}
+}
+
+/// Values of `table` differing from the ones of `base`
+fn table_overrides(table: toml::value::Table, base: &toml::value::Table) -> toml::value::Table {
+	let mut overrides = toml::value::Table::new();
+	for (k, v) in table {
+		match (v, base.get(&k)) {
+			(toml::Value::Table(t), Some(toml::Value::Table(b))) => {
+				let t = table_overrides(t, b);
+				if !t.is_empty() {
+					overrides.insert(k, toml::Value::Table(t));
+				}
+			}
+			(ref v, Some(b)) if v == b => {}
+			(v, _) => {
+				overrides.insert(k, v);
+			}
+		}
+	}
+	overrides
+}
+
+/// Flatten the values of a table to their keys and TOML representation
+fn flatten_values(table: &toml::value::Table, prefix: &str, values: &mut Vec<(String, String)>) {
+	for (k, v) in table.iter() {
+		let key = format!("{}{}", prefix, k);
+		match v {
+			toml::Value::Table(t) => flatten_values(t, &format!("{}.", key), values),
+			_ => values.push((key, value_string(v))),
+		}
+	}
+}
+
+fn value_string(value: &toml::Value) -> String {
+	match value {
+		toml::Value::String(s) => format!("{:?}", s),
+		toml::Value::Array(a) => {
+			let items: Vec<String> = a.iter().map(value_string).collect();
+			format!("[{}]", items.join(", "))
+		}
+		toml::Value::Table(t) => {
+			let items: Vec<String> = t
+				.iter()
+				.map(|(k, v)| format!("{} = {}", k, value_string(v)))
+				.collect();
+			format!("{{ {} }}", items.join(", "))
+		}
+		v => format!("{}", v),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn layered_values() {
+		let mut base: toml::value::Table = toml::from_str(
+			"[wallet]\napi_listen_port = 3415\nno_commit_cache = false\n[logging]\nlog_to_file = true\n",
+		)
+		.unwrap();
+		let system: toml::value::Table =
+			toml::from_str("[wallet]\napi_listen_port = 4415\nno_commit_cache = true\n").unwrap();
+		let wallet: toml::value::Table =
+			toml::from_str("[wallet]\napi_listen_port = 5415\n").unwrap();
+		let mut sources = BTreeMap::new();
+		merge_layer(&mut base, system, ConfigSource::System, "", &mut sources);
+		merge_layer(&mut base, wallet, ConfigSource::Wallet, "", &mut sources);
+
+		let mut values = vec![];
+		flatten_values(&base, "", &mut values);
+		assert_eq!(
+			values,
+			vec![
+				("logging.log_to_file".to_owned(), "true".to_owned()),
+				("wallet.api_listen_port".to_owned(), "5415".to_owned()),
+				("wallet.no_commit_cache".to_owned(), "true".to_owned()),
+			]
+		);
+		assert_eq!(sources.get("logging.log_to_file"), None);
+		assert_eq!(
+			sources.get("wallet.api_listen_port"),
+			Some(&ConfigSource::Wallet)
+		);
+		assert_eq!(
+			sources.get("wallet.no_commit_cache"),
+			Some(&ConfigSource::System)
+		);
+
+		// only the values differing from the inherited ones are overrides
+		let inherited: toml::value::Table =
+			toml::from_str("[wallet]\napi_listen_port = 4415\nno_commit_cache = true\n").unwrap();
+		let overrides = table_overrides(base, &inherited);
+		let expected: toml::value::Table =
+			toml::from_str("[wallet]\napi_listen_port = 5415\n[logging]\nlog_to_file = true\n")
+				.unwrap();
+		assert_eq!(overrides, expected);
+	}
+}
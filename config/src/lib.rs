@@ -30,8 +30,15 @@ mod comments;
 pub mod config;
 pub mod types;
 
-pub use crate::config::{initial_setup_wallet, EPIC_WALLET_DIR, WALLET_CONFIG_FILE_NAME};
+pub use crate::config::{
+	initial_setup_wallet, rotate_api_secret, system_config_path, EPIC_WALLET_DIR,
+	SYSTEM_CONFIG_ENV, WALLET_CONFIG_FILE_NAME,
+};
 pub use crate::types::{
-	ConfigError, EpicboxConfig, GlobalWalletConfig, GlobalWalletConfigMembers, TorConfig,
-	WalletConfig,
+	BackupConfig, BalanceAlertsConfig, BalanceThresholdConfig, ChainParamsConfig, ConfigError,
+	ConfigSource, DecoyOutputConfig, DerivationConfig, EpicboxConfig, ForeignApiAccessConfig,
+	ForeignApiWalletConfig, GlobalWalletConfig, GlobalWalletConfigMembers, LanConfig,
+	LargeSendConfirmConfig, MaturityNotificationsConfig, MessageTemplatesConfig,
+	OpenThrottleConfig, PasswordPolicyConfig, PriceFeedConfig, ReceiveHookConfig, StatementsConfig,
+	SweepConfig, TorConfig, TxHooksConfig, WalletConfig,
 };
@@ -14,6 +14,7 @@
 
 //! Public types for config modules
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::io;
 use std::path::PathBuf;
@@ -56,6 +57,122 @@ pub struct WalletConfig {
 	pub dark_background_color_scheme: Option<bool>,
 	/// The exploding lifetime (minutes) for keybase notification on coins received
 	pub keybase_notify_ttl: Option<u16>,
+	/// Locale used to format command line output, e.g. "en" or "de-DE"
+	pub locale: Option<String>,
+	/// Optional JSON file of translated command line messages
+	pub locale_catalog_path: Option<String>,
+	/// Number of decimals displayed for amounts in command line output
+	pub amount_decimals: Option<usize>,
+	/// Unit in which amounts are displayed and entered on the command line:
+	/// "epic", "milli", "micro" or "raw"
+	pub display_unit: Option<String>,
+	/// Maximum weight of transactions created when sending, unlimited if not set
+	pub max_tx_weight: Option<u64>,
+	/// Whether a send exceeding `max_tx_weight` is split into several sequential
+	/// transactions instead of failing
+	pub auto_split_tx: Option<bool>,
+	/// Optional policy for sweeping funds from a hot account to a cold account
+	pub sweep: Option<SweepConfig>,
+	/// Optional checks run on incoming transactions before the listener signs them
+	pub receive_hook: Option<ReceiveHookConfig>,
+	/// Optional commands run before sending and after finalizing transactions
+	pub tx_hooks: Option<TxHooksConfig>,
+	/// Optional templates of the participant messages attached to transactions
+	/// when none is given on the command line
+	pub message_templates: Option<MessageTemplatesConfig>,
+	/// Optional settings of the local network transport
+	pub lan: Option<LanConfig>,
+	/// Optional extra self output occasionally included in sends
+	pub decoy_output: Option<DecoyOutputConfig>,
+	/// If set, the listener cancels received transactions not seen on chain after
+	/// this many hours, removing the outputs they were expected to create
+	pub pending_receive_expiry_hours: Option<u64>,
+	/// Optional layout of the key derivation paths of accounts, only used when
+	/// the wallet is created
+	pub derivation: Option<DerivationConfig>,
+	/// Maximum number of sends queued through the owner API executed at the same time,
+	/// 1 if not set
+	pub send_queue_workers: Option<usize>,
+	/// Whether sent and invoiced slates always use the oldest slate version, without
+	/// optional fields, for receivers running older wallets
+	pub strict_slate_compat: Option<bool>,
+	/// Optional alerts raised when the spendable balance of an account crosses a threshold
+	pub balance_alerts: Option<BalanceAlertsConfig>,
+	/// Optional notifications of coinbase and height-locked outputs becoming spendable
+	pub maturity_notifications: Option<MaturityNotificationsConfig>,
+	/// Maximum size in bytes of a request body accepted by the owner and foreign
+	/// listeners, 8 MiB if not set
+	pub api_max_request_bytes: Option<usize>,
+	/// Maximum number of calls in a JSON-RPC batch request, 100 if not set
+	pub api_max_batch_len: Option<usize>,
+	/// Maximum number of entries returned by an owner API output or transaction
+	/// query, unlimited if not set
+	pub api_max_results: Option<usize>,
+	/// Whether the foreign API signs the slates it returns with the key of the wallet's
+	/// payment proof address, so senders can check the response came from this wallet
+	pub sign_foreign_responses: Option<bool>,
+	/// Whether a wallet restored from its recovery phrase is scanned in the background
+	/// the first time it's opened, rather than on its first refresh
+	pub auto_initial_scan: Option<bool>,
+	/// Optional monthly statements of the accounts, written while the wallet is listening
+	pub statements: Option<StatementsConfig>,
+	/// Optional requirements on the passwords wallets are created with or changed to
+	pub password_policy: Option<PasswordPolicyConfig>,
+	/// Optional settings of the backoff on failed attempts at opening the wallet
+	/// through the owner API, applied with the default settings if not set
+	pub open_throttle: Option<OpenThrottleConfig>,
+	/// Optional chain parameters overriding those of the chain type, on user-defined
+	/// test chains only
+	pub chain_params: Option<ChainParamsConfig>,
+	/// Optional confirmation asked by the send command for sends of large amounts
+	pub large_send_confirm: Option<LargeSendConfirmConfig>,
+	/// Requests to the node taking longer than this many milliseconds are logged,
+	/// 5000 if not set
+	pub node_slow_request_ms: Option<u64>,
+	/// Calls to the owner and foreign APIs taking longer than this many milliseconds
+	/// are logged, 5000 if not set
+	pub rpc_slow_call_ms: Option<u64>,
+	/// Number of outputs, and of kernel lookups, whose node responses are cached,
+	/// 0 if not set, which disables the cache.
+	pub node_cache_size: Option<usize>,
+	/// Seconds the cached node responses are reused for, 10 if not set
+	pub node_cache_ttl_secs: Option<u64>,
+	/// Whether sends pick their inputs among either the coinbase or the plain
+	/// outputs, rather than mixing both, false if not set
+	pub avoid_mixed_coinbase: Option<bool>,
+	/// Maximum fee increase, in nanoepics, for which sends also spend small outputs
+	/// to consolidate them, no consolidation if not set
+	pub consolidation_fee_threshold: Option<u64>,
+	/// Optional target the wallet's data is backed up to, encrypted
+	pub backup: Option<BackupConfig>,
+	/// Whether the owner API's sends must be approved by a second operator, holding a
+	/// token of the approver scope, before they're sent or finalized
+	pub require_send_approval: Option<bool>,
+	/// Methods the foreign API listener serves, and how often
+	pub foreign_api_access: Option<ForeignApiAccessConfig>,
+	/// Origins of the web frontends allowed to call the owner and foreign APIs from a
+	/// browser, or "*" for any origin. Any origin is allowed if not set.
+	pub api_allowed_origins: Option<Vec<String>>,
+	/// Response headers the web frontends of the allowed origins can read
+	pub api_exposed_headers: Option<Vec<String>>,
+	/// Whether the listeners are behind a reverse proxy, whose `X-Forwarded-For`
+	/// header gives the address of the client
+	pub api_behind_proxy: Option<bool>,
+	/// Seconds a send over http or Tor waits for the recipient's response before the
+	/// send is aborted and its transaction cancelled. The exchange isn't limited if
+	/// not set.
+	pub send_timeout_secs: Option<u64>,
+	/// CA certificate file the owner API's clients must present a certificate signed
+	/// by, over TLS. Clients aren't asked for a certificate if not set.
+	pub owner_api_client_ca_file: Option<String>,
+	/// Further wallets the owner API serves, by the name its calls give in their
+	/// `wallet` member, with the data directory of each
+	pub owner_api_wallets: Option<BTreeMap<String, String>>,
+	/// Further wallets the foreign API listener serves, by the name their API is served
+	/// under at `/<name>/v2/foreign`
+	pub foreign_api_wallets: Option<BTreeMap<String, ForeignApiWalletConfig>>,
+	/// Optional source of the exchange rates of EPIC to fiat currencies
+	pub price_feed: Option<PriceFeedConfig>,
 }
 
 impl Default for WalletConfig {
@@ -75,6 +192,51 @@ impl Default for WalletConfig {
 			tls_certificate_key: None,
 			dark_background_color_scheme: Some(true),
 			keybase_notify_ttl: Some(1440),
+			locale: Some("en".to_owned()),
+			locale_catalog_path: None,
+			amount_decimals: Some(8),
+			display_unit: Some("epic".to_owned()),
+			max_tx_weight: None,
+			auto_split_tx: Some(false),
+			sweep: None,
+			receive_hook: None,
+			tx_hooks: None,
+			message_templates: None,
+			lan: None,
+			decoy_output: None,
+			pending_receive_expiry_hours: None,
+			derivation: None,
+			send_queue_workers: None,
+			strict_slate_compat: Some(false),
+			balance_alerts: None,
+			maturity_notifications: None,
+			api_max_request_bytes: None,
+			api_max_batch_len: None,
+			api_max_results: None,
+			sign_foreign_responses: Some(false),
+			auto_initial_scan: Some(true),
+			statements: None,
+			password_policy: None,
+			open_throttle: None,
+			chain_params: None,
+			large_send_confirm: None,
+			node_slow_request_ms: None,
+			rpc_slow_call_ms: None,
+			node_cache_size: None,
+			node_cache_ttl_secs: None,
+			avoid_mixed_coinbase: None,
+			consolidation_fee_threshold: None,
+			backup: None,
+			require_send_approval: None,
+			foreign_api_access: None,
+			api_allowed_origins: None,
+			api_exposed_headers: None,
+			api_behind_proxy: None,
+			send_timeout_secs: None,
+			owner_api_client_ca_file: None,
+			owner_api_wallets: None,
+			foreign_api_wallets: None,
+			price_feed: None,
 		}
 	}
 }
@@ -159,6 +321,288 @@ impl Default for TorConfig {
 	}
 }
 
+/// Policy for sweeping the balance of a hot account above a threshold to a cold account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SweepConfig {
+	/// Account whose spendable balance is kept at or below `max_hot_balance`
+	pub hot_account: String,
+	/// Account the excess balance is swept to
+	pub cold_account: String,
+	/// Spendable balance, in nanoepics, to leave in the hot account
+	pub max_hot_balance: u64,
+	/// Minimum number of confirmations of outputs to sweep, 10 if not set
+	pub minimum_confirmations: Option<u64>,
+	/// If set, sweep automatically at this interval (seconds) while the wallet is listening
+	pub interval_secs: Option<u64>,
+}
+
+/// Restrictions on the calls served by the foreign API listener, so a public-facing
+/// listener can't be made to build partial slates without end
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForeignApiAccessConfig {
+	/// Methods served, all of them if not set
+	pub allow_methods: Option<Vec<String>>,
+	/// Methods refused, even if allowed
+	pub deny_methods: Option<Vec<String>>,
+	/// Maximum number of calls served per minute, all methods together, unlimited if
+	/// not set
+	pub max_calls_per_minute: Option<u32>,
+	/// Maximum number of calls of a method served per minute, e.g. `receive_tx = 10`
+	pub method_calls_per_minute: Option<BTreeMap<String, u32>>,
+}
+
+/// A further wallet served by the foreign API listener
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForeignApiWalletConfig {
+	/// Data directory of the wallet
+	pub data_file_dir: String,
+	/// File holding the wallet's password, the password of the listener's wallet
+	/// being used if not set
+	pub password_file: Option<String>,
+	/// Hostnames the wallet's API is also served for at `/v2/foreign`
+	pub hostnames: Option<Vec<String>>,
+	/// TLS certificate presented to the clients asking for one of the hostnames, the
+	/// listener's certificate being presented if not set
+	pub tls_certificate_file: Option<String>,
+	/// Private key of the TLS certificate
+	pub tls_certificate_key: Option<String>,
+}
+
+/// Target the wallet's data is backed up to. Backups are encrypted before leaving the
+/// wallet, with a key derived from the wallet password and the passphrase if set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BackupConfig {
+	/// Kind of target: "dir", "s3" or "webdav"
+	pub target: String,
+	/// Directory the backups are written to, for a "dir" target
+	pub dir: Option<String>,
+	/// Endpoint of the S3-compatible service, or URL of the WebDAV collection the
+	/// backups are written to
+	pub url: Option<String>,
+	/// Bucket the backups are written to, for an "s3" target
+	pub bucket: Option<String>,
+	/// Region of the bucket, for an "s3" target, "us-east-1" if not set
+	pub region: Option<String>,
+	/// Access key id, for an "s3" target
+	pub access_key: Option<String>,
+	/// Secret access key, for an "s3" target
+	pub secret_key: Option<String>,
+	/// User name, for a "webdav" target
+	pub username: Option<String>,
+	/// Password, for a "webdav" target
+	pub password: Option<String>,
+	/// Passphrase the encryption key is derived from along with the wallet password,
+	/// needed to restore the backups
+	pub passphrase: Option<String>,
+	/// If set, back up automatically at this interval (seconds) while the wallet is listening
+	pub interval_secs: Option<u64>,
+}
+
+/// Checks run by the listeners on incoming transactions before signing them.
+/// The built-in policy is applied first, then the external command if any.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReceiveHookConfig {
+	/// Command run with the incoming slate as JSON on its standard input. The transaction
+	/// is accepted if the command exits successfully, otherwise its output is the reason
+	/// given to the sender.
+	pub command: Option<String>,
+	/// Seconds the command has to decide, after which it's killed and the transaction
+	/// rejected. 30 seconds if not set.
+	pub command_timeout_secs: Option<u64>,
+	/// Reject transactions of less than this amount, in nanoepics
+	pub min_amount: Option<u64>,
+	/// Reject transactions of more than this amount, in nanoepics
+	pub max_amount: Option<u64>,
+	/// Reject transactions whose sender message doesn't start with this prefix,
+	/// e.g. to require an order reference
+	pub message_prefix: Option<String>,
+}
+
+/// Commands run around the transactions the wallet sends, each with the slate as
+/// JSON on its standard input
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TxHooksConfig {
+	/// Command run before a transaction is sent. The send goes ahead if the command
+	/// exits successfully, otherwise it's vetoed with the command's output as the reason.
+	pub pre_send_command: Option<String>,
+	/// Command run once a transaction is finalized, e.g. for external logging
+	pub post_finalize_command: Option<String>,
+}
+
+/// Participant message templates, in which `{amount}`, `{account}` and
+/// `{invoice_ref}` are replaced by the amount of the transaction, the wallet
+/// account and the reference given with `--ref`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MessageTemplatesConfig {
+	/// Message attached to sent transactions and paid invoices
+	pub send: Option<String>,
+	/// Message attached to received transactions
+	pub receive: Option<String>,
+	/// Message attached to issued invoices, e.g. "Invoice {invoice_ref}: {amount}"
+	pub invoice: Option<String>,
+}
+
+/// Local network transport, over which wallets discovered through mDNS exchange
+/// slates directly
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LanConfig {
+	/// Name the listener advertises the wallet under, the host name if not set
+	pub name: Option<String>,
+	/// Seconds a send looks for the named wallet before failing, 5 if not set
+	pub discovery_timeout_secs: Option<u64>,
+}
+
+/// Spendable balance range of an account, outside of which an alert is raised
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BalanceThresholdConfig {
+	/// Label of the watched account
+	pub account: String,
+	/// Alert when the spendable balance drops below this amount, in nanoepics
+	pub below: Option<u64>,
+	/// Alert when the spendable balance rises above this amount, in nanoepics
+	pub above: Option<u64>,
+}
+
+/// Alerts raised by the wallet's updater when the spendable balance of an account
+/// moves outside of its threshold. Alerts are always logged, and also delivered to
+/// the webhook and command if set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BalanceAlertsConfig {
+	/// Watched accounts and their thresholds
+	pub thresholds: Vec<BalanceThresholdConfig>,
+	/// Minimum number of confirmations of outputs counted as spendable
+	pub minimum_confirmations: Option<u64>,
+	/// URL the alerts are posted to as JSON
+	pub webhook_url: Option<String>,
+	/// Command run with each alert as JSON on its standard input
+	pub command: Option<String>,
+	/// Interval (seconds) between checks while the wallet is listening, 60 if not set
+	pub interval_secs: Option<u64>,
+}
+
+/// Notifications raised by the wallet's updater when a coinbase or height-locked output
+/// becomes spendable. Notifications are always logged, and also posted to the webhook
+/// if set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaturityNotificationsConfig {
+	/// URL the notifications are posted to as JSON
+	pub webhook_url: Option<String>,
+	/// Interval (seconds) between checks while the wallet is listening, 60 if not set
+	pub interval_secs: Option<u64>,
+}
+
+/// Source of the exchange rates of EPIC to fiat currencies: a file of static rates,
+/// or a JSON endpoint the rate is picked out of. Exactly one of `file` and `url` must
+/// be set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriceFeedConfig {
+	/// Path of a JSON file of the price of one EPIC by currency, e.g. `{"USD": 0.25}`
+	pub file: Option<String>,
+	/// URL of a JSON endpoint returning the price of one EPIC, `{currency}` being
+	/// replaced by the lowercase currency code, e.g. `usd`
+	pub url: Option<String>,
+	/// JSONPath of the price in the endpoint's response, e.g.
+	/// `$['epic-cash'].{currency}`, or `$` for a response holding the price only
+	pub json_path: Option<String>,
+}
+
+/// Requirements on the passwords wallets are created with or changed to, checked when
+/// creating or recovering a wallet and when changing its password
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PasswordPolicyConfig {
+	/// Minimum number of characters, 8 if not set
+	pub min_length: Option<usize>,
+	/// Minimum number of kinds of characters used, out of lowercase letters, uppercase
+	/// letters, digits and others, 1 if not set
+	pub min_char_classes: Option<usize>,
+}
+
+/// Backoff on failed attempts at opening the wallet through the owner API. Once the
+/// allowed number of consecutive attempts failed, attempts are refused without checking
+/// the password until the lockout ends, the lockout doubling with each further failure.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OpenThrottleConfig {
+	/// Consecutive failed attempts allowed before opening is locked out, 5 if not set.
+	/// 0 disables the backoff
+	pub max_attempts: Option<u32>,
+	/// Duration (seconds) of the first lockout, 30 if not set
+	pub lockout_secs: Option<u64>,
+	/// Longest lockout (seconds), 3600 if not set
+	pub max_lockout_secs: Option<u64>,
+}
+
+/// Parameters of a user-defined chain, such as a local test chain or a private network,
+/// whose nodes don't run with the defaults of the `UserTesting` or `AutomatedTesting`
+/// chain types. Refused on mainnet and floonet, and when the node turns out to be on
+/// either of them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainParamsConfig {
+	/// Number of blocks before a coinbase output can be spent, as given by the chain
+	/// type if not set
+	pub coinbase_maturity: Option<u64>,
+}
+
+/// Confirmation asked by the send command before sending a large amount, by typing the
+/// exact amount and optionally the first characters of the destination. Skipped with
+/// `--yes`, for scripts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LargeSendConfirmConfig {
+	/// Sends of at least this amount, in nanoepics, must be confirmed
+	pub threshold: u64,
+	/// Number of leading characters of the destination to type as well, none if not set
+	pub dest_chars: Option<usize>,
+}
+
+/// Extra small output sent back to ourselves with some transactions, making heuristics
+/// based on the number of outputs less reliable
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecoyOutputConfig {
+	/// Value of the decoy output, in nanoepics
+	pub amount: u64,
+	/// Fraction of sends, between 0 and 1, including a decoy output. Defaults to 0.5
+	pub frequency: Option<f64>,
+}
+
+/// Monthly statements of the accounts, written as JSON once a month is over, with
+/// the value of their balances in a fiat currency if the price feed is configured
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatementsConfig {
+	/// Directory the statements are written to, one file per account and month
+	pub dir: String,
+	/// ISO 4217 code of the currency the balances are valued in, e.g. `USD`, using the
+	/// wallet's price feed
+	pub fiat_currency: Option<String>,
+}
+
+/// Layout of the key derivation paths of the wallet's accounts, applied when the wallet
+/// is created and stored with it. By default account k is at m/k/0 and its outputs at
+/// m/k/0/n; paths are at most 4 levels deep, the output level included.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DerivationConfig {
+	/// Constant levels preceding the account level, such as a purpose and coin type
+	#[serde(default)]
+	pub purpose: Vec<u32>,
+	/// Whether the constant levels are hardened
+	#[serde(default)]
+	pub hardened_purpose: bool,
+	/// Whether the account level is hardened
+	#[serde(default)]
+	pub hardened_account: bool,
+	/// Whether a chain level, always 0, follows the account level. Defaults to true
+	pub chain_level: Option<bool>,
+}
+
+impl Default for DerivationConfig {
+	fn default() -> DerivationConfig {
+		DerivationConfig {
+			purpose: vec![],
+			hardened_purpose: false,
+			hardened_account: false,
+			chain_level: Some(true),
+		}
+	}
+}
+
 /// Epicbox configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct EpicboxConfig {
@@ -170,6 +614,8 @@ pub struct EpicboxConfig {
 	pub epicbox_protocol_unsecure: Option<bool>,
 	/// Epicbox address id
 	pub epicbox_address_index: Option<u32>,
+	/// Number of incoming slates the listener processes at the same time
+	pub epicbox_receive_workers: Option<usize>,
 }
 
 impl Default for EpicboxConfig {
@@ -179,6 +625,7 @@ impl Default for EpicboxConfig {
 			epicbox_port: Some(443),
 			epicbox_protocol_unsecure: Some(false),
 			epicbox_address_index: Some(0),
+			epicbox_receive_workers: Some(4),
 		}
 	}
 }
@@ -197,10 +644,38 @@ impl From<io::Error> for ConfigError {
 pub struct GlobalWalletConfig {
 	/// Keep track of the file we've read
 	pub config_file_path: Option<PathBuf>,
+	/// System-wide configuration file whose values the wallet's file overrides, if any
+	#[serde(default)]
+	pub system_config_path: Option<PathBuf>,
+	/// Layer each configuration key, e.g. `wallet.api_listen_port`, was read from.
+	/// Keys not listed have their default value.
+	#[serde(skip)]
+	pub sources: BTreeMap<String, ConfigSource>,
 	/// Wallet members
 	pub members: Option<GlobalWalletConfigMembers>,
 }
 
+/// Where the effective value of a configuration key comes from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigSource {
+	/// Built-in default of the chain
+	Default,
+	/// System-wide configuration file shared by the wallets of the host
+	System,
+	/// The wallet's own configuration file
+	Wallet,
+}
+
+impl fmt::Display for ConfigSource {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			ConfigSource::Default => write!(f, "default"),
+			ConfigSource::System => write!(f, "system"),
+			ConfigSource::Wallet => write!(f, "wallet"),
+		}
+	}
+}
+
 /// Wallet internal members
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct GlobalWalletConfigMembers {
@@ -0,0 +1,216 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Basic authentication of the owner API, following changes of its secret file
+//! so the secret can be rotated without restarting the listener
+
+use crate::api::{Handler, HandlerObj, ResponseFuture};
+use crate::util::file::get_first_line;
+use crate::util::{to_base64, Mutex};
+use futures::future::ok;
+use hyper::header::{HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use hyper::{Body, Request, Response, StatusCode};
+use ring::constant_time::verify_slices_are_equal;
+use std::fs;
+use std::mem;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long the previous secret is still accepted once the secret file changes
+pub const OWNER_API_SECRET_GRACE_PERIOD: Duration = Duration::from_secs(300);
+
+/// Minimum time between two checks of the secret file
+const SECRET_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Value of the authorization header for the given secret
+fn basic_auth(api_secret: &str) -> String {
+	"Basic ".to_string() + &to_base64(&("epic:".to_string() + api_secret))
+}
+
+fn file_modified(path: &str) -> Option<SystemTime> {
+	fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Authorization header values currently accepted
+struct AcceptedSecrets {
+	current: String,
+	/// Value before the last change of the secret file, and when it changed
+	previous: Option<(String, Instant)>,
+	/// Modification time of the secret file when last read
+	modified: Option<SystemTime>,
+	checked: Instant,
+}
+
+/// Basic auth middleware accepting the secret in the owner API secret file. A
+/// new secret written to the file is picked up by the running listener, which
+/// keeps accepting the previous one for `OWNER_API_SECRET_GRACE_PERIOD`.
+pub struct RotatingBasicAuthMiddleware {
+	api_secret_path: Option<String>,
+	secrets: Mutex<AcceptedSecrets>,
+	basic_realm: &'static HeaderValue,
+	/// Paths served without authentication
+	ignore_uris: Vec<String>,
+}
+
+impl RotatingBasicAuthMiddleware {
+	/// Create the middleware for the secret read at startup, and the file it came from
+	pub fn new(
+		api_secret: String,
+		api_secret_path: Option<String>,
+		basic_realm: &'static HeaderValue,
+		ignore_uris: Vec<String>,
+	) -> RotatingBasicAuthMiddleware {
+		let modified = api_secret_path.as_ref().and_then(|p| file_modified(p));
+		RotatingBasicAuthMiddleware {
+			api_secret_path,
+			secrets: Mutex::new(AcceptedSecrets {
+				current: basic_auth(&api_secret),
+				previous: None,
+				modified,
+				checked: Instant::now(),
+			}),
+			basic_realm,
+			ignore_uris,
+		}
+	}
+
+	/// Read the secret file again if it changed since last read
+	fn refresh(&self, secrets: &mut AcceptedSecrets) {
+		let path = match self.api_secret_path.as_ref() {
+			Some(p) => p,
+			None => return,
+		};
+		let now = Instant::now();
+		if now.duration_since(secrets.checked) < SECRET_FILE_CHECK_INTERVAL {
+			return;
+		}
+		secrets.checked = now;
+		let modified = file_modified(path);
+		if modified.is_none() || modified == secrets.modified {
+			return;
+		}
+		secrets.modified = modified;
+
+		// keep the current secret if the file can't be read
+		let secret = match get_first_line(Some(path.clone())) {
+			Some(s) if !s.is_empty() => basic_auth(&s),
+			_ => {
+				warn!("Unable to read the changed owner API secret file {}", path);
+				return;
+			}
+		};
+		if secret != secrets.current {
+			warn!(
+				"Owner API secret changed, accepting the previous secret for {} more seconds",
+				OWNER_API_SECRET_GRACE_PERIOD.as_secs()
+			);
+			let previous = mem::replace(&mut secrets.current, secret);
+			secrets.previous = Some((previous, now));
+		}
+	}
+
+	fn is_authorized(&self, auth: &[u8]) -> bool {
+		let mut secrets = self.secrets.lock();
+		self.refresh(&mut secrets);
+		if verify_slices_are_equal(auth, secrets.current.as_bytes()).is_ok() {
+			return true;
+		}
+		match secrets.previous {
+			Some((ref previous, changed)) if changed.elapsed() < OWNER_API_SECRET_GRACE_PERIOD => {
+				verify_slices_are_equal(auth, previous.as_bytes()).is_ok()
+			}
+			_ => false,
+		}
+	}
+
+	fn unauthorized_response(&self) -> ResponseFuture {
+		let response = Response::builder()
+			.status(StatusCode::UNAUTHORIZED)
+			.header(WWW_AUTHENTICATE, self.basic_realm)
+			.body(Body::empty())
+			.unwrap();
+		Box::new(ok(response))
+	}
+}
+
+impl Handler for RotatingBasicAuthMiddleware {
+	fn call(
+		&self,
+		req: Request<Body>,
+		mut handlers: Box<dyn Iterator<Item = HandlerObj>>,
+	) -> ResponseFuture {
+		let next_handler = match handlers.next() {
+			Some(h) => h,
+			None => {
+				let response = Response::builder()
+					.status(StatusCode::INTERNAL_SERVER_ERROR)
+					.body(Body::from("no handler found"))
+					.unwrap();
+				return Box::new(ok(response));
+			}
+		};
+		if req.method().as_str() == "OPTIONS" {
+			return next_handler.call(req, handlers);
+		}
+		if self.ignore_uris.iter().any(|u| req.uri().path() == u) {
+			return next_handler.call(req, handlers);
+		}
+		let authorized = match req.headers().get(AUTHORIZATION) {
+			Some(auth) => self.is_authorized(auth.as_bytes()),
+			None => false,
+		};
+		if authorized {
+			next_handler.call(req, handlers)
+		} else {
+			self.unauthorized_response()
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::config::rotate_api_secret;
+	use std::path::PathBuf;
+	use std::thread;
+
+	#[test]
+	fn secret_rotation() {
+		let dir = "test_output/auth";
+		let _ = fs::create_dir_all(dir);
+		let path = PathBuf::from(format!("{}/.owner_api_secret", dir));
+		fs::write(&path, "old_secret").unwrap();
+		let path_str = path.to_str().unwrap().to_owned();
+		let auth = RotatingBasicAuthMiddleware::new(
+			"old_secret".to_owned(),
+			Some(path_str),
+			&crate::controller::EPIC_OWNER_BASIC_REALM,
+			vec![],
+		);
+		assert!(auth.is_authorized(basic_auth("old_secret").as_bytes()));
+		assert!(!auth.is_authorized(basic_auth("other").as_bytes()));
+
+		// both secrets are accepted once the file changes
+		thread::sleep(SECRET_FILE_CHECK_INTERVAL);
+		let new_secret = rotate_api_secret(&path).unwrap();
+		assert!(auth.is_authorized(basic_auth(&new_secret).as_bytes()));
+		assert!(auth.is_authorized(basic_auth("old_secret").as_bytes()));
+
+		// until the grace period is over
+		auth.secrets.lock().previous.as_mut().unwrap().1 -= OWNER_API_SECRET_GRACE_PERIOD;
+		assert!(!auth.is_authorized(basic_auth("old_secret").as_bytes()));
+		assert!(auth.is_authorized(basic_auth(&new_secret).as_bytes()));
+
+		let _ = fs::remove_dir_all(dir);
+	}
+}
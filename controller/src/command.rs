@@ -15,28 +15,49 @@
 //! Epic wallet command-line function implementations
 
 use crate::api::TLSConfig;
-use crate::config::{EpicboxConfig, TorConfig, WalletConfig, WALLET_CONFIG_FILE_NAME};
+use crate::apiwallet::Owner;
+use crate::auth::OWNER_API_SECRET_GRACE_PERIOD;
+use crate::config::{
+	rotate_api_secret, BackupConfig, DecoyOutputConfig, EpicboxConfig, GlobalWalletConfig,
+	LanConfig, StatementsConfig, SweepConfig, TorConfig, TxHooksConfig, WalletConfig,
+	EPIC_WALLET_DIR, WALLET_CONFIG_FILE_NAME,
+};
 use crate::core::{core, global};
+use crate::cors::CorsMiddleware;
 use crate::error::{Error, ErrorKind};
+use crate::foreign_access::ForeignAccess;
 
 use crate::impls::{
-	create_sender, EpicboxChannel, EpicboxListenChannel, KeybaseAllChannels, SlateGetter as _,
-	SlateReceiver as _,
+	backup_target_from_config, backup_wallet, balance_alerts_from_config, create_sender,
+	discover_lan_peer, maturity_watch_from_config, migrate_store, price_feed_from_config,
+	receive_hook_from_config, restore_wallet, sender_transport, tx_hook_from_config,
+	EpicboxChannel, EpicboxListenChannel, KeybaseAllChannels, LanAdvertiser, LanSlateSender,
+	SlateGetter as _, SlateReceiver as _, SlateSender, StoreBackend,
 };
 use crate::impls::{EmojiSlate, PathToSlate, SlatePutter};
 use crate::keychain;
+use crate::libwallet::api_impl::owner;
 use crate::libwallet::{
-	self, address, InitTxArgs, IssueInvoiceTxArgs, NodeClient, PaymentProof, WalletInst,
-	WalletLCProvider,
+	self, address, AcctPathMapping, Contact, FeePeriod, ImportedOutput, InitTxArgs,
+	IssueInvoiceTxArgs, MnemonicLanguage, NodeClient, OutputData, PaymentProof, PriceFeed,
+	SeedWord, SendTemplate, SigningRequest, Slate, StatementFiat, Swap, SwapMessage, SwapStartArgs,
+	SweepPolicy, TransportEvent, TxFilter, TxKernelFeatures, TxLogEntry, TxLogEntryType,
+	WalletInst, WalletLCProvider,
 };
 
 use crate::util::secp::key::SecretKey;
+use crate::util::secp::pedersen;
 use crate::util::{to_hex, Mutex, ZeroingString};
-use crate::{controller, display};
+use crate::{controller, display, i18n};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use rand::{thread_rng, Rng};
 
+use serde::Serialize;
 use serde_json as json;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 
@@ -70,6 +91,8 @@ pub struct InitArgs {
 	pub password: ZeroingString,
 	pub config: WalletConfig,
 	pub recovery_phrase: Option<ZeroingString>,
+	/// Language of the recovery phrase, detected from the given phrase if `None`
+	pub language: Option<MnemonicLanguage>,
 	pub restore: bool,
 }
 
@@ -83,6 +106,12 @@ where
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	if let Some(d) = args.config.derivation.as_ref() {
+		libwallet::check_derivation_config(d)?;
+	}
+	if let Some(p) = args.config.password_policy.as_ref() {
+		libwallet::check_password_policy(p, &args.password)?;
+	}
 	let mut w_lock = wallet.lock();
 	let p = w_lock.lc_provider()?;
 	p.create_config(
@@ -97,10 +126,18 @@ where
 		None,
 		args.recovery_phrase,
 		args.list_length,
+		args.language,
 		args.password.clone(),
 		false,
 	)?;
 
+	// lay out the new wallet's accounts as configured, before anything is derived
+	if let Some(d) = args.config.derivation.clone() {
+		p.open_wallet(None, args.password.clone(), false, false)?;
+		libwallet::set_derivation_config(&mut **p.wallet_inst()?, None, d)?;
+		p.close_wallet(None)?;
+	}
+
 	let m = p.get_mnemonic(None, args.password)?;
 	show_recovery_phrase(m);
 	Ok(())
@@ -127,6 +164,36 @@ where
 	Ok(())
 }
 
+/// Arguments for verify_seed command
+pub struct VerifySeedArgs {
+	pub password: ZeroingString,
+	/// Positions of the words the user was asked for
+	pub positions: Vec<usize>,
+	/// Words of the recovery phrase given by the user
+	pub words: Vec<SeedWord>,
+}
+
+pub fn verify_seed<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	args: VerifySeedArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut w_lock = wallet.lock();
+	let p = w_lock.lc_provider()?;
+	let m = p.get_mnemonic(None, args.password)?;
+	if !owner::check_seed_words(&m, &args.positions, &args.words) {
+		let msg = "The words entered don't match your recovery phrase".to_owned();
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	p.set_seed_verified(None, true)?;
+	println!("Your recovery phrase backup has been verified.");
+	Ok(())
+}
+
 /// Arguments for listen command
 pub struct ListenArgs {
 	pub method: String,
@@ -135,6 +202,13 @@ pub struct ListenArgs {
 pub fn listen<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
 	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	wallets: HashMap<
+		String,
+		(
+			Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+			Option<SecretKey>,
+		),
+	>,
 	config: &WalletConfig,
 	tor_config: &TorConfig,
 	epicbox_config: &EpicboxConfig,
@@ -146,14 +220,92 @@ where
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	if let Some(s) = config.sweep.as_ref() {
+		if let Some(secs) = s.interval_secs {
+			start_sweeper(
+				wallet.clone(),
+				keychain_mask.clone(),
+				sweep_policy(s),
+				Duration::from_secs(secs),
+			)?;
+		}
+	}
+	if let Some(b) = config.backup.as_ref() {
+		if let Some(secs) = b.interval_secs {
+			match g_args.password.clone() {
+				Some(password) => start_backups(
+					wallet.clone(),
+					config,
+					b,
+					password,
+					Duration::from_secs(secs),
+				)?,
+				None => warn!("No wallet password to encrypt backups with, not backing up"),
+			}
+		}
+	}
+	if let Some(hours) = config.pending_receive_expiry_hours {
+		start_receive_expirer(wallet.clone(), keychain_mask.clone(), hours)?;
+	}
+	if let Some(s) = config.statements.as_ref() {
+		start_statements(wallet.clone(), keychain_mask.clone(), config, s)?;
+	}
+	let api = Owner::new(wallet.clone(), None);
+	if config.auto_initial_scan.unwrap_or(true) {
+		let mask = keychain_mask.lock().clone();
+		if api.start_initial_scan(mask.as_ref())? {
+			warn!("Scanning the restored wallet for its outputs in the background");
+		}
+	}
+	start_updater_watches(&api, keychain_mask.clone(), config)?;
+
+	let receive_hook = config.receive_hook.as_ref().map(receive_hook_from_config);
+	let tx_hook = config.tx_hooks.as_ref().map(tx_hook_from_config);
 	let res = match args.method.as_str() {
+		"http" if !wallets.is_empty() => controller::multi_foreign_listener(
+			wallet.clone(),
+			keychain_mask,
+			foreign_wallet_routes(config, wallets, receive_hook.clone())?,
+			&config.api_listen_addr(),
+			g_args.tls_conf.clone(),
+			tor_config.use_tor_listener,
+			receive_hook,
+			request_limits(config),
+			config.sign_foreign_responses.unwrap_or(false),
+			CorsMiddleware::from_config(config),
+		),
 		"http" => controller::foreign_listener(
 			wallet.clone(),
 			keychain_mask,
 			&config.api_listen_addr(),
 			g_args.tls_conf.clone(),
 			tor_config.use_tor_listener,
+			receive_hook,
+			request_limits(config),
+			config.sign_foreign_responses.unwrap_or(false),
+			false,
+			CorsMiddleware::from_config(config),
 		),
+		// slates are exchanged over plain HTTP on the local network, both sides
+		// authenticating the other with the key of its address instead
+		"lan" => {
+			let mask = keychain_mask.lock().clone();
+			let address = api.get_public_proof_address(mask.as_ref(), 0)?;
+			let name = lan_name(config.lan.as_ref());
+			let _advertiser = LanAdvertiser::start(&name, &config.api_listen_addr(), &address)?;
+			controller::foreign_listener(
+				wallet.clone(),
+				keychain_mask,
+				&config.api_listen_addr(),
+				None,
+				false,
+				receive_hook,
+				request_limits(config),
+				true,
+				true,
+				None,
+			)
+		}
 		"keybase" => {
 			KeybaseAllChannels::new()?.listen(wallet.clone(), keychain_mask.clone(), config.clone())
 		}
@@ -164,6 +316,8 @@ where
 					wallet.clone(),
 					keychain_mask.clone(),
 					epicbox_config.clone(),
+					receive_hook.clone(),
+					tx_hook.clone(),
 					&mut reconnections,
 				);
 				warn!("try to reconnect to epicbox");
@@ -198,13 +352,93 @@ where
 	Ok(())
 }
 
+/// Routes of the further wallets served by the foreign API listener, opened by the
+/// caller, with the hostnames and TLS certificates they're configured with
+fn foreign_wallet_routes<L, C, K>(
+	config: &WalletConfig,
+	wallets: HashMap<
+		String,
+		(
+			Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+			Option<SecretKey>,
+		),
+	>,
+	receive_hook: Option<Arc<dyn libwallet::ReceiveHook>>,
+) -> Result<Vec<controller::ForeignWalletRoute<L, C, K>>, Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut routes = vec![];
+	for (name, (wallet, mask)) in wallets {
+		let wallet_config = config
+			.foreign_api_wallets
+			.as_ref()
+			.and_then(|w| w.get(&name))
+			.ok_or_else(|| ErrorKind::ArgumentError(format!("Unknown wallet: {}", name)))?;
+		let tls_config = match (
+			wallet_config.tls_certificate_file.clone(),
+			wallet_config.tls_certificate_key.clone(),
+		) {
+			(Some(file), Some(key)) => Some(TLSConfig::new(file, key)),
+			(None, None) => None,
+			_ => {
+				let msg = format!("Wallet {} needs both a TLS certificate and its key", name);
+				return Err(ErrorKind::ArgumentError(msg).into());
+			}
+		};
+		routes.push(controller::ForeignWalletRoute {
+			name,
+			hostnames: wallet_config.hostnames.clone().unwrap_or_default(),
+			tls_config,
+			wallet,
+			keychain_mask: Arc::new(Mutex::new(mask)),
+			receive_hook: receive_hook.clone(),
+		});
+	}
+	Ok(routes)
+}
+
+/// Name the wallet is advertised under on the local network: the configured one,
+/// otherwise the host name
+fn lan_name(config: Option<&LanConfig>) -> String {
+	config
+		.and_then(|c| c.name.clone())
+		.or_else(|| std::env::var("HOSTNAME").ok())
+		.or_else(|| std::env::var("COMPUTERNAME").ok())
+		.unwrap_or_else(|| "epic-wallet".to_owned())
+}
+
+/// Default number of seconds a send looks for a wallet on the local network
+const LAN_DISCOVERY_TIMEOUT_SECS: u64 = 5;
+
+/// Limits on the requests served by the listeners, from their configuration
+fn request_limits(config: &WalletConfig) -> controller::RequestLimits {
+	controller::RequestLimits {
+		max_body_bytes: config
+			.api_max_request_bytes
+			.unwrap_or(controller::DEFAULT_MAX_REQUEST_BYTES),
+		max_batch_len: config
+			.api_max_batch_len
+			.unwrap_or(controller::DEFAULT_MAX_BATCH_LEN),
+		max_results: config.api_max_results,
+		foreign_access: config
+			.foreign_api_access
+			.as_ref()
+			.map(|a| Arc::new(ForeignAccess::new(a))),
+	}
+}
+
 pub fn owner_api<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
 	keychain_mask: Option<SecretKey>,
+	wallets: HashMap<String, Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>>,
 	config: &WalletConfig,
 	tor_config: &TorConfig,
 	epicbox_config: &EpicboxConfig,
 	g_args: &GlobalArgs,
+	global_config: Option<GlobalWalletConfig>,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + Send + Sync + 'static,
@@ -217,12 +451,59 @@ where
 	let res = controller::owner_listener(
 		wallet,
 		km,
+		wallets,
 		config.owner_api_listen_addr().as_str(),
 		g_args.api_secret.clone(),
+		config.api_secret_path.clone(),
 		g_args.tls_conf.clone(),
+		config.owner_api_client_ca_file.clone(),
 		config.owner_api_include_foreign.clone(),
 		Some(tor_config.clone()),
 		Some(epicbox_config.clone()),
+		config.send_timeout_secs.map(Duration::from_secs),
+		config.receive_hook.as_ref().map(receive_hook_from_config),
+		config.send_queue_workers,
+		config
+			.balance_alerts
+			.as_ref()
+			.map(balance_alerts_from_config),
+		config
+			.maturity_notifications
+			.as_ref()
+			.map(maturity_watch_from_config),
+		config.tx_hooks.as_ref().map(tx_hook_from_config),
+		config.password_policy.clone(),
+		config.open_throttle.clone(),
+		config.require_send_approval.unwrap_or(false),
+		request_limits(config),
+		config.sign_foreign_responses.unwrap_or(false),
+		CorsMiddleware::from_config(config),
+		global_config,
+	);
+	if let Err(e) = res {
+		return Err(ErrorKind::LibWallet(e.kind(), e.cause_string()).into());
+	}
+	Ok(())
+}
+
+/// Serve the owner API queries on the snapshot open in the wallet, at the owner
+/// API's address
+pub fn view_owner_api<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	config: &WalletConfig,
+	g_args: &GlobalArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + Send + Sync + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let res = controller::snapshot_listener(
+		wallet,
+		config.owner_api_listen_addr().as_str(),
+		g_args.api_secret.clone(),
+		g_args.tls_conf.clone(),
+		request_limits(config),
 	);
 	if let Err(e) = res {
 		return Err(ErrorKind::LibWallet(e.kind(), e.cause_string()).into());
@@ -230,9 +511,81 @@ where
 	Ok(())
 }
 
+/// Arguments for the config show command
+pub struct ConfigShowArgs {
+	/// Whether the merged value of every setting is displayed, instead of the
+	/// wallet's configuration file
+	pub effective: bool,
+}
+
+/// Display the wallet's configuration file, or the effective configuration
+/// merged from the defaults, the system-wide and the wallet's files
+pub fn config_show(config: &GlobalWalletConfig, args: ConfigShowArgs) -> Result<(), Error> {
+	let wallet_file = config.config_file_path.as_ref().filter(|p| p.exists());
+	if args.effective {
+		let values = config
+			.effective_values()
+			.map_err(|e| ErrorKind::GenericError(format!("{}", e)))?;
+		display::config_values(
+			wallet_file.map(|p| p.as_path()),
+			config.system_config_path.as_ref().map(|p| p.as_path()),
+			&values,
+		);
+		return Ok(());
+	}
+	let path = match wallet_file {
+		Some(p) => p,
+		None => {
+			println!("No wallet configuration file, the defaults are used.");
+			return Ok(());
+		}
+	};
+	let contents = fs::read_to_string(path).map_err(|e| {
+		ErrorKind::GenericError(format!("Unable to read {}: {}", path.display(), e))
+	})?;
+	println!("{}:", path.display());
+	println!();
+	println!("{}", contents);
+	if let Some(p) = config.system_config_path.as_ref() {
+		println!(
+			"Settings not in this file are read from {}, use --effective to display them.",
+			p.display()
+		);
+	}
+	Ok(())
+}
+
+/// Replace the owner API secret with a new one and print it. A running owner API
+/// listener picks up the new secret, still accepting the previous one for a while.
+pub fn owner_api_rotate_secret(config: &WalletConfig) -> Result<(), Error> {
+	let path = match config.api_secret_path.as_ref() {
+		Some(p) => PathBuf::from(p),
+		None => {
+			let msg = "No owner API secret is configured (api_secret_path)".to_owned();
+			return Err(ErrorKind::ArgumentError(msg).into());
+		}
+	};
+	let api_secret = rotate_api_secret(&path).map_err(|e| {
+		ErrorKind::GenericError(format!("Unable to replace the owner API secret: {}", e))
+	})?;
+	println!("The new owner API secret is:");
+	println!();
+	println!("{}", api_secret);
+	println!();
+	println!(
+		"It is only shown once. A running owner API listener keeps accepting the previous \
+		 secret for {} minutes.",
+		OWNER_API_SECRET_GRACE_PERIOD.as_secs() / 60
+	);
+	Ok(())
+}
+
 /// Arguments for account command
 pub struct AccountArgs {
 	pub create: Option<String>,
+	pub metadata_label: Option<String>,
+	pub set_metadata: Option<(String, String)>,
+	pub unset_metadata: Option<String>,
 }
 
 pub fn account<L, C, K>(
@@ -245,7 +598,24 @@ where
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
-	if args.create.is_none() {
+	if let Some(label) = args.metadata_label {
+		let res = controller::owner_single_use(wallet, keychain_mask, |api, m| {
+			if let Some((key, value)) = args.set_metadata.as_ref() {
+				api.set_account_metadata(m, &label, key, Some(value.as_str()))?;
+			}
+			if let Some(key) = args.unset_metadata.as_ref() {
+				api.set_account_metadata(m, &label, key, None)?;
+			}
+			thread::sleep(Duration::from_millis(200));
+			info!("Account: '{}' metadata updated", label);
+			Ok(())
+		});
+		if let Err(e) = res {
+			thread::sleep(Duration::from_millis(200));
+			error!("Error updating metadata of account '{}': {}", label, e);
+			return Err(ErrorKind::LibWallet(e.kind(), e.cause_string()).into());
+		}
+	} else if args.create.is_none() {
 		let res = controller::owner_single_use(wallet, keychain_mask, |api, m| {
 			let acct_mappings = api.accounts(m)?;
 			// give logging thread a moment to catch up
@@ -274,9 +644,31 @@ where
 	Ok(())
 }
 
+/// A participant message template from the configuration, along with the details
+/// it's filled in with that are known before the transaction
+#[derive(Clone)]
+pub struct MessageTemplate {
+	pub template: String,
+	pub account: String,
+	pub invoice_ref: Option<String>,
+}
+
+impl MessageTemplate {
+	/// The message for a transaction of the given amount
+	pub fn render(&self, amount: u64) -> String {
+		let amount = format!("{} {}", i18n::amount(amount, false), i18n::unit().symbol());
+		let invoice_ref = self.invoice_ref.as_ref().map(|r| r.as_str()).unwrap_or("");
+		self.template
+			.replace("{amount}", &amount)
+			.replace("{account}", &self.account)
+			.replace("{invoice_ref}", invoice_ref)
+	}
+}
+
 /// Arguments for the send command
 pub struct SendArgs {
 	pub amount: u64,
+	pub src_acct_name: Option<String>,
 	pub message: Option<String>,
 	pub minimum_confirmations: u64,
 	pub selection_strategy: String,
@@ -288,7 +680,20 @@ pub struct SendArgs {
 	pub max_outputs: usize,
 	pub target_slate_version: Option<u16>,
 	pub payment_proof_address: Option<String>,
+	pub response_address: Option<String>,
 	pub ttl_blocks: Option<u64>,
+	pub max_tx_weight: Option<u64>,
+	pub auto_split: bool,
+	pub inputs: Option<Vec<pedersen::Commitment>>,
+	pub decoy_output: Option<DecoyOutputConfig>,
+	pub avoid_mixed_coinbase: bool,
+	pub consolidation_fee_threshold: Option<u64>,
+	pub lock_height: Option<u64>,
+	pub tx_hooks: Option<TxHooksConfig>,
+	pub lan: Option<LanConfig>,
+	pub ignore_node_height: bool,
+	/// Time the recipient of a send over http or Tor has to respond, if limited
+	pub send_timeout: Option<Duration>,
 }
 
 pub fn send<L, C, K>(
@@ -310,27 +715,57 @@ where
 				.into_iter()
 				.map(|strategy| {
 					let init_args = InitTxArgs {
-						src_acct_name: None,
+						src_acct_name: args.src_acct_name.clone(),
 						amount: args.amount,
 						minimum_confirmations: args.minimum_confirmations,
 						max_outputs: args.max_outputs as u32,
 						num_change_outputs: args.change_outputs as u32,
 						selection_strategy_is_use_all: strategy == "all",
-						estimate_only: Some(true),
+						max_tx_weight: args.max_tx_weight,
+						inputs: args.inputs.clone(),
+						avoid_mixed_coinbase: Some(args.avoid_mixed_coinbase),
+						consolidation_fee_threshold: args.consolidation_fee_threshold,
+						kernel_features: args.lock_height.map(TxKernelFeatures::from_lock_height),
 						..Default::default()
 					};
-					let slate = api.init_send_tx(m, init_args).unwrap();
-					(strategy, slate.amount, slate.fee)
+					Ok((strategy, api.estimate_send_tx(m, &init_args)?))
 				})
-				.collect();
+				.collect::<Result<Vec<_>, libwallet::Error>>()?;
 			display::estimate(args.amount, strategies, dark_scheme);
 		} else {
-			let payment_proof_recipient_address = match args.payment_proof_address {
-				Some(ref p) => Some(address::ed25519_parse_pubkey(p)?),
-				None => None,
+			let mut routes = send_routes(api, m, &args)?;
+			// a wallet on the local network is looked up by the name it advertises, and
+			// is paid to and must sign its response with the address it advertises
+			let lan_peer = match args.method.as_str() {
+				"lan" => {
+					let timeout = args
+						.lan
+						.as_ref()
+						.and_then(|l| l.discovery_timeout_secs)
+						.unwrap_or(LAN_DISCOVERY_TIMEOUT_SECS);
+					let peer = discover_lan_peer(&args.dest, Duration::from_secs(timeout))?;
+					routes.routes = vec![("lan".to_owned(), peer.url.clone())];
+					Some(peer)
+				}
+				_ => None,
+			};
+			// a contact given as the destination stands for their proof address
+			let parse_address = |a: &Option<String>| match (a, routes.contact.as_ref()) {
+				(Some(p), Some(c)) if p == &args.dest => {
+					address::pubkey_from_onion_v3(&c.proof_address).map(Some)
+				}
+				(Some(p), _) => address::ed25519_parse_pubkey(p).map(Some),
+				(None, _) => Ok(None),
+			};
+			let (payment_proof_recipient_address, response_address) = match lan_peer {
+				Some(p) => (Some(p.address), Some(p.address)),
+				None => (
+					parse_address(&args.payment_proof_address)?,
+					parse_address(&args.response_address)?,
+				),
 			};
 			let init_args = InitTxArgs {
-				src_acct_name: None,
+				src_acct_name: args.src_acct_name.clone(),
 				amount: args.amount,
 				minimum_confirmations: args.minimum_confirmations,
 				max_outputs: args.max_outputs as u32,
@@ -339,97 +774,303 @@ where
 				message: args.message.clone(),
 				target_slate_version: args.target_slate_version,
 				payment_proof_recipient_address,
+				response_address,
 				ttl_blocks: args.ttl_blocks,
+				max_tx_weight: args.max_tx_weight,
+				decoy_output_amount: args.decoy_output.as_ref().and_then(decoy_output_amount),
+				inputs: args.inputs.clone(),
+				avoid_mixed_coinbase: Some(args.avoid_mixed_coinbase),
+				consolidation_fee_threshold: args.consolidation_fee_threshold,
+				kernel_features: args.lock_height.map(TxKernelFeatures::from_lock_height),
 				send_args: None,
 				..Default::default()
 			};
-			let result = api.init_send_tx(m, init_args);
-			let mut slate = match result {
-				Ok(s) => {
-					info!(
-						"Tx created: {} epic to {} (strategy '{}')",
-						core::amount_to_hr_string(args.amount, false),
-						args.dest,
-						args.selection_strategy,
-					);
-					s
-				}
-				Err(e) => {
-					info!("Tx not created: {}", e);
-					return Err(e);
-				}
+			// each transaction of a split send spends the inputs it was planned with
+			let parts = match args.auto_split {
+				true => owner::split_send_tx(wallet.clone(), m, &init_args)?,
+				false => vec![init_args],
 			};
+			if parts.len() == 1 {
+				send_tx(
+					api,
+					wallet,
+					m,
+					tor_config,
+					epicbox_config,
+					&args,
+					&routes,
+					parts[0].clone(),
+				)?;
+				return Ok(());
+			}
 
-			match args.method.as_str() {
-				"emoji" => {
-					println!("{}", EmojiSlate().encode(&slate));
-					api.tx_lock_outputs(m, &slate, 0)?;
-					return Ok(());
-				}
-				"file" => {
-					PathToSlate((&args.dest).into()).put_tx(&slate)?;
-					api.tx_lock_outputs(m, &slate, 0)?;
-					return Ok(());
+			// only methods completing the exchange right away can be split, the others
+			// hand a single slate over to the recipient
+			for (method, _) in routes.routes.iter() {
+				match method.as_str() {
+					"emoji" | "file" | "epicbox" => {
+						return Err(libwallet::ErrorKind::GenericError(format!(
+							"Sending this amount needs {} transactions, which is not supported with method '{}'",
+							parts.len(),
+							method
+						)))?;
+					}
+					_ => {}
 				}
-				"self" => {
-					api.tx_lock_outputs(m, &slate, 0)?;
-					let km = match keychain_mask.as_ref() {
-						None => None,
-						Some(&m) => Some(m.to_owned()),
-					};
-					controller::foreign_single_use(wallet, km, |api| {
-						slate = api.receive_tx(&slate, Some(&args.dest), None)?;
-						Ok(())
-					})?;
+			}
+			info!(
+				"Splitting {} {} to {} into {} transactions",
+				i18n::amount(args.amount, false),
+				i18n::unit().symbol(),
+				args.dest,
+				parts.len(),
+			);
+			let mut slates = vec![];
+			for init_args in parts.iter() {
+				let res = send_tx(
+					api,
+					wallet.clone(),
+					m,
+					tor_config.clone(),
+					epicbox_config.clone(),
+					&args,
+					&routes,
+					init_args.clone(),
+				);
+				match res {
+					Ok(s) => slates.push(s),
+					Err(e) => {
+						display::split_send(args.amount, parts.len(), &slates, dark_scheme);
+						return Err(e);
+					}
 				}
-				"epicbox" => {
-					let epicbox_channel = Box::new(EpicboxChannel::new(&args.dest, epicbox_config))
-						.expect("error starting epicbox");
+			}
+			display::split_send(args.amount, parts.len(), &slates, dark_scheme);
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
 
-					let km = match keychain_mask.as_ref() {
-						None => None,
-						Some(&m) => Some(m.to_owned()),
-					};
-					slate = epicbox_channel.send(wallet, km, &slate)?;
+/// Methods to try in turn to send a slate, each with its destination
+struct SendRoutes {
+	/// Address book contact the slate is sent to, if the destination names one
+	contact: Option<Contact>,
+	routes: Vec<(String, String)>,
+}
 
-					api.tx_lock_outputs(m, &slate, 0)?;
+/// Methods to send with: the transports of the contact if the destination is an
+/// address book contact given as `@name`, otherwise the requested method
+fn send_routes<L, C, K>(
+	api: &mut Owner<L, C, K>,
+	keychain_mask: Option<&SecretKey>,
+	args: &SendArgs,
+) -> Result<SendRoutes, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	if !args.dest.starts_with('@') {
+		return Ok(SendRoutes {
+			contact: None,
+			routes: vec![(args.method.clone(), args.dest.clone())],
+		});
+	}
+	let name = &args.dest[1..];
+	let contact = api
+		.retrieve_contacts(keychain_mask)?
+		.into_iter()
+		.find(|c| c.name == name)
+		.ok_or_else(|| libwallet::ErrorKind::UnknownContact(name.to_owned()))?;
+	Ok(SendRoutes {
+		routes: contact.transports(),
+		contact: Some(contact),
+	})
+}
 
-					return Ok(());
-				}
-				method => {
-					let sender = create_sender(method, &args.dest, tor_config)?;
+/// Send the slate over a single transport, returning the recipient's response
+fn send_slate<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	tor_config: Option<TorConfig>,
+	epicbox_config: Option<EpicboxConfig>,
+	send_timeout: Option<Duration>,
+	method: &str,
+	dest: &str,
+	slate: &Slate,
+) -> Result<Slate, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	if method == "epicbox" {
+		let epicbox_channel = EpicboxChannel::new(&dest.to_owned(), epicbox_config)?;
+		let km = keychain_mask.map(|m| m.to_owned());
+		return Ok(epicbox_channel.send(wallet, km, slate)?);
+	}
+	let sender: Box<dyn SlateSender> = match method {
+		"lan" => Box::new(LanSlateSender::new(dest, wallet.clone(), keychain_mask)?),
+		_ => create_sender(method, dest, tor_config, send_timeout)?,
+	};
+	let transport = sender_transport(method, dest);
 
-					slate = sender.send_tx(&slate)?;
-					api.tx_lock_outputs(m, &slate, 0)?;
-				}
-			}
+	let res = sender.send_tx(slate);
+	let event = match res {
+		Ok(_) => TransportEvent::slate_sent(slate),
+		Err(_) => TransportEvent::Failure,
+	};
+	if let Err(e) = owner::record_transport_event(wallet, keychain_mask, &transport, event) {
+		warn!("Unable to record transport metrics: {}", e);
+	}
+	Ok(res?)
+}
 
-			api.verify_slate_messages(m, &slate).map_err(|e| {
-				error!("Error validating participant messages: {}", e);
-				e
+/// Create a single transaction and send it with the requested method, finalizing
+/// and posting it if the method completes the exchange right away. Sending to a
+/// contact falls back to their next transport when one fails.
+fn send_tx<L, C, K>(
+	api: &mut Owner<L, C, K>,
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	tor_config: Option<TorConfig>,
+	epicbox_config: Option<EpicboxConfig>,
+	args: &SendArgs,
+	routes: &SendRoutes,
+	init_args: InitTxArgs,
+) -> Result<Slate, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let m = keychain_mask;
+	let amount = init_args.amount;
+	api.set_node_height_guard(!args.ignore_node_height);
+	api.set_tx_hook(args.tx_hooks.as_ref().map(tx_hook_from_config));
+	let result = api.init_send_tx(m, init_args);
+	let mut slate = match result {
+		Ok(s) => {
+			info!(
+				"Tx created: {} {} to {} (strategy '{}')",
+				i18n::amount(amount, false),
+				i18n::unit().symbol(),
+				args.dest,
+				args.selection_strategy,
+			);
+			s
+		}
+		Err(e) => {
+			info!("Tx not created: {}", e);
+			return Err(e);
+		}
+	};
+
+	match args.method.as_str() {
+		"emoji" => {
+			println!("{}", EmojiSlate().encode(&slate));
+			api.tx_lock_outputs(m, &slate, 0)?;
+			return Ok(slate);
+		}
+		"file" => {
+			PathToSlate((&args.dest).into()).put_tx(&slate)?;
+			api.tx_lock_outputs(m, &slate, 0)?;
+			return Ok(slate);
+		}
+		"self" => {
+			api.tx_lock_outputs(m, &slate, 0)?;
+			let km = match keychain_mask.as_ref() {
+				None => None,
+				Some(&m) => Some(m.to_owned()),
+			};
+			controller::foreign_single_use(wallet, km, |api| {
+				slate = api.receive_tx(&slate, Some(&args.dest), None)?;
+				Ok(())
 			})?;
-			slate = api.finalize_tx(m, &slate)?;
-			let result = api.post_tx(m, &slate.tx, args.fluff);
-			match result {
-				Ok(_) => {
-					info!("Tx sent ok",);
-					return Ok(());
+		}
+		_ => {
+			let mut sent = None;
+			let mut last_err = None;
+			for (method, dest) in routes.routes.iter() {
+				let res = send_slate(
+					wallet.clone(),
+					m,
+					tor_config.clone(),
+					epicbox_config.clone(),
+					args.send_timeout,
+					method,
+					dest,
+					&slate,
+				);
+				match res {
+					Ok(s) => {
+						sent = Some((s, sender_transport(method, dest)));
+						break;
+					}
+					Err(e) => {
+						warn!("Sending with {} to {} failed: {}", method, dest, e);
+						last_err = Some(e);
+					}
 				}
-				Err(e) => {
-					error!("Tx sent fail: {}", e);
+			}
+			let (response, transport) = match (sent, last_err) {
+				(Some(s), _) => s,
+				(None, Some(e)) => {
+					// a send timing out is cancelled, to be made again
+					if let libwallet::ErrorKind::SendTimeout(reason) = e.kind() {
+						owner::fail_send_tx(wallet, m, &slate, &reason)?;
+						info!("Tx {} cancelled: {}", slate.id, reason);
+					}
 					return Err(e);
 				}
+				(None, None) => {
+					return Err(libwallet::ErrorKind::GenericError(format!(
+						"No known address to send to {}",
+						args.dest
+					)))?;
+				}
+			};
+			slate = response;
+			api.tx_lock_outputs(m, &slate, 0)?;
+
+			let contact = routes.contact.as_ref().map(|c| c.name.as_str());
+			if contact.is_some() {
+				info!("Tx sent to {} with {}", args.dest, transport);
+			}
+			if let Err(e) = api.record_tx_transport(m, slate.id, &transport, contact) {
+				warn!("Unable to record the transport of the transaction: {}", e);
+			}
+			// the response comes back through the epicbox listener
+			if transport == "epicbox" {
+				return Ok(slate);
 			}
 		}
-		Ok(())
+	}
+
+	api.verify_slate_messages(m, &slate).map_err(|e| {
+		error!("Error validating participant messages: {}", e);
+		e
 	})?;
-	Ok(())
+	slate = api.finalize_tx(m, &slate)?;
+	let result = api.post_tx(m, &slate.tx, args.fluff);
+	match result {
+		Ok(_) => {
+			info!("Tx sent ok",);
+			Ok(slate)
+		}
+		Err(e) => {
+			error!("Tx sent fail: {}", e);
+			Err(e)
+		}
+	}
 }
 
 /// Receive command argument
 pub struct ReceiveArgs {
 	pub input: String,
 	pub message: Option<String>,
+	pub message_template: Option<MessageTemplate>,
 	pub method: String,
 }
 
@@ -461,7 +1102,12 @@ where
 			error!("Error validating participant messages: {}", e);
 			return Err(e);
 		}
-		slate = api.receive_tx(&slate, Some(&g_args.account), args.message.clone())?;
+		let message = args.message.clone().or_else(|| {
+			args.message_template
+				.as_ref()
+				.map(|t| t.render(slate.amount))
+		});
+		slate = api.receive_tx(&slate, Some(&g_args.account), message)?;
 		Ok(())
 	})?;
 	if method == "emoji" {
@@ -485,6 +1131,8 @@ pub struct FinalizeArgs {
 	pub fluff: bool,
 	pub nopost: bool,
 	pub dest: Option<String>,
+	pub tx_hooks: Option<TxHooksConfig>,
+	pub ignore_node_height: bool,
 }
 
 pub fn finalize<L, C, K>(
@@ -542,6 +1190,7 @@ where
 				error!("Error validating participant messages: {}", e);
 				return Err(e);
 			}
+			api.set_tx_hook(args.tx_hooks.as_ref().map(tx_hook_from_config));
 			slate = api.finalize_tx(m, &mut slate)?;
 			Ok(())
 		})?;
@@ -549,6 +1198,7 @@ where
 
 	if !args.nopost {
 		controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+			api.set_node_height_guard(!args.ignore_node_height);
 			let result = api.post_tx(m, &slate.tx, args.fluff);
 			match result {
 				Ok(_) => {
@@ -572,7 +1222,323 @@ where
 	Ok(())
 }
 
-/// Issue Invoice Args
+/// Sign command args
+pub struct SignArgs {
+	pub input: String,
+	pub dest: String,
+}
+
+/// Sign the send of a watch-only wallet's signing request file offline, locking its
+/// outputs and saving the slate for the recipient
+pub fn sign<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: SignArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let contents = fs::read_to_string(&args.input).map_err(|e| {
+		libwallet::ErrorKind::GenericError(format!("Unable to read {}: {}", args.input, e))
+	})?;
+	let request: SigningRequest = json::from_str(&contents).map_err(|e| {
+		libwallet::ErrorKind::SigningRequest(format!("Unable to parse {}: {}", args.input, e))
+	})?;
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let slate = api.sign_tx_offline(m, &request)?;
+		PathToSlate((&args.dest).into()).put_tx(&slate)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		println!(
+			"Signed the send of {} with a fee of {}, spending {} outputs",
+			core::amount_to_hr_string(slate.amount, false),
+			core::amount_to_hr_string(slate.fee, false),
+			request.inputs.len()
+		);
+		info!(
+			"Transaction file {} generated, and can be sent to the recipient.",
+			args.dest
+		);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Swap start command args
+pub struct SwapOfferArgs {
+	pub start_args: SwapStartArgs,
+	pub dest: Option<String>,
+}
+
+/// Swap process command args
+pub struct SwapProcessArgs {
+	pub input: Option<String>,
+	pub id: Option<Uuid>,
+	pub dest: Option<String>,
+}
+
+/// Swap cancel command args
+pub struct SwapCancelArgs {
+	pub id: Uuid,
+}
+
+/// Write a swap message for the other party to a file, <id>.swap by default
+fn write_swap_message(
+	message: &SwapMessage,
+	dest: &Option<String>,
+) -> Result<(), libwallet::Error> {
+	let dest = match dest {
+		Some(d) => d.to_owned(),
+		None => format!("{}.swap", message.id),
+	};
+	let contents = json::to_string_pretty(message).map_err(|e| {
+		libwallet::ErrorKind::GenericError(format!("Unable to serialize swap message: {}", e))
+	})?;
+	fs::write(&dest, contents).map_err(|e| {
+		libwallet::ErrorKind::GenericError(format!("Unable to write {}: {}", dest, e))
+	})?;
+	info!(
+		"Swap message file {} generated, and can be sent to the other party.",
+		dest
+	);
+	Ok(())
+}
+
+/// Print where a swap stands, and the keys of the secondary currency once known
+fn display_swap(swap: &Swap) {
+	println!(
+		"Swap {} of {} for {} {}: {}",
+		swap.id,
+		core::amount_to_hr_string(swap.amount, false),
+		swap.secondary_amount,
+		swap.secondary_currency,
+		swap.status
+	);
+	if let Some(ref script) = swap.secondary_lock_script {
+		println!("{} lock script: {}", swap.secondary_currency, script);
+	}
+	if let Some(ref key) = swap.secondary_key {
+		println!(
+			"{} key: {}",
+			swap.secondary_currency,
+			to_hex(key.0.to_vec())
+		);
+	}
+	if let Some(ref secret) = swap.secondary_secret {
+		println!(
+			"{} secret: {}",
+			swap.secondary_currency,
+			to_hex(secret.0.to_vec())
+		);
+	}
+}
+
+/// Offer a swap of epics as the seller, writing the offer for the buyer
+pub fn swap_start<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: SwapOfferArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let step = api.swap_start(m, args.start_args)?;
+		display_swap(&step.swap);
+		if let Some(ref message) = step.message {
+			write_swap_message(message, &args.dest)?;
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Take this wallet's next step of a swap, writing the message for the other party if
+/// the step sends one
+pub fn swap_process<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: SwapProcessArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let message: Option<SwapMessage> = match args.input {
+		Some(ref input) => {
+			let contents = fs::read_to_string(input).map_err(|e| {
+				libwallet::ErrorKind::GenericError(format!("Unable to read {}: {}", input, e))
+			})?;
+			Some(json::from_str(&contents).map_err(|e| {
+				libwallet::ErrorKind::Swap(format!("Unable to parse {}: {}", input, e))
+			})?)
+		}
+		None => None,
+	};
+	let id = match (args.id, message.as_ref()) {
+		(Some(id), _) => id,
+		(None, Some(m)) => m.id,
+		(None, None) => {
+			return Err(ErrorKind::ArgumentError("No swap id given".to_owned()).into());
+		}
+	};
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let step = api.swap_process(m, id, message)?;
+		display_swap(&step.swap);
+		if let Some(ref message) = step.message {
+			write_swap_message(message, &args.dest)?;
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Cancel a swap, refunding the locked epics once the refund height is reached
+pub fn swap_cancel<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: SwapCancelArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let swap = api.swap_cancel(m, args.id)?;
+		display_swap(&swap);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Watch command args
+pub struct WatchArgs {
+	pub dir: String,
+	pub interval: Duration,
+	pub fluff: bool,
+	pub nopost: bool,
+	pub tx_hooks: Option<TxHooksConfig>,
+	pub ignore_node_height: bool,
+}
+
+/// Folders, within the watched directory, processed response files are moved to
+const WATCH_DONE_DIR: &str = "done";
+const WATCH_ERROR_DIR: &str = "error";
+
+/// Whether the file name is that of a receiver's response, as written by `receive`
+/// (`<file>.response`) or in the `<name>.response.tx` form
+fn is_response_file(path: &Path) -> bool {
+	match path.file_name().and_then(|n| n.to_str()) {
+		Some(n) => n.ends_with(".response") || n.ends_with(".response.tx"),
+		None => false,
+	}
+}
+
+/// Move a processed response file to the given folder of the watched directory
+fn move_response_file(path: &Path, dir: &Path, folder: &str) -> Result<PathBuf, Error> {
+	let folder = dir.join(folder);
+	let dest = folder.join(path.file_name().unwrap_or_default());
+	fs::create_dir_all(&folder)
+		.and_then(|_| fs::rename(path, &dest))
+		.map_err(|e| {
+			ErrorKind::GenericError(format!("Unable to move {}: {}", path.display(), e))
+		})?;
+	Ok(dest)
+}
+
+/// Finalize and post the response file if it answers one of our pending sends.
+/// Returns whether the file was for us.
+fn watch_response_file<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: &WatchArgs,
+	path: &Path,
+) -> Result<bool, Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let slate = PathToSlate(path.into()).get_tx()?;
+	let mut pending = false;
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		pending = txs
+			.iter()
+			.any(|t| t.tx_type == TxLogEntryType::TxSent && !t.confirmed);
+		Ok(())
+	})?;
+	if !pending {
+		return Ok(false);
+	}
+	finalize(
+		wallet,
+		keychain_mask,
+		FinalizeArgs {
+			method: "file".to_owned(),
+			input: path.to_string_lossy().into_owned(),
+			fluff: args.fluff,
+			nopost: args.nopost,
+			dest: None,
+			tx_hooks: args.tx_hooks.clone(),
+			ignore_node_height: args.ignore_node_height,
+		},
+	)?;
+	Ok(true)
+}
+
+/// Watch a directory for the receivers' responses to our file-based sends,
+/// finalizing and posting them as they arrive. Processed files are moved to the
+/// `done` folder of the directory, or to `error` if they couldn't be finalized.
+pub fn watch<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: WatchArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let dir = PathBuf::from(&args.dir);
+	warn!("Watching {} for transaction responses", dir.display());
+	loop {
+		let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+			.map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to read {}: {}", dir.display(), e))
+			})?
+			.filter_map(|e| e.ok().map(|e| e.path()))
+			.filter(|p| p.is_file() && is_response_file(p))
+			.collect();
+		files.sort();
+		for path in files {
+			match watch_response_file(wallet.clone(), keychain_mask, &args, &path) {
+				Ok(false) => debug!("{} isn't for a pending send, ignored", path.display()),
+				Ok(true) => {
+					let dest = move_response_file(&path, &dir, WATCH_DONE_DIR)?;
+					warn!("Finalized {}, moved to {}", path.display(), dest.display());
+				}
+				Err(e) => {
+					let dest = move_response_file(&path, &dir, WATCH_ERROR_DIR)?;
+					error!(
+						"Unable to finalize {}, moved to {}: {}",
+						path.display(),
+						dest.display(),
+						e
+					);
+				}
+			}
+		}
+		thread::sleep(args.interval);
+	}
+}
+
+/// Issue Invoice Args
 pub struct IssueInvoiceArgs {
 	/// output file
 	pub dest: String,
@@ -601,6 +1567,7 @@ where
 /// Arguments for the process_invoice command
 pub struct ProcessInvoiceArgs {
 	pub message: Option<String>,
+	pub message_template: Option<MessageTemplate>,
 	pub minimum_confirmations: u64,
 	pub selection_strategy: String,
 	pub method: String,
@@ -637,13 +1604,11 @@ where
 						max_outputs: args.max_outputs as u32,
 						num_change_outputs: 1u32,
 						selection_strategy_is_use_all: strategy == "all",
-						estimate_only: Some(true),
 						..Default::default()
 					};
-					let slate = api.init_send_tx(m, init_args).unwrap();
-					(strategy, slate.amount, slate.fee)
+					Ok((strategy, api.estimate_send_tx(m, &init_args)?))
 				})
-				.collect();
+				.collect::<Result<Vec<_>, libwallet::Error>>()?;
 			display::estimate(slate.amount, strategies, dark_scheme);
 		} else {
 			let init_args = InitTxArgs {
@@ -653,7 +1618,11 @@ where
 				max_outputs: args.max_outputs as u32,
 				num_change_outputs: 1u32,
 				selection_strategy_is_use_all: args.selection_strategy == "all",
-				message: args.message.clone(),
+				message: args.message.clone().or_else(|| {
+					args.message_template
+						.as_ref()
+						.map(|t| t.render(slate.amount))
+				}),
 				ttl_blocks: args.ttl_blocks,
 				send_args: None,
 				..Default::default()
@@ -666,8 +1635,9 @@ where
 			let mut slate = match result {
 				Ok(s) => {
 					info!(
-						"Invoice processed: {} epic to {} (strategy '{}')",
-						core::amount_to_hr_string(slate.amount, false),
+						"Invoice processed: {} {} to {} (strategy '{}')",
+						i18n::amount(slate.amount, false),
+						i18n::unit().symbol(),
 						args.dest,
 						args.selection_strategy,
 					);
@@ -697,7 +1667,7 @@ where
 					})?;
 				}
 				method => {
-					let sender = create_sender(method, &args.dest, tor_config)?;
+					let sender = create_sender(method, &args.dest, tor_config, None)?;
 					slate = sender.send_tx(&slate)?;
 					api.tx_lock_outputs(m, &slate, 0)?;
 				}
@@ -710,8 +1680,14 @@ where
 /// Info command args
 pub struct InfoArgs {
 	pub minimum_confirmations: u64,
+	/// Whether a restored wallet that hasn't been scanned yet is scanned in the
+	/// background, showing its progress
+	pub auto_initial_scan: bool,
 }
 
+/// How often the progress of the initial scan is shown
+const INITIAL_SCAN_PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
 pub fn info<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
@@ -725,9 +1701,28 @@ where
 	K: keychain::Keychain + 'static,
 {
 	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		if args.auto_initial_scan && api.start_initial_scan(m)? {
+			println!("This wallet was restored and is being scanned for its outputs.");
+			let mut shown = None;
+			while let Some(percent) = api.initial_scan_progress() {
+				if shown != Some(percent) {
+					println!("Initial scan: {}% complete", percent);
+					shown = Some(percent);
+				}
+				thread::sleep(INITIAL_SCAN_PROGRESS_INTERVAL);
+			}
+			println!();
+		}
 		let (validated, wallet_info) =
 			api.retrieve_summary_info(m, true, args.minimum_confirmations)?;
 		display::info(&g_args.account, &wallet_info, validated, dark_scheme);
+		if !api.seed_verified(None)? {
+			println!(
+				"Your recovery phrase backup hasn't been verified yet. \
+				 Run `epic-wallet verify_seed` to confirm it."
+			);
+			println!();
+		}
 		Ok(())
 	})?;
 	Ok(())
@@ -764,6 +1759,10 @@ where
 pub struct TxsArgs {
 	pub id: Option<u32>,
 	pub tx_slate_id: Option<Uuid>,
+	pub tags: Vec<String>,
+	pub contact: Option<String>,
+	pub csv_file: Option<String>,
+	pub detail: bool,
 }
 
 pub fn txs<L, C, K>(
@@ -780,7 +1779,20 @@ where
 {
 	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
 		let res = api.node_height(m)?;
-		let (validated, txs) = api.retrieve_txs(m, true, args.id, args.tx_slate_id)?;
+		let (validated, txs) = match (args.contact.as_ref(), args.tags.is_empty()) {
+			(Some(c), _) => api.retrieve_contact_txs(m, true, c)?,
+			(None, true) => api.retrieve_txs(m, true, args.id, args.tx_slate_id)?,
+			(None, false) => api.retrieve_txs_by_tag(m, true, &args.tags)?,
+		};
+		let txs: Vec<_> = txs
+			.into_iter()
+			.filter(|t| args.id.map_or(true, |id| t.id == id))
+			.filter(|t| {
+				args.tx_slate_id
+					.map_or(true, |id| t.tx_slate_id == Some(id))
+			})
+			.filter(|t| t.has_tags(&args.tags))
+			.collect();
 		let include_status = !args.id.is_some() && !args.tx_slate_id.is_some();
 		display::txs(
 			&g_args.account,
@@ -790,6 +1802,15 @@ where
 			include_status,
 			dark_scheme,
 		)?;
+		if args.detail {
+			display::tx_sizes(&txs);
+			display::input_compositions(&txs);
+		}
+
+		if let Some(f) = args.csv_file.as_ref() {
+			export_txs_csv(f, &txs)?;
+			warn!("Exported {} transactions to {}", txs.len(), f);
+		}
 
 		// if given a particular transaction id or uuid, also get and display associated
 		// inputs/outputs and messages
@@ -821,92 +1842,452 @@ where
 	Ok(())
 }
 
-/// Post
-pub struct PostArgs {
-	pub input: String,
-	pub fluff: bool,
+fn csv_field(field: &str) -> String {
+	if field.contains(|c| c == ',' || c == '"' || c == '\n') {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_owned()
+	}
 }
 
-pub fn post<L, C, K>(
+/// Write transactions to a CSV file. Amounts are written in EPIC with a '.'
+/// decimal point regardless of the display settings, so the file can be
+/// imported into bookkeeping tools.
+fn export_txs_csv(file: &str, txs: &[TxLogEntry]) -> Result<(), libwallet::Error> {
+	let mut out = String::from(
+		"id,type,tx_slate_id,creation_ts,confirmed,confirmation_ts,amount_credited,amount_debited,fee,tags,contact\n",
+	);
+	for t in txs {
+		let fields = vec![
+			t.id.to_string(),
+			t.tx_type.to_string(),
+			t.tx_slate_id.map(|u| u.to_string()).unwrap_or_default(),
+			t.creation_ts.to_rfc3339(),
+			t.confirmed.to_string(),
+			t.confirmation_ts
+				.map(|c| c.to_rfc3339())
+				.unwrap_or_default(),
+			core::amount_to_hr_string(t.amount_credited, false),
+			core::amount_to_hr_string(t.amount_debited, false),
+			t.fee
+				.map(|f| core::amount_to_hr_string(f, false))
+				.unwrap_or_default(),
+			t.tags.join(";"),
+			t.contact.clone().unwrap_or_default(),
+		];
+		let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+		out.push_str(&fields.join(","));
+		out.push('\n');
+	}
+	let mut csv_file = File::create(file)?;
+	csv_file.write_all(out.as_bytes())?;
+	csv_file.sync_all()?;
+	Ok(())
+}
+
+/// Arguments for the migrate command
+pub struct MigrateArgs {
+	/// Backend the wallet's store is converted to
+	pub to: StoreBackend,
+}
+
+/// Convert the store of the closed wallet in `data_dir` to another backend
+pub fn migrate(data_dir: &str, args: MigrateArgs) -> Result<(), Error> {
+	let m = migrate_store(data_dir, args.to)?;
+	println!(
+		"Converted the wallet store from {} to {}: {} records, of which {} outputs, with matching balances",
+		m.from, m.to, m.records, m.outputs
+	);
+	println!("The {} store was kept at {}", m.from, m.backup.display());
+	Ok(())
+}
+
+/// Back up the closed wallet in `data_dir` to the configured backup target
+pub fn backup_now(
+	config: &WalletConfig,
+	data_dir: &str,
+	password: ZeroingString,
+) -> Result<(), Error> {
+	let b = backup_config(config)?;
+	let target = backup_target_from_config(b)?;
+	let name = backup_wallet(
+		&*target,
+		data_dir,
+		&password,
+		b.passphrase.as_ref().map(|p| p.as_str()),
+	)?;
+	println!("Backed the wallet up to {} as {}", target.describe(), name);
+	Ok(())
+}
+
+/// Arguments for the backup restore command
+pub struct BackupRestoreArgs {
+	/// Name of the backup to restore
+	pub name: String,
+}
+
+/// Restore a backup from the configured backup target into `data_dir`, which
+/// mustn't hold a wallet
+pub fn backup_restore(
+	config: &WalletConfig,
+	data_dir: &str,
+	password: ZeroingString,
+	args: BackupRestoreArgs,
+) -> Result<(), Error> {
+	let b = backup_config(config)?;
+	let target = backup_target_from_config(b)?;
+	let files = restore_wallet(
+		&*target,
+		&args.name,
+		data_dir,
+		&password,
+		b.passphrase.as_ref().map(|p| p.as_str()),
+	)?;
+	println!(
+		"Restored {} files of backup {} from {} to {}",
+		files,
+		args.name,
+		target.describe(),
+		data_dir
+	);
+	Ok(())
+}
+
+fn backup_config(config: &WalletConfig) -> Result<&BackupConfig, Error> {
+	config.backup.as_ref().ok_or_else(|| {
+		let msg = "No backup target in the wallet configuration".to_owned();
+		ErrorKind::ArgumentError(msg).into()
+	})
+}
+
+/// Arguments for the db export command
+pub struct DbExportArgs {
+	/// Format of the export, only "jsonl" for now
+	pub format: String,
+	/// File the export is written to
+	pub output: String,
+}
+
+/// Record of a database export, with its type in the "type" field
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DbRecord {
+	Account(AcctPathMapping),
+	Output(OutputData),
+	Tx(TxLogEntry),
+}
+
+/// Export the account mappings, outputs and transaction log of all accounts of
+/// the wallet as one JSON record per line. Private contexts are left out.
+pub fn db_export<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
-	keychain_mask: Option<&SecretKey>,
-	args: PostArgs,
+	args: DbExportArgs,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
-	let slate = PathToSlate((&args.input).into()).get_tx()?;
+	if args.format != "jsonl" {
+		let msg = format!("Unsupported export format: {}", args.format);
+		return Err(ErrorKind::ArgumentError(msg).into());
+	}
+	let mut w_lock = wallet.lock();
+	let w = w_lock.lc_provider()?.wallet_inst()?;
+	let mut records: Vec<DbRecord> = w.acct_path_iter().map(DbRecord::Account).collect();
+	let accounts = records.len();
+	records.extend(w.iter().map(DbRecord::Output));
+	let outputs = records.len() - accounts;
+	records.extend(w.tx_log_iter().map(DbRecord::Tx));
+	let txs = records.len() - accounts - outputs;
+
+	write_jsonl(&args.output, &records)?;
+	println!(
+		"Exported {} accounts, {} outputs and {} transactions to {}",
+		accounts, outputs, txs, args.output
+	);
+	Ok(())
+}
 
-	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
-		api.post_tx(m, &slate.tx, args.fluff)?;
-		info!("Posted transaction");
-		return Ok(());
-	})?;
+fn write_jsonl(file: &str, records: &[DbRecord]) -> Result<(), libwallet::Error> {
+	let mut out = BufWriter::new(File::create(file)?);
+	for r in records {
+		let line = json::to_string(r).map_err(|e| {
+			libwallet::ErrorKind::GenericError(format!("Unable to serialize export record: {}", e))
+		})?;
+		out.write_all(line.as_bytes())?;
+		out.write_all(b"\n")?;
+	}
+	out.flush()?;
 	Ok(())
 }
 
-/// Repost
-pub struct RepostArgs {
-	pub id: u32,
-	pub dump_file: Option<String>,
-	pub fluff: bool,
+/// Arguments for the db import command
+pub struct DbImportArgs {
+	/// File of the outputs to import, one JSON record per line
+	pub input: String,
 }
 
-pub fn repost<L, C, K>(
+/// Import the outputs of a file of JSON records, such as written by `db export`
+/// or exported by another wallet, restoring those unspent on chain
+pub fn db_import<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
-	args: RepostArgs,
+	args: DbImportArgs,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
 	C: NodeClient + 'static,
 	K: keychain::Keychain + 'static,
 {
+	let outputs = read_imported_outputs(&args.input)?;
 	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
-		let (_, txs) = api.retrieve_txs(m, true, Some(args.id), None)?;
-		let stored_tx = api.get_stored_tx(m, &txs[0])?;
-		if stored_tx.is_none() {
-			error!(
-				"Transaction with id {} does not have transaction data. Not reposting.",
-				args.id
-			);
-			return Ok(());
-		}
-		match args.dump_file {
-			None => {
-				if txs[0].confirmed {
-					error!(
-						"Transaction with id {} is confirmed. Not reposting.",
-						args.id
-					);
-					return Ok(());
-				}
-				api.post_tx(m, &stored_tx.unwrap(), args.fluff)?;
-				info!("Reposted transaction at {}", args.id);
-				return Ok(());
-			}
-			Some(f) => {
-				let mut tx_file = File::create(f.clone())?;
-				tx_file.write_all(json::to_string(&stored_tx).unwrap().as_bytes())?;
-				tx_file.sync_all()?;
-				info!("Dumped transaction data for tx {} to {}", args.id, f);
-				return Ok(());
-			}
-		}
+		let summary = api.import_outputs(m, outputs)?;
+		println!(
+			"Imported {} outputs ({} already in the wallet, {} not unspent on chain, {} not \
+			 of this wallet's seed) from {}",
+			summary.num_imported,
+			summary.num_known,
+			summary.num_not_found,
+			summary.num_mismatched,
+			args.input
+		);
+		Ok(())
 	})?;
 	Ok(())
 }
 
-/// Cancel
-pub struct CancelArgs {
-	pub tx_id: Option<u32>,
-	pub tx_slate_id: Option<Uuid>,
-	pub tx_id_string: String,
+/// Output records of a JSON lines file, skipping records of another type
+fn read_imported_outputs(file: &str) -> Result<Vec<ImportedOutput>, libwallet::Error> {
+	let contents = fs::read_to_string(file).map_err(|e| {
+		libwallet::ErrorKind::GenericError(format!("Unable to read {}: {}", file, e))
+	})?;
+	let mut outputs = vec![];
+	for (i, line) in contents.lines().enumerate() {
+		if line.trim().is_empty() {
+			continue;
+		}
+		let parse_error = |e: json::Error| {
+			libwallet::ErrorKind::GenericError(format!("{} line {}: {}", file, i + 1, e))
+		};
+		let record: json::Value = json::from_str(line).map_err(parse_error)?;
+		let is_output = match record.get("type").and_then(|t| t.as_str()) {
+			Some("output") | None => true,
+			Some(_) => false,
+		};
+		if is_output {
+			outputs.push(json::from_value(record).map_err(parse_error)?);
+		}
+	}
+	Ok(outputs)
 }
 
-pub fn cancel<L, C, K>(
+/// Address book
+pub struct ContactsArgs {
+	pub add: Option<Contact>,
+	pub remove: Option<String>,
+}
+
+pub fn contacts<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: ContactsArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		if let Some(c) = args.add.as_ref() {
+			let c = api.save_contact(m, c)?;
+			warn!("Contact '{}' saved", c.name);
+		}
+		if let Some(name) = args.remove.as_ref() {
+			api.remove_contact(m, name)?;
+			warn!("Contact '{}' removed", name);
+		}
+		display::contacts(&api.retrieve_contacts(m)?);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Send templates
+pub struct TemplatesArgs {
+	pub add: Option<SendTemplate>,
+	pub remove: Option<String>,
+}
+
+pub fn templates<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: TemplatesArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		if let Some(t) = args.add.as_ref() {
+			let t = api.save_send_template(m, t)?;
+			warn!("Send template '{}' saved", t.name);
+		}
+		if let Some(name) = args.remove.as_ref() {
+			api.remove_send_template(m, name)?;
+			warn!("Send template '{}' removed", name);
+		}
+		display::send_templates(&api.retrieve_send_templates(m)?);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// The send template with the given name, executed by `send --template`
+pub fn send_template<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	name: &str,
+) -> Result<SendTemplate, Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut template = None;
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		template = Some(api.get_send_template(m, name)?);
+		Ok(())
+	})?;
+	Ok(template.unwrap())
+}
+
+/// Tag
+pub struct TagArgs {
+	pub id: Option<u32>,
+	pub tx_slate_id: Option<Uuid>,
+	pub add: Vec<String>,
+	pub remove: Vec<String>,
+}
+
+pub fn tag<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: TagArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		if args.id.is_none() && args.tx_slate_id.is_none() {
+			let tags = api.retrieve_tx_tags(m)?;
+			display::tx_tags(None, &tags);
+			return Ok(());
+		}
+		let tags = api.update_tx_tags(m, args.id, args.tx_slate_id, &args.add, &args.remove)?;
+		let id = match args.id {
+			Some(i) => i.to_string(),
+			None => args.tx_slate_id.map(|u| u.to_string()).unwrap_or_default(),
+		};
+		display::tx_tags(Some(id.as_str()), &tags);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Post
+pub struct PostArgs {
+	pub input: String,
+	pub fluff: bool,
+	pub ignore_node_height: bool,
+}
+
+pub fn post<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: PostArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let slate = PathToSlate((&args.input).into()).get_tx()?;
+
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		api.set_node_height_guard(!args.ignore_node_height);
+		api.post_tx(m, &slate.tx, args.fluff)?;
+		info!("Posted transaction");
+		return Ok(());
+	})?;
+	Ok(())
+}
+
+/// Repost
+pub struct RepostArgs {
+	pub id: u32,
+	pub dump_file: Option<String>,
+	pub fluff: bool,
+	pub ignore_node_height: bool,
+}
+
+pub fn repost<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: RepostArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, Some(args.id), None)?;
+		let stored_tx = api.get_stored_tx(m, &txs[0])?;
+		if stored_tx.is_none() {
+			error!(
+				"Transaction with id {} does not have transaction data. Not reposting.",
+				args.id
+			);
+			return Ok(());
+		}
+		match args.dump_file {
+			None => {
+				if txs[0].confirmed {
+					error!(
+						"Transaction with id {} is confirmed. Not reposting.",
+						args.id
+					);
+					return Ok(());
+				}
+				api.set_node_height_guard(!args.ignore_node_height);
+				api.post_tx(m, &stored_tx.unwrap(), args.fluff)?;
+				info!("Reposted transaction at {}", args.id);
+				return Ok(());
+			}
+			Some(f) => {
+				let mut tx_file = File::create(f.clone())?;
+				tx_file.write_all(json::to_string(&stored_tx).unwrap().as_bytes())?;
+				tx_file.sync_all()?;
+				info!("Dumped transaction data for tx {} to {}", args.id, f);
+				return Ok(());
+			}
+		}
+	})?;
+	Ok(())
+}
+
+/// Cancel
+pub struct CancelArgs {
+	pub tx_id: Option<u32>,
+	pub tx_slate_id: Option<Uuid>,
+	pub tx_id_string: String,
+}
+
+pub fn cancel<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
 	args: CancelArgs,
@@ -932,10 +2313,487 @@ where
 	Ok(())
 }
 
+/// Bulk cancel
+pub struct CancelTxsArgs {
+	pub filter: TxFilter,
+}
+
+pub fn cancel_txs<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: CancelTxsArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		let results = api.cancel_txs(m, &args.filter)?;
+		display::bulk_results("Cancelled Transactions", &results);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Bulk repost
+pub struct RepostTxsArgs {
+	pub filter: TxFilter,
+	pub fluff: bool,
+	pub ignore_node_height: bool,
+}
+
+pub fn repost_txs<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: RepostTxsArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
+		api.set_node_height_guard(!args.ignore_node_height);
+		let results = api.repost_txs(m, &args.filter, args.fluff)?;
+		display::bulk_results("Reposted Transactions", &results);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Sweep
+pub struct SweepArgs {
+	pub policy: SweepPolicy,
+}
+
+/// Whether this send includes a decoy output, at the configured frequency,
+/// returning its amount if so
+fn decoy_output_amount(config: &DecoyOutputConfig) -> Option<u64> {
+	let frequency = config.frequency.unwrap_or(0.5).max(0.0).min(1.0);
+	match thread_rng().gen_bool(frequency) {
+		true => Some(config.amount),
+		false => None,
+	}
+}
+
+/// Build a sweep policy from its configuration
+pub fn sweep_policy(config: &SweepConfig) -> SweepPolicy {
+	SweepPolicy {
+		hot_account: config.hot_account.clone(),
+		cold_account: config.cold_account.clone(),
+		max_hot_balance: config.max_hot_balance,
+		minimum_confirmations: config
+			.minimum_confirmations
+			.unwrap_or(libwallet::SWEEP_MINIMUM_CONFIRMATIONS),
+	}
+}
+
+fn sweep_once<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	policy: &SweepPolicy,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		match api.sweep_hot_account(m, policy)? {
+			Some(slate) => warn!(
+				"Swept {} {} from account '{}' to account '{}' (tx {})",
+				i18n::amount(slate.amount, false),
+				i18n::unit().symbol(),
+				policy.hot_account,
+				policy.cold_account,
+				slate.id,
+			),
+			None => info!(
+				"Spendable balance of account '{}' is within {} {}, nothing to sweep",
+				policy.hot_account,
+				i18n::amount(policy.max_hot_balance, false),
+				i18n::unit().symbol(),
+			),
+		}
+		Ok(())
+	})?;
+	Ok(())
+}
+
+pub fn sweep<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: SweepArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	sweep_once(wallet, keychain_mask, &args.policy)
+}
+
+/// Run the configured sweep policy at its interval in a background thread
+fn start_sweeper<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	policy: SweepPolicy,
+	interval: Duration,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let _ = thread::Builder::new()
+		.name("wallet-sweeper".to_string())
+		.spawn(move || loop {
+			let mask = keychain_mask.lock().clone();
+			if let Err(e) = sweep_once(wallet.clone(), mask.as_ref(), &policy) {
+				error!("Sweep of account '{}' failed: {}", policy.hot_account, e);
+			}
+			thread::sleep(interval);
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to start sweeper: {}", e)))?;
+	Ok(())
+}
+
+/// How often the listener checks whether the statements of the last month are written
+const STATEMENT_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Start of the month, in UTC, the given time falls in
+fn month_start(time: DateTime<Utc>) -> DateTime<Utc> {
+	let date = time.date_naive();
+	let date = date.with_day(1).unwrap_or(date);
+	Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// Path of the statement of an account for the month starting at the given time
+fn statement_path(dir: &str, account: &str, start: DateTime<Utc>) -> PathBuf {
+	let account: String = account
+		.chars()
+		.map(
+			|c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+				true => c,
+				false => '_',
+			},
+		)
+		.collect();
+	Path::new(dir).join(format!("{}-{}.json", account, start.format("%Y-%m")))
+}
+
+/// Write the statement of each account over the period starting and ending at the
+/// given times to the directory, valuing the balances in the fiat currency at the
+/// feed's current price if both are given. Statements already written are left as
+/// they are. Returns the paths of the statements written.
+pub fn write_statements<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	dir: &str,
+	start: DateTime<Utc>,
+	end: DateTime<Utc>,
+	fiat: Option<(&str, &dyn PriceFeed)>,
+) -> Result<Vec<PathBuf>, Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut written = vec![];
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let missing = api
+			.accounts(m)?
+			.iter()
+			.any(|a| !statement_path(dir, &a.label, start).exists());
+		if !missing {
+			return Ok(());
+		}
+		let price = match fiat {
+			Some((currency, feed)) => match feed.price(currency) {
+				Ok(p) => Some((currency, p)),
+				Err(e) => {
+					warn!("No price in {} to value the statements in: {}", currency, e);
+					None
+				}
+			},
+			None => None,
+		};
+		let (_, statements) = api.account_statements(m, true, start, end)?;
+		for mut statement in statements {
+			let path = statement_path(dir, &statement.account, start);
+			if path.exists() {
+				continue;
+			}
+			if let Some((currency, price)) = price {
+				let value = |amount: u64| amount as f64 / core::consensus::EPIC_BASE as f64 * price;
+				statement.fiat = Some(StatementFiat {
+					currency: currency.to_uppercase(),
+					price,
+					opening_balance: value(statement.opening_balance),
+					closing_balance: value(statement.closing_balance),
+				});
+			}
+			let json = json::to_string_pretty(&statement).map_err(|e| {
+				libwallet::ErrorKind::GenericError(format!("Serializing a statement: {}", e))
+			})?;
+			fs::write(&path, json).map_err(|e| {
+				libwallet::ErrorKind::GenericError(format!(
+					"Writing the statement {}: {}",
+					path.display(),
+					e
+				))
+			})?;
+			written.push(path);
+		}
+		Ok(())
+	})?;
+	Ok(written)
+}
+
+/// Write, in a background thread, the statement of each account for the last month
+/// once it's over, as configured
+fn start_statements<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	config: &WalletConfig,
+	statements: &StatementsConfig,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let price_feed = match (
+		statements.fiat_currency.as_ref(),
+		config.price_feed.as_ref(),
+	) {
+		(Some(_), Some(p)) => Some(price_feed_from_config(p)?),
+		(Some(c), None) => {
+			warn!(
+				"No price feed configured, statements aren't valued in {}",
+				c
+			);
+			None
+		}
+		(None, _) => None,
+	};
+	fs::create_dir_all(&statements.dir).map_err(|e| {
+		ErrorKind::GenericError(format!(
+			"Unable to create the statements directory {}: {}",
+			statements.dir, e
+		))
+	})?;
+	let dir = statements.dir.clone();
+	let currency = statements.fiat_currency.clone();
+	let _ = thread::Builder::new()
+		.name("wallet-statements".to_string())
+		.spawn(move || loop {
+			let end = month_start(Utc::now());
+			let start = month_start(end - chrono::Duration::days(1));
+			let fiat = match (currency.as_ref(), price_feed.as_ref()) {
+				(Some(c), Some(f)) => Some((c.as_str(), &**f)),
+				_ => None,
+			};
+			let mask = keychain_mask.lock().clone();
+			match write_statements(wallet.clone(), mask.as_ref(), &dir, start, end, fiat) {
+				Ok(paths) => {
+					for p in paths {
+						info!("Wrote the statement {}", p.display());
+					}
+				}
+				Err(e) => error!("Unable to write the statements: {}", e),
+			}
+			thread::sleep(STATEMENT_CHECK_INTERVAL);
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to start statements: {}", e)))?;
+	Ok(())
+}
+
+/// Back the wallet's files up to the configured target at the given interval in a
+/// background thread, with the wallet locked while they're read
+fn start_backups<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	config: &WalletConfig,
+	backup: &BackupConfig,
+	password: ZeroingString,
+	interval: Duration,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let target = backup_target_from_config(backup)?;
+	let passphrase = backup.passphrase.clone();
+	let data_dir = Path::new(&config.data_file_dir).join(EPIC_WALLET_DIR);
+	let _ = thread::Builder::new()
+		.name("wallet-backup".to_string())
+		.spawn(move || loop {
+			let res = {
+				let _lock = wallet.lock();
+				backup_wallet(
+					&*target,
+					&data_dir.to_string_lossy(),
+					&password,
+					passphrase.as_ref().map(|p| p.as_str()),
+				)
+			};
+			match res {
+				Ok(name) => info!("Backed the wallet up to {} as {}", target.describe(), name),
+				Err(e) => error!("Backup to {} failed: {}", target.describe(), e),
+			}
+			thread::sleep(interval);
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to start backups: {}", e)))?;
+	Ok(())
+}
+
+/// How often the listener looks for expired received transactions
+const RECEIVE_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Cancel, in a background thread, the received transactions not seen on chain
+/// after the given number of hours
+fn start_receive_expirer<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	expiry_hours: u64,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let filter = TxFilter {
+		tx_type: Some(TxLogEntryType::TxReceived),
+		min_age_hours: Some(expiry_hours),
+	};
+	let _ = thread::Builder::new()
+		.name("wallet-receive-expirer".to_string())
+		.spawn(move || loop {
+			let mask = keychain_mask.lock().clone();
+			let res = controller::owner_single_use(wallet.clone(), mask.as_ref(), |api, m| {
+				for r in api.cancel_txs(m, &filter)? {
+					match r.error {
+						None => warn!(
+							"Received transaction {} not seen on chain after {} hours, cancelled",
+							r.tx_id, expiry_hours
+						),
+						Some(e) => {
+							error!("Unable to expire received transaction {}: {}", r.tx_id, e)
+						}
+					}
+				}
+				Ok(())
+			});
+			if let Err(e) = res {
+				error!("Expiry of received transactions failed: {}", e);
+			}
+			thread::sleep(RECEIVE_EXPIRY_CHECK_INTERVAL);
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to start receive expirer: {}", e)))?;
+	Ok(())
+}
+
+/// How often balance alerts and output maturity are checked while listening,
+/// if not configured
+const UPDATER_WATCH_DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Run the wallet updater in a background thread if balance alerts or maturity
+/// notifications are configured, raising them after each update at the shorter
+/// of their intervals. The updater waits for an initial scan started with the
+/// same API.
+fn start_updater_watches<L, C, K>(
+	api: &Owner<L, C, K>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	config: &WalletConfig,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut intervals = vec![];
+	if let Some(a) = config.balance_alerts.as_ref() {
+		api.set_balance_alerts(Some(balance_alerts_from_config(a)));
+		intervals.push(a.interval_secs);
+	}
+	if let Some(m) = config.maturity_notifications.as_ref() {
+		api.set_maturity_watch(Some(maturity_watch_from_config(m)));
+		intervals.push(m.interval_secs);
+	}
+	let interval = intervals
+		.into_iter()
+		.map(|i| {
+			i.map(Duration::from_secs)
+				.unwrap_or(UPDATER_WATCH_DEFAULT_INTERVAL)
+		})
+		.min();
+	let interval = match interval {
+		Some(i) => i,
+		None => return Ok(()),
+	};
+	let mask = keychain_mask.lock().clone();
+	api.start_updater(mask.as_ref(), interval)?;
+	Ok(())
+}
+
+/// Pending receives command args
+pub struct PendingArgs {
+	pub expiry_hours: Option<u64>,
+}
+
+pub fn pending<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: PendingArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let (validated, txs) = api.retrieve_pending_receives(m, true)?;
+		display::pending_receives(&txs, args.expiry_hours, validated);
+		Ok(())
+	})?;
+	Ok(())
+}
+
+/// Fee report command args
+pub struct FeesArgs {
+	pub period: FeePeriod,
+}
+
+pub fn fees<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: FeesArgs,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	controller::owner_single_use(wallet, keychain_mask, |api, m| {
+		let (validated, report) = api.fee_report(m, true, args.period)?;
+		display::fee_report(&report, validated);
+		Ok(())
+	})?;
+	Ok(())
+}
+
 /// wallet check
 pub struct CheckArgs {
 	pub delete_unconfirmed: bool,
 	pub start_height: Option<u64>,
+	/// Last block to scan, only used for a preview
+	pub end_height: Option<u64>,
+	/// Only report what the scan would find
+	pub preview: bool,
+	/// Account whose outputs only are restored and repaired, if given
+	pub account: Option<String>,
 }
 
 pub fn scan<L, C, K>(
@@ -949,8 +2807,22 @@ where
 	K: keychain::Keychain + 'static,
 {
 	controller::owner_single_use(wallet.clone(), keychain_mask, |api, m| {
-		warn!("Starting output scan ...",);
-		let result = api.scan(m, args.start_height, args.delete_unconfirmed);
+		if args.preview {
+			warn!("Starting output scan preview ...",);
+			let preview = api.scan_preview(m, args.start_height, args.end_height)?;
+			display::scan_preview(&preview);
+			return Ok(());
+		}
+		let result = match args.account.as_ref() {
+			Some(a) => {
+				warn!("Starting output scan of account {} ...", a);
+				api.scan_account(m, a, args.start_height, args.delete_unconfirmed)
+			}
+			None => {
+				warn!("Starting output scan ...",);
+				api.scan(m, args.start_height, args.delete_unconfirmed)
+			}
+		};
 		match result {
 			Ok(_) => {
 				warn!("Wallet check complete",);
@@ -1051,6 +2923,7 @@ where
 /// Proof Verify Args
 pub struct ProofVerifyArgs {
 	pub input_file: String,
+	pub offline: bool,
 }
 
 pub fn proof_verify<L, C, K>(
@@ -1086,6 +2959,19 @@ where
 				return Err(libwallet::ErrorKind::PaymentProofParsing(msg).into());
 			}
 		};
+		if args.offline {
+			return match api.verify_payment_proof_offline(&proof) {
+				Ok(()) => {
+					println!("Payment proof's signatures are valid.");
+					println!("Its kernel wasn't looked up on chain.");
+					Ok(())
+				}
+				Err(e) => {
+					error!("Proof not valid: {}", e);
+					Err(e)
+				}
+			};
+		}
 		let result = api.verify_payment_proof(m, &proof);
 		match result {
 			Ok((iam_sender, iam_recipient)) => {
@@ -1111,3 +2997,178 @@ where
 	})?;
 	Ok(())
 }
+
+/// Arguments for the slate inspect command
+pub struct SlateInspectArgs {
+	pub input: String,
+}
+
+pub fn slate_inspect(
+	args: SlateInspectArgs,
+	dark_background_color_scheme: bool,
+) -> Result<(), Error> {
+	let slate = PathToSlate((&args.input).into()).get_tx()?;
+	display::slate(&slate, dark_background_color_scheme)?;
+	Ok(())
+}
+
+/// Arguments for the slate diff command
+pub struct SlateDiffArgs {
+	pub from: String,
+	pub to: String,
+}
+
+/// A field of a slate which differs between two slates, `None` where the field
+/// is missing from a slate
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlateChange {
+	pub field: String,
+	pub from: Option<String>,
+	pub to: Option<String>,
+}
+
+pub fn slate_diff(args: SlateDiffArgs) -> Result<(), Error> {
+	let from = PathToSlate((&args.from).into()).get_tx()?;
+	let to = PathToSlate((&args.to).into()).get_tx()?;
+	let from = json::to_value(&from).map_err(|_| ErrorKind::Format)?;
+	let to = json::to_value(&to).map_err(|_| ErrorKind::Format)?;
+	display::slate_diff(&args.from, &args.to, &json_changes(&from, &to));
+	Ok(())
+}
+
+/// Fields differing between two JSON documents, by path. Elements of arrays are
+/// identified by their commitment or id where they have one, so inputs and
+/// outputs added by a counterparty don't show as changes to all others.
+fn json_changes(from: &json::Value, to: &json::Value) -> Vec<SlateChange> {
+	let mut from_fields = BTreeMap::new();
+	let mut to_fields = BTreeMap::new();
+	flatten_json("", from, &mut from_fields);
+	flatten_json("", to, &mut to_fields);
+
+	let mut changes = vec![];
+	for (field, value) in from_fields.iter() {
+		match to_fields.get(field) {
+			Some(v) if v == value => {}
+			v => changes.push(SlateChange {
+				field: field.clone(),
+				from: Some(value.clone()),
+				to: v.cloned(),
+			}),
+		}
+	}
+	for (field, value) in to_fields.iter() {
+		if !from_fields.contains_key(field) {
+			changes.push(SlateChange {
+				field: field.clone(),
+				from: None,
+				to: Some(value.clone()),
+			});
+		}
+	}
+	changes.sort_by(|a, b| a.field.cmp(&b.field));
+	changes
+}
+
+fn flatten_json(path: &str, value: &json::Value, fields: &mut BTreeMap<String, String>) {
+	match value {
+		json::Value::Object(o) => {
+			for (k, v) in o {
+				let p = match path.is_empty() {
+					true => k.clone(),
+					false => format!("{}.{}", path, k),
+				};
+				flatten_json(&p, v, fields);
+			}
+		}
+		json::Value::Array(a) if !a.is_empty() => {
+			for (i, v) in a.iter().enumerate() {
+				let key = ["commit", "id"]
+					.iter()
+					.find_map(|k| v.get(*k).map(|id| format!("[{}={}]", k, json_text(id))))
+					.unwrap_or_else(|| format!("[{}]", i));
+				flatten_json(&format!("{}{}", path, key), v, fields);
+			}
+		}
+		v => {
+			fields.insert(path.to_owned(), json_text(v));
+		}
+	}
+}
+
+fn json_text(value: &json::Value) -> String {
+	match value {
+		json::Value::String(s) => s.clone(),
+		v => v.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn slate_json_changes() {
+		let from = json::json!({
+			"amount": "100",
+			"fee": "8",
+			"tx": {
+				"body": {
+					"outputs": [{"commit": "08aa", "proof": "01"}],
+					"kernels": [{"excess": "00"}]
+				}
+			},
+			"participant_data": [{"id": "0", "part_sig": null}]
+		});
+		let to = json::json!({
+			"amount": "100",
+			"fee": "8",
+			"tx": {
+				"body": {
+					"outputs": [{"commit": "0801", "proof": "02"}, {"commit": "08aa", "proof": "01"}],
+					"kernels": [{"excess": "09ff"}]
+				}
+			},
+			"participant_data": [{"id": "0", "part_sig": "ab"}, {"id": "1", "part_sig": "cd"}]
+		});
+		let changes = json_changes(&from, &to);
+		let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+		assert_eq!(
+			fields,
+			vec![
+				"participant_data[id=0].part_sig",
+				"participant_data[id=1].id",
+				"participant_data[id=1].part_sig",
+				"tx.body.kernels[0].excess",
+				"tx.body.outputs[commit=0801].commit",
+				"tx.body.outputs[commit=0801].proof",
+			]
+		);
+		assert_eq!(changes[0].from, Some("null".to_owned()));
+		assert_eq!(changes[0].to, Some("ab".to_owned()));
+		assert_eq!(changes[1].from, None);
+		assert!(json_changes(&from, &from).is_empty());
+	}
+
+	#[test]
+	fn message_template_render() {
+		let mut template = MessageTemplate {
+			template: "Order {invoice_ref} from {account}: {amount}".to_owned(),
+			account: "shop".to_owned(),
+			invoice_ref: Some("INV-42".to_owned()),
+		};
+		let amount = format!(
+			"{} {}",
+			i18n::amount(1_500_000, false),
+			i18n::unit().symbol()
+		);
+		assert_eq!(
+			template.render(1_500_000),
+			format!("Order INV-42 from shop: {}", amount)
+		);
+
+		// placeholders without a value are left empty
+		template.invoice_ref = None;
+		template.template = "{invoice_ref}{account}".to_owned();
+		assert_eq!(template.render(1), "shop");
+	}
+}
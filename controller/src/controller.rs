@@ -14,15 +14,29 @@
 
 //! Controller for wallet.. instantiates and handles listeners (or single-run
 //! invocations) as needed.
-use crate::api::{self, ApiServer, BasicAuthMiddleware, ResponseFuture, Router, TLSConfig};
-use crate::config::{EpicboxConfig, TorConfig};
+use crate::api::{self, ApiServer, ResponseFuture, Router, TLSConfig};
+use crate::auth::RotatingBasicAuthMiddleware;
+use crate::config::{
+	EpicboxConfig, GlobalWalletConfig, OpenThrottleConfig, PasswordPolicyConfig, TorConfig,
+};
+use crate::core::global;
+use crate::cors::CorsMiddleware;
+use crate::events::{OwnerEventsHandler, OWNER_EVENTS_PATH};
+use crate::export::{ExportKind, OwnerExportHandler};
+use crate::foreign_access::ForeignAccess;
 use crate::keychain::Keychain;
+use crate::libwallet::api_impl::owner;
+use crate::libwallet::api_impl::owner_updater::{BalanceAlerts, MaturityWatch};
 use crate::libwallet::{
-	address, Error, ErrorKind, NodeClient, NodeVersionInfo, Slate, WalletInst, WalletLCProvider,
-	EPIC_BLOCK_HEADER_VERSION,
+	address, publish_event, record_event, verify_sender_signature, Error, ErrorKind, NodeClient,
+	NodeVersionInfo, ReceiveHook, ResponseSignature, Slate, TransportEvent, TxHook, WalletEvent,
+	WalletInst, WalletLCProvider, EPIC_BLOCK_HEADER_VERSION,
 };
+use crate::mtls;
+use crate::sni;
 use crate::util::secp::key::SecretKey;
-use crate::util::{from_hex, static_secp_instance, to_base64, Mutex};
+use crate::util::{from_hex, static_secp_instance, to_hex, Mutex};
+use chrono::Utc;
 use failure::ResultExt;
 use futures::future::{err, ok};
 use futures::{Future, Stream};
@@ -33,16 +47,20 @@ use serde_json;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use crate::impls::tor::config as tor_config;
-use crate::impls::tor::process as tor_process;
+use crate::impls::tor::supervisor::{self as tor_supervisor, TorSupervisor};
+use crate::impls::SENDER_SIGNATURE_HEADER;
 
 use crate::apiwallet::{
-	EncryptedRequest, EncryptedResponse, EncryptionErrorResponse, Foreign,
-	ForeignCheckMiddlewareFn, ForeignRpc, Owner, OwnerRpc, OwnerRpcS, RpcId,
+	handle_timed_request, AuditEntry, EncryptedRequest, EncryptedResponse, EncryptionErrorResponse,
+	Foreign, ForeignCheckMiddlewareFn, ForeignRpc, Owner, OwnerRpc, OwnerRpcS, RpcId, TokenCall,
+	TokenScope,
 };
 use easy_jsonrpc_mw;
-use easy_jsonrpc_mw::{Handler, MaybeReply};
+use easy_jsonrpc_mw::MaybeReply;
 
 lazy_static! {
 	pub static ref EPIC_OWNER_BASIC_REALM: HeaderValue =
@@ -76,18 +94,18 @@ fn check_middleware(
 	}
 }
 
-/// initiate the tor listener
+/// initiate the tor listener, supervised so tor is restarted if the hidden
+/// service becomes unreachable
 fn init_tor_listener<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
 	addr: &str,
-) -> Result<tor_process::TorProcess, Error>
+) -> Result<TorSupervisor, Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
 	C: NodeClient + 'static,
 	K: Keychain + 'static,
 {
-	let mut process = tor_process::TorProcess::new();
 	let mask = keychain_mask.lock();
 	// eventually want to read a list of service config keys
 	let mut w_lock = wallet.lock();
@@ -107,14 +125,14 @@ where
 	tor_config::output_tor_listener_config(&tor_dir, addr, &vec![sec_key])
 		.map_err(|e| ErrorKind::TorConfig(format!("{:?}", e).into()))?;
 	// Start TOR process
-	process
-		.torrc_path(&format!("{}/torrc", tor_dir))
-		.working_dir(&tor_dir)
-		.timeout(20)
-		.completion_percent(100)
-		.launch()
-		.map_err(|e| ErrorKind::TorProcess(format!("{:?}", e).into()))?;
-	Ok(process)
+	let process = tor_supervisor::launch_listener_tor(&tor_dir).map_err(ErrorKind::TorProcess)?;
+	Ok(TorSupervisor::start(
+		process,
+		&onion_address,
+		addr,
+		&tor_dir,
+		Duration::from_secs(tor_supervisor::DEFAULT_TOR_CHECK_INTERVAL_SECS),
+	))
 }
 
 /// Instantiate wallet Owner API for a single-use (command line) call
@@ -158,30 +176,58 @@ where
 /// Listener version, providing same API but listening for requests on a
 /// port and wrapping the calls
 /// Note keychain mask is only provided here in case the foreign listener is also being used
-/// in the same wallet instance
+/// in the same wallet instance. The V3 API also serves the further `wallets` given, by
+/// their name, to the calls naming them in their `wallet` member.
 pub fn owner_listener<L, C, K>(
 	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	wallets: HashMap<String, Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>>,
 	addr: &str,
 	api_secret: Option<String>,
+	api_secret_path: Option<String>,
 	tls_config: Option<TLSConfig>,
+	client_ca_file: Option<String>,
 	owner_api_include_foreign: Option<bool>,
 	tor_config: Option<TorConfig>,
 	epicbox_config: Option<EpicboxConfig>,
+	send_timeout: Option<Duration>,
+	receive_hook: Option<Arc<dyn ReceiveHook>>,
+	send_queue_workers: Option<usize>,
+	balance_alerts: Option<BalanceAlerts>,
+	maturity_watch: Option<MaturityWatch>,
+	tx_hook: Option<Arc<dyn TxHook>>,
+	password_policy: Option<PasswordPolicyConfig>,
+	open_throttle: Option<OpenThrottleConfig>,
+	require_approval: bool,
+	limits: RequestLimits,
+	sign_responses: bool,
+	cors: Option<CorsMiddleware>,
+	global_config: Option<GlobalWalletConfig>,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
 	C: NodeClient + 'static,
 	K: Keychain + 'static,
 {
+	if client_ca_file.is_some() && tls_config.is_none() {
+		let msg = "Client certificates require the owner API to use TLS".to_owned();
+		return Err(ErrorKind::ArgumentError(msg).into());
+	}
+	if let Some(n) = wallets.keys().find(|n| n.is_empty()) {
+		let msg = format!("Invalid wallet name for the owner API: {:?}", n);
+		return Err(ErrorKind::ArgumentError(msg).into());
+	}
 	let mut router = Router::new();
-	if api_secret.is_some() {
-		let api_basic_auth =
-			"Basic ".to_string() + &to_base64(&("epic:".to_string() + &api_secret.unwrap()));
-		let basic_auth_middleware = Arc::new(BasicAuthMiddleware::new(
-			api_basic_auth,
+	// ahead of the authentication, so its refusals carry the CORS headers too
+	if let Some(c) = cors {
+		router.add_middleware(Arc::new(c));
+	}
+	if let Some(api_secret) = api_secret {
+		let basic_auth_middleware = Arc::new(RotatingBasicAuthMiddleware::new(
+			api_secret,
+			api_secret_path,
 			&EPIC_OWNER_BASIC_REALM,
-			Some("/v2/foreign".into()),
+			vec!["/v2/foreign".into(), "/health".into(), "/ready".into()],
 		));
 		router.add_middleware(basic_auth_middleware);
 	}
@@ -190,14 +236,30 @@ where
 		running_foreign = true;
 	}
 
-	let api_handler_v2 = OwnerAPIHandlerV2::new(wallet.clone());
+	let api_handler_v2 = OwnerAPIHandlerV2::new(wallet.clone(), require_approval, limits.clone());
 	let api_handler_v3 = OwnerAPIHandlerV3::new(
 		wallet.clone(),
 		keychain_mask.clone(),
 		tor_config,
 		epicbox_config,
+		send_timeout,
 		running_foreign,
+		send_queue_workers,
+		balance_alerts,
+		maturity_watch,
+		tx_hook,
+		password_policy,
+		open_throttle,
+		require_approval,
+		limits.clone(),
+		wallets,
 	);
+	// the configuration the default wallet reloads, on a call or a SIGHUP
+	let reload_config = global_config.is_some();
+	api_handler_v3.owner_api.set_config(global_config);
+	if reload_config {
+		reload_config_on_hangup(api_handler_v3.owner_api.clone())?;
+	}
 
 	router
 		.add_route("/v2/owner", Arc::new(api_handler_v2))
@@ -207,10 +269,33 @@ where
 		.add_route("/v3/owner", Arc::new(api_handler_v3))
 		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
 
+	router
+		.add_route(OWNER_EVENTS_PATH, Arc::new(OwnerEventsHandler))
+		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+
+	for kind in ExportKind::ALL.iter() {
+		let export_handler = OwnerExportHandler::new(
+			*kind,
+			api_handler_v3.owner_api.clone(),
+			api_handler_v3.wallets.clone(),
+		);
+		router
+			.add_route(kind.path(), Arc::new(export_handler))
+			.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+	}
+
+	add_health_routes(&mut router, wallet.clone())?;
+
 	// If so configured, add the foreign API to the same port
 	if running_foreign {
 		warn!("Starting HTTP Foreign API on Owner server at {}.", addr);
-		let foreign_api_handler_v2 = ForeignAPIHandlerV2::new(wallet, keychain_mask);
+		let foreign_api_handler_v2 = ForeignAPIHandlerV2::new(
+			wallet.clone(),
+			keychain_mask.clone(),
+			receive_hook,
+			limits,
+			sign_responses,
+		);
 		router
 			.add_route("/v2/foreign", Arc::new(foreign_api_handler_v2))
 			.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
@@ -219,17 +304,164 @@ where
 	let mut apis = ApiServer::new();
 	warn!("Starting HTTP Owner API server at {}.", addr);
 	let socket_addr: SocketAddr = addr.parse().expect("unable to parse socket address");
+	let api_thread = match (client_ca_file, tls_config) {
+		(Some(ca), Some(tls)) => {
+			warn!(
+				"Owner API clients must present a certificate signed by {}",
+				ca
+			);
+			mtls::start_mutual_tls(socket_addr, router, &tls, &ca)?
+		}
+		(_, tls_config) => {
+			apis.start(socket_addr, router, tls_config)
+				.context(ErrorKind::GenericError(
+					"API thread failed to start".to_string(),
+				))?
+		}
+	};
+	warn!("HTTP Owner listener started.");
+	record_listener_event(&wallet, &keychain_mask, "owner", addr, true);
+	let res = api_thread.join();
+	record_listener_event(&wallet, &keychain_mask, "owner", addr, false);
+	res.map_err(|e| ErrorKind::GenericError(format!("API thread panicked :{:?}", e)).into())
+}
+
+/// Reload the configuration of the owner API each time the process receives a
+/// SIGHUP, as `reload_config` does
+#[cfg(unix)]
+fn reload_config_on_hangup<L, C, K>(owner_api: Arc<Owner<L, C, K>>) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let signals = signal_hook::iterator::Signals::new(&[signal_hook::SIGHUP])
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to catch SIGHUP: {}", e)))?;
+	thread::Builder::new()
+		.name("config-reload".to_string())
+		.spawn(move || {
+			for _ in signals.forever() {
+				match owner_api.reload_config() {
+					Ok(r) => warn!(
+						"Configuration reloaded, applied: {:?}, requiring a restart: {:?}",
+						r.applied, r.restart_required
+					),
+					Err(e) => error!("Unable to reload the configuration: {}", e),
+				}
+			}
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to catch SIGHUP: {}", e)))?;
+	Ok(())
+}
+
+/// Without SIGHUP, the configuration is only reloaded by calls to `reload_config`
+#[cfg(not(unix))]
+fn reload_config_on_hangup<L, C, K>(_owner_api: Arc<Owner<L, C, K>>) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	Ok(())
+}
+
+/// Keep the start or stop of a listener in the events of the wallet if it's open,
+/// publishing the event either way
+fn record_listener_event<L, C, K>(
+	wallet: &Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: &Arc<Mutex<Option<SecretKey>>>,
+	listener: &str,
+	addr: &str,
+	started: bool,
+) where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let (listener, address) = (listener.to_owned(), addr.to_owned());
+	let event = match started {
+		true => WalletEvent::ListenerStarted { listener, address },
+		false => WalletEvent::ListenerStopped { listener, address },
+	};
+	let mask = keychain_mask.lock().clone();
+	let mut w_lock = wallet.lock();
+	match w_lock.lc_provider().and_then(|lc| lc.wallet_inst()) {
+		Ok(w) => record_event(&mut **w, mask.as_ref(), event),
+		Err(_) => publish_event(event),
+	}
+}
+
+/// Listener serving the queries of the V2 owner API on a snapshot of another
+/// wallet, opened with `open_snapshot`. The calls changing a wallet are refused,
+/// as with a read-only token.
+pub fn snapshot_listener<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	addr: &str,
+	api_secret: Option<String>,
+	tls_config: Option<TLSConfig>,
+	limits: RequestLimits,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let mut router = Router::new();
+	if let Some(api_secret) = api_secret {
+		let basic_auth_middleware = Arc::new(RotatingBasicAuthMiddleware::new(
+			api_secret,
+			None,
+			&EPIC_OWNER_BASIC_REALM,
+			vec!["/health".into(), "/ready".into()],
+		));
+		router.add_middleware(basic_auth_middleware);
+	}
+	let api_handler = SnapshotAPIHandler::new(wallet.clone(), limits);
+	router
+		.add_route("/v2/owner", Arc::new(api_handler))
+		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+	add_health_routes(&mut router, wallet)?;
+
+	let mut apis = ApiServer::new();
+	warn!(
+		"Starting HTTP Owner API server of the snapshot at {}.",
+		addr
+	);
+	let socket_addr: SocketAddr = addr.parse().expect("unable to parse socket address");
 	let api_thread =
 		apis.start(socket_addr, router, tls_config)
 			.context(ErrorKind::GenericError(
 				"API thread failed to start".to_string(),
 			))?;
-	warn!("HTTP Owner listener started.");
+	warn!("HTTP Owner listener of the snapshot started.");
 	api_thread
 		.join()
 		.map_err(|e| ErrorKind::GenericError(format!("API thread panicked :{:?}", e)).into())
 }
 
+/// The tor listener of the wallet, or `None` if it can't be started and the
+/// listener is only reachable over HTTP
+fn start_tor_listener<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	addr: &str,
+) -> Option<TorSupervisor>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	match init_tor_listener(wallet, keychain_mask, addr) {
+		Ok(tp) => Some(tp),
+		Err(e) => {
+			warn!("Unable to start TOR listener; Check that TOR executable is installed and on your path");
+			warn!("Tor Error: {}", e);
+			warn!("Listener will be available via HTTP only");
+			None
+		}
+	}
+}
+
 /// Listener version, providing same API but listening for requests on a
 /// port and wrapping the calls
 pub fn foreign_listener<L, C, K>(
@@ -238,6 +470,11 @@ pub fn foreign_listener<L, C, K>(
 	addr: &str,
 	tls_config: Option<TLSConfig>,
 	use_tor: bool,
+	receive_hook: Option<Arc<dyn ReceiveHook>>,
+	limits: RequestLimits,
+	sign_responses: bool,
+	require_sender_signature: bool,
+	cors: Option<CorsMiddleware>,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'static, C, K> + 'static,
@@ -245,26 +482,30 @@ where
 	K: Keychain + 'static,
 {
 	// need to keep in scope while the main listener is running
-	let _tor_process = match use_tor {
-		true => match init_tor_listener(wallet.clone(), keychain_mask.clone(), addr) {
-			Ok(tp) => Some(tp),
-			Err(e) => {
-				warn!("Unable to start TOR listener; Check that TOR executable is installed and on your path");
-				warn!("Tor Error: {}", e);
-				warn!("Listener will be available via HTTP only");
-				None
-			}
-		},
+	let _tor_supervisor = match use_tor {
+		true => start_tor_listener(wallet.clone(), keychain_mask.clone(), addr),
 		false => None,
 	};
 
-	let api_handler_v2 = ForeignAPIHandlerV2::new(wallet, keychain_mask);
+	let mut api_handler_v2 = ForeignAPIHandlerV2::new(
+		wallet.clone(),
+		keychain_mask.clone(),
+		receive_hook,
+		limits,
+		sign_responses,
+	);
+	api_handler_v2.require_sender_signature = require_sender_signature;
 	let mut router = Router::new();
+	if let Some(c) = cors {
+		router.add_middleware(Arc::new(c));
+	}
 
 	router
 		.add_route("/v2/foreign", Arc::new(api_handler_v2))
 		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
 
+	add_health_routes(&mut router, wallet.clone())?;
+
 	let mut apis = ApiServer::new();
 	warn!("Starting HTTP Foreign listener API server at {}.", addr);
 	let socket_addr: SocketAddr = addr.parse().expect("unable to parse socket address");
@@ -275,14 +516,248 @@ where
 			))?;
 
 	warn!("HTTP Foreign listener started.");
+	record_listener_event(&wallet, &keychain_mask, "foreign", addr, true);
+	let res = api_thread.join();
+	record_listener_event(&wallet, &keychain_mask, "foreign", addr, false);
+	res.map_err(|e| ErrorKind::GenericError(format!("API thread panicked :{:?}", e)).into())
+}
 
-	api_thread
-		.join()
-		.map_err(|e| ErrorKind::GenericError(format!("API thread panicked :{:?}", e)).into())
+/// Foreign API of one of the further wallets served by a multi-wallet foreign listener
+pub struct ForeignWalletRoute<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Name of the wallet, its API served at `/<name>/v2/foreign`
+	pub name: String,
+	/// Hostnames the wallet's API is also served for at `/v2/foreign`
+	pub hostnames: Vec<String>,
+	/// TLS certificate presented to the clients asking for one of the hostnames, the
+	/// listener's certificate being presented if not set
+	pub tls_config: Option<TLSConfig>,
+	/// Wallet instance
+	pub wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	/// Keychain mask of the wallet
+	pub keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	/// Hook run on the slates the wallet receives
+	pub receive_hook: Option<Arc<dyn ReceiveHook>>,
+}
+
+/// Listener serving the foreign API of the wallet at `/v2/foreign` as
+/// [`foreign_listener`](fn.foreign_listener.html) does, and the foreign APIs of
+/// further wallets on the same port, each under its name's path prefix and at
+/// `/v2/foreign` for the hostnames it's given. Over TLS, the clients asking for
+/// one of those hostnames are presented the wallet's certificate if it has one
+/// (SNI), the listener's certificate otherwise.
+pub fn multi_foreign_listener<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	routes: Vec<ForeignWalletRoute<L, C, K>>,
+	addr: &str,
+	tls_config: Option<TLSConfig>,
+	use_tor: bool,
+	receive_hook: Option<Arc<dyn ReceiveHook>>,
+	limits: RequestLimits,
+	sign_responses: bool,
+	cors: Option<CorsMiddleware>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	// the tor hidden service only serves the listener's wallet
+	let _tor_supervisor = match use_tor {
+		true => start_tor_listener(wallet.clone(), keychain_mask.clone(), addr),
+		false => None,
+	};
+
+	let mut router = Router::new();
+	if let Some(c) = cors {
+		router.add_middleware(Arc::new(c));
+	}
+
+	let default: api::HandlerObj = Arc::new(ForeignAPIHandlerV2::new(
+		wallet.clone(),
+		keychain_mask.clone(),
+		receive_hook,
+		limits.clone(),
+		sign_responses,
+	));
+	let mut by_host = HashMap::new();
+	let mut certs_by_host = HashMap::new();
+	for route in routes {
+		if route.name.is_empty() || route.name.contains('/') {
+			return Err(ErrorKind::ArgumentError(format!(
+				"Invalid wallet name for the listener: {}",
+				route.name
+			))
+			.into());
+		}
+		let handler: api::HandlerObj = Arc::new(ForeignAPIHandlerV2::new(
+			route.wallet,
+			route.keychain_mask,
+			route.receive_hook,
+			limits.clone(),
+			sign_responses,
+		));
+		// the router keeps its routes for as long as the listener runs
+		let path: &'static str = Box::leak(format!("/{}/v2/foreign", route.name).into_boxed_str());
+		router
+			.add_route(path, handler.clone())
+			.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+		for h in route.hostnames {
+			let h = h.to_lowercase();
+			if by_host.insert(h.clone(), handler.clone()).is_some() {
+				return Err(ErrorKind::ArgumentError(format!(
+					"Hostname {} given for more than one wallet",
+					h
+				))
+				.into());
+			}
+			if let Some(t) = route.tls_config.as_ref() {
+				certs_by_host.insert(h, t.clone());
+			}
+		}
+		warn!("Serving the foreign API of wallet {}", route.name);
+	}
+	router
+		.add_route(
+			"/v2/foreign",
+			Arc::new(HostRouteHandler { by_host, default }),
+		)
+		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+	add_health_routes(&mut router, wallet.clone())?;
+
+	let mut apis = ApiServer::new();
+	warn!("Starting HTTP Foreign listener API server at {}.", addr);
+	let socket_addr: SocketAddr = addr.parse().expect("unable to parse socket address");
+	let api_thread = match (certs_by_host.is_empty(), tls_config) {
+		(false, Some(tls)) => sni::start_sni_tls(socket_addr, router, &tls, &certs_by_host)?,
+		(false, None) => {
+			let msg = "The wallets' TLS certificates need the listener's TLS certificate";
+			return Err(ErrorKind::ArgumentError(msg.to_owned()).into());
+		}
+		(true, tls_config) => {
+			apis.start(socket_addr, router, tls_config)
+				.context(ErrorKind::GenericError(
+					"API thread failed to start".to_string(),
+				))?
+		}
+	};
+
+	warn!("HTTP Foreign listener started.");
+	record_listener_event(&wallet, &keychain_mask, "foreign", addr, true);
+	let res = api_thread.join();
+	record_listener_event(&wallet, &keychain_mask, "foreign", addr, false);
+	res.map_err(|e| ErrorKind::GenericError(format!("API thread panicked :{:?}", e)).into())
+}
+
+/// Hostname a request was sent to, without the port
+fn request_host(req: &Request<Body>) -> Option<String> {
+	let host = req
+		.headers()
+		.get(hyper::header::HOST)
+		.and_then(|v| v.to_str().ok())
+		.or_else(|| req.uri().host())?;
+	let host = match host.starts_with('[') {
+		// IPv6 address, the port after the bracket
+		true => host.split(']').next().map(|h| format!("{}]", h))?,
+		false => host.split(':').next()?.to_owned(),
+	};
+	Some(host.to_lowercase())
+}
+
+/// Transport a foreign API request came over: Tor for the requests to the wallet's
+/// onion address, which the Tor listener forwards to the http one, else http
+fn request_transport(req: &Request<Body>) -> &'static str {
+	match request_host(req) {
+		Some(h) if h.ends_with(".onion") => "tor",
+		_ => "http",
+	}
+}
+
+/// Foreign API handler of the wallet served for a request's hostname, or of the
+/// listener's wallet for the other hostnames
+struct HostRouteHandler {
+	by_host: HashMap<String, api::HandlerObj>,
+	default: api::HandlerObj,
+}
+
+impl HostRouteHandler {
+	fn handler(&self, req: &Request<Body>) -> api::HandlerObj {
+		request_host(req)
+			.and_then(|h| self.by_host.get(&h).cloned())
+			.unwrap_or_else(|| self.default.clone())
+	}
+}
+
+impl api::Handler for HostRouteHandler {
+	fn post(&self, req: Request<Body>) -> ResponseFuture {
+		self.handler(&req).post(req)
+	}
+
+	fn options(&self, req: Request<Body>) -> ResponseFuture {
+		self.handler(&req).options(req)
+	}
 }
 
 type WalletResponseFuture = Box<dyn Future<Item = Response<Body>, Error = Error> + Send>;
 
+/// Default maximum size of a request body accepted by the listeners, in bytes
+pub const DEFAULT_MAX_REQUEST_BYTES: usize = 8 * 1024 * 1024;
+
+/// Default maximum number of calls in a JSON-RPC batch request
+pub const DEFAULT_MAX_BATCH_LEN: usize = 100;
+
+/// Limits on the requests served by the listeners, so gigantic slates or unbounded
+/// queries can't exhaust the resources of the wallet
+#[derive(Clone, Debug)]
+pub struct RequestLimits {
+	/// Maximum size of a request body, in bytes
+	pub max_body_bytes: usize,
+	/// Maximum number of calls in a JSON-RPC batch request
+	pub max_batch_len: usize,
+	/// Maximum number of entries returned by an owner API output or transaction
+	/// query, if limited
+	pub max_results: Option<usize>,
+	/// Methods the foreign API serves, and how often, if restricted
+	pub foreign_access: Option<Arc<ForeignAccess>>,
+}
+
+impl Default for RequestLimits {
+	fn default() -> RequestLimits {
+		RequestLimits {
+			max_body_bytes: DEFAULT_MAX_REQUEST_BYTES,
+			max_batch_len: DEFAULT_MAX_BATCH_LEN,
+			max_results: None,
+			foreign_access: None,
+		}
+	}
+}
+
+impl RequestLimits {
+	/// JSON-RPC error returned instead of handling a batch of more calls than allowed
+	fn check_batch_len(&self, val: &serde_json::Value) -> Result<(), serde_json::Value> {
+		match val.as_array() {
+			Some(calls) if calls.len() > self.max_batch_len => Err(serde_json::json!({
+				"jsonrpc": "2.0",
+				"id": null,
+				"error": {
+					"message": format!(
+						"Batch of {} calls is over the limit of {}",
+						calls.len(),
+						self.max_batch_len
+					),
+					"code": -32600
+				}
+			})),
+			_ => Ok(()),
+		}
+	}
+}
+
 /// V2 API Handler/Wrapper for owner functions
 pub struct OwnerAPIHandlerV2<L, C, K>
 where
@@ -292,6 +767,10 @@ where
 {
 	/// Wallet instance
 	pub wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	/// Whether sends require a second operator's approval
+	pub require_approval: bool,
+	/// Limits on the requests served
+	pub limits: RequestLimits,
 }
 
 impl<L, C, K> OwnerAPIHandlerV2<L, C, K>
@@ -303,8 +782,14 @@ where
 	/// Create a new owner API handler for GET methods
 	pub fn new(
 		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+		require_approval: bool,
+		limits: RequestLimits,
 	) -> OwnerAPIHandlerV2<L, C, K> {
-		OwnerAPIHandlerV2 { wallet }
+		OwnerAPIHandlerV2 {
+			wallet,
+			require_approval,
+			limits,
+		}
 	}
 
 	fn call_api(
@@ -312,9 +797,17 @@ where
 		req: Request<Body>,
 		api: Owner<L, C, K>,
 	) -> Box<dyn Future<Item = serde_json::Value, Error = Error> + Send> {
-		Box::new(parse_body(req).and_then(move |val: serde_json::Value| {
+		let limits = self.limits.clone();
+		let body = parse_body(req, limits.max_body_bytes);
+		Box::new(body.and_then(move |val: serde_json::Value| {
+			if let Err(e) = limits.check_batch_len(&val) {
+				return ok(e);
+			}
 			let owner_api = &api as &dyn OwnerRpc;
-			match owner_api.handle_request(val) {
+			let request = val.clone();
+			let reply = handle_timed_request("owner", owner_api, val);
+			OwnerV3Helpers::audit(&api, &request, &reply);
+			match reply {
 				MaybeReply::Reply(r) => ok(r),
 				MaybeReply::DontReply => {
 					// Since it's http, we need to return something. We return [] because jsonrpc
@@ -327,6 +820,8 @@ where
 
 	fn handle_post_request(&self, req: Request<Body>) -> WalletResponseFuture {
 		let api = Owner::new(self.wallet.clone(), None);
+		api.set_max_results(self.limits.max_results);
+		api.set_require_approval(self.require_approval);
 		Box::new(
 			self.call_api(req, api)
 				.and_then(|resp| ok(json_response_pretty(&resp))),
@@ -356,6 +851,102 @@ where
 	}
 }
 
+/// JSON-RPC error returned instead of handling the calls of a request a read-only
+/// token isn't allowed
+fn check_read_only(val: &serde_json::Value) -> Result<(), serde_json::Value> {
+	let calls: Vec<&serde_json::Value> = match val.as_array() {
+		Some(calls) => calls.iter().collect(),
+		None => vec![val],
+	};
+	for call in calls {
+		let method = call["method"].as_str().unwrap_or("");
+		if !TokenScope::ReadOnly.allows(method, None, false) {
+			return Err(serde_json::json!({
+				"jsonrpc": "2.0",
+				"id": call["id"].clone(),
+				"error": {
+					"message": format!("Method {} isn't allowed on a snapshot", method),
+					"code": -32004
+				}
+			}));
+		}
+	}
+	Ok(())
+}
+
+/// V2 API Handler of the owner queries on a snapshot
+pub struct SnapshotAPIHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Wallet instance, with the snapshot open
+	pub wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	/// Limits on the requests served
+	pub limits: RequestLimits,
+}
+
+impl<L, C, K> SnapshotAPIHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Create a new handler of the owner queries on a snapshot
+	pub fn new(
+		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+		limits: RequestLimits,
+	) -> SnapshotAPIHandler<L, C, K> {
+		SnapshotAPIHandler { wallet, limits }
+	}
+
+	fn handle_post_request(&self, req: Request<Body>) -> WalletResponseFuture {
+		let api = Owner::new(self.wallet.clone(), None);
+		api.set_max_results(self.limits.max_results);
+		let limits = self.limits.clone();
+		let body = parse_body(req, limits.max_body_bytes);
+		Box::new(
+			body.and_then(move |val: serde_json::Value| {
+				let res = limits
+					.check_batch_len(&val)
+					.and_then(|_| check_read_only(&val));
+				if let Err(e) = res {
+					return ok(e);
+				}
+				match handle_timed_request("owner", &api as &dyn OwnerRpc, val) {
+					MaybeReply::Reply(r) => ok(r),
+					// an empty batch response, as something has to be returned over http
+					MaybeReply::DontReply => ok(serde_json::json!([])),
+				}
+			})
+			.and_then(|resp| ok(json_response_pretty(&resp))),
+		)
+	}
+}
+
+impl<L, C, K> api::Handler for SnapshotAPIHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	fn post(&self, req: Request<Body>) -> ResponseFuture {
+		Box::new(
+			self.handle_post_request(req)
+				.and_then(|r| ok(r))
+				.or_else(|e| {
+					error!("Request Error: {:?}", e);
+					ok(create_error_response(e))
+				}),
+		)
+	}
+
+	fn options(&self, _req: Request<Body>) -> ResponseFuture {
+		Box::new(ok(create_ok_response("{}")))
+	}
+}
+
 /// V3 API Handler/Wrapper for owner functions, which include a secure
 /// mode + lifecycle functions
 pub struct OwnerAPIHandlerV3<L, C, K>
@@ -379,6 +970,12 @@ where
 	/// Whether we're running the foreign API on the same port, and therefore
 	/// have to store the mask in-process
 	pub running_foreign: bool,
+
+	/// Limits on the requests served
+	pub limits: RequestLimits,
+
+	/// Handles to the Owner APIs of the further wallets served, by name
+	wallets: Arc<HashMap<String, Arc<Owner<L, C, K>>>>,
 }
 
 pub struct OwnerV3Helpers;
@@ -408,6 +1005,69 @@ impl OwnerV3Helpers {
 		}
 	}
 
+	/// Take the name of the wallet the calls of a request are made to out of their
+	/// `wallet` member, if they give one. The calls of a batch must all be made to the
+	/// same wallet, the JSON-RPC error returned refusing the request otherwise.
+	pub fn take_wallet_name(
+		val: &mut serde_json::Value,
+	) -> Result<Option<String>, serde_json::Value> {
+		let id = match val.is_array() {
+			true => serde_json::Value::Null,
+			false => val["id"].clone(),
+		};
+		let calls: Vec<&mut serde_json::Value> = match val {
+			serde_json::Value::Array(c) => c.iter_mut().collect(),
+			v => vec![v],
+		};
+		let mut names = vec![];
+		for c in calls {
+			let name = match c.as_object_mut().and_then(|c| c.remove("wallet")) {
+				None | Some(serde_json::Value::Null) => None,
+				Some(serde_json::Value::String(n)) => Some(n),
+				Some(n) => {
+					let msg = format!("Invalid wallet name: {}", n);
+					return Err(wallet_error_response(id, msg));
+				}
+			};
+			names.push(name);
+		}
+		match names.first() {
+			Some(n) if names.iter().any(|o| o != n) => {
+				let msg = "The calls of a batch must be made to the same wallet".to_owned();
+				Err(wallet_error_response(id, msg))
+			}
+			Some(n) => Ok(n.clone()),
+			None => Ok(None),
+		}
+	}
+
+	/// Owner API of the wallet the calls of a request are made to, taking its name
+	/// out of them: `api`, the default wallet's, if they don't name one
+	pub fn wallet_owner_api<L, C, K>(
+		api: &Arc<Owner<L, C, K>>,
+		wallets: &HashMap<String, Arc<Owner<L, C, K>>>,
+		val: &mut serde_json::Value,
+	) -> Result<Arc<Owner<L, C, K>>, serde_json::Value>
+	where
+		L: WalletLCProvider<'static, C, K>,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
+		match OwnerV3Helpers::take_wallet_name(val)? {
+			None => Ok(api.clone()),
+			Some(n) => match wallets.get(&n) {
+				Some(w) => Ok(w.clone()),
+				None => {
+					let id = match val.is_array() {
+						true => serde_json::Value::Null,
+						false => val["id"].clone(),
+					};
+					Err(wallet_error_response(id, format!("Unknown wallet {}", n)))
+				}
+			},
+		}
+	}
+
 	/// Checks whether a request is an encrypted request
 	pub fn is_encrypted_request(val: &serde_json::Value) -> bool {
 		if let Some(m) = val["method"].as_str() {
@@ -420,6 +1080,148 @@ impl OwnerV3Helpers {
 		}
 	}
 
+	/// Replace a token issued by `open_wallet` with a scope in the calls of a request
+	/// by the keychain mask it stands for, returning the error response instead if the
+	/// token's scope doesn't allow the method called
+	pub fn apply_token_scope<L, C, K>(
+		api: &Owner<L, C, K>,
+		val: &mut serde_json::Value,
+	) -> Result<(), serde_json::Value>
+	where
+		L: WalletLCProvider<'static, C, K>,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
+		if let Some(calls) = val.as_array_mut() {
+			for c in calls.iter_mut() {
+				OwnerV3Helpers::apply_token_scope(api, c)?;
+			}
+			return Ok(());
+		}
+		let id = val["id"].clone();
+		let call = token_call(val);
+		let token = match &mut val["params"] {
+			serde_json::Value::Object(p) => p.get_mut("token"),
+			serde_json::Value::Array(p) => p.get_mut(0),
+			_ => None,
+		};
+		let token = match token {
+			Some(t) => t,
+			None => return Ok(()),
+		};
+		let (allowed, mask) = match token.as_str().and_then(|t| api.check_token_call(t, &call)) {
+			Some(c) => c,
+			None => return Ok(()),
+		};
+		if !allowed {
+			return Err(serde_json::json!({
+				"jsonrpc": "2.0",
+				"id": id,
+				"error": {
+					"message": format!("Method {} isn't allowed by the token's scope", call.method),
+					"code": -32004
+				}
+			}));
+		}
+		// approvals are made with the approver's own token
+		if call.method == "approve_tx" {
+			return Ok(());
+		}
+		*token = match mask {
+			Some(m) => serde_json::Value::String(to_hex(m.0.to_vec())),
+			None => serde_json::Value::Null,
+		};
+		Ok(())
+	}
+
+	/// Deduct the sends and finalizations of a request which succeeded, as their reply
+	/// gives, from the limits of the tokens issued by `open_wallet` they were made with
+	pub fn spend_token_limits<L, C, K>(
+		api: &Owner<L, C, K>,
+		request: &serde_json::Value,
+		reply: &MaybeReply,
+	) where
+		L: WalletLCProvider<'static, C, K>,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
+		let calls: Vec<&serde_json::Value> = match request.as_array() {
+			Some(c) => c.iter().collect(),
+			None => vec![request],
+		};
+		let replies: Vec<&serde_json::Value> = match reply {
+			MaybeReply::Reply(serde_json::Value::Array(r)) => r.iter().collect(),
+			MaybeReply::Reply(r) => vec![r],
+			MaybeReply::DontReply => vec![],
+		};
+		for call in calls {
+			let token = match param(call, "token", 0).and_then(|t| t.as_str()) {
+				Some(t) => t,
+				None => continue,
+			};
+			let res = match replies.iter().find(|r| r["id"] == call["id"]) {
+				Some(r) if !call["id"].is_null() => r["result"].get("Ok"),
+				_ => None,
+			};
+			let res = match res {
+				Some(r) => r,
+				None => continue,
+			};
+			let slate_id = res["id"]
+				.as_str()
+				.and_then(|id| uuid::Uuid::parse_str(id).ok());
+			let kernels = kernel_excesses(&res["tx"]);
+			match call["method"].as_str().unwrap_or("") {
+				"init_send_tx" | "queue_send" => {
+					let amount = param(call, "args", 1)
+						.and_then(|a| json_u64(&a["amount"]))
+						.unwrap_or(u64::max_value());
+					api.spend_token_limit(token, amount, slate_id, kernels);
+				}
+				"finalize_tx" => {
+					let amount = json_u64(&res["amount"]).unwrap_or(u64::max_value());
+					api.spend_token_limit(token, amount, slate_id, kernels);
+				}
+				_ => {}
+			}
+		}
+	}
+
+	/// Record the calls of a request changing the wallet in its audit log, with the
+	/// outcome their reply gives
+	pub fn audit<L, C, K>(api: &Owner<L, C, K>, request: &serde_json::Value, reply: &MaybeReply)
+	where
+		L: WalletLCProvider<'static, C, K>,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
+		let calls: Vec<&serde_json::Value> = match request.as_array() {
+			Some(c) => c.iter().collect(),
+			None => vec![request],
+		};
+		// a batch can be refused as a whole, with a single reply
+		let (replies, refused): (Vec<&serde_json::Value>, bool) = match reply {
+			MaybeReply::Reply(serde_json::Value::Array(r)) => (r.iter().collect(), false),
+			MaybeReply::Reply(r) => (vec![r], request.is_array()),
+			MaybeReply::DontReply => (vec![], false),
+		};
+		for call in calls {
+			let call_reply = match replies.iter().find(|r| r["id"] == call["id"]) {
+				_ if refused => replies.first().cloned(),
+				Some(r) if !call["id"].is_null() => Some(*r),
+				_ => None,
+			};
+			if let Some(entry) = AuditEntry::from_call(call, call_reply) {
+				if let Err(e) = api.record_audit_entry(&entry) {
+					error!(
+						"Unable to record a call of {} in the audit log: {}",
+						entry.method, e
+					);
+				}
+			}
+		}
+	}
+
 	/// whether encryption is enabled
 	pub fn encryption_enabled(key: Arc<Mutex<Option<SecretKey>>>) -> bool {
 		let share_key_ref = key.lock();
@@ -455,8 +1257,21 @@ impl OwnerV3Helpers {
 	}
 
 	/// Update the shared mask, in case of foreign API being run
-	pub fn update_mask(mask: Arc<Mutex<Option<SecretKey>>>, val: &serde_json::Value) {
+	pub fn update_mask<L, C, K>(
+		api: &Owner<L, C, K>,
+		mask: Arc<Mutex<Option<SecretKey>>>,
+		val: &serde_json::Value,
+	) where
+		L: WalletLCProvider<'static, C, K>,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
 		if let Some(key) = val["result"]["Ok"].as_str() {
+			// a scoped token stands for the wallet's mask
+			if let Some((_, m)) = api.token_scope(key) {
+				*mask.lock() = m;
+				return;
+			}
 			let key_bytes = match from_hex(key.to_owned()) {
 				Ok(k) => k,
 				Err(_) => return,
@@ -608,18 +1423,53 @@ where
 		keychain_mask: Arc<Mutex<Option<SecretKey>>>,
 		tor_config: Option<TorConfig>,
 		epicbox_config: Option<EpicboxConfig>,
+		send_timeout: Option<Duration>,
 		running_foreign: bool,
+		send_queue_workers: Option<usize>,
+		balance_alerts: Option<BalanceAlerts>,
+		maturity_watch: Option<MaturityWatch>,
+		tx_hook: Option<Arc<dyn TxHook>>,
+		password_policy: Option<PasswordPolicyConfig>,
+		open_throttle: Option<OpenThrottleConfig>,
+		require_approval: bool,
+		limits: RequestLimits,
+		wallets: HashMap<String, Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>>,
 	) -> OwnerAPIHandlerV3<L, C, K> {
-		let owner_api = Owner::new(wallet.clone(), None);
-		owner_api.set_tor_config(tor_config);
-		owner_api.set_epicbox_config(epicbox_config);
+		// the balance alerts and maturity watch, keeping the state of their wallet,
+		// are the default wallet's only
+		let new_owner_api = |wallet| {
+			let owner_api = Owner::new(wallet, None);
+			owner_api.set_tor_config(tor_config.clone());
+			owner_api.set_epicbox_config(epicbox_config.clone());
+			owner_api.set_send_timeout(send_timeout);
+			owner_api.set_tx_hook(tx_hook.clone());
+			owner_api.set_max_results(limits.max_results);
+			owner_api.set_password_policy(password_policy.clone());
+			owner_api.set_require_approval(require_approval);
+			if let Some(t) = open_throttle.as_ref() {
+				owner_api.set_open_throttle(t);
+			}
+			if let Some(workers) = send_queue_workers {
+				owner_api.set_send_queue_workers(workers);
+			}
+			owner_api
+		};
+		let owner_api = new_owner_api(wallet.clone());
+		owner_api.set_balance_alerts(balance_alerts);
+		owner_api.set_maturity_watch(maturity_watch);
 		let owner_api = Arc::new(owner_api);
+		let wallets = wallets
+			.into_iter()
+			.map(|(name, w)| (name, Arc::new(new_owner_api(w))))
+			.collect();
 		OwnerAPIHandlerV3 {
 			wallet,
 			owner_api,
 			shared_key: Arc::new(Mutex::new(None)),
 			keychain_mask,
 			running_foreign,
+			limits,
+			wallets: Arc::new(wallets),
 		}
 	}
 
@@ -628,12 +1478,17 @@ where
 		req: Request<Body>,
 		api: Arc<Owner<L, C, K>>,
 	) -> Box<dyn Future<Item = serde_json::Value, Error = Error> + Send> {
+		let wallets = self.wallets.clone();
 		let key = self.shared_key.clone();
 		let mask = self.keychain_mask.clone();
 		let running_foreign = self.running_foreign;
-		Box::new(parse_body(req).and_then(move |val: serde_json::Value| {
+		let limits = self.limits.clone();
+		let body = parse_body(req, limits.max_body_bytes);
+		Box::new(body.and_then(move |val: serde_json::Value| {
+			if let Err(e) = limits.check_batch_len(&val) {
+				return ok(e);
+			}
 			let mut val = val;
-			let owner_api_s = &*api as &dyn OwnerRpcS;
 			let mut is_init_secure_api = OwnerV3Helpers::is_init_secure_api(&val);
 			let mut was_encrypted = false;
 			let mut encrypted_req_id = RpcId::Integer(0);
@@ -650,17 +1505,42 @@ where
 					}
 				}
 				was_encrypted = true;
+				if let Err(e) = limits.check_batch_len(&val) {
+					return ok(e);
+				}
 			}
 			// check again, in case it was an encrypted call to init_secure_api
 			is_init_secure_api = OwnerV3Helpers::is_init_secure_api(&val);
 			// also need to intercept open/close wallet requests
 			let is_open_wallet = OwnerV3Helpers::is_open_wallet(&val);
-			match owner_api_s.handle_request(val) {
+			// the secure API is the listener's, set up by the default wallet
+			let target = match is_init_secure_api {
+				true => OwnerV3Helpers::take_wallet_name(&mut val).map(|_| api.clone()),
+				false => OwnerV3Helpers::wallet_owner_api(&api, &wallets, &mut val),
+			};
+			// the foreign API run alongside is the default wallet's
+			let is_open_wallet =
+				is_open_wallet && target.as_ref().map_or(false, |w| Arc::ptr_eq(w, &api));
+			// audited with the token the caller gave
+			let request = val.clone();
+			let reply = match target {
+				Ok(w) => {
+					let reply = match OwnerV3Helpers::apply_token_scope(&w, &mut val) {
+						Ok(()) => handle_timed_request("owner", &*w as &dyn OwnerRpcS, val),
+						Err(e) => MaybeReply::Reply(e),
+					};
+					OwnerV3Helpers::spend_token_limits(&w, &request, &reply);
+					OwnerV3Helpers::audit(&w, &request, &reply);
+					reply
+				}
+				Err(e) => MaybeReply::Reply(e),
+			};
+			match reply {
 				MaybeReply::Reply(mut r) => {
 					let (_was_error, unencrypted_intercept) =
 						OwnerV3Helpers::check_error_response(&r.clone());
 					if is_open_wallet && running_foreign {
-						OwnerV3Helpers::update_mask(mask, &r.clone());
+						OwnerV3Helpers::update_mask(&api, mask, &r.clone());
 					}
 					if was_encrypted {
 						let res = OwnerV3Helpers::encrypt_response(
@@ -733,6 +1613,15 @@ where
 	pub wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 	/// Keychain mask
 	pub keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+	/// Check applied to incoming slates before they're signed
+	pub receive_hook: Option<Arc<dyn ReceiveHook>>,
+	/// Limits on the requests served
+	pub limits: RequestLimits,
+	/// Whether responses to received slates are signed with the wallet's address key
+	pub sign_responses: bool,
+	/// Whether received slates must be signed by their sender, with the key of the
+	/// sender address of their payment proof
+	pub require_sender_signature: bool,
 }
 
 impl<L, C, K> ForeignAPIHandlerV2<L, C, K>
@@ -745,10 +1634,17 @@ where
 	pub fn new(
 		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 		keychain_mask: Arc<Mutex<Option<SecretKey>>>,
+		receive_hook: Option<Arc<dyn ReceiveHook>>,
+		limits: RequestLimits,
+		sign_responses: bool,
 	) -> ForeignAPIHandlerV2<L, C, K> {
 		ForeignAPIHandlerV2 {
 			wallet,
 			keychain_mask,
+			receive_hook,
+			limits,
+			sign_responses,
+			require_sender_signature: false,
 		}
 	}
 
@@ -757,10 +1653,44 @@ where
 		req: Request<Body>,
 		api: Foreign<'static, L, C, K>,
 	) -> Box<dyn Future<Item = serde_json::Value, Error = Error> + Send> {
-		Box::new(parse_body(req).and_then(move |val: serde_json::Value| {
+		let wallet = self.wallet.clone();
+		let mask = self.keychain_mask.lock().clone();
+		let limits = self.limits.clone();
+		let transport = request_transport(&req);
+		let sender_signature = match self.require_sender_signature {
+			true => Some(
+				req.headers()
+					.get(SENDER_SIGNATURE_HEADER)
+					.and_then(|v| v.to_str().ok())
+					.map(|s| s.to_owned()),
+			),
+			false => None,
+		};
+		let body = parse_body(req, limits.max_body_bytes);
+		Box::new(body.and_then(move |val: serde_json::Value| {
+			if let Err(e) = limits.check_batch_len(&val) {
+				return ok(e);
+			}
+			if let Some(access) = limits.foreign_access.as_ref() {
+				if let Err(e) = access.check(&val) {
+					return ok(e);
+				}
+			}
+			if let Some(sig) = sender_signature {
+				if let Err(e) = check_sender_signature(&val, sig.as_ref().map(|s| s.as_str())) {
+					return ok(e);
+				}
+			}
+			let is_slate_request =
+				val["method"] == "receive_tx" || val["method"] == "finalize_invoice_tx";
 			let foreign_api = &api as &dyn ForeignRpc;
-			match foreign_api.handle_request(val) {
-				MaybeReply::Reply(r) => ok(r),
+			match handle_timed_request("foreign", foreign_api, val.clone()) {
+				MaybeReply::Reply(r) => {
+					if is_slate_request {
+						record_foreign_slate(wallet, mask.as_ref(), transport, &val, &r);
+					}
+					ok(r)
+				}
 				MaybeReply::DontReply => {
 					// Since it's http, we need to return something. We return [] because jsonrpc
 					// clients will parse it as an empty batch response.
@@ -772,7 +1702,9 @@ where
 
 	fn handle_post_request(&self, req: Request<Body>) -> WalletResponseFuture {
 		let mask = self.keychain_mask.lock();
-		let api = Foreign::new(self.wallet.clone(), mask.clone(), Some(check_middleware));
+		let mut api = Foreign::new(self.wallet.clone(), mask.clone(), Some(check_middleware));
+		api.set_receive_hook(self.receive_hook.clone());
+		api.set_sign_responses(self.sign_responses);
 		Box::new(
 			self.call_api(req, api)
 				.and_then(|resp| ok(json_response_pretty(&resp))),
@@ -802,6 +1734,287 @@ where
 	}
 }
 
+/// How long a health check waits for a wallet busy with another request
+const HEALTH_WALLET_LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Maximum age of the node's tip for the node to be considered synced, on
+/// production chains
+const HEALTH_MAX_TIP_AGE_SECS: i64 = 3600;
+
+/// Result of a health check, returned as the body of the response
+#[derive(Serialize)]
+struct HealthStatus {
+	wallet_open: bool,
+	node_reachable: bool,
+	node_synced: bool,
+}
+
+/// Unauthenticated health check of a listener, for load balancers and probes.
+/// Responds 200 when the wallet is open and the node is reachable, and when
+/// readiness is checked, synced. Responds 503 otherwise.
+pub struct HealthHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Wallet instance
+	pub wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	/// Whether the node must also be synced
+	pub check_synced: bool,
+	/// Node client of the open wallet, used while the wallet is busy
+	node_client: Mutex<Option<C>>,
+}
+
+impl<L, C, K> HealthHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Create a new health check handler
+	pub fn new(
+		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+		check_synced: bool,
+	) -> HealthHandler<L, C, K> {
+		HealthHandler {
+			wallet,
+			check_synced,
+			node_client: Mutex::new(None),
+		}
+	}
+
+	fn status(&self) -> HealthStatus {
+		let mut status = HealthStatus {
+			wallet_open: false,
+			node_reachable: false,
+			node_synced: false,
+		};
+
+		// a wallet busy with another request is open, check the node it was last open with
+		if let Some(mut w_lock) = self.wallet.try_lock_for(HEALTH_WALLET_LOCK_TIMEOUT) {
+			let client = match w_lock.lc_provider().and_then(|lc| lc.wallet_inst()) {
+				Ok(w) => Some(w.w2n_client().clone()),
+				Err(_) => None,
+			};
+			*self.node_client.lock() = client;
+		}
+		let client = match self.node_client.lock().clone() {
+			Some(c) => c,
+			None => return status,
+		};
+		status.wallet_open = true;
+
+		let tip_height = match client.get_chain_tip() {
+			Ok((height, _)) => height,
+			Err(_) => return status,
+		};
+		status.node_reachable = true;
+
+		// the timestamps of test chains are meaningless
+		if !global::is_production_mode() {
+			status.node_synced = true;
+		} else if let Ok(Some(tip)) = client.get_header_info(tip_height) {
+			let age = Utc::now().signed_duration_since(tip.timestamp);
+			status.node_synced = age.num_seconds() < HEALTH_MAX_TIP_AGE_SECS;
+		}
+		status
+	}
+}
+
+impl<L, C, K> api::Handler for HealthHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	fn get(&self, _req: Request<Body>) -> ResponseFuture {
+		let status = self.status();
+		let healthy = status.wallet_open
+			&& status.node_reachable
+			&& (status.node_synced || !self.check_synced);
+		let code = match healthy {
+			true => StatusCode::OK,
+			false => StatusCode::SERVICE_UNAVAILABLE,
+		};
+		let body = serde_json::to_string(&status).unwrap_or_default();
+		Box::new(ok(response(code, body)))
+	}
+}
+
+/// Add the health check routes to the router of a listener
+fn add_health_routes<L, C, K>(
+	router: &mut Router,
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let health = HealthHandler::new(wallet.clone(), false);
+	let ready = HealthHandler::new(wallet, true);
+	router
+		.add_route("/health", Arc::new(health))
+		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+	router
+		.add_route("/ready", Arc::new(ready))
+		.map_err(|_| ErrorKind::GenericError("Router failed to add route".to_string()))?;
+	Ok(())
+}
+
+/// Record a slate received by the foreign listener, and the slate returned in
+/// the response, in the transport metrics. Slates received via TOR are
+/// forwarded to this listener and are therefore counted as http.
+/// Check the slates received in a request are signed by their sender, with the
+/// signature in the request's header, returning the error response otherwise
+fn check_sender_signature(
+	val: &serde_json::Value,
+	signature: Option<&str>,
+) -> Result<(), serde_json::Value> {
+	let calls: Vec<&serde_json::Value> = match val.as_array() {
+		Some(calls) => calls.iter().collect(),
+		None => vec![val],
+	};
+	for call in calls.into_iter().filter(|c| c["method"] == "receive_tx") {
+		let res: Result<(), Error> = signature
+			.ok_or_else(|| {
+				ErrorKind::SenderSignature("The sender didn't sign the slate".to_owned()).into()
+			})
+			.and_then(|s| {
+				serde_json::from_str::<ResponseSignature>(s).map_err(|_| {
+					ErrorKind::SenderSignature("Unreadable sender signature".to_owned()).into()
+				})
+			})
+			.and_then(|sig| {
+				let slate = Slate::deserialize_upgrade(&call["params"][0].to_string())?;
+				verify_sender_signature(&slate, &sig)
+			});
+		if let Err(e) = res {
+			warn!("Refused slate from an unauthenticated sender: {}", e);
+			return Err(serde_json::json!({
+				"jsonrpc": "2.0",
+				"id": call["id"],
+				"error": {
+					"message": format!("{}", e),
+					"code": -32600
+				}
+			}));
+		}
+	}
+	Ok(())
+}
+
+fn record_foreign_slate<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Option<&SecretKey>,
+	transport: &str,
+	req: &serde_json::Value,
+	res: &serde_json::Value,
+) where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let mut events = vec![TransportEvent::slate_received(&req["params"])];
+	if res["error"].is_null() && res["result"]["Err"].is_null() {
+		events.push(TransportEvent::slate_sent(&res["result"]["Ok"]));
+	} else {
+		events.push(TransportEvent::Failure);
+	}
+	for event in events {
+		if let Err(e) =
+			owner::record_transport_event(wallet.clone(), keychain_mask, transport, event)
+		{
+			warn!("Unable to record transport metrics: {}", e);
+		}
+	}
+}
+
+/// Parameter of a JSON-RPC call, given by name or at the position
+fn param<'a>(call: &'a serde_json::Value, name: &str, pos: usize) -> Option<&'a serde_json::Value> {
+	match &call["params"] {
+		serde_json::Value::Object(p) => p.get(name),
+		serde_json::Value::Array(p) => p.get(pos),
+		_ => None,
+	}
+}
+
+/// Amount given as a number or a string
+fn json_u64(val: &serde_json::Value) -> Option<u64> {
+	val.as_u64()
+		.or_else(|| val.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Excesses of the kernels of a transaction, leaving out the ones not yet signed
+fn kernel_excesses(tx: &serde_json::Value) -> Vec<String> {
+	tx["body"]["kernels"]
+		.as_array()
+		.map(|k| {
+			k.iter()
+				.filter_map(|k| k["excess"].as_str())
+				.filter(|e| e.chars().any(|c| c != '0'))
+				.map(|e| e.to_owned())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+/// Position of the `refresh_from_node` parameter of the owner API methods having one
+fn refresh_param_pos(method: &str) -> Option<usize> {
+	match method {
+		"retrieve_outputs" | "retrieve_outputs_page" => Some(2),
+		"retrieve_txs"
+		| "retrieve_txs_page"
+		| "retrieve_txs_by_tag"
+		| "retrieve_contact_txs"
+		| "retrieve_pending_receives"
+		| "fee_report"
+		| "retrieve_summary_info"
+		| "retrieve_payment_proof"
+		| "retrieve_payment_proofs" => Some(1),
+		_ => None,
+	}
+}
+
+/// A call of the owner API as checked against the scope of its token. Sends and
+/// finalizations are checked against the limit of their amount, refused if it's
+/// unreadable.
+fn token_call(call: &serde_json::Value) -> TokenCall {
+	let method = call["method"].as_str().unwrap_or("").to_owned();
+	let mut res = TokenCall {
+		method,
+		..Default::default()
+	};
+	match res.method.as_str() {
+		"init_send_tx" | "queue_send" => {
+			let amount = param(call, "args", 1).and_then(|a| json_u64(&a["amount"]));
+			res.amount = Some(amount.unwrap_or(u64::max_value()));
+		}
+		"finalize_tx" => {
+			let slate = param(call, "slate", 1);
+			let amount = slate.and_then(|s| json_u64(&s["amount"]));
+			res.amount = Some(amount.unwrap_or(u64::max_value()));
+			res.slate_id = slate
+				.and_then(|s| s["id"].as_str())
+				.and_then(|id| uuid::Uuid::parse_str(id).ok());
+		}
+		"post_tx" => {
+			res.kernels = param(call, "tx", 1)
+				.map(kernel_excesses)
+				.unwrap_or_default();
+		}
+		m => {
+			if let Some(pos) = refresh_param_pos(m) {
+				res.refresh_from_node = param(call, "refresh_from_node", pos)
+					.and_then(|r| r.as_bool())
+					.unwrap_or(false);
+			}
+		}
+	}
+	res
+}
+
 // Utility to serialize a struct into JSON and produce a sensible Response
 // out of it.
 fn _json_response<T>(s: &T) -> Response<Body>
@@ -826,8 +2039,12 @@ where
 }
 
 fn create_error_response(e: Error) -> Response<Body> {
+	let status = match e.kind() {
+		ErrorKind::RequestLimit(_) => StatusCode::PAYLOAD_TOO_LARGE,
+		_ => StatusCode::INTERNAL_SERVER_ERROR,
+	};
 	Response::builder()
-		.status(StatusCode::INTERNAL_SERVER_ERROR)
+		.status(status)
 		.header("access-control-allow-origin", "*")
 		.header(
 			"access-control-allow-headers",
@@ -872,15 +2089,50 @@ fn response<T: Into<Body>>(status: StatusCode, text: T) -> Response<Body> {
 	builder.body(text.into()).unwrap()
 }
 
-fn parse_body<T>(req: Request<Body>) -> Box<dyn Future<Item = T, Error = Error> + Send>
+/// JSON-RPC error refusing a request made to a wallet the owner API doesn't serve
+fn wallet_error_response(id: serde_json::Value, message: String) -> serde_json::Value {
+	serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": id,
+		"error": {
+			"message": message,
+			"code": -32006
+		}
+	})
+}
+
+fn body_too_large(max_bytes: usize) -> Error {
+	ErrorKind::RequestLimit(format!("body over the limit of {} bytes", max_bytes)).into()
+}
+
+/// Read and deserialize a request body, refusing bodies over `max_bytes` before
+/// buffering them entirely
+fn parse_body<T>(
+	req: Request<Body>,
+	max_bytes: usize,
+) -> Box<dyn Future<Item = T, Error = Error> + Send>
 where
 	for<'de> T: Deserialize<'de> + Send + 'static,
 {
+	let announced_len = req
+		.headers()
+		.get(hyper::header::CONTENT_LENGTH)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|v| v.parse::<usize>().ok());
+	if announced_len.map(|l| l > max_bytes).unwrap_or(false) {
+		return Box::new(err(body_too_large(max_bytes)));
+	}
 	Box::new(
 		req.into_body()
-			.concat2()
-			.map_err(|_| ErrorKind::GenericError("Failed to read request".to_owned()).into())
-			.and_then(|body| match serde_json::from_reader(&body.to_vec()[..]) {
+			.map_err(|_| Error::from(ErrorKind::GenericError("Failed to read request".to_owned())))
+			.fold(Vec::new(), move |mut body, chunk| {
+				if body.len() + chunk.len() > max_bytes {
+					return err(body_too_large(max_bytes));
+				}
+				body.extend_from_slice(&chunk);
+				ok(body)
+			})
+			.and_then(|body| match serde_json::from_slice(&body) {
 				Ok(obj) => ok(obj),
 				Err(e) => {
 					err(ErrorKind::GenericError(format!("Invalid request body: {}", e)).into())
@@ -0,0 +1,247 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-origin access to the API listeners by web frontends, and the address of
+//! the clients of listeners behind a reverse proxy
+
+use crate::api::{Handler, HandlerObj, ResponseFuture};
+use crate::config::WalletConfig;
+use futures::future::{ok, Future};
+use hyper::header::{HeaderMap, HeaderValue, ORIGIN, VARY};
+use hyper::{Body, Request, Response, StatusCode};
+
+/// Header of the addresses a request was forwarded for by proxies, the client's first
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Headers frontends can send
+const ALLOWED_HEADERS: &str = "Content-Type, Authorization";
+
+/// Methods frontends can call
+const ALLOWED_METHODS: &str = "GET, POST, OPTIONS";
+
+/// Sets the CORS headers of the responses for the origins allowed, and only them,
+/// answering the preflight requests itself
+#[derive(Clone)]
+pub struct CorsMiddleware {
+	/// Allowed origins, "*" allowing any
+	allowed_origins: Vec<String>,
+	/// Response headers frontends can read
+	exposed_headers: Vec<String>,
+	/// Whether the client is the first address of `X-Forwarded-For`
+	behind_proxy: bool,
+}
+
+impl CorsMiddleware {
+	/// Create the middleware for the origins and headers given
+	pub fn new(
+		allowed_origins: Vec<String>,
+		exposed_headers: Vec<String>,
+		behind_proxy: bool,
+	) -> CorsMiddleware {
+		CorsMiddleware {
+			allowed_origins,
+			exposed_headers,
+			behind_proxy,
+		}
+	}
+
+	/// The middleware of the wallet configuration, if it sets the origins, headers or
+	/// proxy of the listeners
+	pub fn from_config(config: &WalletConfig) -> Option<CorsMiddleware> {
+		if config.api_allowed_origins.is_none()
+			&& config.api_exposed_headers.is_none()
+			&& config.api_behind_proxy.is_none()
+		{
+			return None;
+		}
+		Some(CorsMiddleware::new(
+			config
+				.api_allowed_origins
+				.clone()
+				.unwrap_or_else(|| vec!["*".to_owned()]),
+			config.api_exposed_headers.clone().unwrap_or_default(),
+			config.api_behind_proxy.unwrap_or(false),
+		))
+	}
+
+	/// Value of the allow origin header for a request's origin, if it's allowed
+	fn allow_origin(&self, origin: Option<&str>) -> Option<String> {
+		if self.allowed_origins.iter().any(|o| o == "*") {
+			return Some("*".to_owned());
+		}
+		origin
+			.filter(|o| self.allowed_origins.iter().any(|a| a == o))
+			.map(|o| o.to_owned())
+	}
+
+	/// Address of the client, as given by the proxy if behind one
+	fn client(&self, req: &Request<Body>) -> Option<String> {
+		if !self.behind_proxy {
+			return None;
+		}
+		req.headers()
+			.get(X_FORWARDED_FOR)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| v.split(',').next())
+			.map(|c| c.trim().to_owned())
+			.filter(|c| !c.is_empty())
+	}
+
+	/// Replace the CORS headers of a response with those for the allowed origin
+	fn set_headers(&self, headers: &mut HeaderMap, allow_origin: Option<&str>) {
+		let cors_headers: Vec<_> = headers
+			.keys()
+			.filter(|k| k.as_str().starts_with("access-control-"))
+			.cloned()
+			.collect();
+		for k in cors_headers {
+			headers.remove(k);
+		}
+		let origin = match allow_origin {
+			Some(o) => o,
+			None => return,
+		};
+		let mut set = |name: &'static str, value: &str| {
+			if let Ok(v) = HeaderValue::from_str(value) {
+				headers.insert(name, v);
+			}
+		};
+		set("access-control-allow-origin", origin);
+		set("access-control-allow-headers", ALLOWED_HEADERS);
+		set("access-control-allow-methods", ALLOWED_METHODS);
+		if !self.exposed_headers.is_empty() {
+			set(
+				"access-control-expose-headers",
+				&self.exposed_headers.join(", "),
+			);
+		}
+		// the API secret is sent by browsers to the listed origins only
+		if origin != "*" {
+			set("access-control-allow-credentials", "true");
+			headers.insert(VARY, HeaderValue::from_static("Origin"));
+		}
+	}
+}
+
+impl Handler for CorsMiddleware {
+	fn call(
+		&self,
+		req: Request<Body>,
+		mut handlers: Box<dyn Iterator<Item = HandlerObj>>,
+	) -> ResponseFuture {
+		let origin = req
+			.headers()
+			.get(ORIGIN)
+			.and_then(|v| v.to_str().ok())
+			.map(|o| o.to_owned());
+		let allow_origin = self.allow_origin(origin.as_ref().map(|o| o.as_str()));
+		match self.client(&req) {
+			Some(c) => debug!("{} {} from {}", req.method(), req.uri().path(), c),
+			None => debug!("{} {}", req.method(), req.uri().path()),
+		}
+		if let (Some(o), None) = (origin.as_ref(), allow_origin.as_ref()) {
+			warn!(
+				"Request from origin {} not allowed to call the API{}",
+				o,
+				self.client(&req)
+					.map(|c| format!(", client {}", c))
+					.unwrap_or_default()
+			);
+		}
+
+		// preflight requests are answered here, the browser not sending the
+		// authorization with them
+		if req.method().as_str() == "OPTIONS" {
+			let status = match allow_origin {
+				Some(_) => StatusCode::OK,
+				None => StatusCode::FORBIDDEN,
+			};
+			let mut response = Response::builder()
+				.status(status)
+				.body(Body::empty())
+				.unwrap();
+			self.set_headers(
+				response.headers_mut(),
+				allow_origin.as_ref().map(|o| o.as_str()),
+			);
+			return Box::new(ok(response));
+		}
+
+		let next_handler = match handlers.next() {
+			Some(h) => h,
+			None => {
+				let response = Response::builder()
+					.status(StatusCode::INTERNAL_SERVER_ERROR)
+					.body(Body::from("no handler found"))
+					.unwrap();
+				return Box::new(ok(response));
+			}
+		};
+		let cors = self.clone();
+		Box::new(next_handler.call(req, handlers).map(move |mut response| {
+			cors.set_headers(
+				response.headers_mut(),
+				allow_origin.as_ref().map(|o| o.as_str()),
+			);
+			response
+		}))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn cors_headers() {
+		let cors = CorsMiddleware::new(
+			vec!["https://wallet.example.com".to_owned()],
+			vec!["Content-Length".to_owned()],
+			true,
+		);
+		assert_eq!(
+			cors.allow_origin(Some("https://wallet.example.com")),
+			Some("https://wallet.example.com".to_owned())
+		);
+		assert_eq!(cors.allow_origin(Some("https://evil.example.com")), None);
+		assert_eq!(cors.allow_origin(None), None);
+
+		let mut headers = HeaderMap::new();
+		headers.insert("access-control-allow-origin", HeaderValue::from_static("*"));
+		cors.set_headers(&mut headers, Some("https://wallet.example.com"));
+		assert_eq!(
+			headers["access-control-allow-origin"],
+			"https://wallet.example.com"
+		);
+		assert_eq!(headers["access-control-expose-headers"], "Content-Length");
+		assert_eq!(headers["access-control-allow-credentials"], "true");
+
+		// the listeners' default headers are removed for other origins
+		cors.set_headers(&mut headers, None);
+		assert!(headers.get("access-control-allow-origin").is_none());
+
+		let req = Request::builder()
+			.header(X_FORWARDED_FOR, "203.0.113.7, 10.0.0.1")
+			.body(Body::empty())
+			.unwrap();
+		assert_eq!(cors.client(&req), Some("203.0.113.7".to_owned()));
+
+		let any = CorsMiddleware::new(vec!["*".to_owned()], vec![], false);
+		assert_eq!(
+			any.allow_origin(Some("https://other.example.com")),
+			Some("*".to_owned())
+		);
+		assert_eq!(any.client(&req), None);
+	}
+}
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::core::core::{self, amount_to_hr_string};
-use crate::core::global;
+use crate::command::SlateChange;
+use crate::config::ConfigSource;
+use crate::core::core::{KernelFeatures, OutputFeatures};
+use crate::i18n;
 use crate::libwallet::{
-	address, AcctPathMapping, Error, OutputCommitMapping, OutputStatus, TxLogEntry, WalletInfo,
+	address, coinbase_maturity, AcctPathMapping, Contact, Error, ErrorKind, FeeReport,
+	OutputCommitMapping, OutputStatus, PaymentProofState, ScanPreview, SendTemplate, Slate,
+	TxBulkResult, TxEstimate, TxLogEntry, TxLogEntryType, WalletInfo,
 };
 use crate::util;
 use prettytable;
 use std::io::prelude::Write;
+use std::path::Path;
 use term;
 
 /// Display outputs in a pretty way
@@ -30,13 +35,13 @@ pub fn outputs(
 	outputs: Vec<OutputCommitMapping>,
 	dark_background_color_scheme: bool,
 ) -> Result<(), Error> {
-	let title = format!(
-		"Wallet Outputs - Account '{}' - Block Height: {}",
-		account, cur_height
+	let title = i18n::tr_args(
+		"outputs.title",
+		&[("account", &account), ("height", &cur_height)],
 	);
 	println!();
 	if term::stdout().is_none() {
-		println!("Could not open terminal");
+		println!("{}", i18n::tr("term.unavailable"));
 		return Ok(());
 	}
 	let mut t = term::stdout().unwrap();
@@ -47,35 +52,37 @@ pub fn outputs(
 	let mut table = table!();
 
 	table.set_titles(row![
-		bMG->"Output Commitment",
-		bMG->"MMR Index",
-		bMG->"Block Height",
-		bMG->"Locked Until",
-		bMG->"Status",
-		bMG->"Coinbase?",
-		bMG->"# Confirms",
-		bMG->"Value",
-		bMG->"Tx"
+		bMG->i18n::tr("outputs.commitment"),
+		bMG->i18n::tr("outputs.mmr_index"),
+		bMG->i18n::tr("outputs.block_height"),
+		bMG->i18n::tr("outputs.locked_until"),
+		bMG->i18n::tr("outputs.status"),
+		bMG->i18n::tr("outputs.coinbase"),
+		bMG->i18n::tr("outputs.confirms"),
+		bMG->i18n::tr("outputs.value"),
+		bMG->i18n::tr("outputs.tx")
 	]);
 
 	for m in outputs {
 		let commit = format!("{}", util::to_hex(m.commit.as_ref().to_vec()));
 		let index = match m.output.mmr_index {
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
 			Some(t) => t.to_string(),
 		};
 		let height = format!("{}", m.output.height);
 		let lock_height = format!("{}", m.output.lock_height);
 		let is_coinbase = format!("{}", m.output.is_coinbase);
 
-		// Mark unconfirmed coinbase outputs as "Mining" instead of "Unconfirmed"
+		// Mark unconfirmed coinbase outputs as "Mining" instead of "Unconfirmed",
+		// and unspent outputs locked against spending as "Frozen"
 		let status = match m.output.status {
-			OutputStatus::Unconfirmed if m.output.is_coinbase => "Mining".to_string(),
+			OutputStatus::Unconfirmed if m.output.is_coinbase => i18n::tr("outputs.mining"),
+			OutputStatus::Unspent if m.output.frozen => i18n::tr("outputs.frozen"),
 			_ => format!("{}", m.output.status),
 		};
 
 		let num_confirmations = format!("{}", m.output.num_confirmations(cur_height));
-		let value = format!("{}", core::amount_to_hr_string(m.output.value, false));
+		let value = format!("{}", i18n::amount(m.output.value, false));
 		let tx = match m.output.tx_log_entry {
 			None => "".to_owned(),
 			Some(t) => t.to_string(),
@@ -113,11 +120,7 @@ pub fn outputs(
 	println!();
 
 	if !validated {
-		println!(
-			"\nWARNING: Wallet failed to verify data. \
-			 The above is from local cache and possibly invalid! \
-			 (is your `epic server` offline or broken?)"
-		);
+		println!("\n{}", i18n::tr("warning.unverified"));
 	}
 	Ok(())
 }
@@ -131,13 +134,13 @@ pub fn txs(
 	include_status: bool,
 	dark_background_color_scheme: bool,
 ) -> Result<(), Error> {
-	let title = format!(
-		"Transaction Log - Account '{}' - Block Height: {}",
-		account, cur_height
+	let title = i18n::tr_args(
+		"txs.title",
+		&[("account", &account), ("height", &cur_height)],
 	);
 	println!();
 	if term::stdout().is_none() {
-		println!("Could not open terminal");
+		println!("{}", i18n::tr("term.unavailable"));
 		return Ok(());
 	}
 	let mut t = term::stdout().unwrap();
@@ -148,68 +151,75 @@ pub fn txs(
 	let mut table = table!();
 
 	table.set_titles(row![
-		bMG->"Id",
-		bMG->"Type",
-		bMG->"Shared Transaction Id",
-		bMG->"Creation Time",
-		bMG->"TTL Cutoff Height",
-		bMG->"Confirmed?",
-		bMG->"Confirmation Time",
-		bMG->"Num. \nInputs",
-		bMG->"Num. \nOutputs",
-		bMG->"Amount \nCredited",
-		bMG->"Amount \nDebited",
-		bMG->"Fee",
-		bMG->"Net \nDifference",
-		bMG->"Payment \nProof",
-		bMG->"Kernel",
-		bMG->"Tx \nData",
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("txs.type"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("txs.creation_time"),
+		bMG->i18n::tr("txs.ttl_cutoff"),
+		bMG->i18n::tr("txs.confirmed"),
+		bMG->i18n::tr("txs.confirmation_time"),
+		bMG->i18n::tr("txs.num_inputs"),
+		bMG->i18n::tr("txs.num_outputs"),
+		bMG->i18n::tr("txs.credited"),
+		bMG->i18n::tr("txs.debited"),
+		bMG->i18n::tr("txs.fee"),
+		bMG->i18n::tr("txs.net_difference"),
+		bMG->i18n::tr("txs.payment_proof"),
+		bMG->i18n::tr("txs.kernel"),
+		bMG->i18n::tr("txs.tx_data"),
+		bMG->i18n::tr("txs.tags"),
+		bMG->i18n::tr("txs.contact"),
 	]);
 
 	for t in txs {
 		let id = format!("{}", t.id);
 		let slate_id = match t.tx_slate_id {
 			Some(m) => format!("{}", m),
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
 		};
 		let entry_type = format!("{}", t.tx_type);
-		let creation_ts = format!("{}", t.creation_ts.format("%Y-%m-%d %H:%M:%S"));
+		let creation_ts = i18n::date(&t.creation_ts);
 		let ttl_cutoff_height = match t.ttl_cutoff_height {
 			Some(b) => format!("{}", b),
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
 		};
 		let confirmation_ts = match t.confirmation_ts {
-			Some(m) => format!("{}", m.format("%Y-%m-%d %H:%M:%S")),
-			None => "None".to_owned(),
+			Some(m) => i18n::date(&m),
+			None => i18n::tr("none"),
 		};
 		let confirmed = format!("{}", t.confirmed);
 		let num_inputs = format!("{}", t.num_inputs);
 		let num_outputs = format!("{}", t.num_outputs);
-		let amount_debited_str = core::amount_to_hr_string(t.amount_debited, true);
-		let amount_credited_str = core::amount_to_hr_string(t.amount_credited, true);
+		let amount_debited_str = i18n::amount(t.amount_debited, true);
+		let amount_credited_str = i18n::amount(t.amount_credited, true);
 		let fee = match t.fee {
-			Some(f) => format!("{}", core::amount_to_hr_string(f, true)),
-			None => "None".to_owned(),
+			Some(f) => format!("{}", i18n::amount(f, true)),
+			None => i18n::tr("none"),
 		};
 		let net_diff = if t.amount_credited >= t.amount_debited {
-			core::amount_to_hr_string(t.amount_credited - t.amount_debited, true)
+			i18n::amount(t.amount_credited - t.amount_debited, true)
 		} else {
 			format!(
 				"-{}",
-				core::amount_to_hr_string(t.amount_debited - t.amount_credited, true)
+				i18n::amount(t.amount_debited - t.amount_credited, true)
 			)
 		};
 		let tx_data = match t.stored_tx {
-			Some(_) => "Yes".to_owned(),
-			None => "None".to_owned(),
+			Some(_) => i18n::tr("yes"),
+			None => i18n::tr("none"),
 		};
 		let kernel_excess = match t.kernel_excess {
 			Some(e) => util::to_hex(e.0.to_vec()),
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
+		};
+		let payment_proof = match t.payment_proof_state() {
+			Some(s) => payment_proof_state(s),
+			None => i18n::tr("none"),
 		};
-		let payment_proof = match t.payment_proof {
-			Some(_) => "Yes".to_owned(),
-			None => "None".to_owned(),
+		let tags = t.tags.join(", ");
+		let contact = match t.contact.as_ref() {
+			Some(c) => c.clone(),
+			None => i18n::tr("none"),
 		};
 		if dark_background_color_scheme {
 			table.add_row(row![
@@ -229,6 +239,8 @@ pub fn txs(
 				bfG->payment_proof,
 				bFB->kernel_excess,
 				bFb->tx_data,
+				bFC->tags,
+				bFC->contact,
 			]);
 		} else {
 			if t.confirmed {
@@ -248,6 +260,8 @@ pub fn txs(
 					bfG->payment_proof,
 					bFB->kernel_excess,
 					bFB->tx_data,
+					bFD->tags,
+					bFD->contact,
 				]);
 			} else {
 				table.add_row(row![
@@ -266,6 +280,8 @@ pub fn txs(
 					bfG->payment_proof,
 					bFB->kernel_excess,
 					bFB->tx_data,
+					bFD->tags,
+					bFD->contact,
 				]);
 			}
 		}
@@ -275,15 +291,32 @@ pub fn txs(
 	table.printstd();
 	println!();
 
-	if !validated && include_status {
+	let unsigned_proofs = txs
+		.iter()
+		.filter(|t| t.tx_type == TxLogEntryType::TxSent)
+		.filter(|t| t.payment_proof_state() == Some(PaymentProofState::Requested))
+		.count();
+	if unsigned_proofs > 0 {
 		println!(
-			"\nWARNING: Wallet failed to verify data. \
-			 The above is from local cache and possibly invalid! \
-			 (is your `epic server` offline or broken?)"
+			"{}",
+			i18n::tr_args("txs.proofs_requested", &[("count", &unsigned_proofs)])
 		);
 	}
+
+	if !validated && include_status {
+		println!("\n{}", i18n::tr("warning.unverified"));
+	}
 	Ok(())
 }
+
+fn payment_proof_state(state: PaymentProofState) -> String {
+	match state {
+		PaymentProofState::Requested => i18n::tr("proof_state.requested"),
+		PaymentProofState::Promised => i18n::tr("proof_state.promised"),
+		PaymentProofState::Received => i18n::tr("proof_state.received"),
+		PaymentProofState::Verified => i18n::tr("proof_state.verified"),
+	}
+}
 /// Display summary info in a pretty way
 pub fn info(
 	account: &str,
@@ -292,122 +325,144 @@ pub fn info(
 	dark_background_color_scheme: bool,
 ) {
 	println!(
-		"\n____ Wallet Summary Info - Account '{}' as of height {} ____\n",
-		account, wallet_info.last_confirmed_height,
+		"\n____ {} ____\n",
+		i18n::tr_args(
+			"info.title",
+			&[
+				("account", &account),
+				("height", &wallet_info.last_confirmed_height),
+				("unit", &i18n::unit().symbol())
+			],
+		)
 	);
 
 	let mut table = table!();
 
 	if dark_background_color_scheme {
 		table.add_row(row![
-			bFG->"Confirmed Total",
-			FG->amount_to_hr_string(wallet_info.total, false)
+			bFG->i18n::tr("info.confirmed_total"),
+			FG->i18n::amount(wallet_info.total, false)
 		]);
 		// Only dispay "Immature Coinbase" if we have related outputs in the wallet.
 		// This row just introduces confusion if the wallet does not receive coinbase rewards.
 		if wallet_info.amount_immature > 0 {
 			table.add_row(row![
-				bFY->format!("Immature Coinbase (< {})", global::coinbase_maturity()),
-				FY->amount_to_hr_string(wallet_info.amount_immature, false)
+				bFY->i18n::tr_args("info.immature", &[("maturity", &coinbase_maturity())]),
+				FY->i18n::amount(wallet_info.amount_immature, false)
 			]);
 		}
 		table.add_row(row![
-			bFY->format!("Awaiting Confirmation (< {})", wallet_info.minimum_confirmations),
-			FY->amount_to_hr_string(wallet_info.amount_awaiting_confirmation, false)
+			bFY->i18n::tr_args(
+				"info.awaiting_confirmation",
+				&[("confirmations", &wallet_info.minimum_confirmations)]
+			),
+			FY->i18n::amount(wallet_info.amount_awaiting_confirmation, false)
 		]);
 		table.add_row(row![
-			bFB->format!("Awaiting Finalization"),
-			FB->amount_to_hr_string(wallet_info.amount_awaiting_finalization, false)
+			bFB->i18n::tr("info.awaiting_finalization"),
+			FB->i18n::amount(wallet_info.amount_awaiting_finalization, false)
 		]);
 		table.add_row(row![
-			Fr->"Locked by previous transaction",
-			Fr->amount_to_hr_string(wallet_info.amount_locked, false)
+			Fr->i18n::tr("info.locked"),
+			Fr->i18n::amount(wallet_info.amount_locked, false)
 		]);
 		table.add_row(row![
 			Fw->"--------------------------------",
 			Fw->"-------------"
 		]);
 		table.add_row(row![
-			bFG->"Currently Spendable",
-			FG->amount_to_hr_string(wallet_info.amount_currently_spendable, false)
+			bFG->i18n::tr("info.spendable"),
+			FG->i18n::amount(wallet_info.amount_currently_spendable, false)
 		]);
 	} else {
 		table.add_row(row![
-			bFG->"Total",
-			FG->amount_to_hr_string(wallet_info.total, false)
+			bFG->i18n::tr("info.total"),
+			FG->i18n::amount(wallet_info.total, false)
 		]);
 		// Only dispay "Immature Coinbase" if we have related outputs in the wallet.
 		// This row just introduces confusion if the wallet does not receive coinbase rewards.
 		if wallet_info.amount_immature > 0 {
 			table.add_row(row![
-				bFB->format!("Immature Coinbase (< {})", global::coinbase_maturity()),
-				FB->amount_to_hr_string(wallet_info.amount_immature, false)
+				bFB->i18n::tr_args("info.immature", &[("maturity", &coinbase_maturity())]),
+				FB->i18n::amount(wallet_info.amount_immature, false)
 			]);
 		}
 		table.add_row(row![
-			bFB->format!("Awaiting Confirmation (< {})", wallet_info.minimum_confirmations),
-			FB->amount_to_hr_string(wallet_info.amount_awaiting_confirmation, false)
+			bFB->i18n::tr_args(
+				"info.awaiting_confirmation",
+				&[("confirmations", &wallet_info.minimum_confirmations)]
+			),
+			FB->i18n::amount(wallet_info.amount_awaiting_confirmation, false)
 		]);
 		table.add_row(row![
-			Fr->"Locked by previous transaction",
-			Fr->amount_to_hr_string(wallet_info.amount_locked, false)
+			Fr->i18n::tr("info.locked"),
+			Fr->i18n::amount(wallet_info.amount_locked, false)
 		]);
 		table.add_row(row![
 			Fw->"--------------------------------",
 			Fw->"-------------"
 		]);
 		table.add_row(row![
-			bFG->"Currently Spendable",
-			FG->amount_to_hr_string(wallet_info.amount_currently_spendable, false)
+			bFG->i18n::tr("info.spendable"),
+			FG->i18n::amount(wallet_info.amount_currently_spendable, false)
 		]);
 	};
 	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
 	table.printstd();
 	println!();
 	if !validated {
-		println!(
-			"\nWARNING: Wallet failed to verify data against a live chain. \
-			 The above is from local cache and only valid up to the given height! \
-			 (is your `epic server` offline or broken?)"
-		);
+		println!("\n{}", i18n::tr("warning.unverified_height"));
 	}
 }
 
 /// Display summary info in a pretty way
 pub fn estimate(
 	amount: u64,
-	strategies: Vec<(
-		&str, // strategy
-		u64,  // total amount to be locked
-		u64,  // fee
-	)>,
+	strategies: Vec<(&str, TxEstimate)>, // estimate of each selection strategy
 	dark_background_color_scheme: bool,
 ) {
 	println!(
-		"\nEstimation for sending {}:\n",
-		amount_to_hr_string(amount, false)
+		"\n{}\n",
+		i18n::tr_args(
+			"estimate.title",
+			&[
+				("amount", &i18n::amount(amount, false)),
+				("unit", &i18n::unit().symbol())
+			]
+		)
 	);
 
 	let mut table = table!();
 
 	table.set_titles(row![
-		bMG->"Selection strategy",
-		bMG->"Fee",
-		bMG->"Will be locked",
+		bMG->i18n::tr("estimate.strategy"),
+		bMG->i18n::tr("estimate.fee"),
+		bMG->i18n::tr("estimate.locked"),
+		bMG->i18n::tr("estimate.inputs"),
+		bMG->i18n::tr("estimate.change"),
 	]);
 
-	for (strategy, total, fee) in strategies {
+	for (strategy, e) in strategies {
+		let change = format!(
+			"{} ({})",
+			i18n::amount(e.change, false),
+			e.num_change_outputs
+		);
 		if dark_background_color_scheme {
 			table.add_row(row![
 				bFC->strategy,
-				FR->amount_to_hr_string(fee, false),
-				FY->amount_to_hr_string(total, false),
+				FR->i18n::amount(e.fee, false),
+				FY->i18n::amount(e.total, false),
+				FC->e.inputs.len(),
+				FG->change,
 			]);
 		} else {
 			table.add_row(row![
 				bFD->strategy,
-				FR->amount_to_hr_string(fee, false),
-				FY->amount_to_hr_string(total, false),
+				FR->i18n::amount(e.fee, false),
+				FY->i18n::amount(e.total, false),
+				FD->e.inputs.len(),
+				FG->change,
 			]);
 		}
 	}
@@ -417,30 +472,716 @@ pub fn estimate(
 
 /// Display list of wallet accounts in a pretty way
 pub fn accounts(acct_mappings: Vec<AcctPathMapping>) {
-	println!("\n____ Wallet Accounts ____\n",);
+	println!("\n____ {} ____\n", i18n::tr("accounts.title"));
 	let mut table = table!();
 
 	table.set_titles(row![
-		mMG->"Name",
-		bMG->"Parent BIP-32 Derivation Path",
+		mMG->i18n::tr("accounts.name"),
+		bMG->i18n::tr("accounts.path"),
+		bMG->i18n::tr("accounts.metadata"),
 	]);
 	for m in acct_mappings {
+		let metadata: Vec<String> = m
+			.metadata
+			.iter()
+			.map(|(k, v)| format!("{}={}", k, v))
+			.collect();
 		table.add_row(row![
 			bFC->m.label,
 			bGC->m.path.to_bip_32_string(),
+			bFD->metadata.join(", "),
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the address book
+pub fn contacts(contacts: &[Contact]) {
+	println!("\n____ {} ____\n", i18n::tr("contacts.title"));
+	if contacts.is_empty() {
+		println!("{}\n", i18n::tr("none"));
+		return;
+	}
+	let mut table = table!();
+
+	table.set_titles(row![
+		mMG->i18n::tr("contacts.name"),
+		bMG->i18n::tr("contacts.proof_address"),
+		bMG->i18n::tr("contacts.epicbox_address"),
+		bMG->i18n::tr("contacts.transports"),
+	]);
+	for c in contacts {
+		let epicbox_address = match c.epicbox_address.as_ref() {
+			Some(a) => a.clone(),
+			None => i18n::tr("none"),
+		};
+		let transports: Vec<String> = c.transports().into_iter().map(|(t, _)| t).collect();
+		table.add_row(row![
+			bFC->c.name,
+			bGC->c.proof_address,
+			bFD->epicbox_address,
+			bFD->transports.join(", "),
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the saved send templates
+pub fn send_templates(templates: &[SendTemplate]) {
+	println!("\n____ {} ____\n", i18n::tr("templates.title"));
+	if templates.is_empty() {
+		println!("{}\n", i18n::tr("none"));
+		return;
+	}
+	let mut table = table!();
+
+	table.set_titles(row![
+		mMG->i18n::tr("templates.name"),
+		bMG->i18n::tr("templates.method"),
+		bMG->i18n::tr("templates.dest"),
+		bMG->i18n::tr("templates.amount"),
+		bMG->i18n::tr("templates.account"),
+		bMG->i18n::tr("templates.message"),
+	]);
+	for t in templates {
+		let account = match t.src_acct_name.as_ref() {
+			Some(a) => a.clone(),
+			None => i18n::tr("templates.active_account"),
+		};
+		table.add_row(row![
+			bFC->t.name,
+			bFD->t.method,
+			bGC->t.dest,
+			bFY->i18n::amount(t.amount, true),
+			bFD->account,
+			bFD->t.message.clone().unwrap_or_default(),
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the received transactions not seen on chain yet, with the time they
+/// expire at if an expiry window is set
+pub fn pending_receives(txs: &[TxLogEntry], expiry_hours: Option<u64>, validated: bool) {
+	println!("\n____ {} ____\n", i18n::tr("pending.title"));
+	if txs.is_empty() {
+		println!("{}\n", i18n::tr("none"));
+		return;
+	}
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("txs.creation_time"),
+		bMG->i18n::tr("txs.credited"),
+		bMG->i18n::tr("pending.expires"),
+	]);
+	for t in txs {
+		let slate_id = match t.tx_slate_id {
+			Some(s) => format!("{}", s),
+			None => i18n::tr("none"),
+		};
+		let expires = match expiry_hours {
+			Some(h) => i18n::date(&(t.creation_ts + chrono::Duration::hours(h as i64))),
+			None => i18n::tr("pending.never"),
+		};
+		table.add_row(row![
+			bFC->t.id,
+			bFC->slate_id,
+			bFB->i18n::date(&t.creation_ts),
+			bFG->i18n::amount(t.amount_credited, true),
+			bFD->expires,
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+	if !validated {
+		println!("\n{}", i18n::tr("warning.unverified"));
+	}
+}
+
+/// Display the fees paid from each account per period, and the transactions that
+/// paid the largest fees
+pub fn fee_report(report: &FeeReport, validated: bool) {
+	println!(
+		"\n____ {} ____\n",
+		i18n::tr_args("fees.title", &[("unit", &i18n::unit().symbol())])
+	);
+	if report.accounts.is_empty() {
+		println!("{}\n", i18n::tr("none"));
+		return;
+	}
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("fees.account"),
+		bMG->i18n::tr("fees.period"),
+		bMG->i18n::tr("fees.num_txs"),
+		bMG->i18n::tr("fees.total"),
+		bMG->i18n::tr("fees.average"),
+		bMG->i18n::tr("fees.largest"),
+	]);
+	for a in report.accounts.iter() {
+		for p in a.periods.iter() {
+			table.add_row(row![
+				bFC->a.account,
+				bFB->i18n::date(&p.start),
+				bFB->p.fees.num_txs,
+				bFY->i18n::amount(p.fees.total_fees, true),
+				bFD->i18n::amount(p.fees.average_fee, true),
+				bFD->i18n::amount(p.fees.largest_fee, true),
+			]);
+		}
+		table.add_row(row![
+			bFC->a.account,
+			bFG->i18n::tr("fees.all_periods"),
+			bFG->a.fees.num_txs,
+			bFG->i18n::amount(a.fees.total_fees, true),
+			bFG->i18n::amount(a.fees.average_fee, true),
+			bFG->i18n::amount(a.fees.largest_fee, true),
+		]);
+	}
+	table.add_row(row![
+		bFG->i18n::tr("fees.all_accounts"),
+		bFG->i18n::tr("fees.all_periods"),
+		bFG->report.fees.num_txs,
+		bFG->i18n::amount(report.fees.total_fees, true),
+		bFG->i18n::amount(report.fees.average_fee, true),
+		bFG->i18n::amount(report.fees.largest_fee, true),
+	]);
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+
+	println!("\n____ {} ____\n", i18n::tr("fees.largest_title"));
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("txs.confirmation_time"),
+		bMG->i18n::tr("txs.debited"),
+		bMG->i18n::tr("txs.fee"),
+	]);
+	for t in report.largest.iter() {
+		let slate_id = match t.tx_slate_id {
+			Some(s) => format!("{}", s),
+			None => i18n::tr("none"),
+		};
+		let confirmation_ts = match t.confirmation_ts {
+			Some(ts) => i18n::date(&ts),
+			None => i18n::tr("none"),
+		};
+		table.add_row(row![
+			bFC->t.id,
+			bFC->slate_id,
+			bFB->confirmation_ts,
+			bFR->i18n::amount(t.amount_debited, true),
+			bFY->i18n::amount(t.fee.unwrap_or(0), true),
 		]);
 	}
 	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
 	table.printstd();
 	println!();
+	if !validated {
+		println!("\n{}", i18n::tr("warning.unverified"));
+	}
+}
+
+/// Display the weight, kernels and serialized size of transactions
+pub fn tx_sizes(txs: &[TxLogEntry]) {
+	println!("\n____ {} ____\n", i18n::tr("tx_size.title"));
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("txs.num_inputs"),
+		bMG->i18n::tr("txs.num_outputs"),
+		bMG->i18n::tr("tx_size.kernels"),
+		bMG->i18n::tr("tx_size.weight"),
+		bMG->i18n::tr("tx_size.size"),
+	]);
+	for t in txs {
+		let slate_id = match t.tx_slate_id {
+			Some(s) => format!("{}", s),
+			None => i18n::tr("none"),
+		};
+		let (inputs, outputs, kernels, weight, size) = match t.tx_size.as_ref() {
+			Some(s) => (
+				s.num_inputs.to_string(),
+				s.num_outputs.to_string(),
+				s.num_kernels.to_string(),
+				s.weight.to_string(),
+				match s.size {
+					Some(b) => i18n::tr_args("tx_size.bytes", &[("bytes", &b)]),
+					None => i18n::tr("tx_size.estimated"),
+				},
+			),
+			None => {
+				let none = i18n::tr("none");
+				(none.clone(), none.clone(), none.clone(), none.clone(), none)
+			}
+		};
+		table.add_row(row![
+			bFC->t.id,
+			bFC->slate_id,
+			bFB->inputs,
+			bFB->outputs,
+			bFB->kernels,
+			bFY->weight,
+			bFD->size,
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the coinbase and plain outputs spent by the sends of transactions,
+/// the sends mixing both kinds being highlighted
+pub fn input_compositions(txs: &[TxLogEntry]) {
+	let txs: Vec<_> = txs
+		.iter()
+		.filter_map(|t| t.input_composition.as_ref().map(|c| (t, c)))
+		.collect();
+	if txs.is_empty() {
+		return;
+	}
+	println!("\n____ {} ____\n", i18n::tr("inputs.title"));
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("inputs.coinbase"),
+		bMG->i18n::tr("inputs.coinbase_amount"),
+		bMG->i18n::tr("inputs.plain"),
+		bMG->i18n::tr("inputs.plain_amount"),
+		bMG->i18n::tr("inputs.mixed"),
+	]);
+	for (t, c) in txs {
+		let mixed = match c.is_mixed() {
+			true => i18n::tr("yes"),
+			false => i18n::tr("no"),
+		};
+		table.add_row(row![
+			bFC->t.id,
+			bFB->c.coinbase_inputs,
+			FY->i18n::amount(c.coinbase_amount, false),
+			bFB->c.plain_inputs,
+			FY->i18n::amount(c.plain_amount, false),
+			bFR->mixed,
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the per-transaction results of a bulk operation
+pub fn bulk_results(title: &str, results: &Vec<TxBulkResult>) {
+	println!("\n____ {} ____\n", title);
+	if results.is_empty() {
+		println!("{}\n", i18n::tr("bulk.no_match"));
+		return;
+	}
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("txs.id"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("bulk.result"),
+	]);
+	for r in results {
+		let slate_id = match r.tx_slate_id {
+			Some(m) => format!("{}", m),
+			None => i18n::tr("none"),
+		};
+		if r.success {
+			table.add_row(row![
+				bFC->r.tx_id,
+				bFD->slate_id,
+				bFG->i18n::tr("ok"),
+			]);
+		} else {
+			table.add_row(row![
+				bFC->r.tx_id,
+				bFD->slate_id,
+				bFR->r.error.clone().unwrap_or_default(),
+			]);
+		}
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Display the combined report of an amount sent in several sequential transactions
+pub fn split_send(
+	amount: u64,
+	num_txs: usize,
+	slates: &[Slate],
+	dark_background_color_scheme: bool,
+) {
+	println!(
+		"\n____ {} ____\n",
+		i18n::tr_args(
+			"split.title",
+			&[
+				("amount", &i18n::amount(amount, false)),
+				("unit", &i18n::unit().symbol()),
+				("sent", &slates.len()),
+				("count", &num_txs),
+			]
+		)
+	);
+
+	let mut table = table!();
+
+	table.set_titles(row![
+		bMG->i18n::tr("split.index"),
+		bMG->i18n::tr("txs.slate_id"),
+		bMG->i18n::tr("proof.amount"),
+		bMG->i18n::tr("txs.fee"),
+	]);
+
+	for (i, slate) in slates.iter().enumerate() {
+		if dark_background_color_scheme {
+			table.add_row(row![
+				bFC->i + 1,
+				bFD->slate.id,
+				FY->i18n::amount(slate.amount, false),
+				FR->i18n::amount(slate.fee, false),
+			]);
+		} else {
+			table.add_row(row![
+				bFD->i + 1,
+				bFD->slate.id,
+				FY->i18n::amount(slate.amount, false),
+				FR->i18n::amount(slate.fee, false),
+			]);
+		}
+	}
+	let sent: u64 = slates.iter().map(|s| s.amount).sum();
+	let fees: u64 = slates.iter().map(|s| s.fee).sum();
+	table.add_row(row![
+		bFG->i18n::tr("split.total"),
+		"",
+		bFY->i18n::amount(sent, false),
+		bFR->i18n::amount(fees, false),
+	]);
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+}
+
+/// Remediation hint for a failed command, if the failure has a known cause
+pub fn error_hint(kind: &ErrorKind) -> Option<String> {
+	let key = match kind {
+		ErrorKind::NodeUnreachable(_) => "hint.node_unreachable",
+		ErrorKind::NodeSyncing(_) => "hint.node_syncing",
+		ErrorKind::NodeAuthFailed(_) => "hint.node_auth_failed",
+		ErrorKind::NodeIncompatible(_) => "hint.node_incompatible",
+		ErrorKind::NodeOutputNotFound(_) => "hint.node_output_not_found",
+		_ => return None,
+	};
+	Some(i18n::tr(key))
+}
+
+/// Display the contents of a slate
+pub fn slate(slate: &Slate, dark_background_color_scheme: bool) -> Result<(), Error> {
+	println!(
+		"\n____ {} ____\n",
+		i18n::tr_args("slate.title", &[("id", &slate.id)])
+	);
+
+	let version = match slate.version_info.orig_version == slate.version_info.version {
+		true => format!("{}", slate.version_info.version),
+		false => i18n::tr_args(
+			"slate.converted",
+			&[
+				("version", &slate.version_info.version),
+				("orig_version", &slate.version_info.orig_version),
+			],
+		),
+	};
+	let ttl = match slate.ttl_cutoff_height {
+		Some(h) => format!("{}", h),
+		None => i18n::tr("none"),
+	};
+	let mut table = table!();
+	table.add_row(row![bFG->i18n::tr("slate.version"), version]);
+	table.add_row(row![
+		bFG->i18n::tr("slate.block_header_version"),
+		slate.version_info.block_header_version
+	]);
+	table.add_row(row![bFG->i18n::tr("slate.num_participants"), slate.num_participants]);
+	table.add_row(row![
+		bFG->i18n::tr("proof.amount"),
+		FY->format!("{} {}", i18n::amount(slate.amount, false), i18n::unit().symbol())
+	]);
+	table.add_row(row![
+		bFG->i18n::tr("txs.fee"),
+		FR->format!("{} {}", i18n::amount(slate.fee, false), i18n::unit().symbol())
+	]);
+	table.add_row(row![bFG->i18n::tr("slate.height"), slate.height]);
+	table.add_row(row![bFG->i18n::tr("slate.lock_height"), slate.lock_height]);
+	table.add_row(row![bFG->i18n::tr("txs.ttl_cutoff"), ttl]);
+	let inputs = slate.tx.inputs();
+	let coinbase_inputs = inputs
+		.iter()
+		.filter(|i| i.features == OutputFeatures::Coinbase)
+		.count();
+	table.add_row(row![
+		bFG->i18n::tr("slate.inputs"),
+		i18n::tr_args(
+			"slate.input_kinds",
+			&[
+				("count", &inputs.len()),
+				("coinbase", &coinbase_inputs),
+				("plain", &(inputs.len() - coinbase_inputs)),
+			],
+		)
+	]);
+	if coinbase_inputs > 0 && coinbase_inputs < inputs.len() {
+		table.add_row(row![bFG->"", bFR->i18n::tr("slate.mixed_inputs")]);
+	}
+	table.add_row(row![bFG->i18n::tr("slate.outputs"), slate.tx.outputs().len()]);
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+
+	println!("{}", i18n::tr("slate.kernels"));
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->i18n::tr("split.index"),
+		bMG->i18n::tr("slate.features"),
+		bMG->i18n::tr("txs.fee"),
+		bMG->i18n::tr("slate.lock_height"),
+		bMG->i18n::tr("proof.kernel_excess"),
+	]);
+	for (i, k) in slate.tx.kernels().iter().enumerate() {
+		let (features, fee, lock_height) = match k.features {
+			KernelFeatures::Plain { fee } => ("Plain", fee, 0),
+			KernelFeatures::Coinbase => ("Coinbase", 0, 0),
+			KernelFeatures::HeightLocked { fee, lock_height } => ("HeightLocked", fee, lock_height),
+		};
+		let excess = match k.excess.0.iter().all(|b| *b == 0) {
+			true => i18n::tr("none"),
+			false => util::to_hex(k.excess.0.to_vec()),
+		};
+		table.add_row(row![
+			i + 1,
+			features,
+			FR->i18n::amount(fee, false),
+			lock_height,
+			excess,
+		]);
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+
+	println!("{}", i18n::tr("slate.participants"));
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->i18n::tr("messages.participant"),
+		bMG->i18n::tr("slate.public_excess"),
+		bMG->i18n::tr("slate.public_nonce"),
+		bMG->i18n::tr("slate.signed"),
+		bMG->i18n::tr("messages.message"),
+	]);
+	let secp = util::static_secp_instance();
+	let secp_lock = secp.lock();
+	for p in slate.participant_data.iter() {
+		let excess = util::to_hex(
+			p.public_blind_excess
+				.serialize_vec(&secp_lock, true)
+				.to_vec(),
+		);
+		let nonce = util::to_hex(p.public_nonce.serialize_vec(&secp_lock, true).to_vec());
+		let signed = match p.part_sig.is_some() {
+			true => i18n::tr("yes"),
+			false => i18n::tr("no"),
+		};
+		let message = p.message.clone().unwrap_or_else(|| i18n::tr("none"));
+		if dark_background_color_scheme {
+			table.add_row(row![bFC->p.id, excess, nonce, bFC->signed, message]);
+		} else {
+			table.add_row(row![bFD->p.id, excess, nonce, bFD->signed, message]);
+		}
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+
+	println!("{}", i18n::tr("slate.payment_proof"));
+	match slate.payment_proof.as_ref() {
+		None => println!("{}", i18n::tr("none")),
+		Some(pp) => {
+			let receiver_signature = match pp.receiver_signature {
+				Some(s) => util::to_hex(s.to_bytes().to_vec()),
+				None => i18n::tr("none"),
+			};
+			let mut table = table!();
+			table.add_row(row![
+				bFG->i18n::tr("proof.sender_onion"),
+				address::onion_v3_from_pubkey(&pp.sender_address)?
+			]);
+			table.add_row(row![
+				bFG->i18n::tr("proof.receiver_onion"),
+				address::onion_v3_from_pubkey(&pp.receiver_address)?
+			]);
+			table.add_row(row![bFG->i18n::tr("proof.receiver_signature"), receiver_signature]);
+			table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+			table.printstd();
+		}
+	}
+	println!();
+	Ok(())
+}
+
+/// Display the changes between two slates
+pub fn slate_diff(from: &str, to: &str, changes: &[SlateChange]) {
+	println!(
+		"\n____ {} ____\n",
+		i18n::tr_args("slate_diff.title", &[("from", &from), ("to", &to)])
+	);
+	if changes.is_empty() {
+		println!("{}\n", i18n::tr("slate_diff.none"));
+		return;
+	}
+
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->"",
+		bMG->i18n::tr("slate_diff.field"),
+		bMG->i18n::tr("slate_diff.before"),
+		bMG->i18n::tr("slate_diff.after"),
+	]);
+	for c in changes {
+		let before = c.from.as_ref().map(|v| abbreviate(v)).unwrap_or_default();
+		let after = c.to.as_ref().map(|v| abbreviate(v)).unwrap_or_default();
+		match (&c.from, &c.to) {
+			(None, _) => table.add_row(row![bFG->"+", FG->c.field, before, FG->after]),
+			(_, None) => table.add_row(row![bFR->"-", FR->c.field, FR->before, after]),
+			_ => table.add_row(row![bFY->"~", FY->c.field, before, FY->after]),
+		};
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Shorten long values such as proofs, keeping both ends
+fn abbreviate(value: &str) -> String {
+	if value.chars().count() <= 40 {
+		return value.to_owned();
+	}
+	let start: String = value.chars().take(18).collect();
+	let end: String = value.chars().skip(value.chars().count() - 18).collect();
+	format!("{}..{}", start, end)
+}
+
+/// Display the effective value of each configuration setting, and the
+/// configuration file it comes from
+pub fn config_values(
+	wallet_file: Option<&Path>,
+	system_file: Option<&Path>,
+	values: &[(String, String, ConfigSource)],
+) {
+	println!("\n____ {} ____\n", i18n::tr("config.title"));
+	if let Some(p) = wallet_file {
+		println!("{}: {}", i18n::tr("config.wallet_file"), p.display());
+	}
+	if let Some(p) = system_file {
+		println!("{}: {}", i18n::tr("config.system_file"), p.display());
+	}
+	println!();
+
+	let mut table = table!();
+	table.set_titles(row![
+		bMG->i18n::tr("config.key"),
+		bMG->i18n::tr("config.value"),
+		bMG->i18n::tr("config.source"),
+	]);
+	for (key, value, source) in values {
+		match source {
+			ConfigSource::Wallet => {
+				table.add_row(row![bFC->key, value, bFG->i18n::tr("config.wallet")])
+			}
+			ConfigSource::System => {
+				table.add_row(row![bFC->key, value, bFY->i18n::tr("config.system")])
+			}
+			ConfigSource::Default => {
+				table.add_row(row![bFC->key, value, i18n::tr("config.default")])
+			}
+		};
+	}
+	table.set_format(*prettytable::format::consts::FORMAT_NO_COLSEP);
+	table.printstd();
+	println!();
+}
+
+/// Display what a scan of a range of blocks would find
+pub fn scan_preview(preview: &ScanPreview) {
+	println!(
+		"\n____ {} ____\n",
+		i18n::tr_args(
+			"scan_preview.title",
+			&[
+				("start", &preview.start_height),
+				("end", &preview.end_height),
+				("unit", &i18n::unit().symbol()),
+			]
+		)
+	);
+
+	let mut table = table!();
+	table.add_row(row![
+		bFG->i18n::tr("scan_preview.outputs"),
+		FG->preview.num_outputs,
+		FG->i18n::amount(preview.total_value, false)
+	]);
+	table.add_row(row![
+		bFY->i18n::tr("scan_preview.missing"),
+		FY->preview.num_missing,
+		FY->i18n::amount(preview.missing_value, false)
+	]);
+	table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+	table.printstd();
+	println!();
+	println!("{}", i18n::tr("scan_preview.note"));
+	println!();
+}
+
+/// Display the tags of a transaction, or all tags in use if no transaction is given
+pub fn tx_tags(tx_id: Option<&str>, tags: &[String]) {
+	let title = match tx_id {
+		Some(id) => i18n::tr_args("tags.tx_title", &[("id", &id)]),
+		None => i18n::tr("tags.title"),
+	};
+	println!("\n____ {} ____\n", title);
+	if tags.is_empty() {
+		println!("{}\n", i18n::tr("none"));
+	} else {
+		println!("{}\n", tags.join(", "));
+	}
 }
 
 /// Display transaction log messages
 pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Result<(), Error> {
-	let title = format!("Transaction Messages - Transaction '{}'", tx.id,);
+	let title = i18n::tr_args("messages.title", &[("id", &tx.id)]);
 	println!();
 	if term::stdout().is_none() {
-		println!("Could not open terminal");
+		println!("{}", i18n::tr("term.unavailable"));
 		return Ok(());
 	}
 	let mut t = term::stdout().unwrap();
@@ -450,7 +1191,7 @@ pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Resul
 
 	let msgs = match tx.messages.clone() {
 		None => {
-			writeln!(t, "{}", "None").unwrap();
+			writeln!(t, "{}", i18n::tr("none")).unwrap();
 			t.reset().unwrap();
 			return Ok(());
 		}
@@ -458,7 +1199,7 @@ pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Resul
 	};
 
 	if msgs.messages.is_empty() {
-		writeln!(t, "{}", "None").unwrap();
+		writeln!(t, "{}", i18n::tr("none")).unwrap();
 		t.reset().unwrap();
 		return Ok(());
 	}
@@ -466,10 +1207,10 @@ pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Resul
 	let mut table = table!();
 
 	table.set_titles(row![
-		bMG->"Participant Id",
-		bMG->"Message",
-		bMG->"Public Key",
-		bMG->"Signature",
+		bMG->i18n::tr("messages.participant"),
+		bMG->i18n::tr("messages.message"),
+		bMG->i18n::tr("messages.public_key"),
+		bMG->i18n::tr("messages.signature"),
 	]);
 
 	let secp = util::static_secp_instance();
@@ -483,11 +1224,11 @@ pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Resul
 		);
 		let message = match m.message {
 			Some(m) => format!("{}", m),
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
 		};
 		let message_sig = match m.message_sig {
 			Some(s) => format!("{}", util::to_hex(s.serialize_der(&secp_lock))),
-			None => "None".to_owned(),
+			None => i18n::tr("none"),
 		};
 		if dark_background_color_scheme {
 			table.add_row(row![
@@ -515,10 +1256,10 @@ pub fn tx_messages(tx: &TxLogEntry, dark_background_color_scheme: bool) -> Resul
 
 /// Display individual Payment Proof
 pub fn payment_proof(tx: &TxLogEntry) -> Result<(), Error> {
-	let title = format!("Payment Proof - Transaction '{}'", tx.id,);
+	let title = i18n::tr_args("proof.title", &[("id", &tx.id)]);
 	println!();
 	if term::stdout().is_none() {
-		println!("Could not open terminal");
+		println!("{}", i18n::tr("term.unavailable"));
 		return Ok(());
 	}
 	let mut t = term::stdout().unwrap();
@@ -528,7 +1269,7 @@ pub fn payment_proof(tx: &TxLogEntry) -> Result<(), Error> {
 
 	let pp = match &tx.payment_proof {
 		None => {
-			writeln!(t, "{}", "None").unwrap();
+			writeln!(t, "{}", i18n::tr("none")).unwrap();
 			t.reset().unwrap();
 			return Ok(());
 		}
@@ -541,18 +1282,18 @@ pub fn payment_proof(tx: &TxLogEntry) -> Result<(), Error> {
 	let receiver_onion_address = address::onion_v3_from_pubkey(&pp.receiver_address)?;
 	let receiver_signature = match pp.receiver_signature {
 		Some(s) => util::to_hex(s.to_bytes().to_vec()),
-		None => "None".to_owned(),
+		None => i18n::tr("none"),
 	};
 	let fee = match tx.fee {
 		Some(f) => f,
 		None => 0,
 	};
 	let amount = if tx.amount_credited >= tx.amount_debited {
-		core::amount_to_hr_string(tx.amount_credited - tx.amount_debited, true)
+		i18n::amount(tx.amount_credited - tx.amount_debited, true)
 	} else {
 		format!(
 			"{}",
-			core::amount_to_hr_string(tx.amount_debited - tx.amount_credited - fee, true)
+			i18n::amount(tx.amount_debited - tx.amount_credited - fee, true)
 		)
 	};
 
@@ -560,21 +1301,57 @@ pub fn payment_proof(tx: &TxLogEntry) -> Result<(), Error> {
 	let sender_onion_address = address::onion_v3_from_pubkey(&pp.sender_address)?;
 	let sender_signature = match pp.sender_signature {
 		Some(s) => util::to_hex(s.to_bytes().to_vec()),
-		None => "None".to_owned(),
+		None => i18n::tr("none"),
 	};
 	let kernel_excess = match tx.kernel_excess {
 		Some(e) => util::to_hex(e.0.to_vec()),
-		None => "None".to_owned(),
+		None => i18n::tr("none"),
 	};
 
-	writeln!(t, "Receiver Address: {}", receiver_address).unwrap();
-	writeln!(t, "Receiver Address (Onion V3): {}", receiver_onion_address).unwrap();
-	writeln!(t, "Receiver Signature: {}", receiver_signature).unwrap();
-	writeln!(t, "Amount: {}", amount).unwrap();
-	writeln!(t, "Kernel Excess: {}", kernel_excess).unwrap();
-	writeln!(t, "Sender Address: {}", sender_address).unwrap();
-	writeln!(t, "Sender Signature: {}", sender_signature).unwrap();
-	writeln!(t, "Sender Address (Onion V3): {}", sender_onion_address).unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.receiver_address"),
+		receiver_address
+	)
+	.unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.receiver_onion"),
+		receiver_onion_address
+	)
+	.unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.receiver_signature"),
+		receiver_signature
+	)
+	.unwrap();
+	writeln!(t, "{}: {}", i18n::tr("proof.amount"), amount).unwrap();
+	writeln!(t, "{}: {}", i18n::tr("proof.kernel_excess"), kernel_excess).unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.sender_address"),
+		sender_address
+	)
+	.unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.sender_signature"),
+		sender_signature
+	)
+	.unwrap();
+	writeln!(
+		t,
+		"{}: {}",
+		i18n::tr("proof.sender_onion"),
+		sender_onion_address
+	)
+	.unwrap();
 
 	t.reset().unwrap();
 
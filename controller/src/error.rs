@@ -104,6 +104,10 @@ pub enum ErrorKind {
 	#[fail(display = "{}", _0)]
 	ArgumentError(String),
 
+	/// Request refused for going over a limit of the listener
+	#[fail(display = "Request too large: {}", _0)]
+	RequestLimit(String),
+
 	/// Other
 	#[fail(display = "Generic error: {}", _0)]
 	GenericError(String),
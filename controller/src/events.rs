@@ -0,0 +1,328 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WebSocket endpoint of the owner listener streaming the wallet's events, so
+//! frontends don't have to poll for them
+
+use crate::api::{self, ResponseFuture};
+use crate::libwallet::subscribe_events;
+use futures::future::{self, ok, Loop};
+use futures::sync::mpsc::{unbounded, UnboundedSender};
+use futures::{Future, Stream};
+use hyper::header::{
+	CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE,
+};
+use hyper::{Body, Request, Response, StatusCode};
+use serde_json;
+use std::thread;
+use tokio::io::AsyncRead;
+use tungstenite::handshake::derive_accept_key;
+
+/// Path of the events endpoint on the owner listener
+pub const OWNER_EVENTS_PATH: &'static str = "/v3/owner/events";
+
+/// Longest frame accepted from a client, which has nothing to send but control frames
+const MAX_CLIENT_FRAME_LEN: usize = 4096;
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Status closing a connection on which the client broke the protocol
+const CLOSE_PROTOCOL_ERROR: u16 = 1002;
+
+/// Frame queued to be sent to a client, and whether it's the last one
+type Outgoing = (Vec<u8>, bool);
+
+/// Frame sent by the server, which isn't masked
+fn server_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+	let mut frame = vec![0x80 | opcode];
+	let len = payload.len();
+	if len < 126 {
+		frame.push(len as u8);
+	} else if len <= u16::max_value() as usize {
+		frame.push(126);
+		frame.extend_from_slice(&(len as u16).to_be_bytes());
+	} else {
+		frame.push(127);
+		frame.extend_from_slice(&(len as u64).to_be_bytes());
+	}
+	frame.extend_from_slice(payload);
+	frame
+}
+
+/// Frame at the start of the bytes received from a client
+#[derive(Debug, PartialEq)]
+enum ClientFrame {
+	/// Opcode and unmasked payload of the frame, and the number of bytes it took
+	Complete(u8, Vec<u8>, usize),
+	/// More bytes are needed
+	Incomplete,
+	/// The frame isn't masked or is too long
+	Invalid,
+}
+
+fn client_frame(buf: &[u8]) -> ClientFrame {
+	if buf.len() < 2 {
+		return ClientFrame::Incomplete;
+	}
+	let opcode = buf[0] & 0x0f;
+	if buf[1] & 0x80 == 0 {
+		return ClientFrame::Invalid;
+	}
+	let (len, start) = match buf[1] & 0x7f {
+		126 if buf.len() < 4 => return ClientFrame::Incomplete,
+		126 => (u16::from_be_bytes([buf[2], buf[3]]) as u64, 4),
+		127 if buf.len() < 10 => return ClientFrame::Incomplete,
+		127 => {
+			let mut len = [0u8; 8];
+			len.copy_from_slice(&buf[2..10]);
+			(u64::from_be_bytes(len), 10)
+		}
+		len => (len as u64, 2),
+	};
+	if len > MAX_CLIENT_FRAME_LEN as u64 {
+		return ClientFrame::Invalid;
+	}
+	let len = len as usize;
+	if buf.len() < start + 4 + len {
+		return ClientFrame::Incomplete;
+	}
+	let mask = &buf[start..start + 4];
+	let payload = buf[start + 4..start + 4 + len]
+		.iter()
+		.enumerate()
+		.map(|(i, b)| b ^ mask[i % 4])
+		.collect();
+	ClientFrame::Complete(opcode, payload, start + 4 + len)
+}
+
+/// Key accepting the WebSocket handshake of a request, if it's one
+fn accept_key(req: &Request<Body>) -> Option<String> {
+	let headers = req.headers();
+	let upgrade = headers
+		.get(UPGRADE)
+		.and_then(|v| v.to_str().ok())
+		.map(|v| v.eq_ignore_ascii_case("websocket"))
+		.unwrap_or(false);
+	let version = headers
+		.get(SEC_WEBSOCKET_VERSION)
+		.and_then(|v| v.to_str().ok());
+	match (upgrade, version, headers.get(SEC_WEBSOCKET_KEY)) {
+		(true, Some("13"), Some(key)) => Some(derive_accept_key(key.as_bytes())),
+		_ => None,
+	}
+}
+
+/// Forward the events published from now on to a connection as text frames. The
+/// forwarding stops at the first event after the connection is closed.
+fn forward_events(tx: UnboundedSender<Outgoing>) {
+	let events = subscribe_events();
+	let _ = thread::Builder::new()
+		.name("owner-events".to_string())
+		.spawn(move || {
+			while let Ok(e) = events.recv() {
+				let json = match serde_json::to_string(&e) {
+					Ok(j) => j,
+					Err(_) => continue,
+				};
+				let frame = server_frame(OPCODE_TEXT, json.as_bytes());
+				if tx.unbounded_send((frame, false)).is_err() {
+					break;
+				}
+			}
+		});
+}
+
+/// Read the frames sent by a client, answering its pings, until it closes the connection
+fn read_client<R>(rd: R, tx: UnboundedSender<Outgoing>) -> impl Future<Item = (), Error = ()> + Send
+where
+	R: AsyncRead + Send + 'static,
+{
+	future::loop_fn((rd, vec![]), move |(rd, mut buf): (R, Vec<u8>)| {
+		let tx = tx.clone();
+		tokio::io::read(rd, vec![0; MAX_CLIENT_FRAME_LEN]).then(move |res| {
+			let (rd, chunk, n) = match res {
+				Ok((rd, chunk, n)) if n > 0 => (rd, chunk, n),
+				// the connection is gone
+				_ => {
+					let _ = tx.unbounded_send((vec![], true));
+					return Ok(Loop::Break(()));
+				}
+			};
+			buf.extend_from_slice(&chunk[..n]);
+			loop {
+				match client_frame(&buf) {
+					ClientFrame::Complete(opcode, payload, len) => {
+						buf.drain(..len);
+						match opcode {
+							OPCODE_PING => {
+								let _ =
+									tx.unbounded_send((server_frame(OPCODE_PONG, &payload), false));
+							}
+							OPCODE_CLOSE => {
+								let status = &payload[..payload.len().min(2)];
+								let _ =
+									tx.unbounded_send((server_frame(OPCODE_CLOSE, status), true));
+								return Ok(Loop::Break(()));
+							}
+							_ => {}
+						}
+					}
+					ClientFrame::Incomplete => return Ok(Loop::Continue((rd, buf))),
+					ClientFrame::Invalid => {
+						let status = CLOSE_PROTOCOL_ERROR.to_be_bytes();
+						let _ = tx.unbounded_send((server_frame(OPCODE_CLOSE, &status), true));
+						return Ok(Loop::Break(()));
+					}
+				}
+			}
+		})
+	})
+}
+
+/// Owner listener endpoint upgrading its requests to WebSockets, on which the events
+/// of the wallet are sent as they happen, each as a JSON text message tagged with
+/// its kind in the `event` field. The client isn't expected to send anything but
+/// pings, which are answered, and the closing of the connection.
+pub struct OwnerEventsHandler;
+
+impl api::Handler for OwnerEventsHandler {
+	fn get(&self, req: Request<Body>) -> ResponseFuture {
+		let key = match accept_key(&req) {
+			Some(k) => k,
+			None => {
+				let res = Response::builder()
+					.status(StatusCode::BAD_REQUEST)
+					.body("Expected a WebSocket handshake".into())
+					.unwrap();
+				return Box::new(ok(res));
+			}
+		};
+
+		// subscribed before responding, so nothing is missed once upgraded
+		let (tx, rx) = unbounded::<Outgoing>();
+		forward_events(tx.clone());
+		let conn = req
+			.into_body()
+			.on_upgrade()
+			.map_err(|e| debug!("Owner events connection not upgraded: {}", e))
+			.and_then(move |upgraded| {
+				let (rd, wr) = upgraded.split();
+				hyper::rt::spawn(read_client(rd, tx));
+				rx.fold(wr, |wr, (frame, last)| {
+					tokio::io::write_all(wr, frame)
+						.map_err(|_| ())
+						.and_then(move |(wr, _)| if last { Err(()) } else { Ok(wr) })
+				})
+				.then(|_| Ok(()))
+			});
+		hyper::rt::spawn(conn);
+
+		let res = Response::builder()
+			.status(StatusCode::SWITCHING_PROTOCOLS)
+			.header(UPGRADE, "websocket")
+			.header(CONNECTION, "Upgrade")
+			.header(SEC_WEBSOCKET_ACCEPT, key.as_str())
+			.body(Body::empty())
+			.unwrap();
+		Box::new(ok(res))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn masked(opcode: u8, payload: &[u8]) -> Vec<u8> {
+		let mask = [0x37, 0xfa, 0x21, 0x3d];
+		let mut frame = vec![0x80 | opcode];
+		if payload.len() < 126 {
+			frame.push(0x80 | payload.len() as u8);
+		} else {
+			frame.push(0x80 | 126);
+			frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+		}
+		frame.extend_from_slice(&mask);
+		frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+		frame
+	}
+
+	#[test]
+	fn websocket_frames() {
+		// RFC 6455 examples
+		assert_eq!(
+			server_frame(OPCODE_TEXT, b"Hello"),
+			vec![0x81, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f]
+		);
+		let long = server_frame(OPCODE_TEXT, &[0; 256]);
+		assert_eq!(&long[..4], &[0x81, 0x7e, 0x01, 0x00]);
+		assert_eq!(long.len(), 260);
+		let longer = server_frame(OPCODE_TEXT, &[0; 65536]);
+		assert_eq!(&longer[..10], &[0x81, 0x7f, 0, 0, 0, 0, 0, 1, 0, 0]);
+
+		let ping = masked(OPCODE_PING, b"Hello");
+		assert_eq!(
+			ping,
+			vec![0x89, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58]
+		);
+		assert_eq!(
+			client_frame(&ping),
+			ClientFrame::Complete(OPCODE_PING, b"Hello".to_vec(), ping.len())
+		);
+		assert_eq!(client_frame(&ping[..8]), ClientFrame::Incomplete);
+
+		// frames split or coalesced across reads
+		let mut buf = masked(OPCODE_TEXT, &[1; 300]);
+		let first = buf.len();
+		buf.extend(masked(OPCODE_CLOSE, &1000u16.to_be_bytes()));
+		assert_eq!(client_frame(&buf[..3]), ClientFrame::Incomplete);
+		match client_frame(&buf) {
+			ClientFrame::Complete(OPCODE_TEXT, p, len) => {
+				assert_eq!(p, vec![1; 300]);
+				assert_eq!(len, first);
+			}
+			f => panic!("unexpected frame {:?}", f),
+		}
+		assert_eq!(
+			client_frame(&buf[first..]),
+			ClientFrame::Complete(OPCODE_CLOSE, vec![0x03, 0xe8], buf.len() - first)
+		);
+
+		// clients must mask their frames, and have little to say
+		let unmasked = server_frame(OPCODE_PING, b"Hello");
+		assert_eq!(client_frame(&unmasked), ClientFrame::Invalid);
+		let huge = masked(OPCODE_TEXT, &[0; MAX_CLIENT_FRAME_LEN + 1]);
+		assert_eq!(client_frame(&huge[..8]), ClientFrame::Invalid);
+	}
+
+	#[test]
+	fn websocket_handshake() {
+		let req = Request::get(OWNER_EVENTS_PATH)
+			.header(UPGRADE, "websocket")
+			.header(CONNECTION, "Upgrade")
+			.header(SEC_WEBSOCKET_VERSION, "13")
+			.header(SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+			.body(Body::empty())
+			.unwrap();
+		assert_eq!(
+			accept_key(&req),
+			Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=".to_owned())
+		);
+
+		let req = Request::get(OWNER_EVENTS_PATH).body(Body::empty()).unwrap();
+		assert_eq!(accept_key(&req), None);
+	}
+}
@@ -0,0 +1,317 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Endpoints of the owner listener streaming exports of the wallet's transactions,
+//! outputs and audit log as JSON lines, so the export of a large wallet is sent a
+//! chunk at a time rather than built in memory as a single response
+
+use crate::api::{self, ResponseFuture};
+use crate::apiwallet::Owner;
+use crate::keychain::Keychain;
+use crate::libwallet::{Error, NodeClient, WalletLCProvider};
+use crate::util::secp::key::SecretKey;
+use crate::util::{from_hex, static_secp_instance};
+use futures::future::ok;
+use futures::sync::mpsc;
+use futures::{Future, Sink, Stream};
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::sync::Arc;
+use std::thread;
+
+/// Records serialized into each chunk of an export
+const RECORDS_PER_CHUNK: usize = 256;
+
+/// Chunks serialized ahead of the client reading them
+const CHUNKS_BUFFERED: usize = 4;
+
+/// Lines of an export, each a serialized record
+type Lines = Box<dyn Iterator<Item = Result<String, serde_json::Error>> + Send>;
+
+/// Records streamed by an export endpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportKind {
+	/// Transactions of the active account, as returned by `retrieve_txs`
+	Txs,
+	/// Outputs of the active account, as returned by `retrieve_outputs`
+	Outputs,
+	/// Entries of the audit log, as returned by `get_audit_log`
+	AuditLog,
+}
+
+impl ExportKind {
+	/// All the exports, each served on its own path
+	pub const ALL: [ExportKind; 3] = [ExportKind::Txs, ExportKind::Outputs, ExportKind::AuditLog];
+
+	/// Path of the export on the owner listener
+	pub fn path(&self) -> &'static str {
+		match self {
+			ExportKind::Txs => "/v3/owner/export/txs",
+			ExportKind::Outputs => "/v3/owner/export/outputs",
+			ExportKind::AuditLog => "/v3/owner/export/audit_log",
+		}
+	}
+
+	/// Owner API method returning the same records, which a scoped token must allow
+	fn method(&self) -> &'static str {
+		match self {
+			ExportKind::Txs => "retrieve_txs",
+			ExportKind::Outputs => "retrieve_outputs",
+			ExportKind::AuditLog => "get_audit_log",
+		}
+	}
+}
+
+/// Parameters of an export, given in the query string of its URL
+#[derive(Debug, Default, PartialEq)]
+pub struct ExportQuery {
+	/// Token of the wallet, its keychain mask or a scoped token, in hex
+	pub token: Option<String>,
+	/// Wallet exported, served by the listener, or `None` for its default wallet
+	pub wallet: Option<String>,
+	/// Whether the spent outputs are exported too
+	pub include_spent: bool,
+	/// Tags the exported transactions must all carry, each given as a `tag` parameter
+	pub tags: Vec<String>,
+}
+
+impl ExportQuery {
+	/// Parameters of a query string, e.g. `token=...&include_spent=true` or
+	/// `token=...&tag=payroll&tag=refund`
+	pub fn parse(query: Option<&str>) -> Result<ExportQuery, String> {
+		let mut res = ExportQuery::default();
+		let query = match query {
+			Some(q) => q,
+			None => return Ok(res),
+		};
+		for (k, v) in url::form_urlencoded::parse(query.as_bytes()) {
+			match k.as_ref() {
+				"token" => res.token = Some(v.into_owned()),
+				"wallet" => res.wallet = Some(v.into_owned()),
+				"tag" => res.tags.push(v.into_owned()),
+				"include_spent" => {
+					res.include_spent = v
+						.parse()
+						.map_err(|_| format!("Invalid include_spent: {}", v))?
+				}
+				_ => return Err(format!("Unknown export parameter: {}", k)),
+			}
+		}
+		Ok(res)
+	}
+}
+
+/// Handler of an export endpoint, streaming the records as JSON lines. Like the
+/// events endpoint, exports aren't encrypted by the secure API, so the listener
+/// should only be reached over TLS or from the local host.
+pub struct OwnerExportHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	kind: ExportKind,
+	/// Owner API of the default wallet
+	owner_api: Arc<Owner<L, C, K>>,
+	/// Owner APIs of the other wallets served by the listener, by name
+	wallets: Arc<HashMap<String, Arc<Owner<L, C, K>>>>,
+}
+
+impl<L, C, K> OwnerExportHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Create a new handler of an export of the wallets served by the owner listener
+	pub fn new(
+		kind: ExportKind,
+		owner_api: Arc<Owner<L, C, K>>,
+		wallets: Arc<HashMap<String, Arc<Owner<L, C, K>>>>,
+	) -> OwnerExportHandler<L, C, K> {
+		OwnerExportHandler {
+			kind,
+			owner_api,
+			wallets,
+		}
+	}
+
+	/// Keychain mask a token stands for, refused if it's a scoped token not
+	/// allowing the records to be read
+	fn keychain_mask(
+		&self,
+		api: &Owner<L, C, K>,
+		token: Option<&str>,
+	) -> Result<Option<SecretKey>, (StatusCode, String)> {
+		let token = match token {
+			Some(t) => t,
+			None => return Ok(None),
+		};
+		if let Some((scope, mask)) = api.token_scope(token) {
+			return match scope.allows(self.kind.method(), None, false) {
+				true => Ok(mask),
+				false => Err((
+					StatusCode::FORBIDDEN,
+					format!(
+						"Method {} isn't allowed by the token's scope",
+						self.kind.method()
+					),
+				)),
+			};
+		}
+		let secp_inst = static_secp_instance();
+		let secp = secp_inst.lock();
+		from_hex(token.to_owned())
+			.ok()
+			.and_then(|b| SecretKey::from_slice(&secp, &b).ok())
+			.map(Some)
+			.ok_or((StatusCode::BAD_REQUEST, "Invalid token".to_owned()))
+	}
+
+	/// Lines of the export asked for by a query
+	fn lines(&self, query: &ExportQuery) -> Result<Lines, (StatusCode, String)> {
+		let api = match query.wallet.as_ref() {
+			Some(name) => self
+				.wallets
+				.get(name)
+				.ok_or((StatusCode::NOT_FOUND, format!("Unknown wallet: {}", name)))?,
+			None => &self.owner_api,
+		};
+		let mask = self.keychain_mask(api, query.token.as_ref().map(|t| t.as_str()))?;
+		let mask = mask.as_ref();
+		let failed = |e: Error| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+		let lines: Lines = match self.kind {
+			ExportKind::Txs => {
+				let txs = api.export_txs(mask, &query.tags).map_err(failed)?;
+				Box::new(txs.into_iter().map(|t| serde_json::to_string(&t)))
+			}
+			ExportKind::Outputs => {
+				let outputs = api
+					.export_outputs(mask, query.include_spent)
+					.map_err(failed)?;
+				Box::new(outputs.into_iter().map(|o| serde_json::to_string(&o)))
+			}
+			ExportKind::AuditLog => {
+				let entries = api.export_audit_log(mask).map_err(failed)?;
+				Box::new(entries.map(|e| serde_json::to_string(&e)))
+			}
+		};
+		Ok(lines)
+	}
+}
+
+impl<L, C, K> api::Handler for OwnerExportHandler<L, C, K>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	fn get(&self, req: Request<Body>) -> ResponseFuture {
+		let lines = ExportQuery::parse(req.uri().query())
+			.map_err(|e| (StatusCode::BAD_REQUEST, e))
+			.and_then(|q| self.lines(&q));
+		let res = match lines {
+			Ok(lines) => Response::builder()
+				.status(StatusCode::OK)
+				.header("access-control-allow-origin", "*")
+				.header(hyper::header::CONTENT_TYPE, "application/x-ndjson")
+				.body(stream_lines(lines))
+				.unwrap(),
+			Err((status, msg)) => Response::builder()
+				.status(status)
+				.header("access-control-allow-origin", "*")
+				.body(msg.into())
+				.unwrap(),
+		};
+		Box::new(ok(res))
+	}
+}
+
+/// Body of the lines, each ended by a newline, serialized by a thread a chunk at a
+/// time as the client reads them. A record failing to serialize aborts the body,
+/// so the client can't take the export for a complete one.
+fn stream_lines(lines: Lines) -> Body {
+	let (mut tx, rx) = mpsc::channel::<Result<Vec<u8>, io::Error>>(CHUNKS_BUFFERED);
+	let _ = thread::Builder::new()
+		.name("owner-export".to_string())
+		.spawn(move || {
+			let mut chunk = vec![];
+			for (i, line) in lines.enumerate() {
+				let line = match line {
+					Ok(l) => l,
+					Err(e) => {
+						let _ = tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).wait();
+						return;
+					}
+				};
+				chunk.extend_from_slice(line.as_bytes());
+				chunk.push(b'\n');
+				if (i + 1) % RECORDS_PER_CHUNK == 0 {
+					tx = match tx.send(Ok(mem::replace(&mut chunk, vec![]))).wait() {
+						Ok(tx) => tx,
+						// the client went away
+						Err(_) => return,
+					};
+				}
+			}
+			if !chunk.is_empty() {
+				let _ = tx.send(Ok(chunk)).wait();
+			}
+		});
+	Body::wrap_stream(rx.then(|r| match r {
+		Ok(chunk) => chunk,
+		Err(()) => Err(io::Error::new(io::ErrorKind::Other, "Export interrupted")),
+	}))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn export_query() {
+		assert_eq!(ExportQuery::parse(None).unwrap(), ExportQuery::default());
+		let query = ExportQuery::parse(Some("token=d202&wallet=cold%20storage&include_spent=true"))
+			.unwrap();
+		assert_eq!(query.token, Some("d202".to_owned()));
+		assert_eq!(query.wallet, Some("cold storage".to_owned()));
+		assert!(query.include_spent);
+		assert!(query.tags.is_empty());
+		let query = ExportQuery::parse(Some("tag=payroll&tag=refund")).unwrap();
+		assert_eq!(query.tags, vec!["payroll".to_owned(), "refund".to_owned()]);
+		assert!(ExportQuery::parse(Some("include_spent=yes")).is_err());
+		assert!(ExportQuery::parse(Some("limit=10")).is_err());
+	}
+
+	#[test]
+	fn export_lines() {
+		// several chunks, the last one partial
+		let count = RECORDS_PER_CHUNK * 2 + 3;
+		let lines: Lines = Box::new((0..count).map(|i| serde_json::to_string(&i)));
+		let body = stream_lines(lines).concat2().wait().unwrap();
+		let body = String::from_utf8(body.to_vec()).unwrap();
+		let records: Vec<usize> = body.lines().map(|l| l.parse().unwrap()).collect();
+		assert_eq!(records, (0..count).collect::<Vec<_>>());
+		assert!(body.ends_with('\n'));
+
+		// a record failing to serialize aborts the body
+		let mut map = HashMap::new();
+		map.insert(vec![1u8], 1);
+		let lines: Lines =
+			Box::new(vec![serde_json::to_string(&1), serde_json::to_string(&map)].into_iter());
+		assert!(stream_lines(lines).concat2().wait().is_err());
+	}
+}
@@ -0,0 +1,209 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Methods the foreign API listener serves, and how many calls of them per minute
+
+use crate::config::ForeignApiAccessConfig;
+use crate::util::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Period the rate limits apply to
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// JSON-RPC error code of a call to a method that isn't served
+const METHOD_DENIED_CODE: i64 = -32601;
+
+/// JSON-RPC error code of a call over the rate limits
+const RATE_LIMITED_CODE: i64 = -32005;
+
+/// Times of the calls served within the last window
+#[derive(Debug, Default)]
+struct CallTimes {
+	all: VecDeque<Instant>,
+	by_method: HashMap<String, VecDeque<Instant>>,
+}
+
+/// Whether `count` more calls can be served within the limit, forgetting the calls
+/// made before the window
+fn within_limit(times: &mut VecDeque<Instant>, limit: u32, count: usize, now: Instant) -> bool {
+	while let Some(t) = times.front() {
+		if now.duration_since(*t) < RATE_LIMIT_WINDOW {
+			break;
+		}
+		times.pop_front();
+	}
+	times.len() + count <= limit as usize
+}
+
+/// Allow and deny lists of the foreign API's methods, and limits on the calls
+/// served per minute, all clients together
+#[derive(Debug)]
+pub struct ForeignAccess {
+	config: ForeignApiAccessConfig,
+	calls: Mutex<CallTimes>,
+}
+
+impl ForeignAccess {
+	/// Create the restrictions of the configuration
+	pub fn new(config: &ForeignApiAccessConfig) -> ForeignAccess {
+		ForeignAccess {
+			config: config.clone(),
+			calls: Mutex::new(CallTimes::default()),
+		}
+	}
+
+	fn is_allowed(&self, method: &str) -> bool {
+		let allowed = match self.config.allow_methods.as_ref() {
+			Some(a) => a.iter().any(|m| m == method),
+			None => true,
+		};
+		let denied = match self.config.deny_methods.as_ref() {
+			Some(d) => d.iter().any(|m| m == method),
+			None => false,
+		};
+		allowed && !denied
+	}
+
+	/// Check the calls of a request, single or batch, can be served, counting them
+	/// against the rate limits if so. The JSON-RPC error returned instead otherwise
+	/// refuses the whole request.
+	pub fn check(&self, val: &serde_json::Value) -> Result<(), serde_json::Value> {
+		let requests = match val.as_array() {
+			Some(r) => r.iter().collect(),
+			None => vec![val],
+		};
+		let methods: Vec<&str> = requests
+			.iter()
+			.map(|c| c["method"].as_str().unwrap_or(""))
+			.collect();
+		let id = match val.is_array() {
+			true => serde_json::Value::Null,
+			false => val["id"].clone(),
+		};
+		if let Some(m) = methods.iter().find(|m| !self.is_allowed(m)) {
+			let msg = format!("Method {} isn't served by this listener", m);
+			return Err(error_response(id, METHOD_DENIED_CODE, msg));
+		}
+
+		let now = Instant::now();
+		let mut calls = self.calls.lock();
+		if let Some(limit) = self.config.max_calls_per_minute {
+			if !within_limit(&mut calls.all, limit, methods.len(), now) {
+				let msg = format!("Over the limit of {} calls per minute", limit);
+				return Err(error_response(id, RATE_LIMITED_CODE, msg));
+			}
+		}
+		if let Some(limits) = self.config.method_calls_per_minute.as_ref() {
+			for m in &methods {
+				if let Some(limit) = limits.get(*m) {
+					let count = methods.iter().filter(|o| *o == m).count();
+					let times = calls.by_method.entry(m.to_string()).or_default();
+					if !within_limit(times, *limit, count, now) {
+						let msg = format!("Over the limit of {} calls of {} per minute", limit, m);
+						return Err(error_response(id, RATE_LIMITED_CODE, msg));
+					}
+				}
+			}
+		}
+
+		// all calls are served, count them
+		for m in &methods {
+			if self.config.max_calls_per_minute.is_some() {
+				calls.all.push_back(now);
+			}
+			let limited = self
+				.config
+				.method_calls_per_minute
+				.as_ref()
+				.map_or(false, |l| l.contains_key(*m));
+			if limited {
+				calls
+					.by_method
+					.entry(m.to_string())
+					.or_default()
+					.push_back(now);
+			}
+		}
+		Ok(())
+	}
+}
+
+fn error_response(id: serde_json::Value, code: i64, message: String) -> serde_json::Value {
+	serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": id,
+		"error": {
+			"message": message,
+			"code": code
+		}
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	fn call(method: &str) -> serde_json::Value {
+		json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": []})
+	}
+
+	#[test]
+	fn foreign_access() {
+		let mut method_limits = std::collections::BTreeMap::new();
+		method_limits.insert("receive_tx".to_owned(), 2);
+		let access = ForeignAccess::new(&ForeignApiAccessConfig {
+			allow_methods: Some(vec!["check_version".to_owned(), "receive_tx".to_owned()]),
+			deny_methods: Some(vec!["check_version".to_owned()]),
+			max_calls_per_minute: Some(4),
+			method_calls_per_minute: Some(method_limits),
+		});
+
+		// only allowed methods which aren't denied are served
+		let err = access.check(&call("build_coinbase")).unwrap_err();
+		assert_eq!(err["error"]["code"], METHOD_DENIED_CODE);
+		assert_eq!(err["id"], 1);
+		assert!(access.check(&call("check_version")).is_err());
+
+		// up to the limit of the method
+		assert!(access.check(&call("receive_tx")).is_ok());
+		assert!(access.check(&call("receive_tx")).is_ok());
+		let err = access.check(&call("receive_tx")).unwrap_err();
+		assert_eq!(err["error"]["code"], RATE_LIMITED_CODE);
+
+		// refused calls aren't counted, and calls of a batch are counted one by one
+		let mut calls = access.calls.lock();
+		calls.by_method.clear();
+		drop(calls);
+		assert!(access
+			.check(&json!([call("receive_tx"), call("receive_tx")]))
+			.is_ok());
+		assert!(access.check(&call("receive_tx")).is_err());
+
+		// the calls of the last minute only count
+		let mut calls = access.calls.lock();
+		for t in calls.by_method.get_mut("receive_tx").unwrap().iter_mut() {
+			*t -= RATE_LIMIT_WINDOW;
+		}
+		drop(calls);
+		assert!(access.check(&call("receive_tx")).is_err());
+		let mut calls = access.calls.lock();
+		for t in calls.all.iter_mut() {
+			*t -= RATE_LIMIT_WINDOW;
+		}
+		drop(calls);
+		assert!(access.check(&call("receive_tx")).is_ok());
+	}
+}
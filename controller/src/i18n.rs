@@ -0,0 +1,610 @@
+// Copyright 2023 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Localization of command line output. Messages are looked up by key in a
+//! catalog holding the English defaults, which can be overridden by a JSON
+//! translation file mapping keys to translated strings. Amounts and dates are
+//! formatted according to the configured locale, with amounts rendered and
+//! parsed in the configured display unit.
+
+use crate::core::consensus::EPIC_BASE;
+use crate::util::Mutex;
+use chrono::{DateTime, Utc};
+use serde_json;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+lazy_static! {
+	static ref LOCALIZER: Mutex<Localizer> = Mutex::new(Localizer::default());
+}
+
+/// Number and date formatting conventions of a locale
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+	/// Language tag, e.g. "en" or "de-DE"
+	pub tag: String,
+	/// Separator between the integer and fractional part of a number
+	pub decimal_separator: char,
+	/// Separator between groups of thousands, if any
+	pub thousands_separator: Option<char>,
+	/// chrono format string used for dates
+	pub date_format: String,
+}
+
+impl Default for Locale {
+	fn default() -> Locale {
+		Locale {
+			tag: "en".to_owned(),
+			decimal_separator: '.',
+			thousands_separator: None,
+			date_format: "%Y-%m-%d %H:%M:%S".to_owned(),
+		}
+	}
+}
+
+impl Locale {
+	/// Formatting conventions for the given language tag, falling back to
+	/// the English defaults for unknown languages
+	pub fn from_tag(tag: &str) -> Locale {
+		let language = tag
+			.split(|c| c == '-' || c == '_')
+			.next()
+			.unwrap_or("")
+			.to_lowercase();
+		let (decimal_separator, thousands_separator, date_format) = match language.as_str() {
+			"de" | "es" | "it" | "nl" | "pt" | "id" | "tr" => (',', Some('.'), "%d.%m.%Y %H:%M:%S"),
+			"fr" | "ru" | "pl" | "cs" | "uk" => (',', Some(' '), "%d.%m.%Y %H:%M:%S"),
+			_ => {
+				let mut locale = Locale::default();
+				locale.tag = tag.to_owned();
+				return locale;
+			}
+		};
+		Locale {
+			tag: tag.to_owned(),
+			decimal_separator,
+			thousands_separator,
+			date_format: date_format.to_owned(),
+		}
+	}
+}
+
+/// Unit in which amounts are displayed and entered on the command line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayUnit {
+	/// Whole EPIC, with 8 decimals
+	Epic,
+	/// Thousandths of an EPIC, with 5 decimals
+	Milli,
+	/// Millionths of an EPIC, with 2 decimals
+	Micro,
+	/// The smallest indivisible amount, without decimals
+	Raw,
+}
+
+impl DisplayUnit {
+	/// Number of decimals an amount in this unit can have
+	pub fn decimals(&self) -> usize {
+		match self {
+			DisplayUnit::Epic => base_decimals(),
+			DisplayUnit::Milli => base_decimals() - 3,
+			DisplayUnit::Micro => base_decimals() - 6,
+			DisplayUnit::Raw => 0,
+		}
+	}
+
+	/// Symbol shown next to amounts in this unit
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			DisplayUnit::Epic => "EPIC",
+			DisplayUnit::Milli => "mEPIC",
+			DisplayUnit::Micro => "µEPIC",
+			DisplayUnit::Raw => "raw",
+		}
+	}
+
+	fn factor(&self) -> u64 {
+		10u64.pow(self.decimals() as u32)
+	}
+}
+
+impl Default for DisplayUnit {
+	fn default() -> DisplayUnit {
+		DisplayUnit::Epic
+	}
+}
+
+impl fmt::Display for DisplayUnit {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = match self {
+			DisplayUnit::Epic => "epic",
+			DisplayUnit::Milli => "milli",
+			DisplayUnit::Micro => "micro",
+			DisplayUnit::Raw => "raw",
+		};
+		write!(f, "{}", name)
+	}
+}
+
+impl FromStr for DisplayUnit {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<DisplayUnit, String> {
+		match s.to_lowercase().as_str() {
+			"epic" => Ok(DisplayUnit::Epic),
+			"milli" => Ok(DisplayUnit::Milli),
+			"micro" => Ok(DisplayUnit::Micro),
+			"raw" => Ok(DisplayUnit::Raw),
+			_ => Err(format!(
+				"Unknown display unit '{}', expected one of epic, milli, micro or raw",
+				s
+			)),
+		}
+	}
+}
+
+/// Message catalog and formatting settings used for command line output
+#[derive(Debug, Clone)]
+pub struct Localizer {
+	/// Formatting conventions
+	pub locale: Locale,
+	/// Unit in which amounts are displayed and entered
+	pub unit: DisplayUnit,
+	/// Maximum number of decimals to display for amounts
+	pub amount_decimals: usize,
+	/// Translated messages, overriding the English defaults
+	messages: HashMap<String, String>,
+}
+
+impl Default for Localizer {
+	fn default() -> Localizer {
+		Localizer {
+			locale: Locale::default(),
+			unit: DisplayUnit::default(),
+			amount_decimals: base_decimals(),
+			messages: HashMap::new(),
+		}
+	}
+}
+
+impl Localizer {
+	/// Create a localizer for the given locale and display unit, optionally
+	/// loading translated messages from a JSON catalog file
+	pub fn new(
+		locale: Option<&str>,
+		catalog_path: Option<&str>,
+		unit: DisplayUnit,
+		amount_decimals: Option<usize>,
+	) -> Localizer {
+		let mut localizer = Localizer::default();
+		if let Some(l) = locale {
+			localizer.locale = Locale::from_tag(l);
+		}
+		localizer.unit = unit;
+		if let Some(d) = amount_decimals {
+			localizer.amount_decimals = d.min(base_decimals());
+		}
+		if let Some(p) = catalog_path {
+			match load_catalog(p) {
+				Ok(m) => localizer.messages = m,
+				Err(e) => warn!("Unable to load message catalog {}: {}", p, e),
+			}
+		}
+		localizer
+	}
+
+	fn message(&self, key: &str) -> String {
+		match self.messages.get(key) {
+			Some(m) => m.clone(),
+			None => default_message(key).unwrap_or(key).to_owned(),
+		}
+	}
+
+	fn amount(&self, amount: u64, truncate: bool) -> String {
+		let factor = self.unit.factor();
+		let decimals = self.unit.decimals();
+		let int_part = group_thousands(
+			&(amount / factor).to_string(),
+			self.locale.thousands_separator,
+		);
+		if decimals == 0 {
+			return int_part;
+		}
+		let mut frac_part = format!("{:0width$}", amount % factor, width = decimals);
+		frac_part.truncate(self.amount_decimals.min(decimals));
+		if truncate {
+			let trimmed = frac_part.trim_end_matches('0').len();
+			frac_part.truncate(trimmed.max(1).min(frac_part.len()));
+		}
+		if frac_part.is_empty() {
+			int_part
+		} else {
+			format!("{}{}{}", int_part, self.locale.decimal_separator, frac_part)
+		}
+	}
+
+	/// Parse an amount entered in the display unit. Only the locale's decimal
+	/// separator is accepted; digit grouping, signs, whitespace and more
+	/// decimals than the unit supports are rejected rather than guessed at.
+	fn parse_amount(&self, input: &str) -> Result<u64, String> {
+		let sep = self.locale.decimal_separator;
+		if input.is_empty() {
+			return Err("Amount is empty".to_owned());
+		}
+		if let Some(c) = input.chars().find(|c| !c.is_ascii_digit() && *c != sep) {
+			if c == '.' || c == ',' || Some(c) == self.locale.thousands_separator {
+				return Err(format!(
+					"Ambiguous separator '{}' in amount '{}', use '{}' as the decimal separator and no digit grouping",
+					c, input, sep
+				));
+			}
+			return Err(format!(
+				"Unexpected character '{}' in amount '{}'",
+				c, input
+			));
+		}
+		let mut parts = input.split(sep);
+		let int_part = parts.next().unwrap_or("");
+		let frac_part = parts.next();
+		if parts.next().is_some() {
+			return Err(format!(
+				"Amount '{}' contains more than one decimal separator",
+				input
+			));
+		}
+		if int_part.is_empty() || frac_part == Some("") {
+			return Err(format!(
+				"Amount '{}' needs digits on both sides of the decimal separator",
+				input
+			));
+		}
+		let decimals = self.unit.decimals();
+		let frac_part = frac_part.unwrap_or("");
+		if frac_part.len() > decimals {
+			return Err(format!(
+				"Amount '{}' has more than the {} decimals allowed in unit '{}'",
+				input, decimals, self.unit
+			));
+		}
+		let too_large = || format!("Amount '{}' is too large", input);
+		let int_value: u64 = int_part.parse().map_err(|_| too_large())?;
+		let frac_value: u64 = if frac_part.is_empty() {
+			0
+		} else {
+			format!("{:0<width$}", frac_part, width = decimals)
+				.parse()
+				.map_err(|_| too_large())?
+		};
+		int_value
+			.checked_mul(self.unit.factor())
+			.and_then(|v| v.checked_add(frac_value))
+			.ok_or_else(too_large)
+	}
+}
+
+/// Set the localizer used for all subsequent command line output
+pub fn init(localizer: Localizer) {
+	*LOCALIZER.lock() = localizer;
+}
+
+/// Translated message for the given key
+pub fn tr(key: &str) -> String {
+	LOCALIZER.lock().message(key)
+}
+
+/// Translated message for the given key, with each `{name}` placeholder
+/// replaced by the corresponding argument
+pub fn tr_args(key: &str, args: &[(&str, &dyn ToString)]) -> String {
+	let mut msg = tr(key);
+	for (name, value) in args {
+		msg = msg.replace(&format!("{{{}}}", name), &value.to_string());
+	}
+	msg
+}
+
+/// Format an amount given in nanoepics for display. If `truncate` is set,
+/// trailing zeros of the fractional part are dropped.
+pub fn amount(amount: u64, truncate: bool) -> String {
+	LOCALIZER.lock().amount(amount, truncate)
+}
+
+/// Parse an amount entered in the configured display unit into nanoepics
+pub fn parse_amount(input: &str) -> Result<u64, String> {
+	LOCALIZER.lock().parse_amount(input)
+}
+
+/// The configured display unit
+pub fn unit() -> DisplayUnit {
+	LOCALIZER.lock().unit
+}
+
+/// Format a date for display
+pub fn date(ts: &DateTime<Utc>) -> String {
+	let format = LOCALIZER.lock().locale.date_format.clone();
+	format!("{}", ts.format(&format))
+}
+
+fn base_decimals() -> usize {
+	EPIC_BASE.to_string().len() - 1
+}
+
+fn group_thousands(digits: &str, separator: Option<char>) -> String {
+	let sep = match separator {
+		Some(s) => s,
+		None => return digits.to_owned(),
+	};
+	let mut out = String::new();
+	for (i, c) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i) % 3 == 0 {
+			out.push(sep);
+		}
+		out.push(c);
+	}
+	out
+}
+
+fn load_catalog(path: &str) -> Result<HashMap<String, String>, String> {
+	let data = fs::read_to_string(path).map_err(|e| format!("{}", e))?;
+	serde_json::from_str(&data).map_err(|e| format!("{}", e))
+}
+
+/// English defaults of all messages in the catalog
+fn default_message(key: &str) -> Option<&'static str> {
+	let msg = match key {
+		"term.unavailable" => "Could not open terminal",
+		"none" => "None",
+		"yes" => "Yes",
+		"no" => "No",
+		"ok" => "OK",
+		"warning.unverified" => {
+			"WARNING: Wallet failed to verify data. \
+			 The above is from local cache and possibly invalid! \
+			 (is your `epic server` offline or broken?)"
+		}
+		"warning.unverified_height" => {
+			"WARNING: Wallet failed to verify data against a live chain. \
+			 The above is from local cache and only valid up to the given height! \
+			 (is your `epic server` offline or broken?)"
+		}
+		"outputs.title" => "Wallet Outputs - Account '{account}' - Block Height: {height}",
+		"outputs.commitment" => "Output Commitment",
+		"outputs.mmr_index" => "MMR Index",
+		"outputs.block_height" => "Block Height",
+		"outputs.locked_until" => "Locked Until",
+		"outputs.status" => "Status",
+		"outputs.coinbase" => "Coinbase?",
+		"outputs.confirms" => "# Confirms",
+		"outputs.value" => "Value",
+		"outputs.tx" => "Tx",
+		"outputs.mining" => "Mining",
+		"outputs.frozen" => "Frozen",
+		"txs.title" => "Transaction Log - Account '{account}' - Block Height: {height}",
+		"txs.id" => "Id",
+		"txs.type" => "Type",
+		"txs.slate_id" => "Shared Transaction Id",
+		"txs.creation_time" => "Creation Time",
+		"txs.ttl_cutoff" => "TTL Cutoff Height",
+		"txs.confirmed" => "Confirmed?",
+		"txs.confirmation_time" => "Confirmation Time",
+		"txs.num_inputs" => "Num. \nInputs",
+		"txs.num_outputs" => "Num. \nOutputs",
+		"txs.credited" => "Amount \nCredited",
+		"txs.debited" => "Amount \nDebited",
+		"txs.fee" => "Fee",
+		"txs.net_difference" => "Net \nDifference",
+		"txs.payment_proof" => "Payment \nProof",
+		"txs.kernel" => "Kernel",
+		"txs.tx_data" => "Tx \nData",
+		"txs.tags" => "Tags",
+		"txs.contact" => "Contact",
+		"txs.proofs_requested" => {
+			"{count} sent transaction(s) still lack the recipient's payment proof signature. \
+			 Ask the recipient to process the slate."
+		}
+		"proof_state.requested" => "Requested",
+		"proof_state.promised" => "Promised",
+		"proof_state.received" => "Received",
+		"proof_state.verified" => "Verified",
+		"tags.title" => "Transaction Tags",
+		"tags.tx_title" => "Tags - Transaction '{id}'",
+		"info.title" => "Wallet Summary Info - Account '{account}' as of height {height} ({unit})",
+		"info.confirmed_total" => "Confirmed Total",
+		"info.total" => "Total",
+		"info.immature" => "Immature Coinbase (< {maturity})",
+		"info.awaiting_confirmation" => "Awaiting Confirmation (< {confirmations})",
+		"info.awaiting_finalization" => "Awaiting Finalization",
+		"info.locked" => "Locked by previous transaction",
+		"info.spendable" => "Currently Spendable",
+		"estimate.title" => "Estimation for sending {amount} {unit}:",
+		"estimate.strategy" => "Selection strategy",
+		"estimate.fee" => "Fee",
+		"estimate.locked" => "Will be locked",
+		"estimate.inputs" => "Inputs",
+		"estimate.change" => "Change (outputs)",
+		"accounts.title" => "Wallet Accounts",
+		"accounts.name" => "Name",
+		"accounts.path" => "Parent BIP-32 Derivation Path",
+		"accounts.metadata" => "Metadata",
+		"contacts.title" => "Address Book",
+		"contacts.name" => "Name",
+		"contacts.proof_address" => "Payment Proof Address",
+		"contacts.epicbox_address" => "Epicbox Address",
+		"contacts.transports" => "Transports",
+		"templates.title" => "Send Templates",
+		"templates.name" => "Name",
+		"templates.method" => "Method",
+		"templates.dest" => "Destination",
+		"templates.amount" => "Amount",
+		"templates.account" => "Account",
+		"templates.message" => "Message",
+		"templates.active_account" => "(active)",
+		"pending.title" => "Received Transactions Pending on Chain",
+		"pending.expires" => "Expires",
+		"pending.never" => "Never",
+		"fees.title" => "Fees Paid ({unit})",
+		"fees.account" => "Account",
+		"fees.period" => "Period \nStarting",
+		"fees.num_txs" => "Num. \nTxs",
+		"fees.total" => "Total Fees",
+		"fees.average" => "Average Fee",
+		"fees.largest" => "Largest Fee",
+		"fees.all_periods" => "All",
+		"fees.all_accounts" => "All Accounts",
+		"fees.largest_title" => "Largest Fees Paid",
+		"tx_size.title" => "Transaction Weight and Size",
+		"tx_size.kernels" => "Num. \nKernels",
+		"tx_size.weight" => "Weight",
+		"tx_size.size" => "Size",
+		"tx_size.bytes" => "{bytes} bytes",
+		"tx_size.estimated" => "(not finalized)",
+		"inputs.title" => "Inputs Spent by Kind",
+		"inputs.coinbase" => "Coinbase \nInputs",
+		"inputs.coinbase_amount" => "Coinbase \nAmount",
+		"inputs.plain" => "Plain \nInputs",
+		"inputs.plain_amount" => "Plain \nAmount",
+		"inputs.mixed" => "Mixed?",
+		"bulk.no_match" => "No matching transactions found.",
+		"bulk.result" => "Result",
+		"hint.node_unreachable" => {
+			"Make sure your node is running and that `check_node_api_http_addr` in the \
+			 wallet configuration (or --api_server_address) points to it."
+		}
+		"hint.node_syncing" => "Your node is still syncing, wait for it to finish and try again.",
+		"hint.node_auth_failed" => {
+			"Make sure `node_api_secret_path` in the wallet configuration points to a file \
+			 containing the node's API secret."
+		}
+		"hint.node_incompatible" => {
+			"Your node is incompatible with this wallet, update it to the latest release."
+		}
+		"hint.node_output_not_found" => {
+			"Your node doesn't know an output of this wallet. Make sure the node is fully \
+			 synced, then run `epic-wallet scan` to bring the wallet in line with the chain."
+		}
+		"split.title" => "Split Send of {amount} {unit} - {sent} of {count} Transactions Sent",
+		"split.index" => "#",
+		"split.total" => "Total",
+		"scan_preview.title" => "Scan Preview - Blocks {start} to {end} ({unit})",
+		"scan_preview.outputs" => "Outputs Found",
+		"scan_preview.missing" => "Not Yet In Wallet",
+		"scan_preview.note" => {
+			"Nothing was written to the wallet. Run `scan` without --preview to restore \
+			 the missing outputs."
+		}
+		"messages.title" => "Transaction Messages - Transaction '{id}'",
+		"messages.participant" => "Participant Id",
+		"messages.message" => "Message",
+		"messages.public_key" => "Public Key",
+		"messages.signature" => "Signature",
+		"proof.title" => "Payment Proof - Transaction '{id}'",
+		"proof.receiver_address" => "Receiver Address",
+		"proof.receiver_onion" => "Receiver Address (Onion V3)",
+		"proof.receiver_signature" => "Receiver Signature",
+		"proof.amount" => "Amount",
+		"proof.kernel_excess" => "Kernel Excess",
+		"proof.sender_address" => "Sender Address",
+		"proof.sender_signature" => "Sender Signature",
+		"proof.sender_onion" => "Sender Address (Onion V3)",
+		"slate.title" => "Slate '{id}'",
+		"slate.version" => "Version",
+		"slate.converted" => "{version} (converted from {orig_version})",
+		"slate.block_header_version" => "Block Header Version",
+		"slate.num_participants" => "Participants",
+		"slate.height" => "Height",
+		"slate.lock_height" => "Lock Height",
+		"slate.inputs" => "Inputs",
+		"slate.input_kinds" => "{count} ({coinbase} coinbase, {plain} plain)",
+		"slate.mixed_inputs" => "Spends coinbase and plain outputs together",
+		"slate.outputs" => "Outputs",
+		"slate.kernels" => "Kernels",
+		"slate.features" => "Features",
+		"slate.participants" => "Participant Data",
+		"slate.public_excess" => "Public Blind Excess",
+		"slate.public_nonce" => "Public Nonce",
+		"slate.signed" => "Signed?",
+		"slate.payment_proof" => "Payment Proof",
+		"slate_diff.title" => "Slate Changes - '{from}' to '{to}'",
+		"slate_diff.none" => "The slates are identical",
+		"slate_diff.field" => "Field",
+		"slate_diff.before" => "Before",
+		"slate_diff.after" => "After",
+		"config.title" => "Effective Wallet Configuration",
+		"config.wallet_file" => "Wallet configuration file",
+		"config.system_file" => "System-wide configuration file",
+		"config.key" => "Setting",
+		"config.value" => "Value",
+		"config.source" => "From",
+		"config.default" => "default",
+		"config.system" => "system",
+		"config.wallet" => "wallet",
+		_ => return None,
+	};
+	Some(msg)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn amount_formatting() {
+		let en = Localizer::new(None, None, DisplayUnit::Epic, None);
+		assert_eq!(en.amount(1_234_500_000_000, false), "12345.00000000");
+		assert_eq!(en.amount(1_234_500_000_000, true), "12345.0");
+
+		let de = Localizer::new(Some("de-DE"), None, DisplayUnit::Epic, Some(2));
+		assert_eq!(de.amount(123_456_789_000_000, false), "1.234.567,89");
+		assert_eq!(de.amount(100_000_000, true), "1,0");
+
+		let none = Localizer::new(Some("fr"), None, DisplayUnit::Epic, Some(0));
+		assert_eq!(none.amount(123_456_789_000_000, false), "1 234 567");
+
+		let milli = Localizer::new(None, None, DisplayUnit::Milli, None);
+		assert_eq!(milli.amount(123_456_789, false), "1234.56789");
+		let raw = Localizer::new(None, None, DisplayUnit::Raw, None);
+		assert_eq!(raw.amount(123_456_789, true), "123456789");
+	}
+
+	#[test]
+	fn amount_parsing() {
+		let en = Localizer::new(None, None, DisplayUnit::Epic, None);
+		assert_eq!(en.parse_amount("1.5"), Ok(150_000_000));
+		assert_eq!(en.parse_amount("12"), Ok(1_200_000_000));
+		assert!(en.parse_amount("1,5").is_err());
+		assert!(en.parse_amount("1.000.000").is_err());
+		assert!(en.parse_amount("1.").is_err());
+		assert!(en.parse_amount(".5").is_err());
+		assert!(en.parse_amount("-1").is_err());
+		assert!(en.parse_amount("0.000000001").is_err());
+		assert!(en.parse_amount("999999999999999").is_err());
+
+		let micro = Localizer::new(Some("de"), None, DisplayUnit::Micro, None);
+		assert_eq!(micro.parse_amount("3,25"), Ok(325));
+		assert!(micro.parse_amount("3.25").is_err());
+		assert!(micro.parse_amount("3,125").is_err());
+
+		let raw = Localizer::new(None, None, DisplayUnit::Raw, None);
+		assert_eq!(raw.parse_amount("42"), Ok(42));
+		assert!(raw.parse_amount("4.2").is_err());
+	}
+
+	#[test]
+	fn message_fallback() {
+		let l = Localizer::default();
+		assert_eq!(l.message("accounts.name"), "Name");
+		assert_eq!(l.message("unknown.key"), "unknown.key");
+	}
+}
@@ -31,9 +31,19 @@ use epic_wallet_util::epic_keychain as keychain;
 use epic_wallet_util::epic_util as util;
 use failure;
 
+mod auth;
 pub mod command;
 pub mod controller;
+mod cors;
 pub mod display;
 mod error;
+mod events;
+mod export;
+mod foreign_access;
+pub mod i18n;
+mod mtls;
+mod sni;
 
+pub use crate::cors::CorsMiddleware;
 pub use crate::error::{Error, ErrorKind};
+pub use crate::foreign_access::ForeignAccess;
@@ -0,0 +1,214 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Listener over TLS only accepting the clients presenting a certificate signed by
+//! a given CA, the API server not asking clients for certificates
+
+use crate::api::{Router, TLSConfig};
+use crate::error::{Error, ErrorKind};
+use futures::{Future, Stream};
+use hyper::Server;
+use rustls::internal::pemfile;
+use rustls::{AllowAnyAuthenticatedClient, Certificate, PrivateKey, RootCertStore, ServerConfig};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::timer::Timeout;
+use tokio_rustls::TlsAcceptor;
+
+/// Time a client has to complete the TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum number of TLS handshakes in progress, the clients connecting beyond it
+/// waiting for one of them to end
+const MAX_PENDING_HANDSHAKES: usize = 256;
+
+fn open(path: &str) -> Result<BufReader<File>, Error> {
+	let file = File::open(path)
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to open {}: {}", path, e)))?;
+	Ok(BufReader::new(file))
+}
+
+pub(crate) fn load_certs(path: &str) -> Result<Vec<Certificate>, Error> {
+	let certs = pemfile::certs(&mut open(path)?)
+		.map_err(|_| ErrorKind::GenericError(format!("Invalid certificates in {}", path)))?;
+	if certs.is_empty() {
+		return Err(ErrorKind::GenericError(format!("No certificate in {}", path)).into());
+	}
+	Ok(certs)
+}
+
+pub(crate) fn load_private_key(path: &str) -> Result<PrivateKey, Error> {
+	let invalid = || ErrorKind::GenericError(format!("Invalid private key in {}", path));
+	let mut keys = pemfile::pkcs8_private_keys(&mut open(path)?).map_err(|_| invalid())?;
+	if keys.is_empty() {
+		keys = pemfile::rsa_private_keys(&mut open(path)?).map_err(|_| invalid())?;
+	}
+	keys.into_iter().next().ok_or_else(|| invalid().into())
+}
+
+/// TLS configuration of a listener requiring client certificates signed by the CA
+fn server_config(tls_config: &TLSConfig, client_ca_file: &str) -> Result<ServerConfig, Error> {
+	let mut roots = RootCertStore::empty();
+	for ca in load_certs(client_ca_file)? {
+		roots.add(&ca).map_err(|e| {
+			ErrorKind::GenericError(format!("Invalid CA certificate {}: {}", client_ca_file, e))
+		})?;
+	}
+	let mut config = ServerConfig::new(AllowAnyAuthenticatedClient::new(roots));
+	config
+		.set_single_cert(
+			load_certs(&tls_config.certificate)?,
+			load_private_key(&tls_config.private_key)?,
+		)
+		.map_err(|e| ErrorKind::GenericError(format!("Invalid TLS certificate: {}", e)))?;
+	Ok(config)
+}
+
+/// Start serving the router at the address, only to the clients authenticated with a
+/// certificate signed by the CA of `client_ca_file`
+pub fn start_mutual_tls(
+	addr: SocketAddr,
+	router: Router,
+	tls_config: &TLSConfig,
+	client_ca_file: &str,
+) -> Result<thread::JoinHandle<()>, Error> {
+	serve_tls(addr, router, server_config(tls_config, client_ca_file)?)
+}
+
+/// Start serving the router at the address over TLS, with the given configuration
+pub(crate) fn serve_tls(
+	addr: SocketAddr,
+	router: Router,
+	config: ServerConfig,
+) -> Result<thread::JoinHandle<()>, Error> {
+	let acceptor = TlsAcceptor::from(Arc::new(config));
+	let listener = TcpListener::bind(&addr)
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to bind {}: {}", addr, e)))?;
+	thread::Builder::new()
+		.name("apis".to_string())
+		.spawn(move || {
+			// the handshakes run concurrently, so a slow or stalled client doesn't hold
+			// up the others. The clients failing the handshake, or not completing it in
+			// time, are dropped, the others served.
+			let tls = listener
+				.incoming()
+				.then(|r| match r {
+					Ok(s) => Ok::<_, io::Error>(Some(s)),
+					Err(e) => {
+						warn!("Unable to accept a client of the listener: {}", e);
+						Ok(None)
+					}
+				})
+				.filter_map(|s| s)
+				.map(move |s| {
+					Timeout::new(acceptor.accept(s), HANDSHAKE_TIMEOUT).then(|r| match r {
+						Ok(s) => Ok::<_, io::Error>(Some(s)),
+						Err(e) => {
+							warn!("Refused a client of the listener: {}", e);
+							Ok(None)
+						}
+					})
+				})
+				.buffer_unordered(MAX_PENDING_HANDSHAKES)
+				.filter_map(|s| s);
+			let server = Server::builder(tls)
+				.serve(router)
+				.map_err(|e| error!("HTTP API server error: {}", e));
+			hyper::rt::run(server);
+		})
+		.map_err(|e| ErrorKind::GenericError(format!("API thread failed to start: {}", e)).into())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::api::{Handler, ResponseFuture};
+	use futures::future::ok;
+	use hyper::{Body, Request, Response, StatusCode};
+	use rustls::{ClientConfig, ClientSession};
+	use std::io::{Read, Write};
+	use std::net::TcpStream;
+	use tokio_rustls::webpki::DNSNameRef;
+
+	struct OkHandler;
+
+	impl Handler for OkHandler {
+		fn get(&self, _req: Request<Body>) -> ResponseFuture {
+			let res = Response::builder()
+				.status(StatusCode::OK)
+				.body(Body::from("ok"))
+				.unwrap();
+			Box::new(ok(res))
+		}
+	}
+
+	fn data_file(name: &str) -> String {
+		format!("{}/tests/data/mtls/{}", env!("CARGO_MANIFEST_DIR"), name)
+	}
+
+	/// Start of the response of the listener to a client with the given certificate,
+	/// empty if the client was refused
+	fn get(addr: SocketAddr, client_cert: Option<&str>) -> String {
+		let mut config = ClientConfig::new();
+		config
+			.root_store
+			.add(&load_certs(&data_file("ca.pem")).unwrap()[0])
+			.unwrap();
+		if let Some(c) = client_cert {
+			config.set_single_client_cert(
+				load_certs(&data_file(&format!("{}.pem", c))).unwrap(),
+				load_private_key(&data_file(&format!("{}.key", c))).unwrap(),
+			);
+		}
+		let name = DNSNameRef::try_from_ascii_str("localhost").unwrap();
+		let mut session = ClientSession::new(&Arc::new(config), name);
+		let mut sock = TcpStream::connect(addr).unwrap();
+		let mut tls = rustls::Stream::new(&mut session, &mut sock);
+		let req = "GET /v2/test HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+		let mut res = vec![];
+		if tls.write_all(req.as_bytes()).is_ok() {
+			let mut buf = [0; 1024];
+			while let Ok(n) = tls.read(&mut buf) {
+				if n == 0 {
+					break;
+				}
+				res.extend_from_slice(&buf[..n]);
+			}
+		}
+		String::from_utf8_lossy(&res).into_owned()
+	}
+
+	#[test]
+	fn mutual_tls_clients() {
+		let addr: SocketAddr = "127.0.0.1:43423".parse().unwrap();
+		let mut router = Router::new();
+		router.add_route("/v2/test", Arc::new(OkHandler)).unwrap();
+		let tls_config = TLSConfig::new(data_file("server.pem"), data_file("server.key"));
+		start_mutual_tls(addr, router, &tls_config, &data_file("ca.pem")).unwrap();
+		thread::sleep(Duration::from_millis(500));
+
+		// a client which never starts its handshake doesn't hold up the others
+		let _stalled = TcpStream::connect(addr).unwrap();
+
+		assert!(get(addr, Some("client")).starts_with("HTTP/1.1 200"));
+		assert_eq!(get(addr, None), "");
+		assert_eq!(get(addr, Some("other_client")), "");
+		assert!(get(addr, Some("client")).starts_with("HTTP/1.1 200"));
+	}
+}
@@ -0,0 +1,92 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Listener over TLS presenting the certificate of the hostname a client asks for
+//! (SNI), the API server only taking a single certificate
+
+use crate::api::{Router, TLSConfig};
+use crate::error::{Error, ErrorKind};
+use crate::mtls::{load_certs, load_private_key, serve_tls};
+use rustls::sign::{self, CertifiedKey};
+use rustls::{ClientHello, NoClientAuth, ResolvesServerCert, ServerConfig};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::thread;
+
+fn certified_key(tls_config: &TLSConfig) -> Result<CertifiedKey, Error> {
+	let key = load_private_key(&tls_config.private_key)?;
+	let key = sign::any_supported_type(&key).map_err(|_| {
+		ErrorKind::GenericError(format!(
+			"Unsupported private key in {}",
+			tls_config.private_key
+		))
+	})?;
+	Ok(CertifiedKey::new(
+		load_certs(&tls_config.certificate)?,
+		Arc::new(key),
+	))
+}
+
+/// Certificate of each hostname, the default one presented to the clients asking
+/// for another hostname or none
+struct SniResolver {
+	by_host: HashMap<String, CertifiedKey>,
+	default: CertifiedKey,
+}
+
+impl SniResolver {
+	fn new(
+		default: &TLSConfig,
+		by_host: &HashMap<String, TLSConfig>,
+	) -> Result<SniResolver, Error> {
+		let mut res = SniResolver {
+			by_host: HashMap::new(),
+			default: certified_key(default)?,
+		};
+		for (host, tls_config) in by_host {
+			res.by_host
+				.insert(host.to_lowercase(), certified_key(tls_config)?);
+		}
+		Ok(res)
+	}
+
+	fn certified_key(&self, host: Option<&str>) -> &CertifiedKey {
+		host.and_then(|h| self.by_host.get(&h.to_lowercase()))
+			.unwrap_or(&self.default)
+	}
+}
+
+impl ResolvesServerCert for SniResolver {
+	fn resolve(&self, client_hello: ClientHello) -> Option<CertifiedKey> {
+		let host = client_hello.server_name().map(|n| {
+			let n: &str = n.into();
+			n
+		});
+		Some(self.certified_key(host).clone())
+	}
+}
+
+/// Start serving the router at the address over TLS, presenting the certificate of
+/// `by_host` for the hostname the client asks for, and `default` for the others
+pub fn start_sni_tls(
+	addr: SocketAddr,
+	router: Router,
+	default: &TLSConfig,
+	by_host: &HashMap<String, TLSConfig>,
+) -> Result<thread::JoinHandle<()>, Error> {
+	let mut config = ServerConfig::new(NoClientAuth::new());
+	config.cert_resolver = Arc::new(SniResolver::new(default, by_host)?);
+	serve_tls(addr, router, config)
+}
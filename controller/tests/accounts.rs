@@ -97,6 +97,21 @@ fn accounts_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
 		Ok(())
 	})?;
 
+	// attach metadata to an account
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_account_metadata(m, "account1", "customer_id", Some("1234"))?;
+		api.set_account_metadata(m, "account1", "department", Some("sales"))?;
+		let mapping = api.set_account_metadata(m, "account1", "department", None)?;
+		assert_eq!(mapping.metadata.len(), 1);
+		let accounts = api.accounts(m)?;
+		let account1 = accounts.iter().find(|a| a.label == "account1").unwrap();
+		assert_eq!(account1.metadata.get("customer_id").unwrap(), "1234");
+		assert!(api
+			.set_account_metadata(m, "no_such_account", "customer_id", Some("1"))
+			.is_err());
+		Ok(())
+	})?;
+
 	// add account to wallet 2
 	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
 		let new_path = api.create_account_path(m, "listener_account").unwrap();
@@ -0,0 +1,97 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the owner API versions and method deprecations reported match the methods served
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_api::{Owner, OwnerRpc};
+use epic_wallet_libwallet as libwallet;
+
+use easy_jsonrpc_mw::{Handler, MaybeReply};
+use impls::test_framework::LocalWalletClient;
+use libwallet::api_impl::foreign;
+use libwallet::ApiStatus;
+use serde_json::json;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Error code of the reply to a call of the method without its arguments
+fn error_code(api: &dyn OwnerRpc, method: &str) -> i64 {
+	let call = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": []});
+	match api.handle_request(call) {
+		MaybeReply::Reply(r) => r["error"]["code"].as_i64().unwrap(),
+		MaybeReply::DontReply => panic!("no reply"),
+	}
+}
+
+fn api_versions_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		_mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+
+	let api = Owner::new(wallet1.clone(), None);
+	let versions = api.get_api_versions()?;
+	assert_eq!(versions.build_version, env!("CARGO_PKG_VERSION"));
+	assert_eq!(
+		versions.foreign_api_version,
+		foreign::check_version().foreign_api_version
+	);
+
+	// the V2 API is deprecated in favour of the V3 one
+	let served: Vec<(u16, &str, ApiStatus)> = versions
+		.owner_api_versions
+		.iter()
+		.map(|v| (v.version, v.endpoint.as_str(), v.status.clone()))
+		.collect();
+	assert_eq!(
+		served,
+		vec![
+			(2, "/v2/owner", ApiStatus::Deprecated),
+			(3, "/v3/owner", ApiStatus::Supported),
+		]
+	);
+	assert!(versions.owner_api_versions[1].deprecated_methods.is_empty());
+
+	// the methods reported as removed aren't served any more, their replacements are
+	let v2 = &versions.owner_api_versions[0];
+	assert!(!v2.deprecated_methods.is_empty());
+	for d in v2.deprecated_methods.iter() {
+		assert_eq!(d.status, ApiStatus::Removed);
+		assert_eq!(error_code(&api, &d.method), -32601, "{}", d.method);
+		let replacement = d.replacement.as_ref().unwrap();
+		assert_eq!(error_code(&api, replacement), -32602, "{}", replacement);
+	}
+	Ok(())
+}
+
+#[test]
+fn wallet_api_versions() {
+	let test_dir = "test_output/api_versions";
+	setup(test_dir);
+	if let Err(e) = api_versions_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,176 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test alerts on the spendable balance of accounts crossing thresholds
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core as core;
+use epic_wallet_util::epic_keychain::Keychain;
+use epic_wallet_util::epic_util::secp::key::SecretKey;
+use epic_wallet_util::epic_util::Mutex;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::api_impl::owner_updater::BalanceAlerts;
+use libwallet::{
+	BalanceAlert, BalanceAlertHook, BalanceAlertKind, BalanceAlertPolicy, BalanceThreshold,
+	InitTxArgs, NodeClient, WalletInst, WalletLCProvider,
+};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Keeps the alerts it's notified of
+#[derive(Default)]
+struct RecordingHook(Mutex<Vec<BalanceAlert>>);
+
+impl BalanceAlertHook for RecordingHook {
+	fn alert(&self, alert: &BalanceAlert) -> Result<(), libwallet::Error> {
+		self.0.lock().push(alert.clone());
+		Ok(())
+	}
+}
+
+/// Refresh the wallet from the node as the updater does before checking balances
+fn refresh<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	mask: Option<&SecretKey>,
+) -> Result<(), libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	wallet::controller::owner_single_use(wallet, mask, |api, m| {
+		let (refreshed, _) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(refreshed);
+		Ok(())
+	})
+}
+
+fn balance_alerts_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// a payout account, funded from the mining account
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "payouts")?;
+		Ok(())
+	})?;
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let hook = Arc::new(RecordingHook::default());
+	let policy = BalanceAlertPolicy {
+		thresholds: vec![
+			BalanceThreshold {
+				account: "payouts".to_owned(),
+				below: Some(reward),
+				above: None,
+			},
+			BalanceThreshold {
+				account: "default".to_owned(),
+				below: None,
+				above: Some(reward),
+			},
+		],
+		minimum_confirmations: 1,
+	};
+	let mut alerts = BalanceAlerts::new(policy, Some(hook.clone()));
+
+	// the empty payout account is below its threshold, the mining account above
+	refresh(wallet1.clone(), mask1)?;
+	alerts.check(wallet1.clone(), mask1, &None)?;
+	{
+		let raised = hook.0.lock();
+		assert_eq!(raised.len(), 2);
+		assert_eq!(raised[0].account, "payouts");
+		assert_eq!(raised[0].kind, BalanceAlertKind::Below);
+		assert_eq!(raised[0].spendable, 0);
+		assert_eq!(raised[1].account, "default");
+		assert_eq!(raised[1].kind, BalanceAlertKind::Above);
+		assert!(raised[1].spendable > reward);
+	}
+
+	// and only alerted once
+	refresh(wallet1.clone(), mask1)?;
+	alerts.check(wallet1.clone(), mask1, &None)?;
+	assert_eq!(hook.0.lock().len(), 2);
+
+	// refill the payout account, which is then within its threshold
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward * 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+		let mut slate = api.init_send_tx(m, args)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		wallet::controller::foreign_single_use(wallet1.clone(), mask1_i.clone(), |api| {
+			slate = api.receive_tx(&slate, Some("payouts"), None)?;
+			Ok(())
+		})?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	refresh(wallet1.clone(), mask1)?;
+	alerts.check(wallet1.clone(), mask1, &None)?;
+	assert_eq!(hook.0.lock().len(), 2);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_balance_alerts() {
+	let test_dir = "test_output/balance_alerts";
+	setup(test_dir);
+	if let Err(e) = balance_alerts_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
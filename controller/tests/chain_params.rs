@@ -0,0 +1,107 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test overriding the coinbase maturity of a custom chain
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_config::ChainParamsConfig;
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core::global::{self, ChainTypes};
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{check_node_chain, coinbase_maturity, set_chain_params};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn chain_params_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// only custom chains can be given other parameters
+	let params = ChainParamsConfig {
+		coinbase_maturity: Some(2),
+	};
+	assert!(set_chain_params(&ChainTypes::Mainnet, Some(&params)).is_err());
+	assert!(set_chain_params(&ChainTypes::Floonet, Some(&params)).is_err());
+	let zero = ChainParamsConfig {
+		coinbase_maturity: Some(0),
+	};
+	assert!(set_chain_params(&ChainTypes::AutomatedTesting, Some(&zero)).is_err());
+	assert_eq!(coinbase_maturity(), global::coinbase_maturity());
+
+	set_chain_params(&ChainTypes::AutomatedTesting, Some(&params))?;
+	assert_eq!(coinbase_maturity(), 2);
+	check_node_chain(&client1)?;
+
+	// coinbase outputs mature as configured
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert!(!outputs.is_empty());
+		for o in outputs.iter() {
+			assert_eq!(o.output.lock_height, o.output.height + 2);
+		}
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		let immature: u64 = outputs
+			.iter()
+			.filter(|o| o.output.height + 2 > info.last_confirmed_height)
+			.map(|o| o.output.value)
+			.sum();
+		assert!(immature > 0);
+		assert_eq!(info.amount_immature, immature);
+		Ok(())
+	})?;
+
+	// without parameters the chain type's are used again
+	set_chain_params(&ChainTypes::AutomatedTesting, None)?;
+	assert_eq!(coinbase_maturity(), global::coinbase_maturity());
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn chain_params() {
+	let test_dir = "test_output/chain_params";
+	setup(test_dir);
+	if let Err(e) = chain_params_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
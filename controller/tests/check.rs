@@ -154,6 +154,23 @@ fn scan_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
 		Ok(())
 	})?;
 
+	// a preview should find the missing outputs without restoring them
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let preview = api.scan_preview(m, None, None)?;
+		assert_eq!(preview.start_height, 1);
+		assert_eq!(preview.end_height, bh);
+		assert_eq!(preview.num_outputs, bh as usize);
+		assert_eq!(preview.total_value, bh * reward);
+		assert_eq!(preview.num_missing, 2);
+		assert_eq!(preview.missing_value, 2 * reward);
+		let preview = api.scan_preview(m, None, Some(bh + 100))?;
+		assert_eq!(preview.end_height, bh);
+		assert!(api.scan_preview(m, Some(bh), Some(1)).is_err());
+		let (_, outputs) = api.retrieve_outputs(m, true, true, false, None)?;
+		assert_eq!(outputs.len(), bh as usize - 2);
+		Ok(())
+	})?;
+
 	// this should restore our missing outputs
 	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
 		api.scan(m, None, true)?;
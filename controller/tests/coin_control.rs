@@ -0,0 +1,132 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test transactions spending the inputs they're given
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::secp::pedersen::Commitment;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs, OutputStatus};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn coin_control_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		// the two oldest rewards, which are mature
+		let mut spendable: Vec<_> = outputs
+			.iter()
+			.filter(|o| o.output.lock_height <= info.last_confirmed_height)
+			.collect();
+		spendable.sort_by_key(|o| o.output.height);
+		let pinned: Vec<Commitment> = spendable[..2].iter().map(|o| o.commit).collect();
+		let value: u64 = spendable[..2].iter().map(|o| o.output.value).sum();
+
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: value / 2,
+			minimum_confirmations: 1,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			inputs: Some(pinned.clone()),
+			..Default::default()
+		};
+		let slate = api.init_send_tx(m, args.clone())?;
+		let inputs: HashSet<_> = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+		assert_eq!(inputs, pinned.iter().cloned().collect());
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// the same outputs are locked now
+		match api.init_send_tx(m, args.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnspendableInput(_, r) => assert!(r.contains("locked")),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("locked output spent"),
+		}
+
+		// an immature reward can't be given either
+		let immature = outputs
+			.iter()
+			.find(|o| o.output.lock_height > info.last_confirmed_height)
+			.unwrap();
+		let args = InitTxArgs {
+			inputs: Some(vec![immature.commit]),
+			..args
+		};
+		match api.init_send_tx(m, args) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnspendableInput(_, r) => assert!(r.contains("mature")),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("immature output spent"),
+		}
+
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		let locked = outputs
+			.iter()
+			.filter(|o| o.output.status == OutputStatus::Locked)
+			.count();
+		assert_eq!(locked, 2);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_coin_control() {
+	let test_dir = "test_output/coin_control";
+	setup(test_dir);
+	if let Err(e) = coin_control_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
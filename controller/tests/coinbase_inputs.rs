@@ -0,0 +1,148 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the coinbase and plain inputs of sends are reported, and sends can avoid
+//! mixing them
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use self::core::global;
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn coinbase_inputs_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = global::coinbase_maturity() as usize;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 3, false);
+
+	let args = |amount: u64, avoid_mixed_coinbase: bool| InitTxArgs {
+		src_acct_name: None,
+		amount,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		avoid_mixed_coinbase: Some(avoid_mixed_coinbase),
+		..Default::default()
+	};
+
+	// wallet 1 only holds coinbase outputs, and records spending one
+	let mut slate_id = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let est = api.estimate_send_tx(m, &args(reward / 2, true))?;
+		assert_eq!(est.input_composition.coinbase_inputs, 1);
+		assert_eq!(est.input_composition.coinbase_amount, reward);
+		assert!(!est.input_composition.is_mixed());
+
+		let mut slate = api.init_send_tx(m, args(reward / 2, true))?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		slate_id = Some(slate.id);
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, None, slate_id)?;
+		let composition = txs[0].input_composition.clone().unwrap();
+		assert_eq!(composition.coinbase_inputs, 1);
+		assert_eq!(composition.plain_inputs, 0);
+		Ok(())
+	})?;
+
+	// wallet 2 holds a plain output of half a reward and a coinbase output
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet2.clone(), mask2, 1, false);
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm, false);
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		api.retrieve_summary_info(m, true, 1)?;
+
+		// the plain output doesn't cover the amount, the coinbase one does
+		let est = api.estimate_send_tx(m, &args(reward * 3 / 4, true))?;
+		assert_eq!(est.input_composition.coinbase_inputs, 1);
+		assert_eq!(est.input_composition.plain_inputs, 0);
+
+		// only both together cover the amount
+		let est = api.estimate_send_tx(m, &args(reward * 5 / 4, false))?;
+		assert!(est.input_composition.is_mixed());
+		assert_eq!(est.input_composition.plain_amount, reward / 2);
+		match api.estimate_send_tx(m, &args(reward * 5 / 4, true)) {
+			Err(e) => match e.kind() {
+				ErrorKind::MixedCoinbaseInputs(_) => {}
+				k => panic!("unexpected error: {}", k),
+			},
+			Ok(_) => panic!("the send mixes coinbase and plain inputs"),
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn coinbase_inputs() {
+	let test_dir = "test_output/coinbase_inputs";
+	setup(test_dir);
+	if let Err(e) = coinbase_inputs_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -23,7 +23,7 @@ use epic_wallet_util::epic_util as util;
 use self::core::global;
 use self::core::global::ChainTypes;
 use self::keychain::ExtKeychain;
-use self::libwallet::WalletInst;
+use self::libwallet::{ViewKey, WalletInst};
 use impls::test_framework::{LocalWalletClient, WalletProxy};
 use impls::{DefaultLCProvider, DefaultWalletImpl};
 use std::fs;
@@ -122,7 +122,7 @@ pub fn create_local_wallet(
 		>;
 	let lc = wallet.lc_provider().unwrap();
 	let _ = lc.set_top_level_directory(&format!("{}/{}", test_dir, name));
-	lc.create_wallet(None, mnemonic, 32, ZeroingString::from(""), false)
+	lc.create_wallet(None, mnemonic, 32, None, ZeroingString::from(""), false)
 		.unwrap();
 	let mask = lc
 		.open_wallet(None, ZeroingString::from(""), create_mask, false)
@@ -166,3 +166,39 @@ pub fn open_local_wallet(
 		.unwrap();
 	(Arc::new(Mutex::new(wallet)), mask)
 }
+
+#[allow(dead_code)]
+pub fn create_watch_only_wallet(
+	test_dir: &str,
+	name: &str,
+	view_key: ViewKey,
+	client: LocalWalletClient,
+) -> Arc<
+	Mutex<
+		Box<
+			dyn WalletInst<
+				'static,
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>,
+	>,
+> {
+	let mut wallet = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client).unwrap())
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	let lc = wallet.lc_provider().unwrap();
+	let _ = lc.set_top_level_directory(&format!("{}/{}", test_dir, name));
+	lc.create_watch_only_wallet(None, view_key).unwrap();
+	let mask = lc
+		.open_wallet(None, ZeroingString::from(""), false, false)
+		.unwrap();
+	assert!(mask.is_none());
+	Arc::new(Mutex::new(wallet))
+}
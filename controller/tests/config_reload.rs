@@ -0,0 +1,98 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test a reload of the configuration applies the settings it can while running
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_api::Owner;
+use epic_wallet_config::GlobalWalletConfig;
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core::global::ChainTypes;
+use impls::test_framework::LocalWalletClient;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn config_reload_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		_mask1,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let api = Owner::new(wallet1.clone(), None);
+	// nothing to reload without a configuration
+	assert!(api.reload_config().is_err());
+
+	let path = format!("{}/epic-wallet.toml", test_dir);
+	let mut config = GlobalWalletConfig::for_chain(&ChainTypes::AutomatedTesting);
+	config.write_to_file(&path).unwrap();
+	api.set_config(Some(GlobalWalletConfig::new(&path).unwrap()));
+
+	let reload = api.reload_config()?;
+	assert!(reload.applied.is_empty());
+	assert!(reload.restart_required.is_empty());
+
+	// the node is applied, the listening port requires a restart
+	{
+		let members = config.members.as_mut().unwrap();
+		members.wallet.check_node_api_http_addr = "http://127.0.0.1:23413".to_owned();
+		members.wallet.api_listen_port = 23415;
+	}
+	config.write_to_file(&path).unwrap();
+	let reload = api.reload_config()?;
+	assert_eq!(reload.applied, vec!["wallet.check_node_api_http_addr"]);
+	assert_eq!(reload.restart_required, vec!["wallet.api_listen_port"]);
+
+	// changes are only reported once
+	let reload = api.reload_config()?;
+	assert!(reload.applied.is_empty());
+
+	// an unreadable file leaves the configuration as it was
+	std::fs::write(&path, "[wallet\n").unwrap();
+	assert!(api.reload_config().is_err());
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn config_reload() {
+	let test_dir = "test_output/config_reload";
+	setup(test_dir);
+	if let Err(e) = config_reload_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
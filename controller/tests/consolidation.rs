@@ -0,0 +1,142 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sends consolidating small outputs into their change when the fee allows it
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use self::core::global;
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::InitTxArgs;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn consolidation_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = global::coinbase_maturity() as usize;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 5, false);
+
+	let args = |max_outputs: u32, threshold: Option<u64>| InitTxArgs {
+		src_acct_name: None,
+		amount: reward * 3 / 2,
+		minimum_confirmations: 2,
+		max_outputs,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		consolidation_fee_threshold: threshold,
+		..Default::default()
+	};
+
+	let mut num_spendable = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// two outputs cover the amount
+		let est = api.estimate_send_tx(m, &args(500, None))?;
+		assert_eq!(est.inputs.len(), 2);
+
+		// the other spendable outputs are consolidated too
+		let est = api.estimate_send_tx(m, &args(500, Some(reward)))?;
+		assert!(est.inputs.len() > 2);
+		assert_eq!(est.change, est.total - est.amount - est.fee);
+		num_spendable = est.inputs.len();
+
+		// but no more than the maximum number of inputs
+		let est = api.estimate_send_tx(m, &args(3, Some(reward)))?;
+		assert_eq!(est.inputs.len(), 3);
+
+		// and not alongside inputs given
+		let given = InitTxArgs {
+			inputs: Some(vec![
+				est.inputs[0].commit.clone(),
+				est.inputs[1].commit.clone(),
+			]),
+			..args(500, Some(reward))
+		};
+		assert_eq!(api.estimate_send_tx(m, &given)?.inputs.len(), 2);
+
+		let mut slate = api.init_send_tx(m, args(500, Some(reward)))?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// the consolidated outputs are spent, leaving the change in their place
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, true, true, false, None)?;
+		let spent = outputs
+			.iter()
+			.filter(|o| o.output.status == libwallet::OutputStatus::Spent)
+			.count();
+		assert_eq!(spent, num_spendable);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn consolidation() {
+	let test_dir = "test_output/consolidation";
+	setup(test_dir);
+	if let Err(e) = consolidation_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
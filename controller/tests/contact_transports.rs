@@ -0,0 +1,159 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the transports remembered for address book contacts
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{address, Contact, InitTxArgs, Slate};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn contact_transports_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut proof_address = None;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		proof_address = Some(api.get_public_proof_address(m, 0)?);
+		Ok(())
+	})?;
+	let onion = address::onion_v3_from_pubkey(&proof_address.unwrap())?;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let contact = Contact {
+			name: "bob".to_owned(),
+			proof_address: onion.clone(),
+			epicbox_address: None,
+			http_address: Some(" http://127.0.0.1:3415 ".to_owned()),
+			preferred_transport: Some("carrier-pigeon".to_owned()),
+			last_transport: None,
+		};
+		// unknown transports and malformed addresses are refused
+		assert!(api.save_contact(m, &contact).is_err());
+		let contact = Contact {
+			preferred_transport: Some("HTTP".to_owned()),
+			..contact
+		};
+		let bad_http = Contact {
+			http_address: Some("127.0.0.1:3415".to_owned()),
+			..contact.clone()
+		};
+		assert!(api.save_contact(m, &bad_http).is_err());
+
+		// the preferred transport comes first, those without an address are left out
+		let saved = api.save_contact(m, &contact)?;
+		assert_eq!(saved.http_address, Some("http://127.0.0.1:3415".to_owned()));
+		assert_eq!(saved.preferred_transport, Some("http".to_owned()));
+		assert_eq!(
+			saved.transports(),
+			vec![
+				("http".to_owned(), "http://127.0.0.1:3415".to_owned()),
+				("tor".to_owned(), onion.clone()),
+			]
+		);
+
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 1_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+		let slate_i = api.init_send_tx(m, args)?;
+		let slate: Slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// the transport is recorded on the transaction and the contact
+		api.record_tx_transport(m, slate.id, "tor", Some("bob"))?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].transport, Some("tor".to_owned()));
+		let bob = api.retrieve_contacts(m)?.remove(0);
+		assert_eq!(bob.last_transport, Some("tor".to_owned()));
+		assert_eq!(bob.transports()[1].0, "tor");
+
+		// and kept when the contact is saved again
+		let bob = api.save_contact(
+			m,
+			&Contact {
+				preferred_transport: None,
+				last_transport: None,
+				..bob
+			},
+		)?;
+		assert_eq!(bob.last_transport, Some("tor".to_owned()));
+		assert_eq!(bob.transports()[0].0, "tor");
+
+		assert!(api
+			.record_tx_transport(m, slate.id, "tor", Some("carol"))
+			.is_err());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_contact_transports() {
+	let test_dir = "test_output/contact_transports";
+	setup(test_dir);
+	if let Err(e) = contact_transports_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,146 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the address book and contact-aware transaction history
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{address, Contact, InitTxArgs, Slate};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// contacts impl
+fn contacts_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut proof_address = None;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		proof_address = Some(api.get_public_proof_address(m, 0)?);
+		Ok(())
+	})?;
+	let proof_address = proof_address.unwrap();
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// unknown contacts are refused
+		assert!(api.remove_contact(m, "bob").is_err());
+		assert!(api.retrieve_contact_txs(m, false, "bob").is_err());
+
+		let contact = Contact {
+			name: " bob ".to_owned(),
+			proof_address: address::onion_v3_from_pubkey(&proof_address)?.to_uppercase(),
+			epicbox_address: None,
+			http_address: None,
+			preferred_transport: None,
+			last_transport: None,
+		};
+		let saved = api.save_contact(m, &contact)?;
+		assert_eq!(saved.name, "bob");
+		assert_eq!(
+			saved.proof_address,
+			address::onion_v3_from_pubkey(&proof_address)?
+		);
+		assert_eq!(api.retrieve_contacts(m)?, vec![saved]);
+
+		// one transaction to the contact, one without a payment proof
+		for recipient in vec![Some(proof_address), None] {
+			let args = InitTxArgs {
+				src_acct_name: None,
+				amount: 1_000_000_000,
+				minimum_confirmations: 2,
+				max_outputs: 500,
+				num_change_outputs: 1,
+				selection_strategy_is_use_all: true,
+				payment_proof_recipient_address: recipient,
+				..Default::default()
+			};
+			let slate_i = api.init_send_tx(m, args)?;
+			let slate: Slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+			api.tx_lock_outputs(m, &slate, 0)?;
+		}
+
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(
+			txs.iter()
+				.filter(|t| t.contact == Some("bob".to_owned()))
+				.count(),
+			1
+		);
+		let (_, txs) = api.retrieve_contact_txs(m, false, "bob")?;
+		assert_eq!(txs.len(), 1);
+		assert!(txs[0].payment_proof.is_some());
+
+		// removing the contact drops the annotation
+		api.remove_contact(m, "bob")?;
+		assert!(api.retrieve_contacts(m)?.is_empty());
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert!(txs.iter().all(|t| t.contact.is_none()));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_contacts() {
+	let test_dir = "test_output/contacts";
+	setup(test_dir);
+	if let Err(e) = contacts_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,118 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test laying out accounts by a configured derivation scheme
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_config as config;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain as keychain;
+
+use self::keychain::ChildNumber;
+use config::DerivationConfig;
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn derivation_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// m/44'/k'/0 accounts
+	let derivation = DerivationConfig {
+		purpose: vec![44],
+		hardened_purpose: true,
+		hardened_account: true,
+		chain_level: Some(true),
+	};
+	{
+		wallet_inst!(wallet1, w);
+		libwallet::set_derivation_config(&mut **w, mask1, derivation.clone())?;
+		let path = w.parent_key_id().to_path();
+		assert_eq!(path.depth, 3);
+		assert_eq!(path.path[0], ChildNumber::Hardened { index: 44 });
+		assert_eq!(path.path[1], ChildNumber::Hardened { index: 0 });
+		assert_eq!(w.derivation_config()?, derivation);
+	}
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let accounts = api.accounts(m)?;
+		assert_eq!(accounts.len(), 1);
+		let default_path = accounts[0].path.clone();
+
+		// outputs are derived below the default account
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert!(!outputs.is_empty());
+		for o in outputs {
+			assert_eq!(o.output.key_id.to_path().depth, 4);
+			assert_eq!(o.output.key_id.parent_path(), default_path);
+		}
+
+		// new accounts follow the scheme
+		let path = api.create_account_path(m, "account1")?.to_path();
+		assert_eq!(path.depth, 3);
+		assert_eq!(path.path[0], ChildNumber::Hardened { index: 44 });
+		assert_eq!(path.path[1], ChildNumber::Hardened { index: 1 });
+		Ok(())
+	})?;
+
+	// the scheme can't be changed once the wallet is in use
+	{
+		wallet_inst!(wallet1, w);
+		assert!(
+			libwallet::set_derivation_config(&mut **w, mask1, DerivationConfig::default()).is_err()
+		);
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_derivation() {
+	let test_dir = "test_output/derivation";
+	setup(test_dir);
+	if let Err(e) = derivation_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
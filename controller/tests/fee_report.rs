@@ -0,0 +1,146 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the report of fees paid by sent transactions
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use chrono::{TimeZone, Utc};
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{FeePeriod, InitTxArgs};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn fee_report_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// two payments, the second spending more inputs
+	let mut fees = vec![];
+	for (amount, use_all) in [(60_000_000_000, false), (120_000_000_000, true)].iter() {
+		wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+			let args = InitTxArgs {
+				src_acct_name: None,
+				amount: *amount,
+				minimum_confirmations: 2,
+				max_outputs: 500,
+				num_change_outputs: 1,
+				selection_strategy_is_use_all: *use_all,
+				..Default::default()
+			};
+			let slate_i = api.init_send_tx(m, args)?;
+			let mut slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+			api.tx_lock_outputs(m, &slate, 0)?;
+			slate = api.finalize_tx(m, &slate)?;
+			api.post_tx(m, &slate.tx, false)?;
+			fees.push(slate.fee);
+			Ok(())
+		})?;
+		let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	}
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (refreshed, report) = api.fee_report(m, true, FeePeriod::Day)?;
+		assert!(refreshed);
+		assert_eq!(report.fees.num_txs, 2);
+		assert_eq!(report.fees.total_fees, fees.iter().sum::<u64>());
+		assert_eq!(report.fees.average_fee, fees.iter().sum::<u64>() / 2);
+		assert_eq!(report.fees.largest_fee, *fees.iter().max().unwrap());
+
+		assert_eq!(report.accounts.len(), 1);
+		let account = &report.accounts[0];
+		assert_eq!(account.account, "default");
+		assert_eq!(account.fees, report.fees);
+		let period_txs: u32 = account.periods.iter().map(|p| p.fees.num_txs).sum();
+		assert_eq!(period_txs, 2);
+
+		// largest first
+		assert_eq!(report.largest.len(), 2);
+		assert!(report.largest[0].fee >= report.largest[1].fee);
+		Ok(())
+	})?;
+
+	// the receiver paid no fees
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, report) = api.fee_report(m, true, FeePeriod::Month)?;
+		assert_eq!(report.fees.num_txs, 0);
+		assert!(report.accounts.is_empty());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_fee_report() {
+	let test_dir = "test_output/fee_report";
+	setup(test_dir);
+	if let Err(e) = fee_report_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
+
+#[test]
+fn fee_period_start() {
+	let t = Utc.with_ymd_and_hms(2020, 3, 19, 15, 4, 5).unwrap();
+	let day = Utc.with_ymd_and_hms(2020, 3, 19, 0, 0, 0).unwrap();
+	let monday = Utc.with_ymd_and_hms(2020, 3, 16, 0, 0, 0).unwrap();
+	let first = Utc.with_ymd_and_hms(2020, 3, 1, 0, 0, 0).unwrap();
+	assert_eq!(FeePeriod::Day.start(t), day);
+	assert_eq!(FeePeriod::Week.start(t), monday);
+	assert_eq!(FeePeriod::Month.start(t), first);
+	assert_eq!(FeePeriod::Week.start(monday), monday);
+}
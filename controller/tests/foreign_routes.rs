@@ -0,0 +1,172 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test routing the foreign API requests of a multi-wallet listener by path prefix
+//! and hostname
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::Mutex;
+use impls::test_framework::LocalWalletClient;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::controller::{multi_foreign_listener, ForeignWalletRoute, RequestLimits};
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+const LISTENER_ADDR: &str = "127.0.0.1:43421";
+
+/// Status code and body of the response to a foreign API call, sent to `path` for `host`
+fn post(path: &str, host: &str, body: &str) -> (u16, String) {
+	let mut stream = TcpStream::connect(LISTENER_ADDR).unwrap();
+	let req = format!(
+		"POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+		 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+		path,
+		host,
+		body.len(),
+		body
+	);
+	stream.write_all(req.as_bytes()).unwrap();
+	let mut res = String::new();
+	stream.read_to_string(&mut res).unwrap();
+	let status = res.split(' ').nth(1).unwrap().parse().unwrap();
+	let body = res.splitn(2, "\r\n\r\n").nth(1).unwrap_or("").to_owned();
+	(status, body)
+}
+
+/// Commitment of the coinbase output built by the wallet answering at `path` for
+/// `host`, the same for a wallet whatever the route it's reached by
+fn coinbase_commit(path: &str, host: &str) -> String {
+	let body = r#"{
+		"jsonrpc": "2.0",
+		"method": "build_coinbase",
+		"id": 1,
+		"params": [{"fees": 0, "height": 1, "key_id": "0300000000000000000000000100000000"}]
+	}"#;
+	let (status, res) = post(path, host, body);
+	assert_eq!(status, 200);
+	let res: serde_json::Value = serde_json::from_str(&res).unwrap();
+	res["result"]["Ok"]["output"]["commit"]
+		.as_str()
+		.unwrap()
+		.to_owned()
+}
+
+fn foreign_routes_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	create_wallet_and_add!(
+		_client3,
+		wallet3,
+		mask3,
+		test_dir,
+		"wallet3",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+
+	let routes = vec![
+		ForeignWalletRoute {
+			name: "wallet2".to_owned(),
+			hostnames: vec!["Wallet2.example.com".to_owned()],
+			tls_config: None,
+			wallet: wallet2,
+			keychain_mask: Arc::new(Mutex::new(mask2)),
+			receive_hook: None,
+		},
+		ForeignWalletRoute {
+			name: "wallet3".to_owned(),
+			hostnames: vec![],
+			tls_config: None,
+			wallet: wallet3,
+			keychain_mask: Arc::new(Mutex::new(mask3)),
+			receive_hook: None,
+		},
+	];
+	thread::spawn(move || {
+		let res = multi_foreign_listener(
+			wallet1,
+			Arc::new(Mutex::new(mask1)),
+			routes,
+			LISTENER_ADDR,
+			None,
+			false,
+			None,
+			RequestLimits::default(),
+			false,
+			None,
+		);
+		if let Err(e) = res {
+			panic!("Foreign listener error: {}", e);
+		}
+	});
+	thread::sleep(Duration::from_millis(500));
+
+	// a wallet is reached under its path prefix, and at the root for its hostnames
+	let commit2 = coinbase_commit("/wallet2/v2/foreign", "127.0.0.1");
+	assert_eq!(
+		coinbase_commit("/v2/foreign", "wallet2.example.com:43421"),
+		commit2
+	);
+	let commit3 = coinbase_commit("/wallet3/v2/foreign", "wallet2.example.com");
+	assert_ne!(commit3, commit2);
+
+	// the listener's wallet answers at the root for the other hostnames
+	let commit1 = coinbase_commit("/v2/foreign", "wallet3.example.com");
+	assert_ne!(commit1, commit2);
+	assert_ne!(commit1, commit3);
+	assert_eq!(coinbase_commit("/v2/foreign", "127.0.0.1"), commit1);
+
+	// unknown wallets aren't served
+	let (status, _) = post("/wallet4/v2/foreign", "127.0.0.1", "{}");
+	assert_eq!(status, 404);
+	Ok(())
+}
+
+#[test]
+fn foreign_routes() {
+	let test_dir = "test_output/foreign_routes";
+	setup(test_dir);
+	if let Err(e) = foreign_routes_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,151 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test caching block headers retrieved from the node
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core as core;
+use epic_wallet_util::epic_util as util;
+
+use self::core::core::hash::Hashed;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{BlockHeaderInfo, NodeClient, TxLogEntryType};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn header_cache_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+	let tip = chain.head_header().unwrap();
+
+	// an update caches the tip's header
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (refreshed, _) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(refreshed);
+		Ok(())
+	})?;
+	{
+		wallet_inst!(wallet1, w);
+		let cached = w.get_block_header(tip.height)?.unwrap();
+		assert_eq!(cached.hash, util::to_hex(tip.hash().to_vec()));
+		assert_eq!(cached.timestamp, tip.timestamp);
+	}
+
+	// ranges of headers are retrieved at once, up to the tip
+	let headers = client1.get_headers(1, tip.height + 10)?;
+	assert_eq!(headers.len() as u64, tip.height);
+	assert_eq!(headers.first().unwrap().height, 1);
+	assert_eq!(
+		headers.last().unwrap().hash,
+		util::to_hex(tip.hash().to_vec())
+	);
+	assert!(client1
+		.get_headers(tip.height + 1, tip.height + 10)?
+		.is_empty());
+
+	// confirmations are dated with the timestamp of their block
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		let coinbases: Vec<_> = txs
+			.iter()
+			.filter(|t| t.tx_type == TxLogEntryType::ConfirmedCoinbase)
+			.collect();
+		assert!(!coinbases.is_empty());
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		for t in coinbases {
+			let output = outputs
+				.iter()
+				.find(|o| o.output.tx_log_entry == Some(t.id))
+				.unwrap();
+			let block = chain.get_header_by_height(output.output.height).unwrap();
+			assert_eq!(t.confirmation_ts, Some(block.timestamp));
+		}
+		Ok(())
+	})?;
+
+	// other heights are retrieved from the node and cached
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let header = api.get_block_header(m, 2)?.unwrap();
+		assert_eq!(header.height, 2);
+		assert!(api.get_block_header(m, tip.height + 100)?.is_none());
+		Ok(())
+	})?;
+	{
+		wallet_inst!(wallet1, w);
+		assert!(w.get_block_header(2)?.is_some());
+		assert!(w.get_block_header(tip.height + 100)?.is_none());
+
+		// headers no longer on the node's chain
+		let mut batch = w.batch(mask1)?;
+		batch.save_block_header(BlockHeaderInfo {
+			height: tip.height + 2,
+			hash: "00".to_owned(),
+			timestamp: tip.timestamp,
+		})?;
+		batch.commit()?;
+	}
+
+	// are dropped on the next update
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.retrieve_summary_info(m, true, 1)?;
+		Ok(())
+	})?;
+	{
+		wallet_inst!(wallet1, w);
+		assert!(w.get_block_header(tip.height + 2)?.is_none());
+		assert!(w.get_block_header(tip.height)?.is_some());
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_header_cache() {
+	let test_dir = "test_output/header_cache";
+	setup(test_dir);
+	if let Err(e) = header_cache_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,167 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the health check endpoints of the listeners
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::ExtKeychain;
+use epic_wallet_util::epic_util::Mutex;
+
+use impls::test_framework::{self, LocalWalletClient};
+use impls::DefaultLCProvider;
+use libwallet::WalletInst;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::controller::RequestLimits;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_local_wallet, create_wallet_proxy, setup};
+
+/// Address of the foreign listener of the wallet with a running node
+const LISTENER_ADDR: &str = "127.0.0.1:43429";
+
+/// Address of the foreign listener of the wallet whose node can't be reached
+const NO_NODE_LISTENER_ADDR: &str = "127.0.0.1:43430";
+
+type TestWallet = Arc<
+	Mutex<
+		Box<
+			dyn WalletInst<
+				'static,
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>,
+	>,
+>;
+
+/// Run the foreign listener of the wallet
+fn start_listener(wallet: TestWallet, addr: &'static str) {
+	thread::spawn(move || {
+		let res = wallet::controller::foreign_listener(
+			wallet,
+			Arc::new(Mutex::new(None)),
+			addr,
+			None,
+			false,
+			None,
+			RequestLimits::default(),
+			false,
+			false,
+			None,
+		);
+		if let Err(e) = res {
+			panic!("Foreign listener error: {}", e);
+		}
+	});
+}
+
+/// Status code and body of the response to a GET of the path
+fn get(addr: &str, path: &str) -> (u16, Value) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	let request = format!(
+		"GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+		path, addr
+	);
+	stream.write_all(request.as_bytes()).unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+	let body = response.splitn(2, "\r\n\r\n").nth(1).unwrap();
+	(status, serde_json::from_str(body).unwrap())
+}
+
+fn health_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// a wallet whose node requests all fail
+	let (node_tx, node_rx) = channel();
+	drop(node_rx);
+	let client2 = LocalWalletClient::new("wallet2", node_tx);
+	let (wallet2, _) = create_local_wallet(test_dir, "wallet2", None, client2, false);
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+
+	start_listener(wallet1.clone(), LISTENER_ADDR);
+	start_listener(wallet2.clone(), NO_NODE_LISTENER_ADDR);
+	thread::sleep(Duration::from_millis(500));
+
+	// ready with the wallet open and the node reachable
+	let (status, body) = get(LISTENER_ADDR, "/health");
+	assert_eq!(status, 200);
+	assert_eq!(body["wallet_open"], true);
+	assert_eq!(body["node_reachable"], true);
+	assert_eq!(get(LISTENER_ADDR, "/ready").0, 200);
+
+	// not if the node can't be reached
+	let (status, body) = get(NO_NODE_LISTENER_ADDR, "/health");
+	assert_eq!(status, 503);
+	assert_eq!(body["wallet_open"], true);
+	assert_eq!(body["node_reachable"], false);
+	assert_eq!(get(NO_NODE_LISTENER_ADDR, "/ready").0, 503);
+
+	// nor with the wallet closed
+	{
+		let mut w_lock = wallet1.lock();
+		w_lock.lc_provider()?.close_wallet(None)?;
+	}
+	let (status, body) = get(LISTENER_ADDR, "/health");
+	assert_eq!(status, 503);
+	assert_eq!(body["wallet_open"], false);
+	assert_eq!(get(LISTENER_ADDR, "/ready").0, 503);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_health() {
+	let test_dir = "test_output/health";
+	setup(test_dir);
+	if let Err(e) = health_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
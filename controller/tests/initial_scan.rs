@@ -0,0 +1,115 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the background initial scan of a restored wallet
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::ZeroingString;
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn initial_scan_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	let mut mnemonic = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, _m| {
+		mnemonic = Some(api.get_mnemonic(None, ZeroingString::from(""))?);
+		Ok(())
+	})?;
+
+	// the same wallet, restored from its recovery phrase
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		mnemonic,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut total = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// a wallet that was created rather than restored needs no scan
+		assert!(!api.start_initial_scan(m)?);
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		Ok(())
+	})?;
+	assert!(total > 0);
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		assert!(api.start_initial_scan(m)?);
+		// only one scan runs at a time
+		assert!(!api.start_initial_scan(m)?);
+		while api.initial_scan_progress().is_some() {
+			// queries don't refresh the wallet while it's scanned
+			let (validated, _) = api.retrieve_summary_info(m, true, 1)?;
+			assert!(!validated || api.initial_scan_progress().is_none());
+			thread::sleep(Duration::from_millis(100));
+		}
+
+		// the restored wallet found the outputs and needs no further scan
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, total);
+		assert!(!api.start_initial_scan(m)?);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_initial_scan() {
+	let test_dir = "test_output/initial_scan";
+	setup(test_dir);
+	if let Err(e) = initial_scan_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
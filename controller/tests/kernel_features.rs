@@ -0,0 +1,156 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sends choosing a height-locked kernel
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use self::core::core::KernelFeatures;
+use self::core::global;
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{InitTxArgs, Slate, TxKernelFeatures};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn kernel_features_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = global::coinbase_maturity() as usize;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 2, false);
+
+	let args = |kernel_features: Option<TxKernelFeatures>, ttl_blocks: Option<u64>| InitTxArgs {
+		src_acct_name: None,
+		amount: reward / 2,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		ttl_blocks,
+		kernel_features,
+		..Default::default()
+	};
+	let locked = |lock_height: u64| Some(TxKernelFeatures::HeightLocked { lock_height });
+
+	let mut lock_height = 0;
+	let mut slate = Slate::blank(2);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let height = api.node_height(m)?.height;
+
+		// the lock height must be above the current height
+		assert!(api.init_send_tx(m, args(locked(height), None)).is_err());
+		// and not beyond the TTL cutoff
+		assert!(api
+			.init_send_tx(m, args(locked(height + 5), Some(2)))
+			.is_err());
+
+		// a plain kernel is recorded as such
+		let plain = api.init_send_tx(m, args(Some(TxKernelFeatures::Plain), None))?;
+		assert_eq!(plain.lock_height, 0);
+		api.tx_lock_outputs(m, &plain, 0)?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(plain.id))?;
+		assert_eq!(txs[0].kernel_features, Some(TxKernelFeatures::Plain));
+		api.cancel_tx(m, None, Some(plain.id))?;
+
+		lock_height = height + 3;
+		slate = api.init_send_tx(m, args(locked(lock_height), None))?;
+		assert_eq!(slate.lock_height, lock_height);
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		match slate.tx.kernels()[0].features {
+			KernelFeatures::HeightLocked { lock_height: l, .. } => assert_eq!(l, lock_height),
+			ref f => panic!("unexpected kernel features {:?}", f),
+		}
+
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].kernel_features, locked(lock_height));
+		Ok(())
+	})?;
+
+	// the recipient records the kernel's lock height too
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].kernel_features, locked(lock_height));
+		Ok(())
+	})?;
+
+	// once the lock height is reached, the transaction is confirmed
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 1, false);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (refreshed, txs) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+		assert!(refreshed);
+		assert!(txs[0].confirmed);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn kernel_features() {
+	let test_dir = "test_output/kernel_features";
+	setup(test_dir);
+	if let Err(e) = kernel_features_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,132 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the notifications of coinbase outputs becoming spendable
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::Keychain;
+use epic_wallet_util::epic_util::secp::key::SecretKey;
+use epic_wallet_util::epic_util::Mutex;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::api_impl::owner_updater::MaturityWatch;
+use libwallet::{MaturityHook, NodeClient, OutputMatured, WalletInst, WalletLCProvider};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Keeps the notifications it's given
+#[derive(Default)]
+struct RecordingHook(Mutex<Vec<OutputMatured>>);
+
+impl MaturityHook for RecordingHook {
+	fn matured(&self, output: &OutputMatured) -> Result<(), libwallet::Error> {
+		self.0.lock().push(output.clone());
+		Ok(())
+	}
+}
+
+/// Refresh the wallet and its header cache from the node, as the updater does
+/// before checking maturity, returning the height of the node
+fn refresh<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	mask: Option<&SecretKey>,
+) -> Result<u64, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let mut height = 0;
+	wallet::controller::owner_single_use(wallet, mask, |api, m| {
+		let (refreshed, _) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(refreshed);
+		height = api.node_height(m)?.height;
+		Ok(())
+	})?;
+	Ok(height)
+}
+
+fn maturity_notifications_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let hook = Arc::new(RecordingHook::default());
+	let mut watch = MaturityWatch::new(Some(hook.clone()));
+
+	// rewards already spendable when the watch starts aren't notified
+	let start = refresh(wallet1.clone(), mask1)?;
+	watch.check(wallet1.clone(), &None)?;
+	assert!(hook.0.lock().is_empty());
+
+	// the rewards maturing as the chain grows are
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+	let tip = refresh(wallet1.clone(), mask1)?;
+	watch.check(wallet1.clone(), &None)?;
+	let notified = hook.0.lock().len();
+	assert!(notified > 0);
+	for o in hook.0.lock().iter() {
+		assert!(o.is_coinbase);
+		assert_eq!(o.account, "default");
+		assert!(o.lock_height > start && o.lock_height <= tip);
+		assert_eq!(o.height, tip);
+	}
+
+	// and only once
+	refresh(wallet1.clone(), mask1)?;
+	watch.check(wallet1.clone(), &None)?;
+	assert_eq!(hook.0.lock().len(), notified);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_maturity_notifications() {
+	let test_dir = "test_output/maturity_notifications";
+	setup(test_dir);
+	if let Err(e) = maturity_notifications_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
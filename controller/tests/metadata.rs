@@ -0,0 +1,107 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the wallet's metadata store
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use libwallet::ErrorKind;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// metadata store impl
+fn metadata_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.get_metadata(m, "gui", "theme")?.is_none());
+		api.set_metadata(m, "gui", "theme", "light")?;
+		api.set_metadata(m, "gui", "theme", "dark")?;
+		api.set_metadata(m, "gui", "lang", "en")?;
+		api.set_metadata(m, "other", "theme", "blue")?;
+
+		let entry = api.get_metadata(m, "gui", "theme")?.unwrap();
+		assert_eq!(entry.value, "dark");
+		let entries = api.retrieve_metadata(m, "gui")?;
+		let keys: Vec<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+		assert_eq!(keys, vec!["lang", "theme"]);
+
+		// namespaces are kept apart
+		assert!(api.delete_metadata(m, "gui", "theme")?);
+		assert!(!api.delete_metadata(m, "gui", "theme")?);
+		assert_eq!(
+			api.get_metadata(m, "other", "theme")?.unwrap().value,
+			"blue"
+		);
+
+		// limits
+		match api
+			.set_metadata(m, "my gui", "theme", "dark")
+			.map_err(|e| e.kind())
+		{
+			Err(ErrorKind::InvalidMetadata(_)) => {}
+			r => panic!("Unexpected result: {:?}", r),
+		}
+		let large = "x".repeat(64 * 1024 + 1);
+		match api
+			.set_metadata(m, "gui", "notes", &large)
+			.map_err(|e| e.kind())
+		{
+			Err(ErrorKind::InvalidMetadata(_)) => {}
+			r => panic!("Unexpected result: {:?}", r),
+		}
+		assert!(api.get_metadata(m, "gui", "notes")?.is_none());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_metadata() {
+	let test_dir = "test_output/metadata";
+	setup(test_dir);
+	if let Err(e) = metadata_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
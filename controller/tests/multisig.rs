@@ -0,0 +1,193 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test an output shared by two wallets, created and then spent by both of them
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs, Slate};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn multisig_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let amount = 2_000_000_000;
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+	let mut slate = Slate::blank(2);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.init_multisig_output(m, args.clone())?;
+		assert!(slate.multisig_output.is_some());
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// the shared output isn't finalized as a regular send
+		match api.finalize_tx(m, &slate) {
+			Err(e) => match e.kind() {
+				ErrorKind::Multisig(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("shared output finalized as a regular send"),
+		}
+		Ok(())
+	})?;
+
+	// nor received as one
+	match client1.send_tx_slate_direct("wallet2", &slate) {
+		Err(_) => (),
+		Ok(_) => panic!("shared output received as a regular send"),
+	}
+
+	// the recipient joins, the sender signs and the recipient completes
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		slate = api.receive_multisig(m, &slate)?;
+		assert_eq!(slate.multisig_output.as_ref().unwrap().parties.len(), 2);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.receive_multisig(m, &slate)?;
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		slate = api.receive_multisig(m, &slate)?;
+		let commit = slate.multisig_output.as_ref().unwrap().commit.unwrap();
+		assert!(slate.tx.outputs().iter().any(|o| o.commitment() == commit));
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let commit = slate.multisig_output.as_ref().unwrap().commit.unwrap();
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// each wallet holds a share, outside of its balance
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		let shared: Vec<_> = outputs.iter().filter(|o| o.output.multisig).collect();
+		assert_eq!(shared.len(), 1);
+		assert_eq!(shared[0].commit, commit);
+		assert_eq!(shared[0].output.value, amount);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, 0);
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert_eq!(outputs.len(), 1);
+		assert!(outputs[0].output.multisig);
+		assert_eq!(outputs[0].commit, commit);
+		Ok(())
+	})?;
+
+	// the sender releases the whole output to the recipient
+	let release_fee = core::libtx::tx_fee(1, 1, 1, None);
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: amount - release_fee,
+		minimum_confirmations: 1,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		inputs: Some(vec![commit]),
+		..Default::default()
+	};
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.init_send_tx(m, args.clone())?;
+		assert_eq!(slate.multisig_input, Some(commit));
+		assert_eq!(slate.fee, release_fee);
+		api.tx_lock_outputs(m, &slate, 0)?;
+		Ok(())
+	})?;
+	slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, amount - release_fee);
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert!(outputs.iter().all(|o| !o.output.multisig));
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert!(outputs.iter().all(|o| o.commit != commit));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_multisig() {
+	let test_dir = "test_output/multisig";
+	setup(test_dir);
+	if let Err(e) = multisig_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
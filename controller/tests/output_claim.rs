@@ -0,0 +1,116 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test verifying claims about the value and blinding factor of outputs
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain as keychain;
+
+use self::keychain::{BlindingFactor, Keychain, SwitchCommitmentType};
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::OutputClaim;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_claim_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	let mut outputs = vec![];
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		outputs = api.retrieve_outputs(m, false, true, false, None)?.1;
+		Ok(())
+	})?;
+	let output = outputs[0].clone();
+	let blinding_factor = {
+		wallet_inst!(wallet1, w);
+		let keychain = w.keychain(mask1)?;
+		let key = keychain.derive_key(
+			output.output.value,
+			&output.output.key_id,
+			&SwitchCommitmentType::Regular,
+		)?;
+		BlindingFactor::from_secret_key(key)
+	};
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// the output's own opening
+		let mut claim = OutputClaim {
+			commit: output.commit.clone(),
+			value: output.output.value,
+			blinding_factor: blinding_factor.clone(),
+		};
+		let res = api.verify_output_claim(m, &claim)?;
+		assert!(res.is_valid());
+		assert_eq!(res.height, Some(output.output.height));
+		assert_eq!(res.is_coinbase, Some(true));
+
+		// a different value doesn't open the commitment
+		claim.value += 1;
+		let res = api.verify_output_claim(m, &claim)?;
+		assert!(!res.is_valid());
+		assert!(res.on_chain);
+		assert!(res.range_proof_valid);
+		assert!(!res.opening_valid);
+
+		// nor does it open any other output
+		claim.value = output.output.value;
+		claim.commit = outputs[1].commit.clone();
+		let res = api.verify_output_claim(m, &claim)?;
+		assert!(!res.opening_valid);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_output_claim() {
+	let test_dir = "test_output/output_claim";
+	setup(test_dir);
+	if let Err(e) = output_claim_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,126 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test outputs recorded under two accounts are deduplicated, so they're counted once
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::OutputData;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_dedup_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let mut outputs: Vec<OutputData> = vec![];
+	let mut default_path = None;
+	let mut other_path = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		outputs = api
+			.retrieve_outputs(m, false, true, false, None)?
+			.1
+			.into_iter()
+			.map(|o| o.output)
+			.collect();
+		default_path = Some(api.accounts(m)?[0].path.clone());
+		other_path = Some(api.create_account_path(m, "restored")?);
+		Ok(())
+	})?;
+	let (default_path, other_path) = (default_path.unwrap(), other_path.unwrap());
+
+	// a restore records an output under another account too
+	let duplicate = |out: &OutputData| -> Result<(), libwallet::Error> {
+		let mut dup = out.clone();
+		dup.root_key_id = other_path.clone();
+		dup.mmr_index = Some(dup.mmr_index.unwrap_or(0) + 1);
+		wallet_inst!(wallet1, w);
+		let mut batch = w.batch(mask1)?;
+		batch.save(dup)?;
+		batch.commit()
+	};
+	duplicate(&outputs[0])?;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_active_account(m, "restored")?;
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, outputs[0].value);
+
+		// the record of the account the output's key belongs to is kept
+		let dups = api.deduplicate_outputs(m)?;
+		assert_eq!(dups.len(), 1);
+		assert_eq!(dups[0].kept.root_key_id, default_path);
+		assert_eq!(dups[0].removed.len(), 1);
+		assert_eq!(dups[0].removed[0].root_key_id, other_path);
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, 0);
+		assert!(api.deduplicate_outputs(m)?.is_empty());
+		Ok(())
+	})?;
+
+	// updates deduplicate the outputs too
+	duplicate(&outputs[1])?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_active_account(m, "default")?;
+		let (refreshed, _) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(refreshed);
+		assert!(api.deduplicate_outputs(m)?.is_empty());
+		api.set_active_account(m, "restored")?;
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, 0);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn output_dedup() {
+	let test_dir = "test_output/output_dedup";
+	setup(test_dir);
+	if let Err(e) = output_dedup_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
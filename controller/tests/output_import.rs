@@ -0,0 +1,132 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test importing outputs exported by another wallet of the same seed
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::ZeroingString;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ImportedOutput, OutputStatus};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_import_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	let mut mnemonic = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, _m| {
+		mnemonic = Some(api.get_mnemonic(None, ZeroingString::from(""))?);
+		Ok(())
+	})?;
+
+	// another wallet of the same seed
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		mnemonic,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// the outputs as exported by the first wallet
+	let mut exported: Vec<ImportedOutput> = vec![];
+	let mut total = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		for o in outputs {
+			assert_eq!(o.output.status, OutputStatus::Unspent);
+			let record = serde_json::to_value(&o.output).unwrap();
+			exported.push(serde_json::from_value(record).unwrap());
+		}
+		Ok(())
+	})?;
+	assert!(total > 0);
+	let num_outputs = exported.len();
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		// are restored without a scan
+		let summary = api.import_outputs(m, exported.clone())?;
+		assert_eq!(summary.num_imported, num_outputs);
+		assert_eq!(summary.imported_value, total);
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, total);
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.len(), num_outputs);
+
+		// once
+		let summary = api.import_outputs(m, exported.clone())?;
+		assert_eq!(summary.num_imported, 0);
+		assert_eq!(summary.num_known, num_outputs);
+
+		// outputs not derived from the seed are skipped
+		let mut mismatched = exported[0].clone();
+		mismatched.value += 1;
+		let mut unknown = mismatched.clone();
+		unknown.commit = None;
+		let summary = api.import_outputs(m, vec![mismatched, unknown])?;
+		assert_eq!(summary.num_mismatched, 1);
+		assert_eq!(summary.num_not_found, 1);
+		assert_eq!(summary.num_imported, 0);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_output_import() {
+	let test_dir = "test_output/output_import";
+	setup(test_dir);
+	if let Err(e) = output_import_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
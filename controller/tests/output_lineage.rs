@@ -0,0 +1,140 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test tracing the transactions that created and spent outputs
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{InitTxArgs, OutputStatus, TxLogEntryType};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_lineage_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut send_id = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 60_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		let slate_i = api.init_send_tx(m, args)?;
+		let mut slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+		send_id = txs[0].id;
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, true, true, false, Some(send_id))?;
+		let change = outputs
+			.iter()
+			.find(|o| o.output.status != OutputStatus::Spent)
+			.unwrap();
+		let input = outputs
+			.iter()
+			.find(|o| o.output.status == OutputStatus::Spent)
+			.unwrap();
+
+		// the change was created by the send, out of the coins it spent
+		let lineage = api.trace_output(m, &change.commit)?.unwrap();
+		assert_eq!(lineage.created_by.as_ref().unwrap().id, send_id);
+		assert!(lineage.spent_by.is_none());
+		assert!(lineage.children.is_empty());
+		assert!(lineage.parents.iter().any(|p| p.commit == input.commit));
+
+		// which were coinbases, spent by the send into the change
+		let lineage = api.trace_output(m, &input.commit)?.unwrap();
+		assert_eq!(
+			lineage.created_by.as_ref().unwrap().tx_type,
+			TxLogEntryType::ConfirmedCoinbase
+		);
+		assert_eq!(lineage.spent_by.as_ref().unwrap().id, send_id);
+		assert!(lineage.cancelled_spends.is_empty());
+		assert!(lineage.parents.is_empty());
+		assert_eq!(lineage.children.len(), 1);
+		assert_eq!(lineage.children[0].commit, change.commit);
+
+		// outputs of other wallets are unknown
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		let mut commit = outputs[0].commit.clone();
+		commit.0[1] ^= 1;
+		assert!(api.trace_output(m, &commit)?.is_none());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_output_lineage() {
+	let test_dir = "test_output/output_lineage";
+	setup(test_dir);
+	if let Err(e) = output_lineage_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
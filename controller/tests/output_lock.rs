@@ -0,0 +1,158 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test locking outputs against spending
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::secp::pedersen::Commitment;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_lock_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		// the reserve is all mature rewards but one
+		let mut spendable: Vec<_> = outputs
+			.iter()
+			.filter(|o| o.output.lock_height <= info.last_confirmed_height)
+			.collect();
+		spendable.sort_by_key(|o| o.output.height);
+		let reserve: Vec<Commitment> = spendable[1..].iter().map(|o| o.commit).collect();
+		for c in reserve.iter() {
+			api.lock_output(m, c)?;
+		}
+		// locking twice is harmless
+		api.lock_output(m, &reserve[0])?;
+
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		let frozen: HashSet<_> = outputs
+			.iter()
+			.filter(|o| o.output.frozen)
+			.map(|o| o.commit)
+			.collect();
+		assert_eq!(frozen, reserve.iter().cloned().collect());
+
+		// selection only has the unlocked output to spend
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: spendable[0].output.value * 2,
+			minimum_confirmations: 1,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		match api.init_send_tx(m, args.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::NotEnoughFunds { .. } => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("locked outputs selected"),
+		}
+		let slate = api.init_send_tx(
+			m,
+			InitTxArgs {
+				amount: spendable[0].output.value / 2,
+				..args.clone()
+			},
+		)?;
+		let inputs: Vec<_> = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+		assert_eq!(inputs, vec![spendable[0].commit]);
+
+		// and a locked output can't be given as an input
+		let pinned = InitTxArgs {
+			amount: spendable[0].output.value / 2,
+			inputs: Some(vec![reserve[0]]),
+			..args.clone()
+		};
+		match api.init_send_tx(m, pinned.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnspendableInput(_, r) => assert_eq!(r, "it's frozen"),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("locked output spent"),
+		}
+
+		// until it's unlocked
+		api.unlock_output(m, &reserve[0])?;
+		let slate = api.init_send_tx(m, pinned)?;
+		let inputs: Vec<_> = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+		assert_eq!(inputs, vec![reserve[0]]);
+
+		// the lock is kept across refreshes
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		let frozen = outputs.iter().filter(|o| o.output.frozen).count();
+		assert_eq!(frozen, reserve.len() - 1);
+
+		// outputs the wallet doesn't hold can't be locked
+		let unknown = Commitment::from_vec(vec![9; 33]);
+		match api.lock_output(m, &unknown) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnknownOutput(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("unknown output locked"),
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_output_lock() {
+	let test_dir = "test_output/output_lock";
+	setup(test_dir);
+	if let Err(e) = output_lock_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,254 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test retrieving the outputs of a wallet a page at a time
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_api as api;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{
+	ErrorKind, InitTxArgs, OutputPageQuery, OutputSortField, OutputStatus, TxPageQuery,
+};
+use std::collections::{HashMap, HashSet};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn output_paging_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, all) = api.retrieve_outputs(m, false, true, false, None)?;
+
+		// pages without sorting follow the wallet's order
+		let query = OutputPageQuery {
+			offset: 2,
+			limit: Some(3),
+			..Default::default()
+		};
+		let (_, page) = api.retrieve_outputs_page(m, false, false, None, &query)?;
+		assert_eq!(page.total, all.len());
+		let commits: Vec<_> = page.outputs.iter().map(|o| o.commit).collect();
+		let expected: Vec<_> = all[2..5].iter().map(|o| o.commit).collect();
+		assert_eq!(commits, expected);
+
+		// the pages of sorted outputs put together are all of them, in order
+		let mut heights = vec![];
+		let mut offset = 0;
+		let mut snapshot = None;
+		loop {
+			let query = OutputPageQuery {
+				offset,
+				limit: Some(4),
+				sort_by: Some(OutputSortField::Height),
+				descending: true,
+				snapshot,
+			};
+			let (_, page) = api.retrieve_outputs_page(m, false, false, None, &query)?;
+			assert_eq!(page.total, all.len());
+			if page.outputs.is_empty() {
+				break;
+			}
+			heights.extend(page.outputs.iter().map(|o| o.output.height));
+			offset += page.outputs.len();
+			snapshot = Some(page.snapshot);
+		}
+		let mut expected: Vec<_> = all.iter().map(|o| o.output.height).collect();
+		expected.sort();
+		expected.reverse();
+		assert_eq!(heights, expected);
+
+		// a page past the last output is empty
+		let query = OutputPageQuery {
+			offset: all.len(),
+			..Default::default()
+		};
+		let (_, page) = api.retrieve_outputs_page(m, false, false, None, &query)?;
+		assert!(page.outputs.is_empty());
+
+		// sorting by status puts the outputs locked by a send after the unspent ones
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 60_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		let ids: HashMap<_, _> = all
+			.iter()
+			.map(|o| (o.commit, o.output_id.clone()))
+			.collect();
+		assert_eq!(ids.values().collect::<HashSet<_>>().len(), all.len());
+		let slate_i = api.init_send_tx(m, args)?;
+		let slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// outputs keep their id through their changes of status, in their history too,
+		// and are listed in the same order each time
+		let (_, outputs) = api.retrieve_outputs(m, true, false, true, None)?;
+		assert!(outputs.len() > all.len());
+		for o in outputs.iter() {
+			if let Some(id) = ids.get(&o.commit) {
+				assert_eq!(&o.output_id, id);
+			}
+		}
+		let (_, again) = api.retrieve_outputs(m, true, false, true, None)?;
+		let order: Vec<_> = outputs.iter().map(|o| &o.output_id).collect();
+		let expected: Vec<_> = again.iter().map(|o| &o.output_id).collect();
+		assert_eq!(order, expected);
+		let query = OutputPageQuery {
+			sort_by: Some(OutputSortField::Status),
+			..Default::default()
+		};
+		let (_, page) = api.retrieve_outputs_page(m, false, false, None, &query)?;
+		let statuses: Vec<_> = page
+			.outputs
+			.iter()
+			.map(|o| o.output.status.clone())
+			.collect();
+		let mut expected = statuses.clone();
+		expected.sort();
+		assert_eq!(statuses, expected);
+		assert_eq!(statuses.last(), Some(&OutputStatus::Locked));
+		Ok(())
+	})?;
+
+	// pages of a snapshot show the wallet as it was when it was taken
+	let owner_api = api::Owner::new(wallet1.clone(), None);
+	let (_, all_outputs) = owner_api.retrieve_outputs(mask1, true, false, false, None)?;
+	let (_, all_txs) = owner_api.retrieve_txs(mask1, false, None, None)?;
+	let mut output_query = OutputPageQuery {
+		limit: Some(2),
+		..Default::default()
+	};
+	let (_, page) = owner_api.retrieve_outputs_page(mask1, true, false, None, &output_query)?;
+	let mut outputs: Vec<_> = page.outputs.iter().map(|o| o.commit).collect();
+	output_query.snapshot = Some(page.snapshot);
+	let mut tx_query = TxPageQuery {
+		limit: Some(2),
+		..Default::default()
+	};
+	let (_, page) = owner_api.retrieve_txs_page(mask1, false, &tx_query)?;
+	let mut txs: Vec<_> = page.txs.iter().map(|t| t.id).collect();
+	tx_query.snapshot = Some(page.snapshot);
+
+	// the wallet gets new outputs and transactions while the listing is paged through
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	loop {
+		output_query.offset = outputs.len();
+		let (_, page) = owner_api.retrieve_outputs_page(mask1, true, true, None, &output_query)?;
+		assert_eq!(page.total, all_outputs.len());
+		if page.outputs.is_empty() {
+			break;
+		}
+		outputs.extend(page.outputs.iter().map(|o| o.commit));
+	}
+	let expected: Vec<_> = all_outputs.iter().map(|o| o.commit).collect();
+	assert_eq!(outputs, expected);
+
+	loop {
+		tx_query.offset = txs.len();
+		let (_, page) = owner_api.retrieve_txs_page(mask1, true, &tx_query)?;
+		assert_eq!(page.total, all_txs.len());
+		if page.txs.is_empty() {
+			break;
+		}
+		txs.extend(page.txs.iter().map(|t| t.id));
+	}
+	let expected: Vec<_> = all_txs.iter().map(|t| t.id).collect();
+	assert_eq!(txs, expected);
+
+	// a new listing sees the new entries
+	let (_, page) = owner_api.retrieve_txs_page(mask1, true, &TxPageQuery::default())?;
+	assert!(page.total > all_txs.len());
+	assert_ne!(Some(page.snapshot), tx_query.snapshot);
+
+	// snapshots are only of the kind of entries they were taken of
+	let query = OutputPageQuery {
+		snapshot: tx_query.snapshot,
+		..Default::default()
+	};
+	assert!(owner_api
+		.retrieve_outputs_page(mask1, true, false, None, &query)
+		.is_err());
+	let query = TxPageQuery {
+		snapshot: Some(Uuid::new_v4()),
+		..Default::default()
+	};
+	match owner_api.retrieve_txs_page(mask1, false, &query) {
+		Err(e) => match e.kind() {
+			ErrorKind::UnknownPageSnapshot(_) => (),
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("page of an unknown snapshot"),
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_output_paging() {
+	let test_dir = "test_output/output_paging";
+	setup(test_dir);
+	if let Err(e) = output_paging_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
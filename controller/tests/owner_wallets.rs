@@ -0,0 +1,143 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the owner API calls naming a wallet are made to that wallet
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_api::{Owner, OwnerRpcS};
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::to_hex;
+
+use easy_jsonrpc_mw::{Handler, MaybeReply};
+use impls::test_framework::{self, LocalWalletClient};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::controller::OwnerV3Helpers;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn owner_wallets_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let api1 = Arc::new(Owner::new(wallet1.clone(), None));
+	let api2 = Arc::new(Owner::new(wallet2.clone(), None));
+	let mut wallets = HashMap::new();
+	wallets.insert("wallet2".to_owned(), api2.clone());
+
+	let token1 = mask1.map(|m| to_hex(m.0.to_vec()));
+	let token2 = mask2.map(|m| to_hex(m.0.to_vec()));
+	let summary = |id: u32, token: &Option<String>, wallet: Option<&str>| {
+		json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "retrieve_summary_info",
+			"wallet": wallet,
+			"params": {
+				"token": token,
+				"refresh_from_node": true,
+				"minimum_confirmations": 1
+			}
+		})
+	};
+	let total = |api: &dyn OwnerRpcS, call: serde_json::Value| match api.handle_request(call) {
+		MaybeReply::Reply(r) => r["result"]["Ok"][1]["total"].as_u64().unwrap(),
+		MaybeReply::DontReply => panic!("no reply"),
+	};
+
+	// the calls not naming a wallet are made to the default one
+	let mut call = summary(1, &token1, None);
+	let api = OwnerV3Helpers::wallet_owner_api(&api1, &wallets, &mut call).unwrap();
+	assert!(Arc::ptr_eq(&api, &api1));
+	assert!(total(&*api as &dyn OwnerRpcS, call) > 0);
+
+	// the others to the wallet they name, which is taken out of the call
+	let mut call = summary(2, &token2, Some("wallet2"));
+	let api = OwnerV3Helpers::wallet_owner_api(&api1, &wallets, &mut call).unwrap();
+	assert!(Arc::ptr_eq(&api, &api2));
+	assert!(call.get("wallet").is_none());
+	assert_eq!(total(&*api as &dyn OwnerRpcS, call), 0);
+
+	// unknown wallets are refused
+	let mut call = summary(3, &token2, Some("wallet3"));
+	let err = OwnerV3Helpers::wallet_owner_api(&api1, &wallets, &mut call).unwrap_err();
+	assert_eq!(err["error"]["code"], -32006);
+	assert_eq!(err["id"], 3);
+
+	// the calls of a batch are made to a single wallet
+	let mut batch = json!([
+		summary(4, &token2, Some("wallet2")),
+		summary(5, &token1, None)
+	]);
+	assert!(OwnerV3Helpers::wallet_owner_api(&api1, &wallets, &mut batch).is_err());
+	let mut batch = json!([
+		summary(6, &token2, Some("wallet2")),
+		summary(7, &token2, Some("wallet2"))
+	]);
+	let api = OwnerV3Helpers::wallet_owner_api(&api1, &wallets, &mut batch).unwrap();
+	assert!(Arc::ptr_eq(&api, &api2));
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn owner_wallets() {
+	let test_dir = "test_output/owner_wallets";
+	setup(test_dir);
+	if let Err(e) = owner_wallets_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,143 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the password policy and the backoff on failed attempts at opening a wallet
+extern crate epic_wallet_api as api;
+extern crate epic_wallet_config as config;
+extern crate epic_wallet_impls as impls;
+
+use config::{OpenThrottleConfig, PasswordPolicyConfig};
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::ExtKeychain;
+use epic_wallet_util::epic_util::{Mutex, ZeroingString};
+use impls::test_framework::LocalWalletClient;
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use libwallet::{ErrorKind, StatusMessage, WalletInst};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn password_policy_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let wallet_proxy = create_wallet_proxy(test_dir);
+	let client1 = LocalWalletClient::new("wallet1", wallet_proxy.tx.clone());
+	let mut wallet1 = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client1).unwrap())
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	let lc = wallet1.lc_provider().unwrap();
+	lc.set_top_level_directory(&format!("{}/wallet1", test_dir))?;
+	let owner_api = api::Owner::new(Arc::new(Mutex::new(wallet1)), None);
+	owner_api.set_password_policy(Some(PasswordPolicyConfig {
+		min_length: Some(10),
+		min_char_classes: Some(2),
+	}));
+	owner_api.set_open_throttle(&OpenThrottleConfig {
+		max_attempts: Some(2),
+		lockout_secs: Some(1),
+		max_lockout_secs: Some(2),
+	});
+
+	// weak passwords are refused
+	for pw in &["Short1", "alllowercaseletters"] {
+		match owner_api.create_wallet(None, None, 32, None, ZeroingString::from(*pw)) {
+			Err(e) => match e.kind() {
+				ErrorKind::WeakPassword(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("wallet created with password {}", pw),
+		}
+	}
+	let pw = ZeroingString::from("correct horse 42");
+	owner_api.create_wallet(None, None, 32, None, pw.clone())?;
+
+	let new_pw = ZeroingString::from("battery staple 42");
+	match owner_api.change_password(None, pw.clone(), ZeroingString::from("staple")) {
+		Err(e) => match e.kind() {
+			ErrorKind::WeakPassword(_) => (),
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("password changed to a weak one"),
+	}
+	owner_api.change_password(None, pw.clone(), new_pw.clone())?;
+
+	// failed attempts at opening the wallet lead to a lockout
+	let wrong = ZeroingString::from("wrong password 1");
+	for _ in 0..2 {
+		match owner_api.open_wallet(None, wrong.clone(), true, None) {
+			Err(e) => match e.kind() {
+				ErrorKind::Lifecycle(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("wallet opened with the wrong password"),
+		}
+	}
+	// the right password isn't even checked while locked out
+	match owner_api.open_wallet(None, new_pw.clone(), true, None) {
+		Err(e) => match e.kind() {
+			ErrorKind::OpenLockedOut(secs) => assert_eq!(secs, 1),
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("wallet opened while locked out"),
+	}
+	thread::sleep(Duration::from_millis(200));
+	let messages = owner_api.get_updater_messages(100)?;
+	assert!(messages.iter().any(|m| match m {
+		StatusMessage::OpenLockout(2, 1) => true,
+		_ => false,
+	}));
+
+	// each further failure doubles the lockout
+	thread::sleep(Duration::from_millis(1100));
+	assert!(owner_api
+		.open_wallet(None, wrong.clone(), true, None)
+		.is_err());
+	match owner_api.open_wallet(None, new_pw.clone(), true, None) {
+		Err(e) => match e.kind() {
+			ErrorKind::OpenLockedOut(secs) => assert_eq!(secs, 2),
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("wallet opened while locked out"),
+	}
+
+	// and opening the wallet forgets the failures
+	thread::sleep(Duration::from_millis(2100));
+	owner_api.open_wallet(None, new_pw.clone(), true, None)?;
+	owner_api.close_wallet(None)?;
+	assert!(owner_api
+		.open_wallet(None, wrong.clone(), true, None)
+		.is_err());
+	owner_api.open_wallet(None, new_pw, true, None)?;
+	owner_api.close_wallet(None)?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_password_policy() {
+	let test_dir = "test_output/password_policy";
+	setup(test_dir);
+	if let Err(e) = password_policy_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -20,7 +20,7 @@ extern crate epic_wallet_util;
 
 use epic_wallet_libwallet as libwallet;
 use impls::test_framework::{self, LocalWalletClient};
-use libwallet::{InitTxArgs, Slate};
+use libwallet::{InitTxArgs, PaymentProof, PaymentProofState, Slate};
 use std::thread;
 
 #[macro_use]
@@ -124,10 +124,17 @@ fn payment_proofs_test_impl(test_dir: &'static str) -> Result<(), libwallet::Err
 		assert!(pp.receiver_signature.is_some());
 		assert_eq!(pp.sender_address_path, 0);
 		assert_eq!(pp.sender_signature, None);
+		assert_eq!(
+			txs[0].payment_proof_state(),
+			Some(PaymentProofState::Promised)
+		);
 
 		// check we should get an error at this point since proof is not complete
 		let pp = sender_api.retrieve_payment_proof(m, true, None, Some(slate.id));
 		assert!(pp.is_err());
+		// nor is it listed
+		let (_, proofs) = sender_api.retrieve_payment_proofs(m, true)?;
+		assert!(proofs.is_empty());
 
 		slate = sender_api.finalize_tx(m, &slate)?;
 
@@ -152,14 +159,33 @@ fn payment_proofs_test_impl(test_dir: &'static str) -> Result<(), libwallet::Err
 		println!("{:?}", pp);
 
 		println!("{:?}", tx);
+		assert_eq!(tx.payment_proof_state(), Some(PaymentProofState::Received));
 		// verify, should be good
 		let res = sender_api.verify_payment_proof(m, &pp)?;
 		assert_eq!(res, (true, false));
 
+		// the completed proof is listed with its transaction
+		let (_, proofs) = sender_api.retrieve_payment_proofs(m, true)?;
+		assert_eq!(proofs.len(), 1);
+		assert_eq!(proofs[0].tx_id, tx.id);
+		assert_eq!(proofs[0].tx_slate_id, Some(slate.id));
+		assert_eq!(proofs[0].proof.excess, pp.excess);
+
+		// an archived proof checks out offline
+		let archived = serde_json::to_string(&pp).unwrap();
+		let archived: PaymentProof = serde_json::from_str(&archived).unwrap();
+		sender_api.verify_payment_proof_offline(&archived)?;
+		let (_, txs) = sender_api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(
+			txs[0].payment_proof_state(),
+			Some(PaymentProofState::Verified)
+		);
+
 		// Modify values, should not be good
 		pp.amount = 20;
 		let res = sender_api.verify_payment_proof(m, &pp);
 		assert!(res.is_err());
+		assert!(sender_api.verify_payment_proof_offline(&pp).is_err());
 		Ok(())
 	})?;
 	//thread::sleep(Duration::from_millis(200));
@@ -0,0 +1,137 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test creating and checking signed payment requests
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use chrono::{Duration as ChronoDuration, Utc};
+use epic_wallet_libwallet as libwallet;
+use libwallet::{ErrorKind, PaymentRequest};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn assert_invalid(result: Result<PaymentRequest, libwallet::Error>) {
+	match result.map_err(|e| e.kind()) {
+		Err(ErrorKind::InvalidPaymentRequest(_)) => {}
+		r => panic!("Unexpected result: {:?}", r),
+	}
+}
+
+/// payment request impl
+fn payment_request_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let expiry = Utc::now() + ChronoDuration::days(1);
+	let mut request = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let r = api.create_payment_request(
+			m,
+			2_000_000_000,
+			Some("Invoice 42".to_owned()),
+			Some(expiry),
+		)?;
+		let address = api.get_public_proof_address(m, 0)?;
+		assert_eq!(
+			r.address,
+			libwallet::address::onion_v3_from_pubkey(&address)?
+		);
+		assert_eq!(r.expiry.unwrap().timestamp(), expiry.timestamp());
+
+		assert_invalid(api.create_payment_request(m, 0, None, None));
+		assert_invalid(api.create_payment_request(m, 1, Some("x".repeat(257)), None));
+		let past = Utc::now() - ChronoDuration::hours(1);
+		assert_invalid(api.create_payment_request(m, 1, None, Some(past)));
+		request = Some(r);
+		Ok(())
+	})?;
+	let request = request.unwrap();
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, _m| {
+		// survives being shared as JSON
+		let json = serde_json::to_string(&request).unwrap();
+		let shared: PaymentRequest = serde_json::from_str(&json).unwrap();
+		let args = api.verify_payment_request(&shared)?;
+		assert_eq!(args.amount, 2_000_000_000);
+		assert_eq!(args.message, Some("Invoice 42".to_owned()));
+		let recipient = args.payment_proof_recipient_address.unwrap();
+		assert_eq!(
+			libwallet::address::onion_v3_from_pubkey(&recipient)?,
+			request.address
+		);
+
+		// any alteration is detected
+		let mut altered = request.clone();
+		altered.amount = 20_000_000_000;
+		assert!(api.verify_payment_request(&altered).is_err());
+		let mut altered = request.clone();
+		altered.memo = Some("Invoice 43".to_owned());
+		assert!(api.verify_payment_request(&altered).is_err());
+		let mut altered = request.clone();
+		altered.expiry = None;
+		assert!(api.verify_payment_request(&altered).is_err());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn payment_request() {
+	let test_dir = "test_output/payment_request";
+	setup(test_dir);
+	if let Err(e) = payment_request_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,141 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test listing and expiring received transactions not seen on chain
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{InitTxArgs, Slate, TxFilter, TxLogEntryType};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn pending_receives_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	// the sender never finalizes
+	let mut slate = Slate::blank(2);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 1_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		slate = api.init_send_tx(m, args)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, pending) = api.retrieve_pending_receives(m, true)?;
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0].tx_slate_id, Some(slate.id));
+
+		// not old enough yet
+		let mut filter = TxFilter {
+			tx_type: Some(TxLogEntryType::TxReceived),
+			min_age_hours: Some(1),
+		};
+		assert!(api.cancel_txs(m, &filter)?.is_empty());
+
+		filter.min_age_hours = Some(0);
+		let res = api.cancel_txs(m, &filter)?;
+		assert_eq!(res.len(), 1);
+		assert!(res[0].success);
+
+		let (_, pending) = api.retrieve_pending_receives(m, true)?;
+		assert!(pending.is_empty());
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_awaiting_finalization, 0);
+
+		// the cancelled receive isn't reposted
+		assert!(api.repost_txs(m, &TxFilter::default(), false)?.is_empty());
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// only the unfinalized send is picked up, not the confirmed coinbases
+		let res = api.repost_txs(m, &TxFilter::default(), false)?;
+		assert_eq!(res.len(), 1);
+		assert_eq!(res[0].tx_slate_id, Some(slate.id));
+		assert!(!res[0].success);
+
+		// an age no transaction can reach
+		let filter = TxFilter {
+			tx_type: None,
+			min_age_hours: Some(u64::MAX),
+		};
+		assert!(api.repost_txs(m, &filter, false)?.is_empty());
+		assert!(api.cancel_txs(m, &filter)?.is_empty());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn pending_receives() {
+	let test_dir = "test_output/pending_receives";
+	setup(test_dir);
+	if let Err(e) = pending_receives_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
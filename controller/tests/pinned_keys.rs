@@ -0,0 +1,100 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test pinning the epicbox keys of contacts on first use
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use libwallet::api_impl::owner;
+use libwallet::KeyPinCheck;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+const CONTACT: &str = "fffqrotuelaodwjblwmifg36xjedjw4azbwvfexmxmmzsb6xvzbkhuqd";
+const KEY: &str = "esWdQ3bHQA7iXwHvZFyn6KQpzRvJqkRRLaR9AfQGdxKbMKJEcDs1";
+const OTHER_KEY: &str = "esiLPQWnHcrGBqyUxv3bRcKbJcfNPAgRsna8uKRkBhNWFTp8ZrHE";
+
+/// key pinning impl
+fn pinned_keys_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// first slate with the contact pins its key
+	let check = owner::check_pinned_key(wallet1.clone(), mask1, CONTACT, KEY)?;
+	assert_eq!(check, KeyPinCheck::New);
+	let check = owner::check_pinned_key(wallet1.clone(), mask1, CONTACT, KEY)?;
+	assert_eq!(check, KeyPinCheck::Known);
+
+	// a different key is reported and doesn't replace the pinned one
+	match owner::check_pinned_key(wallet1.clone(), mask1, CONTACT, OTHER_KEY)? {
+		KeyPinCheck::Changed(pin) => assert_eq!(pin.epicbox_key, KEY),
+		c => panic!("Unexpected check: {:?}", c),
+	}
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let keys = api.retrieve_pinned_keys(m)?;
+		assert_eq!(keys.len(), 1);
+		assert_eq!(keys[0].contact, CONTACT);
+		assert_eq!(keys[0].epicbox_key, KEY);
+		assert_eq!(keys[0].times_seen, 2);
+
+		assert!(api.unpin_key(m, CONTACT)?);
+		assert!(!api.unpin_key(m, CONTACT)?);
+		assert!(api.retrieve_pinned_keys(m)?.is_empty());
+		Ok(())
+	})?;
+
+	// once forgotten, the new key is pinned in its place
+	let check = owner::check_pinned_key(wallet1.clone(), mask1, CONTACT, OTHER_KEY)?;
+	assert_eq!(check, KeyPinCheck::New);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_pinned_keys() {
+	let test_dir = "test_output/pinned_keys";
+	setup(test_dir);
+	if let Err(e) = pinned_keys_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,123 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test reading a wallet store opened read-only while the wallet writes to it
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::ExtKeychain;
+use epic_wallet_util::epic_util::{Mutex, ZeroingString};
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use libwallet::WalletInst;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn read_only_store_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// a reporting job opening the same wallet while it's open for writing
+	let mut reader = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client1.clone()).unwrap())
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	let lc = reader.lc_provider().unwrap();
+	lc.set_top_level_directory(&format!("{}/wallet1", test_dir))?;
+	lc.set_read_only(true)?;
+	let reader_mask = lc.open_wallet(None, ZeroingString::from(""), false, false)?;
+	let reader = Arc::new(Mutex::new(reader));
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let mut total = 0;
+	let mut num_outputs = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		num_outputs = api.retrieve_outputs(m, false, false, false, None)?.1.len();
+		Ok(())
+	})?;
+	assert!(total > 0);
+
+	wallet::controller::owner_single_use(reader.clone(), reader_mask.as_ref(), |api, m| {
+		// sees what the wallet wrote
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, total);
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		assert_eq!(outputs.len(), num_outputs);
+
+		// and can't write itself
+		match api.create_account_path(m, "reporting") {
+			Err(e) => assert!(format!("{}", e).contains("read-only")),
+			Ok(_) => panic!("account created through a read-only store"),
+		}
+		Ok(())
+	})?;
+
+	// the wallet itself is still written to as usual
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "reporting")?;
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(reader.clone(), reader_mask.as_ref(), |api, m| {
+		let accounts = api.accounts(m)?;
+		assert!(accounts.iter().any(|a| a.label == "reporting"));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_read_only_store() {
+	let test_dir = "test_output/read_only_store";
+	setup(test_dir);
+	if let Err(e) = read_only_store_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
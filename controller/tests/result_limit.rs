@@ -0,0 +1,95 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test limiting the number of entries returned by owner API queries
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::ErrorKind;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn result_limit_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		let tx_id = txs[0].id;
+
+		api.set_max_results(Some(3));
+		match api.retrieve_outputs(m, false, true, false, None) {
+			Err(e) => match e.kind() {
+				ErrorKind::ResultLimit(n, 3) if n > 3 => {}
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("returned more outputs than the limit"),
+		}
+		assert!(api.retrieve_txs(m, false, None, None).is_err());
+
+		// narrowed down queries are still answered
+		let (_, txs) = api.retrieve_txs(m, false, Some(tx_id), None)?;
+		assert_eq!(txs.len(), 1);
+		let (_, txs) = api.retrieve_pending_receives(m, false)?;
+		assert!(txs.is_empty());
+
+		api.set_max_results(None);
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		assert!(outputs.len() > 3);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_result_limit() {
+	let test_dir = "test_output/result_limit";
+	setup(test_dir);
+	if let Err(e) = result_limit_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
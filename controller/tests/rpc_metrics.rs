@@ -0,0 +1,126 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the calls handled by the listeners are counted in the RPC metrics
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_api::{handle_timed_request, Owner, OwnerRpcS};
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::to_hex;
+
+use easy_jsonrpc_mw::MaybeReply;
+use impls::test_framework::{self, LocalWalletClient};
+use serde_json::json;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn rpc_metrics_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	let api = Owner::new(wallet1.clone(), None);
+	let token = mask1.map(|m| to_hex(m.0.to_vec()));
+	// cancelling an unknown transaction fails
+	let cancel = |id: u32| {
+		json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "cancel_tx",
+			"params": {
+				"token": token,
+				"tx_id": 1000,
+				"tx_slate_id": null,
+			}
+		})
+	};
+	let top_level_dir = json!({
+		"jsonrpc": "2.0",
+		"id": 3,
+		"method": "get_top_level_directory",
+		"params": {}
+	});
+
+	// a single call, and a batch answered as the handler would
+	let reply = handle_timed_request("owner", &api as &dyn OwnerRpcS, cancel(1));
+	match reply {
+		MaybeReply::Reply(r) => assert!(!r["result"]["Err"].is_null()),
+		MaybeReply::DontReply => panic!("no reply"),
+	}
+	let batch = json!([cancel(2), top_level_dir]);
+	match handle_timed_request("owner", &api as &dyn OwnerRpcS, batch) {
+		MaybeReply::Reply(r) => {
+			let replies = r.as_array().unwrap();
+			assert_eq!(replies.len(), 2);
+			assert_eq!(replies[0]["id"], 2);
+			assert_eq!(replies[1]["id"], 3);
+		}
+		MaybeReply::DontReply => panic!("no reply"),
+	}
+
+	let metrics = api.retrieve_rpc_metrics(mask1)?;
+	assert_eq!(metrics.len(), 2);
+	let cancelled = &metrics[0];
+	assert_eq!(
+		(cancelled.api.as_str(), cancelled.method.as_str()),
+		("owner", "cancel_tx")
+	);
+	assert_eq!(cancelled.calls, 2);
+	assert_eq!(cancelled.errors, 2);
+	assert!(cancelled.p50_ms <= cancelled.p99_ms && cancelled.p99_ms <= cancelled.max_ms);
+	let dir = &metrics[1];
+	assert_eq!(dir.method, "get_top_level_directory");
+	assert_eq!(dir.calls, 1);
+	assert_eq!(dir.errors, 0);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn rpc_metrics() {
+	let test_dir = "test_output/rpc_metrics";
+	setup(test_dir);
+	if let Err(e) = rpc_metrics_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,124 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test scans and refreshes of a single account leave the other accounts as they are
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn scan_account_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// mine into both accounts
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "merchant")?;
+		api.set_active_account(m, "merchant")?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// lose an output of each account
+	let num_outputs = |account: &str| -> Result<usize, libwallet::Error> {
+		let mut num = 0;
+		wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+			api.set_active_account(m, account)?;
+			num = api.retrieve_outputs(m, false, false, false, None)?.1.len();
+			Ok(())
+		})?;
+		Ok(num)
+	};
+	for account in &["default", "merchant"] {
+		let mut lost = None;
+		wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+			api.set_active_account(m, account)?;
+			lost = Some(api.retrieve_outputs(m, false, false, false, None)?.1[0].clone());
+			Ok(())
+		})?;
+		let lost = lost.unwrap().output;
+		wallet_inst!(wallet1, w);
+		let mut batch = w.batch(mask1)?;
+		batch.delete(&lost.key_id, &lost.mmr_index, &None)?;
+		batch.commit()?;
+	}
+	assert_eq!(num_outputs("default")?, 2);
+	assert_eq!(num_outputs("merchant")?, 2);
+
+	// only the merchant account's output is restored
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.scan_account(m, "merchant", None, false)?;
+		assert!(api.scan_account(m, "unknown", None, false).is_err());
+		Ok(())
+	})?;
+	assert_eq!(num_outputs("default")?, 2);
+	assert_eq!(num_outputs("merchant")?, 3);
+
+	// the account refreshed is checked against the node
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.refresh_account(m, "merchant")?);
+		assert!(api.refresh_account(m, "unknown").is_err());
+		Ok(())
+	})?;
+	assert_eq!(num_outputs("merchant")?, 3);
+
+	// a scan of the whole wallet restores the other
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.scan(m, None, false)?;
+		Ok(())
+	})?;
+	assert_eq!(num_outputs("default")?, 3);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn scan_account() {
+	let test_dir = "test_output/scan_account";
+	setup(test_dir);
+	if let Err(e) = scan_account_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,115 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test scans of the chain running in the background as jobs
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::api_impl::owner;
+use libwallet::{ErrorKind, ScanJobStatus};
+use std::cell::RefCell;
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn scan_jobs_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// the scan reports its progress, and stops as soon as told to
+	let reports = RefCell::new(vec![]);
+	let progress = |percent: u8, height: Option<u64>| {
+		reports.borrow_mut().push((percent, height));
+		false
+	};
+	match owner::scan(wallet1.clone(), mask1, None, false, &None, Some(&progress)) {
+		Err(e) => match e.kind() {
+			ErrorKind::ScanCancelled => (),
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("scan not cancelled"),
+	}
+	assert_eq!(reports.borrow().len(), 1);
+	assert!(reports.borrow()[0].1.is_some());
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info_before) = api.retrieve_summary_info(m, true, 1)?;
+		let id = api.start_scan(m, None, false)?;
+		let mut job = api.get_job_status(m, id)?.unwrap();
+		for _ in 0..100 {
+			if job.status != ScanJobStatus::Running {
+				break;
+			}
+			thread::sleep(Duration::from_millis(100));
+			job = api.get_job_status(m, id)?.unwrap();
+		}
+		assert_eq!(job.status, ScanJobStatus::Completed);
+		assert_eq!(job.percent_complete, 100);
+		assert_eq!(job.start_height, 1);
+		assert!(job.current_height.unwrap() > 0);
+		assert!(job.error.is_none());
+
+		// finished and unknown jobs can't be cancelled
+		assert!(!api.cancel_job(m, id)?);
+		assert!(!api.cancel_job(m, Uuid::new_v4())?);
+		assert!(api.get_job_status(m, Uuid::new_v4())?.is_none());
+
+		// the wallet already had all its outputs
+		let (_, info_after) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info_after.total, info_before.total);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn scan_jobs() {
+	let test_dir = "test_output/scan_jobs";
+	setup(test_dir);
+	if let Err(e) = scan_jobs_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
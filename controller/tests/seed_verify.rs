@@ -0,0 +1,127 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test verifying the backup of a wallet's recovery phrase
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::ZeroingString;
+use libwallet::SeedWord;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// seed verification impl
+fn seed_verify_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let pw = || ZeroingString::from("");
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, _m| {
+		assert!(!api.seed_verified(None)?);
+
+		let mnemonic = api.get_mnemonic(None, pw())?;
+		let phrase: Vec<String> = mnemonic.split_whitespace().map(|w| w.to_owned()).collect();
+
+		let positions = api.get_seed_quiz(None, pw(), 4)?;
+		assert_eq!(positions.len(), 4);
+		assert!(positions.windows(2).all(|p| p[0] < p[1]));
+		assert!(positions.iter().all(|p| *p >= 1 && *p <= phrase.len()));
+		assert!(api.get_seed_quiz(None, pw(), phrase.len() + 1).is_err());
+
+		let answers = |positions: &[usize]| -> Vec<SeedWord> {
+			positions
+				.iter()
+				.map(|p| SeedWord {
+					position: *p,
+					word: phrase[*p - 1].clone(),
+				})
+				.collect()
+		};
+
+		// a wrong word doesn't verify the phrase
+		let mut words = answers(&positions);
+		words[0].word = "notaword".to_owned();
+		assert!(!api.verify_seed_words(None, pw(), words.clone())?);
+		assert!(!api.seed_verified(None)?);
+
+		// a quiz is only answered once
+		words[0].word = phrase[positions[0] - 1].to_uppercase();
+		assert!(!api.verify_seed_words(None, pw(), words)?);
+
+		// all the words asked for must be given
+		let positions = api.get_seed_quiz(None, pw(), 4)?;
+		let mut words = answers(&positions);
+		words.pop();
+		assert!(!api.verify_seed_words(None, pw(), words)?);
+
+		// and only those
+		let positions = api.get_seed_quiz(None, pw(), 4)?;
+		let mut words = answers(&positions);
+		let other = (1..=phrase.len()).find(|p| !positions.contains(p)).unwrap();
+		words[0] = answers(&[other]).remove(0);
+		assert!(!api.verify_seed_words(None, pw(), words)?);
+		assert!(!api.seed_verified(None)?);
+
+		let positions = api.get_seed_quiz(None, pw(), 4)?;
+		let mut words = answers(&positions);
+		words[0].word = words[0].word.to_uppercase();
+		assert!(api.verify_seed_words(None, pw(), words)?);
+		assert!(api.seed_verified(None)?);
+
+		// recovering from a phrase resets the verification
+		api.recover_from_mnemonic(None, mnemonic, pw())?;
+		assert!(!api.seed_verified(None)?);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_seed_verify() {
+	let test_dir = "test_output/seed_verify";
+	setup(test_dir);
+	if let Err(e) = seed_verify_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
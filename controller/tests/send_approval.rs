@@ -0,0 +1,232 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sends requiring the approval of a second operator before being sent or finalized
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use epic_wallet_api::{Owner, TokenScope};
+use epic_wallet_util::epic_util::{Mutex, ZeroingString};
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs, InitTxSendArgs, TxApproval};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::controller::RequestLimits;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Address of the foreign listener of the recipient
+const RECIPIENT_ADDR: &str = "127.0.0.1:43431";
+
+fn send_approval_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// the recipient listens over http
+	let wallet2_listener = wallet2.clone();
+	let mask2_listener = Arc::new(Mutex::new(mask2_i.clone()));
+	thread::spawn(move || {
+		let res = wallet::controller::foreign_listener(
+			wallet2_listener,
+			mask2_listener,
+			RECIPIENT_ADDR,
+			None,
+			false,
+			None,
+			RequestLimits::default(),
+			false,
+			false,
+			None,
+		);
+		if let Err(e) = res {
+			panic!("Foreign listener error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// the approver's and another operator's tokens
+	let api = Owner::new(wallet1.clone(), None);
+	let open = |scope| api.open_wallet(None, ZeroingString::from(""), false, Some(scope));
+	let approver = open(TokenScope::Approver)?.unwrap();
+	let operator = open(TokenScope::ReadOnly)?.unwrap();
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: 60_000_000_000,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+
+	{
+		let m = mask1;
+		// nothing to approve while sends don't require approval
+		assert!(api.approve_tx(&approver, 1).is_err());
+		api.set_require_approval(true);
+
+		let slate_i = api.init_send_tx(m, args.clone())?;
+		let slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		let tx_id = txs[0].id;
+		assert_eq!(txs[0].approval, Some(TxApproval::Pending));
+
+		// the send can't be finalized until approved
+		match api.finalize_tx(m, &slate) {
+			Err(e) => match e.kind() {
+				ErrorKind::ApprovalRequired(id) => assert_eq!(id, tx_id.to_string()),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("send finalized without approval"),
+		}
+
+		// only with the approver's token
+		match api.approve_tx(&operator, tx_id) {
+			Err(e) => match e.kind() {
+				ErrorKind::Approval(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("send approved with the wrong token"),
+		}
+		let tx = api.approve_tx(&approver, tx_id)?;
+		assert_eq!(tx.approval, Some(TxApproval::Approved));
+		assert!(api.approve_tx(&approver, tx_id).is_err());
+
+		let slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+	}
+
+	// a send from an account that isn't active is held for approval too
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "savings")?;
+		Ok(())
+	})?;
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("savings")?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("default")?;
+	}
+	{
+		let m = mask1;
+		let args = InitTxArgs {
+			src_acct_name: Some("savings".to_owned()),
+			..args.clone()
+		};
+		let slate_i = api.init_send_tx(m, args)?;
+		let slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		let tx_id = match api.finalize_tx(m, &slate) {
+			Err(e) => match e.kind() {
+				ErrorKind::ApprovalRequired(id) => id.parse::<u32>().unwrap(),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("send finalized without approval"),
+		};
+		let tx = api.approve_tx(&approver, tx_id)?;
+		assert_eq!(tx.tx_slate_id, Some(slate.id));
+		let slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// a send given send arguments isn't sent to its recipient until approved
+	let args = InitTxArgs {
+		send_args: Some(InitTxSendArgs {
+			method: "http".to_owned(),
+			dest: format!("http://{}", RECIPIENT_ADDR),
+			finalize: true,
+			post_tx: true,
+			fluff: false,
+		}),
+		..args.clone()
+	};
+	let slate = api.init_send_tx(mask1, args)?;
+	let received = || -> Result<usize, libwallet::Error> {
+		let mut count = 0;
+		wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+			count = api.retrieve_txs(m, false, None, Some(slate.id))?.1.len();
+			Ok(())
+		})?;
+		Ok(count)
+	};
+	thread::sleep(Duration::from_millis(500));
+	assert_eq!(received()?, 0);
+	let (_, txs) = api.retrieve_txs(mask1, false, None, Some(slate.id))?;
+	assert_eq!(txs[0].approval, Some(TxApproval::Pending));
+
+	// then sent, finalized and posted
+	let tx = api.approve_tx(&approver, txs[0].id)?;
+	assert_eq!(tx.tx_slate_id, Some(slate.id));
+	assert_eq!(received()?, 1);
+	let (_, txs) = api.retrieve_txs(mask1, false, None, Some(slate.id))?;
+	assert_eq!(txs[0].approval, Some(TxApproval::Approved));
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_send_approval() {
+	let test_dir = "test_output/send_approval";
+	setup(test_dir);
+	if let Err(e) = send_approval_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
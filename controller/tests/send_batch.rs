@@ -0,0 +1,171 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test initiating transactions to several recipients in a single call
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use epic_wallet_api::Owner;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{InitTxArgs, InitTxSendArgs, OutputStatus, TxLogEntryType};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Address nothing listens on
+const UNREACHABLE_ADDR: &str = "127.0.0.1:43432";
+
+fn send_batch_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 2)?;
+		let spendable = info.amount_currently_spendable;
+		let args = |amount| InitTxArgs {
+			src_acct_name: None,
+			amount,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+
+		let locked = |api: &Owner<_, _, _>| -> Result<usize, libwallet::Error> {
+			let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+			Ok(outputs
+				.iter()
+				.filter(|o| o.output.status == OutputStatus::Locked)
+				.count())
+		};
+
+		// the last recipient can't be paid from what the others leave, so none are
+		let results = api.init_send_tx_batch(
+			m,
+			vec![args(spendable / 4), args(spendable / 4), args(spendable)],
+		)?;
+		assert_eq!(results.len(), 3);
+		assert_eq!(results[0].amount, spendable / 4);
+		assert!(results.iter().all(|r| r.slate.is_none()));
+		assert!(results[2]
+			.error
+			.as_ref()
+			.unwrap()
+			.contains("Not enough funds"));
+		assert_eq!(locked(api)?, 0);
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert!(txs.iter().all(|t| t.tx_type != TxLogEntryType::TxSent));
+
+		// nor are they if a slate can't be sent to its recipient
+		let mut unreachable = args(spendable / 4);
+		unreachable.send_args = Some(InitTxSendArgs {
+			method: "http".to_owned(),
+			dest: format!("http://{}", UNREACHABLE_ADDR),
+			finalize: true,
+			post_tx: true,
+			fluff: false,
+		});
+		let results = api.init_send_tx_batch(m, vec![args(spendable / 4), unreachable])?;
+		assert!(results.iter().all(|r| r.slate.is_none()));
+		assert_eq!(locked(api)?, 0);
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		let cancelled = txs
+			.iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxSentCancelled)
+			.count();
+		assert_eq!(cancelled, 2);
+
+		// else the transactions spend different outputs
+		let results = api.init_send_tx_batch(m, vec![args(spendable / 4), args(spendable / 4)])?;
+		let mut inputs = HashSet::new();
+		for r in results.iter() {
+			assert!(r.error.is_none());
+			let slate = r.slate.as_ref().unwrap();
+			for i in slate.tx.inputs() {
+				assert!(inputs.insert(i.commitment()));
+			}
+		}
+
+		// which are locked already
+		assert_eq!(locked(api)?, inputs.len());
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		let sent = txs
+			.iter()
+			.filter(|t| t.tx_type == TxLogEntryType::TxSent)
+			.count();
+		assert_eq!(sent, 2);
+
+		// and can be completed as usual
+		for r in results.iter() {
+			let slate = client1.send_tx_slate_direct("wallet2", r.slate.as_ref().unwrap())?;
+			api.finalize_tx(m, &slate)?;
+		}
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_send_batch() {
+	let test_dir = "test_output/send_batch";
+	setup(test_dir);
+	if let Err(e) = send_batch_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
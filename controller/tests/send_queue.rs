@@ -0,0 +1,229 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test queueing sends through the owner API
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use epic_wallet_api::{Owner, SEND_QUEUE_MAX_ATTEMPTS};
+use epic_wallet_util::epic_keychain::Keychain;
+use epic_wallet_util::epic_util::secp::key::SecretKey;
+use epic_wallet_util::epic_util::Mutex;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{
+	InitTxArgs, InitTxSendArgs, NodeClient, SendJob, SendJobStatus, SendPriority, TxLogEntryType,
+	WalletLCProvider,
+};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use wallet::controller::RequestLimits;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Address of the foreign listener of the recipient
+const RECIPIENT_ADDR: &str = "127.0.0.1:43425";
+
+/// Address nothing listens on
+const UNREACHABLE_ADDR: &str = "127.0.0.1:43426";
+
+/// Wait for a queued send to finish, returning its job
+fn wait_for_job<L, C, K>(api: &Owner<L, C, K>, mask: Option<&SecretKey>, id: Uuid) -> SendJob
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let start = Instant::now();
+	loop {
+		let job = api.get_send_job(mask, id).unwrap().unwrap();
+		match job.status {
+			SendJobStatus::Completed | SendJobStatus::Failed => return job,
+			_ => {}
+		}
+		assert!(
+			start.elapsed() < Duration::from_secs(60),
+			"send job not done"
+		);
+		thread::sleep(Duration::from_millis(100));
+	}
+}
+
+fn send_queue_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// the recipient listens over http
+	let wallet2_listener = wallet2.clone();
+	let mask2_listener = Arc::new(Mutex::new(mask2_i.clone()));
+	thread::spawn(move || {
+		let res = wallet::controller::foreign_listener(
+			wallet2_listener,
+			mask2_listener,
+			RECIPIENT_ADDR,
+			None,
+			false,
+			None,
+			RequestLimits::default(),
+			false,
+			false,
+			None,
+		);
+		if let Err(e) = res {
+			panic!("Foreign listener error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let mut args = InitTxArgs {
+			src_acct_name: None,
+			amount: 1_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+
+		// a queued send needs somewhere to go
+		assert!(api
+			.queue_send(m, args.clone(), SendPriority::Normal)
+			.is_err());
+
+		// through a transport that doesn't need the caller
+		args.send_args = Some(InitTxSendArgs {
+			method: "file".to_owned(),
+			dest: "tx.tx".to_owned(),
+			finalize: true,
+			post_tx: true,
+			fluff: false,
+		});
+		assert!(api.queue_send(m, args, SendPriority::High).is_err());
+
+		// and nothing was queued
+		assert!(api.retrieve_send_jobs(m)?.is_empty());
+		assert!(api.get_send_job(m, Uuid::new_v4())?.is_none());
+		Ok(())
+	})?;
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: 1_000_000_000,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		send_args: Some(InitTxSendArgs {
+			method: "http".to_owned(),
+			dest: format!("http://{}", RECIPIENT_ADDR),
+			finalize: true,
+			post_tx: true,
+			fluff: false,
+		}),
+		..Default::default()
+	};
+
+	// a queued send is exchanged, finalized and posted in the background
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let id = api.queue_send(m, args.clone(), SendPriority::Normal)?;
+		let job = wait_for_job(api, m, id);
+		assert_eq!(job.status, SendJobStatus::Completed, "{:?}", job.error);
+		assert_eq!(job.attempts, 1);
+		assert_eq!(api.retrieve_send_jobs(m)?.len(), 1);
+
+		let (_, txs) = api.retrieve_txs(m, false, None, job.slate_id)?;
+		assert_eq!(txs[0].tx_type, TxLogEntryType::TxSent);
+		assert!(api.get_stored_tx(m, &txs[0])?.is_some());
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_currently_spendable, 1_000_000_000);
+		Ok(())
+	})?;
+
+	// a send which can't be exchanged is retried, then fails and is cancelled
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_send_queue_retry_delay(Duration::from_millis(10));
+		let mut args = args.clone();
+		if let Some(sa) = args.send_args.as_mut() {
+			sa.dest = format!("http://{}", UNREACHABLE_ADDR);
+		}
+		let id = api.queue_send(m, args, SendPriority::Normal)?;
+		let job = wait_for_job(api, m, id);
+		assert_eq!(job.status, SendJobStatus::Failed);
+		assert_eq!(job.attempts, SEND_QUEUE_MAX_ATTEMPTS);
+		assert!(job.error.is_some());
+
+		let (_, txs) = api.retrieve_txs(m, false, None, job.slate_id)?;
+		assert_eq!(txs[0].tx_type, TxLogEntryType::TxSentCancelled);
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.amount_locked, 0);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_send_queue() {
+	let test_dir = "test_output/send_queue";
+	setup(test_dir);
+	if let Err(e) = send_queue_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,138 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test saving send templates for repeated payments
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::LocalWalletClient;
+use libwallet::{ErrorKind, SendTemplate};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn send_templates_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "payroll")?;
+		let template = SendTemplate {
+			name: " payroll-john ".to_owned(),
+			method: "Epicbox".to_owned(),
+			dest: "@john".to_owned(),
+			amount: 2_000_000_000,
+			src_acct_name: Some("payroll".to_owned()),
+			message: Some("Salary".to_owned()),
+		};
+		let saved = api.save_send_template(m, &template)?;
+		assert_eq!(saved.name, "payroll-john");
+		assert_eq!(saved.method, "epicbox");
+		assert_eq!(api.get_send_template(m, "payroll-john")?, saved);
+
+		// templates must describe a send the wallet can make
+		let invalid = vec![
+			SendTemplate {
+				method: "emoji".to_owned(),
+				..template.clone()
+			},
+			SendTemplate {
+				dest: " ".to_owned(),
+				..template.clone()
+			},
+			SendTemplate {
+				amount: 0,
+				..template.clone()
+			},
+		];
+		for t in invalid.iter() {
+			assert!(api.save_send_template(m, t).is_err());
+		}
+		let unknown_account = SendTemplate {
+			src_acct_name: Some("savings".to_owned()),
+			..template.clone()
+		};
+		match api.save_send_template(m, &unknown_account) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnknownAccountLabel(a) => assert_eq!(a, "savings"),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("template saved for an unknown account"),
+		}
+
+		// saving under the same name replaces the template
+		let raised = api.save_send_template(
+			m,
+			&SendTemplate {
+				amount: 2_500_000_000,
+				..template.clone()
+			},
+		)?;
+		api.save_send_template(
+			m,
+			&SendTemplate {
+				name: "hosting".to_owned(),
+				method: "http".to_owned(),
+				dest: "http://127.0.0.1:3415".to_owned(),
+				src_acct_name: None,
+				message: None,
+				..template.clone()
+			},
+		)?;
+		let templates = api.retrieve_send_templates(m)?;
+		assert_eq!(templates.len(), 2);
+		assert_eq!(templates[0].name, "hosting");
+		assert_eq!(templates[1], raised);
+
+		api.remove_send_template(m, "hosting")?;
+		match api.remove_send_template(m, "hosting") {
+			Err(e) => match e.kind() {
+				ErrorKind::UnknownSendTemplate(n) => assert_eq!(n, "hosting"),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("unknown template removed"),
+		}
+		assert!(api.get_send_template(m, "hosting").is_err());
+		assert_eq!(api.retrieve_send_templates(m)?, vec![raised]);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn send_templates() {
+	let test_dir = "test_output/send_templates";
+	setup(test_dir);
+	if let Err(e) = send_templates_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,153 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test verifying the recipient's signature of its response when finalizing
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs, Slate};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn signed_response_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut address1 = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		address1 = Some(api.get_public_proof_address(m, 0)?);
+		Ok(())
+	})?;
+	let mut address2 = None;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		address2 = Some(api.get_public_proof_address(m, 0)?);
+		Ok(())
+	})?;
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: 60_000_000_000,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		response_address: address2,
+		..Default::default()
+	};
+
+	// an unsigned response is refused when a signed one is required
+	let mut slate = Slate::blank(1);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.init_send_tx(m, args.clone())?;
+		Ok(())
+	})?;
+	wallet::controller::foreign_single_use(wallet2.clone(), mask2_i.clone(), |api| {
+		slate = api.receive_tx(&slate, None, None)?;
+		Ok(())
+	})?;
+	assert!(slate.response_signature.is_none());
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.tx_lock_outputs(m, &slate, 0)?;
+		match api.finalize_tx(m, &slate) {
+			Err(e) => match e.kind() {
+				ErrorKind::ResponseSignature(_) => {}
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("finalized an unsigned response"),
+		}
+		Ok(())
+	})?;
+
+	// a recipient signing its responses is accepted
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		slate = api.init_send_tx(m, args.clone())?;
+		Ok(())
+	})?;
+	wallet::controller::foreign_single_use(wallet2.clone(), mask2_i.clone(), |api| {
+		api.set_sign_responses(true);
+		slate = api.receive_tx(&slate, None, None)?;
+		Ok(())
+	})?;
+	assert_eq!(
+		slate.response_signature.as_ref().map(|s| s.address),
+		address2
+	);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// unless the signature was swapped on the way back
+		let mut relayed = slate.clone();
+		relayed.response_signature.as_mut().unwrap().address = address1.unwrap();
+		assert!(api.finalize_tx(m, &relayed).is_err());
+
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_signed_response() {
+	let test_dir = "test_output/signed_response";
+	setup(test_dir);
+	if let Err(e) = signed_response_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,243 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test creating slates restricted to an older slate version
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{HttpSlateSender, SlateSender};
+use libwallet::slate_versions::{SlateVersion, VersionedSlate};
+use libwallet::{ErrorKind, InitTxArgs, Slate};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+const V2_LISTENER_ADDR: &str = "127.0.0.1:43424";
+
+/// The JSON-RPC request sent over the stream
+fn read_request(stream: &TcpStream) -> Value {
+	let mut reader = BufReader::new(stream);
+	let mut len = 0;
+	loop {
+		let mut line = String::new();
+		reader.read_line(&mut line).unwrap();
+		let line = line.trim_end().to_lowercase();
+		if line.is_empty() {
+			break;
+		}
+		if let Some(l) = line.strip_prefix("content-length:") {
+			len = l.trim().parse().unwrap();
+		}
+	}
+	let mut body = vec![0; len];
+	reader.read_exact(&mut body).unwrap();
+	serde_json::from_slice(&body).unwrap()
+}
+
+/// Answer the request with the given result
+fn write_response(mut stream: &TcpStream, result: Value) {
+	let body = json!({"jsonrpc": "2.0", "id": 1, "result": {"Ok": result}}).to_string();
+	let res = format!(
+		"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+		 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+		body.len(),
+		body
+	);
+	stream.write_all(res.as_bytes()).unwrap();
+}
+
+fn slate_compat_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut address = None;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		address = Some(api.get_public_proof_address(m, 0)?);
+		Ok(())
+	})?;
+
+	let mut slate = Slate::blank(1);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 60_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			target_slate_version: Some(2),
+			ttl_blocks: Some(10),
+			..Default::default()
+		};
+
+		// a payment proof can't be carried by a V2 slate
+		let proof_args = InitTxArgs {
+			payment_proof_recipient_address: address,
+			..args.clone()
+		};
+		assert!(api.init_send_tx(m, proof_args).is_err());
+
+		// the TTL is left out of the slate
+		let slate_i = api.init_send_tx(m, args)?;
+		assert_eq!(slate_i.version_info.version, 2);
+		assert_eq!(slate_i.version_info.orig_version, 2);
+		assert_eq!(slate_i.ttl_cutoff_height, None);
+
+		slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+
+		// the version the slate was exchanged in is recorded
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].slate_version, Some(2));
+		Ok(())
+	})?;
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	// a wallet only supporting V2 slates gets the slate downgraded, without its TTL
+	let listener = TcpListener::bind(V2_LISTENER_ADDR).unwrap();
+	let (sent_tx, sent_rx) = mpsc::channel();
+	let wallet2_listener = wallet2.clone();
+	let mask2_listener = mask2_i.clone();
+	thread::spawn(move || {
+		for stream in listener.incoming().take(2) {
+			let stream = stream.unwrap();
+			let req = read_request(&stream);
+			match req["method"].as_str() {
+				Some("check_version") => write_response(
+					&stream,
+					json!({"foreign_api_version": 2, "supported_slate_versions": ["V2"]}),
+				),
+				Some("receive_tx") => {
+					let slate_json = req["params"][0].clone();
+					sent_tx.send(slate_json.clone()).unwrap();
+					let mut slate = Slate::deserialize_upgrade(&slate_json.to_string()).unwrap();
+					wallet::controller::foreign_single_use(
+						wallet2_listener.clone(),
+						mask2_listener.clone(),
+						|api| {
+							slate = api.receive_tx(&slate, None, None)?;
+							Ok(())
+						},
+					)
+					.unwrap();
+					let res = VersionedSlate::into_version(slate, SlateVersion::V2);
+					write_response(&stream, serde_json::to_value(&res).unwrap());
+				}
+				m => panic!("unexpected method {:?}", m),
+			}
+		}
+	});
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 60_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			ttl_blocks: Some(10),
+			..Default::default()
+		};
+		let slate_i = api.init_send_tx(m, args)?;
+		assert_eq!(slate_i.version_info.version, 3);
+		assert!(slate_i.ttl_cutoff_height.is_some());
+
+		let sender = HttpSlateSender::new(&format!("http://{}", V2_LISTENER_ADDR)).unwrap();
+		slate = sender.send_tx(&slate_i)?;
+		let sent = sent_rx.recv().unwrap();
+		assert_eq!(sent["version_info"]["version"], json!(2));
+		assert_eq!(sent["version_info"]["orig_version"], json!(2));
+		assert!(sent.get("ttl_cutoff_height").map_or(true, |t| t.is_null()));
+
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].slate_version, Some(2));
+		Ok(())
+	})?;
+
+	// a slate of an unknown version is reported as such
+	let mut json = serde_json::to_value(&slate).unwrap();
+	json["version_info"]["version"] = serde_json::json!(9);
+	match Slate::deserialize_upgrade(&json.to_string()) {
+		Err(e) => match e.kind() {
+			ErrorKind::SlateVersion(9) => {}
+			k => panic!("unexpected error {}", k),
+		},
+		Ok(_) => panic!("slate of unknown version accepted"),
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_slate_compat() {
+	let test_dir = "test_output/slate_compat";
+	setup(test_dir);
+	if let Err(e) = slate_compat_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
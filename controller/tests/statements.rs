@@ -0,0 +1,176 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test account statements and writing them with their fiat value
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use self::core::global;
+use chrono::{Duration as ChronoDuration, Utc};
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use impls::StaticPriceFeed;
+use libwallet::{AccountStatement, InitTxArgs, TxLogEntryType};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn statements_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = global::coinbase_maturity() as usize;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 2, false);
+
+	let amount = reward / 2;
+	let mut fee = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		let mut slate = api.init_send_tx(m, args)?;
+		slate = client1.send_tx_slate_direct("wallet2", &slate)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		fee = slate.fee;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+
+	let now = Utc::now();
+	let (start, end) = (now - ChronoDuration::days(1), now + ChronoDuration::days(1));
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		let (_, statements) = api.account_statements(m, true, start, end)?;
+		assert_eq!(statements.len(), 1);
+		let s = &statements[0];
+		assert_eq!(s.account, "default");
+		assert_eq!(s.opening_balance, 0);
+		assert_eq!(s.closing_balance, info.total);
+		assert_eq!(s.total_fees, fee);
+		assert_eq!(s.closing_balance, s.total_credited - s.total_debited);
+		let sent: Vec<_> = s
+			.lines
+			.iter()
+			.filter(|l| l.tx_type == TxLogEntryType::TxSent)
+			.collect();
+		assert_eq!(sent.len(), 1);
+		assert_eq!(sent[0].debited - sent[0].credited, amount + fee);
+		assert_eq!(s.lines.last().unwrap().balance, s.closing_balance);
+
+		// a later period opens with the balance it closed with
+		let later = (end, end + ChronoDuration::days(1));
+		let (_, statements) = api.account_statements(m, false, later.0, later.1)?;
+		assert_eq!(statements[0].opening_balance, s.closing_balance);
+		assert_eq!(statements[0].closing_balance, s.closing_balance);
+		assert!(statements[0].lines.is_empty());
+		Ok(())
+	})?;
+
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, statements) = api.account_statements(m, true, start, end)?;
+		assert_eq!(statements[0].closing_balance, amount);
+		assert_eq!(statements[0].total_fees, 0);
+		Ok(())
+	})?;
+
+	// statements are written once, valued at the feed's price
+	let dir = format!("{}/statements", test_dir);
+	fs::create_dir_all(&dir).unwrap();
+	let prices = format!("{}/prices.json", test_dir);
+	fs::write(&prices, r#"{"USD": 0.5}"#).unwrap();
+	let feed = StaticPriceFeed::new(&prices);
+	let written = wallet::command::write_statements(
+		wallet2.clone(),
+		mask2,
+		&dir,
+		start,
+		end,
+		Some(("usd", &feed)),
+	)
+	.unwrap();
+	assert_eq!(written.len(), 1);
+	let statement: AccountStatement =
+		serde_json::from_str(&fs::read_to_string(&written[0]).unwrap()).unwrap();
+	assert_eq!(statement.closing_balance, amount);
+	let fiat = statement.fiat.unwrap();
+	assert_eq!(fiat.currency, "USD");
+	assert_eq!(
+		fiat.closing_balance,
+		amount as f64 / core::consensus::EPIC_BASE as f64 * 0.5
+	);
+	let written =
+		wallet::command::write_statements(wallet2.clone(), mask2, &dir, start, end, None).unwrap();
+	assert!(written.is_empty());
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn statements() {
+	let test_dir = "test_output/statements";
+	setup(test_dir);
+	if let Err(e) = statements_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
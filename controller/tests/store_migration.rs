@@ -0,0 +1,117 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test converting a wallet's store between backends
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{migrate_store, StoreBackend};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, open_local_wallet, setup};
+
+fn store_migration_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let mut total = 0;
+	let mut num_outputs = 0;
+	let mut num_txs = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		num_outputs = api.retrieve_outputs(m, true, false, false, None)?.1.len();
+		num_txs = api.retrieve_txs(m, false, None, None)?.1.len();
+		api.create_account_path(m, "savings")?;
+		Ok(())
+	})?;
+	assert!(total > 0);
+	{
+		let mut w_lock = wallet1.lock();
+		w_lock.lc_provider()?.close_wallet(None)?;
+	}
+
+	// there and back again
+	let data_dir = format!("{}/wallet1/wallet_data", test_dir);
+	let m = migrate_store(&data_dir, StoreBackend::Lmdb)?;
+	assert_eq!(m.from, StoreBackend::Sqlite);
+	assert_eq!(m.outputs, num_outputs);
+	assert!(m.backup.exists());
+	assert!(Path::new(&data_dir).join("db/lmdb").exists());
+	assert!(!Path::new(&data_dir).join("db/sqlite").exists());
+
+	// an existing store isn't overwritten
+	assert!(migrate_store(&data_dir, StoreBackend::Lmdb).is_err());
+	std::fs::remove_dir_all(&m.backup).unwrap();
+
+	let m = migrate_store(&data_dir, StoreBackend::Sqlite)?;
+	assert_eq!(m.from, StoreBackend::Lmdb);
+	assert_eq!(m.outputs, num_outputs);
+
+	// the wallet finds everything where it was
+	let (wallet1, mask1_i) = open_local_wallet(test_dir, "wallet1", client1.clone(), false);
+	let mask1 = (&mask1_i).as_ref();
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, false, 1)?;
+		assert_eq!(info.total, total);
+		let (_, outputs) = api.retrieve_outputs(m, true, false, false, None)?;
+		assert_eq!(outputs.len(), num_outputs);
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.len(), num_txs);
+		assert!(api.accounts(m)?.iter().any(|a| a.label == "savings"));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_store_migration() {
+	let test_dir = "test_output/store_migration";
+	setup(test_dir);
+	if let Err(e) = store_migration_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,297 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test atomic swaps of epics between two wallets, redeemed by the buyer or refunded to
+//! the seller
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+use epic_wallet_util::epic_util::secp::Signature;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{
+	ErrorKind, OutputStatus, SwapCurrency, SwapMessage, SwapMessageBody, SwapStartArgs, SwapStatus,
+	SwapStep,
+};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// The message of a step, as read back from the file it's sent in
+fn sent(step: &SwapStep) -> Option<SwapMessage> {
+	let message = step.message.as_ref().expect("no message sent");
+	Some(serde_json::from_str(&serde_json::to_string(message).unwrap()).unwrap())
+}
+
+fn swap_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let amount = 2_000_000_000;
+	let fee = core::libtx::tx_fee(1, 1, 1, None);
+	let args = SwapStartArgs {
+		src_acct_name: None,
+		amount,
+		secondary_currency: SwapCurrency::Btc,
+		secondary_amount: 1_000_000,
+		minimum_confirmations: 2,
+		refund_blocks: 50,
+		secondary_lock_hours: 2,
+	};
+
+	// the buyer could refund the bitcoins before the seller redeemed them
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let short = SwapStartArgs {
+			secondary_lock_hours: 1,
+			..args.clone()
+		};
+		match api.swap_start(m, short) {
+			Err(e) => match e.kind() {
+				ErrorKind::Swap(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("swap started with a lock too short"),
+		}
+		Ok(())
+	})?;
+
+	// the seller offers, the buyer accepts and the seller signs the lock
+	let mut step = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		step = Some(api.swap_start(m, args.clone())?);
+		Ok(())
+	})?;
+	let offer = step.take().unwrap();
+	let id = offer.swap.id;
+	assert_eq!(offer.swap.status, SwapStatus::Offered);
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		step = Some(api.swap_process(m, id, sent(&offer))?);
+		Ok(())
+	})?;
+	let accept = step.take().unwrap();
+	assert_eq!(accept.swap.status, SwapStatus::Accepted);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		step = Some(api.swap_process(m, id, sent(&accept))?);
+		Ok(())
+	})?;
+	let locked = step.take().unwrap();
+	assert_eq!(locked.swap.status, SwapStatus::Locking);
+	assert!(locked.swap.refund_tx.is_some());
+	assert_eq!(
+		locked.swap.secondary_lock_script,
+		accept.swap.secondary_lock_script
+	);
+
+	// the buyer completes and posts the lock
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let s = api.swap_process(m, id, sent(&locked))?;
+		assert_eq!(s.swap.status, SwapStatus::Locking);
+		assert!(s.message.is_none());
+		Ok(())
+	})?;
+
+	// the redeem waits for the lock to be confirmed
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		match api.swap_process(m, id, None) {
+			Err(e) => match e.kind() {
+				ErrorKind::Swap(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("redeem started before the lock was confirmed"),
+		}
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// the seller starts the redeem, the buyer adds its adaptor signature
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		step = Some(api.swap_process(m, id, None)?);
+		Ok(())
+	})?;
+	let redeem = step.take().unwrap();
+	assert_eq!(redeem.swap.status, SwapStatus::Redeeming);
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		step = Some(api.swap_process(m, id, sent(&redeem))?);
+		Ok(())
+	})?;
+	let adapted = step.take().unwrap();
+	assert_eq!(adapted.swap.status, SwapStatus::SecondaryLocking);
+	match adapted.message.as_ref().map(|m| &m.body) {
+		Some(SwapMessageBody::RedeemAdaptor { .. }) => (),
+		_ => panic!("no adaptor signature sent"),
+	}
+
+	// a tampered adaptor signature doesn't check out
+	let mut tampered = sent(&adapted).unwrap();
+	if let SwapMessageBody::RedeemAdaptor {
+		ref mut adaptor, ..
+	} = tampered.body
+	{
+		let mut raw = adaptor.to_raw_data();
+		raw[63] ^= 1;
+		*adaptor = Signature::from_raw_data(&raw).unwrap();
+	}
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.swap_process(m, id, Some(tampered.clone())).is_err());
+		Ok(())
+	})?;
+
+	// the seller checks it and, once the bitcoins are locked, signs the redeem
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let s = api.swap_process(m, id, sent(&adapted))?;
+		assert_eq!(s.swap.status, SwapStatus::SecondaryLocking);
+		assert!(s.message.is_none());
+		step = Some(api.swap_process(m, id, None)?);
+		Ok(())
+	})?;
+	let signed = step.take().unwrap();
+	assert_eq!(signed.swap.status, SwapStatus::Signed);
+
+	// the buyer completes and posts the redeem
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let s = api.swap_process(m, id, sent(&signed))?;
+		assert_eq!(s.swap.status, SwapStatus::Redeemed);
+		assert!(s.swap.secondary_secret.is_none());
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	// the seller takes the secret out of the posted redeem
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let s = api.swap_process(m, id, None)?;
+		assert_eq!(s.swap.status, SwapStatus::Redeemed);
+		assert!(s.swap.secondary_secret.is_some());
+		assert!(s.swap.secondary_key.is_some());
+		// nothing left to cancel
+		assert!(api.swap_cancel(m, id).is_err());
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, amount - fee);
+		Ok(())
+	})?;
+
+	// a swap the buyer walks away from is refunded to the seller from the refund height
+	let args = SwapStartArgs {
+		refund_blocks: 5,
+		secondary_lock_hours: 1,
+		..args
+	};
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		step = Some(api.swap_start(m, args.clone())?);
+		Ok(())
+	})?;
+	let offer = step.take().unwrap();
+	let id = offer.swap.id;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		step = Some(api.swap_process(m, id, sent(&offer))?);
+		Ok(())
+	})?;
+	let accept = step.take().unwrap();
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		step = Some(api.swap_process(m, id, sent(&accept))?);
+		Ok(())
+	})?;
+	let locked = step.take().unwrap();
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		api.swap_process(m, id, sent(&locked))?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		match api.swap_cancel(m, id) {
+			Err(e) => match e.kind() {
+				ErrorKind::Swap(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("refunded before the refund height"),
+		}
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let swap = api.swap_cancel(m, id)?;
+		assert_eq!(swap.status, SwapStatus::Refunded);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let swap = api.swap_cancel(m, id)?;
+		assert_eq!(swap.status, SwapStatus::Cancelled);
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, outputs) = api.retrieve_outputs(m, false, true, false, None)?;
+		assert!(outputs
+			.iter()
+			.any(|o| o.output.value == amount - fee && o.output.status == OutputStatus::Unspent));
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_swap() {
+	let test_dir = "test_output/swap";
+	setup(test_dir);
+	if let Err(e) = swap_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
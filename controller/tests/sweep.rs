@@ -0,0 +1,196 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sweeping the balance of a hot account above a threshold to a cold account
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core as core;
+use epic_wallet_util::epic_keychain::Keychain;
+use epic_wallet_util::epic_util::secp::key::SecretKey;
+use epic_wallet_util::epic_util::Mutex;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{NodeClient, SweepPolicy, WalletInfo, WalletInst, WalletLCProvider, SWEEP_TX_TAG};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Balances of the given account, leaving the mining account active
+fn account_info<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	mask: Option<&SecretKey>,
+	label: &str,
+) -> Result<WalletInfo, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let mut info = None;
+	wallet::controller::owner_single_use(wallet, mask, |api, m| {
+		api.set_active_account(m, label)?;
+		let (refreshed, i) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(refreshed);
+		info = Some(i);
+		api.set_active_account(m, "mining")?;
+		Ok(())
+	})?;
+	Ok(info.unwrap())
+}
+
+fn sweep_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = core::global::coinbase_maturity() as usize;
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "hot")?;
+		api.create_account_path(m, "cold")?;
+		api.create_account_path(m, "mining")?;
+		Ok(())
+	})?;
+
+	// fund the hot account, and mine elsewhere until all its coinbases are mature
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("hot")?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("mining")?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 1, false);
+
+	let hot = account_info(wallet1.clone(), mask1, "hot")?;
+	assert_eq!(hot.amount_currently_spendable, 5 * reward);
+	let mut policy = SweepPolicy {
+		hot_account: "hot".to_owned(),
+		cold_account: "cold".to_owned(),
+		max_hot_balance: hot.amount_currently_spendable,
+		minimum_confirmations: 1,
+	};
+
+	// nothing above the threshold
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.sweep_hot_account(m, &policy)?.is_none());
+		Ok(())
+	})?;
+
+	// the remainder pays the fee
+	policy.max_hot_balance = hot.amount_currently_spendable - reward;
+	let mut fee = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let slate = api.sweep_hot_account(m, &policy)?.unwrap();
+		assert_eq!(slate.amount, reward);
+		fee = slate.fee;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	let hot = account_info(wallet1.clone(), mask1, "hot")?;
+	assert_eq!(hot.total, policy.max_hot_balance - fee);
+	let cold = account_info(wallet1.clone(), mask1, "cold")?;
+	assert_eq!(cold.total, reward);
+
+	// a remainder too low for the fee is left as change, the fee being deducted from
+	// the swept amount
+	let cold_before = cold.total;
+	let hot_before = hot.amount_currently_spendable;
+	policy.max_hot_balance = 1;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let slate = api.sweep_hot_account(m, &policy)?.unwrap();
+		assert_eq!(slate.amount + slate.fee + 1, hot_before);
+		api.set_active_account(m, "cold")?;
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert_eq!(txs[0].tags, vec![SWEEP_TX_TAG.to_owned()]);
+		api.set_active_account(m, "mining")?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	let hot = account_info(wallet1.clone(), mask1, "hot")?;
+	assert_eq!(hot.total, 1);
+	let cold = account_info(wallet1.clone(), mask1, "cold")?;
+	assert!(cold.total > cold_before);
+
+	// too little left to pay the fee of sweeping it
+	policy.max_hot_balance = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.sweep_hot_account(m, &policy)?.is_none());
+		Ok(())
+	})?;
+
+	// without any threshold everything is swept, the fee being deducted from it
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("hot")?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("mining")?;
+	}
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, cm + 1, false);
+	let hot_before = account_info(wallet1.clone(), mask1, "hot")?.amount_currently_spendable;
+	assert_eq!(hot_before, 2 * reward + 1);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let slate = api.sweep_hot_account(m, &policy)?.unwrap();
+		assert_eq!(slate.amount + slate.fee, hot_before);
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
+	let hot = account_info(wallet1.clone(), mask1, "hot")?;
+	assert_eq!(hot.total, 0);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_sweep() {
+	let test_dir = "test_output/sweep";
+	setup(test_dir);
+	if let Err(e) = sweep_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
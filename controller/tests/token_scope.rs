@@ -0,0 +1,180 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the calls made with scoped owner API tokens are limited to their scope
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_api::{Owner, TokenScope};
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::{to_hex, ZeroingString};
+
+use easy_jsonrpc_mw::MaybeReply;
+use impls::test_framework::LocalWalletClient;
+use serde_json::json;
+use wallet::controller::OwnerV3Helpers;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn token_scope_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		_mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		true
+	);
+
+	let api = Owner::new(wallet1.clone(), None);
+	let open = |scope| -> Result<String, libwallet::Error> {
+		let token = api.open_wallet(None, ZeroingString::from(""), true, scope)?;
+		Ok(to_hex(token.unwrap().0.to_vec()))
+	};
+	let read_only = open(Some(TokenScope::ReadOnly))?;
+	let limited = open(Some(TokenScope::SendLimited { max_amount: 1_000 }))?;
+	// the full scope is the mask itself, which the tokens opened with before now stand for
+	let mask = open(Some(TokenScope::Full))?;
+	assert!(api.token_scope(&mask).is_none());
+	let mask = json!(mask);
+
+	// the token is replaced by the wallet's mask for the calls in scope
+	let mut call = json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "retrieve_summary_info",
+		"params": {"token": read_only, "refresh_from_node": false, "minimum_confirmations": 1}
+	});
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call).is_ok());
+	assert_eq!(call["params"]["token"], mask);
+
+	// and the others refused, as are refreshes from the node
+	let mut call = json!({
+		"jsonrpc": "2.0",
+		"id": 2,
+		"method": "init_send_tx",
+		"params": {"token": read_only, "args": {"amount": "500"}}
+	});
+	let err = OwnerV3Helpers::apply_token_scope(&api, &mut call).unwrap_err();
+	assert_eq!(err["error"]["code"], -32004);
+	assert_eq!(err["id"], 2);
+	let mut call = json!({
+		"jsonrpc": "2.0",
+		"id": 3,
+		"method": "retrieve_txs",
+		"params": [read_only, true, null, null]
+	});
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call).is_err());
+	let mut call = json!({
+		"jsonrpc": "2.0",
+		"id": 4,
+		"method": "refresh_account",
+		"params": {"token": read_only}
+	});
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call).is_err());
+
+	// sends are limited to the token's amount in total, counting once they succeed
+	let send = |id: u32, amount: u64| {
+		json!([{
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "init_send_tx",
+			"params": [limited, {"amount": amount}]
+		}])
+	};
+	let sent = |id: u32, slate_id: &str, ok: bool| {
+		let result = match ok {
+			true => {
+				json!({"Ok": {"id": slate_id, "amount": "600", "tx": {"body": {"kernels": []}}}})
+			}
+			false => json!({"Err": {"GenericError": "Send failed"}}),
+		};
+		MaybeReply::Reply(json!([{"jsonrpc": "2.0", "id": id, "result": result}]))
+	};
+	let slate_id = "0436430c-2b02-624c-2032-570501212b00";
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut send(5, 5_000)).is_err());
+	let mut call = send(6, 600);
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call.clone()).is_ok());
+	OwnerV3Helpers::spend_token_limits(&api, &call, &sent(6, slate_id, false));
+	call = send(7, 600);
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call.clone()).is_ok());
+	OwnerV3Helpers::spend_token_limits(&api, &call, &sent(7, slate_id, true));
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut send(8, 600)).is_err());
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut send(9, 400)).is_ok());
+
+	// the slates sent are finalized without counting again, others are counted
+	let finalize = |id: u32, slate_id: &str| {
+		json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "finalize_tx",
+			"params": [limited, {"id": slate_id, "amount": "600"}]
+		})
+	};
+	let call = finalize(10, slate_id);
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call.clone()).is_ok());
+	let excess = "08a8f99853d65cee63c973a78a005f4646b777262440a8bfa090694a339a388865";
+	let tx = json!({"body": {"kernels": [{"excess": excess}]}});
+	let finalized = MaybeReply::Reply(json!({
+		"jsonrpc": "2.0",
+		"id": 10,
+		"result": {"Ok": {"id": slate_id, "amount": "600", "tx": tx}}
+	}));
+	OwnerV3Helpers::spend_token_limits(&api, &call, &finalized);
+	let other = "1ac5f6f2-cdbb-4b54-bb45-bb49d18b6d46";
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut finalize(11, other)).is_err());
+
+	// and only the transactions finalized with the token posted
+	let post = |id: u32, excess: &str| {
+		json!({
+			"jsonrpc": "2.0",
+			"id": id,
+			"method": "post_tx",
+			"params": [limited, {"body": {"kernels": [{"excess": excess}]}}, false]
+		})
+	};
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut post(12, excess)).is_ok());
+	let unknown = "09a89280fa8d888358ab730383f00a3d990b7f2c6b17fc960501f30aac8e014478";
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut post(13, unknown)).is_err());
+
+	// the wallet's own mask isn't a scoped token
+	let mut call = json!({
+		"jsonrpc": "2.0",
+		"id": 14,
+		"method": "init_send_tx",
+		"params": {"token": mask, "args": {"amount": "5000"}}
+	});
+	assert!(OwnerV3Helpers::apply_token_scope(&api, &mut call).is_ok());
+	assert_eq!(call["params"]["token"], mask);
+
+	// nor are the tokens once the wallet is closed
+	api.close_wallet(None)?;
+	assert!(api.token_scope(&read_only).is_none());
+	Ok(())
+}
+
+#[test]
+fn wallet_token_scope() {
+	let test_dir = "test_output/token_scope";
+	setup(test_dir);
+	if let Err(e) = token_scope_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -270,10 +270,33 @@ fn basic_transaction_api(test_dir: &'static str) -> Result<(), libwallet::Error>
 			estimate_only: Some(true),
 			..Default::default()
 		};
-		let est = sender_api.init_send_tx(m, init_args)?;
+		let (_, txs_before) = sender_api.retrieve_txs(m, false, None, None)?;
+		let est = sender_api.init_send_tx(m, init_args.clone())?;
 		assert_eq!(est.amount, 180_000_000_000);
 		assert_eq!(est.fee, 6_000_000);
 
+		// the dry run of the send details the inputs it selects and its change
+		let est = sender_api.estimate_send_tx(m, &init_args)?;
+		assert_eq!(est.amount, amount * 2);
+		assert_eq!(est.total, 180_000_000_000);
+		assert_eq!(est.fee, 6_000_000);
+		assert_eq!(est.change, est.total - est.amount - est.fee);
+		assert_eq!(est.num_change_outputs, 1);
+		let inputs_total: u64 = est.inputs.iter().map(|i| i.output.value).sum();
+		assert_eq!(inputs_total, est.total);
+		assert!(est
+			.inputs
+			.iter()
+			.all(|i| i.output.status == OutputStatus::Unspent));
+
+		// neither estimate started a transaction nor locked its inputs
+		let (_, txs) = sender_api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.len(), txs_before.len());
+		let (_, outputs) = sender_api.retrieve_outputs(m, false, false, false, None)?;
+		assert!(outputs
+			.iter()
+			.all(|o| o.output.status != OutputStatus::Locked));
+
 		Ok(())
 	})?;
 
@@ -0,0 +1,239 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the slates sent and received over a transport are counted in its metrics
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::Keychain;
+use epic_wallet_util::epic_util::secp::key::SecretKey;
+use epic_wallet_util::epic_util::Mutex;
+
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::slate_versions::{SlateVersion, VersionedSlate};
+use libwallet::{
+	InitTxArgs, InitTxSendArgs, NodeClient, Slate, TransportMetrics, WalletInst, WalletLCProvider,
+};
+use serde_json::json;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::controller::RequestLimits;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Address of the foreign listener of the recipient
+const RECIPIENT_ADDR: &str = "127.0.0.1:43427";
+
+/// Address nothing listens on
+const UNREACHABLE_ADDR: &str = "127.0.0.1:43428";
+
+/// Metrics of the wallet's transport
+fn transport_metrics<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	mask: Option<&SecretKey>,
+	transport: &str,
+) -> Result<TransportMetrics, libwallet::Error>
+where
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let mut metrics = None;
+	wallet::controller::owner_single_use(wallet, mask, |api, m| {
+		metrics = api
+			.retrieve_transport_metrics(m)?
+			.into_iter()
+			.find(|t| t.transport == transport);
+		Ok(())
+	})?;
+	Ok(metrics.unwrap_or(TransportMetrics::new(transport)))
+}
+
+/// Post the slate to the recipient's foreign listener as sent to the host, returning
+/// the response's status code
+fn post_slate(host: &str, slate: Slate) -> u16 {
+	let body = json!({
+		"jsonrpc": "2.0",
+		"method": "receive_tx",
+		"id": 1,
+		"params": [VersionedSlate::into_version(slate, SlateVersion::V3), null, null]
+	})
+	.to_string();
+	let mut stream = TcpStream::connect(RECIPIENT_ADDR).unwrap();
+	let request = format!(
+		"POST /v2/foreign HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+		 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+		host,
+		body.len(),
+		body
+	);
+	stream.write_all(request.as_bytes()).unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).unwrap();
+	response.split(' ').nth(1).unwrap().parse().unwrap()
+}
+
+fn transport_metrics_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// the recipient listens over http
+	let wallet2_listener = wallet2.clone();
+	let mask2_listener = Arc::new(Mutex::new(mask2_i.clone()));
+	thread::spawn(move || {
+		let res = wallet::controller::foreign_listener(
+			wallet2_listener,
+			mask2_listener,
+			RECIPIENT_ADDR,
+			None,
+			false,
+			None,
+			RequestLimits::default(),
+			false,
+			false,
+			None,
+		);
+		if let Err(e) = res {
+			panic!("Foreign listener error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	// nothing exchanged yet
+	assert_eq!(
+		transport_metrics(wallet1.clone(), mask1, "http")?,
+		TransportMetrics::new("http")
+	);
+
+	let mut args = InitTxArgs {
+		src_acct_name: None,
+		amount: 1_000_000_000,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		send_args: Some(InitTxSendArgs {
+			method: "http".to_owned(),
+			dest: format!("http://{}", RECIPIENT_ADDR),
+			finalize: true,
+			post_tx: true,
+			fluff: false,
+		}),
+		..Default::default()
+	};
+
+	// a successful send is counted by both sides
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.init_send_tx(m, args.clone())?;
+		Ok(())
+	})?;
+	let sender = transport_metrics(wallet1.clone(), mask1, "http")?;
+	assert_eq!(sender.slates_sent, 1);
+	assert_eq!(sender.slates_received, 0);
+	assert_eq!(sender.failures, 0);
+	assert!(sender.bytes_sent > 0);
+	let recipient = transport_metrics(wallet2.clone(), mask2, "http")?;
+	assert_eq!(recipient.slates_received, 1);
+	assert_eq!(recipient.slates_sent, 1);
+	assert_eq!(recipient.failures, 0);
+	assert!(recipient.bytes_received > 0);
+	assert!(recipient.bytes_sent > 0);
+
+	// a failed one as a failure only
+	if let Some(sa) = args.send_args.as_mut() {
+		sa.dest = format!("http://{}", UNREACHABLE_ADDR);
+	}
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		assert!(api.init_send_tx(m, args.clone()).is_err());
+		Ok(())
+	})?;
+	let after = transport_metrics(wallet1.clone(), mask1, "http")?;
+	assert_eq!(after.failures, 1);
+	assert_eq!(after.slates_sent, sender.slates_sent);
+	assert_eq!(after.bytes_sent, sender.bytes_sent);
+
+	// the slates the Tor listener forwards, sent to the onion address, are counted for Tor
+	let mut slate = Slate::blank(1);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			send_args: None,
+			..args.clone()
+		};
+		slate = api.init_send_tx(m, args)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		Ok(())
+	})?;
+	let onion = "fffqrlbqjvdvgnc3dn5fjtf5x5nmgihgjd6d2vdcb66oamnhcxwdpbqd.onion";
+	assert_eq!(post_slate(onion, slate), 200);
+	let tor = transport_metrics(wallet2.clone(), mask2, "tor")?;
+	assert_eq!(tor.slates_received, 1);
+	assert_eq!(tor.slates_sent, 1);
+	assert!(tor.bytes_received > 0);
+	let http = transport_metrics(wallet2.clone(), mask2, "http")?;
+	assert_eq!(http.slates_received, recipient.slates_received);
+	assert_eq!(http.slates_sent, recipient.slates_sent);
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_transport_metrics() {
+	let test_dir = "test_output/transport_metrics";
+	setup(test_dir);
+	if let Err(e) = transport_metrics_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
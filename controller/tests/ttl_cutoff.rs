@@ -152,13 +152,8 @@ fn ttl_cutoff_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error>
 	// Mine past the ttl block and try to send
 	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 2, false);
 
-	// Wallet 2 will need to have updated past the TTL
-	wallet::controller::owner_single_use(wallet2.clone(), mask2, |sender_api, m| {
-		let (_, _) = sender_api.retrieve_txs(m, true, None, Some(slate.id))?;
-		Ok(())
-	})?;
-
-	// And when wallet 1 sends, should be rejected
+	// And when wallet 1 sends, should be rejected, wallet 2 checking the TTL
+	// against the node's tip although it hasn't updated past it
 	wallet::controller::owner_single_use(wallet1.clone(), mask1, |_sender_api, _m| {
 		let res = client1.send_tx_slate_direct("wallet2", &slate);
 		println!("Send after TTL result is: {:?}", res);
@@ -166,6 +161,13 @@ fn ttl_cutoff_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error>
 		Ok(())
 	})?;
 
+	// without recording the transaction
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |recipient_api, m| {
+		let (_, txs) = recipient_api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert!(txs.is_empty());
+		Ok(())
+	})?;
+
 	// let logging finish
 	thread::sleep(Duration::from_millis(200));
 	Ok(())
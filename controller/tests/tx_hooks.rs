@@ -0,0 +1,138 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the commands run before sending and after finalizing transactions
+#![cfg(unix)]
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_config as config;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+
+use config::TxHooksConfig;
+use impls::test_framework::{self, LocalWalletClient};
+use impls::tx_hook_from_config;
+use libwallet::{ErrorKind, InitTxArgs, OutputStatus};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn tx_hooks_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let _mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: 60_000_000_000,
+		minimum_confirmations: 2,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+
+	// a failing pre-send command vetoes the send
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_tx_hook(Some(tx_hook_from_config(&TxHooksConfig {
+			pre_send_command: Some("echo over the daily limit; exit 1".to_owned()),
+			post_finalize_command: None,
+		})));
+		match api.init_send_tx(m, args.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::SendRejected(r) => assert_eq!(r, "over the daily limit"),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("send not vetoed"),
+		}
+
+		// without locking any outputs
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert!(txs.iter().all(|t| t.tx_slate_id.is_none()));
+		let (_, outputs) = api.retrieve_outputs(m, true, false, false, None)?;
+		assert!(outputs
+			.iter()
+			.all(|o| o.output.status != OutputStatus::Locked));
+		Ok(())
+	})?;
+
+	// the post-finalize command is given the finalized slate
+	let finalized = format!("{}/finalized.json", test_dir);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.set_tx_hook(Some(tx_hook_from_config(&TxHooksConfig {
+			pre_send_command: Some("cat > /dev/null".to_owned()),
+			post_finalize_command: Some(format!("cat > {}", finalized)),
+		})));
+		let slate_i = api.init_send_tx(m, args.clone())?;
+		let mut slate = client1.send_tx_slate_direct("wallet2", &slate_i)?;
+		api.tx_lock_outputs(m, &slate, 0)?;
+		slate = api.finalize_tx(m, &slate)?;
+
+		let json: serde_json::Value =
+			serde_json::from_str(&fs::read_to_string(&finalized).unwrap()).unwrap();
+		assert_eq!(json["id"], slate.id.to_string());
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_tx_hooks() {
+	let test_dir = "test_output/tx_hooks";
+	setup(test_dir);
+	if let Err(e) = tx_hooks_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
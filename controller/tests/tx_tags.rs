@@ -0,0 +1,110 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test tagging transactions and filtering them by tag
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn tags(tags: &[&str]) -> Vec<String> {
+	tags.iter().map(|t| t.to_string()).collect()
+}
+
+fn tx_tags_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 4, false);
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		// tags are trimmed and lowercased, and kept sorted
+		let res = api.update_tx_tags(m, Some(0), None, &tags(&["mining", " Payroll "]), &[])?;
+		assert_eq!(res, tags(&["mining", "payroll"]));
+		let res = api.update_tx_tags(m, Some(1), None, &tags(&["mining", "refund"]), &[])?;
+		assert_eq!(res, tags(&["mining", "refund"]));
+		let res = api.update_tx_tags(m, Some(1), None, &[], &tags(&["refund"]))?;
+		assert_eq!(res, tags(&["mining"]));
+		assert!(api
+			.update_tx_tags(m, Some(2), None, &tags(&["a,b"]), &[])
+			.is_err());
+		assert!(api
+			.update_tx_tags(m, Some(99), None, &tags(&["mining"]), &[])
+			.is_err());
+		assert_eq!(api.retrieve_tx_tags(m)?, tags(&["mining", "payroll"]));
+
+		// no tags don't filter
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		assert_eq!(txs.len(), 4);
+		let (_, txs) = api.retrieve_txs_by_tag(m, false, &[])?;
+		assert_eq!(txs.len(), 4);
+
+		// a transaction must carry all of the tags
+		let (_, txs) = api.retrieve_txs_by_tag(m, false, &tags(&["mining"]))?;
+		assert_eq!(txs.iter().map(|t| t.id).collect::<Vec<_>>(), vec![0, 1]);
+		let (_, txs) = api.retrieve_txs_by_tag(m, false, &tags(&["Mining", "payroll"]))?;
+		assert_eq!(txs.iter().map(|t| t.id).collect::<Vec<_>>(), vec![0]);
+		let (_, txs) = api.retrieve_txs_by_tag(m, false, &tags(&["refund"]))?;
+		assert!(txs.is_empty());
+
+		// and the export
+		let txs = api.export_txs(m, &tags(&["payroll"]))?;
+		assert_eq!(txs.len(), 1);
+		assert_eq!(txs[0].id, 0);
+		assert_eq!(txs[0].tags, tags(&["mining", "payroll"]));
+		assert_eq!(api.export_txs(m, &[])?.len(), 4);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn tx_tags() {
+	let test_dir = "test_output/tx_tags";
+	setup(test_dir);
+	if let Err(e) = tx_tags_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,154 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test sending with a maximum transaction weight
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_util::epic_core as core;
+
+use epic_wallet_libwallet as libwallet;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::api_impl::owner;
+use libwallet::{ErrorKind, InitTxArgs};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// max tx weight impl
+fn tx_weight_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	// Create a new proxy to simulate server and wallet responses
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		true
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	// few values to keep things shorter
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let cm = core::global::coinbase_maturity();
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.create_account_path(m, "listener")?;
+		Ok(())
+	})?;
+
+	let bh = cm + 10;
+	let _ =
+		test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, bh as usize, false);
+
+	// room for two inputs, a change output and the recipient's output
+	let max_tx_weight = (2 * core::consensus::BLOCK_INPUT_WEIGHT
+		+ 2 * core::consensus::BLOCK_OUTPUT_WEIGHT
+		+ core::consensus::BLOCK_KERNEL_WEIGHT) as u64;
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: reward * 3,
+		minimum_confirmations: 1,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		max_tx_weight: Some(max_tx_weight),
+		..Default::default()
+	};
+
+	// a single transaction would need more inputs than the weight allows
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let res = api.init_send_tx(m, args.clone());
+		match res.map_err(|e| e.kind()) {
+			Err(ErrorKind::TxTooHeavy {
+				max_weight,
+				needed_txs,
+				..
+			}) => {
+				assert_eq!(max_weight, max_tx_weight);
+				assert_eq!(needed_txs, 2);
+			}
+			r => panic!("Unexpected result: {:?}", r.map(|s| s.id)),
+		}
+		Ok(())
+	})?;
+
+	// send the amount in the planned transactions instead
+	let parts = owner::split_send_tx(wallet1.clone(), mask1, &args)?;
+	assert_eq!(parts.len(), 2);
+	assert_eq!(parts.iter().map(|p| p.amount).sum::<u64>(), reward * 3);
+	// each transaction spends its own planned inputs
+	let inputs: Vec<_> = parts.iter().map(|p| p.inputs.clone().unwrap()).collect();
+	assert!(inputs[0].iter().all(|c| !inputs[1].contains(c)));
+	for args in parts {
+		wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+			let planned = args.inputs.clone().unwrap();
+			let mut slate = api.init_send_tx(m, args.clone())?;
+			assert!(slate.tx.inputs().len() <= 2);
+			let spent: Vec<_> = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+			assert_eq!(spent.len(), planned.len());
+			assert!(spent.iter().all(|c| planned.contains(c)));
+			api.tx_lock_outputs(m, &slate, 0)?;
+			wallet::controller::foreign_single_use(wallet1.clone(), mask1_i.clone(), |api| {
+				slate = api.receive_tx(&slate, Some("listener"), None)?;
+				Ok(())
+			})?;
+			slate = api.finalize_tx(m, &slate)?;
+			api.post_tx(m, &slate.tx, false)?;
+			Ok(())
+		})?;
+	}
+
+	// Check total in 'listener' account
+	{
+		wallet_inst!(wallet1, w);
+		w.set_parent_key_id_by_name("listener")?;
+	}
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (wallet1_refreshed, wallet1_info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(wallet1_refreshed);
+		assert_eq!(wallet1_info.total, reward * 3);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_tx_weight() {
+	let test_dir = "test_output/tx_weight";
+	setup(test_dir);
+	if let Err(e) = tx_weight_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,129 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test exploring a snapshot of a wallet, loaded read-only from its db export
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_keychain::ExtKeychain;
+use epic_wallet_util::epic_util::Mutex;
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{DefaultLCProvider, DefaultWalletImpl};
+use libwallet::WalletInst;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use wallet::command::{self, DbExportArgs};
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn view_snapshot_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 5, false);
+
+	let mut total = 0;
+	let mut num_txs = 0;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		num_txs = api.retrieve_txs(m, false, None, None)?.1.len();
+		Ok(())
+	})?;
+	assert!(total > 0);
+
+	let snapshot = format!("{}/wallet1.jsonl", test_dir);
+	command::db_export(
+		wallet1.clone(),
+		DbExportArgs {
+			format: "jsonl".to_owned(),
+			output: snapshot.clone(),
+		},
+	)
+	.unwrap();
+
+	// another wallet explores the snapshot, without wallet1's keys
+	let mut viewer = Box::new(DefaultWalletImpl::<LocalWalletClient>::new(client1.clone()).unwrap())
+		as Box<
+			dyn WalletInst<
+				DefaultLCProvider<'static, LocalWalletClient, ExtKeychain>,
+				LocalWalletClient,
+				ExtKeychain,
+			>,
+		>;
+	let lc = viewer.lc_provider().unwrap();
+	lc.open_snapshot(&snapshot)?;
+	assert_eq!(lc.snapshot(), Some(snapshot.clone()));
+	let viewer = Arc::new(Mutex::new(viewer));
+
+	wallet::controller::owner_single_use(viewer.clone(), None, |api, m| {
+		// shown as exported, not refreshed from the node
+		let (validated, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(!validated);
+		assert_eq!(info.total, total);
+		let (_, txs) = api.retrieve_txs(m, true, None, None)?;
+		assert_eq!(txs.len(), num_txs);
+
+		match api.create_account_path(m, "explorer") {
+			Err(e) => assert!(format!("{}", e).contains("read-only")),
+			Ok(_) => panic!("account created in a snapshot"),
+		}
+		Ok(())
+	})?;
+
+	{
+		let mut w_lock = viewer.lock();
+		let lc = w_lock.lc_provider()?;
+		lc.close_wallet(None)?;
+		assert_eq!(lc.snapshot(), None);
+	}
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_view_snapshot() {
+	let test_dir = "test_output/view_snapshot";
+	setup(test_dir);
+	if let Err(e) = view_snapshot_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,176 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the events published by wallets as transactions are exchanged
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::to_hex;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{subscribe_events, InitTxArgs, OutputStatus, Slate, WalletEvent};
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+fn wallet_events_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let events = subscribe_events();
+	let mut slate = Slate::blank(1);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: 60_000_000_000,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: false,
+			..Default::default()
+		};
+		slate = api.init_send_tx(m, args)?;
+		Ok(())
+	})?;
+	wallet::controller::foreign_single_use(wallet2.clone(), mask2_i.clone(), |api| {
+		slate = api.receive_tx(&slate, None, None)?;
+		Ok(())
+	})?;
+	let mut spent = vec![];
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		api.tx_lock_outputs(m, &slate, 0)?;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		spent = outputs
+			.iter()
+			.filter(|o| o.output.status == OutputStatus::Locked)
+			.map(|o| to_hex(o.commit.0.to_vec()))
+			.collect();
+		slate = api.finalize_tx(m, &slate)?;
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	assert!(!spent.is_empty());
+
+	// the events of the exchange are published once the sender sees it on chain
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (refreshed, _) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+		assert!(refreshed);
+		Ok(())
+	})?;
+
+	let events: Vec<WalletEvent> = events.try_iter().collect();
+	let received = WalletEvent::TxReceived {
+		tx_slate_id: slate.id,
+		amount: slate.amount,
+	};
+	assert_eq!(events.iter().filter(|e| **e == received).count(), 1);
+	assert!(events.iter().any(|e| match e {
+		WalletEvent::TxConfirmed { tx_slate_id, .. } => *tx_slate_id == Some(slate.id),
+		_ => false,
+	}));
+	for c in spent.iter() {
+		assert!(events.iter().any(|e| match e {
+			WalletEvent::OutputSpent { commit, .. } => commit == c,
+			_ => false,
+		}));
+	}
+	assert!(events.iter().any(|e| match e {
+		WalletEvent::ScanProgress { percent } => *percent == 100,
+		_ => false,
+	}));
+
+	// the wallets keep their events, for the clients which missed them
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let stored = api.get_events(m, None)?;
+		assert_eq!(stored.len(), 1);
+		assert_eq!(stored[0].event, received);
+		assert!(api.get_events(m, Some(stored[0].id))?.is_empty());
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let stored = api.get_events(m, None)?;
+		assert!(stored.windows(2).all(|w| w[0].id < w[1].id));
+		assert!(stored.iter().any(|e| match &e.event {
+			WalletEvent::TxConfirmed { tx_slate_id, .. } => *tx_slate_id == Some(slate.id),
+			_ => false,
+		}));
+		// progress isn't kept
+		assert!(!stored.iter().any(|e| match e.event {
+			WalletEvent::ScanProgress { .. } => true,
+			_ => false,
+		}));
+		let last = stored.last().unwrap().id;
+		let later = api.get_events(m, Some(stored[0].id))?;
+		assert_eq!(later.len(), stored.len() - 1);
+		assert_eq!(later.last().unwrap().id, last);
+		Ok(())
+	})?;
+
+	// each event is tagged with its kind
+	let json = serde_json::to_value(&received).unwrap();
+	assert_eq!(json["event"], "TxReceived");
+	assert_eq!(json["amount"], slate.amount.to_string());
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_events() {
+	let test_dir = "test_output/wallet_events";
+	setup(test_dir);
+	if let Err(e) = wallet_events_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,168 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test the responses to file sends dropped in a watched folder are finalized
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_core as core;
+
+use impls::test_framework::{self, LocalWalletClient};
+use impls::{PathToSlate, SlatePutter as _};
+use libwallet::InitTxArgs;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use wallet::command::WatchArgs;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, setup};
+
+/// Wait for the watcher to move a file to the given path
+fn wait_for_file(path: &Path) {
+	let start = Instant::now();
+	while !path.exists() {
+		assert!(
+			start.elapsed() < Duration::from_secs(60),
+			"{} not written",
+			path.display()
+		);
+		thread::sleep(Duration::from_millis(100));
+	}
+}
+
+fn watch_folder_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		_client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	create_wallet_and_add!(
+		_client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let reward = core::consensus::BLOCK_TIME_SEC * core::consensus::EPIC_BASE;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let dir = Path::new(test_dir).join("watch");
+	fs::create_dir_all(&dir).unwrap();
+
+	// wallet 1 sends, wallet 2 receives and writes its response
+	let mut slate = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let args = InitTxArgs {
+			src_acct_name: None,
+			amount: reward * 2,
+			minimum_confirmations: 2,
+			max_outputs: 500,
+			num_change_outputs: 1,
+			selection_strategy_is_use_all: true,
+			..Default::default()
+		};
+		let s = api.init_send_tx(m, args)?;
+		api.tx_lock_outputs(m, &s, 0)?;
+		slate = Some(s);
+		Ok(())
+	})?;
+	let mut slate = slate.unwrap();
+	wallet::controller::foreign_single_use(wallet2.clone(), mask2_i.clone(), |api| {
+		slate = api.receive_tx(&slate, None, None)?;
+		Ok(())
+	})?;
+
+	// the watcher finalizes and posts the response, and files it as done
+	let watch_wallet = wallet1.clone();
+	let watch_mask = mask1_i.clone();
+	let watch_dir = dir.to_string_lossy().into_owned();
+	thread::spawn(move || {
+		let args = WatchArgs {
+			dir: watch_dir,
+			interval: Duration::from_millis(100),
+			fluff: false,
+			nopost: false,
+			tx_hooks: None,
+			ignore_node_height: false,
+		};
+		if let Err(e) = wallet::command::watch(watch_wallet, (&watch_mask).as_ref(), args) {
+			warn!("Watcher stopped: {}", e);
+		}
+	});
+	PathToSlate(dir.join("send.response.tx")).put_tx(&slate)?;
+	wait_for_file(&dir.join("done").join("send.response.tx"));
+	assert!(!dir.join("send.response.tx").exists());
+
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, false, None, Some(slate.id))?;
+		assert!(api.get_stored_tx(m, &txs[0])?.is_some());
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 3, false);
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, reward * 2);
+		Ok(())
+	})?;
+
+	// a response which can't be finalized is filed as an error
+	fs::write(dir.join("bad.response.tx"), "not a slate").unwrap();
+	wait_for_file(&dir.join("error").join("bad.response.tx"));
+	assert!(!dir.join("bad.response.tx").exists());
+
+	// other files are left alone
+	fs::write(dir.join("notes.txt"), "not a response").unwrap();
+	thread::sleep(Duration::from_millis(500));
+	assert!(dir.join("notes.txt").exists());
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_watch_folder() {
+	let test_dir = "test_output/watch_folder";
+	setup(test_dir);
+	if let Err(e) = watch_folder_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
@@ -0,0 +1,255 @@
+// Copyright 2019 The Epic Developers
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test a watch-only wallet created from the view key of another wallet's account
+#[macro_use]
+extern crate log;
+extern crate epic_wallet_controller as wallet;
+extern crate epic_wallet_impls as impls;
+
+use epic_wallet_libwallet as libwallet;
+use epic_wallet_util::epic_util::ZeroingString;
+use impls::test_framework::{self, LocalWalletClient};
+use libwallet::{ErrorKind, InitTxArgs, SigningRequest, ViewKey};
+use std::collections::HashSet;
+use std::thread;
+use std::time::Duration;
+
+#[macro_use]
+mod common;
+use common::{clean_output_dir, create_wallet_proxy, create_watch_only_wallet, setup};
+
+fn watch_only_test_impl(test_dir: &'static str) -> Result<(), libwallet::Error> {
+	let seed_phrase = "affair pistol cancel crush garment candy ancient flag work \
+	                   market crush dry stand focus mutual weapon offer ceiling rival turn team spring \
+	                   where swift";
+	let seed_phrase = Some(ZeroingString::from(seed_phrase));
+
+	let mut wallet_proxy = create_wallet_proxy(test_dir);
+	let chain = wallet_proxy.chain.clone();
+
+	create_wallet_and_add!(
+		client1,
+		wallet1,
+		mask1_i,
+		test_dir,
+		"wallet1",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask1 = (&mask1_i).as_ref();
+	// the same seed on an offline host, never updated from the node
+	create_wallet_and_add!(
+		client_offline,
+		offline,
+		mask_offline_i,
+		test_dir,
+		"offline",
+		seed_phrase,
+		&mut wallet_proxy,
+		false
+	);
+	let mask_offline = (&mask_offline_i).as_ref();
+	create_wallet_and_add!(
+		client2,
+		wallet2,
+		mask2_i,
+		test_dir,
+		"wallet2",
+		None,
+		&mut wallet_proxy,
+		false
+	);
+	let mask2 = (&mask2_i).as_ref();
+
+	let mut view_key = None;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, _m| {
+		let key = api.export_view_key(None, ZeroingString::from(""), None)?;
+		// only the account's public keys
+		let json = serde_json::to_string(&key).unwrap();
+		assert_eq!(serde_json::from_str::<ViewKey>(&json).unwrap(), key);
+		view_key = Some(key);
+		Ok(())
+	})?;
+
+	// on another host, without the seed
+	let watch_client = LocalWalletClient::new("watch", wallet_proxy.tx.clone());
+	let watch =
+		create_watch_only_wallet(test_dir, "watch", view_key.unwrap(), watch_client.clone());
+	wallet_proxy.add_wallet(
+		"watch",
+		watch_client.get_send_instance(),
+		watch.clone(),
+		None,
+	);
+
+	// Set the wallet proxy listener running
+	thread::spawn(move || {
+		if let Err(e) = wallet_proxy.run() {
+			error!("Wallet Proxy error: {}", e);
+		}
+	});
+
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet1.clone(), mask1, 10, false);
+
+	let mut total = 0;
+	let mut spendable = 0;
+	let mut commits = HashSet::new();
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		total = info.total;
+		spendable = info.amount_currently_spendable;
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		commits = outputs.iter().map(|o| o.commit).collect();
+		Ok(())
+	})?;
+	assert!(total > 0);
+
+	wallet::controller::owner_single_use(watch.clone(), None, |api, m| {
+		// the first update scans the chain
+		let (validated, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert!(validated);
+		assert_eq!(info.total, total);
+		assert_eq!(info.amount_currently_spendable, spendable);
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		let found: HashSet<_> = outputs.iter().map(|o| o.commit).collect();
+		assert_eq!(found, commits);
+		let (_, txs) = api.retrieve_txs(m, false, None, None)?;
+		assert_eq!(txs.len(), commits.len());
+
+		// nothing more is found by the following updates
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, total);
+		Ok(())
+	})?;
+
+	let args = InitTxArgs {
+		src_acct_name: None,
+		amount: spendable / 2,
+		minimum_confirmations: 1,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+	let mut inputs = HashSet::new();
+	let mut request = None;
+	wallet::controller::owner_single_use(watch.clone(), None, |api, m| {
+		// can't sign
+		match api.init_send_tx(m, args.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::KeychainDoesntExist => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("watch-only wallet signed a send"),
+		}
+
+		let r = api.init_unsigned_send_tx(args.clone())?;
+		let slate = &r.slate;
+		assert_eq!(slate.amount, args.amount);
+		assert!(slate.fee > 0);
+		assert!(slate.tx.outputs().is_empty());
+		assert!(slate.participant_data.is_empty());
+		inputs = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+		assert!(!inputs.is_empty());
+		assert!(inputs.iter().all(|c| commits.contains(c)));
+		assert_eq!(r.inputs.len(), inputs.len());
+
+		// nor sign one
+		match api.sign_tx_offline(m, &r) {
+			Err(e) => match e.kind() {
+				ErrorKind::KeychainDoesntExist => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("watch-only wallet signed a send"),
+		}
+		request = Some(r);
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet1.clone(), mask1, |api, _m| {
+		match api.init_unsigned_send_tx(args.clone()) {
+			Err(e) => match e.kind() {
+				ErrorKind::WatchOnly(_) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("signing request built by a wallet with keys"),
+		}
+		Ok(())
+	})?;
+
+	// the request is taken to the offline host as a file
+	let json = serde_json::to_string(&request.unwrap()).unwrap();
+	let request: SigningRequest = serde_json::from_str(&json).unwrap();
+	let fee = request.slate.fee;
+	let mut slate = request.slate.clone();
+	wallet::controller::owner_single_use(offline.clone(), mask_offline, |api, m| {
+		slate = api.sign_tx_offline(m, &request)?;
+		assert_eq!(slate.fee, fee);
+		let signed: HashSet<_> = slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+		assert_eq!(signed, inputs);
+		assert_eq!(slate.tx.outputs().len(), 1);
+		assert_eq!(slate.participant_data.len(), 1);
+		api.tx_lock_outputs(m, &slate, 0)?;
+
+		// the request is signed once
+		match api.sign_tx_offline(m, &request) {
+			Err(e) => match e.kind() {
+				ErrorKind::UnspendableInput(_, _) => (),
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("request signed twice"),
+		}
+		Ok(())
+	})?;
+	slate = client_offline.send_tx_slate_direct("wallet2", &slate)?;
+	wallet::controller::owner_single_use(offline.clone(), mask_offline, |api, m| {
+		slate = api.finalize_tx(m, &slate)?;
+		Ok(())
+	})?;
+
+	// and the transaction posted from the watch-only wallet
+	wallet::controller::owner_single_use(watch.clone(), None, |api, m| {
+		api.post_tx(m, &slate.tx, false)?;
+		Ok(())
+	})?;
+	let _ = test_framework::award_blocks_to_wallet(&chain, wallet2.clone(), mask2, 3, false);
+
+	wallet::controller::owner_single_use(watch.clone(), None, |api, m| {
+		let (_, info) = api.retrieve_summary_info(m, true, 1)?;
+		assert_eq!(info.total, total - args.amount - fee);
+		let (_, outputs) = api.retrieve_outputs(m, false, false, false, None)?;
+		assert!(outputs.iter().all(|o| !inputs.contains(&o.commit)));
+		Ok(())
+	})?;
+	wallet::controller::owner_single_use(wallet2.clone(), mask2, |api, m| {
+		let (_, txs) = api.retrieve_txs(m, true, None, Some(slate.id))?;
+		assert!(txs[0].confirmed);
+		assert_eq!(txs[0].amount_credited, args.amount);
+		Ok(())
+	})?;
+
+	// let logging finish
+	thread::sleep(Duration::from_millis(200));
+	Ok(())
+}
+
+#[test]
+fn wallet_watch_only() {
+	let test_dir = "test_output/watch_only";
+	setup(test_dir);
+	if let Err(e) = watch_only_test_impl(test_dir) {
+		panic!("Libwallet Error: {} - {}", e, e.backtrace().unwrap());
+	}
+	clean_output_dir(test_dir);
+}
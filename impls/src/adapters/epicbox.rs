@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::adapters::slate_pool::{SlatePool, Turn};
 use crate::config::EpicboxConfig;
 use crate::epicbox::protocol::{
 	ProtocolError, ProtocolRequest, ProtocolRequestV2, ProtocolResponseV2,
@@ -23,10 +24,11 @@ use crate::util::secp::key::PublicKey;
 
 use crate::libwallet::wallet_lock;
 use crate::libwallet::{
-	address, Address, AddressType, EpicboxAddress, TxProof, DEFAULT_EPICBOX_PORT_443,
-	DEFAULT_EPICBOX_PORT_80,
+	address, Address, EpicboxAddress, TxProof, DEFAULT_EPICBOX_PORT_443, DEFAULT_EPICBOX_PORT_80,
+};
+use crate::libwallet::{
+	KeyPinCheck, NodeClient, ReceiveHook, TransportEvent, TxHook, WalletInst, WalletLCProvider,
 };
-use crate::libwallet::{NodeClient, WalletInst, WalletLCProvider};
 
 use crate::Error;
 use crate::ErrorKind;
@@ -76,6 +78,8 @@ const CONNECTION_ERR_MSG: &str = "\nCan't connect to the epicbox server!\n\
 	Check your epic-wallet.toml settings and make sure epicbox domain is correct.\n";
 const DEFAULT_CHALLENGE_RAW: &str = "7WUDtkSaKyGRUnQ22rE3QUXChV8DmA6NnunDYP4vheTpc";
 const EPICBOX_PROTOCOL_VERSION: &str = "2.0.0";
+/// Number of incoming slates processed at the same time, unless configured
+const DEFAULT_RECEIVE_WORKERS: usize = 4;
 
 /// Epicbox 'plugin' implementation
 pub enum CloseReason {
@@ -123,6 +127,8 @@ impl EpicboxListenChannel {
 		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 		keychain_mask: Arc<Mutex<Option<SecretKey>>>,
 		epicbox_config: EpicboxConfig,
+		receive_hook: Option<Arc<dyn ReceiveHook>>,
+		tx_hook: Option<Arc<dyn TxHook>>,
 		reconnections: &mut u32,
 	) -> Result<(), Error>
 	where
@@ -187,8 +193,11 @@ impl EpicboxListenChannel {
 		let cpublisher = publisher.clone();
 		let mask = keychain_mask.lock();
 		let km = mask.clone();
-		let controller = EpicboxController::new(container, cpublisher, wallet, km, reconnections)
-			.expect("Could not init epicbox listener!");
+		let mut controller =
+			EpicboxController::new(container, cpublisher, wallet, km, reconnections)
+				.expect("Could not init epicbox listener!");
+		controller.receive_hook = receive_hook;
+		controller.tx_hook = tx_hook;
 
 		info!("Starting epicbox listener for: {}", address);
 
@@ -223,10 +232,20 @@ impl EpicboxChannel {
 			Some(epicbox_config) => epicbox_config,
 		};
 
+		let dest = EpicboxAddress::from_str(&self.dest)?;
+		check_contact_key(wallet.clone(), keychain_mask.as_ref(), slate, false, &dest)?;
+
 		let container = Container::new(config.clone());
 
 		let (tx, rx): (Sender<bool>, Receiver<bool>) = channel();
-		let listener = start_epicbox(container.clone(), wallet, keychain_mask, config, tx).unwrap();
+		let listener = start_epicbox(
+			container.clone(),
+			wallet.clone(),
+			keychain_mask.clone(),
+			config,
+			tx,
+		)
+		.unwrap();
 
 		container
 			.lock()
@@ -252,6 +271,12 @@ impl EpicboxChannel {
 			.publish(&vslate, &self.dest)
 			.unwrap();
 
+		record_epicbox_event(
+			wallet,
+			keychain_mask.as_ref(),
+			TransportEvent::slate_sent(&vslate),
+		);
+
 		let slate: Slate = VersionedSlate::into_version(slate.clone(), SlateVersion::V2).into();
 		Ok(slate)
 	}
@@ -418,6 +443,12 @@ where
 	/// Keychain mask
 	pub keychain_mask: Option<SecretKey>,
 	pub reconnections: &'a mut u32,
+	/// Check applied to incoming slates before they're signed
+	pub receive_hook: Option<Arc<dyn ReceiveHook>>,
+	/// Hook run on the responses to sent slates once they're finalized
+	pub tx_hook: Option<Arc<dyn TxHook>>,
+	/// Workers processing the incoming slates
+	pool: SlatePool,
 }
 pub struct Container {
 	pub config: EpicboxConfig,
@@ -469,59 +500,221 @@ where
 	K: Keychain + 'static,
 {
 	pub fn new(
-		// TODO: check if container is required
-		_container: Arc<Mutex<Container>>,
+		container: Arc<Mutex<Container>>,
 		publisher: P,
 		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
 		keychain_mask: Option<SecretKey>,
 		reconnections: &'a mut u32,
 	) -> Result<Self, Error> {
+		let receive_workers = container
+			.lock()
+			.config
+			.epicbox_receive_workers
+			.unwrap_or(DEFAULT_RECEIVE_WORKERS);
 		Ok(Self {
 			publisher,
 			wallet,
 			keychain_mask,
 			reconnections: reconnections,
+			receive_hook: None,
+			tx_hook: None,
+			pool: SlatePool::new(receive_workers),
 		})
 	}
+}
 
-	fn process_incoming_slate(
-		&self,
-		_address: Option<String>,
-		slate: &mut Slate,
-		_tx_proof: Option<&mut TxProof>,
-	) -> Result<bool, Error> {
-		/* build owner and foreign here */
-		//let wallet = self.wallet.clone();
+/// An incoming slate along with what a worker needs to process it, away from the
+/// listener's thread
+struct IncomingSlate<P, L, C, K>
+where
+	P: Publisher,
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	publisher: P,
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Option<SecretKey>,
+	receive_hook: Option<Arc<dyn ReceiveHook>>,
+	tx_hook: Option<Arc<dyn TxHook>>,
+	from: EpicboxAddress,
+	slate: Slate,
+	version: SlateVersion,
+}
 
-		wallet_lock!(self.wallet, w);
+impl<P, L, C, K> IncomingSlate<P, L, C, K>
+where
+	P: Publisher,
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	/// Check the slate, then in its turn sign or finalize it and post the response back
+	fn process(mut self, turn: &Turn) {
+		record_epicbox_event(
+			self.wallet.clone(),
+			self.keychain_mask.as_ref(),
+			TransportEvent::slate_received(&self.slate),
+		);
 
-		if slate.num_participants > slate.participant_data.len() {
-			if slate.tx.inputs().len() == 0 {
-				// TODO: invoicing
-			} else {
-				info!("Received new transaction (foreign::receive_tx)");
-				let ret_slate = foreign::receive_tx(
-					&mut **w,
+		let result = self.check().and_then(|_| {
+			turn.wait();
+			self.sign_or_finalize()
+		});
+
+		match result {
+			Ok(()) => {}
+			Err(e) => {
+				record_epicbox_event(
+					self.wallet.clone(),
 					self.keychain_mask.as_ref(),
-					&slate,
-					None,
-					None,
-					false,
+					TransportEvent::Failure,
 				);
-				*slate = ret_slate.unwrap();
+				error!("{}", e)
 			}
+		}
+	}
 
-			Ok(false)
-		} else {
-			info!("Finalize transaction (owner::finalize_tx)");
-			let slate = owner::finalize_tx(&mut **w, self.keychain_mask.as_ref(), slate)?;
+	/// Checks which don't persist anything, run alongside the other slates
+	fn check(&self) -> Result<(), Error> {
+		// a new slate comes from its sender, a returned one from its recipient
+		let from_sender = self.slate.num_participants > self.slate.participant_data.len();
+		check_contact_key(
+			self.wallet.clone(),
+			self.keychain_mask.as_ref(),
+			&self.slate,
+			from_sender,
+			&self.from,
+		)?;
+		if from_sender && self.slate.tx.inputs().len() > 0 {
+			if let Some(h) = self.receive_hook.as_ref() {
+				h.check(&self.slate)?;
+			}
+		}
+		Ok(())
+	}
 
-			info!("Post transaction to the network (owner::post_tx)");
-			owner::post_tx(w.w2n_client(), &slate.tx, false)?;
-			Ok(true)
+	fn sign_or_finalize(&mut self) -> Result<(), Error> {
+		let is_finalized = {
+			wallet_lock!(self.wallet, w);
+
+			if self.slate.num_participants > self.slate.participant_data.len() {
+				if self.slate.tx.inputs().len() == 0 {
+					// TODO: invoicing
+				} else {
+					info!("Received new transaction (foreign::receive_tx)");
+					self.slate = foreign::receive_tx(
+						&mut **w,
+						self.keychain_mask.as_ref(),
+						&self.slate,
+						None,
+						None,
+						false,
+						false,
+					)?;
+				}
+				false
+			} else {
+				info!("Finalize transaction (owner::finalize_tx)");
+				let slate = owner::finalize_tx(&mut **w, self.keychain_mask.as_ref(), &self.slate)?;
+				if let Some(h) = self.tx_hook.as_ref() {
+					if let Err(e) = h.post_finalize(&slate) {
+						warn!(
+							"Post-finalize hook failed for transaction {}: {}",
+							slate.id, e
+						);
+					}
+				}
+
+				info!("Post transaction to the network (owner::post_tx)");
+				owner::post_tx(w.w2n_client(), &slate.tx, false)?;
+				true
+			}
+		};
+
+		if is_finalized {
+			warn!(
+				"Slate [{}] finalized successfully",
+				self.slate.id.to_string()
+			);
+			return Ok(());
 		}
+		let slate = VersionedSlate::into_version(self.slate.clone(), self.version);
+		record_epicbox_event(
+			self.wallet.clone(),
+			self.keychain_mask.as_ref(),
+			TransportEvent::slate_sent(&slate),
+		);
+		self.publisher.post_slate(&slate, &self.from, false)
+	}
+}
+
+/// Check the epicbox key the other party to a slate uses against the key pinned for
+/// them on first use. The party is identified by their payment proof address, so slates
+/// without a payment proof can't be checked. `from_sender` tells whether the other party
+/// is the slate's sender or its recipient.
+fn check_contact_key<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+	from_sender: bool,
+	epicbox_address: &EpicboxAddress,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	let proof = match slate.payment_proof.as_ref() {
+		Some(p) => p,
+		None => return Ok(()),
+	};
+	let contact = match from_sender {
+		true => address::onion_v3_from_pubkey(&proof.sender_address)?,
+		false => address::onion_v3_from_pubkey(&proof.receiver_address)?,
+	};
+	let epicbox_key = &epicbox_address.public_key;
+	match owner::check_pinned_key(wallet, keychain_mask, &contact, epicbox_key)? {
+		KeyPinCheck::New => info!("Pinned epicbox key {} for {}", epicbox_key, contact),
+		KeyPinCheck::Known => {}
+		KeyPinCheck::Changed(pin) => {
+			warn!("##############################################################");
+			warn!("WARNING: EPICBOX KEY OF {} HAS CHANGED!", contact);
+			warn!(
+				"Pinned key: {} (first seen {})",
+				pin.epicbox_key, pin.first_seen
+			);
+			warn!("Slate [{}] uses key: {}", slate.id, epicbox_key);
+			warn!("The epicbox relay may be impersonating this contact. Refusing the slate.");
+			warn!("If the contact confirms the new key, forget the old one with unpin_key.");
+			warn!("##############################################################");
+			let kind = crate::libwallet::ErrorKind::EpicboxKeyChanged {
+				contact,
+				pinned: pin.epicbox_key,
+				presented: epicbox_key.clone(),
+			};
+			return Err(crate::libwallet::Error::from(kind).into());
+		}
+	}
+	Ok(())
+}
+
+/// Record an epicbox slate event in the transport metrics, logging rather
+/// than failing if the metrics can't be saved
+fn record_epicbox_event<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K> + 'static>>>,
+	keychain_mask: Option<&SecretKey>,
+	event: TransportEvent,
+) where
+	L: WalletLCProvider<'static, C, K> + 'static,
+	C: NodeClient + 'static,
+	K: Keychain + 'static,
+{
+	if let Err(e) = owner::record_transport_event(wallet, keychain_mask, "epicbox", event) {
+		warn!("Unable to record transport metrics: {}", e);
 	}
 }
+
 pub trait SubscriptionHandler: Send {
 	fn on_slate(&self, from: &dyn Address, slate: &VersionedSlate, proof: Option<&mut TxProof>);
 	fn on_close(&self, result: CloseReason);
@@ -534,9 +727,14 @@ where
 	C: NodeClient + 'static,
 	K: Keychain + 'static,
 {
-	fn on_slate(&self, from: &dyn Address, slate: &VersionedSlate, tx_proof: Option<&mut TxProof>) {
+	fn on_slate(
+		&self,
+		from: &dyn Address,
+		slate: &VersionedSlate,
+		_tx_proof: Option<&mut TxProof>,
+	) {
 		let version = slate.version();
-		let mut slate: Slate = slate.clone().into();
+		let slate: Slate = slate.clone().into();
 
 		if slate.num_participants > slate.participant_data.len() {
 			debug!(
@@ -554,34 +752,25 @@ where
 			);
 		};
 
-		if from.address_type() == AddressType::Epicbox {
-			EpicboxAddress::from_str(&from.to_string()).expect("invalid epicbox address");
-		}
-
-		let result = self
-			.process_incoming_slate(Some(from.to_string()), &mut slate, tx_proof)
-			.and_then(|is_finalized| {
-				if !is_finalized {
-					let _id = slate.id.clone();
-					let slate = VersionedSlate::into_version(slate, version);
-
-					self.publisher
-						.post_slate(&slate, from, false)
-						.map_err(|e| {
-							error!("{}: {}", "ERROR", e);
-							e
-						})
-						.expect("failed posting slate!");
-				} else {
-					warn!("Slate [{}] finalized successfully", slate.id.to_string());
-				}
-				Ok(())
-			});
+		let from = match EpicboxAddress::from_str(&from.to_string()) {
+			Ok(a) => a,
+			Err(e) => {
+				error!("Slate [{}] refused: {}", slate.id, e);
+				return;
+			}
+		};
 
-		match result {
-			Ok(()) => {}
-			Err(e) => error!("{}", e),
-		}
+		let incoming = IncomingSlate {
+			publisher: self.publisher.clone(),
+			wallet: self.wallet.clone(),
+			keychain_mask: self.keychain_mask.clone(),
+			receive_hook: self.receive_hook.clone(),
+			tx_hook: self.tx_hook.clone(),
+			from,
+			slate,
+			version,
+		};
+		self.pool.submit(move |turn| incoming.process(turn));
 	}
 
 	fn on_close(&self, reason: CloseReason) {
@@ -621,7 +810,7 @@ impl Subscriber for EpicboxSubscriber {
 	}
 }
 
-pub trait Publisher: Send {
+pub trait Publisher: Send + Clone + 'static {
 	fn post_slate(
 		&self,
 		slate: &VersionedSlate,
@@ -870,12 +1059,15 @@ impl EpicboxBroker {
 
 		let skey = secret_key.clone();
 
-		let message =
-			EncryptedMessage::new(serde_json::to_string(&slate).unwrap(), &to, &pkey, &skey)
-				.map_err(|_| error!("could not encrypt slate!"))
-				.unwrap();
+		// encrypted to the key published in the recipient's address, which the recipient
+		// checks against its own before decrypting
+		let slate_ser = serde_json::to_string(&slate).map_err(|_| ErrorKind::Format)?;
+		let message = EncryptedMessage::new(slate_ser, &to, &pkey, &skey).map_err(|e| {
+			error!("could not encrypt slate!");
+			e
+		})?;
 
-		let message_ser = serde_json::to_string(&message).unwrap();
+		let message_ser = serde_json::to_string(&message).map_err(|_| ErrorKind::Format)?;
 		let mut challenge = String::new();
 		challenge.push_str(&message_ser);
 
@@ -14,25 +14,40 @@
 
 /// HTTP Wallet 'plugin' implementation
 use crate::client_utils::{Client, ClientError};
-use crate::libwallet::slate_versions::{SlateVersion, VersionedSlate};
-use crate::libwallet::{Error, ErrorKind, Slate};
+use crate::libwallet::slate_versions::{SlateVersion, VersionedSlate, CURRENT_SLATE_VERSION};
+use crate::libwallet::{Error, ErrorKind, ResponseSignature, Slate};
 use crate::SlateSender;
+use hyper::header::HeaderValue;
 use serde::Serialize;
 use serde_json::{json, Value};
+use std::cmp;
 use std::net::SocketAddr;
 use std::path::MAIN_SEPARATOR;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::tor::config as tor_config;
 use crate::tor::process as tor_process;
 
 const TOR_CONFIG_PATH: &'static str = "tor/sender";
 
+/// Attempts at posting a slate to the recipient, and seconds waited before the
+/// first retry (doubled at each further one)
+const RECEIVE_TX_ATTEMPTS: u32 = 4;
+const RECEIVE_TX_RETRY_SECS: u64 = 5;
+
+/// Header carrying the sender's signature of the slate it posts, for recipients
+/// authenticating their senders
+pub const SENDER_SIGNATURE_HEADER: &'static str = "x-epic-sender-signature";
+
 #[derive(Clone)]
 pub struct HttpSlateSender {
 	base_url: String,
 	use_socks: bool,
 	socks_proxy_addr: Option<SocketAddr>,
 	tor_config_dir: String,
+	sender_signature: Option<ResponseSignature>,
+	timeout: Option<Duration>,
 }
 
 impl HttpSlateSender {
@@ -46,6 +61,8 @@ impl HttpSlateSender {
 				use_socks: false,
 				socks_proxy_addr: None,
 				tor_config_dir: String::from(""),
+				sender_signature: None,
+				timeout: None,
 			})
 		}
 	}
@@ -64,8 +81,41 @@ impl HttpSlateSender {
 		Ok(ret)
 	}
 
-	/// Check version of the listening wallet
-	fn check_other_version(&self, url: &str) -> Result<SlateVersion, Error> {
+	/// Sign the posted slate as its sender, with the signature made with the key of
+	/// the sender address
+	pub fn with_sender_signature(mut self, signature: ResponseSignature) -> HttpSlateSender {
+		self.sender_signature = Some(signature);
+		self
+	}
+
+	/// Abort the exchange with the recipient once it takes longer than the timeout,
+	/// rather than retrying the post of the slate until the attempts run out
+	pub fn with_timeout(mut self, timeout: Duration) -> HttpSlateSender {
+		self.timeout = Some(timeout);
+		self
+	}
+
+	fn timed_out(&self) -> Error {
+		let secs = self.timeout.map(|t| t.as_secs()).unwrap_or(0);
+		let report = format!("no response from the recipient within {}s", secs);
+		error!("Posting transaction slate: {}", report);
+		ErrorKind::SendTimeout(report).into()
+	}
+
+	/// Time left before the timeout of an exchange started at `start`, if limited
+	fn time_left(&self, start: Instant) -> Result<Option<Duration>, Error> {
+		let timeout = match self.timeout {
+			Some(t) => t,
+			None => return Ok(None),
+		};
+		match timeout.checked_sub(start.elapsed()) {
+			Some(left) if left > Duration::from_secs(0) => Ok(Some(left)),
+			_ => Err(self.timed_out()),
+		}
+	}
+
+	/// Slate versions the listening wallet supports, as reported by its `check_version`
+	fn check_other_version(&self, url: &str, start: Instant) -> Result<Vec<u16>, Error> {
 		let req = json!({
 			"jsonrpc": "2.0",
 			"method": "check_version",
@@ -73,7 +123,12 @@ impl HttpSlateSender {
 			"params": []
 		});
 
-		let res: String = self.post(url, None, req).map_err(|e| {
+		let res = self.post(url, None, req, self.time_left(start)?);
+		if res.is_err() {
+			// the request may have been cut short by the timeout
+			self.time_left(start)?;
+		}
+		let res: String = res.map_err(|e| {
 			let mut report = format!("Performing version check (is recipient listening?): {}", e);
 			let err_string = format!("{}", e);
 			if err_string.contains("404") {
@@ -87,7 +142,12 @@ impl HttpSlateSender {
 			ErrorKind::ClientCallback(report)
 		})?;
 
-		let res: Value = serde_json::from_str(&res).unwrap();
+		let unrecognized = |res: &Value| -> Error {
+			let report = format!("Other wallet reports unrecognized API format: {}", res);
+			error!("{}", report);
+			ErrorKind::ClientCallback(report).into()
+		};
+		let res: Value = serde_json::from_str(&res).map_err(|_| unrecognized(&json!(res)))?;
 		trace!("Response: {}", res);
 		if res["error"] != json!(null) {
 			let report = format!(
@@ -101,27 +161,20 @@ impl HttpSlateSender {
 		let resp_value = res["result"]["Ok"].clone();
 		trace!("resp_value: {}", resp_value.clone());
 		let foreign_api_version: u16 =
-			serde_json::from_value(resp_value["foreign_api_version"].clone()).unwrap();
+			serde_json::from_value(resp_value["foreign_api_version"].clone())
+				.map_err(|_| unrecognized(&resp_value))?;
 		let supported_slate_versions: Vec<String> =
-			serde_json::from_value(resp_value["supported_slate_versions"].clone()).unwrap();
-
-		// trivial tests for now, but will be expanded later
+			serde_json::from_value(resp_value["supported_slate_versions"].clone())
+				.map_err(|_| unrecognized(&resp_value))?;
 		if foreign_api_version < 2 {
-			let report = format!("Other wallet reports unrecognized API format.");
-			error!("{}", report);
-			return Err(ErrorKind::ClientCallback(report).into());
-		}
-
-		if supported_slate_versions.contains(&"V3".to_owned()) {
-			return Ok(SlateVersion::V3);
-		}
-		if supported_slate_versions.contains(&"V2".to_owned()) {
-			return Ok(SlateVersion::V2);
+			return Err(unrecognized(&resp_value));
 		}
 
-		let report = format!("Unable to negotiate slate format with other wallet.");
-		error!("{}", report);
-		Err(ErrorKind::ClientCallback(report).into())
+		// versions this wallet doesn't know of, newer or not, are reported as they are
+		Ok(supported_slate_versions
+			.iter()
+			.filter_map(|v| v.strip_prefix('V').and_then(|n| n.parse().ok()))
+			.collect())
 	}
 
 	fn post<IN>(
@@ -129,6 +182,7 @@ impl HttpSlateSender {
 		url: &str,
 		api_secret: Option<String>,
 		input: IN,
+		time_left: Option<Duration>,
 	) -> Result<String, ClientError>
 	where
 		IN: Serialize,
@@ -138,7 +192,18 @@ impl HttpSlateSender {
 			client.use_socks = true;
 			client.socks_proxy_addr = self.socks_proxy_addr.clone();
 		}
-		let req = client.create_post_request(url, api_secret, &input)?;
+		if let Some(t) = time_left {
+			client.timeout = cmp::min(client.timeout, t);
+		}
+		let mut req = client.create_post_request(url, api_secret, &input)?;
+		if let Some(sig) = self.sender_signature.as_ref() {
+			let value = serde_json::to_string(sig)
+				.ok()
+				.and_then(|s| HeaderValue::from_str(&s).ok());
+			if let Some(v) = value {
+				req.headers_mut().insert(SENDER_SIGNATURE_HEADER, v);
+			}
+		}
 		let res = client.send_request(req)?;
 		Ok(res)
 	}
@@ -177,20 +242,35 @@ impl SlateSender for HttpSlateSender {
 				.map_err(|e| ErrorKind::TorProcess(format!("{:?}", e).into()))?;
 		}
 
-		let slate_send = match self.check_other_version(&url_str)? {
-			SlateVersion::V3 => VersionedSlate::into_version(slate.clone(), SlateVersion::V3),
-			SlateVersion::V2 => {
-				let mut slate = slate.clone();
-				if let Some(_) = slate.payment_proof {
-					return Err(ErrorKind::ClientCallback("Payment proof requested, but other wallet does not support payment proofs. Please urge other user to upgrade, or re-send tx without a payment proof".into()).into());
-				}
-				if let Some(_) = slate.ttl_cutoff_height {
-					warn!("Slate TTL value will be ignored and removed by other wallet, as other wallet does not support this feature. Please urge other user to upgrade");
-				}
-				slate.version_info.version = 2;
-				slate.version_info.orig_version = 2;
-				VersionedSlate::into_version(slate, SlateVersion::V2)
+		// a slate restricted to an older version is sent as such, even to newer wallets
+		let start = Instant::now();
+		let other_versions = self.check_other_version(&url_str, start)?;
+		let version = negotiate_slate_version(&other_versions, slate.version_info.orig_version)
+			.ok_or_else(|| {
+				let report = format!(
+					"Unable to negotiate slate format with other wallet, which supports slate versions {:?} \
+					 while this wallet supports versions 2 to {}.",
+					other_versions, slate.version_info.orig_version
+				);
+				error!("{}", report);
+				ErrorKind::ClientCallback(report)
+			})?;
+		info!("Sending slate version {} to {}", version, self.base_url);
+		let mut slate = slate.clone();
+		if version < slate.version_info.orig_version {
+			if slate.ttl_cutoff_height.is_some() && version < 3 {
+				warn!("Slate TTL value will be ignored and removed by other wallet, as other wallet does not support this feature. Please urge other user to upgrade");
 			}
+			slate.restrict_to_version(version).map_err(|e| {
+				ErrorKind::ClientCallback(format!(
+					"Other wallet only supports slate version {}: {}. Please urge other user to upgrade, or re-send tx without this feature",
+					version, e
+				))
+			})?;
+		}
+		let slate_send = match version {
+			2 => VersionedSlate::into_version(slate, SlateVersion::V2),
+			_ => VersionedSlate::into_version(slate, SlateVersion::V3),
 		};
 		// Note: not using easy-jsonrpc as don't want the dependencies in this crate
 		let req = json!({
@@ -205,11 +285,39 @@ impl SlateSender for HttpSlateSender {
 		});
 		trace!("Sending receive_tx request: {}", req);
 
-		let res: String = self.post(&url_str, None, req).map_err(|e| {
-			let report = format!("Posting transaction slate (is recipient listening?): {}", e);
-			error!("{}", report);
-			ErrorKind::ClientCallback(report)
-		})?;
+		// the recipient returns the response it already signed if the slate made it
+		// there, so an exchange interrupted on the way back is completed by retrying
+		let mut attempt = 1;
+		let res: String = loop {
+			match self.post(&url_str, None, req.clone(), self.time_left(start)?) {
+				Ok(r) => break r,
+				Err(e) if attempt < RECEIVE_TX_ATTEMPTS => {
+					let delay = RECEIVE_TX_RETRY_SECS << (attempt - 1);
+					// no retry the timeout doesn't leave time for
+					if let Some(left) = self.time_left(start)? {
+						if left <= Duration::from_secs(delay) {
+							return Err(self.timed_out());
+						}
+					}
+					warn!(
+						"Posting transaction slate failed ({}), retrying in {}s (attempt {} of {})",
+						e,
+						delay,
+						attempt + 1,
+						RECEIVE_TX_ATTEMPTS
+					);
+					thread::sleep(Duration::from_secs(delay));
+					attempt += 1;
+				}
+				Err(e) => {
+					self.time_left(start)?;
+					let report =
+						format!("Posting transaction slate (is recipient listening?): {}", e);
+					error!("{}", report);
+					return Err(ErrorKind::ClientCallback(report).into());
+				}
+			}
+		};
 
 		let res: Value = serde_json::from_str(&res).unwrap();
 		trace!("Response: {}", res);
@@ -231,6 +339,16 @@ impl SlateSender for HttpSlateSender {
 	}
 }
 
+/// Highest slate version both wallets support, the other wallet supporting the given
+/// versions and this one those from 2 up to the given version
+fn negotiate_slate_version(other_versions: &[u16], max_version: u16) -> Option<u16> {
+	other_versions
+		.iter()
+		.filter(|v| **v >= 2 && **v <= cmp::min(max_version, CURRENT_SLATE_VERSION))
+		.max()
+		.cloned()
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct SchemeNotHttp;
 
@@ -240,3 +358,21 @@ impl Into<Error> for SchemeNotHttp {
 		ErrorKind::GenericError(err_str).into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn slate_version_negotiation() {
+		assert_eq!(negotiate_slate_version(&[3, 2], 3), Some(3));
+		assert_eq!(negotiate_slate_version(&[2], 3), Some(2));
+		// a restricted slate isn't sent in a newer version
+		assert_eq!(negotiate_slate_version(&[3, 2], 2), Some(2));
+		// versions unknown to this wallet are skipped
+		assert_eq!(negotiate_slate_version(&[4, 3], 3), Some(3));
+		assert_eq!(negotiate_slate_version(&[4], 3), None);
+		assert_eq!(negotiate_slate_version(&[3], 2), None);
+		assert_eq!(negotiate_slate_version(&[], 3), None);
+	}
+}
@@ -17,6 +17,7 @@
 use crate::adapters::{SlateReceiver, SlateSender};
 use crate::config::WalletConfig;
 use crate::libwallet::api_impl::foreign;
+use crate::receive_hook_from_config;
 
 use crate::libwallet::{Error, ErrorKind, NodeClient, Slate, WalletInst, WalletLCProvider};
 use crate::util::Mutex;
@@ -366,6 +367,7 @@ impl SlateReceiver for KeybaseAllChannels {
 		let mut w_lock = wallet.lock();
 		let lc = w_lock.lc_provider()?;
 		let w_inst = lc.wallet_inst()?;
+		let receive_hook = config.receive_hook.as_ref().map(receive_hook_from_config);
 
 		info!("Listening for transactions on keybase ...");
 		loop {
@@ -405,16 +407,17 @@ impl SlateReceiver for KeybaseAllChannels {
 							error!("Error validating participant messages: {}", e);
 							return Err(e);
 						}
-						let res = {
-							let r = foreign::receive_tx(
+						let res = match receive_hook.as_ref().map(|h| h.check(&slate)) {
+							Some(Err(e)) => Err(e),
+							_ => foreign::receive_tx(
 								&mut **w_inst,
 								(mask).as_ref(),
 								&slate,
 								None,
 								None,
 								false,
-							);
-							r
+								false,
+							),
 						};
 						match res {
 							// Reply to the same channel with topic SLATE_SIGNED
@@ -0,0 +1,215 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local network transport: listeners advertise their foreign API through mDNS,
+//! and senders look them up by name to post slates to them directly
+use crate::adapters::{HttpSlateSender, SlateSender};
+use crate::keychain::Keychain;
+use crate::libwallet::{
+	address, create_sender_signature, Error, ErrorKind, NodeClient, Slate, WalletInst,
+	WalletLCProvider,
+};
+use crate::util::secp::key::SecretKey;
+use crate::util::Mutex;
+use ed25519_dalek::PublicKey as DalekPublicKey;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Service type under which wallets advertise their foreign API
+pub const LAN_SERVICE_TYPE: &'static str = "_epic-wallet._tcp.local.";
+
+/// Property of the advertisement holding the wallet's address
+const ADDRESS_PROPERTY: &'static str = "address";
+
+/// A wallet found on the local network
+#[derive(Clone, Debug)]
+pub struct LanPeer {
+	/// Name the wallet is advertised under
+	pub name: String,
+	/// URL of its foreign API
+	pub url: String,
+	/// Address whose key signs the wallet's responses
+	pub address: DalekPublicKey,
+}
+
+fn mdns_error(e: mdns_sd::Error) -> Error {
+	ErrorKind::WalletComms(format!("mDNS: {}", e)).into()
+}
+
+/// Address of the interface the local network is reached through
+fn local_ipv4() -> Result<Ipv4Addr, Error> {
+	// connecting a UDP socket sends nothing, it only picks the route
+	let socket = UdpSocket::bind("0.0.0.0:0")?;
+	socket.connect("224.0.0.251:5353")?;
+	match socket.local_addr()?.ip() {
+		IpAddr::V4(ip) if !ip.is_unspecified() => Ok(ip),
+		_ => Err(ErrorKind::WalletComms("No local network interface found".to_owned()).into()),
+	}
+}
+
+/// Advertisement of the listener on the local network, withdrawn when dropped
+pub struct LanAdvertiser {
+	daemon: ServiceDaemon,
+	fullname: String,
+}
+
+impl LanAdvertiser {
+	/// Advertise the foreign API listening at `listen_addr` under `name`, along with
+	/// the wallet's address
+	pub fn start(
+		name: &str,
+		listen_addr: &str,
+		address: &DalekPublicKey,
+	) -> Result<LanAdvertiser, Error> {
+		let addr: std::net::SocketAddr = listen_addr.parse().map_err(|_| {
+			ErrorKind::WalletComms(format!("Invalid listen address {}", listen_addr))
+		})?;
+		let ip = match addr.ip() {
+			IpAddr::V4(ip) if ip.is_loopback() => {
+				return Err(ErrorKind::WalletComms(format!(
+					"The listener at {} can't be reached from the local network",
+					listen_addr
+				))
+				.into());
+			}
+			IpAddr::V4(ip) if !ip.is_unspecified() => ip,
+			_ => local_ipv4()?,
+		};
+
+		let onion = address::onion_v3_from_pubkey(address)?;
+		let properties = [(ADDRESS_PROPERTY, onion.as_str())];
+		let host = format!("{}.local.", name);
+		let info = ServiceInfo::new(
+			LAN_SERVICE_TYPE,
+			name,
+			&host,
+			ip,
+			addr.port(),
+			&properties[..],
+		)
+		.map_err(mdns_error)?;
+		let fullname = info.get_fullname().to_owned();
+
+		let daemon = ServiceDaemon::new().map_err(mdns_error)?;
+		daemon.register(info).map_err(mdns_error)?;
+		warn!(
+			"Advertising the listener on the local network as {} at {}:{}",
+			name,
+			ip,
+			addr.port()
+		);
+		Ok(LanAdvertiser { daemon, fullname })
+	}
+}
+
+impl Drop for LanAdvertiser {
+	fn drop(&mut self) {
+		let _ = self.daemon.unregister(&self.fullname);
+		let _ = self.daemon.shutdown();
+	}
+}
+
+/// Look for the wallet advertised under `name` on the local network, for at most
+/// `timeout`
+pub fn discover_lan_peer(name: &str, timeout: Duration) -> Result<LanPeer, Error> {
+	let daemon = ServiceDaemon::new().map_err(mdns_error)?;
+	let receiver = daemon.browse(LAN_SERVICE_TYPE).map_err(mdns_error)?;
+	let fullname = format!("{}.{}", name, LAN_SERVICE_TYPE);
+	let deadline = Instant::now() + timeout;
+
+	let mut res = Err(ErrorKind::WalletComms(format!(
+		"No wallet named {} found on the local network",
+		name
+	))
+	.into());
+	loop {
+		let now = Instant::now();
+		if now >= deadline {
+			break;
+		}
+		let info = match receiver.recv_timeout(deadline - now) {
+			Ok(ServiceEvent::ServiceResolved(info)) => info,
+			Ok(_) => continue,
+			Err(_) => break,
+		};
+		if !info.get_fullname().eq_ignore_ascii_case(&fullname) {
+			continue;
+		}
+		let ip = match info.get_addresses().iter().next() {
+			Some(ip) => *ip,
+			None => continue,
+		};
+		let address = match info.get_property_val_str(ADDRESS_PROPERTY) {
+			Some(a) => address::pubkey_from_onion_v3(a),
+			None => Err(ErrorKind::WalletComms(format!(
+				"Wallet {} doesn't advertise its address",
+				name
+			))
+			.into()),
+		};
+		res = address.map(|address| LanPeer {
+			name: name.to_owned(),
+			url: format!("http://{}:{}", ip, info.get_port()),
+			address,
+		});
+		break;
+	}
+	let _ = daemon.shutdown();
+	res
+}
+
+/// Posts slates to a wallet on the local network, signed with the key of the
+/// sender's address so the recipient can tell who it receives from
+pub struct LanSlateSender {
+	url: String,
+	sender_key: SecretKey,
+}
+
+impl LanSlateSender {
+	/// Create a sender to the foreign API at `url`, signing as the wallet's address
+	pub fn new<L, C, K>(
+		url: &str,
+		wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+		keychain_mask: Option<&SecretKey>,
+	) -> Result<LanSlateSender, Error>
+	where
+		L: WalletLCProvider<'static, C, K> + 'static,
+		C: NodeClient + 'static,
+		K: Keychain + 'static,
+	{
+		let sender_key = {
+			let mut w_lock = wallet.lock();
+			let w = w_lock.lc_provider()?.wallet_inst()?;
+			let parent_key_id = w.parent_key_id();
+			let k = w.keychain(keychain_mask)?;
+			address::address_from_derivation_path(&k, &parent_key_id, 0)?
+		};
+		Ok(LanSlateSender {
+			url: url.to_owned(),
+			sender_key,
+		})
+	}
+}
+
+impl SlateSender for LanSlateSender {
+	fn send_tx(&self, slate: &Slate) -> Result<Slate, Error> {
+		let signature = create_sender_signature(slate, self.sender_key.clone())?;
+		HttpSlateSender::new(&self.url)
+			.map_err(|_| ErrorKind::WalletComms(format!("Invalid peer URL {}", self.url)))?
+			.with_sender_signature(signature)
+			.send_tx(slate)
+	}
+}
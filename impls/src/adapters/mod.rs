@@ -17,6 +17,8 @@ mod epicbox;
 mod file;
 pub mod http;
 mod keybase;
+mod lan;
+mod slate_pool;
 
 pub use self::emoji::EmojiSlate;
 pub use self::epicbox::{
@@ -25,8 +27,9 @@ pub use self::epicbox::{
 };
 pub use self::epicbox::{EpicboxChannel, EpicboxListenChannel};
 pub use self::file::PathToSlate;
-pub use self::http::{HttpSlateSender, SchemeNotHttp};
+pub use self::http::{HttpSlateSender, SchemeNotHttp, SENDER_SIGNATURE_HEADER};
 pub use self::keybase::{KeybaseAllChannels, KeybaseChannel};
+pub use self::lan::{discover_lan_peer, LanAdvertiser, LanPeer, LanSlateSender, LAN_SERVICE_TYPE};
 use crate::config::{TorConfig, WalletConfig};
 use crate::libwallet::{Error, ErrorKind, NodeClient, Slate, WalletInst, WalletLCProvider};
 use crate::tor::config::complete_tor_address;
@@ -35,6 +38,7 @@ use crate::keychain::Keychain;
 use crate::util::secp::key::SecretKey;
 use crate::util::Mutex;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Sends transactions to a corresponding SlateReceiver
 pub trait SlateSender {
@@ -71,11 +75,13 @@ pub trait SlateGetter {
 	fn get_tx(&self) -> Result<Slate, Error>;
 }
 
-/// select a SlateSender based on method and dest fields from, e.g., SendArgs
+/// select a SlateSender based on method and dest fields from, e.g., SendArgs.
+/// Exchanges over http and Tor are aborted after the timeout, if given.
 pub fn create_sender(
 	method: &str,
 	dest: &str,
 	tor_config: Option<TorConfig>,
+	timeout: Option<Duration>,
 ) -> Result<Box<dyn SlateSender>, Error> {
 	let invalid = || {
 		ErrorKind::WalletComms(format!(
@@ -96,8 +102,15 @@ pub fn create_sender(
 		Err(_) => dest.into(),
 	};
 
+	let with_timeout = |sender: HttpSlateSender| match timeout {
+		Some(t) => sender.with_timeout(t),
+		None => sender,
+	};
+
 	Ok(match method {
-		"http" => Box::new(HttpSlateSender::new(&dest).map_err(|_| invalid())?),
+		"http" => Box::new(with_timeout(
+			HttpSlateSender::new(&dest).map_err(|_| invalid())?,
+		)),
 
 		"tor" => match tor_config {
 			None => {
@@ -105,10 +118,10 @@ pub fn create_sender(
 					ErrorKind::WalletComms("Tor Configuration required".to_string()).into(),
 				);
 			}
-			Some(tc) => Box::new(
+			Some(tc) => Box::new(with_timeout(
 				HttpSlateSender::with_socks_proxy(&dest, &tc.socks_proxy_addr, &tc.send_config_dir)
 					.map_err(|_| invalid())?,
-			),
+			)),
 		},
 		"keybase" => Box::new(KeybaseChannel::new(dest.to_owned())?),
 
@@ -134,3 +147,12 @@ pub fn create_sender(
 		}
 	})
 }
+
+/// Name of the transport used by the sender `create_sender` returns for the
+/// given method and destination, as recorded in the transport metrics
+pub fn sender_transport(method: &str, dest: &str) -> String {
+	match complete_tor_address(dest) {
+		Ok(_) => "tor".to_owned(),
+		Err(_) => method.to_owned(),
+	}
+}
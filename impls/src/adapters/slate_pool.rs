@@ -0,0 +1,174 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded pool of threads processing incoming slates. Slates are processed at the
+//! same time, but each waits for its turn, in the order they were received, before
+//! persisting its results. A slate failing or panicking doesn't affect the others.
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+
+/// Order in which processed slates persist their results
+struct Sequencer {
+	next: Mutex<u64>,
+	cond: Condvar,
+}
+
+impl Sequencer {
+	fn lock(&self) -> MutexGuard<u64> {
+		// nothing panics while holding the lock, but a turn mustn't panic in drop
+		self.next.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+/// Position of a slate in the order results are persisted. The order moves on to
+/// the next slate once the turn is dropped, whether or not the slate was processed
+/// successfully.
+pub struct Turn {
+	seq: u64,
+	sequencer: Arc<Sequencer>,
+}
+
+impl Turn {
+	/// Wait until the slates received before this one are done
+	pub fn wait(&self) {
+		let mut next = self.sequencer.lock();
+		while *next != self.seq {
+			next = self
+				.sequencer
+				.cond
+				.wait(next)
+				.unwrap_or_else(PoisonError::into_inner);
+		}
+	}
+}
+
+impl Drop for Turn {
+	fn drop(&mut self) {
+		self.wait();
+		*self.sequencer.lock() += 1;
+		self.sequencer.cond.notify_all();
+	}
+}
+
+struct Job {
+	turn: Turn,
+	run: Box<dyn FnOnce(&Turn) + Send>,
+}
+
+/// Workers processing incoming slates, one at a time each
+pub struct SlatePool {
+	jobs: Option<SyncSender<Job>>,
+	workers: Vec<JoinHandle<()>>,
+	sequencer: Arc<Sequencer>,
+	received: AtomicU64,
+}
+
+impl SlatePool {
+	/// Start `size` workers. As many slates again can wait for a worker, after which
+	/// submitting blocks until one is free.
+	pub fn new(size: usize) -> SlatePool {
+		let size = size.max(1);
+		let (tx, rx) = sync_channel::<Job>(size);
+		let rx = Arc::new(Mutex::new(rx));
+		let workers = (0..size)
+			.map(|_| {
+				let rx = rx.clone();
+				thread::spawn(move || SlatePool::work(rx))
+			})
+			.collect();
+		SlatePool {
+			jobs: Some(tx),
+			workers,
+			sequencer: Arc::new(Sequencer {
+				next: Mutex::new(0),
+				cond: Condvar::new(),
+			}),
+			received: AtomicU64::new(0),
+		}
+	}
+
+	fn work(rx: Arc<Mutex<Receiver<Job>>>) {
+		loop {
+			let job = match rx.lock().unwrap_or_else(PoisonError::into_inner).recv() {
+				Ok(j) => j,
+				Err(_) => break,
+			};
+			let Job { turn, run } = job;
+			if panic::catch_unwind(AssertUnwindSafe(|| run(&turn))).is_err() {
+				error!("Processing of an incoming slate panicked");
+			}
+		}
+	}
+
+	/// Process a slate on the next free worker. `run` is given the slate's turn, to
+	/// wait on before persisting anything.
+	pub fn submit<F>(&self, run: F)
+	where
+		F: FnOnce(&Turn) + Send + 'static,
+	{
+		let turn = Turn {
+			seq: self.received.fetch_add(1, Ordering::SeqCst),
+			sequencer: self.sequencer.clone(),
+		};
+		let job = Job {
+			turn,
+			run: Box::new(run),
+		};
+		if let Some(jobs) = self.jobs.as_ref() {
+			if jobs.send(job).is_err() {
+				error!("No worker left to process an incoming slate");
+			}
+		}
+	}
+}
+
+impl Drop for SlatePool {
+	fn drop(&mut self) {
+		// let the workers finish the slates already received
+		self.jobs.take();
+		for w in self.workers.drain(..) {
+			let _ = w.join();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[test]
+	fn slate_pool_order() {
+		let persisted = Arc::new(Mutex::new(vec![]));
+		{
+			let pool = SlatePool::new(4);
+			for i in 0..8u64 {
+				let persisted = persisted.clone();
+				pool.submit(move |turn| {
+					// the first slates are the slowest to process
+					thread::sleep(Duration::from_millis(10 * (8 - i)));
+					if i == 2 {
+						panic!("slate {} refused", i);
+					}
+					turn.wait();
+					persisted.lock().unwrap().push(i);
+				});
+			}
+		}
+		assert_eq!(*persisted.lock().unwrap(), vec![0, 1, 3, 4, 5, 6, 7]);
+	}
+}
@@ -18,18 +18,19 @@
 
 use crate::serialization as ser;
 use crate::serialization::Serializable;
-use crate::Error;
-use sqlite::{self, Connection};
-use std::path::PathBuf;
+use crate::{Error, ErrorKind};
+use sqlite::{self, Connection, OpenFlags};
+use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::Duration;
 
 const SQLITE_MAX_RETRIES: u8 = 3;
-static SQLITE_FILENAME: &str = "epic.db";
+pub(crate) static SQLITE_FILENAME: &str = "epic.db";
 
 /// Basic struct holding the SQLite database connection
 pub struct Store {
 	db: Connection,
+	read_only: bool,
 }
 
 impl Store {
@@ -37,7 +38,48 @@ impl Store {
 		let db_path = db_path.join(SQLITE_FILENAME);
 		let db: Connection = sqlite::open(db_path)?;
 		Store::check_or_create(&db)?;
-		Ok(Store { db })
+		Ok(Store {
+			db,
+			read_only: false,
+		})
+	}
+
+	/// Opens an existing database for reading only, alongside the process writing to it
+	/// Nothing is created and every write through its batches is refused
+	pub fn open_read_only(db_path: PathBuf) -> Result<Store, Error> {
+		let db_path = db_path.join(SQLITE_FILENAME);
+		if !db_path.exists() {
+			return Err(ErrorKind::SQLiteError(format!(
+				"No wallet database at {}",
+				db_path.display()
+			))
+			.into());
+		}
+		let db = Connection::open_with_flags(db_path, OpenFlags::new().set_read_only())?;
+		Ok(Store {
+			db,
+			read_only: true,
+		})
+	}
+
+	/// Writes a consistent copy of the database to the given file, which mustn't exist,
+	/// with `VACUUM INTO`, whatever the process writing to it is doing
+	pub fn snapshot(&self, dest: &Path) -> Result<(), sqlite::Error> {
+		let dest = dest.to_string_lossy().replace('\'', "''");
+		self.execute(format!("VACUUM INTO '{}'", dest))
+	}
+
+	/// Whether the database was opened for reading only
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+
+	/// Fails with a ReadOnlyStore error if the database was opened for reading only
+	pub fn check_writable(&self) -> Result<(), Error> {
+		match self.read_only {
+			true => Err(ErrorKind::ReadOnlyStore.into()),
+			false => Ok(()),
+		}
 	}
 
 	/// Handle the creation of the database
@@ -141,6 +183,29 @@ impl Store {
 			.collect()
 	}
 
+	/// Returns every record of the database with its key, its value left as stored
+	pub fn records(&self) -> Result<Vec<(Vec<u8>, String)>, Error> {
+		let mut records = vec![];
+		for row in self.db.prepare("SELECT key, data FROM data;")?.into_iter() {
+			let row = row?;
+			let column = row.read::<&str, _>("key");
+			// keys are written in the Debug format of their bytes
+			let key: Option<Vec<u8>> = column
+				.trim_start_matches('[')
+				.trim_end_matches(']')
+				.split(", ")
+				.map(|b| b.parse().ok())
+				.collect();
+			match key {
+				Some(k) => records.push((k, row.read::<&str, _>("data").to_string())),
+				None => {
+					return Err(ErrorKind::SQLiteError(format!("Invalid key {}", column)).into())
+				}
+			}
+		}
+		Ok(records)
+	}
+
 	/// Builds a new batch to be used with this store
 	pub fn batch(&self) -> Batch {
 		Batch { store: self }
@@ -184,6 +249,7 @@ impl<'a> Batch<'_> {
 	/// Writes a single value to the db, given a key and a Serializable enum
 	/// Specialized queries are used for TxLogEntry and OutputData to make best use of queriable columns
 	pub fn put(&self, key: &[u8], value: Serializable) -> Result<(), Error> {
+		self.store.check_writable()?;
 		// serialize value to json
 		let value_s = ser::serialize(&value).unwrap();
 		let prefix = key[0] as char;
@@ -291,6 +357,7 @@ impl<'a> Batch<'_> {
 
 	/// Deletes a key from the db
 	pub fn delete(&self, key: &[u8]) -> Result<(), Error> {
+		self.store.check_writable()?;
 		let statement = format!(
 			r#"
 		DELETE 
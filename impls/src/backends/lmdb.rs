@@ -14,12 +14,15 @@
 
 use super::db::{self, Store};
 use crate::blake2::blake2b::{Blake2b, Blake2bResult};
+use crate::config::DerivationConfig;
 use crate::core::core::Transaction;
 use crate::core::ser;
 use crate::keychain::{ChildNumber, ExtKeychain, Identifier, Keychain, SwitchCommitmentType};
 use crate::libwallet::{
-	AcctPathMapping, Context, Error, ErrorKind, NodeClient, OutputData, OutputStatus,
-	ScannedBlockInfo, TxLogEntry, WalletBackend, WalletInitStatus, WalletOutputBatch,
+	AcctPathMapping, BlockHeaderInfo, Contact, Context, Error, ErrorKind, MetadataEntry,
+	NodeClient, OutputData, OutputStatus, PinnedKey, ScannedBlockInfo, SendTemplate, Slate,
+	StoredEvent, Swap, TransportMetrics, TxLogEntry, WalletBackend, WalletInitStatus,
+	WalletOutputBatch,
 };
 use crate::serialization::Serializable;
 use crate::store::Error as StoreError;
@@ -30,6 +33,7 @@ use crate::util::{self, secp};
 use rand::rngs::mock::StepRng;
 use rand::thread_rng;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::marker::PhantomData;
@@ -38,12 +42,12 @@ use std::{fs, path};
 use uuid::Uuid;
 
 pub const DB_DIR: &'static str = "db";
-const SQLITE_DIR: &'static str = "sqlite";
+pub(super) const SQLITE_DIR: &'static str = "sqlite";
 pub const TX_SAVE_DIR: &'static str = "saved_txs";
 
 const OUTPUT_HISTORY_PREFIX: u8 = 'h' as u8;
 const OUTPUT_HISTORY_ID_PREFIX: u8 = 'j' as u8;
-const OUTPUT_PREFIX: u8 = 'o' as u8;
+pub(super) const OUTPUT_PREFIX: u8 = 'o' as u8;
 const DERIV_PREFIX: u8 = 'd' as u8;
 const CONFIRMED_HEIGHT_PREFIX: u8 = 'c' as u8;
 const PRIVATE_TX_CONTEXT_PREFIX: u8 = 'p' as u8;
@@ -54,6 +58,79 @@ const LAST_SCANNED_BLOCK: u8 = 'l' as u8;
 const LAST_SCANNED_KEY: &str = "LAST_SCANNED_KEY";
 const WALLET_INIT_STATUS: u8 = 'w' as u8;
 const WALLET_INIT_STATUS_KEY: &str = "WALLET_INIT_STATUS";
+const TRANSPORT_METRICS_PREFIX: u8 = 'm' as u8;
+const PINNED_KEY_PREFIX: u8 = 'k' as u8;
+const CONTACT_PREFIX: u8 = 'n' as u8;
+const SEND_TEMPLATE_PREFIX: u8 = 's' as u8;
+const SLATE_RESPONSE_PREFIX: u8 = 'r' as u8;
+const METADATA_PREFIX: u8 = 'e' as u8;
+const BLOCK_HEADER_PREFIX: u8 = 'b' as u8;
+const SWAP_PREFIX: u8 = 'x' as u8;
+const EVENT_PREFIX: u8 = 'z' as u8;
+const DERIVATION_CONFIG: u8 = 'v' as u8;
+const DERIVATION_CONFIG_KEY: &str = "DERIVATION_CONFIG";
+
+/// How the values stored under a key prefix are serialized by the LMDB store the
+/// wallet used before SQLite
+pub(super) enum RecordKind {
+	/// JSON, length prefixed
+	Json,
+	/// Binary u32
+	U32,
+	/// Binary u64
+	U64,
+}
+
+/// Every key prefix of the store, with how its values are serialized
+pub(super) const RECORD_KINDS: [(u8, RecordKind); 21] = [
+	(OUTPUT_HISTORY_PREFIX, RecordKind::Json),
+	(OUTPUT_HISTORY_ID_PREFIX, RecordKind::U32),
+	(OUTPUT_PREFIX, RecordKind::Json),
+	(DERIV_PREFIX, RecordKind::U32),
+	(CONFIRMED_HEIGHT_PREFIX, RecordKind::U64),
+	(PRIVATE_TX_CONTEXT_PREFIX, RecordKind::Json),
+	(TX_LOG_ENTRY_PREFIX, RecordKind::Json),
+	(TX_LOG_ID_PREFIX, RecordKind::U32),
+	(ACCOUNT_PATH_MAPPING_PREFIX, RecordKind::Json),
+	(LAST_SCANNED_BLOCK, RecordKind::Json),
+	(WALLET_INIT_STATUS, RecordKind::Json),
+	(TRANSPORT_METRICS_PREFIX, RecordKind::Json),
+	(PINNED_KEY_PREFIX, RecordKind::Json),
+	(CONTACT_PREFIX, RecordKind::Json),
+	(SEND_TEMPLATE_PREFIX, RecordKind::Json),
+	(SLATE_RESPONSE_PREFIX, RecordKind::Json),
+	(METADATA_PREFIX, RecordKind::Json),
+	(BLOCK_HEADER_PREFIX, RecordKind::Json),
+	(SWAP_PREFIX, RecordKind::Json),
+	(EVENT_PREFIX, RecordKind::Json),
+	(DERIVATION_CONFIG, RecordKind::Json),
+];
+
+/// The value of a record from its JSON, typed after its key prefix
+pub(super) fn record_from_json(prefix: u8, json: &str) -> serde_json::Result<Serializable> {
+	let record = match prefix {
+		OUTPUT_PREFIX | OUTPUT_HISTORY_PREFIX => {
+			Serializable::OutputData(serde_json::from_str(json)?)
+		}
+		TX_LOG_ENTRY_PREFIX => Serializable::TxLogEntry(serde_json::from_str(json)?),
+		ACCOUNT_PATH_MAPPING_PREFIX => Serializable::AcctPathMapping(serde_json::from_str(json)?),
+		PRIVATE_TX_CONTEXT_PREFIX => Serializable::Context(serde_json::from_str(json)?),
+		LAST_SCANNED_BLOCK => Serializable::ScannedBlockInfo(serde_json::from_str(json)?),
+		WALLET_INIT_STATUS => Serializable::WalletInitStatus(serde_json::from_str(json)?),
+		TRANSPORT_METRICS_PREFIX => Serializable::TransportMetrics(serde_json::from_str(json)?),
+		PINNED_KEY_PREFIX => Serializable::PinnedKey(serde_json::from_str(json)?),
+		CONTACT_PREFIX => Serializable::Contact(serde_json::from_str(json)?),
+		SEND_TEMPLATE_PREFIX => Serializable::SendTemplate(serde_json::from_str(json)?),
+		SLATE_RESPONSE_PREFIX => Serializable::Slate(serde_json::from_str(json)?),
+		METADATA_PREFIX => Serializable::MetadataEntry(serde_json::from_str(json)?),
+		BLOCK_HEADER_PREFIX => Serializable::BlockHeaderInfo(serde_json::from_str(json)?),
+		SWAP_PREFIX => Serializable::Swap(serde_json::from_str(json)?),
+		EVENT_PREFIX => Serializable::StoredEvent(serde_json::from_str(json)?),
+		DERIVATION_CONFIG => Serializable::DerivationConfig(serde_json::from_str(json)?),
+		_ => Serializable::Numeric(serde_json::from_str(json)?),
+	};
+	Ok(record)
+}
 
 /// test to see if database files exist in the current directory. If so,
 /// use a DB backend for all operations
@@ -62,6 +139,15 @@ pub fn wallet_db_exists(data_file_dir: &str) -> bool {
 	db_path.exists()
 }
 
+/// Store key of a metadata entry, the namespace and key separated by a zero byte
+/// which the validated namespace can't contain
+fn metadata_key(namespace: &str, key: &str) -> Vec<u8> {
+	let mut k = namespace.as_bytes().to_vec();
+	k.push(0);
+	k.extend_from_slice(key.as_bytes());
+	k
+}
+
 /// Helper to derive XOR keys for storing private transaction keys in the DB
 /// (blind_xor_key, nonce_xor_key)
 fn private_ctx_xor_keys<K>(
@@ -135,27 +221,58 @@ where
 		let default_account = AcctPathMapping {
 			label: "default".to_owned(),
 			path: LMDBBackend::<C, K>::default_path(),
+			metadata: BTreeMap::new(),
 		};
 		let acct_key = to_key(
 			ACCOUNT_PATH_MAPPING_PREFIX,
 			&mut default_account.label.as_bytes().to_vec(),
 		);
 
-		{
+		// don't overwrite an existing mapping, which may carry metadata
+		if !store.exists(&acct_key)? {
 			let batch = store.batch();
 			batch.put(&acct_key, Serializable::AcctPathMapping(default_account))?;
 		}
+		Ok(LMDBBackend::from_store(store, data_file_dir, n_client))
+	}
+
+	/// Open the store of an existing wallet for reading only, e.g. for reporting
+	/// while another process holds it for writing. Every write through the
+	/// returned backend fails with a read-only store error.
+	pub fn new_read_only(data_file_dir: &str, n_client: C) -> Result<Self, Error> {
+		let db_path = path::Path::new(data_file_dir).join(DB_DIR).join(SQLITE_DIR);
+		let store = db::Store::open_read_only(db_path)?;
+		Ok(LMDBBackend::from_store(store, data_file_dir, n_client))
+	}
+
+	fn from_store(store: Store, data_file_dir: &str, n_client: C) -> Self {
+		let acct_key = to_key(
+			ACCOUNT_PATH_MAPPING_PREFIX,
+			&mut "default".as_bytes().to_vec(),
+		);
+		// the default account is moved when the wallet has its own derivation scheme
+		let parent_key_id = match store
+			.get(&acct_key)
+			.and_then(Serializable::as_acct_path_mapping)
+		{
+			Some(m) => m.path,
+			None => LMDBBackend::<C, K>::default_path(),
+		};
 
-		let res = LMDBBackend {
+		LMDBBackend {
 			db: store,
 			data_file_dir: data_file_dir.to_owned(),
 			keychain: None,
 			master_checksum: Box::new(None),
-			parent_key_id: LMDBBackend::<C, K>::default_path(),
+			parent_key_id,
 			w2n_client: n_client,
 			_phantom: &PhantomData,
-		};
-		Ok(res)
+		}
+	}
+
+	/// Whether the store was opened for reading only
+	pub fn is_read_only(&self) -> bool {
+		self.db.is_read_only()
 	}
 
 	fn default_path() -> Identifier {
@@ -403,6 +520,7 @@ where
 	}
 
 	fn store_tx(&self, uuid: &str, tx: &Transaction) -> Result<(), Error> {
+		self.db.check_writable()?;
 		let filename = format!("{}.epictx", uuid);
 		let path = path::Path::new(&self.data_file_dir)
 			.join(TX_SAVE_DIR)
@@ -547,6 +665,162 @@ where
 		};
 		Ok(status)
 	}
+
+	fn derivation_config(&self) -> Result<DerivationConfig, Error> {
+		let config_key = to_key(
+			DERIVATION_CONFIG,
+			&mut DERIVATION_CONFIG_KEY.as_bytes().to_vec(),
+		);
+		let config = match self.db.get(&config_key) {
+			Some(s) => Serializable::as_derivation_config(s).unwrap_or_default(),
+			None => DerivationConfig::default(),
+		};
+		Ok(config)
+	}
+
+	fn transport_metrics_iter<'a>(&'a self) -> Box<dyn Iterator<Item = TransportMetrics> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[TRANSPORT_METRICS_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_transport_metrics)
+			.collect();
+		Box::new(serializables.into_iter().map(|x| x))
+	}
+
+	fn get_transport_metrics(&self, transport: &str) -> Result<Option<TransportMetrics>, Error> {
+		let metrics_key = to_key(TRANSPORT_METRICS_PREFIX, &mut transport.as_bytes().to_vec());
+		Ok(match self.db.get(&metrics_key) {
+			Some(s) => Serializable::as_transport_metrics(s),
+			None => None,
+		})
+	}
+
+	fn pinned_keys_iter<'a>(&'a self) -> Box<dyn Iterator<Item = PinnedKey> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[PINNED_KEY_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_pinned_key)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn get_pinned_key(&self, contact: &str) -> Result<Option<PinnedKey>, Error> {
+		let key = to_key(PINNED_KEY_PREFIX, &mut contact.as_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_pinned_key(s),
+			None => None,
+		})
+	}
+
+	fn contacts_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Contact> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[CONTACT_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_contact)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn get_contact(&self, name: &str) -> Result<Option<Contact>, Error> {
+		let key = to_key(CONTACT_PREFIX, &mut name.as_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_contact(s),
+			None => None,
+		})
+	}
+
+	fn send_templates_iter<'a>(&'a self) -> Box<dyn Iterator<Item = SendTemplate> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[SEND_TEMPLATE_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_send_template)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn get_send_template(&self, name: &str) -> Result<Option<SendTemplate>, Error> {
+		let key = to_key(SEND_TEMPLATE_PREFIX, &mut name.as_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_send_template(s),
+			None => None,
+		})
+	}
+
+	fn get_slate_response(&self, slate_id: &Uuid) -> Result<Option<Slate>, Error> {
+		let key = to_key(SLATE_RESPONSE_PREFIX, &mut slate_id.as_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_slate(s),
+			None => None,
+		})
+	}
+
+	fn get_metadata(&self, namespace: &str, key: &str) -> Result<Option<MetadataEntry>, Error> {
+		let key = to_key(METADATA_PREFIX, &mut metadata_key(namespace, key));
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_metadata_entry(s),
+			None => None,
+		})
+	}
+
+	fn metadata_iter<'a>(&'a self) -> Box<dyn Iterator<Item = MetadataEntry> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[METADATA_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_metadata_entry)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn get_block_header(&self, height: u64) -> Result<Option<BlockHeaderInfo>, Error> {
+		let key = to_key(BLOCK_HEADER_PREFIX, &mut height.to_be_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_block_header(s),
+			None => None,
+		})
+	}
+
+	fn block_headers_iter<'a>(&'a self) -> Box<dyn Iterator<Item = BlockHeaderInfo> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[BLOCK_HEADER_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_block_header)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn swaps_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Swap> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[SWAP_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_swap)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn get_swap(&self, id: &Uuid) -> Result<Option<Swap>, Error> {
+		let key = to_key(SWAP_PREFIX, &mut id.as_bytes().to_vec());
+		Ok(match self.db.get(&key) {
+			Some(s) => Serializable::as_swap(s),
+			None => None,
+		})
+	}
+
+	fn events_iter<'a>(&'a self) -> Box<dyn Iterator<Item = StoredEvent> + 'a> {
+		let serializables: Vec<_> = self
+			.db
+			.iter(&[EVENT_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_stored_event)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
 }
 
 /// An atomic batch in which all changes can be committed all at once or
@@ -793,6 +1067,173 @@ where
 		Ok(())
 	}
 
+	fn save_derivation_config(&mut self, config: DerivationConfig) -> Result<(), Error> {
+		let config_key = to_key(
+			DERIVATION_CONFIG,
+			&mut DERIVATION_CONFIG_KEY.as_bytes().to_vec(),
+		);
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&config_key, Serializable::DerivationConfig(config))?;
+		Ok(())
+	}
+
+	fn save_transport_metrics(&mut self, metrics: TransportMetrics) -> Result<(), Error> {
+		let metrics_key = to_key(
+			TRANSPORT_METRICS_PREFIX,
+			&mut metrics.transport.as_bytes().to_vec(),
+		);
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&metrics_key, Serializable::TransportMetrics(metrics))?;
+		Ok(())
+	}
+
+	fn save_pinned_key(&mut self, key: PinnedKey) -> Result<(), Error> {
+		let pin_key = to_key(PINNED_KEY_PREFIX, &mut key.contact.as_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&pin_key, Serializable::PinnedKey(key))?;
+		Ok(())
+	}
+
+	fn delete_pinned_key(&mut self, contact: &str) -> Result<(), Error> {
+		let pin_key = to_key(PINNED_KEY_PREFIX, &mut contact.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&pin_key)?;
+		Ok(())
+	}
+
+	fn save_contact(&mut self, contact: Contact) -> Result<(), Error> {
+		let contact_key = to_key(CONTACT_PREFIX, &mut contact.name.as_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&contact_key, Serializable::Contact(contact))?;
+		Ok(())
+	}
+
+	fn delete_contact(&mut self, name: &str) -> Result<(), Error> {
+		let contact_key = to_key(CONTACT_PREFIX, &mut name.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&contact_key)?;
+		Ok(())
+	}
+
+	fn save_send_template(&mut self, template: SendTemplate) -> Result<(), Error> {
+		let template_key = to_key(SEND_TEMPLATE_PREFIX, &mut template.name.as_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&template_key, Serializable::SendTemplate(template))?;
+		Ok(())
+	}
+
+	fn delete_send_template(&mut self, name: &str) -> Result<(), Error> {
+		let template_key = to_key(SEND_TEMPLATE_PREFIX, &mut name.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&template_key)?;
+		Ok(())
+	}
+
+	fn save_slate_response(&mut self, slate: Slate) -> Result<(), Error> {
+		let response_key = to_key(SLATE_RESPONSE_PREFIX, &mut slate.id.as_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&response_key, Serializable::Slate(slate))?;
+		Ok(())
+	}
+
+	fn delete_slate_response(&mut self, slate_id: &Uuid) -> Result<(), Error> {
+		let response_key = to_key(SLATE_RESPONSE_PREFIX, &mut slate_id.as_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&response_key)?;
+		Ok(())
+	}
+
+	fn save_metadata(&mut self, entry: MetadataEntry) -> Result<(), Error> {
+		let entry_key = to_key(
+			METADATA_PREFIX,
+			&mut metadata_key(&entry.namespace, &entry.key),
+		);
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&entry_key, Serializable::MetadataEntry(entry))?;
+		Ok(())
+	}
+
+	fn delete_metadata(&mut self, namespace: &str, key: &str) -> Result<(), Error> {
+		let entry_key = to_key(METADATA_PREFIX, &mut metadata_key(namespace, key));
+		self.db.borrow().as_ref().unwrap().delete(&entry_key)?;
+		Ok(())
+	}
+
+	fn save_block_header(&mut self, header: BlockHeaderInfo) -> Result<(), Error> {
+		let header_key = to_key(
+			BLOCK_HEADER_PREFIX,
+			&mut header.height.to_be_bytes().to_vec(),
+		);
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&header_key, Serializable::BlockHeaderInfo(header))?;
+		Ok(())
+	}
+
+	fn delete_block_header(&mut self, height: u64) -> Result<(), Error> {
+		let header_key = to_key(BLOCK_HEADER_PREFIX, &mut height.to_be_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&header_key)?;
+		Ok(())
+	}
+
+	fn save_swap(&mut self, swap: Swap) -> Result<(), Error> {
+		let swap_key = to_key(SWAP_PREFIX, &mut swap.id.as_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&swap_key, Serializable::Swap(swap))?;
+		Ok(())
+	}
+
+	fn events_iter(&self) -> Box<dyn Iterator<Item = StoredEvent>> {
+		let serializables: Vec<_> = self
+			.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.iter(&[EVENT_PREFIX])
+			.into_iter()
+			.filter_map(Serializable::as_stored_event)
+			.collect();
+		Box::new(serializables.into_iter())
+	}
+
+	fn save_event(&mut self, event: StoredEvent) -> Result<(), Error> {
+		let event_key = to_key(EVENT_PREFIX, &mut event.id.to_be_bytes().to_vec());
+		self.db
+			.borrow()
+			.as_ref()
+			.unwrap()
+			.put_ser(&event_key, Serializable::StoredEvent(event))?;
+		Ok(())
+	}
+
+	fn delete_event(&mut self, id: u64) -> Result<(), Error> {
+		let event_key = to_key(EVENT_PREFIX, &mut id.to_be_bytes().to_vec());
+		self.db.borrow().as_ref().unwrap().delete(&event_key)?;
+		Ok(())
+	}
+
 	fn save_child_index(&mut self, parent_id: &Identifier, child_n: u32) -> Result<(), Error> {
 		let deriv_key = to_key(DERIV_PREFIX, &mut parent_id.to_bytes().to_vec());
 		self.db
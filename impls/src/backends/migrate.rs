@@ -0,0 +1,303 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Offline conversion of a wallet's store between the SQLite backend the wallet uses
+//! and the LMDB one it used before. The converted store is built next to the current
+//! one and only replaces it once its records and balances are checked to match.
+use super::db;
+use super::lmdb::{record_from_json, RecordKind, DB_DIR, OUTPUT_PREFIX, RECORD_KINDS, SQLITE_DIR};
+use crate::core::ser;
+use crate::keychain::Identifier;
+use crate::libwallet::{Error, ErrorKind, OutputStatus};
+use crate::serialization::{self, Serializable};
+use crate::store;
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory of the LMDB environment in the wallet's database directory
+const LMDB_DIR: &'static str = "lmdb";
+
+/// Name of the wallet's database in the LMDB environment
+const LMDB_DB_NAME: &'static str = "db";
+
+/// Storage backend of a wallet's store
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StoreBackend {
+	/// LMDB, as used by the wallet before SQLite
+	Lmdb,
+	/// SQLite
+	Sqlite,
+}
+
+impl StoreBackend {
+	fn dir_name(&self) -> &'static str {
+		match self {
+			StoreBackend::Lmdb => LMDB_DIR,
+			StoreBackend::Sqlite => SQLITE_DIR,
+		}
+	}
+
+	fn other(&self) -> StoreBackend {
+		match self {
+			StoreBackend::Lmdb => StoreBackend::Sqlite,
+			StoreBackend::Sqlite => StoreBackend::Lmdb,
+		}
+	}
+}
+
+impl fmt::Display for StoreBackend {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			StoreBackend::Lmdb => write!(f, "LMDB"),
+			StoreBackend::Sqlite => write!(f, "SQLite"),
+		}
+	}
+}
+
+/// Outcome of converting a wallet's store
+#[derive(Debug, Clone)]
+pub struct StoreMigration {
+	/// Backend the store was converted from
+	pub from: StoreBackend,
+	/// Backend the store was converted to
+	pub to: StoreBackend,
+	/// Number of records converted
+	pub records: usize,
+	/// Number of outputs among them
+	pub outputs: usize,
+	/// Where the store converted from was moved
+	pub backup: PathBuf,
+}
+
+/// JSON value of a record of the LMDB store, written with a length prefix
+struct LmdbJson(Vec<u8>);
+
+impl ser::Writeable for LmdbJson {
+	fn write<W: ser::Writer>(&self, writer: &mut W) -> Result<(), ser::Error> {
+		writer.write_bytes(&self.0)
+	}
+}
+
+impl ser::Readable for LmdbJson {
+	fn read(reader: &mut dyn ser::Reader) -> Result<LmdbJson, ser::Error> {
+		Ok(LmdbJson(reader.read_bytes_len_prefix()?))
+	}
+}
+
+struct Record {
+	key: Vec<u8>,
+	value: Serializable,
+}
+
+/// What has to match between a store and its conversion: the number of records
+/// under each key prefix and the balance of each account
+#[derive(Debug, PartialEq)]
+struct Tally {
+	records: BTreeMap<u8, usize>,
+	balances: BTreeMap<Identifier, u64>,
+}
+
+impl Tally {
+	fn of(records: &[Record]) -> Tally {
+		let mut tally = Tally {
+			records: BTreeMap::new(),
+			balances: BTreeMap::new(),
+		};
+		for r in records {
+			*tally.records.entry(r.key[0]).or_insert(0) += 1;
+			if let (OUTPUT_PREFIX, Serializable::OutputData(o)) = (r.key[0], &r.value) {
+				if o.status != OutputStatus::Spent && o.status != OutputStatus::Deleted {
+					*tally.balances.entry(o.root_key_id.clone()).or_insert(0) += o.value;
+				}
+			}
+		}
+		tally
+	}
+
+	fn outputs(&self) -> usize {
+		self.records.get(&OUTPUT_PREFIX).cloned().unwrap_or(0)
+	}
+}
+
+fn record(key: Vec<u8>, json: &str) -> Result<Record, Error> {
+	let value = record_from_json(key[0], json)
+		.map_err(|e| ErrorKind::Backend(format!("Invalid record {}: {}", key[0] as char, e)))?;
+	Ok(Record { key, value })
+}
+
+fn read_store(db_dir: &Path, backend: StoreBackend, dir_name: &str) -> Result<Vec<Record>, Error> {
+	let mut records = vec![];
+	match backend {
+		StoreBackend::Lmdb => {
+			let store = store::Store::new(
+				&db_dir.to_string_lossy(),
+				Some(dir_name),
+				Some(LMDB_DB_NAME),
+				None,
+			)?;
+			for (prefix, kind) in RECORD_KINDS.iter() {
+				match kind {
+					RecordKind::Json => {
+						for (key, value) in store.iter::<LmdbJson>(&[*prefix])? {
+							let json = String::from_utf8(value.0).map_err(|_| {
+								ErrorKind::Backend(format!("Invalid record {}", *prefix as char))
+							})?;
+							records.push(record(key, &json)?);
+						}
+					}
+					RecordKind::U32 => {
+						for (key, value) in store.iter::<u32>(&[*prefix])? {
+							let value = Serializable::Numeric(value.into());
+							records.push(Record { key, value });
+						}
+					}
+					RecordKind::U64 => {
+						for (key, value) in store.iter::<u64>(&[*prefix])? {
+							let value = Serializable::Numeric(value);
+							records.push(Record { key, value });
+						}
+					}
+				}
+			}
+		}
+		StoreBackend::Sqlite => {
+			let store = db::Store::open_read_only(db_dir.join(dir_name))?;
+			for (key, json) in store.records()? {
+				if !RECORD_KINDS.iter().any(|(p, _)| key.first() == Some(p)) {
+					return Err(ErrorKind::Backend(format!("Unknown record key {:?}", key)).into());
+				}
+				records.push(record(key, &json)?);
+			}
+		}
+	}
+	Ok(records)
+}
+
+fn write_store(
+	db_dir: &Path,
+	backend: StoreBackend,
+	dir_name: &str,
+	records: Vec<Record>,
+) -> Result<(), Error> {
+	match backend {
+		StoreBackend::Lmdb => {
+			let store = store::Store::new(
+				&db_dir.to_string_lossy(),
+				Some(dir_name),
+				Some(LMDB_DB_NAME),
+				None,
+			)?;
+			let batch = store.batch()?;
+			for r in records {
+				let kind = RECORD_KINDS.iter().find(|(p, _)| *p == r.key[0]);
+				match (kind, r.value) {
+					(Some((_, RecordKind::U32)), Serializable::Numeric(n)) => {
+						batch.put_ser(&r.key, &(n as u32))?
+					}
+					(Some((_, RecordKind::U64)), Serializable::Numeric(n)) => {
+						batch.put_ser(&r.key, &n)?
+					}
+					(_, value) => {
+						let json = serialization::serialize(&value).map_err(|e| {
+							ErrorKind::Backend(format!("Unable to serialize record: {}", e))
+						})?;
+						batch.put_ser(&r.key, &LmdbJson(json.into_bytes()))?
+					}
+				}
+			}
+			batch.commit()?;
+		}
+		StoreBackend::Sqlite => {
+			let path = db_dir.join(dir_name);
+			fs::create_dir_all(&path)?;
+			let store = db::Store::new(path)?;
+			let batch = store.batch();
+			for r in records {
+				batch.put(&r.key, r.value)?;
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Convert the store of the wallet in `data_file_dir` to the given backend. The wallet
+/// mustn't be open while it's converted. The converted store is built aside and read
+/// back, and replaces the wallet's store only if it holds as many records of each kind
+/// and the same balance for each account. The store converted from is kept, moved next
+/// to the new one.
+pub fn migrate_store(data_file_dir: &str, to: StoreBackend) -> Result<StoreMigration, Error> {
+	let from = to.other();
+	let db_dir = Path::new(data_file_dir).join(DB_DIR);
+	let source = db_dir.join(from.dir_name());
+	let target = db_dir.join(to.dir_name());
+	if !source.exists() {
+		let msg = format!("No {} store found at {}", from, source.display());
+		return Err(ErrorKind::Backend(msg).into());
+	}
+	if target.exists() {
+		let msg = format!(
+			"A {} store already exists at {}, move it away first",
+			to,
+			target.display()
+		);
+		return Err(ErrorKind::Backend(msg).into());
+	}
+
+	// left over by an interrupted conversion
+	let staging_name = format!("{}.migrating", to.dir_name());
+	let staging = db_dir.join(&staging_name);
+	if staging.exists() {
+		fs::remove_dir_all(&staging)?;
+	}
+
+	let records = read_store(&db_dir, from, from.dir_name())?;
+	let expected = Tally::of(&records);
+	let num_records = records.len();
+	write_store(&db_dir, to, &staging_name, records)?;
+	let converted = Tally::of(&read_store(&db_dir, to, &staging_name)?);
+	if converted != expected {
+		let _ = fs::remove_dir_all(&staging);
+		let msg = format!(
+			"The converted store doesn't match the wallet's, left unchanged: \
+			 expected {:?}, found {:?}",
+			expected, converted
+		);
+		return Err(ErrorKind::Backend(msg).into());
+	}
+
+	let mut backup = db_dir.join(format!("{}.bak", from.dir_name()));
+	if backup.exists() {
+		backup = db_dir.join(format!(
+			"{}.{}.bak",
+			from.dir_name(),
+			Utc::now().timestamp()
+		));
+	}
+	fs::rename(&source, &backup)?;
+	fs::rename(&staging, &target)?;
+	info!(
+		"Converted the wallet store from {} to {}, {} records",
+		from, to, num_records
+	);
+	Ok(StoreMigration {
+		from,
+		to,
+		records: num_records,
+		outputs: expected.outputs(),
+		backup,
+	})
+}
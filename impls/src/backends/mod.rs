@@ -14,5 +14,10 @@
 
 mod db;
 mod lmdb;
+mod migrate;
+mod snapshot;
 
+pub(crate) use self::db::{Store, SQLITE_FILENAME};
 pub use self::lmdb::{wallet_db_exists, LMDBBackend};
+pub use self::migrate::{migrate_store, StoreBackend, StoreMigration};
+pub use self::snapshot::open_snapshot_store;
@@ -0,0 +1,90 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only store of a snapshot of another wallet, loaded from the JSON lines
+//! written by `db export`
+
+use std::fs;
+use std::path::Path;
+
+use super::lmdb::LMDBBackend;
+use crate::keychain::Keychain;
+use crate::libwallet::{
+	AcctPathMapping, Error, ErrorKind, NodeClient, OutputData, TxLogEntry, WalletBackend,
+};
+
+/// Record of a snapshot, with its type in the "type" field
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SnapshotRecord {
+	Account(AcctPathMapping),
+	Output(OutputData),
+	Tx(TxLogEntry),
+}
+
+/// Records of a snapshot file, skipping the records of types it doesn't know of
+fn read_snapshot(snapshot_file: &str) -> Result<Vec<SnapshotRecord>, Error> {
+	let contents = fs::read_to_string(snapshot_file)
+		.map_err(|e| ErrorKind::GenericError(format!("Unable to read {}: {}", snapshot_file, e)))?;
+	let mut records = vec![];
+	for (i, line) in contents.lines().enumerate() {
+		if line.trim().is_empty() {
+			continue;
+		}
+		let parse_error = |e: serde_json::Error| {
+			ErrorKind::GenericError(format!("{} line {}: {}", snapshot_file, i + 1, e))
+		};
+		let record: serde_json::Value = serde_json::from_str(line).map_err(parse_error)?;
+		match record.get("type").and_then(|t| t.as_str()) {
+			Some("account") | Some("output") | Some("tx") => {
+				records.push(serde_json::from_value(record).map_err(parse_error)?)
+			}
+			_ => {}
+		}
+	}
+	Ok(records)
+}
+
+/// Load the snapshot into a new store in the given directory, replacing any store
+/// left there, and open it for reading only
+pub fn open_snapshot_store<'ck, C, K>(
+	snapshot_file: &str,
+	data_file_dir: &str,
+	n_client: C,
+) -> Result<LMDBBackend<'ck, C, K>, Error>
+where
+	C: NodeClient + 'ck,
+	K: Keychain + 'ck,
+{
+	let records = read_snapshot(snapshot_file)?;
+	if Path::new(data_file_dir).exists() {
+		fs::remove_dir_all(data_file_dir)?;
+	}
+	{
+		let mut store: LMDBBackend<'ck, C, K> = LMDBBackend::new(data_file_dir, n_client.clone())?;
+		let mut batch = store.batch_no_mask()?;
+		for r in records {
+			match r {
+				SnapshotRecord::Account(a) => batch.save_acct_path(a)?,
+				SnapshotRecord::Output(o) => batch.save(o)?,
+				SnapshotRecord::Tx(t) => {
+					let parent_key_id = t.parent_key_id.clone();
+					batch.save_tx_log_entry(t, &parent_key_id)?
+				}
+			}
+		}
+		batch.commit()?;
+	}
+	LMDBBackend::new_read_only(data_file_dir, n_client)
+}
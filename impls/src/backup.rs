@@ -0,0 +1,659 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backups of the wallet's files, encrypted before being written to a backup target:
+//! a local directory, an S3-compatible bucket or a WebDAV collection
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, HOST, USER_AGENT};
+use hyper::{Body, Request};
+use rand::{thread_rng, Rng};
+use ring::{aead, digest, hmac, pbkdf2};
+
+use crate::backends::{Store, SQLITE_FILENAME};
+use crate::client_utils::Client;
+use crate::config::BackupConfig;
+use crate::libwallet::{Error, ErrorKind};
+use crate::lifecycle::SEED_FILE;
+use crate::util::{from_hex, to_base64, to_hex};
+
+/// Version of the format of the backups written
+pub const BACKUP_VERSION: u16 = 1;
+
+/// Iterations of the derivation of the encryption key, the backups being stored
+/// out of the wallet's host
+const BACKUP_KEY_ITERATIONS: u32 = 100_000;
+
+/// Files of the wallet's data directory left out of backups
+const SKIPPED_FILES: [&str; 1] = ["lock.mdb"];
+
+fn backup_error(msg: String) -> Error {
+	ErrorKind::Backup(msg).into()
+}
+
+/// Where backups are written to and read back from
+pub trait BackupTarget: Send + Sync {
+	/// Description of the target, for messages
+	fn describe(&self) -> String;
+
+	/// Write a backup under the given name
+	fn put(&self, name: &str, contents: &str) -> Result<(), Error>;
+
+	/// Read back the backup of the given name
+	fn get(&self, name: &str) -> Result<String, Error>;
+}
+
+/// Writes backups to a local directory, e.g. one synced to another host
+pub struct DirBackupTarget {
+	dir: PathBuf,
+}
+
+impl DirBackupTarget {
+	/// Create a target writing to the given directory
+	pub fn new(dir: &str) -> DirBackupTarget {
+		DirBackupTarget {
+			dir: PathBuf::from(dir),
+		}
+	}
+}
+
+impl BackupTarget for DirBackupTarget {
+	fn describe(&self) -> String {
+		format!("directory {}", self.dir.display())
+	}
+
+	fn put(&self, name: &str, contents: &str) -> Result<(), Error> {
+		fs::create_dir_all(&self.dir)
+			.and_then(|_| fs::write(self.dir.join(name), contents))
+			.map_err(|e| backup_error(format!("Writing {} to {}: {}", name, self.describe(), e)))
+	}
+
+	fn get(&self, name: &str) -> Result<String, Error> {
+		fs::read_to_string(self.dir.join(name))
+			.map_err(|e| backup_error(format!("Reading {} from {}: {}", name, self.describe(), e)))
+	}
+}
+
+/// Writes backups to a bucket of an S3-compatible service, with requests signed with
+/// AWS signature version 4
+pub struct S3BackupTarget {
+	endpoint: String,
+	bucket: String,
+	region: String,
+	access_key: String,
+	secret_key: String,
+}
+
+impl S3BackupTarget {
+	/// Create a target writing to the bucket of the service at the endpoint
+	pub fn new(
+		endpoint: &str,
+		bucket: &str,
+		region: &str,
+		access_key: &str,
+		secret_key: &str,
+	) -> S3BackupTarget {
+		S3BackupTarget {
+			endpoint: endpoint.trim_end_matches('/').to_owned(),
+			bucket: bucket.to_owned(),
+			region: region.to_owned(),
+			access_key: access_key.to_owned(),
+			secret_key: secret_key.to_owned(),
+		}
+	}
+
+	fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+		let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+		hmac::sign(&key, data.as_bytes()).as_ref().to_vec()
+	}
+
+	/// Request of the object of the given name, signed
+	fn request(&self, method: &str, name: &str, body: &str) -> Result<Request<Body>, Error> {
+		let url = format!("{}/{}/{}", self.endpoint, self.bucket, name);
+		let host = match url
+			.parse::<hyper::Uri>()
+			.ok()
+			.and_then(|u| u.authority_part().cloned())
+		{
+			Some(a) => a.to_string(),
+			None => {
+				return Err(backup_error(format!(
+					"Invalid S3 endpoint {}",
+					self.endpoint
+				)))
+			}
+		};
+		let now = Utc::now();
+		let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+		let date = now.format("%Y%m%d").to_string();
+		let payload_hash = to_hex(
+			digest::digest(&digest::SHA256, body.as_bytes())
+				.as_ref()
+				.to_vec(),
+		);
+
+		let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+		let canonical_request = format!(
+			"{}\n/{}/{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+			method, self.bucket, name, host, payload_hash, amz_date, signed_headers, payload_hash
+		);
+		let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+		let string_to_sign = format!(
+			"AWS4-HMAC-SHA256\n{}\n{}\n{}",
+			amz_date,
+			scope,
+			to_hex(
+				digest::digest(&digest::SHA256, canonical_request.as_bytes())
+					.as_ref()
+					.to_vec()
+			)
+		);
+		let mut key = S3BackupTarget::hmac(format!("AWS4{}", self.secret_key).as_bytes(), &date);
+		for part in &[self.region.as_str(), "s3", "aws4_request"] {
+			key = S3BackupTarget::hmac(&key, part);
+		}
+		let signature = to_hex(S3BackupTarget::hmac(&key, &string_to_sign));
+		let authorization = format!(
+			"AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+			self.access_key, scope, signed_headers, signature
+		);
+
+		Request::builder()
+			.method(method)
+			.uri(url.as_str())
+			.header(HOST, host.as_str())
+			.header(USER_AGENT, "epic-client")
+			.header(CONTENT_TYPE, "application/json")
+			.header("x-amz-content-sha256", payload_hash.as_str())
+			.header("x-amz-date", amz_date.as_str())
+			.header(AUTHORIZATION, authorization)
+			.body(body.to_owned().into())
+			.map_err(|e| backup_error(format!("Bad request {} {}: {}", method, url, e)))
+	}
+}
+
+impl BackupTarget for S3BackupTarget {
+	fn describe(&self) -> String {
+		format!("S3 bucket {} at {}", self.bucket, self.endpoint)
+	}
+
+	fn put(&self, name: &str, contents: &str) -> Result<(), Error> {
+		let req = self.request("PUT", name, contents)?;
+		Client::new()
+			.send_request(req)
+			.map_err(|e| backup_error(format!("Writing {} to {}: {}", name, self.describe(), e)))?;
+		Ok(())
+	}
+
+	fn get(&self, name: &str) -> Result<String, Error> {
+		let req = self.request("GET", name, "")?;
+		Client::new()
+			.send_request(req)
+			.map_err(|e| backup_error(format!("Reading {} from {}: {}", name, self.describe(), e)))
+	}
+}
+
+/// Writes backups to a WebDAV collection, e.g. of a Nextcloud instance
+pub struct WebDavBackupTarget {
+	url: String,
+	credentials: Option<(String, String)>,
+}
+
+impl WebDavBackupTarget {
+	/// Create a target writing to the collection at the URL, with basic authentication
+	/// if credentials are given
+	pub fn new(url: &str, credentials: Option<(String, String)>) -> WebDavBackupTarget {
+		WebDavBackupTarget {
+			url: url.trim_end_matches('/').to_owned(),
+			credentials,
+		}
+	}
+
+	fn request(&self, method: &str, name: &str, body: &str) -> Result<Request<Body>, Error> {
+		let url = format!("{}/{}", self.url, name);
+		let mut builder = Request::builder();
+		if let Some((ref user, ref password)) = self.credentials {
+			let basic_auth = format!("Basic {}", to_base64(&format!("{}:{}", user, password)));
+			builder.header(AUTHORIZATION, basic_auth);
+		}
+		builder
+			.method(method)
+			.uri(url.as_str())
+			.header(USER_AGENT, "epic-client")
+			.header(CONTENT_TYPE, "application/json")
+			.body(body.to_owned().into())
+			.map_err(|e| backup_error(format!("Bad request {} {}: {}", method, url, e)))
+	}
+}
+
+impl BackupTarget for WebDavBackupTarget {
+	fn describe(&self) -> String {
+		format!("WebDAV collection {}", self.url)
+	}
+
+	fn put(&self, name: &str, contents: &str) -> Result<(), Error> {
+		let req = self.request("PUT", name, contents)?;
+		Client::new()
+			.send_request(req)
+			.map_err(|e| backup_error(format!("Writing {} to {}: {}", name, self.describe(), e)))?;
+		Ok(())
+	}
+
+	fn get(&self, name: &str) -> Result<String, Error> {
+		let req = self.request("GET", name, "")?;
+		Client::new()
+			.send_request(req)
+			.map_err(|e| backup_error(format!("Reading {} from {}: {}", name, self.describe(), e)))
+	}
+}
+
+/// Build the backup target described by the configuration
+pub fn backup_target_from_config(config: &BackupConfig) -> Result<Box<dyn BackupTarget>, Error> {
+	let required = |value: &Option<String>, name: &str| {
+		value.clone().ok_or_else(|| {
+			backup_error(format!(
+				"'{}' is required for a '{}' backup target",
+				name, config.target
+			))
+		})
+	};
+	match config.target.as_str() {
+		"dir" => Ok(Box::new(DirBackupTarget::new(&required(
+			&config.dir,
+			"dir",
+		)?))),
+		"s3" => Ok(Box::new(S3BackupTarget::new(
+			&required(&config.url, "url")?,
+			&required(&config.bucket, "bucket")?,
+			config.region.as_ref().map_or("us-east-1", |r| r.as_str()),
+			&required(&config.access_key, "access_key")?,
+			&required(&config.secret_key, "secret_key")?,
+		))),
+		"webdav" => {
+			let credentials = match config.username {
+				Some(ref u) => Some((u.clone(), required(&config.password, "password")?)),
+				None => None,
+			};
+			Ok(Box::new(WebDavBackupTarget::new(
+				&required(&config.url, "url")?,
+				credentials,
+			)))
+		}
+		t => Err(backup_error(format!(
+			"Unknown backup target '{}', expected 'dir', 's3' or 'webdav'",
+			t
+		))),
+	}
+}
+
+/// File of the wallet's data directory
+#[derive(Serialize, Deserialize)]
+struct BackupFile {
+	/// Path relative to the data directory
+	path: String,
+	/// Contents, hex encoded
+	data: String,
+}
+
+/// Wallet's files, encrypted with a key derived from the wallet password, plus the
+/// passphrase if one was set
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EncryptedBackup {
+	/// Version of the backup format
+	pub version: u16,
+	/// When the backup was made
+	pub created: DateTime<Utc>,
+	/// Whether a passphrase is needed along with the wallet password
+	pub passphrase: bool,
+	salt: String,
+	nonce: String,
+	encrypted_files: String,
+}
+
+fn backup_key(salt: &[u8], password: &str, passphrase: Option<&str>) -> [u8; 32] {
+	let mut secret = password.as_bytes().to_vec();
+	if let Some(p) = passphrase {
+		secret.push(0);
+		secret.extend_from_slice(p.as_bytes());
+	}
+	let mut key = [0; 32];
+	pbkdf2::derive(
+		pbkdf2::PBKDF2_HMAC_SHA512,
+		NonZeroU32::new(BACKUP_KEY_ITERATIONS).unwrap(),
+		salt,
+		&secret,
+		&mut key,
+	);
+	key
+}
+
+/// Consistent copy of the SQLite database in the directory, rather than the live file
+/// the wallet may be writing to. The copy is written next to the database, so the
+/// wallet's data doesn't leave its directory unencrypted, and removed once read.
+fn snapshot_db(dir: &Path) -> Result<Vec<u8>, Error> {
+	let dest = dir.join(format!("{}.backup", SQLITE_FILENAME));
+	let snapshot_error = |e: String| backup_error(format!("Snapshot of {}: {}", dir.display(), e));
+	if dest.exists() {
+		fs::remove_file(&dest).map_err(|e| snapshot_error(e.to_string()))?;
+	}
+	let store =
+		Store::open_read_only(dir.to_path_buf()).map_err(|e| snapshot_error(e.to_string()))?;
+	store
+		.snapshot(&dest)
+		.map_err(|e| snapshot_error(e.to_string()))?;
+	let data = fs::read(&dest).map_err(|e| snapshot_error(e.to_string()));
+	let _ = fs::remove_file(&dest);
+	data
+}
+
+/// Files under a directory, relative to the data directory
+fn collect_files(data_dir: &Path, dir: &Path, files: &mut Vec<BackupFile>) -> Result<(), Error> {
+	let entries =
+		fs::read_dir(dir).map_err(|e| backup_error(format!("Reading {}: {}", dir.display(), e)))?;
+	for entry in entries {
+		let path = entry
+			.map_err(|e| backup_error(format!("Reading {}: {}", dir.display(), e)))?
+			.path();
+		if path.is_dir() {
+			collect_files(data_dir, &path, files)?;
+			continue;
+		}
+		let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+		if SKIPPED_FILES.contains(&name) {
+			continue;
+		}
+		// the database's journal is part of its snapshot
+		if name.starts_with(SQLITE_FILENAME) && name != SQLITE_FILENAME {
+			continue;
+		}
+		let data = match name == SQLITE_FILENAME {
+			true => snapshot_db(dir)?,
+			false => fs::read(&path)
+				.map_err(|e| backup_error(format!("Reading {}: {}", path.display(), e)))?,
+		};
+		let relative = path.strip_prefix(data_dir).unwrap();
+		files.push(BackupFile {
+			path: relative.to_string_lossy().replace('\\', "/"),
+			data: to_hex(data),
+		});
+	}
+	Ok(())
+}
+
+impl EncryptedBackup {
+	/// Back up the files of a wallet's data directory
+	pub fn from_dir(
+		data_dir: &str,
+		password: &str,
+		passphrase: Option<&str>,
+	) -> Result<EncryptedBackup, Error> {
+		let data_dir = Path::new(data_dir);
+		if !data_dir.join(SEED_FILE).exists() {
+			return Err(backup_error(format!(
+				"No wallet in {} to back up",
+				data_dir.display()
+			)));
+		}
+		let mut files = vec![];
+		collect_files(data_dir, data_dir, &mut files)?;
+		let mut enc_bytes = serde_json::to_vec(&files)
+			.map_err(|e| backup_error(format!("Serializing backup: {}", e)))?;
+
+		let salt: [u8; 16] = thread_rng().gen();
+		let nonce: [u8; 12] = thread_rng().gen();
+		let key = backup_key(&salt, password, passphrase);
+		let unbound_key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key).unwrap();
+		let sealing_key = aead::LessSafeKey::new(unbound_key);
+		sealing_key
+			.seal_in_place_append_tag(
+				aead::Nonce::assume_unique_for_key(nonce),
+				aead::Aad::empty(),
+				&mut enc_bytes,
+			)
+			.map_err(|_| ErrorKind::Encryption)?;
+
+		Ok(EncryptedBackup {
+			version: BACKUP_VERSION,
+			created: Utc::now(),
+			passphrase: passphrase.is_some(),
+			salt: to_hex(salt.to_vec()),
+			nonce: to_hex(nonce.to_vec()),
+			encrypted_files: to_hex(enc_bytes),
+		})
+	}
+
+	/// Decrypt the backup and write its files to a data directory without a wallet,
+	/// returning the number of files restored
+	pub fn restore(
+		&self,
+		data_dir: &str,
+		password: &str,
+		passphrase: Option<&str>,
+	) -> Result<usize, Error> {
+		if self.version > BACKUP_VERSION {
+			return Err(backup_error(format!(
+				"Backup version {} isn't supported, upgrade the wallet",
+				self.version
+			)));
+		}
+		if self.passphrase && passphrase.is_none() {
+			return Err(backup_error(
+				"The backup is encrypted with a passphrase".to_owned(),
+			));
+		}
+		let data_dir = Path::new(data_dir);
+		if data_dir.join(SEED_FILE).exists() {
+			return Err(backup_error(format!(
+				"A wallet already exists in {}",
+				data_dir.display()
+			)));
+		}
+
+		let decode = |s: &str| from_hex(s.to_owned()).map_err(|_| ErrorKind::Decryption);
+		let salt = decode(&self.salt)?;
+		let nonce = decode(&self.nonce)?;
+		let mut enc_bytes = decode(&self.encrypted_files)?;
+		if nonce.len() != 12 {
+			return Err(ErrorKind::Decryption.into());
+		}
+		let mut n = [0u8; 12];
+		n.copy_from_slice(&nonce);
+		let key = backup_key(&salt, password, passphrase.filter(|_| self.passphrase));
+		let unbound_key = aead::UnboundKey::new(&aead::CHACHA20_POLY1305, &key).unwrap();
+		let opening_key = aead::LessSafeKey::new(unbound_key);
+		let files_bytes = opening_key
+			.open_in_place(
+				aead::Nonce::assume_unique_for_key(n),
+				aead::Aad::empty(),
+				&mut enc_bytes,
+			)
+			.map_err(|_| {
+				backup_error(
+					"Unable to decrypt the backup, wrong password or passphrase".to_owned(),
+				)
+			})?;
+		let files: Vec<BackupFile> = serde_json::from_slice(files_bytes)
+			.map_err(|e| backup_error(format!("Invalid backup contents: {}", e)))?;
+
+		for f in &files {
+			let relative = Path::new(&f.path);
+			if relative.is_absolute() || relative.components().any(|c| c.as_os_str() == "..") {
+				return Err(backup_error(format!("Invalid path {} in backup", f.path)));
+			}
+			let path = data_dir.join(relative);
+			let data = decode(&f.data)?;
+			if let Some(parent) = path.parent() {
+				fs::create_dir_all(parent)
+					.map_err(|e| backup_error(format!("Creating {}: {}", parent.display(), e)))?;
+			}
+			fs::write(&path, data)
+				.map_err(|e| backup_error(format!("Writing {}: {}", path.display(), e)))?;
+		}
+		Ok(files.len())
+	}
+}
+
+/// Back up the wallet's data directory to the target, returning the name of the backup.
+/// The wallet's database is backed up from a snapshot, consistent even while it's written.
+pub fn backup_wallet(
+	target: &dyn BackupTarget,
+	data_dir: &str,
+	password: &str,
+	passphrase: Option<&str>,
+) -> Result<String, Error> {
+	let backup = EncryptedBackup::from_dir(data_dir, password, passphrase)?;
+	let name = format!(
+		"epic-wallet-{}.backup",
+		backup.created.format("%Y%m%d-%H%M%S")
+	);
+	let contents = serde_json::to_string(&backup)
+		.map_err(|e| backup_error(format!("Serializing backup: {}", e)))?;
+	target.put(&name, &contents)?;
+	Ok(name)
+}
+
+/// Read back a backup from the target and restore it to a data directory without a
+/// wallet, returning the number of files restored
+pub fn restore_wallet(
+	target: &dyn BackupTarget,
+	name: &str,
+	data_dir: &str,
+	password: &str,
+	passphrase: Option<&str>,
+) -> Result<usize, Error> {
+	let contents = target.get(name)?;
+	let backup: EncryptedBackup = serde_json::from_str(&contents)
+		.map_err(|e| backup_error(format!("Invalid backup {}: {}", name, e)))?;
+	backup.restore(data_dir, password, passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn wallet_dir(name: &str) -> PathBuf {
+		let dir = std::env::temp_dir().join(format!("epic_backup_{}", name));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(dir.join("db").join("lmdb")).unwrap();
+		fs::write(dir.join(SEED_FILE), "seed").unwrap();
+		fs::write(dir.join("db").join("lmdb").join("data.mdb"), [0u8, 1, 2]).unwrap();
+		fs::write(dir.join("db").join("lmdb").join("lock.mdb"), "lock").unwrap();
+		dir
+	}
+
+	#[test]
+	fn backup_restore() {
+		let dir = wallet_dir("source");
+		let target_dir = std::env::temp_dir().join("epic_backup_target");
+		let target = DirBackupTarget::new(target_dir.to_str().unwrap());
+		let name = backup_wallet(&target, dir.to_str().unwrap(), "pass", Some("phrase")).unwrap();
+
+		let dest = std::env::temp_dir().join("epic_backup_dest");
+		let _ = fs::remove_dir_all(&dest);
+		let dest = dest.to_str().unwrap();
+		// the passphrase is needed along with the password
+		assert!(restore_wallet(&target, &name, dest, "pass", None).is_err());
+		assert!(restore_wallet(&target, &name, dest, "pass", Some("wrong")).is_err());
+		assert!(restore_wallet(&target, &name, dest, "wrong", Some("phrase")).is_err());
+		assert_eq!(
+			restore_wallet(&target, &name, dest, "pass", Some("phrase")).unwrap(),
+			2
+		);
+		let dest = Path::new(dest);
+		assert_eq!(fs::read(dest.join(SEED_FILE)).unwrap(), b"seed");
+		assert_eq!(
+			fs::read(dest.join("db").join("lmdb").join("data.mdb")).unwrap(),
+			vec![0u8, 1, 2]
+		);
+		assert!(!dest.join("db").join("lmdb").join("lock.mdb").exists());
+
+		// an existing wallet isn't overwritten
+		assert!(restore_wallet(
+			&target,
+			&name,
+			dest.to_str().unwrap(),
+			"pass",
+			Some("phrase")
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn backup_sqlite_snapshot() {
+		let dir = wallet_dir("sqlite");
+		let db_dir = dir.join("db").join("sqlite");
+		fs::create_dir_all(&db_dir).unwrap();
+		let db = sqlite::open(db_dir.join(SQLITE_FILENAME)).unwrap();
+		db.execute("CREATE TABLE data (value TEXT); INSERT INTO data VALUES ('kept');")
+			.unwrap();
+		fs::write(
+			db_dir.join(format!("{}-journal", SQLITE_FILENAME)),
+			"journal",
+		)
+		.unwrap();
+		let target_dir = std::env::temp_dir().join("epic_backup_sqlite_target");
+		let target = DirBackupTarget::new(target_dir.to_str().unwrap());
+		let name = backup_wallet(&target, dir.to_str().unwrap(), "pass", None).unwrap();
+		// the snapshot is taken while the database is open, and not left behind
+		assert!(!db_dir.join(format!("{}.backup", SQLITE_FILENAME)).exists());
+
+		let dest = std::env::temp_dir().join("epic_backup_sqlite_dest");
+		let _ = fs::remove_dir_all(&dest);
+		assert_eq!(
+			restore_wallet(&target, &name, dest.to_str().unwrap(), "pass", None).unwrap(),
+			3
+		);
+		let dest_db = dest.join("db").join("sqlite");
+		assert!(!dest_db
+			.join(format!("{}-journal", SQLITE_FILENAME))
+			.exists());
+		let restored = sqlite::open(dest_db.join(SQLITE_FILENAME)).unwrap();
+		let mut values = vec![];
+		restored
+			.iterate("SELECT value FROM data", |row| {
+				values.push(row[0].1.unwrap().to_owned());
+				true
+			})
+			.unwrap();
+		assert_eq!(values, vec!["kept".to_owned()]);
+	}
+
+	#[test]
+	fn backup_target_config() {
+		let mut config = BackupConfig {
+			target: "s3".to_owned(),
+			dir: None,
+			url: Some("https://s3.example.com".to_owned()),
+			bucket: Some("wallets".to_owned()),
+			region: None,
+			access_key: Some("key".to_owned()),
+			secret_key: None,
+			username: None,
+			password: None,
+			passphrase: None,
+			interval_secs: None,
+		};
+		assert!(backup_target_from_config(&config).is_err());
+		config.secret_key = Some("secret".to_owned());
+		assert_eq!(
+			backup_target_from_config(&config).unwrap().describe(),
+			"S3 bucket wallets at https://s3.example.com"
+		);
+		config.target = "ftp".to_owned();
+		assert!(backup_target_from_config(&config).is_err());
+	}
+}
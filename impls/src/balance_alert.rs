@@ -0,0 +1,154 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Balance alert hooks, delivering the alerts raised by the updater
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+
+use crate::client_utils::Client;
+use crate::config::BalanceAlertsConfig;
+use crate::libwallet::api_impl::owner_updater::BalanceAlerts;
+use crate::libwallet::{
+	BalanceAlert, BalanceAlertHook, BalanceAlertPolicy, BalanceThreshold, Error, ErrorKind,
+};
+
+/// Default minimum number of confirmations of outputs counted as spendable
+const DEFAULT_MINIMUM_CONFIRMATIONS: u64 = 10;
+
+/// Posts the alerts as JSON to a URL
+pub struct WebhookBalanceAlertHook {
+	url: String,
+}
+
+impl WebhookBalanceAlertHook {
+	/// Create a hook posting to the given URL
+	pub fn new(url: &str) -> WebhookBalanceAlertHook {
+		WebhookBalanceAlertHook {
+			url: url.to_owned(),
+		}
+	}
+}
+
+impl BalanceAlertHook for WebhookBalanceAlertHook {
+	fn alert(&self, alert: &BalanceAlert) -> Result<(), Error> {
+		Client::new()
+			.post_no_ret(&self.url, None, alert)
+			.map_err(|e| {
+				ErrorKind::GenericError(format!("Posting balance alert to {}: {}", self.url, e))
+			})?;
+		Ok(())
+	}
+}
+
+/// Runs an external command with the alert as JSON on its standard input
+pub struct CommandBalanceAlertHook {
+	command: String,
+}
+
+impl CommandBalanceAlertHook {
+	/// Create a hook running the given command line through the system shell
+	pub fn new(command: &str) -> CommandBalanceAlertHook {
+		CommandBalanceAlertHook {
+			command: command.to_owned(),
+		}
+	}
+
+	fn shell_command(&self) -> Command {
+		if cfg!(windows) {
+			let mut c = Command::new("cmd");
+			c.arg("/C").arg(&self.command);
+			c
+		} else {
+			let mut c = Command::new("sh");
+			c.arg("-c").arg(&self.command);
+			c
+		}
+	}
+}
+
+impl BalanceAlertHook for CommandBalanceAlertHook {
+	fn alert(&self, alert: &BalanceAlert) -> Result<(), Error> {
+		let json = serde_json::to_string(alert).map_err(|e| {
+			ErrorKind::GenericError(format!("Unable to serialize balance alert: {}", e))
+		})?;
+
+		let mut child = self
+			.shell_command()
+			.stdin(Stdio::piped())
+			.stdout(Stdio::null())
+			.spawn()
+			.map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to run balance alert command: {}", e))
+			})?;
+		if let Some(mut stdin) = child.stdin.take() {
+			let _ = stdin.write_all(json.as_bytes());
+		}
+		let status = child.wait()?;
+		if !status.success() {
+			return Err(ErrorKind::GenericError(format!(
+				"Balance alert command failed: {}",
+				status
+			))
+			.into());
+		}
+		Ok(())
+	}
+}
+
+/// All configured hooks, each notified of every alert
+struct BalanceAlertHooks(Vec<Box<dyn BalanceAlertHook>>);
+
+impl BalanceAlertHook for BalanceAlertHooks {
+	fn alert(&self, alert: &BalanceAlert) -> Result<(), Error> {
+		let mut res = Ok(());
+		for hook in self.0.iter() {
+			if let Err(e) = hook.alert(alert) {
+				res = Err(e);
+			}
+		}
+		res
+	}
+}
+
+/// Build the balance alerts described by the configuration, delivered to the
+/// webhook and command if set
+pub fn balance_alerts_from_config(config: &BalanceAlertsConfig) -> BalanceAlerts {
+	let policy = BalanceAlertPolicy {
+		thresholds: config
+			.thresholds
+			.iter()
+			.map(|t| BalanceThreshold {
+				account: t.account.clone(),
+				below: t.below,
+				above: t.above,
+			})
+			.collect(),
+		minimum_confirmations: config
+			.minimum_confirmations
+			.unwrap_or(DEFAULT_MINIMUM_CONFIRMATIONS),
+	};
+	let mut hooks: Vec<Box<dyn BalanceAlertHook>> = vec![];
+	if let Some(u) = config.webhook_url.as_ref() {
+		hooks.push(Box::new(WebhookBalanceAlertHook::new(u)));
+	}
+	if let Some(c) = config.command.as_ref() {
+		hooks.push(Box::new(CommandBalanceAlertHook::new(c)));
+	}
+	let hook: Option<Arc<dyn BalanceAlertHook>> = match hooks.is_empty() {
+		true => None,
+		false => Some(Arc::new(BalanceAlertHooks(hooks))),
+	};
+	BalanceAlerts::new(policy, hook)
+}
@@ -49,6 +49,13 @@ pub enum ErrorKind {
 	_NotFound,
 	#[fail(display = "Request error: {}", _0)]
 	RequestError(String),
+	#[fail(display = "Request error: {}", _0)]
+	ConnectionError(String),
+	#[fail(
+		display = "Request error: Wrong response code: {} with data {}",
+		_0, _1
+	)]
+	ResponseCode(u16, String),
 	#[fail(display = "ResponseError error: {}", _0)]
 	ResponseError(String),
 }
@@ -70,7 +77,7 @@ impl Display for Error {
 }
 
 impl Error {
-	pub fn _kind(&self) -> &ErrorKind {
+	pub fn kind(&self) -> &ErrorKind {
 		self.inner.get_context()
 	}
 }
@@ -91,11 +98,16 @@ impl From<Context<ErrorKind>> for Error {
 
 pub type ClientResponseFuture<T> = Box<dyn Future<Item = T, Error = Error> + Send>;
 
+/// Default timeout of connecting, reading and writing
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+
 pub struct Client {
 	/// Whether to use socks proxy
 	pub use_socks: bool,
 	/// Proxy url/port
 	pub socks_proxy_addr: Option<SocketAddr>,
+	/// Timeout of connecting, reading and writing
+	pub timeout: Duration,
 }
 
 impl Client {
@@ -104,6 +116,7 @@ impl Client {
 		Client {
 			use_socks: false,
 			socks_proxy_addr: None,
+			timeout: DEFAULT_TIMEOUT,
 		}
 	}
 
@@ -298,23 +311,22 @@ impl Client {
 			false => {
 				let https = hyper_rustls::HttpsConnector::new(1);
 				let mut connector = TimeoutConnector::new(https);
-				connector.set_connect_timeout(Some(Duration::from_secs(20)));
-				connector.set_read_timeout(Some(Duration::from_secs(20)));
-				connector.set_write_timeout(Some(Duration::from_secs(20)));
+				connector.set_connect_timeout(Some(self.timeout));
+				connector.set_read_timeout(Some(self.timeout));
+				connector.set_write_timeout(Some(self.timeout));
 				let client = hyper::Client::builder().build::<_, hyper::Body>(connector);
 				Box::new(
 					client
 						.request(req)
 						.map_err(|e| {
-							ErrorKind::RequestError(format!("Cannot make request: {}", e)).into()
+							ErrorKind::ConnectionError(format!("Cannot make request: {}", e)).into()
 						})
 						.and_then(|resp| {
 							if !resp.status().is_success() {
-								Either::A(err(ErrorKind::RequestError(format!(
-									"Wrong response code: {} with data {:?}",
-									resp.status(),
-									resp.body()
-								))
+								Either::A(err(ErrorKind::ResponseCode(
+									resp.status().as_u16(),
+									format!("{:?}", resp.body()),
+								)
 								.into()))
 							} else {
 								Either::B(
@@ -347,23 +359,22 @@ impl Client {
 				};
 				let socks_connector = Socksv5Connector::new(addr);
 				let mut connector = TimeoutConnector::new(socks_connector);
-				connector.set_connect_timeout(Some(Duration::from_secs(20)));
-				connector.set_read_timeout(Some(Duration::from_secs(20)));
-				connector.set_write_timeout(Some(Duration::from_secs(20)));
+				connector.set_connect_timeout(Some(self.timeout));
+				connector.set_read_timeout(Some(self.timeout));
+				connector.set_write_timeout(Some(self.timeout));
 				let client = hyper::Client::builder().build::<_, hyper::Body>(connector);
 				Box::new(
 					client
 						.request(req)
 						.map_err(|e| {
-							ErrorKind::RequestError(format!("Cannot make request: {}", e)).into()
+							ErrorKind::ConnectionError(format!("Cannot make request: {}", e)).into()
 						})
 						.and_then(|resp| {
 							if !resp.status().is_success() {
-								Either::A(err(ErrorKind::RequestError(format!(
-									"Wrong response code: {} with data {:?}",
-									resp.status(),
-									resp.body()
-								))
+								Either::A(err(ErrorKind::ResponseCode(
+									resp.status().as_u16(),
+									format!("{:?}", resp.body()),
+								)
 								.into()))
 							} else {
 								Either::B(
@@ -16,4 +16,4 @@ mod client;
 mod socksv5;
 
 pub use self::socksv5::Socksv5Connector;
-pub use client::{Client, Error as ClientError};
+pub use client::{Client, Error as ClientError, ErrorKind as ClientErrorKind};
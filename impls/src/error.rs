@@ -91,6 +91,10 @@ pub enum ErrorKind {
 	#[fail(display = "SQLite Error")]
 	SQLiteError(String),
 
+	/// Write attempted through a read-only store
+	#[fail(display = "The wallet store is open read-only and can't be written to")]
+	ReadOnlyStore,
+
 	/// Other
 	#[fail(display = "Generic error: {}", _0)]
 	GenericError(String),
@@ -103,6 +107,10 @@ pub enum ErrorKind {
 
 	#[fail(display = "Epicbox websocket terminated unexpectedly")]
 	EpicboxWebsocketAbnormalTermination,
+
+	/// Error of the node embedded in the wallet's process
+	#[fail(display = "Embedded node error: {}", _0)]
+	EmbeddedNode(String),
 }
 
 impl Fail for Error {
@@ -22,6 +22,8 @@ use blake2_rfc as blake2;
 extern crate serde_derive;
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate lazy_static;
 
 use epic_wallet_config as config;
 use epic_wallet_libwallet as libwallet;
@@ -34,25 +36,50 @@ use epic_wallet_util::epic_util as util;
 
 mod adapters;
 mod backends;
+mod backup;
+mod balance_alert;
 mod client_utils;
 pub mod epicbox;
 mod error;
 mod lifecycle;
+mod maturity;
 mod node_clients;
+mod price_feed;
+mod receive_hook;
 mod serialization;
 pub mod test_framework;
 pub mod tor;
+mod tx_hook;
 
 pub use crate::adapters::{
-	create_sender, Container, EmojiSlate, EpicboxBroker, EpicboxChannel, EpicboxController,
-	EpicboxListenChannel, EpicboxListener, EpicboxPublisher, EpicboxSubscriber, HttpSlateSender,
-	KeybaseAllChannels, KeybaseChannel, Listener, ListenerInterface, PathToSlate, SlateGetter,
-	SlatePutter, SlateReceiver, SlateSender, Subscriber,
+	create_sender, discover_lan_peer, sender_transport, Container, EmojiSlate, EpicboxBroker,
+	EpicboxChannel, EpicboxController, EpicboxListenChannel, EpicboxListener, EpicboxPublisher,
+	EpicboxSubscriber, HttpSlateSender, KeybaseAllChannels, KeybaseChannel, LanAdvertiser, LanPeer,
+	LanSlateSender, Listener, ListenerInterface, PathToSlate, SlateGetter, SlatePutter,
+	SlateReceiver, SlateSender, Subscriber, LAN_SERVICE_TYPE, SENDER_SIGNATURE_HEADER,
+};
+pub use crate::backends::{
+	migrate_store, wallet_db_exists, LMDBBackend, StoreBackend, StoreMigration,
+};
+pub use crate::backup::{
+	backup_target_from_config, backup_wallet, restore_wallet, BackupTarget, DirBackupTarget,
+	EncryptedBackup, S3BackupTarget, WebDavBackupTarget,
+};
+pub use crate::balance_alert::{
+	balance_alerts_from_config, CommandBalanceAlertHook, WebhookBalanceAlertHook,
 };
-pub use crate::backends::{wallet_db_exists, LMDBBackend};
 pub use crate::error::{Error, ErrorKind};
 pub use crate::lifecycle::DefaultLCProvider;
-pub use crate::node_clients::HTTPNodeClient;
+pub use crate::maturity::{maturity_watch_from_config, WebhookMaturityHook};
+#[cfg(feature = "embedded_node")]
+pub use crate::node_clients::{EmbeddedNode, LocalNode, LocalNodeClient};
+pub use crate::node_clients::{
+	HTTPNodeClient, DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS,
+};
+pub use crate::price_feed::{price_feed_from_config, HttpPriceFeed, StaticPriceFeed};
+pub use crate::receive_hook::{receive_hook_from_config, CommandReceiveHook, PolicyReceiveHook};
+pub use crate::tor::supervisor::{listener_statuses, ListenerStatus, TorSupervisor};
+pub use crate::tx_hook::{tx_hook_from_config, CommandTxHook};
 
 use crate::keychain::{ExtKeychain, Keychain};
 
@@ -14,24 +14,36 @@
 
 //! Default wallet lifecycle provider
 
+use crate::backends::open_snapshot_store;
 use crate::config::{
 	config, EpicboxConfig, GlobalWalletConfig, GlobalWalletConfigMembers, TorConfig, WalletConfig,
 	EPIC_WALLET_DIR,
 };
 use crate::core::global;
-use crate::keychain::Keychain;
+use crate::keychain::{Identifier, Keychain};
 use crate::libwallet::{
-	Error, ErrorKind, NodeClient, WalletBackend, WalletInitStatus, WalletLCProvider,
+	Error, ErrorKind, MnemonicLanguage, NodeClient, ViewKey, WalletBackend, WalletInitStatus,
+	WalletLCProvider,
 };
 use crate::lifecycle::seed::WalletSeed;
 use crate::util::secp::key::SecretKey;
 use crate::util::ZeroingString;
 use crate::LMDBBackend;
+use chrono::Utc;
 use epic_wallet_util::epic_util::logger::LoggingConfig;
 use failure::ResultExt;
 use std::fs;
 use std::path::PathBuf;
 
+/// Marker file recording that the recovery phrase has been verified
+const SEED_VERIFIED_FILE: &str = "wallet.seed.verified";
+
+/// Prefix of the temporary directory a snapshot's store is loaded into
+const SNAPSHOT_DIR_PREFIX: &str = "epic_wallet_snapshot";
+
+/// View key of a watch-only wallet, which has no seed file
+const VIEW_KEY_FILE: &str = "wallet.view";
+
 pub struct DefaultLCProvider<'a, C, K>
 where
 	C: NodeClient + 'a,
@@ -39,6 +51,9 @@ where
 {
 	data_dir: String,
 	node_client: C,
+	read_only: bool,
+	/// File of the snapshot open in place of a wallet
+	snapshot: Option<String>,
 	backend: Option<Box<dyn WalletBackend<'a, C, K> + 'a>>,
 }
 
@@ -52,11 +67,18 @@ where
 		DefaultLCProvider {
 			node_client,
 			data_dir: "default".to_owned(),
+			read_only: false,
+			snapshot: None,
 			backend: None,
 		}
 	}
 }
 
+/// Directory a snapshot's store is loaded into, one per process
+fn snapshot_dir() -> PathBuf {
+	std::env::temp_dir().join(format!("{}_{}", SNAPSHOT_DIR_PREFIX, std::process::id()))
+}
+
 impl<'a, C, K> WalletLCProvider<'a, C, K> for DefaultLCProvider<'a, C, K>
 where
 	C: NodeClient + 'a,
@@ -71,6 +93,22 @@ where
 		Ok(self.data_dir.to_owned())
 	}
 
+	fn set_read_only(&mut self, read_only: bool) -> Result<(), Error> {
+		self.read_only = read_only;
+		Ok(())
+	}
+
+	fn set_node(&mut self, node_url: &str, node_api_secret: Option<String>) -> Result<(), Error> {
+		self.node_client.set_node_url(node_url);
+		self.node_client
+			.set_node_api_secret(node_api_secret.clone());
+		if let Some(b) = self.backend.as_mut() {
+			b.w2n_client().set_node_url(node_url);
+			b.w2n_client().set_node_api_secret(node_api_secret);
+		}
+		Ok(())
+	}
+
 	fn create_config(
 		&self,
 		chain_type: &global::ChainTypes,
@@ -149,6 +187,11 @@ where
 		abs_path.push(self.data_dir.clone());
 
 		default_config.update_paths(&PathBuf::from(abs_path));
+		// only the values specific to this wallet are written over the system-wide file
+		let system_config = config::system_config_path(chain_type);
+		if system_config.exists() {
+			default_config.system_config_path = Some(system_config);
+		}
 		let res = default_config.write_to_file(config_file_name.to_str().unwrap());
 		if let Err(e) = res {
 			let msg = format!(
@@ -180,9 +223,14 @@ where
 		_name: Option<&str>,
 		mnemonic: Option<ZeroingString>,
 		mnemonic_length: usize,
+		language: Option<MnemonicLanguage>,
 		password: ZeroingString,
 		test_mode: bool,
 	) -> Result<(), Error> {
+		if self.read_only {
+			let msg = "A wallet can't be created with a read-only store".to_owned();
+			return Err(ErrorKind::Lifecycle(msg).into());
+		}
 		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
 		data_dir_name.push(EPIC_WALLET_DIR);
 		let data_dir_name = data_dir_name.to_str().unwrap();
@@ -193,7 +241,18 @@ where
 				return Err(ErrorKind::WalletSeedExists(msg))?;
 			}
 		}
-		let _ = WalletSeed::init_file(&data_dir_name, mnemonic_length, mnemonic.clone(), password);
+		let res = WalletSeed::init_file(
+			&data_dir_name,
+			mnemonic_length,
+			mnemonic.clone(),
+			language,
+			password,
+		);
+		// in test mode the seed file may already exist, otherwise a phrase that
+		// isn't in the given language leaves no seed to open
+		if !test_mode {
+			res?;
+		}
 		info!("Wallet seed file created");
 		let mut wallet: LMDBBackend<'a, C, K> =
 			match LMDBBackend::new(&data_dir_name, self.node_client.clone()) {
@@ -225,14 +284,23 @@ where
 		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
 		data_dir_name.push(EPIC_WALLET_DIR);
 		let data_dir_name = data_dir_name.to_str().unwrap();
-		let mut wallet: LMDBBackend<'a, C, K> =
-			match LMDBBackend::new(&data_dir_name, self.node_client.clone()) {
-				Err(e) => {
-					let msg = format!("Error opening wallet: {}, Data Dir: {}", e, &data_dir_name);
-					return Err(ErrorKind::Lifecycle(msg).into());
-				}
-				Ok(d) => d,
-			};
+		let backend = match self.read_only {
+			true => LMDBBackend::new_read_only(&data_dir_name, self.node_client.clone()),
+			false => LMDBBackend::new(&data_dir_name, self.node_client.clone()),
+		};
+		let mut wallet: LMDBBackend<'a, C, K> = match backend {
+			Err(e) => {
+				let msg = format!("Error opening wallet: {}, Data Dir: {}", e, &data_dir_name);
+				return Err(ErrorKind::Lifecycle(msg).into());
+			}
+			Ok(d) => d,
+		};
+		// a watch-only wallet is opened without a keychain, on the account of its view key
+		if let Some(view_key) = self.view_key(None)? {
+			wallet.set_parent_key_id(view_key.parent_key_id);
+			self.backend = Some(Box::new(wallet));
+			return Ok(None);
+		}
 		let wallet_seed = WalletSeed::from_file(&data_dir_name, password).context(
 			ErrorKind::Lifecycle("Error opening wallet (is password correct?)".into()),
 		)?;
@@ -251,6 +319,9 @@ where
 			None => {}
 		};
 		self.backend = None;
+		if self.snapshot.take().is_some() {
+			fs::remove_dir_all(snapshot_dir()).context(ErrorKind::IO)?;
+		}
 		Ok(())
 	}
 
@@ -261,7 +332,108 @@ where
 		let res = WalletSeed::seed_file_exists(&data_dir_name).context(ErrorKind::CallbackImpl(
 			"Error checking for wallet existence",
 		))?;
-		Ok(res)
+		Ok(res || self.view_key(None)?.is_some())
+	}
+
+	fn export_view_key(
+		&self,
+		_name: Option<&str>,
+		password: ZeroingString,
+		parent_key_id: &Identifier,
+	) -> Result<ViewKey, Error> {
+		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		data_dir_name.push(EPIC_WALLET_DIR);
+		let data_dir_name = data_dir_name.to_str().unwrap();
+		let wallet_seed = WalletSeed::from_file(&data_dir_name, password).context(
+			ErrorKind::Lifecycle("Error opening wallet seed file".into()),
+		)?;
+		let view_key = wallet_seed
+			.derive_view_key(global::is_floonet(), parent_key_id)
+			.context(ErrorKind::Lifecycle("Error deriving view key".into()))?;
+		Ok(view_key)
+	}
+
+	fn create_watch_only_wallet(
+		&mut self,
+		_name: Option<&str>,
+		view_key: ViewKey,
+	) -> Result<(), Error> {
+		if self.read_only {
+			let msg = "A wallet can't be created with a read-only store".to_owned();
+			return Err(ErrorKind::Lifecycle(msg).into());
+		}
+		if self.wallet_exists(None)? {
+			let msg = "A wallet already exists in the top level directory".to_owned();
+			return Err(ErrorKind::WalletSeedExists(msg).into());
+		}
+		if view_key.is_floo != global::is_floonet() {
+			let msg = "The view key was exported for another chain".to_owned();
+			return Err(ErrorKind::Lifecycle(msg).into());
+		}
+		let mut data_dir_name = PathBuf::from(self.data_dir.clone());
+		data_dir_name.push(EPIC_WALLET_DIR);
+		fs::create_dir_all(&data_dir_name).context(ErrorKind::IO)?;
+		let mut view_key_file = data_dir_name.clone();
+		view_key_file.push(VIEW_KEY_FILE);
+		let data_dir_name = data_dir_name.to_str().unwrap();
+
+		let json = serde_json::to_string_pretty(&view_key)
+			.map_err(|e| ErrorKind::Lifecycle(format!("Error serializing view key: {}", e)))?;
+		fs::write(&view_key_file, json).context(ErrorKind::IO)?;
+		info!("Watch-only wallet view key file created");
+		let mut wallet: LMDBBackend<'a, C, K> =
+			match LMDBBackend::new(&data_dir_name, self.node_client.clone()) {
+				Err(e) => {
+					let msg = format!("Error creating wallet: {}, Data Dir: {}", e, &data_dir_name);
+					error!("{}", msg);
+					return Err(ErrorKind::Lifecycle(msg).into());
+				}
+				Ok(d) => d,
+			};
+		// the account's outputs are only found by scanning the chain
+		let mut batch = wallet.batch_no_mask()?;
+		batch.save_init_status(WalletInitStatus::InitNeedsScanning)?;
+		batch.commit()?;
+		info!("Wallet database backend created at {}", data_dir_name);
+		Ok(())
+	}
+
+	fn view_key(&self, _name: Option<&str>) -> Result<Option<ViewKey>, Error> {
+		if self.snapshot.is_some() {
+			return Ok(None);
+		}
+		let mut view_key_file = PathBuf::from(self.data_dir.clone());
+		view_key_file.push(EPIC_WALLET_DIR);
+		view_key_file.push(VIEW_KEY_FILE);
+		if !view_key_file.exists() {
+			return Ok(None);
+		}
+		let json = fs::read_to_string(&view_key_file).context(ErrorKind::IO)?;
+		let view_key = serde_json::from_str(&json)
+			.map_err(|e| ErrorKind::Lifecycle(format!("Error reading view key file: {}", e)))?;
+		Ok(Some(view_key))
+	}
+
+	fn open_snapshot(&mut self, snapshot_file: &str) -> Result<(), Error> {
+		self.close_wallet(None)?;
+		let data_dir = snapshot_dir();
+		let backend: LMDBBackend<'a, C, K> = open_snapshot_store(
+			snapshot_file,
+			data_dir.to_str().unwrap(),
+			self.node_client.clone(),
+		)
+		.map_err(|e| {
+			let msg = format!("Error opening snapshot {}: {}", snapshot_file, e);
+			ErrorKind::Lifecycle(msg)
+		})?;
+		info!("Opened snapshot {} read-only", snapshot_file);
+		self.backend = Some(Box::new(backend));
+		self.snapshot = Some(snapshot_file.to_owned());
+		Ok(())
+	}
+
+	fn snapshot(&self) -> Option<String> {
+		self.snapshot.clone()
 	}
 
 	fn get_mnemonic(
@@ -282,7 +454,7 @@ where
 	}
 
 	fn validate_mnemonic(&self, mnemonic: ZeroingString) -> Result<(), Error> {
-		match WalletSeed::from_mnemonic(mnemonic) {
+		match WalletSeed::from_mnemonic(mnemonic, None) {
 			Ok(_) => Ok(()),
 			Err(_) => Err(ErrorKind::GenericError("Validating mnemonic".into()))?,
 		}
@@ -299,6 +471,31 @@ where
 		WalletSeed::recover_from_phrase(data_dir_name, mnemonic, password).context(
 			ErrorKind::Lifecycle("Error recovering from mnemonic".into()),
 		)?;
+		// the new seed's phrase hasn't been verified yet
+		self.set_seed_verified(None, false)?;
+		Ok(())
+	}
+
+	fn seed_verified(&self, _name: Option<&str>) -> Result<bool, Error> {
+		// a snapshot has no recovery phrase to back up
+		if self.snapshot.is_some() {
+			return Ok(true);
+		}
+		let mut marker = PathBuf::from(self.data_dir.clone());
+		marker.push(EPIC_WALLET_DIR);
+		marker.push(SEED_VERIFIED_FILE);
+		Ok(marker.exists())
+	}
+
+	fn set_seed_verified(&self, _name: Option<&str>, verified: bool) -> Result<(), Error> {
+		let mut marker = PathBuf::from(self.data_dir.clone());
+		marker.push(EPIC_WALLET_DIR);
+		marker.push(SEED_VERIFIED_FILE);
+		if verified {
+			fs::write(&marker, Utc::now().to_rfc3339()).context(ErrorKind::IO)?;
+		} else if marker.exists() {
+			fs::remove_file(&marker).context(ErrorKind::IO)?;
+		}
 		Ok(())
 	}
 
@@ -336,6 +533,7 @@ where
 			data_dir_name,
 			0,
 			Some(ZeroingString::from(orig_mnemonic)),
+			Some(orig_wallet_seed.language()),
 			new.clone(),
 		);
 		info!("Wallet seed file created");
@@ -23,27 +23,71 @@ use ring::aead;
 use ring::pbkdf2;
 use serde_json;
 
-use crate::keychain::{mnemonic, Keychain};
+use crate::keychain::{mnemonic, Identifier, Keychain};
+use crate::libwallet::{MnemonicLanguage, ViewKey};
 use crate::util;
 use crate::{Error, ErrorKind};
 use failure::ResultExt;
 
 pub const SEED_FILE: &'static str = "wallet.seed";
 
+/// Wordlist of the given language
+fn bip39_language(language: MnemonicLanguage) -> bip39::Language {
+	match language {
+		MnemonicLanguage::English => bip39::Language::English,
+		MnemonicLanguage::ChineseSimplified => bip39::Language::SimplifiedChinese,
+		MnemonicLanguage::ChineseTraditional => bip39::Language::TraditionalChinese,
+		MnemonicLanguage::Czech => bip39::Language::Czech,
+		MnemonicLanguage::French => bip39::Language::French,
+		MnemonicLanguage::Italian => bip39::Language::Italian,
+		MnemonicLanguage::Japanese => bip39::Language::Japanese,
+		MnemonicLanguage::Korean => bip39::Language::Korean,
+		MnemonicLanguage::Portuguese => bip39::Language::Portuguese,
+		MnemonicLanguage::Spanish => bip39::Language::Spanish,
+	}
+}
+
+/// Seed of a wallet, with the language its recovery phrase is shown in
 #[derive(Clone, Debug, PartialEq)]
-pub struct WalletSeed(Vec<u8>);
+pub struct WalletSeed(Vec<u8>, MnemonicLanguage);
 
 impl WalletSeed {
 	pub fn from_bytes(bytes: &[u8]) -> WalletSeed {
-		WalletSeed(bytes.to_vec())
+		WalletSeed(bytes.to_vec(), MnemonicLanguage::English)
 	}
 
-	pub fn from_mnemonic(word_list: util::ZeroingString) -> Result<WalletSeed, Error> {
-		let res = mnemonic::to_entropy(&word_list);
-		match res {
-			Ok(s) => Ok(WalletSeed::from_bytes(&s)),
-			Err(_) => Err(ErrorKind::Mnemonic.into()),
+	/// Seed of a recovery phrase in the given language, or in the first language
+	/// its words and checksum are valid in if not given. English phrases are read
+	/// as they always were, the other languages by their standard BIP39 wordlists.
+	pub fn from_mnemonic(
+		word_list: util::ZeroingString,
+		language: Option<MnemonicLanguage>,
+	) -> Result<WalletSeed, Error> {
+		let languages = match language {
+			Some(l) => vec![l],
+			None => MnemonicLanguage::ALL.to_vec(),
+		};
+		for language in languages {
+			let entropy = match language {
+				MnemonicLanguage::English => mnemonic::to_entropy(&word_list).ok(),
+				l => bip39::Mnemonic::parse_in(bip39_language(l), word_list.as_str())
+					.ok()
+					.map(|m| m.to_entropy()),
+			};
+			if let Some(e) = entropy {
+				return Ok(WalletSeed(e, language));
+			}
 		}
+		Err(ErrorKind::Mnemonic.into())
+	}
+
+	/// The same seed, its recovery phrase shown in the given language
+	pub fn with_language(self, language: MnemonicLanguage) -> WalletSeed {
+		WalletSeed(self.0, language)
+	}
+
+	pub fn language(&self) -> MnemonicLanguage {
+		self.1
 	}
 
 	pub fn _from_hex(hex: &str) -> Result<WalletSeed, Error> {
@@ -57,6 +101,11 @@ impl WalletSeed {
 	}
 
 	pub fn to_mnemonic(&self) -> Result<String, Error> {
+		if self.1 != MnemonicLanguage::English {
+			return bip39::Mnemonic::from_entropy_in(bip39_language(self.1), &self.0)
+				.map(|m| m.to_string())
+				.map_err(|_| ErrorKind::Mnemonic.into());
+		}
 		let result = mnemonic::from_entropy(&self.0);
 		match result {
 			Ok(r) => Ok(r),
@@ -74,13 +123,22 @@ impl WalletSeed {
 		Ok(result)
 	}
 
+	pub fn derive_view_key(
+		&self,
+		is_floonet: bool,
+		parent_key_id: &Identifier,
+	) -> Result<ViewKey, Error> {
+		let result = ViewKey::from_seed(&self.0, is_floonet, parent_key_id)?;
+		Ok(result)
+	}
+
 	pub fn init_new(seed_length: usize) -> WalletSeed {
 		let mut seed: Vec<u8> = vec![];
 		let mut rng = thread_rng();
 		for _ in 0..seed_length {
 			seed.push(rng.gen());
 		}
-		WalletSeed(seed)
+		WalletSeed::from_bytes(&seed)
 	}
 
 	pub fn seed_file_exists(data_file_dir: &str) -> Result<bool, Error> {
@@ -134,7 +192,7 @@ impl WalletSeed {
 					.to_owned(),
 			))?;
 		}
-		let seed = WalletSeed::from_mnemonic(word_list)?;
+		let seed = WalletSeed::from_mnemonic(word_list, None)?;
 		let enc_seed = EncryptedWalletSeed::from_seed(&seed, password)?;
 		let enc_seed_json = serde_json::to_string_pretty(&enc_seed).context(ErrorKind::Format)?;
 		let mut file = File::create(seed_file_path).context(ErrorKind::IO)?;
@@ -148,6 +206,7 @@ impl WalletSeed {
 		data_file_dir: &str,
 		seed_length: usize,
 		recovery_phrase: Option<util::ZeroingString>,
+		language: Option<MnemonicLanguage>,
 		password: util::ZeroingString,
 	) -> Result<WalletSeed, Error> {
 		// create directory if it doesn't exist
@@ -163,8 +222,8 @@ impl WalletSeed {
 		}
 
 		let seed = match recovery_phrase {
-			Some(p) => WalletSeed::from_mnemonic(p)?,
-			None => WalletSeed::init_new(seed_length),
+			Some(p) => WalletSeed::from_mnemonic(p, language)?,
+			None => WalletSeed::init_new(seed_length).with_language(language.unwrap_or_default()),
 		};
 
 		let enc_seed = EncryptedWalletSeed::from_seed(&seed, password)?;
@@ -225,6 +284,10 @@ pub struct EncryptedWalletSeed {
 	pub salt: String,
 	/// Nonce
 	pub nonce: String,
+	/// Language the recovery phrase is shown in, English for the seed files
+	/// written before it was recorded
+	#[serde(default)]
+	pub language: MnemonicLanguage,
 }
 
 impl EncryptedWalletSeed {
@@ -265,6 +328,7 @@ impl EncryptedWalletSeed {
 			encrypted_seed: util::to_hex(enc_bytes.to_vec()),
 			salt: util::to_hex(salt.to_vec()),
 			nonce: util::to_hex(nonce.to_vec()),
+			language: seed.1,
 		})
 	}
 
@@ -309,7 +373,7 @@ impl EncryptedWalletSeed {
 			encrypted_seed.pop();
 		}
 
-		Ok(WalletSeed::from_bytes(&encrypted_seed))
+		Ok(WalletSeed(encrypted_seed, self.language))
 	}
 }
 
@@ -336,4 +400,34 @@ mod tests {
 		let decrypted_wallet_seed = enc_wallet_seed.decrypt(&password);
 		assert!(decrypted_wallet_seed.is_err());
 	}
+
+	#[test]
+	fn wallet_seed_languages() {
+		let wallet_seed = WalletSeed::init_new(32);
+		let english = wallet_seed.to_mnemonic().unwrap();
+		for language in MnemonicLanguage::ALL.iter() {
+			let seed = wallet_seed.clone().with_language(*language);
+			let phrase = ZeroingString::from(seed.to_mnemonic().unwrap());
+			assert_eq!(phrase.split_whitespace().count(), 24);
+			// the language is detected from the words
+			let recovered = WalletSeed::from_mnemonic(phrase.clone(), None).unwrap();
+			assert_eq!(recovered.0, wallet_seed.0);
+			assert_eq!(recovered.language(), *language);
+			let recovered = WalletSeed::from_mnemonic(phrase, Some(*language)).unwrap();
+			assert_eq!(recovered, seed);
+
+			// and kept in the seed file
+			let password = ZeroingString::from("passwoid");
+			let enc_seed = EncryptedWalletSeed::from_seed(&seed, password.clone()).unwrap();
+			assert_eq!(enc_seed.decrypt(&password).unwrap(), seed);
+		}
+		let spanish = wallet_seed.with_language(MnemonicLanguage::Spanish);
+		let phrase = ZeroingString::from(spanish.to_mnemonic().unwrap());
+		assert!(WalletSeed::from_mnemonic(phrase, Some(MnemonicLanguage::French)).is_err());
+		assert!(WalletSeed::from_mnemonic(
+			ZeroingString::from(english),
+			Some(MnemonicLanguage::Spanish)
+		)
+		.is_err());
+	}
 }
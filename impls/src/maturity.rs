@@ -0,0 +1,59 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Maturity hooks, delivering the notifications of outputs becoming spendable
+use std::sync::Arc;
+
+use crate::client_utils::Client;
+use crate::config::MaturityNotificationsConfig;
+use crate::libwallet::api_impl::owner_updater::MaturityWatch;
+use crate::libwallet::{Error, ErrorKind, MaturityHook, OutputMatured};
+
+/// Posts the notifications as JSON to a URL
+pub struct WebhookMaturityHook {
+	url: String,
+}
+
+impl WebhookMaturityHook {
+	/// Create a hook posting to the given URL
+	pub fn new(url: &str) -> WebhookMaturityHook {
+		WebhookMaturityHook {
+			url: url.to_owned(),
+		}
+	}
+}
+
+impl MaturityHook for WebhookMaturityHook {
+	fn matured(&self, output: &OutputMatured) -> Result<(), Error> {
+		Client::new()
+			.post_no_ret(&self.url, None, output)
+			.map_err(|e| {
+				ErrorKind::GenericError(format!(
+					"Posting maturity notification to {}: {}",
+					self.url, e
+				))
+			})?;
+		Ok(())
+	}
+}
+
+/// Build the maturity watch described by the configuration, delivered to the
+/// webhook if set
+pub fn maturity_watch_from_config(config: &MaturityNotificationsConfig) -> MaturityWatch {
+	let hook = config
+		.webhook_url
+		.as_ref()
+		.map(|u| Arc::new(WebhookMaturityHook::new(u)) as Arc<dyn MaturityHook>);
+	MaturityWatch::new(hook)
+}
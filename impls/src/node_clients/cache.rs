@@ -0,0 +1,290 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cache of the outputs and kernels the node returned, so refreshes made in quick
+//! succession don't request the same commitments and excesses again. The cache is
+//! off unless it's given a size.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::core::core::TxKernel;
+use crate::util::secp::pedersen;
+use crate::util::Mutex;
+
+/// Entries kept by each cache, unless configured otherwise: none, the caches are
+/// opt-in
+pub const DEFAULT_NODE_CACHE_SIZE: usize = 0;
+
+/// Seconds a response is reused for, unless configured otherwise
+pub const DEFAULT_NODE_CACHE_TTL_SECS: u64 = 10;
+
+/// Location of an output, as returned by `get_outputs_from_node`
+pub type OutputLocation = (String, u64, u64);
+
+/// Kernel with its height and MMR index, as returned by `get_kernel`
+pub type KernelLocation = (TxKernel, u64, u64);
+
+/// Least recently used cache of values expiring after a time to live
+pub(crate) struct LruCache<K, V> {
+	capacity: usize,
+	ttl: Duration,
+	/// Values with the time they were stored and the last use of their key
+	entries: HashMap<K, (V, Instant, u64)>,
+	/// Keys by last use, least recent first
+	uses: BTreeMap<u64, K>,
+	next_use: u64,
+}
+
+impl<K, V> LruCache<K, V>
+where
+	K: Clone + Eq + Hash,
+	V: Clone,
+{
+	/// An empty cache of the given number of entries
+	pub fn new(capacity: usize, ttl: Duration) -> LruCache<K, V> {
+		LruCache {
+			capacity,
+			ttl,
+			entries: HashMap::new(),
+			uses: BTreeMap::new(),
+			next_use: 0,
+		}
+	}
+
+	/// Value stored for a key, if it hasn't expired
+	pub fn get(&mut self, key: &K) -> Option<V> {
+		let expired = match self.entries.get(key) {
+			Some((_, stored, _)) => stored.elapsed() > self.ttl,
+			None => return None,
+		};
+		if expired {
+			if let Some((_, _, used)) = self.entries.remove(key) {
+				self.uses.remove(&used);
+			}
+			return None;
+		}
+		let use_ = self.next_use;
+		self.next_use += 1;
+		let entry = self.entries.get_mut(key)?;
+		self.uses.remove(&entry.2);
+		self.uses.insert(use_, key.clone());
+		entry.2 = use_;
+		Some(entry.0.clone())
+	}
+
+	/// Store a value, evicting the least recently used entry if the cache is full
+	pub fn insert(&mut self, key: K, value: V) {
+		if self.capacity == 0 {
+			return;
+		}
+		if let Some((_, _, used)) = self.entries.remove(&key) {
+			self.uses.remove(&used);
+		}
+		while self.entries.len() >= self.capacity {
+			let oldest = match self.uses.keys().next() {
+				Some(u) => *u,
+				None => break,
+			};
+			if let Some(k) = self.uses.remove(&oldest) {
+				self.entries.remove(&k);
+			}
+		}
+		let use_ = self.next_use;
+		self.next_use += 1;
+		self.uses.insert(use_, key.clone());
+		self.entries.insert(key, (value, Instant::now(), use_));
+	}
+
+	/// Forget all the entries
+	pub fn clear(&mut self) {
+		self.entries.clear();
+		self.uses.clear();
+	}
+}
+
+/// Caches of a node client, shared by its clones. Only the outputs and kernels the
+/// node found are cached, those it didn't may be confirmed by the next block. The
+/// caches are emptied when the chain tip's height changes, as outputs may have been
+/// spent, and when a transaction is posted.
+#[derive(Clone)]
+pub(crate) struct NodeCache {
+	outputs: Arc<Mutex<LruCache<pedersen::Commitment, OutputLocation>>>,
+	/// Kernels by excess and the heights they were looked up between
+	kernels: Arc<Mutex<LruCache<(pedersen::Commitment, Option<u64>, Option<u64>), KernelLocation>>>,
+	/// Height of the chain tip when the responses were cached
+	height: Arc<Mutex<Option<u64>>>,
+}
+
+impl NodeCache {
+	/// Empty caches of the given number of entries each, reusing the responses for
+	/// the given number of seconds. A size of 0 disables the caches.
+	pub fn new(size: usize, ttl_secs: u64) -> NodeCache {
+		let ttl = Duration::from_secs(ttl_secs);
+		NodeCache {
+			outputs: Arc::new(Mutex::new(LruCache::new(size, ttl))),
+			kernels: Arc::new(Mutex::new(LruCache::new(size, ttl))),
+			height: Arc::new(Mutex::new(None)),
+		}
+	}
+
+	/// Cached locations of the given outputs, and the outputs to request from the node
+	pub fn outputs(
+		&self,
+		commits: &[pedersen::Commitment],
+	) -> (
+		HashMap<pedersen::Commitment, OutputLocation>,
+		Vec<pedersen::Commitment>,
+	) {
+		let mut cache = self.outputs.lock();
+		let mut hits = HashMap::new();
+		let mut misses = vec![];
+		for commit in commits {
+			match cache.get(commit) {
+				Some(location) => {
+					hits.insert(commit.clone(), location);
+				}
+				None => misses.push(commit.clone()),
+			}
+		}
+		(hits, misses)
+	}
+
+	/// Store the locations of the outputs the node found
+	pub fn insert_outputs(&self, found: &HashMap<pedersen::Commitment, OutputLocation>) {
+		let mut cache = self.outputs.lock();
+		for (commit, location) in found {
+			cache.insert(commit.clone(), location.clone());
+		}
+	}
+
+	/// Cached kernel found by a lookup
+	pub fn kernel(
+		&self,
+		excess: &pedersen::Commitment,
+		min_height: Option<u64>,
+		max_height: Option<u64>,
+	) -> Option<KernelLocation> {
+		self.kernels
+			.lock()
+			.get(&(excess.clone(), min_height, max_height))
+	}
+
+	/// Store the kernel found by a lookup
+	pub fn insert_kernel(
+		&self,
+		excess: &pedersen::Commitment,
+		min_height: Option<u64>,
+		max_height: Option<u64>,
+		kernel: KernelLocation,
+	) {
+		self.kernels
+			.lock()
+			.insert((excess.clone(), min_height, max_height), kernel);
+	}
+
+	/// Forget all the responses if the chain tip is at another height than when they
+	/// were cached
+	pub fn set_height(&self, height: u64) {
+		let mut current = self.height.lock();
+		if *current != Some(height) {
+			*current = Some(height);
+			self.clear();
+		}
+	}
+
+	/// Forget all the responses, e.g. when switching to another node
+	pub fn clear(&self) {
+		self.outputs.lock().clear();
+		self.kernels.lock().clear();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::thread;
+
+	#[test]
+	fn lru_cache() {
+		let mut cache = LruCache::new(2, Duration::from_secs(60));
+		cache.insert(1, "a");
+		cache.insert(2, "b");
+		// 1 is used, so 2 is the least recently used
+		assert_eq!(cache.get(&1), Some("a"));
+		cache.insert(3, "c");
+		assert_eq!(cache.get(&2), None);
+		assert_eq!(cache.get(&1), Some("a"));
+		assert_eq!(cache.get(&3), Some("c"));
+		cache.insert(3, "d");
+		assert_eq!(cache.get(&3), Some("d"));
+		assert_eq!(cache.entries.len(), 2);
+		assert_eq!(cache.uses.len(), 2);
+
+		// entries expire
+		let mut cache = LruCache::new(2, Duration::from_millis(50));
+		cache.insert(1, "a");
+		thread::sleep(Duration::from_millis(100));
+		assert_eq!(cache.get(&1), None);
+		assert!(cache.uses.is_empty());
+
+		// a size of 0 caches nothing
+		let mut cache = LruCache::new(0, Duration::from_secs(60));
+		cache.insert(1, "a");
+		assert_eq!(cache.get(&1), None);
+	}
+
+	#[test]
+	fn node_cache_outputs() {
+		let cache = NodeCache::new(10, 60);
+		let commits: Vec<_> = (0..3u8)
+			.map(|i| pedersen::Commitment::from_vec(vec![i; 33]))
+			.collect();
+		let (hits, misses) = cache.outputs(&commits);
+		assert!(hits.is_empty());
+		assert_eq!(misses, commits);
+
+		// only the first output is known to the node, so only it is cached
+		let mut found = HashMap::new();
+		found.insert(commits[0].clone(), ("08".to_owned(), 1, 2));
+		cache.insert_outputs(&found);
+		let (hits, misses) = cache.outputs(&commits);
+		assert_eq!(hits.len(), 1);
+		assert_eq!(hits[&commits[0]], ("08".to_owned(), 1, 2));
+		assert_eq!(misses, commits[1..].to_vec());
+
+		cache.clear();
+		assert_eq!(cache.outputs(&commits).1.len(), 3);
+
+		// the responses are forgotten once the chain tip moves
+		cache.set_height(10);
+		cache.insert_outputs(&found);
+		cache.set_height(10);
+		assert_eq!(cache.outputs(&commits).0.len(), 1);
+		cache.set_height(11);
+		assert!(cache.outputs(&commits).0.is_empty());
+	}
+
+	#[test]
+	fn node_cache_default_off() {
+		let cache = NodeCache::new(DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS);
+		let commit = pedersen::Commitment::from_vec(vec![0; 33]);
+		let mut found = HashMap::new();
+		found.insert(commit.clone(), ("08".to_owned(), 1, 2));
+		cache.insert_outputs(&found);
+		assert!(cache.outputs(&[commit]).0.is_empty());
+	}
+}
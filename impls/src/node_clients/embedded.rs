@@ -0,0 +1,107 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Epic node started in the wallet's process, for applications shipping the wallet
+//! and the node as a single binary
+
+use std::sync::Arc;
+
+use epic_pool::TxSource;
+use epic_servers::{Server, ServerConfig};
+
+use super::local::{LocalNode, LocalNodeClient};
+use crate::chain::Chain;
+use crate::core::core::Transaction;
+use crate::error::{Error, ErrorKind};
+use crate::libwallet;
+use crate::util::Mutex;
+
+/// Version of the epic crates the node is built from, as pinned in Cargo.toml
+const EMBEDDED_NODE_VERSION: &str = "3.4.0";
+
+/// Epic node running in the wallet's process, syncing with its peers like a
+/// standalone node would. The node is stopped when stopped explicitly or dropped.
+pub struct EmbeddedNode {
+	/// Server of the node, `None` once stopped
+	server: Mutex<Option<Server>>,
+	chain: Arc<Chain>,
+}
+
+impl EmbeddedNode {
+	/// Start a node with the given configuration
+	pub fn start(config: ServerConfig) -> Result<Arc<EmbeddedNode>, Error> {
+		let server = Server::new(config)
+			.map_err(|e| ErrorKind::EmbeddedNode(format!("Starting the node: {:?}", e)))?;
+		info!("Embedded node started");
+		let chain = server.chain.clone();
+		Ok(Arc::new(EmbeddedNode {
+			server: Mutex::new(Some(server)),
+			chain,
+		}))
+	}
+
+	/// Client of the node, for the wallet to be opened with
+	pub fn client(node: &Arc<EmbeddedNode>) -> LocalNodeClient {
+		LocalNodeClient::new(node.clone())
+	}
+
+	/// Whether the node is still running
+	pub fn is_running(&self) -> bool {
+		self.server.lock().is_some()
+	}
+
+	/// Stop the node. The wallet's requests fail afterwards.
+	pub fn stop(&self) {
+		if let Some(server) = self.server.lock().take() {
+			server.stop();
+			info!("Embedded node stopped");
+		}
+	}
+}
+
+impl Drop for EmbeddedNode {
+	fn drop(&mut self) {
+		self.stop();
+	}
+}
+
+impl LocalNode for EmbeddedNode {
+	fn chain(&self) -> Arc<Chain> {
+		self.chain.clone()
+	}
+
+	fn version(&self) -> String {
+		EMBEDDED_NODE_VERSION.to_owned()
+	}
+
+	fn post_tx(&self, tx: Transaction, fluff: bool) -> Result<(), libwallet::Error> {
+		let server = self.server.lock();
+		let server = server.as_ref().ok_or_else(|| {
+			libwallet::ErrorKind::ClientCallback("The embedded node is stopped".to_owned())
+		})?;
+		let mut tx_pool = server.tx_pool.write();
+		let header = tx_pool.blockchain.chain_head().map_err(|e| {
+			libwallet::ErrorKind::ClientCallback(format!("Getting the chain head: {}", e))
+		})?;
+		tx_pool
+			.add_to_pool(TxSource::PushApi, tx, !fluff, &header)
+			.map_err(|e| {
+				libwallet::ErrorKind::ClientCallback(format!(
+					"Posting transaction to node: {:?}",
+					e
+				))
+			})?;
+		Ok(())
+	}
+}
@@ -19,21 +19,58 @@ use futures::{stream, Stream};
 
 use crate::api::{self, LocatedTxKernel};
 use crate::core::core::TxKernel;
-use crate::libwallet::{NodeClient, NodeVersionInfo, TxWrapper};
+use crate::libwallet::{
+	publish_event, BlockHeaderInfo, NodeClient, NodeRequestMetrics, NodeVersionInfo, TxWrapper,
+	WalletEvent,
+};
+use chrono::{DateTime, Utc};
 use semver::Version;
+use std::cmp;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
 use tokio::runtime::Runtime;
 
-use crate::client_utils::Client;
+use super::cache::{NodeCache, DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS};
+use super::metrics::NodeMetrics;
+use crate::client_utils::{Client, ClientError, ClientErrorKind};
 use crate::libwallet;
 use crate::util::secp::pedersen;
-use crate::util::{self, to_hex};
+use crate::util::{self, to_hex, Mutex};
+
+/// Number of headers requested from the node at once by `get_headers`
+const HEADERS_BATCH_SIZE: usize = 100;
+
+/// Header info of a block header returned by the node
+fn header_info(h: api::BlockHeaderPrintable) -> Result<BlockHeaderInfo, libwallet::Error> {
+	let timestamp = DateTime::parse_from_rfc3339(&h.timestamp)
+		.map_err(|e| {
+			libwallet::ErrorKind::ClientCallback(format!("Invalid block header timestamp: {}", e))
+		})?
+		.with_timezone(&Utc);
+	Ok(BlockHeaderInfo {
+		height: h.height,
+		hash: h.hash,
+		timestamp,
+	})
+}
+
+/// Whether a node of the given version can look kernels up
+fn supports_kernel_lookup(version: &NodeVersionInfo) -> Result<bool, libwallet::Error> {
+	let version = Version::parse(&version.node_version)
+		.map_err(|_| libwallet::ErrorKind::NodeIncompatible("Unable to parse version".into()))?;
+	Ok(version > Version::new(2, 0, 0))
+}
 
 #[derive(Clone)]
 pub struct HTTPNodeClient {
 	pub node_url: String,
 	node_api_secret: Option<String>,
-	node_version_info: Option<NodeVersionInfo>,
+	/// Version the node reported, shared by the clones of the client
+	node_version_info: Arc<Mutex<Option<NodeVersionInfo>>>,
+	metrics: NodeMetrics,
+	/// Outputs and kernels the node returned, shared by the clones of the client
+	cache: NodeCache,
 }
 
 impl HTTPNodeClient {
@@ -42,14 +79,132 @@ impl HTTPNodeClient {
 		HTTPNodeClient {
 			node_url: node_url.to_owned(),
 			node_api_secret: node_api_secret,
-			node_version_info: None,
+			node_version_info: Arc::new(Mutex::new(None)),
+			metrics: NodeMetrics::new(),
+			cache: NodeCache::new(DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS),
 		}
 	}
 
+	/// Cache up to the given number of outputs and of kernel lookups, reusing the
+	/// node's responses for the given number of seconds. A size of 0 disables the
+	/// caches.
+	pub fn set_cache(&mut self, size: usize, ttl_secs: u64) {
+		self.cache = NodeCache::new(size, ttl_secs);
+	}
+
+	/// Log the requests taking longer than the given number of milliseconds, 5000 by
+	/// default
+	pub fn set_slow_request_ms(&mut self, ms: u64) {
+		self.metrics.set_slow_request_ms(ms);
+	}
+
+	/// Make a request to the node, timing it under the given method and URL pattern
+	fn timed<T, F>(
+		&self,
+		method: &'static str,
+		url_pattern: &'static str,
+		f: F,
+	) -> Result<T, ClientError>
+	where
+		F: FnOnce() -> Result<T, ClientError>,
+	{
+		let start = Instant::now();
+		let res = f();
+		self.metrics
+			.record(method, url_pattern, start.elapsed(), res.is_ok());
+		res
+	}
+
 	/// Allow returning the chain height without needing a wallet instantiated
 	pub fn chain_height(&self) -> Result<(u64, String), libwallet::Error> {
 		self.get_chain_tip()
 	}
+
+	/// Request the node's version, falling back to 1.0.0 for nodes without the
+	/// version endpoint
+	fn fetch_version_info(&self) -> Option<NodeVersionInfo> {
+		let url = format!("{}/v1/version", self.node_url());
+		let client = Client::new();
+		let res = self.timed("get_version_info", "/v1/version", || {
+			client.get::<NodeVersionInfo>(url.as_str(), self.node_api_secret())
+		});
+		match res {
+			Ok(mut n) => {
+				n.verified = Some(true);
+				Some(n)
+			}
+			Err(e) => {
+				// If node isn't available, allow offline functions
+				// unfortunately have to parse string due to error structure
+				let err_string = format!("{}", e);
+				if err_string.contains("404") {
+					Some(NodeVersionInfo {
+						node_version: "1.0.0".into(),
+						block_header_version: 1,
+						verified: Some(false),
+					})
+				} else {
+					error!("Unable to contact Node to get version info: {}", e);
+					None
+				}
+			}
+		}
+	}
+
+	/// Request the node's version again, in case another node now answers at the node
+	/// URL. Returns the version seen before along with the new one if they differ.
+	fn check_version_change(&self) -> Option<(String, NodeVersionInfo)> {
+		let current = self.fetch_version_info()?;
+		let mut cached = self.node_version_info.lock();
+		let previous = match cached.as_ref() {
+			Some(v) if v.node_version != current.node_version => v.node_version.clone(),
+			_ => return None,
+		};
+		warn!(
+			"Node version changed from {} to {}",
+			previous, current.node_version
+		);
+		*cached = Some(current.clone());
+		publish_event(WalletEvent::NodeVersionChanged {
+			previous_version: previous.clone(),
+			node_version: current.node_version.clone(),
+		});
+		Some((previous, current))
+	}
+
+	/// Map a failed node API call to the wallet error describing its cause. `lookup`
+	/// is set for calls querying given outputs or kernels, where a missing resource
+	/// means the node doesn't know them.
+	fn node_error(&self, report: String, e: &ClientError, lookup: bool) -> libwallet::Error {
+		if let ClientErrorKind::ResponseCode(404, _) | ClientErrorKind::ResponseError(_) = e.kind()
+		{
+			// rather than what the node is missing, report the node being replaced
+			// by one of another version
+			if let Some((previous, current)) = self.check_version_change() {
+				return libwallet::ErrorKind::NodeIncompatible(format!(
+					"Node version changed from {} to {}: {}",
+					previous, current.node_version, report
+				))
+				.into();
+			}
+		}
+		node_error_kind(e.kind(), report, lookup, || self.is_syncing()).into()
+	}
+
+	/// Whether the node reports it's still syncing with the network
+	fn is_syncing(&self) -> bool {
+		let url = format!("{}/v1/status", self.node_url());
+		let client = Client::new();
+		match self.timed("is_syncing", "/v1/status", || {
+			client.get::<serde_json::Value>(url.as_str(), self.node_api_secret())
+		}) {
+			Ok(status) => match status["sync_status"].as_str() {
+				Some(s) => s != "no_sync",
+				None => false,
+			},
+			Err(_) => false,
+		}
+	}
 }
 
 impl NodeClient for HTTPNodeClient {
@@ -62,6 +217,8 @@ impl NodeClient for HTTPNodeClient {
 
 	fn set_node_url(&mut self, node_url: &str) {
 		self.node_url = node_url.to_owned();
+		self.node_version_info = Arc::new(Mutex::new(None));
+		self.cache.clear();
 	}
 
 	fn set_node_api_secret(&mut self, node_api_secret: Option<String>) {
@@ -69,31 +226,13 @@ impl NodeClient for HTTPNodeClient {
 	}
 
 	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
-		if let Some(v) = self.node_version_info.as_ref() {
+		if let Some(v) = self.node_version_info.lock().as_ref() {
 			return Some(v.clone());
 		}
-		let url = format!("{}/v1/version", self.node_url());
-		let client = Client::new();
-		let mut retval = match client.get::<NodeVersionInfo>(url.as_str(), self.node_api_secret()) {
-			Ok(n) => n,
-			Err(e) => {
-				// If node isn't available, allow offline functions
-				// unfortunately have to parse string due to error structure
-				let err_string = format!("{}", e);
-				return if err_string.contains("404") {
-					Some(NodeVersionInfo {
-						node_version: "1.0.0".into(),
-						block_header_version: 1,
-						verified: Some(false),
-					})
-				} else {
-					error!("Unable to contact Node to get version info: {}", e);
-					None
-				};
-			}
-		};
-		retval.verified = Some(true);
-		self.node_version_info = Some(retval.clone());
+		let retval = self.fetch_version_info()?;
+		if retval.verified == Some(true) {
+			*self.node_version_info.lock() = Some(retval.clone());
+		}
 		Some(retval)
 	}
 
@@ -107,11 +246,15 @@ impl NodeClient for HTTPNodeClient {
 			url = format!("{}/v1/pool/push_tx", dest);
 		}
 		let client = Client::new();
-		let res = client.post_no_ret(url.as_str(), self.node_api_secret(), tx);
+		let res = self.timed("post_tx", "/v1/pool/push_tx", || {
+			client.post_no_ret(url.as_str(), self.node_api_secret(), tx)
+		});
+		// the transaction's inputs aren't unspent outputs anymore
+		self.cache.clear();
 		if let Err(e) = res {
 			let report = format!("Posting transaction to node: {}", e);
 			error!("Post TX Error: {}", e);
-			return Err(libwallet::ErrorKind::ClientCallback(report).into());
+			return Err(self.node_error(report, &e, false));
 		}
 		Ok(())
 	}
@@ -121,17 +264,86 @@ impl NodeClient for HTTPNodeClient {
 		let addr = self.node_url();
 		let url = format!("{}/v1/chain", addr);
 		let client = Client::new();
-		let res = client.get::<api::Tip>(url.as_str(), self.node_api_secret());
+		let res = self.timed("get_chain_tip", "/v1/chain", || {
+			client.get::<api::Tip>(url.as_str(), self.node_api_secret())
+		});
 		match res {
 			Err(e) => {
 				let report = format!("Getting chain height from node: {}", e);
 				error!("Get chain height error: {}", e);
-				Err(libwallet::ErrorKind::ClientCallback(report).into())
+				Err(self.node_error(report, &e, false))
+			}
+			Ok(r) => {
+				self.cache.set_height(r.height);
+				Ok((r.height, r.last_block_pushed))
+			}
+		}
+	}
+
+	/// Return the header of the block at the given height
+	fn get_header_info(&self, height: u64) -> Result<Option<BlockHeaderInfo>, libwallet::Error> {
+		let addr = self.node_url();
+		let url = format!("{}/v1/headers/{}", addr, height);
+		let client = Client::new();
+		let res = self.timed("get_header_info", "/v1/headers/{height}", || {
+			client.get::<api::BlockHeaderPrintable>(url.as_str(), self.node_api_secret())
+		});
+		match res {
+			Err(e) => {
+				let report = format!("Getting block header from node: {}", e);
+				error!("Get block header error: {}", e);
+				Err(self.node_error(report, &e, false))
 			}
-			Ok(r) => Ok((r.height, r.last_block_pushed)),
+			Ok(h) => Ok(Some(header_info(h)?)),
 		}
 	}
 
+	/// Return the headers of the blocks between the given heights, requested
+	/// concurrently in batches
+	fn get_headers(
+		&self,
+		start_height: u64,
+		end_height: u64,
+	) -> Result<Vec<BlockHeaderInfo>, libwallet::Error> {
+		let end_height = cmp::min(end_height, self.get_chain_tip()?.0);
+		if start_height > end_height {
+			return Ok(vec![]);
+		}
+		let addr = self.node_url();
+		let client = Client::new();
+		let heights: Vec<u64> = (start_height..=end_height).collect();
+		let mut headers = Vec::with_capacity(heights.len());
+
+		for chunk in heights.chunks(HEADERS_BATCH_SIZE) {
+			let mut tasks = Vec::new();
+			for height in chunk {
+				let url = format!("{}/v1/headers/{}", addr, height);
+				tasks.push(
+					client.get_async::<api::BlockHeaderPrintable>(
+						url.as_str(),
+						self.node_api_secret(),
+					),
+				);
+			}
+			let task = stream::futures_unordered(tasks).collect();
+			let mut rt = Runtime::new().unwrap();
+			let results = self.timed("get_headers", "/v1/headers/{height}", || rt.block_on(task));
+			let results = match results {
+				Ok(h) => h,
+				Err(e) => {
+					let report = format!("Getting block headers from node: {}", e);
+					error!("Get block headers error: {}", e);
+					return Err(self.node_error(report, &e, false));
+				}
+			};
+			for h in results {
+				headers.push(header_info(h)?);
+			}
+		}
+		headers.sort_by_key(|h| h.height);
+		Ok(headers)
+	}
+
 	/// Get kernel implementation
 	fn get_kernel(
 		&mut self,
@@ -141,16 +353,26 @@ impl NodeClient for HTTPNodeClient {
 	) -> Result<Option<(TxKernel, u64, u64)>, libwallet::Error> {
 		let version = self
 			.get_version_info()
-			.ok_or(libwallet::ErrorKind::ClientCallback(
+			.ok_or(libwallet::ErrorKind::NodeUnreachable(
 				"Unable to get version".into(),
 			))?;
-		let version = Version::parse(&version.node_version)
-			.map_err(|_| libwallet::ErrorKind::ClientCallback("Unable to parse version".into()))?;
-		if version <= Version::new(2, 0, 0) {
-			return Err(libwallet::ErrorKind::ClientCallback(
-				"Kernel lookup not supported by node, please upgrade it".into(),
-			)
-			.into());
+		if !supports_kernel_lookup(&version)? {
+			// the node may have been upgraded since its version was requested
+			let upgraded = match self.check_version_change() {
+				Some((_, current)) => supports_kernel_lookup(&current)?,
+				None => false,
+			};
+			if !upgraded {
+				return Err(libwallet::ErrorKind::NodeIncompatible(format!(
+					"Kernel lookup not supported by node version {}, please upgrade it",
+					version.node_version
+				))
+				.into());
+			}
+		}
+
+		if let Some(kernel) = self.cache.kernel(excess, min_height, max_height) {
+			return Ok(Some(kernel));
 		}
 
 		let mut query = String::new();
@@ -174,11 +396,18 @@ impl NodeClient for HTTPNodeClient {
 			query
 		);
 		let client = Client::new();
-		let res: Option<LocatedTxKernel> = client
-			.get(url.as_str(), self.node_api_secret())
-			.map_err(|e| libwallet::ErrorKind::ClientCallback(format!("Kernel lookup: {}", e)))?;
-
-		Ok(res.map(|k| (k.tx_kernel, k.height, k.mmr_index)))
+		let res: Option<LocatedTxKernel> = self
+			.timed("get_kernel", "/v1/chain/kernels/{excess}", || {
+				client.get(url.as_str(), self.node_api_secret())
+			})
+			.map_err(|e| self.node_error(format!("Kernel lookup: {}", e), &e, true))?;
+
+		let kernel = res.map(|k| (k.tx_kernel, k.height, k.mmr_index));
+		if let Some(k) = kernel.as_ref() {
+			self.cache
+				.insert_kernel(excess, min_height, max_height, k.clone());
+		}
+		Ok(kernel)
 	}
 
 	/// Retrieve outputs from node
@@ -187,6 +416,12 @@ impl NodeClient for HTTPNodeClient {
 		wallet_outputs: Vec<pedersen::Commitment>,
 	) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, libwallet::Error> {
 		let addr = self.node_url();
+		// only the outputs not requested recently are requested again
+		let (mut api_outputs, wallet_outputs) = self.cache.outputs(&wallet_outputs);
+		if wallet_outputs.is_empty() {
+			return Ok(api_outputs);
+		}
+
 		// build the necessary query params -
 		// ?id=xxx&id=yyy&id=zzz
 		let query_params: Vec<String> = wallet_outputs
@@ -194,8 +429,6 @@ impl NodeClient for HTTPNodeClient {
 			.map(|commit| format!("id={}", util::to_hex(commit.as_ref().to_vec())))
 			.collect();
 
-		// build a map of api outputs by commit so we can look them up efficiently
-		let mut api_outputs: HashMap<pedersen::Commitment, (String, u64, u64)> = HashMap::new();
 		let mut tasks = Vec::new();
 
 		let client = Client::new();
@@ -208,23 +441,30 @@ impl NodeClient for HTTPNodeClient {
 		let task = stream::futures_unordered(tasks).collect();
 
 		let mut rt = Runtime::new().unwrap();
-		let results = match rt.block_on(task) {
+		let results = self.timed("get_outputs_from_node", "/v1/chain/outputs/byids", || {
+			rt.block_on(task)
+		});
+		let results = match results {
 			Ok(outputs) => outputs,
 			Err(e) => {
 				let report = format!("Getting outputs by id: {}", e);
 				error!("Outputs by id failed: {}", e);
-				return Err(libwallet::ErrorKind::ClientCallback(report).into());
+				return Err(self.node_error(report, &e, true));
 			}
 		};
 
+		// build a map of api outputs by commit so we can look them up efficiently
+		let mut found = HashMap::new();
 		for res in results {
 			for out in res {
-				api_outputs.insert(
+				found.insert(
 					out.commit.commit(),
 					(util::to_hex(out.commit.to_vec()), out.height, out.mmr_index),
 				);
 			}
 		}
+		self.cache.insert_outputs(&found);
+		api_outputs.extend(found);
 		Ok(api_outputs)
 	}
 
@@ -255,7 +495,10 @@ impl NodeClient for HTTPNodeClient {
 
 		let client = Client::new();
 
-		match client.get::<api::OutputListing>(url.as_str(), self.node_api_secret()) {
+		let res = self.timed("get_outputs_by_pmmr_index", "/v1/txhashset/outputs", || {
+			client.get::<api::OutputListing>(url.as_str(), self.node_api_secret())
+		});
+		match res {
 			Ok(o) => {
 				for out in o.outputs {
 					let is_coinbase = match out.output_type {
@@ -300,7 +543,7 @@ impl NodeClient for HTTPNodeClient {
 					addr, e
 				);
 				let report = format!("outputs by pmmr index: {}", e);
-				Err(libwallet::ErrorKind::ClientCallback(report))?
+				Err(self.node_error(report, &e, false))
 			}
 		}
 	}
@@ -321,16 +564,53 @@ impl NodeClient for HTTPNodeClient {
 
 		let client = Client::new();
 
-		match client.get::<api::OutputListing>(url.as_str(), self.node_api_secret()) {
+		let res = self.timed(
+			"height_range_to_pmmr_indices",
+			"/v1/txhashset/heightstopmmr",
+			|| client.get::<api::OutputListing>(url.as_str(), self.node_api_secret()),
+		);
+		match res {
 			Ok(o) => Ok((o.last_retrieved_index, o.highest_index)),
 			Err(e) => {
 				// if we got anything other than 200 back from server, bye
 				error!("heightstopmmr: error contacting {}. Error: {}", addr, e);
-				let report = format!(": {}", e);
-				Err(libwallet::ErrorKind::ClientCallback(report))?
+				let report = format!("Height range to pmmr indices: {}", e);
+				Err(self.node_error(report, &e, false))
 			}
 		}
 	}
+
+	fn request_metrics(&self) -> Vec<NodeRequestMetrics> {
+		self.metrics.snapshot()
+	}
+}
+
+/// The wallet error kind of a failed node API call, as described in `node_error`.
+/// Whether the node is syncing is only asked for server errors, which syncing may
+/// explain, so other failures don't cost another request to the node.
+fn node_error_kind<F>(
+	e: &ClientErrorKind,
+	report: String,
+	lookup: bool,
+	syncing: F,
+) -> libwallet::ErrorKind
+where
+	F: Fn() -> bool,
+{
+	match e {
+		ClientErrorKind::ConnectionError(_) => libwallet::ErrorKind::NodeUnreachable(report),
+		ClientErrorKind::ResponseCode(401, _) | ClientErrorKind::ResponseCode(403, _) => {
+			libwallet::ErrorKind::NodeAuthFailed(report)
+		}
+		ClientErrorKind::ResponseCode(404, _) if lookup => {
+			libwallet::ErrorKind::NodeOutputNotFound(report)
+		}
+		ClientErrorKind::ResponseCode(404, _) => libwallet::ErrorKind::NodeIncompatible(report),
+		ClientErrorKind::ResponseCode(c, _) if *c >= 500 && syncing() => {
+			libwallet::ErrorKind::NodeSyncing(report)
+		}
+		_ => libwallet::ErrorKind::ClientCallback(report),
+	}
 }
 
 /*
@@ -359,3 +639,198 @@ fn single_create_coinbase(url: &str, block_fees: &BlockFees) -> Result<CbData, E
 	))?;
 	Ok(res)
 }*/
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::libwallet::subscribe_events;
+	use serde_json::json;
+	use std::cell::Cell;
+	use std::io::{BufRead, BufReader, Write};
+	use std::net::TcpListener;
+	use std::thread;
+
+	/// Run a node answering the version it's set to, and 404 to any other request,
+	/// returning its URL
+	fn mock_node(version: Arc<Mutex<String>>) -> String {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = listener.local_addr().unwrap();
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let mut stream = match stream {
+					Ok(s) => s,
+					Err(_) => continue,
+				};
+				let mut reader = BufReader::new(&stream);
+				let mut request_line = String::new();
+				let _ = reader.read_line(&mut request_line);
+				loop {
+					let mut line = String::new();
+					match reader.read_line(&mut line) {
+						Ok(n) if n > 0 && !line.trim_end().is_empty() => {}
+						_ => break,
+					}
+				}
+				let (status, body) = match request_line.split(' ').nth(1) {
+					Some("/v1/version") => (
+						"200 OK",
+						json!({"node_version": version.lock().clone(), "block_header_version": 6})
+							.to_string(),
+					),
+					_ => ("404 Not Found", String::new()),
+				};
+				let res = format!(
+					"HTTP/1.1 {}\r\nContent-Type: application/json\r\n\
+					 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+					status,
+					body.len(),
+					body
+				);
+				let _ = stream.write_all(res.as_bytes());
+			}
+		});
+		format!("http://{}", addr)
+	}
+
+	/// Versions of the node version changes among the events
+	fn version_changes(events: &std::sync::mpsc::Receiver<WalletEvent>) -> Vec<(String, String)> {
+		events
+			.try_iter()
+			.filter_map(|e| match e {
+				WalletEvent::NodeVersionChanged {
+					previous_version,
+					node_version,
+				} => Some((previous_version, node_version)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	fn node_version_change() {
+		let version = Arc::new(Mutex::new("3.0.0".to_owned()));
+		let mut client = HTTPNodeClient::new(&mock_node(version.clone()), None);
+		let events = subscribe_events();
+		assert_eq!(client.get_version_info().unwrap().node_version, "3.0.0");
+
+		// a failover to an older node is reported as such, rather than as the
+		// endpoints it's missing
+		*version.lock() = "1.0.0".to_owned();
+		match client.get_chain_tip() {
+			Err(e) => match e.kind() {
+				libwallet::ErrorKind::NodeIncompatible(m) => {
+					assert!(m.contains("from 3.0.0 to 1.0.0"), "{}", m)
+				}
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("chain tip of a node without the endpoint"),
+		}
+		assert_eq!(
+			version_changes(&events),
+			vec![("3.0.0".to_owned(), "1.0.0".to_owned())]
+		);
+
+		// the kernel lookups it doesn't support are refused without being requested
+		let excess = pedersen::Commitment::from_vec(vec![0; 33]);
+		match client.get_kernel(&excess, None, None) {
+			Err(e) => match e.kind() {
+				libwallet::ErrorKind::NodeIncompatible(m) => {
+					assert!(m.contains("version 1.0.0"), "{}", m)
+				}
+				k => panic!("unexpected error {}", k),
+			},
+			Ok(_) => panic!("kernel lookup on a node not supporting it"),
+		}
+
+		// and the change is only reported once
+		assert!(client.get_chain_tip().is_err());
+		assert!(version_changes(&events).is_empty());
+	}
+
+	#[test]
+	fn node_error_mapping() {
+		let asked = Cell::new(0);
+		let syncing = |s: bool| {
+			let asked = &asked;
+			move || {
+				asked.set(asked.get() + 1);
+				s
+			}
+		};
+		let kind = |e: ClientErrorKind, lookup: bool, s: bool| {
+			node_error_kind(&e, "report".to_owned(), lookup, syncing(s))
+		};
+
+		match kind(
+			ClientErrorKind::ConnectionError("refused".to_owned()),
+			false,
+			true,
+		) {
+			libwallet::ErrorKind::NodeUnreachable(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		for code in vec![401, 403] {
+			match kind(
+				ClientErrorKind::ResponseCode(code, String::new()),
+				false,
+				true,
+			) {
+				libwallet::ErrorKind::NodeAuthFailed(_) => {}
+				k => panic!("unexpected error {}", k),
+			}
+		}
+		match kind(
+			ClientErrorKind::ResponseCode(404, String::new()),
+			true,
+			true,
+		) {
+			libwallet::ErrorKind::NodeOutputNotFound(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		match kind(
+			ClientErrorKind::ResponseCode(404, String::new()),
+			false,
+			true,
+		) {
+			libwallet::ErrorKind::NodeIncompatible(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		// a response that can't be parsed isn't taken for an incompatible node
+		match kind(
+			ClientErrorKind::ResponseError("parse".to_owned()),
+			false,
+			true,
+		) {
+			libwallet::ErrorKind::ClientCallback(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		match kind(
+			ClientErrorKind::ResponseCode(400, String::new()),
+			false,
+			true,
+		) {
+			libwallet::ErrorKind::ClientCallback(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		assert_eq!(asked.get(), 0);
+
+		// only server errors ask whether the node is syncing
+		match kind(
+			ClientErrorKind::ResponseCode(503, String::new()),
+			false,
+			true,
+		) {
+			libwallet::ErrorKind::NodeSyncing(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		match kind(
+			ClientErrorKind::ResponseCode(500, String::new()),
+			false,
+			false,
+		) {
+			libwallet::ErrorKind::ClientCallback(_) => {}
+			k => panic!("unexpected error {}", k),
+		}
+		assert_eq!(asked.get(), 2);
+	}
+}
@@ -0,0 +1,409 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Node client reading the chain of a node running in the same process as the
+//! wallet, so the wallet can be embedded in an application without a separate
+//! node to talk to over HTTP
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::metrics::NodeMetrics;
+use crate::api;
+use crate::chain::Chain;
+use crate::core::core::hash::Hashed;
+use crate::core::core::{BlockHeader, OutputFeatures, OutputIdentifier, Transaction, TxKernel};
+use crate::core::ser;
+use crate::libwallet::{
+	self, BlockHeaderInfo, NodeClient, NodeRequestMetrics, NodeVersionInfo, TxWrapper,
+};
+use crate::util::secp::pedersen;
+use crate::util::{self, to_hex};
+
+/// Node URL reported by the client, as there's no URL to reach the node at
+const LOCAL_NODE_URL: &str = "local";
+
+/// Pattern under which the requests to the node are timed
+const LOCAL_URL_PATTERN: &str = "in-process";
+
+/// Node running in the wallet's process, whose chain the wallet reads directly
+pub trait LocalNode: Send + Sync {
+	/// Chain of the node
+	fn chain(&self) -> Arc<Chain>;
+
+	/// Semver version of the node
+	fn version(&self) -> String;
+
+	/// Add a transaction to the node's pool, to be stemmed unless fluffed
+	fn post_tx(&self, tx: Transaction, fluff: bool) -> Result<(), libwallet::Error>;
+}
+
+/// Error of a request to the node
+fn node_error<E: Display>(context: &str, e: E) -> libwallet::Error {
+	libwallet::ErrorKind::ClientCallback(format!("{}: {}", context, e)).into()
+}
+
+/// Header info of a block header of the node's chain
+fn header_info(h: &BlockHeader) -> BlockHeaderInfo {
+	BlockHeaderInfo {
+		height: h.height,
+		hash: to_hex(h.hash().to_vec()),
+		timestamp: h.timestamp,
+	}
+}
+
+/// Client of a node running in the same process as the wallet
+#[derive(Clone)]
+pub struct LocalNodeClient {
+	node: Arc<dyn LocalNode>,
+	/// Latency statistics, shared by the clones of the client
+	metrics: NodeMetrics,
+}
+
+impl LocalNodeClient {
+	/// Create a client of the given node
+	pub fn new(node: Arc<dyn LocalNode>) -> LocalNodeClient {
+		LocalNodeClient {
+			node,
+			metrics: NodeMetrics::new(),
+		}
+	}
+
+	/// Set the duration above which requests to the node are logged, in milliseconds
+	pub fn set_slow_request_ms(&mut self, ms: u64) {
+		self.metrics.set_slow_request_ms(ms);
+	}
+
+	/// Make a request to the node, timing it under the given method
+	fn timed<T, F>(&self, method: &'static str, f: F) -> Result<T, libwallet::Error>
+	where
+		F: FnOnce(&Chain) -> Result<T, libwallet::Error>,
+	{
+		let start = Instant::now();
+		let res = f(&self.node.chain());
+		self.metrics
+			.record(method, LOCAL_URL_PATTERN, start.elapsed(), res.is_ok());
+		res
+	}
+}
+
+impl NodeClient for LocalNodeClient {
+	fn node_url(&self) -> &str {
+		LOCAL_NODE_URL
+	}
+
+	/// The node is always the one running in the process
+	fn set_node_url(&mut self, _node_url: &str) {}
+
+	fn node_api_secret(&self) -> Option<String> {
+		None
+	}
+
+	fn set_node_api_secret(&mut self, _node_api_secret: Option<String>) {}
+
+	fn post_tx(&self, tx: &TxWrapper, fluff: bool) -> Result<(), libwallet::Error> {
+		let tx_bin = util::from_hex(tx.tx_hex.clone())
+			.map_err(|e| node_error("Decoding the transaction", e))?;
+		let tx: Transaction = ser::deserialize(&mut &tx_bin[..], ser::ProtocolVersion(1))
+			.map_err(|e| node_error("Deserializing the transaction", e))?;
+		let start = Instant::now();
+		let res = self.node.post_tx(tx, fluff);
+		self.metrics
+			.record("post_tx", LOCAL_URL_PATTERN, start.elapsed(), res.is_ok());
+		if let Err(e) = res.as_ref() {
+			error!("Post TX Error: {}", e);
+		}
+		res
+	}
+
+	fn get_version_info(&mut self) -> Option<NodeVersionInfo> {
+		let res = self.timed("get_version_info", |chain| {
+			let head = chain
+				.head_header()
+				.map_err(|e| node_error("Getting the chain head", e))?;
+			Ok(NodeVersionInfo {
+				node_version: self.node.version(),
+				block_header_version: head.version.0,
+				verified: Some(true),
+			})
+		});
+		match res {
+			Ok(v) => Some(v),
+			Err(e) => {
+				error!("Unable to get the version of the local node: {}", e);
+				None
+			}
+		}
+	}
+
+	fn get_chain_tip(&self) -> Result<(u64, String), libwallet::Error> {
+		self.timed("get_chain_tip", |chain| {
+			let head = chain
+				.head()
+				.map_err(|e| node_error("Getting chain height from node", e))?;
+			Ok((head.height, to_hex(head.last_block_h.to_vec())))
+		})
+	}
+
+	fn get_header_info(&self, height: u64) -> Result<Option<BlockHeaderInfo>, libwallet::Error> {
+		self.timed("get_header_info", |chain| {
+			Ok(chain
+				.get_header_by_height(height)
+				.ok()
+				.map(|h| header_info(&h)))
+		})
+	}
+
+	fn get_headers(
+		&self,
+		start_height: u64,
+		end_height: u64,
+	) -> Result<Vec<BlockHeaderInfo>, libwallet::Error> {
+		self.timed("get_headers", |chain| {
+			let mut headers = vec![];
+			for height in start_height..=end_height {
+				match chain.get_header_by_height(height) {
+					Ok(h) => headers.push(header_info(&h)),
+					Err(_) => break,
+				}
+			}
+			Ok(headers)
+		})
+	}
+
+	fn get_kernel(
+		&mut self,
+		excess: &pedersen::Commitment,
+		min_height: Option<u64>,
+		max_height: Option<u64>,
+	) -> Result<Option<(TxKernel, u64, u64)>, libwallet::Error> {
+		self.timed("get_kernel", |chain| {
+			chain
+				.get_kernel_height(excess, min_height, max_height)
+				.map_err(|e| node_error("Getting kernel from node", e))
+		})
+	}
+
+	fn get_outputs_from_node(
+		&self,
+		wallet_outputs: Vec<pedersen::Commitment>,
+	) -> Result<HashMap<pedersen::Commitment, (String, u64, u64)>, libwallet::Error> {
+		self.timed("get_outputs_from_node", |chain| {
+			let mut api_outputs = HashMap::new();
+			for commit in wallet_outputs {
+				// the node's output set is keyed by features and commitment
+				for features in [OutputFeatures::Plain, OutputFeatures::Coinbase].iter() {
+					let id = OutputIdentifier::new(*features, &commit);
+					if chain.is_unspent(&id).is_err() {
+						continue;
+					}
+					let height = chain
+						.get_header_for_output(&id)
+						.map_err(|e| node_error("Getting the block of an output", e))?
+						.height;
+					let mmr_index = chain.get_output_pos(&commit).unwrap_or(0);
+					api_outputs.insert(
+						commit.clone(),
+						(to_hex(commit.as_ref().to_vec()), height, mmr_index),
+					);
+					break;
+				}
+			}
+			Ok(api_outputs)
+		})
+	}
+
+	fn get_outputs_by_pmmr_index(
+		&self,
+		start_index: u64,
+		end_index: Option<u64>,
+		max_outputs: u64,
+	) -> Result<
+		(
+			u64,
+			u64,
+			Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+		),
+		libwallet::Error,
+	> {
+		let chain = self.node.chain();
+		self.timed("get_outputs_by_pmmr_index", |_| {
+			let (last_retrieved_index, highest_index, outputs) = chain
+				.unspent_outputs_by_pmmr_index(start_index, max_outputs, end_index)
+				.map_err(|e| node_error("Getting outputs by PMMR index from node", e))?;
+			let mut api_outputs = Vec::with_capacity(outputs.len());
+			for output in outputs.iter() {
+				let out =
+					api::OutputPrintable::from_output(output, chain.clone(), None, true, false)
+						.map_err(|e| node_error("Reading an output of the node", e))?;
+				let is_coinbase = match out.output_type {
+					api::OutputType::Coinbase => true,
+					api::OutputType::Transaction => false,
+				};
+				let proof = out
+					.range_proof()
+					.map_err(|e| node_error("Reading the range proof of an output", e))?;
+				api_outputs.push((
+					out.commit,
+					proof,
+					is_coinbase,
+					out.block_height.unwrap_or(0),
+					out.mmr_index,
+				));
+			}
+			Ok((highest_index, last_retrieved_index, api_outputs))
+		})
+	}
+
+	fn height_range_to_pmmr_indices(
+		&self,
+		start_height: u64,
+		end_height: Option<u64>,
+	) -> Result<(u64, u64), libwallet::Error> {
+		self.timed("height_range_to_pmmr_indices", |chain| {
+			chain
+				.block_height_range_to_pmmr_indices(start_height, end_height)
+				.map_err(|e| node_error("Getting outputs within height range from node", e))
+		})
+	}
+
+	fn request_metrics(&self) -> Vec<NodeRequestMetrics> {
+		self.metrics.snapshot()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::core::global::{self, ChainTypes};
+	use crate::keychain::ExtKeychain;
+	use crate::libwallet::WalletInst;
+	use crate::test_framework::{self, WalletProxy};
+	use crate::util::{Mutex, ZeroingString};
+	use crate::{DefaultLCProvider, DefaultWalletImpl};
+	use std::fs;
+
+	/// Node of the test framework's chain, keeping the transactions posted to it
+	struct MockNode {
+		chain: Arc<Chain>,
+		posted: Mutex<Vec<(Transaction, bool)>>,
+	}
+
+	impl LocalNode for MockNode {
+		fn chain(&self) -> Arc<Chain> {
+			self.chain.clone()
+		}
+
+		fn version(&self) -> String {
+			"3.4.0".to_owned()
+		}
+
+		fn post_tx(&self, tx: Transaction, fluff: bool) -> Result<(), libwallet::Error> {
+			self.posted.lock().push((tx, fluff));
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn local_node_client() {
+		let test_dir = "target/test_output/local_node";
+		let _ = fs::remove_dir_all(test_dir);
+		global::set_mining_mode(ChainTypes::AutomatedTesting);
+		let proxy: WalletProxy<
+			DefaultLCProvider<LocalNodeClient, ExtKeychain>,
+			LocalNodeClient,
+			ExtKeychain,
+		> = WalletProxy::new(test_dir);
+		let chain = proxy.chain.clone();
+		let node = Arc::new(MockNode {
+			chain: chain.clone(),
+			posted: Mutex::new(vec![]),
+		});
+		let mut client = LocalNodeClient::new(node.clone());
+
+		let version = client.get_version_info().unwrap();
+		assert_eq!(version.node_version, "3.4.0");
+		assert_eq!(version.verified, Some(true));
+
+		// mine a few blocks to a wallet reading the chain through the client
+		let mut wallet =
+			Box::new(DefaultWalletImpl::<LocalNodeClient>::new(client.clone()).unwrap())
+				as Box<
+					dyn WalletInst<
+						DefaultLCProvider<'static, LocalNodeClient, ExtKeychain>,
+						LocalNodeClient,
+						ExtKeychain,
+					>,
+				>;
+		let lc = wallet.lc_provider().unwrap();
+		lc.set_top_level_directory(&format!("{}/wallet", test_dir))
+			.unwrap();
+		lc.create_wallet(None, None, 32, None, ZeroingString::from(""), false)
+			.unwrap();
+		lc.open_wallet(None, ZeroingString::from(""), false, false)
+			.unwrap();
+		let wallet = Arc::new(Mutex::new(wallet));
+		test_framework::award_blocks_to_wallet(&chain, wallet, None, 5, false).unwrap();
+
+		// the chain is read directly
+		let (height, hash) = client.get_chain_tip().unwrap();
+		assert_eq!(height, 5);
+		assert_eq!(hash, to_hex(chain.head().unwrap().last_block_h.to_vec()));
+		assert_eq!(client.get_header_info(5).unwrap().unwrap().hash, hash);
+		assert!(client.get_header_info(6).unwrap().is_none());
+		let headers = client.get_headers(1, 10).unwrap();
+		assert_eq!(headers.len(), 5);
+		assert_eq!(headers[4].hash, hash);
+
+		// with the outputs and kernels of its blocks
+		let (_, _, outputs) = client.get_outputs_by_pmmr_index(1, None, 100).unwrap();
+		assert!(outputs.len() >= 5);
+		assert!(outputs.iter().all(|o| o.2));
+		let commits: Vec<pedersen::Commitment> = outputs.iter().map(|o| o.0.clone()).collect();
+		let found = client.get_outputs_from_node(commits.clone()).unwrap();
+		assert_eq!(found.len(), commits.len());
+		assert!(found.values().all(|o| o.1 >= 1 && o.1 <= 5));
+		let block = chain
+			.get_block(&chain.get_header_by_height(1).unwrap().hash())
+			.unwrap();
+		let excess = block.kernels()[0].excess;
+		let (kernel, kernel_height, _) = client.get_kernel(&excess, None, None).unwrap().unwrap();
+		assert_eq!(kernel.excess, excess);
+		assert_eq!(kernel_height, 1);
+
+		// transactions are handed to the node
+		let tx = Transaction::empty();
+		let tx_hex = to_hex(ser::ser_vec(&tx, ser::ProtocolVersion(1)).unwrap());
+		client.post_tx(&TxWrapper { tx_hex }, true).unwrap();
+		let tx_hex = "not a transaction".to_owned();
+		assert!(client.post_tx(&TxWrapper { tx_hex }, false).is_err());
+		let posted = node.posted.lock();
+		assert_eq!(posted.len(), 1);
+		assert_eq!(posted[0].0.hash(), tx.hash());
+		assert!(posted[0].1);
+
+		// and the requests timed
+		let metrics = client.request_metrics();
+		let tip = metrics
+			.iter()
+			.find(|m| m.method == "get_chain_tip")
+			.unwrap();
+		assert_eq!(tip.url_pattern, LOCAL_URL_PATTERN);
+		assert!(tip.requests > 0);
+
+		let _ = fs::remove_dir_all(test_dir);
+	}
+}
@@ -0,0 +1,172 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timing of the requests made to the node, logging the slow ones and keeping
+//! latency statistics over the latest requests of each method
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::libwallet::NodeRequestMetrics;
+use crate::util::Mutex;
+
+/// Requests slower than this are logged, unless configured otherwise
+const DEFAULT_SLOW_REQUEST_MS: u64 = 5000;
+
+/// Number of latest requests of a method the latency statistics are computed over
+const LATENCY_WINDOW: usize = 100;
+
+/// Requests made with a method of the node client
+struct MethodSamples {
+	url_pattern: &'static str,
+	requests: u64,
+	failures: u64,
+	slow_requests: u64,
+	/// Durations of the latest requests, in milliseconds, oldest first
+	latencies: VecDeque<u64>,
+}
+
+/// Request metrics of a node client, shared by its clones
+#[derive(Clone)]
+pub(crate) struct NodeMetrics {
+	slow_request_ms: u64,
+	methods: Arc<Mutex<HashMap<&'static str, MethodSamples>>>,
+}
+
+impl NodeMetrics {
+	/// No requests yet
+	pub fn new() -> NodeMetrics {
+		NodeMetrics {
+			slow_request_ms: DEFAULT_SLOW_REQUEST_MS,
+			methods: Arc::new(Mutex::new(HashMap::new())),
+		}
+	}
+
+	/// Duration above which requests are logged, in milliseconds
+	pub fn set_slow_request_ms(&mut self, ms: u64) {
+		self.slow_request_ms = ms;
+	}
+
+	/// Record a request made with the given method to a URL of the given pattern
+	pub fn record(
+		&self,
+		method: &'static str,
+		url_pattern: &'static str,
+		took: Duration,
+		ok: bool,
+	) {
+		let ms = took.as_millis() as u64;
+		let slow = ms > self.slow_request_ms;
+		if slow {
+			warn!(
+				"Slow node request: {} {} took {} ms{}",
+				method,
+				url_pattern,
+				ms,
+				if ok { "" } else { " and failed" }
+			);
+		}
+		let mut methods = self.methods.lock();
+		let samples = methods.entry(method).or_insert_with(|| MethodSamples {
+			url_pattern,
+			requests: 0,
+			failures: 0,
+			slow_requests: 0,
+			latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+		});
+		samples.requests += 1;
+		if !ok {
+			samples.failures += 1;
+		}
+		if slow {
+			samples.slow_requests += 1;
+		}
+		if samples.latencies.len() == LATENCY_WINDOW {
+			samples.latencies.pop_front();
+		}
+		samples.latencies.push_back(ms);
+	}
+
+	/// Statistics of each method used so far, ordered by method
+	pub fn snapshot(&self) -> Vec<NodeRequestMetrics> {
+		let methods = self.methods.lock();
+		let mut retval: Vec<NodeRequestMetrics> = methods
+			.iter()
+			.map(|(method, s)| {
+				let mut latencies: Vec<u64> = s.latencies.iter().cloned().collect();
+				latencies.sort();
+				let count = latencies.len() as u64;
+				// nearest rank
+				let p95 = (latencies.len() * 95 + 99) / 100;
+				NodeRequestMetrics {
+					method: method.to_string(),
+					url_pattern: s.url_pattern.to_owned(),
+					requests: s.requests,
+					failures: s.failures,
+					slow_requests: s.slow_requests,
+					avg_ms: latencies.iter().sum::<u64>() / count,
+					p95_ms: latencies[p95.saturating_sub(1)],
+					max_ms: latencies[latencies.len() - 1],
+				}
+			})
+			.collect();
+		retval.sort_by(|a, b| a.method.cmp(&b.method));
+		retval
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn node_metrics_rolling_latencies() {
+		let mut metrics = NodeMetrics::new();
+		metrics.set_slow_request_ms(150);
+		let clone = metrics.clone();
+		for ms in 1..=200 {
+			clone.record(
+				"get_chain_tip",
+				"/v1/chain",
+				Duration::from_millis(ms),
+				true,
+			);
+		}
+		metrics.record(
+			"post_tx",
+			"/v1/pool/push_tx",
+			Duration::from_millis(10),
+			false,
+		);
+
+		let snapshot = metrics.snapshot();
+		assert_eq!(snapshot.len(), 2);
+		let tip = &snapshot[0];
+		assert_eq!(tip.method, "get_chain_tip");
+		assert_eq!(tip.url_pattern, "/v1/chain");
+		assert_eq!(tip.requests, 200);
+		assert_eq!(tip.failures, 0);
+		assert_eq!(tip.slow_requests, 50);
+		// only the latest 100 requests, of 101 to 200 ms, are in the statistics
+		assert_eq!(tip.avg_ms, 150);
+		assert_eq!(tip.p95_ms, 195);
+		assert_eq!(tip.max_ms, 200);
+
+		let post = &snapshot[1];
+		assert_eq!(post.requests, 1);
+		assert_eq!(post.failures, 1);
+		assert_eq!(post.p95_ms, 10);
+	}
+}
@@ -12,6 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod cache;
+#[cfg(feature = "embedded_node")]
+mod embedded;
 mod http;
+#[cfg(feature = "embedded_node")]
+mod local;
+mod metrics;
 
+pub use self::cache::{DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS};
+#[cfg(feature = "embedded_node")]
+pub use self::embedded::EmbeddedNode;
 pub use self::http::HTTPNodeClient;
+#[cfg(feature = "embedded_node")]
+pub use self::local::{LocalNode, LocalNodeClient};
@@ -0,0 +1,237 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Price feeds, reading the exchange rates of EPIC to fiat currencies from a file
+//! of static rates or from a JSON endpoint
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
+use crate::client_utils::Client;
+use crate::config::PriceFeedConfig;
+use crate::libwallet::{Error, ErrorKind, PriceFeed};
+
+/// JSONPath of the price in an endpoint's response, if not configured
+const DEFAULT_JSON_PATH: &str = "$";
+
+/// Step of a JSONPath into a JSON value
+#[derive(Debug, Clone, PartialEq)]
+enum PathStep {
+	/// Member of an object
+	Key(String),
+	/// Element of an array
+	Index(usize),
+}
+
+/// Steps of a JSONPath of the forms `$.a.b`, `$['a'].b` and `$.a[0]`, the other
+/// JSONPath expressions such as wildcards and filters not picking a single value
+fn parse_json_path(path: &str) -> Result<Vec<PathStep>, String> {
+	let invalid = || format!("Invalid JSONPath: {}", path);
+	let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+	let mut steps = vec![];
+	while !rest.is_empty() {
+		if let Some(r) = rest.strip_prefix('.') {
+			let end = r.find(|c| c == '.' || c == '[').unwrap_or(r.len());
+			if end == 0 {
+				return Err(invalid());
+			}
+			steps.push(PathStep::Key(r[..end].to_owned()));
+			rest = &r[end..];
+		} else if let Some(r) = rest.strip_prefix('[') {
+			let end = r.find(']').ok_or_else(invalid)?;
+			let inner = &r[..end];
+			let quoted = |q| inner.strip_prefix(q).and_then(|i| i.strip_suffix(q));
+			let step = match quoted('\'').or_else(|| quoted('"')) {
+				Some(key) => PathStep::Key(key.to_owned()),
+				None => PathStep::Index(inner.parse().map_err(|_| invalid())?),
+			};
+			steps.push(step);
+			rest = &r[end + 1..];
+		} else {
+			return Err(invalid());
+		}
+	}
+	Ok(steps)
+}
+
+/// Value the steps of a JSONPath lead to
+fn json_path_value<'a>(
+	value: &'a serde_json::Value,
+	steps: &[PathStep],
+) -> Option<&'a serde_json::Value> {
+	steps.iter().try_fold(value, |v, step| match step {
+		PathStep::Key(k) => v.get(k),
+		PathStep::Index(i) => v.get(i),
+	})
+}
+
+/// Price held by a value, a number or a string of one
+fn price_value(value: &serde_json::Value) -> Option<f64> {
+	let price = match value {
+		serde_json::Value::Number(n) => n.as_f64(),
+		serde_json::Value::String(s) => s.trim().parse().ok(),
+		_ => None,
+	};
+	price.filter(|p: &f64| p.is_finite() && *p > 0.0)
+}
+
+/// Reads the prices from a JSON file of the price of one EPIC by currency, e.g.
+/// `{"USD": 0.25, "EUR": "0.23"}`, read again on each request so it can be updated
+/// while the wallet runs
+pub struct StaticPriceFeed {
+	path: String,
+}
+
+impl StaticPriceFeed {
+	/// Create a feed reading the file at the given path
+	pub fn new(path: &str) -> StaticPriceFeed {
+		StaticPriceFeed {
+			path: path.to_owned(),
+		}
+	}
+}
+
+impl PriceFeed for StaticPriceFeed {
+	fn price(&self, currency: &str) -> Result<f64, Error> {
+		let contents = fs::read_to_string(&self.path).map_err(|e| {
+			ErrorKind::GenericError(format!("Reading the prices of {}: {}", self.path, e))
+		})?;
+		let prices: HashMap<String, serde_json::Value> =
+			serde_json::from_str(&contents).map_err(|e| {
+				ErrorKind::GenericError(format!("Parsing the prices of {}: {}", self.path, e))
+			})?;
+		prices
+			.iter()
+			.find(|(c, _)| c.eq_ignore_ascii_case(currency))
+			.and_then(|(_, p)| price_value(p))
+			.ok_or_else(|| {
+				ErrorKind::GenericError(format!("No price in {} in {}", currency, self.path)).into()
+			})
+	}
+}
+
+/// Reads the prices from a JSON endpoint, picking the price out of its response
+/// with a JSONPath
+pub struct HttpPriceFeed {
+	url: String,
+	json_path: String,
+	steps: Vec<PathStep>,
+}
+
+impl HttpPriceFeed {
+	/// Create a feed requesting the URL, `{currency}` in the URL and the JSONPath
+	/// being replaced by the lowercase currency code
+	pub fn new(url: &str, json_path: &str) -> Result<HttpPriceFeed, Error> {
+		// checked with a currency in place, as the path is only complete with one
+		let steps = parse_json_path(&json_path.replace("{currency}", "usd"))
+			.map_err(ErrorKind::GenericError)?;
+		Ok(HttpPriceFeed {
+			url: url.to_owned(),
+			json_path: json_path.to_owned(),
+			steps,
+		})
+	}
+}
+
+impl PriceFeed for HttpPriceFeed {
+	fn price(&self, currency: &str) -> Result<f64, Error> {
+		let currency = currency.to_lowercase();
+		let url = self.url.replace("{currency}", &currency);
+		let steps = match self.json_path.contains("{currency}") {
+			true => parse_json_path(&self.json_path.replace("{currency}", &currency))
+				.map_err(ErrorKind::GenericError)?,
+			false => self.steps.clone(),
+		};
+		let response: serde_json::Value = Client::new().get(&url, None).map_err(|e| {
+			ErrorKind::GenericError(format!("Requesting the price from {}: {}", url, e))
+		})?;
+		json_path_value(&response, &steps)
+			.and_then(price_value)
+			.ok_or_else(|| {
+				ErrorKind::GenericError(format!(
+					"No price at {} in the response of {}",
+					self.json_path, url
+				))
+				.into()
+			})
+	}
+}
+
+/// Build the price feed described by the configuration
+pub fn price_feed_from_config(config: &PriceFeedConfig) -> Result<Arc<dyn PriceFeed>, Error> {
+	match (config.file.as_ref(), config.url.as_ref()) {
+		(Some(f), None) => Ok(Arc::new(StaticPriceFeed::new(f))),
+		(None, Some(u)) => {
+			let json_path = config
+				.json_path
+				.as_ref()
+				.map(|p| p.as_str())
+				.unwrap_or(DEFAULT_JSON_PATH);
+			Ok(Arc::new(HttpPriceFeed::new(u, json_path)?))
+		}
+		_ => Err(ErrorKind::GenericError(
+			"The price feed needs exactly one of a file and a URL".to_owned(),
+		)
+		.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn price_feed_json_path() {
+		let response = json!({"epic-cash": {"usd": 0.25, "eur": "0.23"}, "data": [{"price": 1.5}]});
+		let price = |path: &str| {
+			let steps = parse_json_path(path).unwrap();
+			json_path_value(&response, &steps).and_then(price_value)
+		};
+		assert_eq!(price("$['epic-cash'].usd"), Some(0.25));
+		assert_eq!(price("$[\"epic-cash\"]['eur']"), Some(0.23));
+		assert_eq!(price("$.data[0].price"), Some(1.5));
+		assert_eq!(price("$.data[1].price"), None);
+		assert_eq!(price("$['epic-cash']"), None);
+		assert_eq!(price_value(&json!(12)), Some(12.0));
+		assert!(parse_json_path("epic.usd").is_err());
+		assert!(parse_json_path("$..usd").is_err());
+		assert!(parse_json_path("$.data[*]").is_err());
+
+		let file = std::env::temp_dir().join("epic_price_feed.json");
+		fs::write(&file, r#"{"USD": 0.25, "eur": "0.23", "GBP": 0}"#).unwrap();
+		let config = PriceFeedConfig {
+			file: Some(file.to_str().unwrap().to_owned()),
+			url: None,
+			json_path: None,
+		};
+		let feed = price_feed_from_config(&config).unwrap();
+		assert_eq!(feed.price("usd").unwrap(), 0.25);
+		assert_eq!(feed.price("EUR").unwrap(), 0.23);
+		assert!(feed.price("GBP").is_err());
+		assert!(feed.price("JPY").is_err());
+
+		let config = PriceFeedConfig {
+			file: None,
+			url: Some("https://prices.example/{currency}".to_owned()),
+			json_path: Some("$.prices[".to_owned()),
+		};
+		assert!(price_feed_from_config(&config).is_err());
+		let config = PriceFeedConfig {
+			url: None,
+			..config
+		};
+		assert!(price_feed_from_config(&config).is_err());
+	}
+}
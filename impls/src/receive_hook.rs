@@ -0,0 +1,284 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receive hooks, checking incoming slates before the listeners sign them
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::ReceiveHookConfig;
+use crate::core::core::amount_to_hr_string;
+use crate::libwallet::{Error, ErrorKind, ReceiveHook, Slate, SlateVersion, VersionedSlate};
+
+/// Built-in policy on the amount and sender message of incoming slates
+pub struct PolicyReceiveHook {
+	min_amount: Option<u64>,
+	max_amount: Option<u64>,
+	message_prefix: Option<String>,
+}
+
+impl PolicyReceiveHook {
+	/// Create the policy from the receive hook configuration
+	pub fn new(config: &ReceiveHookConfig) -> PolicyReceiveHook {
+		PolicyReceiveHook {
+			min_amount: config.min_amount,
+			max_amount: config.max_amount,
+			message_prefix: config.message_prefix.clone(),
+		}
+	}
+}
+
+impl ReceiveHook for PolicyReceiveHook {
+	fn check(&self, slate: &Slate) -> Result<(), Error> {
+		if let Some(min) = self.min_amount {
+			if slate.amount < min {
+				let reason = format!(
+					"amount below minimum of {}",
+					amount_to_hr_string(min, false)
+				);
+				return Err(ErrorKind::ReceiveRejected(reason).into());
+			}
+		}
+		if let Some(max) = self.max_amount {
+			if slate.amount > max {
+				let reason = format!(
+					"amount above maximum of {}",
+					amount_to_hr_string(max, false)
+				);
+				return Err(ErrorKind::ReceiveRejected(reason).into());
+			}
+		}
+		if let Some(prefix) = self.message_prefix.as_ref() {
+			let message = slate
+				.participant_data
+				.iter()
+				.find(|p| p.id == 0)
+				.and_then(|p| p.message.as_ref());
+			match message {
+				Some(m) if m.starts_with(prefix.as_str()) => {}
+				_ => {
+					let reason = format!("sender message must start with '{}'", prefix);
+					return Err(ErrorKind::ReceiveRejected(reason).into());
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Seconds a receive hook command has to decide if not configured
+const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 30;
+
+/// Interval at which a running receive hook command is checked for its exit
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Read all of an output of a command in another thread
+fn read_aside<R: Read + Send + 'static>(r: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+	thread::spawn(move || {
+		let mut out = vec![];
+		if let Some(mut r) = r {
+			let _ = r.read_to_end(&mut out);
+		}
+		out
+	})
+}
+
+/// Runs an external command with the incoming slate as JSON on its standard input.
+/// The slate is accepted if the command exits successfully, otherwise the first line
+/// of its output is returned to the sender as the reason. A command still running
+/// after the timeout is killed and the slate rejected.
+pub struct CommandReceiveHook {
+	command: String,
+	timeout: Duration,
+}
+
+impl CommandReceiveHook {
+	/// Create a hook running the given command line through the system shell
+	pub fn new(command: &str, timeout: Duration) -> CommandReceiveHook {
+		CommandReceiveHook {
+			command: command.to_owned(),
+			timeout,
+		}
+	}
+
+	fn shell_command(&self) -> Command {
+		if cfg!(windows) {
+			let mut c = Command::new("cmd");
+			c.arg("/C").arg(&self.command);
+			c
+		} else {
+			let mut c = Command::new("sh");
+			c.arg("-c").arg(&self.command);
+			c
+		}
+	}
+}
+
+impl ReceiveHook for CommandReceiveHook {
+	fn check(&self, slate: &Slate) -> Result<(), Error> {
+		let versioned = VersionedSlate::into_version(slate.clone(), SlateVersion::V3);
+		let json = serde_json::to_string(&versioned).map_err(|_| ErrorKind::SlateSer)?;
+
+		let mut child = self
+			.shell_command()
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()
+			.map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to run receive hook command: {}", e))
+			})?;
+		// the pipes are written and read aside, so a command which doesn't read its
+		// input or fills its output can't block the wait for its exit
+		if let Some(mut stdin) = child.stdin.take() {
+			// the command may decide without reading all of its input
+			thread::spawn(move || stdin.write_all(json.as_bytes()));
+		}
+		let stdout = read_aside(child.stdout.take());
+		let stderr = read_aside(child.stderr.take());
+
+		let deadline = Instant::now() + self.timeout;
+		let status = loop {
+			if let Some(status) = child.try_wait()? {
+				break status;
+			}
+			if Instant::now() >= deadline {
+				let _ = child.kill();
+				let _ = child.wait();
+				let reason = "receive hook timed out".to_owned();
+				warn!("Incoming transaction {} rejected: {}", slate.id, reason);
+				return Err(ErrorKind::ReceiveRejected(reason).into());
+			}
+			thread::sleep(COMMAND_POLL_INTERVAL);
+		};
+		if status.success() {
+			return Ok(());
+		}
+
+		let stdout = stdout.join().unwrap_or_default();
+		let stderr = stderr.join().unwrap_or_default();
+		let stdout = String::from_utf8_lossy(&stdout);
+		let stderr = String::from_utf8_lossy(&stderr);
+		let reason = stdout
+			.lines()
+			.chain(stderr.lines())
+			.map(|l| l.trim())
+			.find(|l| !l.is_empty())
+			.unwrap_or("rejected by receive hook")
+			.to_owned();
+		warn!("Incoming transaction {} rejected: {}", slate.id, reason);
+		Err(ErrorKind::ReceiveRejected(reason).into())
+	}
+}
+
+/// All configured checks, applied in order until one rejects the slate
+struct ReceiveHooks(Vec<Box<dyn ReceiveHook>>);
+
+impl ReceiveHook for ReceiveHooks {
+	fn check(&self, slate: &Slate) -> Result<(), Error> {
+		for hook in self.0.iter() {
+			hook.check(slate)?;
+		}
+		Ok(())
+	}
+}
+
+/// Build the receive hook described by the configuration: the built-in
+/// policy followed by the external command, if set
+pub fn receive_hook_from_config(config: &ReceiveHookConfig) -> Arc<dyn ReceiveHook> {
+	let mut hooks: Vec<Box<dyn ReceiveHook>> = vec![Box::new(PolicyReceiveHook::new(config))];
+	if let Some(c) = config.command.as_ref() {
+		let timeout = config
+			.command_timeout_secs
+			.unwrap_or(DEFAULT_COMMAND_TIMEOUT_SECS);
+		hooks.push(Box::new(CommandReceiveHook::new(
+			c,
+			Duration::from_secs(timeout),
+		)));
+	}
+	Arc::new(ReceiveHooks(hooks))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn config() -> ReceiveHookConfig {
+		ReceiveHookConfig {
+			command: None,
+			command_timeout_secs: None,
+			min_amount: Some(10),
+			max_amount: Some(100),
+			message_prefix: None,
+		}
+	}
+
+	#[test]
+	fn policy_amounts() {
+		let hook = PolicyReceiveHook::new(&config());
+		let mut slate = Slate::blank(2);
+		slate.amount = 50;
+		assert!(hook.check(&slate).is_ok());
+		slate.amount = 5;
+		assert!(hook.check(&slate).is_err());
+		slate.amount = 500;
+		assert!(hook.check(&slate).is_err());
+	}
+
+	#[test]
+	fn policy_message_prefix() {
+		let mut config = config();
+		config.message_prefix = Some("order:".to_owned());
+		let hook = PolicyReceiveHook::new(&config);
+		let mut slate = Slate::blank(2);
+		slate.amount = 50;
+		match hook.check(&slate).map_err(|e| e.kind()) {
+			Err(ErrorKind::ReceiveRejected(_)) => {}
+			r => panic!("Unexpected result: {:?}", r),
+		}
+	}
+
+	#[cfg(not(windows))]
+	#[test]
+	fn command_hook() {
+		let mut slate = Slate::blank(2);
+		slate.amount = 50;
+		let timeout = Duration::from_secs(5);
+
+		let hook = CommandReceiveHook::new("cat > /dev/null", timeout);
+		assert!(hook.check(&slate).is_ok());
+
+		// the first line of the output is the reason
+		let hook = CommandReceiveHook::new("echo 'unknown order'; exit 1", timeout);
+		match hook.check(&slate).map_err(|e| e.kind()) {
+			Err(ErrorKind::ReceiveRejected(r)) => assert_eq!(r, "unknown order"),
+			r => panic!("Unexpected result: {:?}", r),
+		}
+
+		// the slate is given as JSON
+		let hook = CommandReceiveHook::new("grep -q '\"amount\": *\"50\"'", timeout);
+		assert!(hook.check(&slate).is_ok());
+
+		// a command taking too long is killed
+		let hook = CommandReceiveHook::new("sleep 10", Duration::from_millis(200));
+		let start = Instant::now();
+		match hook.check(&slate).map_err(|e| e.kind()) {
+			Err(ErrorKind::ReceiveRejected(r)) => assert_eq!(r, "receive hook timed out"),
+			r => panic!("Unexpected result: {:?}", r),
+		}
+		assert!(start.elapsed() < Duration::from_secs(5));
+	}
+}
@@ -14,8 +14,11 @@
 
 //! Responsible for handling the serialization and deserialization of structs common to the database
 
+use crate::config::DerivationConfig;
 use epic_wallet_libwallet::{
-	AcctPathMapping, Context, OutputData, ScannedBlockInfo, TxLogEntry, WalletInitStatus,
+	AcctPathMapping, BlockHeaderInfo, Contact, Context, MetadataEntry, OutputData, PinnedKey,
+	ScannedBlockInfo, SendTemplate, Slate, StoredEvent, Swap, TransportMetrics, TxLogEntry,
+	WalletInitStatus,
 };
 use serde::Serialize;
 use serde_json::Result;
@@ -31,6 +34,16 @@ pub enum Serializable {
 	ScannedBlockInfo(ScannedBlockInfo),
 	WalletInitStatus(WalletInitStatus),
 	Context(Context),
+	TransportMetrics(TransportMetrics),
+	PinnedKey(PinnedKey),
+	Contact(Contact),
+	Slate(Slate),
+	SendTemplate(SendTemplate),
+	MetadataEntry(MetadataEntry),
+	BlockHeaderInfo(BlockHeaderInfo),
+	Swap(Swap),
+	StoredEvent(StoredEvent),
+	DerivationConfig(DerivationConfig),
 	Numeric(u64),
 }
 
@@ -72,6 +85,86 @@ impl Serializable {
 		}
 	}
 
+	/// Converts a Serializable into a TransportMetrics
+	pub fn as_transport_metrics(self) -> Option<TransportMetrics> {
+		match self {
+			Serializable::TransportMetrics(metrics) => Some(metrics),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a PinnedKey
+	pub fn as_pinned_key(self) -> Option<PinnedKey> {
+		match self {
+			Serializable::PinnedKey(key) => Some(key),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a Contact
+	pub fn as_contact(self) -> Option<Contact> {
+		match self {
+			Serializable::Contact(contact) => Some(contact),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a SendTemplate
+	pub fn as_send_template(self) -> Option<SendTemplate> {
+		match self {
+			Serializable::SendTemplate(template) => Some(template),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a Swap
+	pub fn as_swap(self) -> Option<Swap> {
+		match self {
+			Serializable::Swap(swap) => Some(swap),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a StoredEvent
+	pub fn as_stored_event(self) -> Option<StoredEvent> {
+		match self {
+			Serializable::StoredEvent(event) => Some(event),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a Slate
+	pub fn as_slate(self) -> Option<Slate> {
+		match self {
+			Serializable::Slate(slate) => Some(slate),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a MetadataEntry
+	pub fn as_metadata_entry(self) -> Option<MetadataEntry> {
+		match self {
+			Serializable::MetadataEntry(entry) => Some(entry),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a BlockHeaderInfo
+	pub fn as_block_header(self) -> Option<BlockHeaderInfo> {
+		match self {
+			Serializable::BlockHeaderInfo(header) => Some(header),
+			_ => None,
+		}
+	}
+
+	/// Converts a Serializable into a DerivationConfig
+	pub fn as_derivation_config(self) -> Option<DerivationConfig> {
+		match self {
+			Serializable::DerivationConfig(config) => Some(config),
+			_ => None,
+		}
+	}
+
 	/// Converts a Serializable into a Context
 	pub fn as_context(self) -> Option<Context> {
 		match self {
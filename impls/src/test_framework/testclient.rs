@@ -20,6 +20,7 @@ use crate::api::{self, LocatedTxKernel};
 use crate::chain::types::NoopAdapter;
 use crate::chain::Chain;
 
+use crate::core::core::hash::Hashed;
 use crate::core::core::{Transaction, TxKernel};
 use crate::core::global::{set_mining_mode, ChainTypes};
 use crate::core::{pow, ser};
@@ -28,7 +29,8 @@ use crate::libwallet;
 use crate::libwallet::api_impl::foreign;
 use crate::libwallet::slate_versions::v3::SlateV3;
 use crate::libwallet::{
-	NodeClient, NodeVersionInfo, Slate, TxWrapper, WalletInst, WalletLCProvider,
+	BlockHeaderInfo, NodeClient, NodeRequestMetrics, NodeVersionInfo, Slate, TxWrapper, WalletInst,
+	WalletLCProvider,
 };
 use crate::util;
 use crate::util::secp::key::SecretKey;
@@ -151,6 +153,8 @@ where
 				"send_tx_slate" => self.send_tx_slate(m)?,
 				"post_tx" => self.post_tx(m)?,
 				"get_kernel" => self.get_kernel(m)?,
+				"get_header_info" => self.get_header_info(m)?,
+				"get_headers" => self.get_headers(m)?,
 				_ => panic!("Unknown Wallet Proxy Message"),
 			};
 
@@ -230,6 +234,7 @@ where
 				None,
 				None,
 				false,
+				false,
 			) {
 				Err(e) => {
 					return Ok(WalletProxyMessage {
@@ -267,6 +272,58 @@ where
 		})
 	}
 
+	/// get block header at height
+	fn get_header_info(
+		&mut self,
+		m: WalletProxyMessage,
+	) -> Result<WalletProxyMessage, libwallet::Error> {
+		let height = m.body.parse::<u64>().unwrap();
+		let header = self
+			.chain
+			.get_header_by_height(height)
+			.ok()
+			.map(|h| BlockHeaderInfo {
+				height: h.height,
+				hash: util::to_hex(h.hash().to_vec()),
+				timestamp: h.timestamp,
+			});
+
+		Ok(WalletProxyMessage {
+			sender_id: "node".to_owned(),
+			dest: m.sender_id,
+			method: m.method,
+			body: serde_json::to_string(&header).unwrap(),
+		})
+	}
+
+	/// get block headers between heights
+	fn get_headers(
+		&mut self,
+		m: WalletProxyMessage,
+	) -> Result<WalletProxyMessage, libwallet::Error> {
+		let split: Vec<&str> = m.body.split(",").collect();
+		let start_height = split[0].parse::<u64>().unwrap();
+		let end_height = split[1].parse::<u64>().unwrap();
+		let mut headers = vec![];
+		for height in start_height..=end_height {
+			match self.chain.get_header_by_height(height) {
+				Ok(h) => headers.push(BlockHeaderInfo {
+					height: h.height,
+					hash: util::to_hex(h.hash().to_vec()),
+					timestamp: h.timestamp,
+				}),
+				Err(_) => break,
+			}
+		}
+
+		Ok(WalletProxyMessage {
+			sender_id: "node".to_owned(),
+			dest: m.sender_id,
+			method: m.method,
+			body: serde_json::to_string(&headers).unwrap(),
+		})
+	}
+
 	/// get api outputs
 	fn get_outputs_from_node(
 		&mut self,
@@ -486,6 +543,56 @@ impl NodeClient for LocalWalletClient {
 		Ok((split[0].parse::<u64>().unwrap(), split[1].to_owned()))
 	}
 
+	/// Return the header of the block at the given height
+	fn get_header_info(&self, height: u64) -> Result<Option<BlockHeaderInfo>, libwallet::Error> {
+		let m = WalletProxyMessage {
+			sender_id: self.id.clone(),
+			dest: self.node_url().to_owned(),
+			method: "get_header_info".to_owned(),
+			body: format!("{}", height),
+		};
+		{
+			let p = self.proxy_tx.lock();
+			p.send(m).context(libwallet::ErrorKind::ClientCallback(
+				"Get block header send".to_owned(),
+			))?;
+		}
+		let r = self.rx.lock();
+		let m = r.recv().unwrap();
+		trace!("Received get_header_info response: {:?}", m.clone());
+		let header: Option<BlockHeaderInfo> = serde_json::from_str(&m.body).context(
+			libwallet::ErrorKind::ClientCallback("Parsing get_header_info response".to_owned()),
+		)?;
+		Ok(header)
+	}
+
+	/// Return the headers of the blocks between the given heights
+	fn get_headers(
+		&self,
+		start_height: u64,
+		end_height: u64,
+	) -> Result<Vec<BlockHeaderInfo>, libwallet::Error> {
+		let m = WalletProxyMessage {
+			sender_id: self.id.clone(),
+			dest: self.node_url().to_owned(),
+			method: "get_headers".to_owned(),
+			body: format!("{},{}", start_height, end_height),
+		};
+		{
+			let p = self.proxy_tx.lock();
+			p.send(m).context(libwallet::ErrorKind::ClientCallback(
+				"Get block headers send".to_owned(),
+			))?;
+		}
+		let r = self.rx.lock();
+		let m = r.recv().unwrap();
+		trace!("Received get_headers response: {:?}", m.clone());
+		let headers: Vec<BlockHeaderInfo> = serde_json::from_str(&m.body).context(
+			libwallet::ErrorKind::ClientCallback("Parsing get_headers response".to_owned()),
+		)?;
+		Ok(headers)
+	}
+
 	/// Retrieve outputs from node
 	fn get_outputs_from_node(
 		&self,
@@ -646,6 +753,11 @@ impl NodeClient for LocalWalletClient {
 		let o: api::OutputListing = serde_json::from_str(&m.body).unwrap();
 		Ok((o.last_retrieved_index, o.highest_index))
 	}
+
+	/// Requests to the proxy aren't timed
+	fn request_metrics(&self) -> Vec<NodeRequestMetrics> {
+		vec![]
+	}
 }
 unsafe impl<'a, L, C, K> Send for WalletProxy<'a, L, C, K>
 where
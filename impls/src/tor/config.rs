@@ -34,6 +34,10 @@ const TORRC_FILE: &'static str = "torrc";
 const TOR_DATA_DIR: &'static str = "data";
 const AUTH_CLIENTS_DIR: &'static str = "authorized_clients";
 const HIDDEN_SERVICES_DIR: &'static str = "onion_service_addresses";
+/// File tor writes the address of its control port to, in the listener's directory
+pub const CONTROL_PORT_FILE: &'static str = "control_port";
+/// File of the cookie authenticating to tor's control port
+pub const CONTROL_AUTH_COOKIE_FILE: &'static str = "control_auth_cookie";
 
 #[cfg(unix)]
 fn set_permissions(file_path: &str) -> Result<(), Error> {
@@ -191,6 +195,17 @@ pub fn output_torrc(
 		props.add_item("HiddenServicePort", &format!("80 {}", wallet_listener_addr));
 	}
 
+	// a control port for the hidden services to be checked over, on a free local port
+	if !service_dirs.is_empty() {
+		props.add_item("ControlPort", "auto");
+		props.add_item(
+			"ControlPortWriteToFile",
+			&format!("./{}", CONTROL_PORT_FILE),
+		);
+		props.add_item("CookieAuthentication", "1");
+		props.add_item("CookieAuthFile", &format!("./{}", CONTROL_AUTH_COOKIE_FILE));
+	}
+
 	props.write_to_file(&torrc_file_path)?;
 
 	Ok(())
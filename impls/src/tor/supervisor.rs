@@ -0,0 +1,343 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Supervision of the tor process of a listener's hidden service, which can die
+//! or stop publishing the service's descriptor without the listener noticing.
+//! The supervisor checks the descriptor over tor's control port and restarts
+//! the process when the service is unreachable.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use super::config::{CONTROL_AUTH_COOKIE_FILE, CONTROL_PORT_FILE};
+use super::process::TorProcess;
+use crate::util::{to_hex, Mutex};
+
+/// Time between two checks of a hidden service
+pub const DEFAULT_TOR_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Consecutive checks a running tor process may fail to publish the descriptor
+/// over before it's restarted, as publishing takes a while after bootstrapping
+const MAX_UNPUBLISHED_CHECKS: u32 = 3;
+
+/// Time allowed to the control port to answer
+const CONTROL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Seconds tor is given to bootstrap when (re)started
+const TOR_LAUNCH_TIMEOUT_SECS: u32 = 20;
+
+/// Status of the hidden service of a listener, as last checked by its supervisor
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ListenerStatus {
+	/// Kind of the listener, `tor` for a hidden service
+	pub listener: String,
+	/// Onion address of the hidden service
+	pub address: String,
+	/// Address of the listener the hidden service forwards to
+	pub bind_address: String,
+	/// Whether the tor process was running at the last check
+	pub running: bool,
+	/// Whether tor had published the hidden service's descriptor at the last
+	/// check, unknown if its control port couldn't be queried
+	pub descriptor_published: Option<bool>,
+	/// Number of times the tor process was restarted
+	pub restarts: u32,
+	/// When the service was last checked
+	pub last_check: Option<DateTime<Utc>>,
+	/// Error of the last check or restart, if it failed
+	pub last_error: Option<String>,
+}
+
+lazy_static! {
+	static ref LISTENER_STATUS: Mutex<HashMap<String, ListenerStatus>> = Mutex::new(HashMap::new());
+}
+
+/// Status of the hidden services supervised in this process
+pub fn listener_statuses() -> Vec<ListenerStatus> {
+	let mut statuses: Vec<ListenerStatus> = LISTENER_STATUS.lock().values().cloned().collect();
+	statuses.sort_by(|a, b| a.address.cmp(&b.address));
+	statuses
+}
+
+fn update_status<F>(address: &str, f: F)
+where
+	F: FnOnce(&mut ListenerStatus),
+{
+	if let Some(s) = LISTENER_STATUS.lock().get_mut(address) {
+		f(s);
+	}
+}
+
+/// Start tor with the listener configuration written to the directory
+pub fn launch_listener_tor(tor_dir: &str) -> Result<TorProcess, String> {
+	let mut process = TorProcess::new();
+	process
+		.torrc_path(&format!("{}/torrc", tor_dir))
+		.working_dir(tor_dir)
+		.timeout(TOR_LAUNCH_TIMEOUT_SECS)
+		.completion_percent(100)
+		.launch()
+		.map_err(|e| format!("{:?}", e))?;
+	Ok(process)
+}
+
+/// Whether tor has published the descriptor of the hidden service, asked over
+/// the control port tor wrote to the directory
+pub fn descriptor_published(tor_dir: &str, onion_address: &str) -> Result<bool, String> {
+	let port_file = Path::new(tor_dir).join(CONTROL_PORT_FILE);
+	let port =
+		fs::read_to_string(&port_file).map_err(|e| format!("Reading the control port: {}", e))?;
+	let addr = port
+		.lines()
+		.find_map(|l| l.trim().strip_prefix("PORT="))
+		.ok_or_else(|| format!("No control port in {}", port_file.display()))?
+		.to_owned();
+	let cookie = fs::read(Path::new(tor_dir).join(CONTROL_AUTH_COOKIE_FILE))
+		.map_err(|e| format!("Reading the control cookie: {}", e))?;
+
+	let stream = TcpStream::connect(&addr).map_err(|e| format!("Connecting to {}: {}", addr, e))?;
+	let _ = stream.set_read_timeout(Some(CONTROL_TIMEOUT));
+	let _ = stream.set_write_timeout(Some(CONTROL_TIMEOUT));
+	let mut writer = stream
+		.try_clone()
+		.map_err(|e| format!("Control connection: {}", e))?;
+	let mut reader = BufReader::new(stream);
+	let mut command = |c: String| -> Result<String, String> {
+		writer
+			.write_all(format!("{}\r\n", c).as_bytes())
+			.map_err(|e| format!("Writing to the control port: {}", e))?;
+		let mut line = String::new();
+		reader
+			.read_line(&mut line)
+			.map_err(|e| format!("Reading from the control port: {}", e))?;
+		Ok(line)
+	};
+
+	let reply = command(format!("AUTHENTICATE {}", to_hex(cookie)))?;
+	if !reply.starts_with("250") {
+		return Err(format!(
+			"Control port authentication refused: {}",
+			reply.trim()
+		));
+	}
+	let service_id = onion_address.trim_end_matches(".onion");
+	let reply = command(format!("GETINFO hs/service/desc/id/{}", service_id))?;
+	let _ = command("QUIT".to_owned());
+	// 552 is tor's reply when it has no descriptor for the service
+	match &reply[..reply.len().min(3)] {
+		"250" => Ok(true),
+		"552" => Ok(false),
+		_ => Err(format!("Unexpected control port reply: {}", reply.trim())),
+	}
+}
+
+/// Supervisor of the tor process of a listener's hidden service, restarting it if
+/// it dies or doesn't publish the service. The process is stopped along with the
+/// supervisor when it's dropped.
+pub struct TorSupervisor {
+	address: String,
+	stop: Arc<AtomicBool>,
+	thread: Option<JoinHandle<()>>,
+}
+
+impl TorSupervisor {
+	/// Supervise the tor process started for the hidden service of the onion
+	/// address, forwarding to the listener at the bind address, with its
+	/// configuration in the directory
+	pub fn start(
+		process: TorProcess,
+		onion_address: &str,
+		bind_address: &str,
+		tor_dir: &str,
+		check_interval: Duration,
+	) -> TorSupervisor {
+		LISTENER_STATUS.lock().insert(
+			onion_address.to_owned(),
+			ListenerStatus {
+				listener: "tor".to_owned(),
+				address: onion_address.to_owned(),
+				bind_address: bind_address.to_owned(),
+				running: true,
+				descriptor_published: None,
+				restarts: 0,
+				last_check: None,
+				last_error: None,
+			},
+		);
+		let stop = Arc::new(AtomicBool::new(false));
+		let thread = {
+			let stop = stop.clone();
+			let address = onion_address.to_owned();
+			let tor_dir = tor_dir.to_owned();
+			thread::Builder::new()
+				.name("tor-supervisor".to_string())
+				.spawn(move || supervise(process, &address, &tor_dir, check_interval, &stop))
+				.map_err(|e| error!("Unable to supervise the tor process: {}", e))
+				.ok()
+		};
+		TorSupervisor {
+			address: onion_address.to_owned(),
+			stop,
+			thread,
+		}
+	}
+}
+
+impl Drop for TorSupervisor {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::SeqCst);
+		if let Some(t) = self.thread.take() {
+			let _ = t.join();
+		}
+		LISTENER_STATUS.lock().remove(&self.address);
+	}
+}
+
+/// Check the hidden service at each interval until stopped, restarting tor when
+/// it's dead or hasn't published the service for too long
+fn supervise(
+	mut process: TorProcess,
+	address: &str,
+	tor_dir: &str,
+	check_interval: Duration,
+	stop: &AtomicBool,
+) {
+	let mut unpublished_checks = 0;
+	let mut last_check = Instant::now();
+	while !stop.load(Ordering::SeqCst) {
+		thread::sleep(Duration::from_millis(500));
+		if last_check.elapsed() < check_interval {
+			continue;
+		}
+		last_check = Instant::now();
+
+		let running = match process.process.as_mut().map(|c| c.try_wait()) {
+			Some(Ok(None)) => true,
+			_ => false,
+		};
+		let published = match running {
+			true => descriptor_published(tor_dir, address),
+			false => Err("The tor process isn't running".to_owned()),
+		};
+		unpublished_checks = match published {
+			Ok(true) => 0,
+			_ => unpublished_checks + 1,
+		};
+		update_status(address, |s| {
+			s.running = running;
+			s.descriptor_published = published.as_ref().ok().cloned();
+			s.last_check = Some(Utc::now());
+			s.last_error = published.as_ref().err().cloned();
+		});
+		if running && unpublished_checks < MAX_UNPUBLISHED_CHECKS {
+			continue;
+		}
+
+		warn!(
+			"The hidden service {} is unreachable ({}), restarting tor",
+			address,
+			match &published {
+				Ok(_) => "descriptor not published".to_owned(),
+				Err(e) => e.clone(),
+			}
+		);
+		let _ = process.kill();
+		match launch_listener_tor(tor_dir) {
+			Ok(p) => {
+				process = p;
+				unpublished_checks = 0;
+				update_status(address, |s| {
+					s.running = true;
+					s.descriptor_published = None;
+					s.restarts += 1;
+				});
+				warn!("Tor restarted for the hidden service {}", address);
+			}
+			Err(e) => {
+				error!("Unable to restart tor: {}", e);
+				update_status(address, |s| {
+					s.running = false;
+					s.last_error = Some(format!("Restarting tor: {}", e));
+				});
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::net::TcpListener;
+
+	/// Control port answering the descriptor query with the given reply
+	fn fake_control_port(tor_dir: &str, reply: &'static str) -> JoinHandle<Vec<String>> {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let port = format!("PORT={}\n", listener.local_addr().unwrap());
+		fs::write(Path::new(tor_dir).join(CONTROL_PORT_FILE), port).unwrap();
+		fs::write(Path::new(tor_dir).join(CONTROL_AUTH_COOKIE_FILE), [1u8; 32]).unwrap();
+		thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut writer = stream.try_clone().unwrap();
+			let mut lines = vec![];
+			for l in BufReader::new(stream).lines() {
+				let l = l.unwrap();
+				match l.split(' ').next() {
+					Some("AUTHENTICATE") => writer.write_all(b"250 OK\r\n").unwrap(),
+					Some("GETINFO") => writer.write_all(reply.as_bytes()).unwrap(),
+					_ => {
+						lines.push(l);
+						break;
+					}
+				}
+				lines.push(l);
+			}
+			lines
+		})
+	}
+
+	#[test]
+	fn tor_descriptor_check() {
+		let tor_dir = std::env::temp_dir().join("epic_tor_supervisor");
+		let _ = fs::remove_dir_all(&tor_dir);
+		fs::create_dir_all(&tor_dir).unwrap();
+		let tor_dir = tor_dir.to_str().unwrap();
+		let address = "2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid.onion";
+
+		assert!(descriptor_published(tor_dir, address).is_err());
+
+		let control = fake_control_port(tor_dir, "552 Unrecognized key\r\n");
+		assert_eq!(descriptor_published(tor_dir, address), Ok(false));
+		let lines = control.join().unwrap();
+		assert_eq!(lines[0], format!("AUTHENTICATE {}", to_hex(vec![1u8; 32])));
+		assert_eq!(
+			lines[1],
+			"GETINFO hs/service/desc/id/2a6at2obto3uvkpkitqp4wxcg6u36qf534eucbskqciturczzc5suyid"
+		);
+
+		let control = fake_control_port(tor_dir, "250+hs/service/desc/id/x=\r\n");
+		assert_eq!(descriptor_published(tor_dir, address), Ok(true));
+		control.join().unwrap();
+
+		let _ = fs::remove_dir_all(tor_dir);
+	}
+}
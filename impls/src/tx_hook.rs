@@ -0,0 +1,113 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction hooks, running external commands before sends and after finalizing
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
+
+use crate::config::TxHooksConfig;
+use crate::libwallet::{Error, ErrorKind, Slate, SlateVersion, TxHook, VersionedSlate};
+
+/// Runs the configured commands with the slate as JSON on their standard input.
+/// A send is vetoed if the pre-send command fails, with the first line of its
+/// output as the reason.
+pub struct CommandTxHook {
+	pre_send_command: Option<String>,
+	post_finalize_command: Option<String>,
+}
+
+impl CommandTxHook {
+	/// Create a hook running the given command lines through the system shell
+	pub fn new(config: &TxHooksConfig) -> CommandTxHook {
+		CommandTxHook {
+			pre_send_command: config.pre_send_command.clone(),
+			post_finalize_command: config.post_finalize_command.clone(),
+		}
+	}
+
+	fn run(command: &str, slate: &Slate) -> Result<Output, Error> {
+		let mut c = if cfg!(windows) {
+			let mut c = Command::new("cmd");
+			c.arg("/C").arg(command);
+			c
+		} else {
+			let mut c = Command::new("sh");
+			c.arg("-c").arg(command);
+			c
+		};
+		let versioned = VersionedSlate::into_version(slate.clone(), SlateVersion::V3);
+		let json = serde_json::to_string(&versioned).map_err(|_| ErrorKind::SlateSer)?;
+
+		let mut child = c
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()
+			.map_err(|e| {
+				ErrorKind::GenericError(format!("Unable to run transaction hook command: {}", e))
+			})?;
+		if let Some(mut stdin) = child.stdin.take() {
+			// the command may decide without reading all of its input
+			let _ = stdin.write_all(json.as_bytes());
+		}
+		Ok(child.wait_with_output()?)
+	}
+}
+
+impl TxHook for CommandTxHook {
+	fn pre_send(&self, slate: &Slate) -> Result<(), Error> {
+		let command = match self.pre_send_command.as_ref() {
+			Some(c) => c,
+			None => return Ok(()),
+		};
+		let output = CommandTxHook::run(command, slate)?;
+		if output.status.success() {
+			return Ok(());
+		}
+
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		let reason = stdout
+			.lines()
+			.chain(stderr.lines())
+			.map(|l| l.trim())
+			.find(|l| !l.is_empty())
+			.unwrap_or("vetoed by pre-send hook")
+			.to_owned();
+		warn!("Outgoing transaction {} vetoed: {}", slate.id, reason);
+		Err(ErrorKind::SendRejected(reason).into())
+	}
+
+	fn post_finalize(&self, slate: &Slate) -> Result<(), Error> {
+		let command = match self.post_finalize_command.as_ref() {
+			Some(c) => c,
+			None => return Ok(()),
+		};
+		let output = CommandTxHook::run(command, slate)?;
+		if !output.status.success() {
+			return Err(ErrorKind::GenericError(format!(
+				"Post-finalize hook command failed: {}",
+				output.status
+			))
+			.into());
+		}
+		Ok(())
+	}
+}
+
+/// Build the transaction hook described by the configuration
+pub fn tx_hook_from_config(config: &TxHooksConfig) -> Arc<dyn TxHook> {
+	Arc::new(CommandTxHook::new(config))
+}
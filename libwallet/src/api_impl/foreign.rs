@@ -21,8 +21,8 @@ use crate::epic_util::secp::key::SecretKey;
 use crate::internal::{tx, updater};
 use crate::slate_versions::SlateVersion;
 use crate::{
-	address, BlockFees, CbData, Error, ErrorKind, NodeClient, Slate, TxLogEntryType, VersionInfo,
-	WalletBackend,
+	address, record_event, BlockFees, CbData, Error, ErrorKind, NodeClient, Slate, TxLogEntryType,
+	VersionInfo, WalletBackend, WalletEvent,
 };
 
 const FOREIGN_API_VERSION: u16 = 2;
@@ -78,6 +78,7 @@ pub fn receive_tx<'a, T: ?Sized, C, K>(
 	slate: &Slate,
 	dest_acct_name: Option<&str>,
 	message: Option<String>,
+	sign_response: bool,
 	use_test_rng: bool,
 ) -> Result<Slate, Error>
 where
@@ -86,6 +87,11 @@ where
 	K: Keychain + 'a,
 {
 	let mut ret_slate = slate.clone();
+	if ret_slate.multisig_output.is_some() {
+		return Err(ErrorKind::Multisig(
+			"a shared output is received with receive_multisig".to_owned(),
+		))?;
+	}
 	check_ttl(w, &ret_slate)?;
 	let parent_key_id = match dest_acct_name {
 		Some(d) => {
@@ -107,6 +113,13 @@ where
 	)?;
 	for t in &tx {
 		if t.tx_type == TxLogEntryType::TxReceived {
+			// a sender retrying an interrupted exchange gets the same response again
+			if let Some(r) = w.get_slate_response(&ret_slate.id)? {
+				if is_same_exchange(&ret_slate, &r) {
+					debug!("Returning kept response to slate {}", ret_slate.id);
+					return Ok(r);
+				}
+			}
 			return Err(ErrorKind::TransactionAlreadyReceived(ret_slate.id.to_string()).into());
 		}
 	}
@@ -145,9 +158,45 @@ where
 		p.receiver_signature = Some(sig);
 	}
 
+	if sign_response {
+		ret_slate.response_signature = Some(tx::create_response_signature(
+			&ret_slate,
+			&excess,
+			address::address_from_derivation_path(&keychain, &parent_key_id, 0)?,
+		)?);
+	}
+
+	// keep the response, in case it doesn't make it back to the sender
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_slate_response(ret_slate.clone())?;
+		batch.commit()?;
+	}
+
+	record_event(
+		w,
+		keychain_mask,
+		WalletEvent::TxReceived {
+			tx_slate_id: ret_slate.id,
+			amount: ret_slate.amount,
+		},
+	);
 	Ok(ret_slate)
 }
 
+/// Whether the slate is the one the response was signed for, i.e. it's the sender
+/// sending the same slate again rather than a different one reusing its id
+fn is_same_exchange(slate: &Slate, response: &Slate) -> bool {
+	let sender = |s: &Slate| {
+		s.participant_with_id(0)
+			.map(|p| (p.public_blind_excess, p.public_nonce))
+	};
+	slate.amount == response.amount
+		&& slate.fee == response.fee
+		&& sender(slate).is_some()
+		&& sender(slate) == sender(response)
+}
+
 /// Receive an tx that this wallet has issued
 pub fn finalize_invoice_tx<'a, T: ?Sized, C, K>(
 	w: &mut T,
@@ -14,36 +14,103 @@
 
 //! Generic implementation of owner API functions
 
+use chrono::{DateTime, TimeZone, Utc};
 use uuid::Uuid;
 
 use crate::epic_core::core::hash::Hashed;
-use crate::epic_core::core::Transaction;
+use crate::epic_core::core::{Input, OutputFeatures, Transaction};
+use crate::epic_core::global;
+use crate::epic_core::libtx::{proof, tx_fee};
 use crate::epic_core::ser;
 use crate::epic_util;
 use crate::epic_util::secp::key::SecretKey;
+use crate::epic_util::secp::pedersen;
 use crate::epic_util::Mutex;
 
+use crate::api_impl::foreign;
 use crate::api_impl::owner_updater::StatusMessage;
-use crate::epic_keychain::{Identifier, Keychain};
+use crate::epic_keychain::{Identifier, Keychain, SwitchCommitmentType};
 use crate::epic_util::secp::key::PublicKey;
-use crate::epicbox_address::EpicboxAddress;
-use crate::internal::{keys, scan, selection, tx, updater};
+use crate::epicbox_address::{Address, EpicboxAddress};
+use crate::internal::{keys, multisig, scan, selection, swap, tx, updater};
 use crate::slate::{PaymentInfo, Slate};
-use crate::types::{AcctPathMapping, NodeClient, TxLogEntry, TxWrapper, WalletBackend, WalletInfo};
+use crate::types::{
+	AcctPathMapping, BlockHeaderInfo, Contact, Context, InputComposition, KeyPinCheck,
+	MetadataEntry, NodeClient, NodeRequestMetrics, OutputData, OutputStatus, PaymentProofState,
+	PinnedKey, SendTemplate, Swap, SwapRole, SwapStatus, TransportEvent, TransportMetrics,
+	TxApproval, TxHook, TxKernelFeatures, TxLogEntry, TxWrapper, WalletBackend, WalletInfo,
+	CONTACT_TRANSPORTS, SEND_TEMPLATE_METHODS,
+};
 use crate::{
-	address, wallet_lock, InitTxArgs, IssueInvoiceTxArgs, NodeHeightResult, OutputCommitMapping,
-	PaymentProof, ScannedBlockInfo, TxLogEntryType, WalletInitStatus, WalletInst, WalletLCProvider,
+	address, record_event, wallet_lock, AccountFeeReport, AccountStatement, ApiStatus, ApiVersions,
+	DeduplicatedOutput, FeePeriod, FeePeriodSummary, FeeReport, FeeSummary, ImportedOutput,
+	InitTxArgs, IssueInvoiceTxArgs, MethodDeprecation, NodeHeightResult, OutputClaim,
+	OutputClaimVerification, OutputCommitMapping, OutputImportSummary, OutputLineage,
+	OwnerApiVersion, PaymentProof, PaymentProofEntry, PaymentRequest, ScanPreview,
+	ScannedBlockInfo, SeedWord, SigningRequest, StatementLine, SwapMessage, SwapMessageBody,
+	SwapStartArgs, SwapStep, SweepPolicy, TxBulkResult, TxEstimate, TxFilter, TxLogEntryType,
+	ViewKey, WalletEvent, WalletInitStatus, WalletInst, WalletLCProvider, SWEEP_TX_TAG,
 };
 
 use crate::{Error, ErrorKind};
+use ed25519_dalek::Keypair as DalekKeypair;
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use ed25519_dalek::SecretKey as DalekSecretKey;
+use rand::{thread_rng, Rng};
 
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 const USER_MESSAGE_MAX_LEN: usize = 256;
 
+/// How many blocks the node's tip may be behind the height the wallet last refreshed to
+/// for transactions to still be posted to it
+pub const POST_MAX_NODE_LAG: u64 = 5;
+
+/// Age of the node's tip beyond which the node is considered to be syncing
+pub const POST_MAX_TIP_AGE_SECS: i64 = 3600;
+const METADATA_NAMESPACE_MAX_LEN: usize = 64;
+const METADATA_KEY_MAX_LEN: usize = 256;
+const METADATA_VALUE_MAX_LEN: usize = 64 * 1024;
+const METADATA_MAX_ENTRIES_PER_NAMESPACE: usize = 1_000;
+
+/// Owner API methods which are deprecated or were removed, with the version they apply to
+/// and the method replacing them
+const OWNER_METHOD_DEPRECATIONS: &[(u16, &str, ApiStatus, Option<&str>)] = &[
+	(2, "check_repair", ApiStatus::Removed, Some("scan")),
+	(2, "restore", ApiStatus::Removed, Some("scan")),
+];
+
+/// Return the owner API versions served by the wallet and the deprecation status of
+/// their methods
+pub fn api_versions() -> ApiVersions {
+	let version = |version: u16, status: ApiStatus| OwnerApiVersion {
+		version,
+		endpoint: format!("/v{}/owner", version),
+		status,
+		deprecated_methods: OWNER_METHOD_DEPRECATIONS
+			.iter()
+			.filter(|d| d.0 == version)
+			.map(|d| MethodDeprecation {
+				method: d.1.to_owned(),
+				status: d.2.clone(),
+				replacement: d.3.map(|r| r.to_owned()),
+			})
+			.collect(),
+	};
+	ApiVersions {
+		build_version: env!("CARGO_PKG_VERSION").to_owned(),
+		// the unencrypted V2 API is superseded by the secure V3 API
+		owner_api_versions: vec![
+			version(2, ApiStatus::Deprecated),
+			version(3, ApiStatus::Supported),
+		],
+		foreign_api_version: foreign::check_version().foreign_api_version,
+	}
+}
+
 /// List of accounts
 pub fn accounts<'a, T: ?Sized, C, K>(w: &mut T) -> Result<Vec<AcctPathMapping>, Error>
 where
@@ -68,6 +135,37 @@ where
 	keys::new_acct_path(&mut *w, keychain_mask, label)
 }
 
+/// Set or, if `value` is `None`, remove a metadata entry of an account
+pub fn set_account_metadata<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	label: &str,
+	key: &str,
+	value: Option<&str>,
+) -> Result<AcctPathMapping, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut mapping = match w.get_acct_path(label.to_owned())? {
+		Some(m) => m,
+		None => return Err(ErrorKind::UnknownAccountLabel(label.to_owned()).into()),
+	};
+	let key = key.trim();
+	if key.is_empty() {
+		return Err(ErrorKind::GenericError("Account metadata key is empty".to_owned()).into());
+	}
+	match value {
+		Some(v) => mapping.metadata.insert(key.to_owned(), v.to_owned()),
+		None => mapping.metadata.remove(key),
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_acct_path(mapping.clone())?;
+	batch.commit()?;
+	Ok(mapping)
+}
+
 /// set active account
 pub fn set_active_account<'a, T: ?Sized, C, K>(w: &mut T, label: &str) -> Result<(), Error>
 where
@@ -187,11 +285,433 @@ where
 
 	wallet_lock!(wallet_inst, w);
 	let parent_key_id = w.parent_key_id();
-	let txs = updater::retrieve_txs(&mut **w, tx_id, tx_slate_id, Some(&parent_key_id), false)?;
+	let mut txs = updater::retrieve_txs(&mut **w, tx_id, tx_slate_id, Some(&parent_key_id), false)?;
+	annotate_contacts(&mut **w, &mut txs)?;
 
 	Ok((validated, txs))
 }
 
+/// Statements of the transactions each account confirmed between the start and the
+/// end of a period, with the account's balance of confirmed transactions before and
+/// after it. Transactions count towards the period they were confirmed in.
+pub fn account_statements<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	refresh_from_node: bool,
+	start: DateTime<Utc>,
+	end: DateTime<Utc>,
+) -> Result<(bool, Vec<AccountStatement>), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut validated = false;
+	if refresh_from_node {
+		validated = update_wallet_state(
+			wallet_inst.clone(),
+			keychain_mask,
+			status_send_channel,
+			false,
+		)?;
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let mut txs: Vec<TxLogEntry> = w
+		.tx_log_iter()
+		.filter(|t| t.confirmed)
+		.filter(|t| t.confirmation_ts.unwrap_or(t.creation_ts) < end)
+		.collect();
+	txs.sort_by_key(|t| (t.confirmation_ts.unwrap_or(t.creation_ts), t.id));
+
+	let mut statements = vec![];
+	for acct in w.acct_path_iter() {
+		let mut statement = AccountStatement {
+			account: acct.label,
+			start,
+			end,
+			opening_balance: 0,
+			closing_balance: 0,
+			total_credited: 0,
+			total_debited: 0,
+			total_fees: 0,
+			lines: vec![],
+			fiat: None,
+		};
+		let mut balance: u64 = 0;
+		for t in txs.iter().filter(|t| t.parent_key_id == acct.path) {
+			balance = balance
+				.saturating_add(t.amount_credited)
+				.saturating_sub(t.amount_debited);
+			let time = t.confirmation_ts.unwrap_or(t.creation_ts);
+			if time < start {
+				statement.opening_balance = balance;
+				continue;
+			}
+			let fee = match t.tx_type {
+				TxLogEntryType::TxSent => t.fee.unwrap_or(0),
+				_ => 0,
+			};
+			statement.total_credited += t.amount_credited;
+			statement.total_debited += t.amount_debited;
+			statement.total_fees += fee;
+			statement.lines.push(StatementLine {
+				tx_id: t.id,
+				tx_slate_id: t.tx_slate_id,
+				tx_type: t.tx_type.clone(),
+				time,
+				credited: t.amount_credited,
+				debited: t.amount_debited,
+				fee,
+				balance,
+			});
+		}
+		statement.closing_balance = balance;
+		statements.push(statement);
+	}
+	statements.sort_by(|a, b| a.account.cmp(&b.account));
+	Ok((validated, statements))
+}
+
+/// Number of transactions paying the largest fees listed by a fee report
+const FEE_REPORT_LARGEST_TXS: usize = 10;
+
+/// Summarize the fees paid by the confirmed transactions sent from each account,
+/// over periods of the given length
+pub fn fee_report<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	refresh_from_node: bool,
+	period: FeePeriod,
+) -> Result<(bool, FeeReport), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut validated = false;
+	if refresh_from_node {
+		validated = update_wallet_state(
+			wallet_inst.clone(),
+			keychain_mask,
+			status_send_channel,
+			false,
+		)?;
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let labels: HashMap<Identifier, String> =
+		w.acct_path_iter().map(|a| (a.path, a.label)).collect();
+	let mut txs: Vec<TxLogEntry> = w
+		.tx_log_iter()
+		.filter(|t| t.tx_type == TxLogEntryType::TxSent && t.confirmed)
+		.collect();
+	txs.sort_by_key(|t| t.confirmation_ts.unwrap_or(t.creation_ts));
+
+	let mut fees = FeeSummary::default();
+	let mut accounts: Vec<AccountFeeReport> = vec![];
+	for t in txs.iter() {
+		let fee = t.fee.unwrap_or(0);
+		let label = labels.get(&t.parent_key_id).cloned().unwrap_or_default();
+		let start = period.start(t.confirmation_ts.unwrap_or(t.creation_ts));
+		let index = match accounts.iter().position(|a| a.account == label) {
+			Some(i) => i,
+			None => {
+				accounts.push(AccountFeeReport {
+					account: label,
+					fees: FeeSummary::default(),
+					periods: vec![],
+				});
+				accounts.len() - 1
+			}
+		};
+		let account = &mut accounts[index];
+		// transactions are sorted, so only the last period can be the same
+		match account.periods.last_mut() {
+			Some(p) if p.start == start => p.fees.add(fee),
+			_ => {
+				let mut period_fees = FeeSummary::default();
+				period_fees.add(fee);
+				account.periods.push(FeePeriodSummary {
+					start,
+					fees: period_fees,
+				});
+			}
+		}
+		account.fees.add(fee);
+		fees.add(fee);
+	}
+	accounts.sort_by(|a, b| a.account.cmp(&b.account));
+
+	txs.sort_by(|a, b| b.fee.cmp(&a.fee));
+	txs.truncate(FEE_REPORT_LARGEST_TXS);
+	annotate_contacts(&mut **w, &mut txs)?;
+
+	Ok((
+		validated,
+		FeeReport {
+			period,
+			fees,
+			accounts,
+			largest: txs,
+		},
+	))
+}
+
+/// Fill in the address book contact of each transaction, matched on the payment
+/// proof address of the other party
+pub fn annotate_contacts<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	txs: &mut [TxLogEntry],
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let contacts: HashMap<String, String> = w
+		.contacts_iter()
+		.map(|c| (c.proof_address, c.name))
+		.collect();
+	for t in txs.iter_mut() {
+		t.contact = match t.counterparty_address() {
+			Some(a) => contacts.get(&address::onion_v3_from_pubkey(a)?).cloned(),
+			None => None,
+		};
+	}
+	Ok(())
+}
+
+/// Retrieve the address book, sorted by name
+pub fn retrieve_contacts<'a, T: ?Sized, C, K>(w: &mut T) -> Result<Vec<Contact>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut contacts: Vec<Contact> = w.contacts_iter().collect();
+	contacts.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(contacts)
+}
+
+/// Add a contact to the address book, replacing the addresses of an existing contact
+/// with the same name
+pub fn save_contact<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	contact: &Contact,
+) -> Result<Contact, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let name = contact.name.trim();
+	if name.is_empty() {
+		return Err(ErrorKind::GenericError("Contact name may not be empty".to_owned()).into());
+	}
+	let proof_address = address::pubkey_from_onion_v3(contact.proof_address.trim())?;
+	let epicbox_address = match contact.epicbox_address.as_ref() {
+		Some(a) => Some(EpicboxAddress::from_str(a.trim())?.to_string()),
+		None => None,
+	};
+	let http_address = match contact.http_address.as_ref().map(|a| a.trim()) {
+		Some(a) if a.starts_with("http://") || a.starts_with("https://") => Some(a.to_owned()),
+		Some(a) => {
+			return Err(ErrorKind::GenericError(format!(
+				"HTTP address should start with http:// or https://: {}",
+				a
+			))
+			.into());
+		}
+		None => None,
+	};
+	let preferred_transport = match contact.preferred_transport.as_ref() {
+		Some(t) => {
+			let t = t.trim().to_lowercase();
+			if !CONTACT_TRANSPORTS.contains(&t.as_str()) {
+				return Err(ErrorKind::GenericError(format!(
+					"Unknown transport '{}', expected one of {}",
+					t,
+					CONTACT_TRANSPORTS.join(", ")
+				))
+				.into());
+			}
+			Some(t)
+		}
+		None => None,
+	};
+	// the transport that last worked is only ever recorded by the wallet
+	let last_transport = w.get_contact(name)?.and_then(|c| c.last_transport);
+	let contact = Contact {
+		name: name.to_owned(),
+		proof_address: address::onion_v3_from_pubkey(&proof_address)?,
+		epicbox_address,
+		http_address,
+		preferred_transport,
+		last_transport,
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_contact(contact.clone())?;
+	batch.commit()?;
+	Ok(contact)
+}
+
+/// Record the transport a transaction was sent with on its log entry and, if it
+/// was sent to a contact, as the transport that last worked for the contact
+pub fn record_tx_transport<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	tx_slate_id: Uuid,
+	transport: &str,
+	contact: Option<&str>,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = w.parent_key_id();
+	let mut tx = updater::retrieve_txs(w, None, Some(tx_slate_id), Some(&parent_key_id), false)?
+		.into_iter()
+		.find(|t| t.tx_type == TxLogEntryType::TxSent)
+		.ok_or_else(|| ErrorKind::TransactionDoesntExist(tx_slate_id.to_string()))?;
+	tx.transport = Some(transport.to_owned());
+	let contact = match contact {
+		Some(name) => match w.get_contact(name)? {
+			Some(c) => Some(Contact {
+				last_transport: Some(transport.to_owned()),
+				..c
+			}),
+			None => return Err(ErrorKind::UnknownContact(name.to_owned()).into()),
+		},
+		None => None,
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_tx_log_entry(tx, &parent_key_id)?;
+	if let Some(c) = contact {
+		batch.save_contact(c)?;
+	}
+	batch.commit()?;
+	Ok(())
+}
+
+/// Remove a contact from the address book
+pub fn remove_contact<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	name: &str,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if w.get_contact(name)?.is_none() {
+		return Err(ErrorKind::UnknownContact(name.to_owned()).into());
+	}
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_contact(name)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Retrieve the saved send templates, sorted by name
+pub fn retrieve_send_templates<'a, T: ?Sized, C, K>(w: &mut T) -> Result<Vec<SendTemplate>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut templates: Vec<SendTemplate> = w.send_templates_iter().collect();
+	templates.sort_by(|a, b| a.name.cmp(&b.name));
+	Ok(templates)
+}
+
+/// Retrieve the send template with the given name
+pub fn get_send_template<'a, T: ?Sized, C, K>(w: &mut T, name: &str) -> Result<SendTemplate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_send_template(name)?
+		.ok_or_else(|| ErrorKind::UnknownSendTemplate(name.to_owned()).into())
+}
+
+/// Save a send template, replacing the template with the same name
+pub fn save_send_template<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	template: &SendTemplate,
+) -> Result<SendTemplate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let name = template.name.trim();
+	if name.is_empty() {
+		let msg = "Send template name may not be empty".to_owned();
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	let method = template.method.trim().to_lowercase();
+	if !SEND_TEMPLATE_METHODS.contains(&method.as_str()) {
+		return Err(ErrorKind::GenericError(format!(
+			"Unknown method '{}', expected one of {}",
+			method,
+			SEND_TEMPLATE_METHODS.join(", ")
+		))
+		.into());
+	}
+	let dest = template.dest.trim();
+	if dest.is_empty() {
+		let msg = "Send template destination may not be empty".to_owned();
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	if template.amount == 0 {
+		let msg = "Send template amount may not be zero".to_owned();
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	if let Some(a) = template.src_acct_name.as_ref() {
+		if !w.acct_path_iter().any(|m| m.label == *a) {
+			return Err(ErrorKind::UnknownAccountLabel(a.clone()).into());
+		}
+	}
+	let template = SendTemplate {
+		name: name.to_owned(),
+		method,
+		dest: dest.to_owned(),
+		..template.clone()
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_send_template(template.clone())?;
+	batch.commit()?;
+	Ok(template)
+}
+
+/// Remove a send template
+pub fn remove_send_template<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	name: &str,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if w.get_send_template(name)?.is_none() {
+		return Err(ErrorKind::UnknownSendTemplate(name.to_owned()).into());
+	}
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_send_template(name)?;
+	batch.commit()?;
+	Ok(())
+}
+
 /// Retrieve summary info
 pub fn retrieve_summary_info<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -260,8 +780,52 @@ where
 	if txs.1.len() != 1 {
 		return Err(ErrorKind::PaymentProofRetrieval("Transaction doesn't exist".into()).into());
 	}
+	payment_proof_from_tx(txs.1[0].clone())
+}
+
+/// Retrieve the payment proofs of all the transactions of the current account whose
+/// proofs are complete
+pub fn retrieve_payment_proofs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	refresh_from_node: bool,
+) -> Result<(bool, Vec<PaymentProofEntry>), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (validated, txs) = retrieve_txs(
+		wallet_inst,
+		keychain_mask,
+		status_send_channel,
+		refresh_from_node,
+		None,
+		None,
+	)?;
+	let proofs = txs
+		.into_iter()
+		.filter(|t| t.payment_proof.is_some())
+		.filter_map(|t| {
+			let tx_id = t.id;
+			let tx_slate_id = t.tx_slate_id;
+			// proofs still waiting for the recipient's signature are left out
+			payment_proof_from_tx(t)
+				.ok()
+				.map(|proof| PaymentProofEntry {
+					tx_id,
+					tx_slate_id,
+					proof,
+				})
+		})
+		.collect();
+	Ok((validated, proofs))
+}
+
+/// Package the payment proof of a transaction
+fn payment_proof_from_tx(tx: TxLogEntry) -> Result<PaymentProof, Error> {
 	// Pull out all needed fields, returning an error if they're not present
-	let tx = txs.1[0].clone();
 	let proof = match tx.payment_proof {
 		Some(p) => p,
 		None => {
@@ -317,19 +881,18 @@ where
 	})
 }
 
-/// Initiate tx as sender
-pub fn init_send_tx<'a, T: ?Sized, C, K>(
+/// Parent key of the account a send is made from, the number of change outputs of the
+/// send and the maximum number of inputs it selects
+fn send_selection<'a, T: ?Sized, C, K>(
 	w: &mut T,
-	keychain_mask: Option<&SecretKey>,
-	args: InitTxArgs,
-	use_test_rng: bool,
-) -> Result<Slate, Error>
+	args: &InitTxArgs,
+) -> Result<(Identifier, usize, usize), Error>
 where
 	T: WalletBackend<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	let parent_key_id = match args.src_acct_name {
+	let parent_key_id = match args.src_acct_name.clone() {
 		Some(d) => {
 			let pm = w.get_acct_path(d)?;
 			match pm {
@@ -340,48 +903,316 @@ where
 		None => w.parent_key_id(),
 	};
 
-	let message = match args.message {
-		Some(mut m) => {
-			m.truncate(USER_MESSAGE_MAX_LEN);
-			Some(m)
+	// a decoy output is one more change output, for the weight and the fee
+	let num_outputs = args.num_change_outputs as usize + args.decoy_output_amount.map_or(0, |_| 1);
+
+	// keep input selection within the weight limit where the wallet's outputs allow it
+	let max_outputs = match args.max_tx_weight {
+		Some(max_weight) => {
+			let max_inputs = selection::max_inputs_for_weight(max_weight, num_outputs + 1);
+			cmp::max(1, cmp::min(args.max_outputs as usize, max_inputs))
 		}
-		None => None,
+		None => args.max_outputs as usize,
 	};
+	Ok((parent_key_id, num_outputs, max_outputs))
+}
 
-	let mut slate = tx::new_tx_slate(&mut *w, args.amount, 2, use_test_rng, args.ttl_blocks)?;
-
-	// if we just want to estimate, don't save a context, just send the results
-	// back
-	if let Some(true) = args.estimate_only {
-		let (total, fee) = tx::estimate_send_tx(
+/// Inputs a send is restricted to: those given, or if the send avoids mixing coinbase
+/// and plain outputs, those of one kind covering it. Unless they're given, small
+/// outputs are added to be consolidated if the send allows the fee increase.
+fn send_inputs<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	args: &InitTxArgs,
+	parent_key_id: &Identifier,
+	num_outputs: usize,
+	max_outputs: usize,
+) -> Result<Option<Vec<pedersen::Commitment>>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let consolidate = args.inputs.is_none() && args.consolidation_fee_threshold.is_some();
+	if args.avoid_mixed_coinbase != Some(true) && !consolidate {
+		return Ok(args.inputs.clone());
+	}
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+	let inputs = match args.avoid_mixed_coinbase {
+		Some(true) => Some(selection::select_unmixed_inputs(
 			&mut *w,
-			keychain_mask,
 			args.amount,
+			current_height,
 			args.minimum_confirmations,
-			args.max_outputs as usize,
-			args.num_change_outputs as usize,
+			max_outputs,
+			num_outputs,
 			args.selection_strategy_is_use_all,
-			&parent_key_id,
-		)?;
-		slate.amount = total;
-		slate.fee = fee;
-		return Ok(slate);
+			args.inputs.as_ref().map(|i| i.as_slice()),
+			parent_key_id,
+		)?),
+		_ => None,
+	};
+	match args.consolidation_fee_threshold {
+		Some(max_fee_increase) if consolidate => {
+			let inputs = selection::select_consolidating_inputs(
+				&mut *w,
+				args.amount,
+				current_height,
+				args.minimum_confirmations,
+				max_outputs,
+				num_outputs,
+				args.selection_strategy_is_use_all,
+				inputs.as_ref().map(|i| i.as_slice()),
+				max_fee_increase,
+				parent_key_id,
+			)?;
+			Ok(Some(inputs))
+		}
+		_ => Ok(inputs),
 	}
+}
 
-	let mut context = tx::add_inputs_to_slate(
-		&mut *w,
-		keychain_mask,
-		&mut slate,
+/// Set the lock height of a send's slate from the kernel features chosen for it,
+/// checking the kernel can be confirmed before the send expires and that the node
+/// reports the header version the slate is built for
+fn set_kernel_features<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	slate: &mut Slate,
+	kernel_features: &Option<TxKernelFeatures>,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let lock_height = match kernel_features {
+		Some(TxKernelFeatures::HeightLocked { lock_height }) => *lock_height,
+		_ => return Ok(()),
+	};
+	let invalid = |msg: String| -> Error { ErrorKind::KernelFeatures(msg).into() };
+	if lock_height <= slate.height {
+		return Err(invalid(format!(
+			"lock height {} isn't above the current height {}",
+			lock_height, slate.height
+		)));
+	}
+	if let Some(cutoff) = slate.ttl_cutoff_height {
+		if lock_height > cutoff {
+			return Err(invalid(format!(
+				"lock height {} is beyond the TTL cutoff height {}",
+				lock_height, cutoff
+			)));
+		}
+	}
+	// a node that doesn't report its version can't be checked, as elsewhere
+	if let Some(v) = w.w2n_client().get_version_info() {
+		if v.verified == Some(false) {
+			return Err(invalid(format!(
+				"node {} doesn't report its header version, it may not accept height-locked kernels",
+				v.node_version
+			)));
+		}
+		if v.block_header_version < slate.version_info.block_header_version {
+			return Err(invalid(format!(
+				"node is at header version {}, the transaction is built for version {}",
+				v.block_header_version, slate.version_info.block_header_version
+			)));
+		}
+	}
+	slate.lock_height = lock_height;
+	Ok(())
+}
+
+/// Dry run of a send, selecting its inputs and computing its fee as `init_send_tx`
+/// would, without building the transaction or locking anything. It's also the
+/// `estimate_only` path of `init_send_tx`, and must never save a context, being
+/// allowed for read-only tokens.
+pub fn estimate_send_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: &InitTxArgs,
+) -> Result<TxEstimate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (parent_key_id, num_outputs, max_outputs) = send_selection(&mut *w, args)?;
+	let inputs = send_inputs(&mut *w, args, &parent_key_id, num_outputs, max_outputs)?;
+	let (inputs, total, fee) = tx::estimate_send_tx(
+		&mut *w,
+		keychain_mask,
+		args.amount,
 		args.minimum_confirmations,
-		args.max_outputs as usize,
-		args.num_change_outputs as usize,
+		max_outputs,
+		num_outputs,
 		args.selection_strategy_is_use_all,
+		inputs.as_ref().map(|i| i.as_slice()),
+		&parent_key_id,
+	)?;
+	let change = total.saturating_sub(args.amount + fee);
+	let input_composition = InputComposition::from_outputs(inputs.iter().map(|i| &i.output));
+	Ok(TxEstimate {
+		amount: args.amount,
+		fee,
+		inputs,
+		total,
+		change,
+		num_change_outputs: match change {
+			0 => 0,
+			_ => num_outputs,
+		},
+		input_composition,
+	})
+}
+
+/// Select the inputs of a send from a watch-only wallet and compute its fee, into a
+/// slate without any outputs or signatures. The send is then signed on the host holding
+/// the wallet's keys by `sign_tx_offline`, from the returned request.
+pub fn init_unsigned_send_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	args: InitTxArgs,
+) -> Result<SigningRequest, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (parent_key_id, num_outputs, max_outputs) = send_selection(&mut *w, &args)?;
+	let mut slate = tx::new_tx_slate(&mut *w, args.amount, 2, false, args.ttl_blocks)?;
+	set_kernel_features(&mut *w, &mut slate, &args.kernel_features)?;
+	let inputs = send_inputs(&mut *w, &args, &parent_key_id, num_outputs, max_outputs)?;
+	let (coins, _, _, fee) = selection::select_coins_and_fee(
+		&mut *w,
+		args.amount,
+		slate.height,
+		args.minimum_confirmations,
+		max_outputs,
+		num_outputs,
+		args.selection_strategy_is_use_all,
+		inputs.as_ref().map(|i| i.as_slice()),
+		None,
 		&parent_key_id,
-		0,
-		message,
-		true,
-		use_test_rng,
 	)?;
+	for coin in coins.iter() {
+		let commit = match coin.commit.clone().map(epic_util::from_hex) {
+			Some(Ok(c)) => pedersen::Commitment::from_vec(c),
+			_ => {
+				let msg = format!("no commitment stored for output {}", coin.key_id);
+				return Err(ErrorKind::WatchOnly(msg).into());
+			}
+		};
+		let features = match coin.is_coinbase {
+			true => OutputFeatures::Coinbase,
+			false => OutputFeatures::Plain,
+		};
+		slate.tx = slate.tx.with_input(Input::new(features, commit));
+	}
+	slate.fee = fee;
+	let message = match args.message {
+		Some(mut m) => {
+			m.truncate(USER_MESSAGE_MAX_LEN);
+			Some(m)
+		}
+		None => None,
+	};
+	Ok(SigningRequest {
+		slate,
+		inputs: coins,
+		num_change_outputs: args.num_change_outputs,
+		next_child_index: w.current_child_index(&parent_key_id)?,
+		message,
+	})
+}
+
+/// Initiate tx as sender
+pub fn init_send_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: InitTxArgs,
+	use_test_rng: bool,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (parent_key_id, num_outputs, max_outputs) = send_selection(&mut *w, &args)?;
+
+	let mut slate = tx::new_tx_slate(&mut *w, args.amount, 2, use_test_rng, args.ttl_blocks)?;
+	set_kernel_features(&mut *w, &mut slate, &args.kernel_features)?;
+
+	// if we just want to estimate, don't save a context, just send the results
+	// back
+	if let Some(true) = args.estimate_only {
+		let estimate = estimate_send_tx(&mut *w, keychain_mask, &args)?;
+		slate.amount = estimate.total;
+		slate.fee = estimate.fee;
+		return Ok(slate);
+	}
+
+	let message = match args.message {
+		Some(mut m) => {
+			m.truncate(USER_MESSAGE_MAX_LEN);
+			Some(m)
+		}
+		None => None,
+	};
+
+	let inputs = send_inputs(&mut *w, &args, &parent_key_id, num_outputs, max_outputs)?;
+	let inputs = inputs.as_ref().map(|i| i.as_slice());
+	let mut context = match multisig::shared_input(&mut *w, inputs, &parent_key_id)? {
+		Some(output) => multisig::add_shared_input_to_slate(
+			&mut *w,
+			keychain_mask,
+			&mut slate,
+			&output,
+			message,
+			use_test_rng,
+		)?,
+		None => tx::add_inputs_to_slate(
+			&mut *w,
+			keychain_mask,
+			&mut slate,
+			args.minimum_confirmations,
+			max_outputs,
+			args.num_change_outputs as usize,
+			args.decoy_output_amount,
+			args.selection_strategy_is_use_all,
+			inputs,
+			&parent_key_id,
+			0,
+			message,
+			true,
+			use_test_rng,
+		)?,
+	};
+
+	if let Some(max_weight) = args.max_tx_weight {
+		// the recipient adds one output of their own
+		let weight = selection::tx_weight(
+			slate.tx.inputs().len(),
+			slate.tx.outputs().len() + 1,
+			slate.tx.kernels().len(),
+		);
+		if weight > max_weight {
+			let current_height = w.w2n_client().get_chain_tip()?.0;
+			let needed_txs = selection::split_send_amounts(
+				&mut *w,
+				args.amount,
+				current_height,
+				args.minimum_confirmations,
+				num_outputs,
+				max_weight,
+				&parent_key_id,
+			)?
+			.len();
+			return Err(ErrorKind::TxTooHeavy {
+				weight,
+				max_weight,
+				needed_txs,
+			})?;
+		}
+	}
 
 	// Payment Proof, add addresses to slate and save address
 	// TODO: Note we only use single derivation path for now,
@@ -402,6 +1233,11 @@ where
 
 		context.payment_proof_derivation_index = Some(deriv_path);
 	}
+	context.response_address = args.response_address;
+
+	if let Some(v) = args.target_slate_version {
+		slate.restrict_to_version(v)?;
+	}
 
 	// Save the aggsig context in our DB for when we
 	// recieve the transaction back
@@ -410,10 +1246,267 @@ where
 		batch.save_private_context(slate.id.as_bytes(), 0, &context)?;
 		batch.commit()?;
 	}
-	if let Some(v) = args.target_slate_version {
-		slate.version_info.orig_version = v;
+
+	Ok(slate)
+}
+
+/// Initiate a send to each of several recipients as `init_send_tx` would, their inputs
+/// selected in a single pass over the wallet's outputs so no two sends spend the same
+/// output, then lock the outputs of all of them. Each send is run by the pre-send hook
+/// if any, and marked as awaiting approval if requested, before its outputs are locked.
+/// Either all the sends are initiated or none are: if one can't be, those initiated
+/// before it are undone and its error is given in its place, the others failing
+/// alongside it. Estimates don't select from the shared outputs, nor lock any.
+pub fn init_send_tx_batch<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: Vec<InitTxArgs>,
+	tx_hook: Option<&dyn TxHook>,
+	request_approval: bool,
+	use_test_rng: bool,
+) -> Result<Vec<Result<Slate, Error>>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let count = args.len();
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+	let mut outputs: Vec<OutputData> = w.iter().collect();
+	outputs.sort_by_key(|out| out.value);
+	// given inputs aren't selected for the other sends
+	let given: HashSet<String> = args
+		.iter()
+		.filter_map(|a| a.inputs.as_ref())
+		.flatten()
+		.map(|c| epic_util::to_hex(c.0.to_vec()))
+		.collect();
+	outputs.retain(|out| out.commit.as_ref().map_or(true, |c| !given.contains(c)));
+
+	let mut slates = vec![];
+	let mut failure = None;
+	for (i, mut a) in args.into_iter().enumerate() {
+		let estimate_only = a.estimate_only == Some(true);
+		if a.inputs.is_none() && !estimate_only {
+			match batch_inputs(&mut *w, &mut outputs, &a, current_height) {
+				Ok(inputs) => a.inputs = Some(inputs),
+				Err(e) => {
+					failure = Some((i, e));
+					break;
+				}
+			}
+		}
+		match init_send_tx(&mut *w, keychain_mask, a, use_test_rng) {
+			Ok(slate) => slates.push((slate, estimate_only)),
+			Err(e) => {
+				failure = Some((i, e));
+				break;
+			}
+		}
+		if estimate_only {
+			continue;
+		}
+		let slate = &slates[i].0;
+		let res = match tx_hook {
+			Some(h) => h.pre_send(slate),
+			None => Ok(()),
+		};
+		let res = match request_approval {
+			true => res.and_then(|_| request_tx_approval(&mut *w, keychain_mask, slate.id)),
+			false => res,
+		};
+		if let Err(e) = res {
+			failure = Some((i, e));
+			break;
+		}
+	}
+
+	// the outputs of all the sends are locked once they're all initiated
+	let mut locked = vec![];
+	if failure.is_none() {
+		for (i, (slate, estimate_only)) in slates.iter().enumerate() {
+			if *estimate_only {
+				continue;
+			}
+			match tx_lock_outputs(&mut *w, keychain_mask, slate, 0) {
+				Ok(()) => locked.push(slate.id),
+				Err(e) => {
+					failure = Some((i, e));
+					break;
+				}
+			}
+		}
+	}
+	let (failed, error) = match failure {
+		Some(f) => f,
+		None => return Ok(slates.into_iter().map(|(s, _)| Ok(s)).collect()),
+	};
+
+	// else the sends initiated are undone
+	for (slate, estimate_only) in slates.iter() {
+		if *estimate_only {
+			continue;
+		}
+		if locked.contains(&slate.id) {
+			let context = w.get_private_context(keychain_mask, slate.id.as_bytes(), 0)?;
+			let parent_key_id = context.parent_key_id;
+			tx::cancel_tx(&mut *w, keychain_mask, &parent_key_id, None, Some(slate.id))?;
+		}
+		let mut batch = w.batch(keychain_mask)?;
+		batch.delete_private_context(slate.id.as_bytes(), 0)?;
+		batch.commit()?;
+	}
+	let msg = format!("not initiated, the send to recipient {} failed", failed);
+	let mut results: Vec<Result<Slate, Error>> = (0..count)
+		.map(|_| Err(ErrorKind::GenericError(msg.clone()).into()))
+		.collect();
+	results[failed] = Err(error);
+	Ok(results)
+}
+
+/// Inputs of a send of a batch, selected from the outputs the sends before it left
+fn batch_inputs<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	outputs: &mut Vec<OutputData>,
+	args: &InitTxArgs,
+	current_height: u64,
+) -> Result<Vec<pedersen::Commitment>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (parent_key_id, num_outputs, max_outputs) = send_selection(&mut *w, args)?;
+	let mut select = |coinbase| {
+		selection::select_batch_inputs(
+			outputs,
+			args.amount,
+			current_height,
+			args.minimum_confirmations,
+			max_outputs,
+			num_outputs,
+			args.selection_strategy_is_use_all,
+			coinbase,
+			&parent_key_id,
+		)
+	};
+	let coins = match args.avoid_mixed_coinbase {
+		// plain outputs are preferred
+		Some(true) => match select(Some(false)) {
+			Ok(coins) => coins,
+			Err(_) => select(Some(true))?,
+		},
+		_ => select(None)?,
+	};
+	selection::output_commitments(&coins)
+}
+
+/// Sign the send of a watch-only wallet's signing request as `init_send_tx` would, from
+/// the request's inputs, without contacting the node. The inputs must be outputs of the
+/// active account, they're added to the wallet if it doesn't know them yet.
+pub fn sign_tx_offline<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	request: &SigningRequest,
+	use_test_rng: bool,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let invalid = |msg: &str| -> Error { ErrorKind::SigningRequest(msg.to_owned()).into() };
+	let mut slate = request.slate.clone();
+	if !slate.participant_data.is_empty() || !slate.tx.outputs().is_empty() {
+		return Err(invalid("the slate was signed already"));
+	}
+	if request.inputs.is_empty() {
+		return Err(invalid("the send has no inputs"));
+	}
+
+	// the inputs must be those of the slate, with the keys of the wallet
+	let keychain = w.keychain(keychain_mask)?;
+	let parent_key_id = w.parent_key_id();
+	let mut commits = vec![];
+	for input in request.inputs.iter() {
+		if input.root_key_id != parent_key_id {
+			let msg = format!("input {} isn't of the active account", input.key_id);
+			return Err(invalid(&msg));
+		}
+		let commit = keychain.commit(input.value, &input.key_id, &SwitchCommitmentType::Regular)?;
+		if input.commit != Some(epic_util::to_hex(commit.0.to_vec())) {
+			let msg = format!("input {} doesn't belong to the wallet", input.key_id);
+			return Err(invalid(&msg));
+		}
+		commits.push(commit);
+	}
+	let slate_inputs: HashSet<pedersen::Commitment> =
+		slate.tx.inputs().iter().map(|i| i.commitment()).collect();
+	if slate_inputs.len() != commits.len() || commits.iter().any(|c| !slate_inputs.contains(c)) {
+		return Err(invalid("the inputs aren't those of the slate"));
+	}
+
+	// the watch-only wallet may know outputs of the account derived after those the
+	// wallet knows of, the change must be derived after them too
+	let next_child = request
+		.inputs
+		.iter()
+		.map(|i| i.n_child + 1)
+		.chain(vec![
+			request.next_child_index,
+			w.current_child_index(&parent_key_id)?,
+		])
+		.max()
+		.unwrap_or(0);
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		for input in request.inputs.iter() {
+			if batch.get(&input.key_id, &input.mmr_index).is_err() {
+				batch.save(OutputData {
+					status: OutputStatus::Unspent,
+					tx_log_entry: None,
+					frozen: false,
+					..input.clone()
+				})?;
+			}
+		}
+		batch.save_child_index(&parent_key_id, next_child)?;
+		batch.commit()?;
 	}
 
+	let fee = slate.fee;
+	slate.tx = Transaction::empty();
+	let mut context = selection::build_send_tx(
+		&mut *w,
+		&keychain,
+		keychain_mask,
+		&mut slate,
+		1,
+		commits.len(),
+		request.num_change_outputs as usize,
+		None,
+		false,
+		Some(&commits),
+		parent_key_id.clone(),
+		use_test_rng,
+	)?;
+	if slate.fee != fee {
+		let msg = format!("fee of {} instead of {}", fee, slate.fee);
+		return Err(invalid(&msg));
+	}
+	let _ = slate.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		0,
+		request.message.clone(),
+		use_test_rng,
+	)?;
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(slate.id.as_bytes(), 0, &context)?;
+		batch.commit()?;
+	}
 	Ok(slate)
 }
 
@@ -460,6 +1553,10 @@ where
 		use_test_rng,
 	)?;
 
+	if let Some(v) = args.target_slate_version {
+		slate.restrict_to_version(v)?;
+	}
+
 	// Save the aggsig context in our DB for when we
 	// recieve the transaction back
 	{
@@ -468,10 +1565,6 @@ where
 		batch.commit()?;
 	}
 
-	if let Some(v) = args.target_slate_version {
-		slate.version_info.orig_version = v;
-	}
-
 	Ok(slate)
 }
 
@@ -538,7 +1631,9 @@ where
 		args.minimum_confirmations,
 		args.max_outputs as usize,
 		args.num_change_outputs as usize,
+		args.decoy_output_amount,
 		args.selection_strategy_is_use_all,
+		args.inputs.as_ref().map(|i| i.as_slice()),
 		&parent_key_id,
 		0,
 		message,
@@ -546,6 +1641,10 @@ where
 		use_test_rng,
 	)?;
 
+	if let Some(v) = args.target_slate_version {
+		ret_slate.restrict_to_version(v)?;
+	}
+
 	// Save the aggsig context in our DB for when we
 	// recieve the transaction back
 	{
@@ -554,10 +1653,6 @@ where
 		batch.commit()?;
 	}
 
-	if let Some(v) = args.target_slate_version {
-		ret_slate.version_info.orig_version = v;
-	}
-
 	Ok(ret_slate)
 }
 
@@ -589,11 +1684,18 @@ where
 	K: Keychain + 'a,
 {
 	let mut sl = slate.clone();
+	if sl.multisig_output.is_some() {
+		return Err(ErrorKind::Multisig(
+			"a shared output is completed with receive_multisig".to_owned(),
+		))?;
+	}
 	check_ttl(w, &sl)?;
 	let context = w.get_private_context(keychain_mask, sl.id.as_bytes(), 0)?;
+	check_tx_approved(w, &sl, &context)?;
 	let parent_key_id = w.parent_key_id();
 	tx::complete_tx(&mut *w, keychain_mask, &mut sl, 0, &context)?;
 	tx::verify_slate_payment_proof(&mut *w, keychain_mask, &parent_key_id, &context, &sl)?;
+	tx::verify_response_signature(&mut *w, keychain_mask, &parent_key_id, &context, &sl)?;
 	tx::update_stored_tx(&mut *w, keychain_mask, &context, &mut sl, false)?;
 	tx::update_message(&mut *w, keychain_mask, &mut sl)?;
 	{
@@ -604,66 +1706,1143 @@ where
 	Ok(sl)
 }
 
-/// cancel tx
-pub fn cancel_tx<'a, L, C, K>(
-	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+/// Mark the send of a slate as awaiting the approval of a second operator, which
+/// `finalize_tx` requires
+pub fn request_tx_approval<'a, T: ?Sized, C, K>(
+	w: &mut T,
 	keychain_mask: Option<&SecretKey>,
-	status_send_channel: &Option<Sender<StatusMessage>>,
-	tx_id: Option<u32>,
-	tx_slate_id: Option<Uuid>,
+	tx_slate_id: Uuid,
 ) -> Result<(), Error>
 where
-	L: WalletLCProvider<'a, C, K>,
+	T: WalletBackend<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	if !update_wallet_state(
-		wallet_inst.clone(),
-		keychain_mask,
-		status_send_channel,
-		false,
-	)? {
-		return Err(ErrorKind::TransactionCancellationError(
-			"Can't contact running Epic node. Not Cancelling.",
-		))?;
-	}
-	wallet_lock!(wallet_inst, w);
-	let parent_key_id = w.parent_key_id();
-	tx::cancel_tx(&mut **w, keychain_mask, &parent_key_id, tx_id, tx_slate_id)
+	// the log entry is created when the outputs are locked, after the slate is sent
+	let mut context = w.get_private_context(keychain_mask, tx_slate_id.as_bytes(), 0)?;
+	context.approval = Some(TxApproval::Pending);
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_private_context(tx_slate_id.as_bytes(), 0, &context)?;
+	batch.commit()?;
+	Ok(())
 }
 
-/// get stored tx
-pub fn get_stored_tx<'a, T: ?Sized, C, K>(
-	w: &T,
-	entry: &TxLogEntry,
-) -> Result<Option<Transaction>, Error>
+/// Approve a send awaiting approval, returning its log entry. The send is looked
+/// for in every account, as it may have been made from one that isn't active; the
+/// active account's send is approved if several accounts have one with the id.
+pub fn approve_tx<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	tx_id: u32,
+) -> Result<TxLogEntry, Error>
 where
 	T: WalletBackend<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	w.get_stored_tx(entry)
-}
-
-/// Posts a transaction to the chain
-/// take a client impl instead of wallet so as not to have to lock the wallet
-pub fn post_tx<'a, C>(client: &C, tx: &Transaction, fluff: bool) -> Result<(), Error>
+	let txs = updater::retrieve_txs(w, Some(tx_id), None, None, false)?;
+	if txs.is_empty() {
+		return Err(ErrorKind::TransactionDoesntExist(tx_id.to_string()).into());
+	}
+	let mut pending: Vec<TxLogEntry> = txs
+		.into_iter()
+		.filter(|t| t.approval == Some(TxApproval::Pending))
+		.collect();
+	if pending.len() > 1 {
+		let parent_key_id = w.parent_key_id();
+		pending.retain(|t| t.parent_key_id == parent_key_id);
+	}
+	let mut tx = match pending.len() {
+		1 => pending.remove(0),
+		0 => {
+			let msg = format!("transaction {} isn't awaiting approval", tx_id);
+			return Err(ErrorKind::Approval(msg).into());
+		}
+		_ => {
+			let msg = format!(
+				"several accounts have a transaction {} awaiting approval, select the account of the one to approve",
+				tx_id
+			);
+			return Err(ErrorKind::Approval(msg).into());
+		}
+	};
+	tx.approval = Some(TxApproval::Approved);
+	let parent_key_id = tx.parent_key_id.clone();
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_tx_log_entry(tx.clone(), &parent_key_id)?;
+	batch.commit()?;
+	Ok(tx)
+}
+
+/// Fail if the send of the slate is awaiting approval, in the account it was made
+/// from
+fn check_tx_approved<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	slate: &Slate,
+	context: &Context,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let txs = updater::retrieve_txs(w, None, Some(slate.id), Some(&context.parent_key_id), false)?;
+	// the approval is only in the context until the outputs are locked
+	if txs.is_empty() && context.approval == Some(TxApproval::Pending) {
+		return Err(ErrorKind::ApprovalRequired(slate.id.to_string()).into());
+	}
+	match txs.iter().find(|t| t.approval == Some(TxApproval::Pending)) {
+		Some(t) => Err(ErrorKind::ApprovalRequired(t.id.to_string()).into()),
+		None => Ok(()),
+	}
+}
+
+/// Initiate a tx creating an output of the amount shared with the recipient, as the
+/// sender. The recipient joins it with `receive_multisig`.
+pub fn init_multisig_output<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: InitTxArgs,
+	use_test_rng: bool,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (parent_key_id, _, max_outputs) = send_selection(&mut *w, &args)?;
+	let mut slate = tx::new_tx_slate(&mut *w, args.amount, 2, use_test_rng, args.ttl_blocks)?;
+	let message = match args.message {
+		Some(mut m) => {
+			m.truncate(USER_MESSAGE_MAX_LEN);
+			Some(m)
+		}
+		None => None,
+	};
+
+	updater::refresh_outputs(&mut *w, keychain_mask, &parent_key_id, false)?;
+	let keychain = w.keychain(keychain_mask)?;
+	let mut context = selection::build_send_tx(
+		&mut *w,
+		&keychain,
+		keychain_mask,
+		&mut slate,
+		args.minimum_confirmations,
+		max_outputs,
+		args.num_change_outputs as usize,
+		None,
+		args.selection_strategy_is_use_all,
+		args.inputs.as_ref().map(|i| i.as_slice()),
+		parent_key_id.clone(),
+		use_test_rng,
+	)?;
+	multisig::add_output_share(
+		&mut *w,
+		keychain_mask,
+		&mut slate,
+		&mut context,
+		use_test_rng,
+	)?;
+	let _ = slate.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		0,
+		message,
+		use_test_rng,
+	)?;
+
+	if let Some(v) = args.target_slate_version {
+		slate.restrict_to_version(v)?;
+	}
+	{
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(slate.id.as_bytes(), 0, &context)?;
+		batch.commit()?;
+	}
+	Ok(slate)
+}
+
+/// Take this wallet's next step building the shared output of a slate: join it as the
+/// recipient, add the sender's rounds of its range proof and partial signature, or
+/// build its range proof and the final transaction as the recipient
+pub fn receive_multisig<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+	use_test_rng: bool,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut sl = slate.clone();
+	if sl.multisig_output.is_none() {
+		return Err(ErrorKind::Multisig(
+			"the slate doesn't create a shared output".to_owned(),
+		))?;
+	}
+	check_ttl(w, &sl)?;
+	if let Ok(context) = w.get_private_context(keychain_mask, sl.id.as_bytes(), 0) {
+		sl.fill_round_2(
+			&w.keychain(keychain_mask)?,
+			&context.sec_key,
+			&context.sec_nonce,
+			0,
+		)?;
+		multisig::add_proof_rounds(&mut *w, keychain_mask, &mut sl, &context)?;
+		tx::update_message(&mut *w, keychain_mask, &sl)?;
+		let mut batch = w.batch(keychain_mask)?;
+		batch.delete_private_context(sl.id.as_bytes(), 0)?;
+		batch.commit()?;
+	} else if let Ok(context) = w.get_private_context(keychain_mask, sl.id.as_bytes(), 1) {
+		multisig::complete_output(&mut *w, keychain_mask, &mut sl, &context)?;
+		tx::complete_tx(&mut *w, keychain_mask, &mut sl, 1, &context)?;
+		tx::update_stored_tx(&mut *w, keychain_mask, &context, &sl, true)?;
+		tx::update_message(&mut *w, keychain_mask, &sl)?;
+		let mut batch = w.batch(keychain_mask)?;
+		batch.delete_private_context(sl.id.as_bytes(), 1)?;
+		batch.commit()?;
+	} else {
+		let parent_key_id = w.parent_key_id();
+		let context = multisig::join_output(
+			&mut *w,
+			keychain_mask,
+			&mut sl,
+			&parent_key_id,
+			use_test_rng,
+		)?;
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_private_context(sl.id.as_bytes(), 1, &context)?;
+		batch.commit()?;
+	}
+	Ok(sl)
+}
+
+/// Start a swap of epics for bitcoins or litecoins as the seller: lock the epics in an
+/// output shared with the buyer, offering the swap to the buyer
+pub fn swap_start<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	args: SwapStartArgs,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	swap::check_start_args(&args)?;
+	let init_args = InitTxArgs {
+		src_acct_name: args.src_acct_name.clone(),
+		amount: args.amount,
+		minimum_confirmations: args.minimum_confirmations,
+		max_outputs: 500,
+		num_change_outputs: 1,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+	let lock_slate = init_multisig_output(&mut *w, keychain_mask, init_args, use_test_rng)?;
+	tx_lock_outputs(&mut *w, keychain_mask, &lock_slate, 0)?;
+	swap::offer(&mut *w, keychain_mask, lock_slate, &args, use_test_rng)
+}
+
+/// Take this wallet's next step of a swap, given the other party's last message if it
+/// sent one. Posts the transactions the step completes.
+pub fn swap_process<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: Uuid,
+	message: Option<SwapMessage>,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let body = match message {
+		Some(m) if m.id != id => {
+			return Err(ErrorKind::Swap(format!(
+				"the message is about swap {}, not {}",
+				m.id, id
+			)))?;
+		}
+		Some(m) => Some(m.body),
+		None => None,
+	};
+	let swap = match w.get_swap(&id)? {
+		Some(s) => s,
+		None => match body {
+			Some(SwapMessageBody::Offer(offer)) => {
+				return swap::accept(&mut *w, keychain_mask, &id, offer, use_test_rng);
+			}
+			_ => return Err(ErrorKind::Swap(format!("no swap {}", id)))?,
+		},
+	};
+
+	let (step, tx) = match (swap.role, swap.status, body) {
+		(
+			SwapRole::Seller,
+			SwapStatus::Offered,
+			Some(SwapMessageBody::Accept {
+				lock_slate,
+				refund_slate,
+				refund_public,
+				secret_public,
+			}),
+		) => (
+			swap::sign_lock(
+				&mut *w,
+				keychain_mask,
+				swap,
+				lock_slate,
+				refund_slate,
+				refund_public,
+				secret_public,
+			)?,
+			None,
+		),
+		(
+			SwapRole::Buyer,
+			SwapStatus::Accepted,
+			Some(SwapMessageBody::LockSigned { lock_slate }),
+		) => {
+			let (step, tx) = swap::complete_lock(&mut *w, keychain_mask, swap, lock_slate)?;
+			(step, Some(tx))
+		}
+		(SwapRole::Seller, SwapStatus::Locking, None) => (
+			swap::start_redeem(&mut *w, keychain_mask, swap, use_test_rng)?,
+			None,
+		),
+		(SwapRole::Buyer, SwapStatus::Locking, Some(SwapMessageBody::Redeem { redeem_slate })) => (
+			swap::adapt_redeem(&mut *w, keychain_mask, swap, redeem_slate, use_test_rng)?,
+			None,
+		),
+		(
+			SwapRole::Seller,
+			SwapStatus::Redeeming,
+			Some(SwapMessageBody::RedeemAdaptor {
+				redeem_slate,
+				adaptor,
+			}),
+		) => (
+			swap::check_adaptor(&mut *w, keychain_mask, swap, redeem_slate, adaptor)?,
+			None,
+		),
+		(SwapRole::Seller, SwapStatus::SecondaryLocking, None) => {
+			(swap::sign_redeem(&mut *w, keychain_mask, swap)?, None)
+		}
+		(
+			SwapRole::Buyer,
+			SwapStatus::SecondaryLocking,
+			Some(SwapMessageBody::RedeemSigned { redeem_slate }),
+		) => {
+			let (step, tx) = swap::complete_redeem(&mut *w, keychain_mask, swap, redeem_slate)?;
+			(step, Some(tx))
+		}
+		(SwapRole::Seller, SwapStatus::Signed, None) => {
+			(swap::reveal_secret(&mut *w, keychain_mask, swap)?, None)
+		}
+		(_, status, Some(_)) => {
+			return Err(ErrorKind::Swap(format!(
+				"unexpected message for swap {}, which is {}",
+				id, status
+			)))?;
+		}
+		(_, status, None) => {
+			return Err(ErrorKind::Swap(format!(
+				"swap {} is {}, waiting for the other party",
+				id, status
+			)))?;
+		}
+	};
+	if let Some(tx) = tx {
+		post_tx(w.w2n_client(), &tx, false)?;
+	}
+	Ok(step)
+}
+
+/// Cancel a swap, posting the refund of the locked epics to the seller if the swap's
+/// epics were locked
+pub fn swap_cancel<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: Uuid,
+) -> Result<Swap, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let swap = match w.get_swap(&id)? {
+		Some(s) => s,
+		None => return Err(ErrorKind::Swap(format!("no swap {}", id)))?,
+	};
+	let (swap, refund) = swap::cancel(&mut *w, keychain_mask, swap)?;
+	if let Some(tx) = refund {
+		post_tx(w.w2n_client(), &tx, false)?;
+	}
+	Ok(swap)
+}
+
+/// cancel tx
+pub fn cancel_tx<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	tx_id: Option<u32>,
+	tx_slate_id: Option<Uuid>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if !update_wallet_state(
+		wallet_inst.clone(),
+		keychain_mask,
+		status_send_channel,
+		false,
+	)? {
+		return Err(ErrorKind::TransactionCancellationError(
+			"Can't contact running Epic node. Not Cancelling.",
+		))?;
+	}
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = w.parent_key_id();
+	tx::cancel_tx(&mut **w, keychain_mask, &parent_key_id, tx_id, tx_slate_id)
+}
+
+/// Abandon a send whose slate exchange failed, unlocking its outputs and keeping its
+/// log entry as a cancelled send with the reason, so the wallet is ready to send again.
+/// The entry is created first if the outputs weren't locked yet.
+pub fn fail_send_tx<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+	reason: &str,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	// the send's account, which may not be the active one anymore
+	let context = w.get_private_context(keychain_mask, slate.id.as_bytes(), 0)?;
+	let parent_key_id = context.parent_key_id.clone();
+	let locked =
+		updater::retrieve_txs(&mut **w, None, Some(slate.id), Some(&parent_key_id), false)?
+			.iter()
+			.any(|t| t.tx_type == TxLogEntryType::TxSent);
+	if !locked {
+		selection::lock_tx_context(&mut **w, keychain_mask, slate, &context)?;
+	}
+	tx::cancel_tx(
+		&mut **w,
+		keychain_mask,
+		&parent_key_id,
+		None,
+		Some(slate.id),
+	)?;
+
+	let mut tx =
+		updater::retrieve_txs(&mut **w, None, Some(slate.id), Some(&parent_key_id), false)?
+			.into_iter()
+			.next()
+			.ok_or_else(|| ErrorKind::TransactionDoesntExist(slate.id.to_string()))?;
+	tx.failure_reason = Some(reason.to_owned());
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_tx_log_entry(tx, &parent_key_id)?;
+	batch.delete_private_context(slate.id.as_bytes(), 0)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// cancel all unconfirmed txs matching the given filter
+pub fn cancel_txs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	filter: &TxFilter,
+) -> Result<Vec<TxBulkResult>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if !update_wallet_state(
+		wallet_inst.clone(),
+		keychain_mask,
+		status_send_channel,
+		false,
+	)? {
+		return Err(ErrorKind::TransactionCancellationError(
+			"Can't contact running Epic node. Not Cancelling.",
+		))?;
+	}
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = w.parent_key_id();
+	let txs = bulk_txs(&mut **w, &parent_key_id, filter)?;
+	let mut results = vec![];
+	for t in txs.iter() {
+		let res = tx::cancel_tx(&mut **w, keychain_mask, &parent_key_id, Some(t.id), None);
+		results.push(TxBulkResult::new(t, res));
+	}
+	Ok(results)
+}
+
+/// The unconfirmed transactions of the account matching the filter, of the sent and
+/// received ones, the only ones cancelled or reposted in bulk
+fn bulk_txs<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	parent_key_id: &Identifier,
+	filter: &TxFilter,
+) -> Result<Vec<TxLogEntry>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let now = Utc::now();
+	let txs = updater::retrieve_txs(w, None, None, Some(parent_key_id), false)?
+		.into_iter()
+		.filter(|t| {
+			(t.tx_type == TxLogEntryType::TxSent || t.tx_type == TxLogEntryType::TxReceived)
+				&& filter.matches(t, now)
+		})
+		.collect();
+	Ok(txs)
+}
+
+/// repost the stored transactions of all unconfirmed txs matching the given filter
+pub fn repost_txs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	filter: &TxFilter,
+	fluff: bool,
+) -> Result<Vec<TxBulkResult>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	update_wallet_state(
+		wallet_inst.clone(),
+		keychain_mask,
+		status_send_channel,
+		false,
+	)?;
+	let (client, to_post) = {
+		wallet_lock!(wallet_inst, w);
+		let parent_key_id = w.parent_key_id();
+		let mut to_post = vec![];
+		for t in bulk_txs(&mut **w, &parent_key_id, filter)? {
+			let stored_tx = w.get_stored_tx(&t)?;
+			to_post.push((t, stored_tx));
+		}
+		(w.w2n_client().clone(), to_post)
+	};
+	let mut results = vec![];
+	for (t, stored_tx) in to_post.iter() {
+		let res = match stored_tx {
+			Some(s) => post_tx(&client, s, fluff),
+			None => Err(ErrorKind::GenericError(
+				"Transaction does not have transaction data".to_owned(),
+			)
+			.into()),
+		};
+		results.push(TxBulkResult::new(t, res));
+	}
+	Ok(results)
+}
+
+/// get stored tx
+pub fn get_stored_tx<'a, T: ?Sized, C, K>(
+	w: &T,
+	entry: &TxLogEntry,
+) -> Result<Option<Transaction>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	w.get_stored_tx(entry)
+}
+
+/// Posts a transaction to the chain
+/// take a client impl instead of wallet so as not to have to lock the wallet
+pub fn post_tx<'a, C>(client: &C, tx: &Transaction, fluff: bool) -> Result<(), Error>
+where
+	C: NodeClient + 'a,
+{
+	let tx_hex = epic_util::to_hex(ser::ser_vec(tx, ser::ProtocolVersion(1)).unwrap());
+	let res = client.post_tx(&TxWrapper { tx_hex }, fluff);
+	if let Err(e) = res {
+		error!("api: post_tx: failed with error: {}", e);
+		Err(e)
+	} else {
+		debug!(
+			"api: post_tx: successfully posted tx: {}, fluff? {}",
+			tx.hash(),
+			fluff
+		);
+		Ok(())
+	}
+}
+
+/// Check the node can be posted to: its tip isn't more than `POST_MAX_NODE_LAG` blocks
+/// behind the height the wallet last refreshed to and, on production chains, is recent
+/// enough for the node not to be syncing. Transactions posted to a node behind the wallet
+/// fail with confusing errors about missing outputs.
+pub fn check_node_height<'a, T: ?Sized, C, K>(wallet: &mut T) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let wallet_height = wallet.last_confirmed_height()?;
+	let client = wallet.w2n_client();
+	let node_height = client.get_chain_tip()?.0;
+	if node_height + POST_MAX_NODE_LAG < wallet_height {
+		return Err(ErrorKind::NodeNotReady(format!(
+			"the node is at height {}, behind the wallet's height {}",
+			node_height, wallet_height
+		))
+		.into());
+	}
+	if global::is_production_mode() {
+		if let Some(tip) = client.get_header_info(node_height)? {
+			let age = Utc::now()
+				.signed_duration_since(tip.timestamp)
+				.num_seconds();
+			if age > POST_MAX_TIP_AGE_SECS {
+				return Err(ErrorKind::NodeNotReady(format!(
+					"the node's latest block is {} minutes old, it may still be syncing",
+					age / 60
+				))
+				.into());
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Record a slate transport event in the persisted transport metrics
+pub fn record_transport_event<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	transport: &str,
+	event: TransportEvent,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	let mut metrics = w
+		.get_transport_metrics(transport)?
+		.unwrap_or(TransportMetrics::new(transport));
+	metrics.record(&event);
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_transport_metrics(metrics)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Check the epicbox key a slate was exchanged with against the key pinned for the
+/// contact, pinning it if this is the first slate with them. A changed key is reported
+/// but never replaces the pinned one, see [`unpin_key`](fn.unpin_key.html)
+pub fn check_pinned_key<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	contact: &str,
+	epicbox_key: &str,
+) -> Result<KeyPinCheck, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	let (pin, check) = match w.get_pinned_key(contact)? {
+		None => (PinnedKey::new(contact, epicbox_key), KeyPinCheck::New),
+		Some(pin) if pin.epicbox_key != epicbox_key => return Ok(KeyPinCheck::Changed(pin)),
+		Some(mut pin) => {
+			pin.last_seen = Utc::now();
+			pin.times_seen += 1;
+			(pin, KeyPinCheck::Known)
+		}
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_pinned_key(pin)?;
+	batch.commit()?;
+	Ok(check)
+}
+
+/// Retrieve the epicbox keys pinned for known contacts
+pub fn retrieve_pinned_keys<'a, T: ?Sized, C, K>(w: &mut T) -> Result<Vec<PinnedKey>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(w.pinned_keys_iter().collect())
+}
+
+/// Forget the epicbox key pinned for a contact, so the key of their next slate is
+/// pinned instead. Returns whether a key was pinned for the contact
+pub fn unpin_key<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	contact: &str,
+) -> Result<bool, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if w.get_pinned_key(contact)?.is_none() {
+		return Ok(false);
+	}
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_pinned_key(contact)?;
+	batch.commit()?;
+	Ok(true)
+}
+
+fn check_metadata_name(namespace: &str, key: &str) -> Result<(), Error> {
+	let valid_namespace = namespace
+		.chars()
+		.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+	if namespace.is_empty() || namespace.len() > METADATA_NAMESPACE_MAX_LEN || !valid_namespace {
+		let reason = format!(
+			"namespace must be 1 to {} letters, digits, '-', '_' or '.'",
+			METADATA_NAMESPACE_MAX_LEN
+		);
+		return Err(ErrorKind::InvalidMetadata(reason).into());
+	}
+	if key.is_empty() || key.len() > METADATA_KEY_MAX_LEN {
+		let reason = format!("key must be 1 to {} bytes", METADATA_KEY_MAX_LEN);
+		return Err(ErrorKind::InvalidMetadata(reason).into());
+	}
+	Ok(())
+}
+
+/// Retrieve the metadata entry stored under the given namespace and key
+pub fn get_metadata<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	namespace: &str,
+	key: &str,
+) -> Result<Option<MetadataEntry>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	check_metadata_name(namespace, key)?;
+	w.get_metadata(namespace, key)
+}
+
+/// Retrieve all metadata entries of a namespace, sorted by key
+pub fn retrieve_metadata<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	namespace: &str,
+) -> Result<Vec<MetadataEntry>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut entries: Vec<MetadataEntry> = w
+		.metadata_iter()
+		.filter(|e| e.namespace == namespace)
+		.collect();
+	entries.sort_by(|a, b| a.key.cmp(&b.key));
+	Ok(entries)
+}
+
+/// Remove the records of the outputs recorded more than once with the same
+/// commitment across the wallet's accounts, returning what was removed
+pub fn deduplicate_outputs<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<Vec<DeduplicatedOutput>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	updater::dedup_outputs(w, keychain_mask)
+}
+
+/// Retrieve the events kept in the wallet's store after the one with the given id,
+/// or all of them, oldest first
+pub fn get_events<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	since_id: Option<u64>,
+) -> Result<Vec<StoredEvent>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let since_id = since_id.unwrap_or(0);
+	let mut events: Vec<StoredEvent> = w.events_iter().filter(|e| e.id > since_id).collect();
+	events.sort_by_key(|e| e.id);
+	Ok(events)
+}
+
+/// Store a metadata value, replacing the value already stored under the same
+/// namespace and key. Fails if the value is too large or the namespace is full
+pub fn set_metadata<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	namespace: &str,
+	key: &str,
+	value: &str,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	check_metadata_name(namespace, key)?;
+	if value.len() > METADATA_VALUE_MAX_LEN {
+		let reason = format!("value may not exceed {} bytes", METADATA_VALUE_MAX_LEN);
+		return Err(ErrorKind::InvalidMetadata(reason).into());
+	}
+	if w.get_metadata(namespace, key)?.is_none() {
+		let count = w
+			.metadata_iter()
+			.filter(|e| e.namespace == namespace)
+			.count();
+		if count >= METADATA_MAX_ENTRIES_PER_NAMESPACE {
+			let reason = format!(
+				"namespace '{}' already holds {} entries",
+				namespace, METADATA_MAX_ENTRIES_PER_NAMESPACE
+			);
+			return Err(ErrorKind::InvalidMetadata(reason).into());
+		}
+	}
+	let entry = MetadataEntry {
+		namespace: namespace.to_owned(),
+		key: key.to_owned(),
+		value: value.to_owned(),
+		updated_ts: Utc::now(),
+	};
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_metadata(entry)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Remove a metadata entry. Returns whether an entry was stored under the namespace and key
+pub fn delete_metadata<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	namespace: &str,
+	key: &str,
+) -> Result<bool, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	check_metadata_name(namespace, key)?;
+	if w.get_metadata(namespace, key)?.is_none() {
+		return Ok(false);
+	}
+	let mut batch = w.batch(keychain_mask)?;
+	batch.delete_metadata(namespace, key)?;
+	batch.commit()?;
+	Ok(true)
+}
+
+/// Arguments of the sequential transactions needed to send `args.amount` without any
+/// of them exceeding `args.max_tx_weight`, each with the amount it sends and the inputs
+/// it was planned with, so the transactions can't pick each other's outputs. Without a
+/// weight limit, or with the inputs already given, these are the arguments themselves.
+pub fn split_send_tx<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	args: &InitTxArgs,
+) -> Result<Vec<InitTxArgs>, Error>
 where
+	L: WalletLCProvider<'a, C, K>,
 	C: NodeClient + 'a,
+	K: Keychain + 'a,
 {
-	let tx_hex = epic_util::to_hex(ser::ser_vec(tx, ser::ProtocolVersion(1)).unwrap());
-	let res = client.post_tx(&TxWrapper { tx_hex }, fluff);
-	if let Err(e) = res {
-		error!("api: post_tx: failed with error: {}", e);
-		Err(e)
-	} else {
-		debug!(
-			"api: post_tx: successfully posted tx: {}, fluff? {}",
-			tx.hash(),
-			fluff
-		);
-		Ok(())
+	let max_weight = match args.max_tx_weight {
+		Some(w) if args.inputs.is_none() => w,
+		_ => return Ok(vec![args.clone()]),
+	};
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = match args.src_acct_name.as_ref() {
+		Some(d) => match w.get_acct_path(d.to_owned())? {
+			Some(p) => p.path,
+			None => w.parent_key_id(),
+		},
+		None => w.parent_key_id(),
+	};
+	updater::refresh_outputs(&mut **w, keychain_mask, &parent_key_id, false)?;
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+	let parts = selection::split_send_amounts(
+		&mut **w,
+		args.amount,
+		current_height,
+		args.minimum_confirmations,
+		args.num_change_outputs as usize + args.decoy_output_amount.map_or(0, |_| 1),
+		max_weight,
+		&parent_key_id,
+	)?;
+	if parts.len() == 1 {
+		return Ok(vec![args.clone()]);
+	}
+	Ok(parts
+		.into_iter()
+		.map(|(amount, inputs)| InitTxArgs {
+			amount,
+			inputs: Some(inputs),
+			..args.clone()
+		})
+		.collect())
+}
+
+/// Add and remove tags of a single transaction, returning its resulting tags
+pub fn update_tx_tags<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	tx_id: Option<u32>,
+	tx_slate_id: Option<Uuid>,
+	add: &[String],
+	remove: &[String],
+) -> Result<Vec<String>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let add = normalize_tx_tags(add)?;
+	let remove = normalize_tx_tags(remove)?;
+	let tx_id_string = match (tx_id, tx_slate_id) {
+		(Some(i), _) => i.to_string(),
+		(None, Some(u)) => u.to_string(),
+		(None, None) => return Err(ErrorKind::TransactionDoesntExist(String::new()).into()),
+	};
+
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = w.parent_key_id();
+	let mut txs = updater::retrieve_txs(&mut **w, tx_id, tx_slate_id, Some(&parent_key_id), false)?;
+	if txs.len() != 1 {
+		return Err(ErrorKind::TransactionDoesntExist(tx_id_string).into());
+	}
+	let mut tx = txs.remove(0);
+	tx.tags.retain(|t| !remove.contains(t));
+	for t in add {
+		if !tx.tags.contains(&t) {
+			tx.tags.push(t);
+		}
+	}
+	tx.tags.sort();
+	let tags = tx.tags.clone();
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_tx_log_entry(tx, &parent_key_id)?;
+	batch.commit()?;
+	Ok(tags)
+}
+
+/// Retrieve all tags used by transactions of the active account, sorted
+pub fn retrieve_tx_tags<'a, T: ?Sized, C, K>(w: &mut T) -> Result<Vec<String>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = w.parent_key_id();
+	let mut tags: Vec<String> = updater::retrieve_txs(w, None, None, Some(&parent_key_id), false)?
+		.into_iter()
+		.flat_map(|t| t.tags)
+		.collect();
+	tags.sort();
+	tags.dedup();
+	Ok(tags)
+}
+
+/// Tags are stored trimmed and lowercased, and may not be empty or contain
+/// commas, which separate tags on the command line and in exports
+fn normalize_tx_tags(tags: &[String]) -> Result<Vec<String>, Error> {
+	tags.iter()
+		.map(|t| {
+			let tag = t.trim().to_lowercase();
+			if tag.is_empty() || tag.contains(',') {
+				Err(ErrorKind::InvalidTxTag(t.clone()).into())
+			} else {
+				Ok(tag)
+			}
+		})
+		.collect()
+}
+
+/// Transfer the spendable balance of the policy's hot account above its
+/// threshold to the cold account, recording both sides as an internal
+/// transfer tagged with [`SWEEP_TX_TAG`](../types/constant.SWEEP_TX_TAG.html).
+/// Returns the posted slate, or `None` if there was nothing to sweep.
+pub fn sweep_hot_account<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	policy: &SweepPolicy,
+) -> Result<Option<Slate>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	// never move funds based on a stale view of the chain
+	if !update_wallet_state(
+		wallet_inst.clone(),
+		keychain_mask,
+		status_send_channel,
+		true,
+	)? {
+		return Err(ErrorKind::GenericError(
+			"Unable to refresh wallet state from node, sweep aborted".to_owned(),
+		)
+		.into());
+	}
+
+	wallet_lock!(wallet_inst, w);
+	let hot = match w.get_acct_path(policy.hot_account.clone())? {
+		Some(m) => m,
+		None => return Err(ErrorKind::UnknownAccountLabel(policy.hot_account.clone()).into()),
+	};
+	let cold = match w.get_acct_path(policy.cold_account.clone())? {
+		Some(m) => m,
+		None => return Err(ErrorKind::UnknownAccountLabel(policy.cold_account.clone()).into()),
+	};
+	if hot.path == cold.path {
+		return Err(ErrorKind::GenericError(
+			"Hot and cold accounts of a sweep policy must differ".to_owned(),
+		)
+		.into());
+	}
+
+	let info = updater::retrieve_info(&mut **w, &hot.path, policy.minimum_confirmations)?;
+	if info.amount_currently_spendable <= policy.max_hot_balance {
+		return Ok(None);
+	}
+	let mut args = InitTxArgs {
+		src_acct_name: Some(hot.label.clone()),
+		amount: info.amount_currently_spendable - policy.max_hot_balance,
+		minimum_confirmations: policy.minimum_confirmations,
+		selection_strategy_is_use_all: false,
+		..Default::default()
+	};
+
+	// the fee is paid from the balance left in the hot account when it covers the fee
+	// of spending all its outputs, otherwise they're all spent and the fee is deducted
+	// from the swept amount, leaving exactly the balance behind, as change if any
+	let current_height = w.w2n_client().get_chain_tip()?.0;
+	let num_inputs = w
+		.iter()
+		.filter(|out| {
+			out.root_key_id == hot.path
+				&& out.eligible_to_spend(current_height, policy.minimum_confirmations)
+		})
+		.count();
+	let num_outputs = match policy.max_hot_balance {
+		0 => 1,
+		_ => 2,
+	};
+	let fee = tx_fee(num_inputs, num_outputs, 1, None);
+	if policy.max_hot_balance < fee {
+		if args.amount <= fee {
+			return Ok(None);
+		}
+		args.amount -= fee;
+		args.max_outputs = num_inputs as u32;
+		args.selection_strategy_is_use_all = true;
+	}
+
+	// the transfer is built from the hot account's point of view
+	let active_parent_key_id = w.parent_key_id();
+	w.set_parent_key_id(hot.path.clone());
+	let res = sweep_transfer(&mut **w, keychain_mask, &cold, args);
+	w.set_parent_key_id(active_parent_key_id);
+	let slate = res?;
+
+	let client = w.w2n_client().clone();
+	post_tx(&client, &slate.tx, false)?;
+	Ok(Some(slate))
+}
+
+fn sweep_transfer<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	cold: &AcctPathMapping,
+	args: InitTxArgs,
+) -> Result<Slate, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let hot_path = w.parent_key_id();
+	let slate = init_send_tx(&mut *w, keychain_mask, args, false)?;
+	tx_lock_outputs(&mut *w, keychain_mask, &slate, 0)?;
+	let slate = foreign::receive_tx(
+		&mut *w,
+		keychain_mask,
+		&slate,
+		Some(cold.label.as_str()),
+		None,
+		false,
+		false,
+	)?;
+	let slate = finalize_tx(&mut *w, keychain_mask, &slate)?;
+
+	for parent_key_id in vec![&hot_path, &cold.path] {
+		let txs = updater::retrieve_txs(&mut *w, None, Some(slate.id), Some(parent_key_id), false)?;
+		let mut batch = w.batch(keychain_mask)?;
+		for mut t in txs {
+			if !t.tags.iter().any(|tag| tag == SWEEP_TX_TAG) {
+				t.tags.push(SWEEP_TX_TAG.to_owned());
+				t.tags.sort();
+			}
+			batch.save_tx_log_entry(t, parent_key_id)?;
+		}
+		batch.commit()?;
 	}
+	Ok(slate)
+}
+
+/// Retrieve transport metrics
+pub fn retrieve_transport_metrics<'a, T: ?Sized, C, K>(
+	w: &mut T,
+) -> Result<Vec<TransportMetrics>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(w.transport_metrics_iter().collect())
+}
+
+/// Retrieve the latency statistics of the requests made to the node
+pub fn retrieve_node_metrics<'a, T: ?Sized, C, K>(
+	w: &mut T,
+) -> Result<Vec<NodeRequestMetrics>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	Ok(w.w2n_client().request_metrics())
 }
 
 /// verify slate messages
@@ -671,21 +2850,69 @@ pub fn verify_slate_messages(slate: &Slate) -> Result<(), Error> {
 	slate.verify_messages()
 }
 
+/// Pick `count` distinct random positions (starting from 1) of the words of the
+/// given recovery phrase, in ascending order, for the user to confirm
+pub fn seed_quiz(mnemonic: &str, count: usize) -> Result<Vec<usize>, Error> {
+	let num_words = mnemonic.split_whitespace().count();
+	if count == 0 || count > num_words {
+		let msg = format!(
+			"Number of words to verify must be between 1 and {}",
+			num_words
+		);
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	let mut positions: Vec<usize> = (1..=num_words).collect();
+	thread_rng().shuffle(&mut positions);
+	positions.truncate(count);
+	positions.sort();
+	Ok(positions)
+}
+
+/// Whether the given words are those of the recovery phrase at exactly the given
+/// positions, each answered once
+pub fn check_seed_words(mnemonic: &str, positions: &[usize], words: &[SeedWord]) -> bool {
+	let phrase: Vec<&str> = mnemonic.split_whitespace().collect();
+	let mut answered: Vec<usize> = words.iter().map(|w| w.position).collect();
+	answered.sort();
+	let mut positions = positions.to_vec();
+	positions.sort();
+	!words.is_empty()
+		&& answered == positions
+		&& words.iter().all(|w| {
+			w.position > 0
+				&& phrase
+					.get(w.position - 1)
+					.map(|p| p.eq_ignore_ascii_case(w.word.trim()))
+					.unwrap_or(false)
+		})
+}
+
 /// check repair
 /// Accepts a wallet inst instead of a raw wallet so it can
-/// lock as little as possible
+/// lock as little as possible. `progress` is passed on to the scan,
+/// which it can cancel
 pub fn scan<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
 	start_height: Option<u64>,
 	delete_unconfirmed: bool,
 	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
 ) -> Result<(), Error>
 where
 	L: WalletLCProvider<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
+	if let Some(view_key) = watch_only_view_key(wallet_inst.clone())? {
+		return watch_only_scan(
+			wallet_inst,
+			&view_key,
+			start_height.unwrap_or(1),
+			status_send_channel,
+			progress,
+		);
+	}
 	update_outputs(wallet_inst.clone(), keychain_mask, true)?;
 	let tip = {
 		wallet_lock!(wallet_inst, w);
@@ -696,23 +2923,203 @@ where
 		Some(h) => h,
 		None => 1,
 	};
-
-	let mut info = scan::scan(
-		wallet_inst.clone(),
+
+	let mut info = scan::scan(
+		wallet_inst.clone(),
+		keychain_mask,
+		delete_unconfirmed,
+		start_height,
+		tip.0,
+		None,
+		status_send_channel,
+		progress,
+	)?;
+	info.hash = tip.1;
+
+	wallet_lock!(wallet_inst, w);
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save_last_scanned_block(info)?;
+	batch.commit()?;
+
+	Ok(())
+}
+
+/// Parent key id of the account with the label
+fn account_parent_key_id<'a, T: ?Sized, C, K>(w: &mut T, label: &str) -> Result<Identifier, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	match w.acct_path_iter().find(|a| a.label == label) {
+		Some(a) => Ok(a.path),
+		None => Err(ErrorKind::UnknownAccountLabel(label.to_owned()).into()),
+	}
+}
+
+/// Scan of the chain only restoring and repairing the outputs of the account with the
+/// label. The wallet's last scanned block is left as it is, the other accounts not being
+/// checked.
+pub fn scan_account<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	account: &str,
+	start_height: Option<u64>,
+	delete_unconfirmed: bool,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = {
+		wallet_lock!(wallet_inst, w);
+		account_parent_key_id(&mut **w, account)?
+	};
+	// a watch-only wallet has its view key's account only
+	if let Some(view_key) = watch_only_view_key(wallet_inst.clone())? {
+		return watch_only_scan(
+			wallet_inst,
+			&view_key,
+			start_height.unwrap_or(1),
+			status_send_channel,
+			progress,
+		);
+	}
+	let tip = {
+		wallet_lock!(wallet_inst, w);
+		updater::refresh_outputs(&mut **w, keychain_mask, &parent_key_id, true)?;
+		w.w2n_client().get_chain_tip()?
+	};
+	scan::scan(
+		wallet_inst,
+		keychain_mask,
+		delete_unconfirmed,
+		start_height.unwrap_or(1),
+		tip.0,
+		Some(&parent_key_id),
+		status_send_channel,
+		progress,
+	)?;
+	Ok(())
+}
+
+/// Refresh of the outputs and transactions of the account with the label only, from
+/// the node, returning whether the node could be reached. Unlike
+/// [`update_wallet_state`](fn.update_wallet_state.html), all the account's outputs are
+/// checked but the recent blocks aren't scanned for new outputs.
+pub fn refresh_account<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	account: &str,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<bool, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = {
+		wallet_lock!(wallet_inst, w);
+		account_parent_key_id(&mut **w, account)?
+	};
+	// a snapshot is shown as it was exported
+	if is_snapshot(wallet_inst.clone())? {
+		return Ok(false);
+	}
+	if let Some(view_key) = watch_only_view_key(wallet_inst.clone())? {
+		return update_watch_only(wallet_inst, &view_key, status_send_channel);
+	}
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::UpdatingOutputs(format!(
+			"Updating outputs of account {} from node",
+			account
+		)));
+	}
+	let refreshed = {
+		wallet_lock!(wallet_inst, w);
+		updater::refresh_outputs(&mut **w, keychain_mask, &parent_key_id, true)
+	};
+	if let Err(e) = refreshed {
+		if let ErrorKind::InvalidKeychainMask = e.kind() {
+			return Err(e);
+		}
+		if let Some(ref s) = status_send_channel {
+			let _ = s.send(StatusMessage::UpdateWarning(
+				"Unable to contact node".to_owned(),
+			));
+		}
+		return Ok(false);
+	}
+
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::UpdatingTransactions(format!(
+			"Updating transactions of account {}",
+			account
+		)));
+	}
+	let mut txs = {
+		wallet_lock!(wallet_inst, w);
+		updater::retrieve_txs(&mut **w, None, None, Some(&parent_key_id), true)?
+	};
+	update_txs_via_kernel(wallet_inst, keychain_mask, &parent_key_id, &mut txs)
+}
+
+/// Restore outputs exported by another wallet, with their key derivation, checking
+/// them against the node's UTXO set instead of scanning the chain
+pub fn import_outputs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	outputs: Vec<ImportedOutput>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<OutputImportSummary, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	scan::import_outputs(wallet_inst, keychain_mask, outputs, status_send_channel)
+}
+
+/// Report the wallet outputs a scan between the given heights would find, without
+/// changing the wallet. The range defaults to the whole chain.
+pub fn scan_preview<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	start_height: Option<u64>,
+	end_height: Option<u64>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<ScanPreview, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let tip = {
+		wallet_lock!(wallet_inst, w);
+		w.w2n_client().get_chain_tip()?
+	};
+	let start_height = start_height.unwrap_or(1);
+	let end_height = match end_height {
+		Some(h) => cmp::min(h, tip.0),
+		None => tip.0,
+	};
+	if start_height > end_height {
+		let msg = format!(
+			"Start height {} is above end height {}",
+			start_height, end_height
+		);
+		return Err(ErrorKind::GenericError(msg).into());
+	}
+	scan::preview(
+		wallet_inst,
 		keychain_mask,
-		delete_unconfirmed,
 		start_height,
-		tip.0,
+		end_height,
 		status_send_channel,
-	)?;
-	info.hash = tip.1;
-
-	wallet_lock!(wallet_inst, w);
-	let mut batch = w.batch(keychain_mask)?;
-	batch.save_last_scanned_block(info)?;
-	batch.commit()?;
-
-	Ok(())
+	)
 }
 
 /// node height
@@ -736,6 +3143,18 @@ where
 			updated_from_node: true,
 		}),
 		Err(_) => {
+			// fall back to the most recent header cached from the node
+			let cached = {
+				wallet_lock!(wallet_inst, w);
+				w.block_headers_iter().max_by_key(|h| h.height)
+			};
+			if let Some(h) = cached {
+				return Ok(NodeHeightResult {
+					height: h.height,
+					header_hash: h.hash,
+					updated_from_node: false,
+				});
+			}
 			let outputs =
 				retrieve_outputs(wallet_inst, keychain_mask, &None, true, false, false, None)?;
 			let height = match outputs.1.iter().map(|m| m.output.height).max() {
@@ -750,6 +3169,31 @@ where
 		}
 	}
 }
+/// Header of the block at the given height, from the wallet's header cache
+/// if present, otherwise retrieved from the node and cached
+pub fn get_block_header<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	height: u64,
+) -> Result<Option<BlockHeaderInfo>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	if let Some(h) = w.get_block_header(height)? {
+		return Ok(Some(h));
+	}
+	let header = w.w2n_client().get_header_info(height)?;
+	if let Some(ref h) = header {
+		let mut batch = w.batch(keychain_mask)?;
+		batch.save_block_header(h.clone())?;
+		batch.commit()?;
+	}
+	Ok(header)
+}
+
 /// Experimental, wrap the entire definition of how a wallet's state is updated
 pub fn update_wallet_state<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -762,6 +3206,13 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
+	// a snapshot is shown as it was exported
+	if is_snapshot(wallet_inst.clone())? {
+		return Ok(false);
+	}
+	if let Some(view_key) = watch_only_view_key(wallet_inst.clone())? {
+		return update_watch_only(wallet_inst, &view_key, status_send_channel);
+	}
 	let parent_key_id = {
 		wallet_lock!(wallet_inst, w);
 		w.parent_key_id().clone()
@@ -771,7 +3222,22 @@ where
 		w.w2n_client().clone()
 	};
 
-	// Step 1: Update outputs and transactions purely based on UTXO state
+	// Step 1: Update outputs and transactions purely based on UTXO state, once the
+	// outputs recorded more than once are deduplicated so they aren't counted twice
+	let dups = {
+		wallet_lock!(wallet_inst, w);
+		updater::dedup_outputs(&mut **w, keychain_mask)
+	};
+	match dups {
+		Ok(dups) => {
+			if let Some(ref s) = status_send_channel {
+				for d in dups.iter() {
+					let _ = s.send(StatusMessage::UpdateWarning(updater::dedup_message(d)));
+				}
+			}
+		}
+		Err(e) => warn!("Unable to deduplicate the wallet's outputs: {}", e),
+	}
 	if let Some(ref s) = status_send_channel {
 		let _ = s.send(StatusMessage::UpdatingOutputs(
 			"Updating outputs from node".to_owned(),
@@ -799,7 +3265,7 @@ where
 		wallet_lock!(wallet_inst, w);
 		updater::retrieve_txs(&mut **w, None, None, Some(&parent_key_id), true)?
 	};
-	result = update_txs_via_kernel(wallet_inst.clone(), keychain_mask, &mut txs)?;
+	result = update_txs_via_kernel(wallet_inst.clone(), keychain_mask, &parent_key_id, &mut txs)?;
 	if !result {
 		if let Some(ref s) = status_send_channel {
 			let _ = s.send(StatusMessage::UpdateWarning(
@@ -859,7 +3325,9 @@ where
 		false,
 		start_index,
 		tip.0,
+		None,
 		status_send_channel,
+		None,
 	)?;
 
 	info.hash = tip.1;
@@ -873,6 +3341,37 @@ where
 		batch.commit()?;
 	}
 
+	// Step 4: Cache the headers up to the tip, noting any reorg since the last update
+	let start_height = {
+		wallet_lock!(wallet_inst, w);
+		updater::header_fetch_start(&mut **w, tip.0)
+	};
+	match client.get_headers(start_height, tip.0) {
+		Ok(headers) => {
+			let reorg = {
+				wallet_lock!(wallet_inst, w);
+				updater::cache_headers(&mut **w, keychain_mask, headers)?
+			};
+			if reorg {
+				{
+					wallet_lock!(wallet_inst, w);
+					record_event(
+						&mut **w,
+						keychain_mask,
+						WalletEvent::ReorgDetected { height: tip.0 },
+					);
+				}
+				if let Some(ref s) = status_send_channel {
+					let _ = s.send(StatusMessage::UpdateWarning(format!(
+						"Chain reorg detected below height {}",
+						tip.0
+					)));
+				}
+			}
+		}
+		Err(e) => warn!("Unable to retrieve block headers from node: {}", e),
+	}
+
 	// Step 5: Cancel any transactions with an expired TTL
 	for tx in txs {
 		if let Some(e) = tx.ttl_cutoff_height {
@@ -887,22 +3386,58 @@ where
 	Ok(result)
 }
 
-/// Check TTL
+/// Check TTL, against the node's tip so a slate isn't signed past its TTL
+/// while the wallet hasn't been updated. The height of the wallet's last
+/// update is used if the node can't be reached.
 pub fn check_ttl<'a, T: ?Sized, C, K>(w: &mut T, slate: &Slate) -> Result<(), Error>
 where
 	T: WalletBackend<'a, C, K>,
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	// Refuse if TTL is expired
+	let ttl_cutoff_height = match slate.ttl_cutoff_height {
+		Some(e) => e,
+		None => return Ok(()),
+	};
 	let last_confirmed_height = w.last_confirmed_height()?;
-	if let Some(e) = slate.ttl_cutoff_height {
-		if last_confirmed_height >= e {
-			return Err(ErrorKind::TransactionExpired)?;
+	let height = match w.w2n_client().get_chain_tip() {
+		Ok((tip, _)) => tip.max(last_confirmed_height),
+		Err(e) => {
+			warn!("Unable to check slate TTL against the node's tip: {}", e);
+			last_confirmed_height
 		}
+	};
+	// Refuse if TTL is expired
+	if height >= ttl_cutoff_height {
+		warn!(
+			"Refusing slate {}, its TTL expired at height {} (current height {})",
+			slate.id, ttl_cutoff_height, height
+		);
+		return Err(ErrorKind::TransactionExpired)?;
 	}
 	Ok(())
 }
+/// Check the sender and recipient signatures of a payment proof, which needs neither
+/// the node nor a wallet
+pub fn verify_payment_proof_signatures(proof: &PaymentProof) -> Result<(), Error> {
+	let sender_pubkey = address::pubkey_from_onion_v3(&proof.sender_address)?;
+	let msg = tx::payment_proof_message(proof.amount, &proof.excess, sender_pubkey)?;
+
+	let recipient_pubkey = address::pubkey_from_onion_v3(&proof.recipient_address)?;
+	if let Err(_) = recipient_pubkey.verify(&msg, &proof.recipient_sig) {
+		return Err(ErrorKind::PaymentProof(
+			"Invalid recipient signature".to_owned(),
+		))?;
+	};
+
+	if let Err(_) = sender_pubkey.verify(&msg, &proof.sender_sig) {
+		return Err(ErrorKind::PaymentProof(
+			"Invalid sender signature".to_owned(),
+		))?;
+	};
+	Ok(())
+}
+
 /// Verify/validate arbitrary payment proof
 /// Returns (whether this wallet is the sender, whether this wallet is the recipient)
 pub fn verify_payment_proof<'a, L, C, K>(
@@ -916,7 +3451,6 @@ where
 	K: Keychain + 'a,
 {
 	let sender_pubkey = address::pubkey_from_onion_v3(&proof.sender_address)?;
-	let msg = tx::payment_proof_message(proof.amount, &proof.excess, sender_pubkey)?;
 
 	let (mut client, parent_key_id, keychain) = {
 		wallet_lock!(wallet_inst, w);
@@ -947,19 +3481,8 @@ where
 	};
 
 	// Check Sigs
+	verify_payment_proof_signatures(proof)?;
 	let recipient_pubkey = address::pubkey_from_onion_v3(&proof.recipient_address)?;
-	if let Err(_) = recipient_pubkey.verify(&msg, &proof.recipient_sig) {
-		return Err(ErrorKind::PaymentProof(
-			"Invalid recipient signature".to_owned(),
-		))?;
-	};
-
-	let sender_pubkey = address::pubkey_from_onion_v3(&proof.sender_address)?;
-	if let Err(_) = sender_pubkey.verify(&msg, &proof.sender_sig) {
-		return Err(ErrorKind::PaymentProof(
-			"Invalid sender signature".to_owned(),
-		))?;
-	};
 
 	// for now, simple test as to whether one of the addresses belongs to this wallet
 	let sec_key = address::address_from_derivation_path(&keychain, &parent_key_id, 0)?;
@@ -974,11 +3497,429 @@ where
 	let sender_mine = my_address_pubkey == sender_pubkey;
 	let recipient_mine = my_address_pubkey == recipient_pubkey;
 
+	// record that the proof of one of our payments checked out
+	if sender_mine {
+		wallet_lock!(wallet_inst, w);
+		let tx = w
+			.tx_log_iter()
+			.find(|t| t.kernel_excess == Some(proof.excess) && t.payment_proof.is_some());
+		if let Some(mut t) = tx {
+			if let Some(ref mut p) = t.payment_proof {
+				p.state = Some(PaymentProofState::Verified);
+			}
+			let tx_parent_key_id = t.parent_key_id.clone();
+			let mut batch = w.batch(keychain_mask)?;
+			batch.save_tx_log_entry(t, &tx_parent_key_id)?;
+			batch.commit()?;
+		}
+	}
+
 	Ok((sender_mine, recipient_mine))
 }
 
+/// Message signed by a payment request: a fixed prefix followed by the address key,
+/// the amount and expiry as big endian integers (0 for no expiry) and the memo
+fn payment_request_message(
+	address: &DalekPublicKey,
+	amount: u64,
+	memo: &Option<String>,
+	expiry: &Option<DateTime<Utc>>,
+) -> Vec<u8> {
+	let mut msg = b"epic-payment-request".to_vec();
+	msg.extend_from_slice(address.as_bytes());
+	msg.extend_from_slice(&amount.to_be_bytes());
+	msg.extend_from_slice(&expiry.map(|e| e.timestamp()).unwrap_or(0).to_be_bytes());
+	if let Some(m) = memo {
+		msg.extend_from_slice(m.as_bytes());
+	}
+	msg
+}
+
+fn check_payment_request_fields(
+	amount: u64,
+	memo: &Option<String>,
+	expiry: &Option<DateTime<Utc>>,
+) -> Result<(), Error> {
+	if amount == 0 {
+		let reason = "amount must be greater than zero".to_owned();
+		return Err(ErrorKind::InvalidPaymentRequest(reason).into());
+	}
+	if let Some(m) = memo {
+		if m.len() > USER_MESSAGE_MAX_LEN {
+			let reason = format!("memo may not exceed {} bytes", USER_MESSAGE_MAX_LEN);
+			return Err(ErrorKind::InvalidPaymentRequest(reason).into());
+		}
+	}
+	if let Some(e) = expiry {
+		if *e <= Utc::now() {
+			let reason = format!("expired at {}", e.to_rfc3339());
+			return Err(ErrorKind::InvalidPaymentRequest(reason).into());
+		}
+	}
+	Ok(())
+}
+
+/// Create a payment request for the given amount, signed with the payment proof address
+/// of the active account
+pub fn create_payment_request<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	amount: u64,
+	memo: Option<String>,
+	expiry: Option<DateTime<Utc>>,
+) -> Result<PaymentRequest, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	// the expiry is signed in whole seconds
+	let expiry = expiry.map(|e| Utc.timestamp(e.timestamp(), 0));
+	check_payment_request_fields(amount, &memo, &expiry)?;
+
+	let sec_key = {
+		wallet_lock!(wallet_inst, w);
+		let parent_key_id = w.parent_key_id();
+		let k = w.keychain(keychain_mask)?;
+		address::address_from_derivation_path(&k, &parent_key_id, 0)?
+	};
+	let (secret, public) = address::ed25519_keypair(&sec_key)?;
+	let keypair = DalekKeypair { secret, public };
+	let msg = payment_request_message(&public, amount, &memo, &expiry);
+	Ok(PaymentRequest {
+		address: address::onion_v3_from_pubkey(&public)?,
+		amount,
+		memo,
+		expiry,
+		signature: keypair.sign(&msg),
+	})
+}
+
+/// Verify a payment request received from another wallet, returning the arguments to send
+/// the requested amount with a payment proof from the requesting wallet
+pub fn verify_payment_request(request: &PaymentRequest) -> Result<InitTxArgs, Error> {
+	let address = address::pubkey_from_onion_v3(&request.address)?;
+	let msg = payment_request_message(&address, request.amount, &request.memo, &request.expiry);
+	if address.verify(&msg, &request.signature).is_err() {
+		let reason = "invalid signature".to_owned();
+		return Err(ErrorKind::InvalidPaymentRequest(reason).into());
+	}
+	check_payment_request_fields(request.amount, &request.memo, &request.expiry)?;
+	Ok(InitTxArgs {
+		amount: request.amount,
+		message: request.memo.clone(),
+		payment_proof_recipient_address: Some(address),
+		..Default::default()
+	})
+}
+
+/// Check a counterparty's claim about the value and blinding factor of an output against
+/// the output and its rangeproof on the node's chain
+pub fn verify_output_claim<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	claim: &OutputClaim,
+) -> Result<OutputClaimVerification, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	let keychain = w.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+
+	// a zero blinding factor can't open any commitment
+	let opening_valid = match claim.blinding_factor.secret_key(secp) {
+		Ok(key) => secp.commit(claim.value, key)? == claim.commit,
+		Err(_) => false,
+	};
+	let mut res = OutputClaimVerification {
+		on_chain: false,
+		height: None,
+		is_coinbase: None,
+		range_proof_valid: false,
+		opening_valid,
+	};
+
+	let client = w.w2n_client();
+	let outputs = client.get_outputs_from_node(vec![claim.commit])?;
+	if let Some((_, height, mmr_index)) = outputs.get(&claim.commit) {
+		let (_, _, listing) = client.get_outputs_by_pmmr_index(*mmr_index, Some(*mmr_index), 1)?;
+		if let Some((commit, range_proof, is_coinbase, _, _)) =
+			listing.into_iter().find(|o| o.0 == claim.commit)
+		{
+			res.on_chain = true;
+			res.height = Some(*height);
+			res.is_coinbase = Some(is_coinbase);
+			res.range_proof_valid = proof::verify(secp, commit, range_proof, None).is_ok();
+		}
+	}
+	Ok(res)
+}
+
+/// Trace the wallet transactions that created and spent the output with the given
+/// commitment, from the output's recorded states. Returns `None` if the output isn't
+/// one of the wallet's.
+pub fn trace_output<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	commit: &pedersen::Commitment,
+) -> Result<Option<OutputLineage>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	wallet_lock!(wallet_inst, w);
+	let current = updater::retrieve_outputs(&mut **w, keychain_mask, true, false, None, None)?;
+	// every earlier state of the outputs, each naming the transaction that last touched it
+	let states = updater::retrieve_outputs(&mut **w, keychain_mask, true, true, None, None)?;
+	let txs: Vec<TxLogEntry> = w.tx_log_iter().collect();
+
+	let output = match current
+		.iter()
+		.chain(states.iter().rev())
+		.find(|o| o.commit == *commit)
+	{
+		Some(o) => o.clone(),
+		None => return Ok(None),
+	};
+	let parent_key_id = output.output.root_key_id.clone();
+	let is_spending = |o: &OutputCommitMapping| {
+		o.output.status == OutputStatus::Locked || o.output.status == OutputStatus::Spent
+	};
+	let tx = |id: u32| {
+		txs.iter()
+			.find(|t| t.id == id && t.parent_key_id == parent_key_id)
+			.cloned()
+	};
+	// latest state of each output in the given states, without duplicates
+	let latest = |outputs: Vec<&OutputCommitMapping>| {
+		let mut res: Vec<OutputCommitMapping> = vec![];
+		for o in outputs {
+			if res.iter().any(|r| r.commit == o.commit) {
+				continue;
+			}
+			match current.iter().find(|c| c.commit == o.commit) {
+				Some(c) => res.push(c.clone()),
+				None => res.push(o.clone()),
+			}
+		}
+		res
+	};
+
+	// a transaction locking the output spends it, any other one touching it created it
+	let own_states: Vec<&OutputCommitMapping> =
+		states.iter().filter(|o| o.commit == *commit).collect();
+	let mut spending_ids: Vec<u32> = vec![];
+	for o in own_states.iter().filter(|o| is_spending(o)) {
+		if let Some(id) = o.output.tx_log_entry {
+			if !spending_ids.contains(&id) {
+				spending_ids.push(id);
+			}
+		}
+	}
+	let created_by = own_states
+		.iter()
+		.filter_map(|o| o.output.tx_log_entry)
+		.find(|id| !spending_ids.contains(id))
+		.and_then(&tx);
+
+	let mut spent_by = None;
+	let mut cancelled_spends = vec![];
+	for t in spending_ids.into_iter().filter_map(&tx) {
+		match t.tx_type {
+			TxLogEntryType::TxSentCancelled | TxLogEntryType::TxReceivedCancelled => {
+				cancelled_spends.push(t)
+			}
+			_ => spent_by = Some(t),
+		}
+	}
+
+	let parents = match created_by {
+		Some(ref t) => latest(
+			states
+				.iter()
+				.filter(|o| {
+					o.output.root_key_id == parent_key_id
+						&& o.output.tx_log_entry == Some(t.id)
+						&& is_spending(o)
+				})
+				.collect(),
+		),
+		None => vec![],
+	};
+	let children = match spent_by {
+		Some(ref t) => latest(
+			states
+				.iter()
+				.filter(|o| {
+					o.output.root_key_id == parent_key_id
+						&& o.output.tx_log_entry == Some(t.id)
+						&& o.commit != *commit
+						&& !is_spending(o)
+				})
+				.collect(),
+		),
+		None => vec![],
+	};
+
+	Ok(Some(OutputLineage {
+		output,
+		created_by,
+		spent_by,
+		cancelled_spends,
+		parents,
+		children,
+	}))
+}
+
+/// Freeze or unfreeze the output with the given commitment. A frozen output is left
+/// out of coin selection until it's unfrozen.
+pub fn set_output_frozen<'a, T: ?Sized, C, K>(
+	w: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	commit: &pedersen::Commitment,
+	frozen: bool,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let commit = epic_util::to_hex(commit.0.to_vec());
+	let mut out = w
+		.iter()
+		.find(|o| o.commit.as_ref() == Some(&commit))
+		.ok_or_else(|| ErrorKind::UnknownOutput(commit.clone()))?;
+	if frozen && out.status == OutputStatus::Spent {
+		return Err(ErrorKind::GenericError(format!("Output {} is spent already", commit)).into());
+	}
+	if out.frozen == frozen {
+		return Ok(());
+	}
+	out.frozen = frozen;
+	let mut batch = w.batch(keychain_mask)?;
+	batch.save(out)?;
+	batch.commit()?;
+	Ok(())
+}
+
 /// Attempt to upda
 /// Attempt to update outputs in wallet, return whether it was successful
+/// Whether a snapshot of another wallet is open rather than a wallet
+fn is_snapshot<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+) -> Result<bool, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut w_lock = wallet_inst.lock();
+	Ok(w_lock.lc_provider()?.snapshot().is_some())
+}
+
+/// The view key of the wallet, if it's watch-only
+fn watch_only_view_key<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+) -> Result<Option<ViewKey>, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut w_lock = wallet_inst.lock();
+	w_lock.lc_provider()?.view_key(None)
+}
+
+/// Scan of a watch-only wallet from the given height up to the tip
+fn watch_only_scan<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	view_key: &ViewKey,
+	start_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let tip = {
+		wallet_lock!(wallet_inst, w);
+		w.w2n_client().get_chain_tip()?
+	};
+	let mut info = scan::scan_watch_only(
+		wallet_inst.clone(),
+		view_key,
+		start_height,
+		tip.0,
+		status_send_channel,
+		progress,
+	)?;
+	info.hash = tip.1;
+
+	wallet_lock!(wallet_inst, w);
+	let mut batch = w.batch_no_mask()?;
+	batch.save_last_scanned_block(info)?;
+	batch.save_init_status(WalletInitStatus::InitComplete)?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Update of a watch-only wallet, scanning the chain a bit before the last scanned
+/// block, or all of it on the first update. Returns whether the node could be reached.
+fn update_watch_only<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	view_key: &ViewKey,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<bool, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let start_height = {
+		wallet_lock!(wallet_inst, w);
+		match w.init_status()? {
+			WalletInitStatus::InitComplete => w.last_scanned_block()?.height.saturating_sub(100),
+			_ => 1,
+		}
+	};
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::UpdatingOutputs(
+			"Updating outputs from node".to_owned(),
+		));
+	}
+	match watch_only_scan(
+		wallet_inst,
+		view_key,
+		start_height,
+		status_send_channel,
+		None,
+	) {
+		Ok(()) => Ok(true),
+		Err(e) => match e.kind() {
+			ErrorKind::ClientCallback(_)
+			| ErrorKind::NodeUnreachable(_)
+			| ErrorKind::NodeSyncing(_)
+			| ErrorKind::NodeAuthFailed(_)
+			| ErrorKind::NodeIncompatible(_)
+			| ErrorKind::NodeOutputNotFound(_) => {
+				if let Some(ref s) = status_send_channel {
+					let _ = s.send(StatusMessage::UpdateWarning(
+						"Updater Thread unable to contact node".to_owned(),
+					));
+				}
+				Ok(false)
+			}
+			_ => Err(e),
+		},
+	}
+}
+
 fn update_outputs<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
@@ -1006,6 +3947,7 @@ where
 fn update_txs_via_kernel<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
+	parent_key_id: &Identifier,
 	txs: &mut Vec<TxLogEntry>,
 ) -> Result<bool, Error>
 where
@@ -1013,11 +3955,6 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	let parent_key_id = {
-		wallet_lock!(wallet_inst, w);
-		w.parent_key_id().clone()
-	};
-
 	let mut client = {
 		wallet_lock!(wallet_inst, w);
 		w.w2n_client().clone()
@@ -1045,9 +3982,8 @@ where
 				debug!("Kernel Retrieved: {:?}", k);
 				wallet_lock!(wallet_inst, w);
 				let mut batch = w.batch(keychain_mask)?;
-				tx.confirmed = true;
-				tx.update_confirmation_ts();
-				batch.save_tx_log_entry(tx.clone(), &parent_key_id)?;
+				tx.confirm();
+				batch.save_tx_log_entry(tx.clone(), parent_key_id)?;
 				batch.commit()?;
 			}
 		} else {
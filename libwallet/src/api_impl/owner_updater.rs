@@ -19,14 +19,22 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+use chrono::Utc;
+
+use crate::epic_core::core::amount_to_hr_string;
 use crate::epic_keychain::Keychain;
 use crate::epic_util::secp::key::SecretKey;
 use crate::epic_util::Mutex;
 
 use crate::api_impl::owner;
-use crate::types::NodeClient;
-use crate::Error;
+use crate::internal::updater;
+use crate::types::{BalanceAlertHook, MaturityHook, NodeClient, OutputStatus};
+use crate::{
+	wallet_lock, BalanceAlert, BalanceAlertKind, BalanceAlertPolicy, Error, OutputMatured, SendJob,
+	SendJobStatus,
+};
 use crate::{WalletInst, WalletLCProvider};
+use std::collections::HashMap;
 
 const MESSAGE_QUEUE_MAX_LEN: usize = 10_000;
 
@@ -48,6 +56,15 @@ pub enum StatusMessage {
 	ScanningComplete(String),
 	/// Warning of issues that may have occured during an update
 	UpdateWarning(String),
+	/// A queued send changed state
+	SendJobUpdate(SendJob),
+	/// The spendable balance of an account moved outside of its alert threshold
+	BalanceAlert(BalanceAlert),
+	/// A locked output of the wallet became spendable
+	OutputMatured(OutputMatured),
+	/// Opening the wallet was locked out after the given number of consecutive failed
+	/// attempts, for the given number of seconds
+	OpenLockout(u32, u64),
 }
 
 /// Helper function that starts a simple log thread for updater messages
@@ -77,6 +94,38 @@ pub fn start_updater_log_thread(
 					}
 					StatusMessage::ScanningComplete(s) => warn!("{}", s),
 					StatusMessage::UpdateWarning(s) => warn!("{}", s),
+					StatusMessage::SendJobUpdate(j) => match j.status {
+						SendJobStatus::Failed => warn!(
+							"Queued send {} failed: {}",
+							j.id,
+							j.error.unwrap_or_default()
+						),
+						_ => debug!("Queued send {} is {:?}", j.id, j.status),
+					},
+					StatusMessage::BalanceAlert(a) => warn!(
+						"Spendable balance of account '{}' is {}, {} the alert threshold of {}",
+						a.account,
+						amount_to_hr_string(a.spendable, false),
+						match a.kind {
+							BalanceAlertKind::Below => "below",
+							BalanceAlertKind::Above => "above",
+						},
+						amount_to_hr_string(a.threshold, false),
+					),
+					StatusMessage::OutputMatured(o) => warn!(
+						"{} of {} in account '{}' is spendable from height {}",
+						match o.is_coinbase {
+							true => "Coinbase reward",
+							false => "Output",
+						},
+						amount_to_hr_string(o.value, false),
+						o.account,
+						o.lock_height,
+					),
+					StatusMessage::OpenLockout(failures, secs) => warn!(
+						"Opening the wallet failed {} times in a row, locked out for {} seconds",
+						failures, secs
+					),
 				}
 			}
 		})?;
@@ -84,6 +133,200 @@ pub fn start_updater_log_thread(
 	Ok(())
 }
 
+/// Balance thresholds evaluated by the updater, with the hook notified of the
+/// alerts they raise and the side of each threshold its account was last seen on
+pub struct BalanceAlerts {
+	policy: BalanceAlertPolicy,
+	hook: Option<Arc<dyn BalanceAlertHook>>,
+	crossed: Vec<Option<BalanceAlertKind>>,
+}
+
+impl BalanceAlerts {
+	/// Watch the thresholds of the policy. Alerts are sent as status messages of
+	/// the updater, and to the hook if any.
+	pub fn new(policy: BalanceAlertPolicy, hook: Option<Arc<dyn BalanceAlertHook>>) -> Self {
+		let crossed = vec![None; policy.thresholds.len()];
+		BalanceAlerts {
+			policy,
+			hook,
+			crossed,
+		}
+	}
+
+	/// Record the spendable balance of the account of the threshold at `index`,
+	/// returning an alert if the balance just moved outside of the threshold
+	fn update(&mut self, index: usize, spendable: u64, height: u64) -> Option<BalanceAlert> {
+		let threshold = &self.policy.thresholds[index];
+		let crossed = match (threshold.below, threshold.above) {
+			(Some(b), _) if spendable < b => Some((BalanceAlertKind::Below, b)),
+			(_, Some(a)) if spendable > a => Some((BalanceAlertKind::Above, a)),
+			_ => None,
+		};
+		let kind = crossed.map(|(k, _)| k);
+		if kind == self.crossed[index] {
+			return None;
+		}
+		self.crossed[index] = kind;
+		crossed.map(|(kind, value)| BalanceAlert {
+			account: threshold.account.clone(),
+			kind,
+			threshold: value,
+			spendable,
+			height,
+			time: Utc::now(),
+		})
+	}
+
+	/// Evaluate the thresholds against the current state of the wallet, sending
+	/// the alerts raised to the status channel and the hook
+	pub fn check<'a, L, C, K>(
+		&mut self,
+		wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+		keychain_mask: Option<&SecretKey>,
+		status_send_channel: &Option<Sender<StatusMessage>>,
+	) -> Result<(), Error>
+	where
+		L: WalletLCProvider<'a, C, K>,
+		C: NodeClient + 'a,
+		K: Keychain + 'a,
+	{
+		let mut alerts = vec![];
+		{
+			wallet_lock!(wallet_inst, w);
+			let active_parent_key_id = w.parent_key_id();
+			for index in 0..self.policy.thresholds.len() {
+				let account = self.policy.thresholds[index].account.clone();
+				let path = match w.get_acct_path(account.clone())? {
+					Some(m) => m.path,
+					None => {
+						warn!(
+							"Balance alert threshold set on unknown account '{}'",
+							account
+						);
+						continue;
+					}
+				};
+				// the update only refreshed the outputs of the active account
+				if path != active_parent_key_id {
+					updater::refresh_outputs(&mut **w, keychain_mask, &path, false)?;
+				}
+				let info =
+					updater::retrieve_info(&mut **w, &path, self.policy.minimum_confirmations)?;
+				let spendable = info.amount_currently_spendable;
+				if let Some(a) = self.update(index, spendable, info.last_confirmed_height) {
+					alerts.push(a);
+				}
+			}
+		}
+
+		for alert in alerts {
+			if let Some(h) = self.hook.as_ref() {
+				if let Err(e) = h.alert(&alert) {
+					error!(
+						"Unable to deliver balance alert of account '{}': {}",
+						alert.account, e
+					);
+				}
+			}
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::BalanceAlert(alert));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Watches the wallet's locked outputs from the header cache, notifying those that
+/// become spendable as the cached chain grows past their lock height
+pub struct MaturityWatch {
+	hook: Option<Arc<dyn MaturityHook>>,
+	last_height: Option<u64>,
+}
+
+impl MaturityWatch {
+	/// Watch the wallet's outputs. Notifications are sent as status messages of
+	/// the updater, and to the hook if any.
+	pub fn new(hook: Option<Arc<dyn MaturityHook>>) -> Self {
+		MaturityWatch {
+			hook,
+			last_height: None,
+		}
+	}
+
+	/// Record the height of the header cache, returning the range of lock
+	/// heights, exclusive of its start, reached since the last check. Outputs
+	/// spendable before the first check aren't notified.
+	fn update(&mut self, tip: u64) -> Option<(u64, u64)> {
+		let last = self.last_height.replace(tip);
+		match last {
+			Some(l) if l < tip => Some((l, tip)),
+			_ => None,
+		}
+	}
+
+	/// Notify the outputs that became spendable since the last check to the
+	/// status channel and the hook
+	pub fn check<'a, L, C, K>(
+		&mut self,
+		wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+		status_send_channel: &Option<Sender<StatusMessage>>,
+	) -> Result<(), Error>
+	where
+		L: WalletLCProvider<'a, C, K>,
+		C: NodeClient + 'a,
+		K: Keychain + 'a,
+	{
+		let mut matured = vec![];
+		{
+			wallet_lock!(wallet_inst, w);
+			// a reorg lowers the tip, outputs are notified again once back above it
+			let tip = match w.block_headers_iter().map(|h| h.height).max() {
+				Some(h) => h,
+				None => return Ok(()),
+			};
+			let (from, to) = match self.update(tip) {
+				Some(r) => r,
+				None => return Ok(()),
+			};
+			let accounts: HashMap<_, _> = w.acct_path_iter().map(|a| (a.path, a.label)).collect();
+			for o in w.iter() {
+				let locked = o.is_coinbase || o.lock_height > o.height;
+				if o.status != OutputStatus::Unspent || !locked {
+					continue;
+				}
+				if o.lock_height <= from || o.lock_height > to {
+					continue;
+				}
+				matured.push(OutputMatured {
+					account: accounts.get(&o.root_key_id).cloned().unwrap_or_default(),
+					commit: o.commit,
+					value: o.value,
+					is_coinbase: o.is_coinbase,
+					lock_height: o.lock_height,
+					height: tip,
+					time: Utc::now(),
+				});
+			}
+		}
+
+		for output in matured {
+			if let Some(h) = self.hook.as_ref() {
+				if let Err(e) = h.matured(&output) {
+					error!(
+						"Unable to deliver maturity notification of output {}: {}",
+						output.commit.as_ref().map(|c| c.as_str()).unwrap_or("?"),
+						e
+					);
+				}
+			}
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::OutputMatured(output));
+			}
+		}
+		Ok(())
+	}
+}
+
 /// Handles and launches a background update thread
 pub struct Updater<'a, L, C, K>
 where
@@ -93,6 +336,8 @@ where
 {
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	is_running: Arc<AtomicBool>,
+	balance_alerts: Arc<Mutex<Option<BalanceAlerts>>>,
+	maturity_watch: Arc<Mutex<Option<MaturityWatch>>>,
 }
 
 impl<'a, L, C, K> Updater<'a, L, C, K>
@@ -105,11 +350,15 @@ where
 	pub fn new(
 		wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 		is_running: Arc<AtomicBool>,
+		balance_alerts: Arc<Mutex<Option<BalanceAlerts>>>,
+		maturity_watch: Arc<Mutex<Option<MaturityWatch>>>,
 	) -> Self {
 		is_running.store(false, Ordering::Relaxed);
 		Updater {
 			wallet_inst,
 			is_running,
+			balance_alerts,
+			maturity_watch,
 		}
 	}
 
@@ -123,12 +372,37 @@ where
 		self.is_running.store(true, Ordering::Relaxed);
 		loop {
 			// Business goes here
-			owner::update_wallet_state(
+			let updated = owner::update_wallet_state(
 				self.wallet_inst.clone(),
 				(&keychain_mask).as_ref(),
 				status_send_channel,
 				false,
 			)?;
+			// balances and maturity are only checked once confirmed by the node
+			if updated {
+				if let Some(a) = self.balance_alerts.lock().as_mut() {
+					let res = a.check(
+						self.wallet_inst.clone(),
+						(&keychain_mask).as_ref(),
+						status_send_channel,
+					);
+					if let (Err(e), Some(s)) = (res, status_send_channel) {
+						let _ = s.send(StatusMessage::UpdateWarning(format!(
+							"Unable to check balance alerts: {}",
+							e
+						)));
+					}
+				}
+				if let Some(m) = self.maturity_watch.lock().as_mut() {
+					let res = m.check(self.wallet_inst.clone(), status_send_channel);
+					if let (Err(e), Some(s)) = (res, status_send_channel) {
+						let _ = s.send(StatusMessage::UpdateWarning(format!(
+							"Unable to check output maturity: {}",
+							e
+						)));
+					}
+				}
+			}
 			if !self.is_running.load(Ordering::Relaxed) {
 				break;
 			}
@@ -137,3 +411,50 @@ where
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::BalanceThreshold;
+
+	#[test]
+	fn balance_alerts_on_crossing() {
+		let policy = BalanceAlertPolicy {
+			thresholds: vec![BalanceThreshold {
+				account: "payouts".to_owned(),
+				below: Some(100),
+				above: Some(1_000),
+			}],
+			minimum_confirmations: 10,
+		};
+		let mut alerts = BalanceAlerts::new(policy, None);
+		assert!(alerts.update(0, 500, 1).is_none());
+
+		// raised once when crossing, until back within the thresholds
+		let alert = alerts.update(0, 50, 2).unwrap();
+		assert_eq!(alert.kind, BalanceAlertKind::Below);
+		assert_eq!(alert.threshold, 100);
+		assert_eq!(alert.spendable, 50);
+		assert!(alerts.update(0, 20, 3).is_none());
+		assert!(alerts.update(0, 500, 4).is_none());
+		assert!(alerts.update(0, 50, 5).is_some());
+
+		let alert = alerts.update(0, 2_000, 6).unwrap();
+		assert_eq!(alert.kind, BalanceAlertKind::Above);
+		assert_eq!(alert.threshold, 1_000);
+		assert!(alerts.update(0, 3_000, 7).is_none());
+	}
+
+	#[test]
+	fn maturity_watch_ranges() {
+		let mut watch = MaturityWatch::new(None);
+		// outputs already spendable when the watch starts aren't notified
+		assert_eq!(watch.update(100), None);
+		assert_eq!(watch.update(100), None);
+		assert_eq!(watch.update(103), Some((100, 103)));
+
+		// a reorg lowers the tip, the heights reached again are notified again
+		assert_eq!(watch.update(101), None);
+		assert_eq!(watch.update(104), Some((101, 104)));
+	}
+}
@@ -14,14 +14,23 @@
 
 //! Types specific to the wallet api, mostly argument serialization
 
+use crate::blake2::blake2b::blake2b;
 use crate::epic_core::libtx::secp_ser;
-use crate::epic_util::secp::pedersen;
+use crate::epic_keychain::{BlindingFactor, Identifier};
+use crate::epic_util::secp::key::PublicKey;
+use crate::epic_util::secp::{pedersen, Signature};
+use crate::epic_util::to_hex;
+use crate::slate::Slate;
 use crate::slate_versions::ser as dalek_ser;
 use crate::slate_versions::SlateVersion;
-use crate::types::OutputData;
+use crate::types::{
+	InputComposition, OutputData, Swap, SwapCurrency, TxKernelFeatures, TxLogEntry, TxLogEntryType,
+};
 
+use chrono::prelude::*;
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use ed25519_dalek::Signature as DalekSignature;
+use uuid::Uuid;
 
 pub use crate::epic_core::core::block_fees::BlockFees;
 /// Send TX API Args
@@ -88,7 +97,8 @@ pub struct InitTxArgs {
 	pub message: Option<String>,
 	/// Optionally set the output target slate version (acceptable
 	/// down to the minimum slate version compatible with the current. If `None` the slate
-	/// is generated with the latest version.
+	/// is generated with the latest version. Optional fields the target version doesn't
+	/// know are left out, and features it can't carry, such as payment proofs, are refused.
 	pub target_slate_version: Option<u16>,
 	/// Number of blocks from current after which TX should be ignored
 	#[serde(with = "secp_ser::opt_string_or_u64")]
@@ -96,11 +106,52 @@ pub struct InitTxArgs {
 	/// If set, require a payment proof for the particular recipient
 	#[serde(with = "dalek_ser::option_dalek_pubkey_serde")]
 	pub payment_proof_recipient_address: Option<DalekPublicKey>,
+	/// If set, require the recipient to sign its response with the key of this address,
+	/// checked when the transaction is finalized. Otherwise a signed response is checked
+	/// against the payment proof recipient address, if any.
+	#[serde(default, with = "dalek_ser::option_dalek_pubkey_serde")]
+	pub response_address: Option<DalekPublicKey>,
 	/// If true, just return an estimate of the resulting slate, containing fees and amounts
 	/// locked without actually locking outputs or creating the transaction. Note if this is set to
 	/// 'true', the amount field in the slate will contain the total amount locked, not the provided
-	/// transaction amount
+	/// transaction amount. The inputs selected and the change are returned by
+	/// [`estimate_send_tx`](../epic_wallet_api/owner/struct.Owner.html#method.estimate_send_tx),
+	/// as a [`TxEstimate`](struct.TxEstimate.html)
 	pub estimate_only: Option<bool>,
+	/// If set, the maximum weight of the resulting transaction. Input selection is kept
+	/// within the limit where possible, otherwise the transaction is rejected with the number
+	/// of transactions needed to send the amount.
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub max_tx_weight: Option<u64>,
+	/// If set, an extra output of this amount (in nanoepics) is split off the change and
+	/// sent back to ourselves, so the number of outputs gives less away about which one
+	/// is the payment. Skipped if the change doesn't cover it.
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub decoy_output_amount: Option<u64>,
+	/// If set, the commitments of the outputs spent by the transaction, instead of
+	/// selecting them from the wallet. Each must be a spendable output of the source
+	/// account, and together they must cover the amount and the fee.
+	#[serde(default, with = "dalek_ser::option_commitments_serde")]
+	pub inputs: Option<Vec<pedersen::Commitment>>,
+	/// If true, the transaction spends either coinbase or plain outputs but not both,
+	/// so the provenance of mining rewards is kept apart from other funds. The send is
+	/// rejected if neither kind covers the amount on its own, or if the given `inputs`
+	/// mix them.
+	#[serde(default)]
+	pub avoid_mixed_coinbase: Option<bool>,
+	/// If set, the smallest spendable outputs worth less than the amount are spent
+	/// too, besides those covering the amount, while each raises the fee by at most
+	/// this amount (in nanoepics). They're consolidated into the change, keeping the
+	/// number of outputs of the wallet down. Ignored if `inputs` is given.
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub consolidation_fee_threshold: Option<u64>,
+	/// Features of the transaction's kernel, plain if `None`. A height-locked kernel
+	/// keeps the transaction from being confirmed before its lock height, which must
+	/// be above the current height and, if a TTL is set, not beyond its cutoff. The
+	/// node rejects the transaction until the lock height is reached, so it's
+	/// finalized but only posted then.
+	#[serde(default)]
+	pub kernel_features: Option<TxKernelFeatures>,
 	/// Sender arguments. If present, the underlying function will also attempt to send the
 	/// transaction to a destination and optionally finalize the result
 	pub send_args: Option<InitTxSendArgs>,
@@ -136,6 +187,13 @@ impl Default for InitTxArgs {
 			ttl_blocks: None,
 			estimate_only: Some(false),
 			payment_proof_recipient_address: None,
+			response_address: None,
+			max_tx_weight: None,
+			decoy_output_amount: None,
+			inputs: None,
+			avoid_mixed_coinbase: None,
+			consolidation_fee_threshold: None,
+			kernel_features: None,
 			send_args: None,
 		}
 	}
@@ -155,7 +213,8 @@ pub struct IssueInvoiceTxArgs {
 	pub message: Option<String>,
 	/// Optionally set the output target slate version (acceptable
 	/// down to the minimum slate version compatible with the current. If `None` the slate
-	/// is generated with the latest version.
+	/// is generated with the latest version. Optional fields the target version doesn't
+	/// know are left out, and features it can't carry, such as payment proofs, are refused.
 	pub target_slate_version: Option<u16>,
 }
 
@@ -170,6 +229,180 @@ impl Default for IssueInvoiceTxArgs {
 	}
 }
 
+/// Result of a dry run of a send, selecting its inputs and computing its fee without
+/// building the transaction
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TxEstimate {
+	/// Amount sent
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Fee of the transaction
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub fee: u64,
+	/// Outputs the transaction would spend
+	pub inputs: Vec<OutputCommitMapping>,
+	/// Total value of the inputs, locked once the transaction is created
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total: u64,
+	/// Value coming back to the wallet as change
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub change: u64,
+	/// Number of outputs the change is split into
+	pub num_change_outputs: usize,
+	/// Number and value of the coinbase and plain outputs spent
+	#[serde(default)]
+	pub input_composition: InputComposition,
+}
+
+/// Send built by a watch-only wallet, to be signed by the wallet holding its keys,
+/// which needn't be online
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SigningRequest {
+	/// Slate of the send, with its inputs, amount, fee and height but no outputs nor
+	/// participant data
+	pub slate: Slate,
+	/// The outputs spent by the send
+	pub inputs: Vec<OutputData>,
+	/// Number of outputs the change is split into
+	pub num_change_outputs: u32,
+	/// Next child index of the account on the watch-only wallet, the change outputs are
+	/// derived from it or later ones
+	pub next_child_index: u32,
+	/// Message of the sender
+	pub message: Option<String>,
+}
+
+/// Terms the seller of epics offers an atomic swap on
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapStartArgs {
+	/// The human readable account name the epics are sent from, the active account if not set
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// Amount of epics sold, in nanoepics
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Currency the epics are sold for
+	pub secondary_currency: SwapCurrency,
+	/// Amount of the secondary currency asked, in its smallest unit
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub secondary_amount: u64,
+	/// The minimum number of confirmations of the outputs the epics are sent from
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub minimum_confirmations: u64,
+	/// Number of blocks after which the seller can take the epics back, if the swap
+	/// didn't complete
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub refund_blocks: u64,
+	/// Hours after which the buyer can take the secondary currency back. Must be at least
+	/// twice as long as the epic refund takes, for the seller to redeem the secondary
+	/// currency once the buyer redeemed the epics.
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub secondary_lock_hours: u64,
+}
+
+impl Default for SwapStartArgs {
+	fn default() -> SwapStartArgs {
+		SwapStartArgs {
+			src_acct_name: None,
+			amount: 0,
+			secondary_currency: SwapCurrency::Btc,
+			secondary_amount: 0,
+			minimum_confirmations: 10,
+			refund_blocks: 720,
+			secondary_lock_hours: 24,
+		}
+	}
+}
+
+/// Terms of an atomic swap offered by the seller, with the seller's first round of the
+/// lock and refund transactions
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapOffer {
+	/// Amount of epics sold, in nanoepics
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Currency the epics are sold for
+	pub secondary_currency: SwapCurrency,
+	/// Amount of the secondary currency asked, in its smallest unit
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub secondary_amount: u64,
+	/// Height from which the seller can take the locked epics back
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub refund_height: u64,
+	/// Time from which the buyer can take the secondary currency back, in seconds since
+	/// the epoch
+	pub secondary_lock_time: i64,
+	/// Seller's key of the redeem branch of the secondary lock script
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub redeem_public: PublicKey,
+	/// Lock transaction, creating the output shared by both parties
+	pub lock_slate: Slate,
+	/// The seller's refund transaction of the shared output
+	pub refund_slate: Slate,
+}
+
+/// Step of an atomic swap sent to the other party
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum SwapMessageBody {
+	/// The seller offers the swap
+	Offer(SwapOffer),
+	/// The buyer joins the lock transaction and signs the seller's refund
+	Accept {
+		/// Lock transaction, joined by the buyer
+		lock_slate: Slate,
+		/// The seller's refund transaction, signed by the buyer
+		refund_slate: Slate,
+		/// Buyer's key of the refund branch of the secondary lock script
+		#[serde(with = "secp_ser::pubkey_serde")]
+		refund_public: PublicKey,
+		/// Public key of the secret the redeem transaction reveals
+		#[serde(with = "secp_ser::pubkey_serde")]
+		secret_public: PublicKey,
+	},
+	/// The seller signs the lock transaction, for the buyer to complete and post
+	LockSigned {
+		/// Lock transaction, signed by the seller
+		lock_slate: Slate,
+	},
+	/// The seller starts the redeem transaction of the locked epics, once they're confirmed
+	Redeem {
+		/// Redeem transaction, spending the shared output to the buyer
+		redeem_slate: Slate,
+	},
+	/// The buyer adds its output to the redeem transaction, and its partial signature plus
+	/// the secret
+	RedeemAdaptor {
+		/// Redeem transaction, with the buyer's output
+		redeem_slate: Slate,
+		/// Buyer's partial signature of the redeem transaction, plus the secret
+		#[serde(with = "secp_ser::sig_serde")]
+		adaptor: Signature,
+	},
+	/// The seller signs the redeem transaction, once the secondary currency is locked
+	RedeemSigned {
+		/// Redeem transaction, signed by the seller
+		redeem_slate: Slate,
+	},
+}
+
+/// Message exchanged by the parties of an atomic swap
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapMessage {
+	/// Id of the swap
+	pub id: Uuid,
+	/// The step
+	pub body: SwapMessageBody,
+}
+
+/// Atomic swap after one of its steps
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SwapStep {
+	/// The swap, as stored
+	pub swap: Swap,
+	/// Message to send to the other party, if the step has one
+	pub message: Option<SwapMessage>,
+}
+
 /// Map Outputdata to commits
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OutputCommitMapping {
@@ -181,6 +414,154 @@ pub struct OutputCommitMapping {
 		deserialize_with = "secp_ser::commitment_from_hex"
 	)]
 	pub commit: pedersen::Commitment,
+	/// Opaque id of the output, which doesn't change with its status, confirmation or the
+	/// records kept of it. The history records of an output have the output's id.
+	pub output_id: String,
+}
+
+impl OutputCommitMapping {
+	/// Map an output to its commitment
+	pub fn new(output: OutputData, commit: pedersen::Commitment) -> OutputCommitMapping {
+		let mut bytes = commit.0.to_vec();
+		bytes.extend_from_slice(&output.key_id.to_bytes());
+		let output_id = to_hex(blake2b(16, &[], &bytes).as_bytes().to_vec());
+		OutputCommitMapping {
+			output,
+			commit,
+			output_id,
+		}
+	}
+}
+
+/// Field a page of outputs is sorted by
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum OutputSortField {
+	/// Value of the output
+	Value,
+	/// Height of the output
+	Height,
+	/// Status of the output, in the order unconfirmed, unspent, locked, spent
+	Status,
+}
+
+/// Which outputs are returned by
+/// [`retrieve_outputs_page`](../epic_wallet_api/owner/struct.Owner.html#method.retrieve_outputs_page),
+/// and in which order
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct OutputPageQuery {
+	/// Number of outputs skipped, once sorted
+	#[serde(default)]
+	pub offset: usize,
+	/// Maximum number of outputs returned, or `None` for all the outputs after `offset`
+	#[serde(default)]
+	pub limit: Option<usize>,
+	/// Field the outputs are sorted by, or `None` to keep the wallet's order, by
+	/// derivation. Outputs with the same value of the field keep the wallet's order.
+	#[serde(default)]
+	pub sort_by: Option<OutputSortField>,
+	/// Whether the outputs are sorted in descending order
+	#[serde(default)]
+	pub descending: bool,
+	/// Snapshot returned with an earlier page, to page through the outputs as they were
+	/// then, or `None` to take a new snapshot of the outputs
+	#[serde(default)]
+	pub snapshot: Option<Uuid>,
+}
+
+impl OutputPageQuery {
+	/// Sort the outputs of the snapshot and cut the page out of them
+	pub fn apply(&self, mut outputs: Vec<OutputCommitMapping>, snapshot: Uuid) -> OutputPage {
+		let total = outputs.len();
+		match self.sort_by {
+			Some(field) => outputs.sort_by(|a, b| {
+				let (a, b) = (&a.output, &b.output);
+				let ord = match field {
+					OutputSortField::Value => a.value.cmp(&b.value),
+					OutputSortField::Height => a.height.cmp(&b.height),
+					OutputSortField::Status => a.status.cmp(&b.status),
+				};
+				match self.descending {
+					true => ord.reverse(),
+					false => ord,
+				}
+			}),
+			None if self.descending => outputs.reverse(),
+			None => (),
+		}
+		let outputs = outputs
+			.into_iter()
+			.skip(self.offset)
+			.take(self.limit.unwrap_or(usize::MAX))
+			.collect();
+		OutputPage {
+			total,
+			outputs,
+			snapshot,
+		}
+	}
+}
+
+/// Page of the outputs of an account
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutputPage {
+	/// Number of outputs matched, in all pages
+	pub total: usize,
+	/// Outputs of the page
+	pub outputs: Vec<OutputCommitMapping>,
+	/// Snapshot the page was cut from, to give when querying the next pages
+	pub snapshot: Uuid,
+}
+
+/// Which transactions are returned by
+/// [`retrieve_txs_page`](../epic_wallet_api/owner/struct.Owner.html#method.retrieve_txs_page)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TxPageQuery {
+	/// Number of transactions skipped
+	#[serde(default)]
+	pub offset: usize,
+	/// Maximum number of transactions returned, or `None` for all the transactions
+	/// after `offset`
+	#[serde(default)]
+	pub limit: Option<usize>,
+	/// Whether the transactions are returned newest first, rather than in the order
+	/// they were logged in
+	#[serde(default)]
+	pub descending: bool,
+	/// Snapshot returned with an earlier page, to page through the transactions as
+	/// they were then, or `None` to take a new snapshot of the transactions
+	#[serde(default)]
+	pub snapshot: Option<Uuid>,
+}
+
+impl TxPageQuery {
+	/// Cut the page out of the transactions of the snapshot
+	pub fn apply(&self, mut txs: Vec<TxLogEntry>, snapshot: Uuid) -> TxPage {
+		let total = txs.len();
+		if self.descending {
+			txs.reverse();
+		}
+		let txs = txs
+			.into_iter()
+			.skip(self.offset)
+			.take(self.limit.unwrap_or(usize::MAX))
+			.collect();
+		TxPage {
+			total,
+			txs,
+			snapshot,
+		}
+	}
+}
+
+/// Page of the transactions of an account
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TxPage {
+	/// Number of transactions, in all pages
+	pub total: usize,
+	/// Transactions of the page
+	pub txs: Vec<TxLogEntry>,
+	/// Snapshot the page was cut from, to give when querying the next pages
+	pub snapshot: Uuid,
 }
 
 /// Node height result
@@ -204,6 +585,230 @@ pub struct VersionInfo {
 	pub supported_slate_versions: Vec<SlateVersion>,
 }
 
+/// Support status of an owner API version or method
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ApiStatus {
+	/// Supported
+	Supported,
+	/// Still served, but will be removed in a future release
+	Deprecated,
+	/// No longer served
+	Removed,
+}
+
+/// Owner API method which is deprecated or has been removed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MethodDeprecation {
+	/// Method name
+	pub method: String,
+	/// Whether the method is deprecated or removed
+	pub status: ApiStatus,
+	/// Method to call instead, if any
+	pub replacement: Option<String>,
+}
+
+/// Owner API version served by the wallet
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OwnerApiVersion {
+	/// API version
+	pub version: u16,
+	/// Endpoint the version is served on
+	pub endpoint: String,
+	/// Support status of the version as a whole
+	pub status: ApiStatus,
+	/// Deprecated and removed methods of the version. Methods not listed are supported
+	pub deprecated_methods: Vec<MethodDeprecation>,
+}
+
+/// API versions request result
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiVersions {
+	/// Version of the wallet build serving the APIs
+	pub build_version: String,
+	/// Owner API versions
+	pub owner_api_versions: Vec<OwnerApiVersion>,
+	/// Foreign API version
+	pub foreign_api_version: u16,
+}
+
+/// Request for a payment of a given amount, signed with the payment proof address of the
+/// requesting wallet so the amount and memo can't be altered on the way to the sender
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaymentRequest {
+	/// Payment proof address (Onion V3) of the requesting wallet, also its Tor address
+	pub address: String,
+	/// Requested amount
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Memo describing the payment, used as the sender's message
+	pub memo: Option<String>,
+	/// Time after which the request is no longer valid
+	pub expiry: Option<DateTime<Utc>>,
+	/// Signature over all of the above by the requesting wallet
+	#[serde(with = "dalek_ser::dalek_sig_serde")]
+	pub signature: DalekSignature,
+}
+
+/// A counterparty's claim about an output: the value it holds and the blinding factor
+/// it was committed to with, i.e. the opening of the commitment
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutputClaim {
+	/// Commitment of the output
+	#[serde(
+		serialize_with = "secp_ser::as_hex",
+		deserialize_with = "secp_ser::commitment_from_hex"
+	)]
+	pub commit: pedersen::Commitment,
+	/// Claimed value of the output
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub value: u64,
+	/// Claimed blinding factor of the output
+	#[serde(
+		serialize_with = "secp_ser::as_hex",
+		deserialize_with = "secp_ser::blind_from_hex"
+	)]
+	pub blinding_factor: BlindingFactor,
+}
+
+/// Outcome of checking an [`OutputClaim`] against the chain
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputClaimVerification {
+	/// Whether the output is unspent on the node's chain
+	pub on_chain: bool,
+	/// Height of the block the output was included in, if on chain
+	#[serde(with = "secp_ser::opt_string_or_u64")]
+	pub height: Option<u64>,
+	/// Whether the output is a coinbase output, if on chain
+	pub is_coinbase: Option<bool>,
+	/// Whether the on-chain rangeproof of the output is valid
+	pub range_proof_valid: bool,
+	/// Whether the claimed value and blinding factor open the commitment
+	pub opening_valid: bool,
+}
+
+impl OutputClaimVerification {
+	/// Whether the claim holds, i.e. an unspent output with a valid rangeproof
+	/// is opened by the claimed value and blinding factor
+	pub fn is_valid(&self) -> bool {
+		self.on_chain && self.range_proof_valid && self.opening_valid
+	}
+}
+
+/// Wallet transactions an output went through, as recorded by the wallet
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OutputLineage {
+	/// Latest state of the output
+	pub output: OutputCommitMapping,
+	/// Transaction that created the output: a receive, a coinbase, or the send the
+	/// output is the change of
+	pub created_by: Option<TxLogEntry>,
+	/// Transaction that spent the output, or is spending it while it's locked
+	pub spent_by: Option<TxLogEntry>,
+	/// Cancelled transactions that tried to spend the output
+	pub cancelled_spends: Vec<TxLogEntry>,
+	/// Outputs spent by `created_by`, which the output is the change of
+	pub parents: Vec<OutputCommitMapping>,
+	/// Outputs created by `spent_by`, i.e. the change of the spend
+	pub children: Vec<OutputCommitMapping>,
+}
+
+/// State of a send queued with `queue_send`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SendJobStatus {
+	/// Waiting for a worker
+	Queued,
+	/// Being sent by a worker
+	Running,
+	/// Sent, and finalized and posted if requested
+	Completed,
+	/// Given up on, see the job's error
+	Failed,
+}
+
+/// Priority of a queued send. Higher priority sends are started first, sends of the
+/// same priority in the order queued
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SendPriority {
+	/// Started once no other sends are waiting
+	Low,
+	/// Default priority
+	Normal,
+	/// Started before any other waiting sends
+	High,
+}
+
+impl Default for SendPriority {
+	fn default() -> SendPriority {
+		SendPriority::Normal
+	}
+}
+
+/// A send executed in the background by a send queue worker
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SendJob {
+	/// Id of the job, returned when queueing
+	pub id: Uuid,
+	/// Current state of the job
+	pub status: SendJobStatus,
+	/// Method of the send, as in the send arguments
+	pub method: String,
+	/// Destination of the send
+	pub dest: String,
+	/// Amount being sent
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Priority of the job in the queue
+	pub priority: SendPriority,
+	/// Number of exchanges with the recipient attempted so far
+	pub attempts: u32,
+	/// Id of the slate, once created
+	pub slate_id: Option<Uuid>,
+	/// Error of the last failed attempt
+	pub error: Option<String>,
+	/// Time the job was queued
+	pub created_ts: DateTime<Utc>,
+	/// Time the job last changed
+	pub updated_ts: DateTime<Utc>,
+}
+
+/// State of a scan started with `start_scan`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ScanJobStatus {
+	/// Checking the chain's outputs, or repairing the wallet with those found
+	Running,
+	/// Scanned up to the chain tip as of the start of the scan
+	Completed,
+	/// Stopped by an error, see the job's error
+	Failed,
+	/// Stopped by `cancel_job` before it completed
+	Cancelled,
+}
+
+/// A scan of the chain running in the background
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScanJob {
+	/// Id of the job, returned when starting the scan
+	pub id: Uuid,
+	/// Current state of the job
+	pub status: ScanJobStatus,
+	/// Height of the first block scanned
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub start_height: u64,
+	/// Whether the scan deletes unconfirmed outputs and their transactions
+	pub delete_unconfirmed: bool,
+	/// Percentage of the scan complete
+	pub percent_complete: u8,
+	/// Height of the last output checked, once any were
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub current_height: Option<u64>,
+	/// Error the scan failed with
+	pub error: Option<String>,
+	/// Time the job was started
+	pub created_ts: DateTime<Utc>,
+	/// Time the job last changed
+	pub updated_ts: DateTime<Utc>,
+}
+
 /// Packaged Payment Proof
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PaymentProof {
@@ -227,3 +832,479 @@ pub struct PaymentProof {
 	#[serde(with = "dalek_ser::dalek_sig_serde")]
 	pub sender_sig: DalekSignature,
 }
+
+/// Payment proof of a transaction of the wallet, as listed by
+/// [`retrieve_payment_proofs`](../epic_wallet_api/owner/struct.Owner.html#method.retrieve_payment_proofs)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaymentProofEntry {
+	/// Id of the transaction in the wallet's log
+	pub tx_id: u32,
+	/// Slate id of the transaction
+	pub tx_slate_id: Option<Uuid>,
+	/// The proof, as exported with `retrieve_payment_proof`
+	pub proof: PaymentProof,
+}
+
+/// Filter used to select transactions for bulk operations such as
+/// [`cancel_txs`](../epic_wallet_api/owner/struct.Owner.html#method.cancel_txs) and
+/// [`repost_txs`](../epic_wallet_api/owner/struct.Owner.html#method.repost_txs).
+/// Only unconfirmed transactions are ever matched.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TxFilter {
+	/// If set, only match transactions of the given type
+	pub tx_type: Option<TxLogEntryType>,
+	/// If set, only match transactions created at least this many hours ago
+	pub min_age_hours: Option<u64>,
+}
+
+impl TxFilter {
+	/// Whether the given transaction matches this filter, relative to `now`
+	pub fn matches(&self, tx: &TxLogEntry, now: DateTime<Utc>) -> bool {
+		if tx.confirmed {
+			return false;
+		}
+		if let Some(t) = self.tx_type.as_ref() {
+			if &tx.tx_type != t {
+				return false;
+			}
+		}
+		if let Some(h) = self.min_age_hours {
+			// compared in whole hours, as a duration of any number of hours can't be made
+			let age = now.signed_duration_since(tx.creation_ts).num_hours();
+			if age < 0 || (age as u64) < h {
+				return false;
+			}
+		}
+		true
+	}
+}
+
+/// Tag recorded on both sides of the internal transfers made by
+/// [`sweep_hot_account`](../epic_wallet_api/owner/struct.Owner.html#method.sweep_hot_account)
+pub const SWEEP_TX_TAG: &str = "sweep";
+
+/// Minimum number of confirmations of the outputs swept, unless configured otherwise
+pub const SWEEP_MINIMUM_CONFIRMATIONS: u64 = 10;
+
+/// Policy for moving the spendable balance of a hot account above a threshold
+/// to a cold account in the same wallet
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SweepPolicy {
+	/// Account whose spendable balance is kept at or below `max_hot_balance`
+	pub hot_account: String,
+	/// Account the excess balance is transferred to
+	pub cold_account: String,
+	/// Spendable balance to leave in the hot account, in nanoepics. Transaction
+	/// fees are paid from this remainder, or from the swept amount if the remainder
+	/// is too low to cover them.
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub max_hot_balance: u64,
+	/// The minimum number of confirmations an output should have to be swept
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub minimum_confirmations: u64,
+}
+
+/// Spendable balance range of an account, outside of which the updater raises
+/// a [`BalanceAlert`](struct.BalanceAlert.html)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BalanceThreshold {
+	/// Label of the watched account
+	pub account: String,
+	/// Alert when the spendable balance drops below this amount, in nanoepics
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub below: Option<u64>,
+	/// Alert when the spendable balance rises above this amount, in nanoepics
+	#[serde(default, with = "secp_ser::opt_string_or_u64")]
+	pub above: Option<u64>,
+}
+
+/// Balance thresholds evaluated by the updater thread after each update
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BalanceAlertPolicy {
+	/// Watched accounts and their thresholds
+	pub thresholds: Vec<BalanceThreshold>,
+	/// The minimum number of confirmations of outputs counted as spendable
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub minimum_confirmations: u64,
+}
+
+/// Side of a threshold the spendable balance of an account crossed to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BalanceAlertKind {
+	/// The balance dropped below the `below` threshold
+	Below,
+	/// The balance rose above the `above` threshold
+	Above,
+}
+
+/// Raised once when the spendable balance of an account moves outside of its
+/// [`BalanceThreshold`](struct.BalanceThreshold.html), and again only after it
+/// has been back within it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BalanceAlert {
+	/// Label of the account
+	pub account: String,
+	/// Which threshold was crossed
+	pub kind: BalanceAlertKind,
+	/// Value of the crossed threshold
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub threshold: u64,
+	/// Spendable balance of the account
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub spendable: u64,
+	/// Height the balance was computed at
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub height: u64,
+	/// When the alert was raised
+	pub time: DateTime<Utc>,
+}
+
+/// Raised by the updater once a coinbase or height-locked output of the wallet
+/// becomes spendable, the header cache having reached its lock height
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutputMatured {
+	/// Label of the account the output belongs to
+	pub account: String,
+	/// Commitment of the output, if known
+	pub commit: Option<String>,
+	/// Value of the output
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub value: u64,
+	/// Whether the output is a coinbase reward
+	pub is_coinbase: bool,
+	/// Height the output was locked until
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub lock_height: u64,
+	/// Height of the header cache when the output was seen maturing
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub height: u64,
+	/// When the notification was raised
+	pub time: DateTime<Utc>,
+}
+
+/// Outputs belonging to the wallet's seed found in a range of blocks by
+/// [`scan_preview`](../epic_wallet_api/owner/struct.Owner.html#method.scan_preview),
+/// without any change made to the wallet
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScanPreview {
+	/// Height of the first block of the range
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub start_height: u64,
+	/// Height of the last block of the range
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub end_height: u64,
+	/// Number of unspent outputs in the range belonging to the wallet's seed
+	pub num_outputs: usize,
+	/// Total value of these outputs
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total_value: u64,
+	/// Number of these outputs the wallet doesn't know of yet, which a scan would restore
+	pub num_missing: usize,
+	/// Total value of the outputs the wallet doesn't know of yet
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub missing_value: u64,
+}
+
+/// An output exported by another wallet, e.g. an output record of `db export`, with the
+/// key derivation needed to restore it without scanning the chain
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImportedOutput {
+	/// Identifier of the key the output's blinding factor is derived from
+	pub key_id: Identifier,
+	/// Value of the output
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub value: u64,
+	/// Commitment of the output, checked against the one derived from the key if given
+	#[serde(default, with = "secp_ser::option_commitment_serde")]
+	pub commit: Option<pedersen::Commitment>,
+	/// Whether the output is a coinbase output
+	#[serde(default)]
+	pub is_coinbase: bool,
+}
+
+/// Outcome of importing a list of [`ImportedOutput`]s
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OutputImportSummary {
+	/// Number of outputs restored to the wallet
+	pub num_imported: usize,
+	/// Total value of the restored outputs
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub imported_value: u64,
+	/// Number of outputs the wallet already knew of
+	pub num_known: usize,
+	/// Number of outputs not unspent on the node's chain
+	pub num_not_found: usize,
+	/// Number of outputs whose commitment doesn't derive from the wallet's seed
+	pub num_mismatched: usize,
+}
+
+/// Output recorded more than once in the wallet with the same commitment, e.g.
+/// under two accounts after a restore, and which of its records was kept
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeduplicatedOutput {
+	/// Commitment of the output
+	pub commit: String,
+	/// Record kept
+	pub kept: OutputData,
+	/// Records removed, so the output isn't counted more than once
+	pub removed: Vec<OutputData>,
+}
+
+/// Outcome of a batch send for a single recipient
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InitTxBatchResult {
+	/// Amount sent to the recipient
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Slate of the transaction to the recipient, if it was initiated
+	pub slate: Option<Slate>,
+	/// Error message if it wasn't
+	pub error: Option<String>,
+}
+
+impl InitTxBatchResult {
+	/// Build a result entry from the amount sent and the outcome of initiating the send
+	pub fn new<E: std::fmt::Display>(amount: u64, res: Result<Slate, E>) -> InitTxBatchResult {
+		match res {
+			Ok(slate) => InitTxBatchResult {
+				amount,
+				slate: Some(slate),
+				error: None,
+			},
+			Err(e) => InitTxBatchResult {
+				amount,
+				slate: None,
+				error: Some(format!("{}", e)),
+			},
+		}
+	}
+}
+
+/// A word of the wallet's recovery phrase, given back to confirm the phrase was backed up
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeedWord {
+	/// Position of the word in the recovery phrase, starting from 1
+	pub position: usize,
+	/// The word at that position
+	pub word: String,
+}
+
+/// Outcome of a bulk operation for a single transaction
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TxBulkResult {
+	/// Id of the transaction log entry
+	pub tx_id: u32,
+	/// Slate id of the transaction, if any
+	pub tx_slate_id: Option<Uuid>,
+	/// Whether the operation succeeded for this transaction
+	pub success: bool,
+	/// Error message if the operation failed
+	pub error: Option<String>,
+}
+
+impl TxBulkResult {
+	/// Build a result entry from a transaction and the outcome of the operation
+	pub fn new<E: std::fmt::Display>(tx: &TxLogEntry, res: Result<(), E>) -> TxBulkResult {
+		TxBulkResult {
+			tx_id: tx.id,
+			tx_slate_id: tx.tx_slate_id.clone(),
+			success: res.is_ok(),
+			error: res.err().map(|e| format!("{}", e)),
+		}
+	}
+}
+
+/// Confirmed transaction of an account statement
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StatementLine {
+	/// Id of the transaction in the account's transaction log
+	pub tx_id: u32,
+	/// Slate id of the transaction, if any
+	pub tx_slate_id: Option<Uuid>,
+	/// Type of the transaction
+	pub tx_type: TxLogEntryType,
+	/// When the transaction was confirmed
+	pub time: DateTime<Utc>,
+	/// Amount credited to the account, including the change of a send
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub credited: u64,
+	/// Amount debited from the account, including the fee of a send
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub debited: u64,
+	/// Fee paid, for a send
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub fee: u64,
+	/// Balance of the account after the transaction
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub balance: u64,
+}
+
+/// Value of the balances of a statement in a fiat currency, at the price of EPIC
+/// when the statement was made
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StatementFiat {
+	/// ISO 4217 code of the currency, e.g. `USD`
+	pub currency: String,
+	/// Price of one EPIC in the currency
+	pub price: f64,
+	/// Value of the opening balance
+	pub opening_balance: f64,
+	/// Value of the closing balance
+	pub closing_balance: f64,
+}
+
+/// Statement of the transactions an account confirmed over a period, returned by
+/// [`account_statements`](../epic_wallet_api/owner/struct.Owner.html#method.account_statements).
+/// Its lines are flat, so it converts to CSV one line per row.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AccountStatement {
+	/// Label of the account
+	pub account: String,
+	/// Start of the period
+	pub start: DateTime<Utc>,
+	/// End of the period, exclusive
+	pub end: DateTime<Utc>,
+	/// Balance of the confirmed transactions before the period
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub opening_balance: u64,
+	/// Balance of the confirmed transactions at the end of the period
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub closing_balance: u64,
+	/// Total credited over the period
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total_credited: u64,
+	/// Total debited over the period
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total_debited: u64,
+	/// Fees paid over the period
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total_fees: u64,
+	/// Transactions confirmed over the period, oldest first
+	pub lines: Vec<StatementLine>,
+	/// Value of the balances in a fiat currency, if a price was available
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub fiat: Option<StatementFiat>,
+}
+
+/// Length of the periods fees are summarized over by a
+/// [`FeeReport`](struct.FeeReport.html)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FeePeriod {
+	/// Calendar days
+	Day,
+	/// Weeks starting on Monday
+	Week,
+	/// Calendar months
+	Month,
+}
+
+impl FeePeriod {
+	/// Start of the period, in UTC, the given time falls in
+	pub fn start(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+		let date = time.date_naive();
+		let start = match self {
+			FeePeriod::Day => date,
+			FeePeriod::Week => {
+				date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+			}
+			FeePeriod::Month => date.with_day(1).unwrap_or(date),
+		};
+		Utc.from_utc_datetime(&start.and_hms_opt(0, 0, 0).unwrap_or_default())
+	}
+}
+
+/// Fees paid by a set of transactions
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct FeeSummary {
+	/// Number of transactions
+	pub num_txs: u32,
+	/// Sum of their fees
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub total_fees: u64,
+	/// Average fee per transaction, rounded down
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub average_fee: u64,
+	/// Largest fee of a single transaction
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub largest_fee: u64,
+}
+
+impl FeeSummary {
+	/// Count a transaction paying the given fee
+	pub fn add(&mut self, fee: u64) {
+		self.num_txs += 1;
+		self.total_fees += fee;
+		self.average_fee = self.total_fees / self.num_txs as u64;
+		self.largest_fee = std::cmp::max(self.largest_fee, fee);
+	}
+}
+
+/// Fees paid within a period
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeePeriodSummary {
+	/// Start of the period
+	pub start: DateTime<Utc>,
+	/// Fees paid by the transactions confirmed within it
+	pub fees: FeeSummary,
+}
+
+/// Fees paid from an account, in total and per period
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AccountFeeReport {
+	/// Label of the account
+	pub account: String,
+	/// Fees paid by all the transactions sent from the account
+	pub fees: FeeSummary,
+	/// Fees paid in each period a transaction was confirmed in, oldest first
+	pub periods: Vec<FeePeriodSummary>,
+}
+
+/// Fees paid by the confirmed transactions sent from the wallet, returned by
+/// [`fee_report`](../epic_wallet_api/owner/struct.Owner.html#method.fee_report)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FeeReport {
+	/// Length of the periods fees are summarized over
+	pub period: FeePeriod,
+	/// Fees paid from all accounts
+	pub fees: FeeSummary,
+	/// Fees paid from each account that sent a transaction, by label
+	pub accounts: Vec<AccountFeeReport>,
+	/// The transactions that paid the largest fees, largest first
+	pub largest: Vec<TxLogEntry>,
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn tx_filter_matches() {
+		let now = Utc::now();
+		let mut tx = TxLogEntry::new(Identifier::zero(), TxLogEntryType::TxSent, 0);
+		tx.creation_ts = now - chrono::Duration::hours(5);
+
+		assert!(TxFilter::default().matches(&tx, now));
+		let filter = TxFilter {
+			tx_type: Some(TxLogEntryType::TxReceived),
+			min_age_hours: None,
+		};
+		assert!(!filter.matches(&tx, now));
+
+		let mut filter = TxFilter {
+			tx_type: Some(TxLogEntryType::TxSent),
+			min_age_hours: Some(5),
+		};
+		assert!(filter.matches(&tx, now));
+		filter.min_age_hours = Some(6);
+		assert!(!filter.matches(&tx, now));
+		// ages no duration can hold match nothing, instead of overflowing
+		filter.min_age_hours = Some(u64::MAX);
+		assert!(!filter.matches(&tx, now));
+		filter.min_age_hours = Some(i64::MAX as u64 / 3600 + 1);
+		assert!(!filter.matches(&tx, now));
+
+		filter.min_age_hours = None;
+		tx.confirmed = true;
+		assert!(!filter.matches(&tx, now));
+	}
+}
@@ -0,0 +1,94 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chain parameters overridden through the wallet configuration, for the user-defined
+//! chains of test harnesses and private networks whose nodes don't run with the
+//! parameters of the chain type
+
+use crate::config::ChainParamsConfig;
+use crate::epic_core::core::hash::Hashed;
+use crate::epic_core::genesis;
+use crate::epic_core::global::{self, ChainTypes};
+use crate::epic_util::{to_hex, RwLock};
+use crate::error::{Error, ErrorKind};
+use crate::types::NodeClient;
+
+lazy_static! {
+	static ref COINBASE_MATURITY: RwLock<Option<u64>> = RwLock::new(None);
+}
+
+/// Apply the chain parameters configured for the wallet's chain, only accepted on the
+/// user testing and automated testing chains
+pub fn set_chain_params(
+	chain_type: &ChainTypes,
+	params: Option<&ChainParamsConfig>,
+) -> Result<(), Error> {
+	let maturity = params.and_then(|p| p.coinbase_maturity);
+	if let Some(m) = maturity {
+		match chain_type {
+			ChainTypes::UserTesting | ChainTypes::AutomatedTesting => (),
+			_ => {
+				return Err(ErrorKind::ChainParams(format!(
+					"coinbase_maturity can't be overridden on {:?}",
+					chain_type
+				))
+				.into());
+			}
+		}
+		if m == 0 {
+			let msg = "coinbase_maturity must be at least 1".to_owned();
+			return Err(ErrorKind::ChainParams(msg).into());
+		}
+	}
+	*COINBASE_MATURITY.write() = maturity;
+	Ok(())
+}
+
+/// Number of blocks before a coinbase output can be spent, as overridden for the chain
+/// or as given by its chain type
+pub fn coinbase_maturity() -> u64 {
+	match *COINBASE_MATURITY.read() {
+		Some(m) => m,
+		None => global::coinbase_maturity(),
+	}
+}
+
+/// Check the node isn't on mainnet or floonet when chain parameters are overridden, by
+/// the hash of its genesis block. Nothing is checked if the node can't be reached.
+pub fn check_node_chain<C>(client: &C) -> Result<(), Error>
+where
+	C: NodeClient,
+{
+	if COINBASE_MATURITY.read().is_none() {
+		return Ok(());
+	}
+	let genesis = match client.get_header_info(0) {
+		Ok(Some(h)) => h,
+		_ => return Ok(()),
+	};
+	let known = vec![
+		(ChainTypes::Mainnet, genesis::genesis_main()),
+		(ChainTypes::Floonet, genesis::genesis_floo()),
+	];
+	for (chain_type, block) in known {
+		if to_hex(block.hash().to_vec()) == genesis.hash {
+			return Err(ErrorKind::ChainParams(format!(
+				"the node is on {:?}, whose parameters can't be overridden",
+				chain_type
+			))
+			.into());
+		}
+	}
+	Ok(())
+}
@@ -49,10 +49,82 @@ pub enum ErrorKind {
 		needed_disp: String,
 	},
 
+	/// Transaction exceeds the configured maximum weight
+	#[fail(
+		display = "Transaction weight {} exceeds the maximum of {}, sending this amount needs {} transactions",
+		weight, max_weight, needed_txs
+	)]
+	TxTooHeavy {
+		/// Weight of the transaction
+		weight: u64,
+		/// Maximum allowed weight
+		max_weight: u64,
+		/// Number of transactions needed to stay within the maximum weight
+		needed_txs: usize,
+	},
+
+	/// An output given as an input of a transaction can't be spent by it
+	#[fail(display = "Output {} can't be spent: {}", _0, _1)]
+	UnspendableInput(String, String),
+
+	/// A send would spend both coinbase and plain outputs, which it was asked to avoid
+	#[fail(display = "Coinbase and plain outputs can't be kept apart: {}", _0)]
+	MixedCoinbaseInputs(String),
+
+	/// The kernel features chosen for a send can't be used
+	#[fail(display = "Kernel features error: {}", _0)]
+	KernelFeatures(String),
+
+	/// No output of the wallet has the given commitment
+	#[fail(display = "Output {} doesn't belong to the wallet", _0)]
+	UnknownOutput(String),
+
+	/// A new wallet password doesn't meet the configured policy
+	#[fail(display = "Password doesn't meet the wallet's policy: {}", _0)]
+	WeakPassword(String),
+
+	/// Opening the wallet is locked out after too many failed attempts
+	#[fail(
+		display = "Too many failed attempts at opening the wallet, try again in {} seconds",
+		_0
+	)]
+	OpenLockedOut(u64),
+
+	/// Chain parameters overridden in the configuration can't be used
+	#[fail(display = "Chain parameters error: {}", _0)]
+	ChainParams(String),
+
+	/// A scan was stopped before it completed
+	#[fail(display = "Scan cancelled")]
+	ScanCancelled,
+
 	/// Fee error
 	#[fail(display = "Fee Error: {}", _0)]
 	Fee(String),
 
+	/// The node is too far behind the wallet, or still syncing, to post transactions to
+	#[fail(display = "Node not ready to post transactions: {}", _0)]
+	NodeNotReady(String),
+
+	/// A query matched more entries than the caller is allowed to retrieve at once
+	#[fail(
+		display = "Query matched {} entries, over the limit of {}, narrow it down",
+		_0, _1
+	)]
+	ResultLimit(usize, usize),
+
+	/// A page was queried from a snapshot that doesn't exist or has expired
+	#[fail(display = "Unknown or expired page snapshot {}", _0)]
+	UnknownPageSnapshot(String),
+
+	/// The recipient's response isn't signed as expected
+	#[fail(display = "Response signature error: {}", _0)]
+	ResponseSignature(String),
+
+	/// The sender of a slate didn't prove it controls the address it claims
+	#[fail(display = "Sender signature error: {}", _0)]
+	SenderSignature(String),
+
 	/// LibTX Error
 	#[fail(display = "LibTx Error")]
 	LibTX(libtx::ErrorKind),
@@ -69,6 +141,26 @@ pub enum ErrorKind {
 	#[fail(display = "Client Callback Error: {}", _0)]
 	ClientCallback(String),
 
+	/// Node could not be reached
+	#[fail(display = "Node unreachable: {}", _0)]
+	NodeUnreachable(String),
+
+	/// Node is still syncing with the network
+	#[fail(display = "Node is syncing: {}", _0)]
+	NodeSyncing(String),
+
+	/// Node rejected the API secret
+	#[fail(display = "Node authentication failed: {}", _0)]
+	NodeAuthFailed(String),
+
+	/// Node version is incompatible with the wallet
+	#[fail(display = "Incompatible node version: {}", _0)]
+	NodeIncompatible(String),
+
+	/// Node doesn't know the requested output or kernel
+	#[fail(display = "Output not found on node: {}", _0)]
+	NodeOutputNotFound(String),
+
 	/// Secp Error
 	#[fail(display = "Secp error")]
 	Secp(secp::Error),
@@ -153,6 +245,10 @@ pub enum ErrorKind {
 	#[fail(display = "Tx dump Error: {}", _0)]
 	TransactionDumpError(&'static str),
 
+	/// Invalid transaction tag
+	#[fail(display = "Invalid transaction tag '{}'", _0)]
+	InvalidTxTag(String),
+
 	/// Attempt to repost a transaction that's already confirmed
 	#[fail(display = "Transaction already confirmed error")]
 	TransactionAlreadyConfirmed,
@@ -161,6 +257,32 @@ pub enum ErrorKind {
 	#[fail(display = "Transaction {} has already been received", _0)]
 	TransactionAlreadyReceived(String),
 
+	/// Incoming transaction refused by the receive hook
+	#[fail(display = "Transaction rejected by recipient: {}", _0)]
+	ReceiveRejected(String),
+
+	/// Outgoing transaction vetoed by the pre-send hook
+	#[fail(display = "Transaction rejected by pre-send hook: {}", _0)]
+	SendRejected(String),
+
+	/// Wallet deletion attempted without a valid confirmation token
+	#[fail(display = "Invalid or expired confirmation token")]
+	InvalidConfirmationToken,
+
+	/// Epicbox key of a known contact differs from the one pinned on first use
+	#[fail(
+		display = "Epicbox key of contact {} changed from {} to {}",
+		contact, pinned, presented
+	)]
+	EpicboxKeyChanged {
+		/// Payment proof address of the contact
+		contact: String,
+		/// Key pinned for the contact
+		pinned: String,
+		/// Key presented by this slate
+		presented: String,
+	},
+
 	/// Attempt to repost a transaction that's not completed and stored
 	#[fail(display = "Transaction building not completed: {}", _0)]
 	TransactionBuildingNotCompleted(u32),
@@ -177,6 +299,26 @@ pub enum ErrorKind {
 	#[fail(display = "Unknown Account Label '{}'", _0)]
 	UnknownAccountLabel(String),
 
+	/// Reference unknown address book contact
+	#[fail(display = "Unknown contact '{}'", _0)]
+	UnknownContact(String),
+
+	/// Reference unknown send template
+	#[fail(display = "Unknown send template '{}'", _0)]
+	UnknownSendTemplate(String),
+
+	/// Payment request which can't be created or isn't valid
+	#[fail(display = "Invalid payment request: {}", _0)]
+	InvalidPaymentRequest(String),
+
+	/// Key derivation layout which can't be used
+	#[fail(display = "Invalid derivation scheme: {}", _0)]
+	InvalidDerivationScheme(String),
+
+	/// Metadata namespace, key or value outside the store's limits
+	#[fail(display = "Invalid metadata: {}", _0)]
+	InvalidMetadata(String),
+
 	/// Error from summing commitments via committed trait.
 	#[fail(display = "Committed Error")]
 	Committed(committed::Error),
@@ -194,7 +336,11 @@ pub enum ErrorKind {
 	SlateDeser,
 
 	/// Unknown slate version
-	#[fail(display = "Unknown Slate Version: {}", _0)]
+	#[fail(
+		display = "Unsupported slate version {}, this wallet reads slate versions 2 and 3. \
+		           The other wallet may need an upgrade, or to send an older slate version",
+		_0
+	)]
 	SlateVersion(u16),
 
 	/// Compatibility error between incoming slate versions and what's expected
@@ -209,6 +355,38 @@ pub enum ErrorKind {
 	#[fail(display = "Lifecycle Error: {}", _0)]
 	Lifecycle(String),
 
+	/// Operation needing the private keys of the wallet, on a watch-only wallet
+	#[fail(display = "Watch-only wallet: {}", _0)]
+	WatchOnly(String),
+
+	/// Signing request of a watch-only wallet that can't be signed
+	#[fail(display = "Invalid signing request: {}", _0)]
+	SigningRequest(String),
+
+	/// Output shared with another wallet that can't be built or spent
+	#[fail(display = "Multisig error: {}", _0)]
+	Multisig(String),
+
+	/// Atomic swap that can't progress as asked
+	#[fail(display = "Swap error: {}", _0)]
+	Swap(String),
+
+	/// Wallet backup that can't be written, read or decrypted
+	#[fail(display = "Backup error: {}", _0)]
+	Backup(String),
+
+	/// Send finalized before a second operator approved it
+	#[fail(display = "Transaction {} is awaiting approval", _0)]
+	ApprovalRequired(String),
+
+	/// Approval of a send that can't be given
+	#[fail(display = "Approval error: {}", _0)]
+	Approval(String),
+
+	/// The recipient didn't respond to a sent slate in time
+	#[fail(display = "Send timed out: {}", _0)]
+	SendTimeout(String),
+
 	/// Invalid Keychain Mask Error
 	#[fail(display = "Supplied Keychain Mask Token is incorrect")]
 	InvalidKeychainMask,
@@ -0,0 +1,176 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Events of the wallets open in this process, published as they happen to the
+//! listeners subscribed to them, such as frontends connected to the owner API.
+//! The events of a wallet are also kept in its store, for the clients catching up
+//! on those they missed while offline.
+
+use crate::epic_core::libtx::secp_ser;
+use crate::epic_keychain::Keychain;
+use crate::epic_util::secp::key::SecretKey;
+use crate::epic_util::Mutex;
+use crate::types::{NodeClient, WalletBackend, WalletOutputBatch};
+use crate::Error;
+use chrono::{DateTime, Utc};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use uuid::Uuid;
+
+/// Most events kept in a wallet's store, the oldest dropped first
+pub const MAX_STORED_EVENTS: usize = 1000;
+
+/// Something that happened to a wallet, published to the event subscribers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event")]
+pub enum WalletEvent {
+	/// A transaction sent to the wallet was received and signed
+	TxReceived {
+		/// Id of the transaction's slate
+		tx_slate_id: Uuid,
+		/// Amount received, in nanoepics
+		#[serde(with = "secp_ser::string_or_u64")]
+		amount: u64,
+	},
+	/// A transaction of the wallet was confirmed on chain
+	TxConfirmed {
+		/// Id of the transaction in the wallet's log
+		tx_id: u32,
+		/// Id of the transaction's slate, if any
+		tx_slate_id: Option<Uuid>,
+		/// Height the wallet saw the transaction confirmed at
+		#[serde(with = "secp_ser::string_or_u64")]
+		height: u64,
+	},
+	/// An output of the wallet was spent on chain
+	OutputSpent {
+		/// Commitment of the output
+		commit: String,
+		/// Value of the output, in nanoepics
+		#[serde(with = "secp_ser::string_or_u64")]
+		value: u64,
+	},
+	/// The chain was reorganised since the wallet's last update
+	ReorgDetected {
+		/// Height of the chain's tip after the reorg
+		#[serde(with = "secp_ser::string_or_u64")]
+		height: u64,
+	},
+	/// The node behind the wallet's node URL reports another version than it did
+	/// before, e.g. after a failover to another node
+	NodeVersionChanged {
+		/// Version the node reported before
+		previous_version: String,
+		/// Version the node reports now
+		node_version: String,
+	},
+	/// A scan of the chain for the wallet's outputs progressed
+	ScanProgress {
+		/// Percentage of the scan complete
+		percent: u8,
+	},
+	/// A listener of the wallet started accepting requests
+	ListenerStarted {
+		/// Kind of the listener, e.g. "owner" or "foreign"
+		listener: String,
+		/// Address it listens on
+		address: String,
+	},
+	/// A listener of the wallet stopped
+	ListenerStopped {
+		/// Kind of the listener, e.g. "owner" or "foreign"
+		listener: String,
+		/// Address it listened on
+		address: String,
+	},
+}
+
+/// An event kept in a wallet's store
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StoredEvent {
+	/// Id of the event, increasing with each event of the wallet
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub id: u64,
+	/// When the event was recorded
+	pub timestamp: DateTime<Utc>,
+	/// The event
+	pub event: WalletEvent,
+}
+
+lazy_static! {
+	static ref SUBSCRIBERS: Mutex<Vec<Sender<WalletEvent>>> = Mutex::new(vec![]);
+}
+
+/// Subscribe to the events published from now on, until the receiver is dropped
+pub fn subscribe_events() -> Receiver<WalletEvent> {
+	let (tx, rx) = channel();
+	SUBSCRIBERS.lock().push(tx);
+	rx
+}
+
+/// Publish an event to the current subscribers, forgetting those gone
+pub fn publish_event(event: WalletEvent) {
+	SUBSCRIBERS.lock().retain(|s| s.send(event.clone()).is_ok());
+}
+
+/// Keep events in the store the batch writes to, dropping the oldest ones over
+/// [`MAX_STORED_EVENTS`]
+pub fn store_events<K>(
+	batch: &mut dyn WalletOutputBatch<K>,
+	events: &[WalletEvent],
+) -> Result<(), Error>
+where
+	K: Keychain,
+{
+	if events.is_empty() {
+		return Ok(());
+	}
+	let mut ids: Vec<u64> = batch.events_iter().map(|e| e.id).collect();
+	ids.sort();
+	let mut next_id = ids.last().map(|id| id + 1).unwrap_or(1);
+	for e in events {
+		batch.save_event(StoredEvent {
+			id: next_id,
+			timestamp: Utc::now(),
+			event: e.clone(),
+		})?;
+		ids.push(next_id);
+		next_id += 1;
+	}
+	let excess = ids.len().saturating_sub(MAX_STORED_EVENTS);
+	for id in ids.into_iter().take(excess) {
+		batch.delete_event(id)?;
+	}
+	Ok(())
+}
+
+/// Keep an event in the wallet's store and publish it. The event is published
+/// even if it can't be stored, e.g. as the store is read-only.
+pub fn record_event<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	event: WalletEvent,
+) where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let res = wallet.batch(keychain_mask).and_then(|mut batch| {
+		store_events(&mut *batch, &[event.clone()])?;
+		batch.commit()
+	});
+	if let Err(e) = res {
+		warn!("Unable to store the wallet event {:?}: {}", event, e);
+	}
+	publish_event(event);
+}
@@ -22,7 +22,10 @@
 #![warn(missing_docs)]
 
 pub mod keys;
+pub mod multisig;
+pub mod password;
 pub mod scan;
 pub mod selection;
+pub mod swap;
 pub mod tx;
 pub mod updater;
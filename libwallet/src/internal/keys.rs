@@ -13,10 +13,12 @@
 // limitations under the License.
 
 //! Wallet key management functions
-use crate::epic_keychain::{ChildNumber, ExtKeychain, Identifier, Keychain};
+use crate::config::DerivationConfig;
+use crate::epic_keychain::{ChildNumber, ExtKeychainPath, Identifier, Keychain};
 use crate::epic_util::secp::key::SecretKey;
 use crate::error::{Error, ErrorKind};
 use crate::types::{AcctPathMapping, NodeClient, WalletBackend};
+use std::collections::BTreeMap;
 
 /// Get next available key in the wallet for a given parent
 pub fn next_available_key<'a, T: ?Sized, C, K>(
@@ -75,27 +77,24 @@ where
 		return Err(ErrorKind::AccountLabelAlreadyExists(label.clone()).into());
 	}
 
-	// We're always using paths at m/k/0 for parent keys for output derivations
-	// so find the highest of those, then increment (to conform with external/internal
-	// derivation chains in BIP32 spec)
-
-	let highest_entry = wallet.acct_path_iter().max_by(|a, b| {
-		<u32>::from(a.path.to_path().path[0]).cmp(&<u32>::from(b.path.to_path().path[0]))
-	});
-
-	let return_id = {
-		if let Some(e) = highest_entry {
-			let mut p = e.path.to_path();
-			p.path[0] = ChildNumber::from(<u32>::from(p.path[0]) + 1);
-			p.to_identifier()
-		} else {
-			ExtKeychain::derive_key_id(2, 0, 0, 0, 0)
-		}
+	// Parent keys for output derivations are laid out by the wallet's derivation
+	// scheme, m/k/0 by default, so find the highest account index of those, then
+	// increment (to conform with external/internal derivation chains in BIP32 spec)
+	let config = wallet.derivation_config()?;
+	let highest_index = wallet
+		.acct_path_iter()
+		.filter_map(|a| account_index(&config, &a.path))
+		.max();
+
+	let return_id = match highest_index {
+		Some(i) => account_path(&config, i + 1),
+		None => account_path(&config, 0),
 	};
 
 	let save_path = AcctPathMapping {
 		label: label.to_owned(),
 		path: return_id.clone(),
+		metadata: BTreeMap::new(),
 	};
 
 	let mut batch = wallet.batch(keychain_mask)?;
@@ -117,9 +116,15 @@ where
 	K: Keychain + 'a,
 {
 	let label = label.to_owned();
+	// keep any metadata already attached to the account
+	let metadata = match wallet.get_acct_path(label.clone())? {
+		Some(m) => m.metadata,
+		None => BTreeMap::new(),
+	};
 	let save_path = AcctPathMapping {
 		label: label.to_owned(),
 		path: path.clone(),
+		metadata,
 	};
 
 	let mut batch = wallet.batch(keychain_mask)?;
@@ -127,3 +132,129 @@ where
 	batch.commit()?;
 	Ok(())
 }
+
+/// Check the derivation scheme leaves room for the output level below accounts
+pub fn check_derivation_config(config: &DerivationConfig) -> Result<(), Error> {
+	let depth = account_depth(config);
+	if depth > 3 {
+		let reason = format!("account paths are {} levels deep, at most 3 allowed", depth);
+		return Err(ErrorKind::InvalidDerivationScheme(reason).into());
+	}
+	if config.purpose.iter().any(|p| *p >= 1 << 31) {
+		let reason = "purpose constants must be below 2^31".to_owned();
+		return Err(ErrorKind::InvalidDerivationScheme(reason).into());
+	}
+	Ok(())
+}
+
+/// Parent path of the account with the given index under the derivation scheme
+pub fn account_path(config: &DerivationConfig, index: u32) -> Identifier {
+	let mut path = ExtKeychainPath::new(account_depth(config), 0, 0, 0, 0);
+	for (i, p) in config.purpose.iter().enumerate() {
+		path.path[i] = child_number(*p, config.hardened_purpose);
+	}
+	path.path[config.purpose.len()] = child_number(index, config.hardened_account);
+	path.to_identifier()
+}
+
+/// Index of the account at the given parent path, if laid out by the derivation scheme
+fn account_index(config: &DerivationConfig, path: &Identifier) -> Option<u32> {
+	let p = path.to_path();
+	if p.depth != account_depth(config) {
+		return None;
+	}
+	let index = match p.path[config.purpose.len()] {
+		ChildNumber::Normal { index } => index,
+		ChildNumber::Hardened { index } => index,
+	};
+	match account_path(config, index) == *path {
+		true => Some(index),
+		false => None,
+	}
+}
+
+fn account_depth(config: &DerivationConfig) -> u8 {
+	let chain_level = config.chain_level.unwrap_or(true) as usize;
+	(config.purpose.len() + 1 + chain_level) as u8
+}
+
+fn child_number(index: u32, hardened: bool) -> ChildNumber {
+	match hardened {
+		true => ChildNumber::Hardened { index },
+		false => ChildNumber::Normal { index },
+	}
+}
+
+/// Lay out the accounts of a new wallet by the given derivation scheme, moving the
+/// default account to the scheme's first account. Refused once the wallet holds
+/// outputs or transactions, as they would no longer be found under their accounts.
+pub fn set_derivation_config<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	config: DerivationConfig,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	check_derivation_config(&config)?;
+	if wallet.iter().next().is_some() || wallet.tx_log_iter().next().is_some() {
+		let reason = "wallet already holds outputs or transactions".to_owned();
+		return Err(ErrorKind::InvalidDerivationScheme(reason).into());
+	}
+	if wallet.acct_path_iter().any(|a| a.label != "default") {
+		let reason = "wallet already has accounts other than the default".to_owned();
+		return Err(ErrorKind::InvalidDerivationScheme(reason).into());
+	}
+
+	let path = account_path(&config, 0);
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.save_derivation_config(config)?;
+		batch.commit()?;
+	}
+	set_acct_path(wallet, keychain_mask, "default", &path)?;
+	wallet.set_parent_key_id(path);
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn default_layout() {
+		let config = DerivationConfig::default();
+		assert!(check_derivation_config(&config).is_ok());
+		let path = account_path(&config, 3);
+		assert_eq!(path, ExtKeychainPath::new(2, 3, 0, 0, 0).to_identifier());
+		assert_eq!(account_index(&config, &path), Some(3));
+	}
+
+	#[test]
+	fn hardened_layout() {
+		let config = DerivationConfig {
+			purpose: vec![44],
+			hardened_purpose: true,
+			hardened_account: true,
+			chain_level: Some(true),
+		};
+		assert!(check_derivation_config(&config).is_ok());
+		let path = account_path(&config, 1).to_path();
+		assert_eq!(path.depth, 3);
+		assert_eq!(path.path[0], ChildNumber::Hardened { index: 44 });
+		assert_eq!(path.path[1], ChildNumber::Hardened { index: 1 });
+
+		// the default layout's accounts aren't recognised
+		let default_path = account_path(&DerivationConfig::default(), 1);
+		assert_eq!(account_index(&config, &default_path), None);
+
+		// no room left for the output level
+		let config = DerivationConfig {
+			purpose: vec![44, 0],
+			..config
+		};
+		assert!(check_derivation_config(&config).is_err());
+	}
+}
@@ -0,0 +1,728 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Outputs shared by the two parties of a transaction, only spendable by both. Each
+//! party derives its share of the blinding factor of the output from its own keychain,
+//! the parties exchange their partial commitments and build the range proof of the
+//! output together, neither learning the other's share.
+//!
+//! A shared output is created in four steps: the sender selects its inputs and adds its
+//! partial commitment, the recipient joins with its own and the first round of the range
+//! proof, the sender adds its rounds of the range proof and its partial signature, and
+//! the recipient builds the range proof and the final transaction. It's spent whole to
+//! one of the parties, the other party sending it and each adding its share to its excess.
+
+use crate::blake2::blake2b::blake2b;
+use crate::epic_core::core::amount_to_hr_string;
+use crate::epic_core::core::transaction::{Input, Output, OutputFeatures};
+use crate::epic_core::libtx::tx_fee;
+use crate::epic_keychain::{BlindSum, BlindingFactor, Identifier, Keychain, SwitchCommitmentType};
+use crate::epic_util::secp::key::{PublicKey, SecretKey};
+use crate::epic_util::secp::pedersen::{Commitment, RangeProof};
+use crate::epic_util::secp::Secp256k1;
+use crate::epic_util::{from_hex, to_hex};
+use crate::internal::{keys, updater};
+use crate::slate::{MultisigOutput, MultisigParty, Slate};
+use crate::types::{
+	Context, NodeClient, OutputData, OutputStatus, TxLogEntry, TxLogEntryType, WalletBackend,
+};
+use crate::{Error, ErrorKind};
+use rand::rngs::mock::StepRng;
+use rand::thread_rng;
+
+/// Steps of a range proof built by several parties
+const PROOF_STEP_T_ONE_T_TWO: u8 = 1;
+const PROOF_STEP_TAU_X: u8 = 2;
+const PROOF_STEP_FINAL: u8 = 0;
+
+/// Id of the participant joining the shared output
+const JOINING_PARTICIPANT_ID: u64 = 1;
+
+fn multisig_error(msg: &str) -> Error {
+	ErrorKind::Multisig(msg.to_owned()).into()
+}
+
+/// Our share of the blinding factor of a shared output
+pub fn share_blind<K>(keychain: &K, key_id: &Identifier) -> Result<SecretKey, Error>
+where
+	K: Keychain,
+{
+	Ok(keychain.derive_key(0, key_id, &SwitchCommitmentType::None)?)
+}
+
+/// Our private nonce of the range proof of a shared output, only known to us
+fn private_nonce(
+	secp: &Secp256k1,
+	blind: &SecretKey,
+	common_nonce: &SecretKey,
+) -> Result<SecretKey, Error> {
+	let hash = blake2b(32, &common_nonce.0, &blind.0);
+	Ok(SecretKey::from_slice(secp, hash.as_bytes())?)
+}
+
+/// Commitment of a shared output to the amount, the sum of the partial commitments
+fn shared_commit(
+	secp: &Secp256k1,
+	amount: u64,
+	parties: &[MultisigParty],
+) -> Result<Commitment, Error> {
+	let mut commits = vec![secp.commit_value(amount)?];
+	for p in parties {
+		commits.push(Commitment::from_pubkey(secp, &p.partial_commit)?);
+	}
+	Ok(secp.commit_sum(commits, vec![])?)
+}
+
+/// First round of the range proof, our T1 and T2
+fn proof_t_one_t_two(
+	secp: &Secp256k1,
+	amount: u64,
+	blind: &SecretKey,
+	common_nonce: &SecretKey,
+	commit: Commitment,
+) -> Result<(PublicKey, PublicKey), Error> {
+	let nonce = private_nonce(secp, blind, common_nonce)?;
+	let mut t_one = PublicKey::new();
+	let mut t_two = PublicKey::new();
+	secp.bullet_proof_multisig(
+		amount,
+		blind.clone(),
+		common_nonce.clone(),
+		None,
+		None,
+		None,
+		Some(&mut t_one),
+		Some(&mut t_two),
+		vec![commit],
+		Some(&nonce),
+		PROOF_STEP_T_ONE_T_TWO,
+	);
+	Ok((t_one, t_two))
+}
+
+/// Sums of the T1 and T2 of all the parties
+fn t_one_t_two_sums(
+	secp: &Secp256k1,
+	parties: &[MultisigParty],
+) -> Result<(PublicKey, PublicKey), Error> {
+	let mut t_ones = vec![];
+	let mut t_twos = vec![];
+	for p in parties {
+		match (&p.t_one, &p.t_two) {
+			(Some(t_one), Some(t_two)) => {
+				t_ones.push(t_one);
+				t_twos.push(t_two);
+			}
+			_ => {
+				return Err(multisig_error(
+					"a party's first round of the range proof is missing",
+				))
+			}
+		}
+	}
+	Ok((
+		PublicKey::from_combination(secp, t_ones)?,
+		PublicKey::from_combination(secp, t_twos)?,
+	))
+}
+
+/// Second round of the range proof, our share of tau_x
+fn proof_tau_x(
+	secp: &Secp256k1,
+	amount: u64,
+	blind: &SecretKey,
+	common_nonce: &SecretKey,
+	commit: Commitment,
+	parties: &[MultisigParty],
+) -> Result<SecretKey, Error> {
+	let (mut t_one, mut t_two) = t_one_t_two_sums(secp, parties)?;
+	let nonce = private_nonce(secp, blind, common_nonce)?;
+	let mut tau_x = SecretKey::new(secp, &mut thread_rng());
+	secp.bullet_proof_multisig(
+		amount,
+		blind.clone(),
+		common_nonce.clone(),
+		None,
+		None,
+		Some(&mut tau_x),
+		Some(&mut t_one),
+		Some(&mut t_two),
+		vec![commit],
+		Some(&nonce),
+		PROOF_STEP_TAU_X,
+	);
+	Ok(tau_x)
+}
+
+/// Range proof of a shared output from both rounds of all the parties
+fn build_proof(
+	secp: &Secp256k1,
+	amount: u64,
+	blind: &SecretKey,
+	common_nonce: &SecretKey,
+	commit: Commitment,
+	parties: &[MultisigParty],
+) -> Result<RangeProof, Error> {
+	let (mut t_one, mut t_two) = t_one_t_two_sums(secp, parties)?;
+	let mut tau_x: Option<SecretKey> = None;
+	for p in parties {
+		let share = match &p.tau_x {
+			Some(t) => t,
+			None => {
+				return Err(multisig_error(
+					"a party's second round of the range proof is missing",
+				))
+			}
+		};
+		tau_x = Some(match tau_x {
+			Some(mut sum) => {
+				sum.add_assign(secp, share)?;
+				sum
+			}
+			None => share.clone(),
+		});
+	}
+	let mut tau_x = tau_x.ok_or_else(|| multisig_error("the shared output has no parties"))?;
+	let nonce = private_nonce(secp, blind, common_nonce)?;
+	let proof = secp
+		.bullet_proof_multisig(
+			amount,
+			blind.clone(),
+			common_nonce.clone(),
+			None,
+			None,
+			Some(&mut tau_x),
+			Some(&mut t_one),
+			Some(&mut t_two),
+			vec![commit],
+			Some(&nonce),
+			PROOF_STEP_FINAL,
+		)
+		.ok_or_else(|| multisig_error("the range proof couldn't be built"))?;
+	secp.verify_bullet_proof(commit, proof, None)
+		.map_err(|_| multisig_error("the range proof of the shared output is invalid"))?;
+	Ok(proof)
+}
+
+/// Checks the parties of the shared output the slate creates, once both added their
+/// partial commitment, against our share and the slate's participants. Returns the
+/// commitment of the output.
+fn check_parties(
+	secp: &Secp256k1,
+	slate: &Slate,
+	blind: &SecretKey,
+	participant_id: usize,
+) -> Result<Commitment, Error> {
+	let output = match slate.multisig_output {
+		Some(ref o) => o,
+		None => return Err(multisig_error("the slate doesn't create a shared output")),
+	};
+	if output.parties.len() != 2 {
+		return Err(multisig_error("the shared output wasn't joined"));
+	}
+	let partial_commit = PublicKey::from_secret_key(secp, blind)?;
+	match output
+		.parties
+		.iter()
+		.find(|p| p.id == participant_id as u64)
+	{
+		Some(p) if p.partial_commit == partial_commit => (),
+		_ => return Err(multisig_error("our share of the shared output is missing")),
+	}
+	// the joining party could otherwise cancel the initiator's partial commitment with
+	// its own, its partial signature proves it knows the private key of its excess
+	let joining = output
+		.parties
+		.iter()
+		.find(|p| p.id == JOINING_PARTICIPANT_ID);
+	match (
+		joining,
+		slate.participant_with_id(JOINING_PARTICIPANT_ID as usize),
+	) {
+		(Some(p), Some(d)) if p.partial_commit == d.public_blind_excess => (),
+		_ => {
+			return Err(multisig_error(
+				"the joining party's partial commitment isn't its excess",
+			))
+		}
+	}
+	let commit = shared_commit(secp, slate.amount, &output.parties)?;
+	if output.commit != Some(commit) {
+		return Err(multisig_error(
+			"the commitment of the shared output doesn't match its parties",
+		));
+	}
+	Ok(commit)
+}
+
+/// Our party of the shared output the slate creates
+fn our_party(slate: &mut Slate, participant_id: usize) -> Result<&mut MultisigParty, Error> {
+	slate
+		.multisig_output
+		.as_mut()
+		.and_then(|o| o.parties.iter_mut().find(|p| p.id == participant_id as u64))
+		.ok_or_else(|| multisig_error("our share of the shared output is missing"))
+}
+
+/// The shared output the wallet holds a share of, as stored
+fn shared_output_data(
+	parent_key_id: &Identifier,
+	key_id: &Identifier,
+	commit: &Commitment,
+	amount: u64,
+	height: u64,
+	tx_log_entry: Option<u32>,
+) -> OutputData {
+	OutputData {
+		root_key_id: parent_key_id.clone(),
+		key_id: key_id.clone(),
+		n_child: key_id.to_path().last_path_index(),
+		commit: Some(to_hex(commit.0.to_vec())),
+		mmr_index: None,
+		value: amount,
+		status: OutputStatus::Unconfirmed,
+		height,
+		lock_height: 0,
+		is_coinbase: false,
+		tx_log_entry,
+		frozen: false,
+		multisig: true,
+	}
+}
+
+/// Adds our share of a new shared output of the slate's amount to the slate, as the
+/// sender, and to our excess in the context
+pub fn add_output_share<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &mut Slate,
+	context: &mut Context,
+	use_test_rng: bool,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if slate.amount == 0 {
+		return Err(multisig_error("a shared output can't be empty"));
+	}
+	let key_id = keys::next_available_key(wallet, keychain_mask)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let blind = share_blind(&keychain, &key_id)?;
+	let common_nonce = match use_test_rng {
+		false => SecretKey::new(secp, &mut thread_rng()),
+		true => SecretKey::new(secp, &mut StepRng::new(1234567890u64, 1)),
+	};
+	let sec_key = keychain.blind_sum(
+		&BlindSum::new()
+			.add_blinding_factor(BlindingFactor::from_secret_key(context.sec_key.clone()))
+			.add_blinding_factor(BlindingFactor::from_secret_key(blind.clone())),
+	)?;
+	context.sec_key = sec_key.secret_key(secp)?;
+	context.multisig_key_id = Some(key_id);
+	slate.multisig_output = Some(MultisigOutput {
+		common_nonce: BlindingFactor::from_secret_key(common_nonce),
+		commit: None,
+		parties: vec![MultisigParty {
+			id: context.participant_id as u64,
+			partial_commit: PublicKey::from_secret_key(secp, &blind)?,
+			t_one: None,
+			t_two: None,
+			tau_x: None,
+		}],
+	});
+	Ok(())
+}
+
+/// Joins the shared output the slate creates, as the recipient: adds our partial
+/// commitment, the commitment of the output and our first round of its range proof to
+/// the slate, and records the output. Returns our context, with our share as excess.
+pub fn join_output<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &mut Slate,
+	parent_key_id: &Identifier,
+	use_test_rng: bool,
+) -> Result<Context, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	match slate.multisig_output {
+		Some(ref o) if o.parties.len() == 1 && o.commit.is_none() => (),
+		Some(_) => return Err(multisig_error("the shared output was joined already")),
+		None => return Err(multisig_error("the slate doesn't create a shared output")),
+	}
+	if slate.participant_data.len() != 1 || slate.amount == 0 {
+		return Err(multisig_error("the slate isn't a new shared output"));
+	}
+	let amount = slate.amount;
+	let key_id = keys::next_available_key(wallet, keychain_mask)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let blind = share_blind(&keychain, &key_id)?;
+
+	let commit = {
+		let output = slate.multisig_output.as_mut().unwrap();
+		output.parties.push(MultisigParty {
+			id: JOINING_PARTICIPANT_ID,
+			partial_commit: PublicKey::from_secret_key(secp, &blind)?,
+			t_one: None,
+			t_two: None,
+			tau_x: None,
+		});
+		let commit = shared_commit(secp, amount, &output.parties)?;
+		let common_nonce = output.common_nonce.secret_key(secp)?;
+		let (t_one, t_two) = proof_t_one_t_two(secp, amount, &blind, &common_nonce, commit)?;
+		let ours = output.parties.last_mut().unwrap();
+		ours.t_one = Some(t_one);
+		ours.t_two = Some(t_two);
+		output.commit = Some(commit);
+		commit
+	};
+
+	// our excess is our share of the output
+	let mut context = Context::new(
+		secp,
+		blind,
+		parent_key_id,
+		use_test_rng,
+		JOINING_PARTICIPANT_ID as usize,
+	);
+	context.multisig_key_id = Some(key_id.clone());
+	slate.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		JOINING_PARTICIPANT_ID as usize,
+		None,
+		use_test_rng,
+	)?;
+
+	let mut batch = wallet.batch(keychain_mask)?;
+	let log_id = batch.next_tx_log_id(parent_key_id)?;
+	let mut t = TxLogEntry::new(parent_key_id.clone(), TxLogEntryType::TxReceived, log_id);
+	t.tx_slate_id = Some(slate.id);
+	// shared outputs aren't credited to the balance
+	t.num_outputs = 1;
+	t.messages = Some(slate.participant_messages());
+	t.ttl_cutoff_height = slate.ttl_cutoff_height;
+	t.kernel_lookup_min_height = Some(slate.height);
+	batch.save(shared_output_data(
+		parent_key_id,
+		&key_id,
+		&commit,
+		amount,
+		slate.height,
+		Some(log_id),
+	))?;
+	batch.save_tx_log_entry(t, parent_key_id)?;
+	batch.commit()?;
+	Ok(context)
+}
+
+/// Adds our rounds of the range proof of the shared output to the slate as the sender,
+/// once the recipient joined it, and records the output
+pub fn add_proof_rounds<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &mut Slate,
+	context: &Context,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let key_id = match context.multisig_key_id {
+		Some(ref k) => k.clone(),
+		None => {
+			return Err(multisig_error(
+				"the transaction has no share of a shared output",
+			))
+		}
+	};
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let blind = share_blind(&keychain, &key_id)?;
+	let amount = slate.amount;
+	let commit = check_parties(secp, slate, &blind, context.participant_id)?;
+	if our_party(slate, context.participant_id)?.t_one.is_some() {
+		return Err(multisig_error(
+			"our rounds of the range proof were added already",
+		));
+	}
+
+	let output = slate.multisig_output.as_mut().unwrap();
+	let common_nonce = output.common_nonce.secret_key(secp)?;
+	let (t_one, t_two) = proof_t_one_t_two(secp, amount, &blind, &common_nonce, commit)?;
+	{
+		let ours = output
+			.parties
+			.iter_mut()
+			.find(|p| p.id == context.participant_id as u64)
+			.unwrap();
+		ours.t_one = Some(t_one);
+		ours.t_two = Some(t_two);
+	}
+	let tau_x = proof_tau_x(secp, amount, &blind, &common_nonce, commit, &output.parties)?;
+	our_party(slate, context.participant_id)?.tau_x = Some(tau_x);
+
+	// the kernel excess, once the recipient adds the output
+	let excess = secp.commit_sum(vec![slate.calc_excess(&keychain)?, commit], vec![])?;
+	let tx = updater::retrieve_txs(
+		wallet,
+		None,
+		Some(slate.id),
+		Some(&context.parent_key_id),
+		false,
+	)?
+	.into_iter()
+	.find(|t| t.tx_type == TxLogEntryType::TxSent);
+	let mut batch = wallet.batch(keychain_mask)?;
+	if let Some(ref t) = tx {
+		let mut t = t.clone();
+		t.kernel_excess = Some(excess);
+		batch.save_tx_log_entry(t, &context.parent_key_id)?;
+	}
+	batch.save(shared_output_data(
+		&context.parent_key_id,
+		&key_id,
+		&commit,
+		amount,
+		slate.height,
+		tx.map(|t| t.id),
+	))?;
+	batch.commit()?;
+	Ok(())
+}
+
+/// Builds the range proof of the shared output as the recipient, once the sender added
+/// its rounds, and adds the output to the slate's transaction
+pub fn complete_output<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &mut Slate,
+	context: &Context,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let key_id = match context.multisig_key_id {
+		Some(ref k) => k.clone(),
+		None => {
+			return Err(multisig_error(
+				"the transaction has no share of a shared output",
+			))
+		}
+	};
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let blind = share_blind(&keychain, &key_id)?;
+	let amount = slate.amount;
+	let commit = check_parties(secp, slate, &blind, context.participant_id)?;
+	if our_party(slate, context.participant_id)?.tau_x.is_some() {
+		return Err(multisig_error("the shared output was built already"));
+	}
+
+	let proof = {
+		let output = slate.multisig_output.as_mut().unwrap();
+		let common_nonce = output.common_nonce.secret_key(secp)?;
+		let tau_x = proof_tau_x(secp, amount, &blind, &common_nonce, commit, &output.parties)?;
+		output
+			.parties
+			.iter_mut()
+			.find(|p| p.id == context.participant_id as u64)
+			.unwrap()
+			.tau_x = Some(tau_x);
+		build_proof(secp, amount, &blind, &common_nonce, commit, &output.parties)?
+	};
+	slate.tx = slate.tx.clone().with_output(Output {
+		features: OutputFeatures::Plain,
+		commit,
+		proof,
+	});
+	Ok(())
+}
+
+/// The shared output a send spends, if its inputs are a shared output of the account
+pub fn shared_input<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	inputs: Option<&[Commitment]>,
+	parent_key_id: &Identifier,
+) -> Result<Option<OutputData>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let inputs = match inputs {
+		Some(i) => i,
+		None => return Ok(None),
+	};
+	let commits: Vec<String> = inputs.iter().map(|c| to_hex(c.0.to_vec())).collect();
+	let shared = wallet.iter().find(|o| {
+		o.multisig
+			&& o.root_key_id == *parent_key_id
+			&& o.commit.as_ref().map_or(false, |c| commits.contains(c))
+	});
+	if shared.is_some() && inputs.len() > 1 {
+		return Err(multisig_error("a shared output is spent alone"));
+	}
+	Ok(shared)
+}
+
+/// Adds the shared output to the slate as the input of a send to the other party,
+/// which adds its share of the output to the transaction. The whole output is sent,
+/// the slate's amount must be its value less the fee.
+pub fn add_shared_input_to_slate<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &mut Slate,
+	output: &OutputData,
+	message: Option<String>,
+	use_test_rng: bool,
+) -> Result<Context, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let commit = match output.commit.clone().map(from_hex) {
+		Some(Ok(c)) => Commitment::from_vec(c),
+		_ => return Err(multisig_error("no commitment stored for the shared output")),
+	};
+	if output.status != OutputStatus::Unspent {
+		return Err(multisig_error(&format!(
+			"shared output {} is {}",
+			to_hex(commit.0.to_vec()),
+			output.status
+		)));
+	}
+	let fee = tx_fee(1, 1, 1, None);
+	if output.value <= fee || slate.amount + fee != output.value {
+		return Err(multisig_error(&format!(
+			"a shared output is sent whole, {} once the fee of {} paid",
+			amount_to_hr_string(output.value.saturating_sub(fee), false),
+			amount_to_hr_string(fee, false),
+		)));
+	}
+
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	slate.fee = fee;
+	slate.multisig_input = Some(commit);
+	slate.update_kernel();
+	slate.tx = slate
+		.tx
+		.clone()
+		.with_input(Input::new(OutputFeatures::Plain, commit));
+
+	// our excess is minus our share of the output
+	let blind = share_blind(&keychain, &output.key_id)?;
+	let sec_key = keychain
+		.blind_sum(&BlindSum::new().sub_blinding_factor(BlindingFactor::from_secret_key(blind)))?;
+	let mut context = Context::new(
+		secp,
+		sec_key.secret_key(secp)?,
+		&output.root_key_id,
+		use_test_rng,
+		0,
+	);
+	context.fee = fee;
+	context.add_input(&output.key_id, &output.mmr_index, output.value);
+	slate.fill_round_1(
+		&keychain,
+		&mut context.sec_key,
+		&context.sec_nonce,
+		0,
+		message,
+		use_test_rng,
+	)?;
+	Ok(context)
+}
+
+/// Subtracts our share of the shared output the slate spends from our excess, as its
+/// recipient, and locks the output along with the received one
+pub fn add_input_share<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	slate: &Slate,
+	received_key_id: &Identifier,
+	context: &mut Context,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let commit = match slate.multisig_input {
+		Some(c) => c,
+		None => return Ok(()),
+	};
+	let commit_hex = to_hex(commit.0.to_vec());
+	let mut output = match wallet
+		.iter()
+		.find(|o| o.multisig && o.commit.as_ref() == Some(&commit_hex))
+	{
+		Some(o) => o,
+		None => {
+			return Err(multisig_error(&format!(
+				"no share of shared output {}",
+				commit_hex
+			)))
+		}
+	};
+	if !slate.tx.inputs().iter().any(|i| i.commitment() == commit) {
+		return Err(multisig_error(
+			"the transaction doesn't spend the shared output",
+		));
+	}
+	if output.status != OutputStatus::Unspent {
+		return Err(multisig_error(&format!(
+			"shared output {} is {}",
+			commit_hex, output.status
+		)));
+	}
+	// the sender could otherwise keep part of the output
+	if slate.amount + slate.fee < output.value {
+		return Err(multisig_error(&format!(
+			"the shared output of {} is sent whole, not {}",
+			amount_to_hr_string(output.value, false),
+			amount_to_hr_string(slate.amount, false),
+		)));
+	}
+
+	let keychain = wallet.keychain(keychain_mask)?;
+	let blind = share_blind(&keychain, &output.key_id)?;
+	let sec_key = keychain.blind_sum(
+		&BlindSum::new()
+			.add_blinding_factor(BlindingFactor::from_secret_key(context.sec_key.clone()))
+			.sub_blinding_factor(BlindingFactor::from_secret_key(blind)),
+	)?;
+	context.sec_key = sec_key.secret_key(keychain.secp())?;
+	context.add_input(&output.key_id, &output.mmr_index, output.value);
+
+	// cancelling the receive unlocks the output
+	output.tx_log_entry = wallet.get(received_key_id, &None)?.tx_log_entry;
+	let mut batch = wallet.batch(keychain_mask)?;
+	batch.lock_output(&mut output)?;
+	batch.commit()?;
+	Ok(())
+}
@@ -0,0 +1,85 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of wallet passwords against the configured policy
+
+use crate::config::PasswordPolicyConfig;
+use crate::error::{Error, ErrorKind};
+
+/// Minimum number of characters of a password, if the policy doesn't say
+pub const DEFAULT_PASSWORD_MIN_LENGTH: usize = 8;
+
+/// Kinds of characters a password is made of, out of lowercase letters, uppercase
+/// letters, digits and others
+fn char_classes(password: &str) -> usize {
+	let mut classes = [false; 4];
+	for c in password.chars() {
+		let class = match c {
+			c if c.is_lowercase() => 0,
+			c if c.is_uppercase() => 1,
+			c if c.is_numeric() => 2,
+			_ => 3,
+		};
+		classes[class] = true;
+	}
+	classes.iter().filter(|c| **c).count()
+}
+
+/// Check a password a wallet is about to be encrypted with meets the policy
+pub fn check_password_policy(policy: &PasswordPolicyConfig, password: &str) -> Result<(), Error> {
+	let min_length = policy.min_length.unwrap_or(DEFAULT_PASSWORD_MIN_LENGTH);
+	let length = password.chars().count();
+	if length < min_length {
+		let reason = format!(
+			"it's {} characters long, at least {} required",
+			length, min_length
+		);
+		return Err(ErrorKind::WeakPassword(reason).into());
+	}
+	let min_classes = policy.min_char_classes.unwrap_or(1);
+	let classes = char_classes(password);
+	if classes < min_classes {
+		let reason = format!(
+			"it uses {} of lowercase letters, uppercase letters, digits and other \
+			 characters, at least {} required",
+			classes, min_classes
+		);
+		return Err(ErrorKind::WeakPassword(reason).into());
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn password_policy() {
+		let policy = PasswordPolicyConfig {
+			min_length: Some(10),
+			min_char_classes: Some(3),
+		};
+		assert!(check_password_policy(&policy, "Sh0rt!").is_err());
+		assert!(check_password_policy(&policy, "longbutlowercase").is_err());
+		assert!(check_password_policy(&policy, "Long and Spaced").is_ok());
+		assert!(check_password_policy(&policy, "ünïcödé Wörd9").is_ok());
+
+		let policy = PasswordPolicyConfig {
+			min_length: None,
+			min_char_classes: None,
+		};
+		assert!(check_password_policy(&policy, "1234567").is_err());
+		assert!(check_password_policy(&policy, "12345678").is_ok());
+	}
+}
@@ -14,17 +14,20 @@
 //! Functions to restore a wallet's outputs from just the master seed
 
 use crate::api_impl::owner_updater::StatusMessage;
-use crate::epic_core::global;
+use crate::chain_params;
 use crate::epic_core::libtx::proof;
 use crate::epic_keychain::{Identifier, Keychain, SwitchCommitmentType};
 use crate::epic_util::secp::key::SecretKey;
-use crate::epic_util::secp::pedersen;
-use crate::epic_util::Mutex;
+use crate::epic_util::secp::{pedersen, ContextFlag, Secp256k1};
+use crate::epic_util::{from_hex, to_hex, Mutex};
 use crate::internal::{keys, updater};
 use crate::types::*;
-use crate::{wallet_lock, Error, OutputCommitMapping};
+use crate::{
+	publish_event, wallet_lock, Error, ErrorKind, ImportedOutput, OutputCommitMapping,
+	OutputImportSummary, ScanPreview, ViewKey, WalletEvent,
+};
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
@@ -61,35 +64,51 @@ struct RestoredTxStats {
 	pub num_outputs: usize,
 }
 
-fn identify_utxo_outputs<'a, K>(
-	keychain: &K,
-	outputs: Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
-	status_send_channel: &Option<Sender<StatusMessage>>,
-	percentage_complete: u8,
-) -> Result<Vec<OutputResult>, Error>
+/// Amount, key and switch commitment type of an output, if its range proof could be
+/// rewound
+type RewoundOutput = Option<(u64, Identifier, SwitchCommitmentType)>;
+
+/// Rewinds range proofs with the keys of the wallet's seed
+fn keychain_rewinder<'k, K>(
+	keychain: &'k K,
+) -> impl Fn(&pedersen::Commitment, &pedersen::RangeProof) -> Result<RewoundOutput, Error> + 'k
 where
-	K: Keychain + 'a,
+	K: Keychain,
 {
-	let mut wallet_outputs: Vec<OutputResult> = Vec::new();
-
 	let legacy_builder = proof::LegacyProofBuilder::new(keychain);
 	let builder = proof::ProofBuilder::new(keychain);
+	move |commit: &pedersen::Commitment, proof: &pedersen::RangeProof| {
+		// Try new rewind first
+		let info_new = proof::rewind(keychain.secp(), &builder, *commit, None, *proof)?;
+
+		// If new didn't work, try legacy rewind
+		if info_new.is_none() {
+			Ok(proof::rewind(
+				keychain.secp(),
+				&legacy_builder,
+				*commit,
+				None,
+				*proof,
+			)?)
+		} else {
+			Ok(info_new)
+		}
+	}
+}
+
+fn identify_utxo_outputs(
+	rewind: &dyn Fn(&pedersen::Commitment, &pedersen::RangeProof) -> Result<RewoundOutput, Error>,
+	outputs: Vec<(pedersen::Commitment, pedersen::RangeProof, bool, u64, u64)>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	percentage_complete: u8,
+) -> Result<Vec<OutputResult>, Error> {
+	let mut wallet_outputs: Vec<OutputResult> = Vec::new();
 
 	for output in outputs.iter() {
 		let (commit, proof, is_coinbase, height, mmr_index) = output;
 		// attempt to unwind message from the RP and get a value
 		// will fail if it's not ours
-		let info = {
-			// Try new rewind first
-			let info_new = proof::rewind(keychain.secp(), &builder, *commit, None, *proof)?;
-
-			// If new didn't work, try legacy rewind
-			if info_new.is_none() {
-				proof::rewind(keychain.secp(), &legacy_builder, *commit, None, *proof)?
-			} else {
-				info_new
-			}
-		};
+		let info = rewind(commit, proof)?;
 
 		let (amount, key_id, switch) = match info {
 			Some(i) => i,
@@ -99,7 +118,7 @@ where
 		};
 
 		let lock_height = if *is_coinbase {
-			*height + global::coinbase_maturity()
+			*height + chain_params::coinbase_maturity()
 		} else {
 			*height
 		};
@@ -134,21 +153,22 @@ where
 	Ok(wallet_outputs)
 }
 
-fn collect_chain_outputs<'a, C, K>(
-	keychain: &K,
+fn collect_chain_outputs<'a, C>(
+	rewind: &dyn Fn(&pedersen::Commitment, &pedersen::RangeProof) -> Result<RewoundOutput, Error>,
 	client: C,
 	start_index: u64,
 	end_index: Option<u64>,
 	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
 ) -> Result<(Vec<OutputResult>, u64), Error>
 where
 	C: NodeClient + 'a,
-	K: Keychain + 'a,
 {
 	let batch_size = 1000;
 	let start_index_stat = start_index;
 	let mut start_index = start_index;
 	let mut result_vec: Vec<OutputResult> = vec![];
+	let mut current_height = None;
 	let last_retrieved_return_index;
 	loop {
 		let (highest_index, last_retrieved_index, outputs) =
@@ -167,9 +187,20 @@ where
 		if let Some(ref s) = status_send_channel {
 			let _ = s.send(StatusMessage::Scanning(msg, perc_complete));
 		}
+		publish_event(WalletEvent::ScanProgress {
+			percent: perc_complete,
+		});
+		if let Some(h) = outputs.iter().map(|o| o.3).max() {
+			current_height = Some(h);
+		}
+		if let Some(p) = progress {
+			if !p(perc_complete, current_height) {
+				return Err(ErrorKind::ScanCancelled.into());
+			}
+		}
 
 		result_vec.append(&mut identify_utxo_outputs(
-			keychain,
+			rewind,
 			outputs.clone(),
 			status_send_channel,
 			perc_complete as u8,
@@ -184,6 +215,54 @@ where
 	Ok((result_vec, last_retrieved_return_index))
 }
 
+/// Find the unspent outputs belonging to the wallet's seed between the given heights
+/// and compare them with the outputs known to the wallet, without changing the wallet
+pub fn preview<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	start_height: u64,
+	end_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<ScanPreview, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (client, keychain) = {
+		wallet_lock!(wallet_inst, w);
+		(w.w2n_client().clone(), w.keychain(keychain_mask)?.clone())
+	};
+
+	let pmmr_range = client.height_range_to_pmmr_indices(start_height, Some(end_height))?;
+	let (chain_outs, _) = collect_chain_outputs(
+		&keychain_rewinder(&keychain),
+		client,
+		pmmr_range.0,
+		Some(pmmr_range.1),
+		status_send_channel,
+		None,
+	)?;
+
+	let wallet_outputs = {
+		wallet_lock!(wallet_inst, w);
+		updater::retrieve_outputs(&mut **w, keychain_mask, true, false, None, None)?
+	};
+	let missing_outs: Vec<&OutputResult> = chain_outs
+		.iter()
+		.filter(|o| !wallet_outputs.iter().any(|wo| wo.commit == o.commit))
+		.collect();
+
+	Ok(ScanPreview {
+		start_height,
+		end_height,
+		num_outputs: chain_outs.len(),
+		total_value: chain_outs.iter().map(|o| o.value).sum(),
+		num_missing: missing_outs.len(),
+		missing_value: missing_outs.iter().map(|o| o.value).sum(),
+	})
+}
+
 ///
 fn restore_missing_output<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
@@ -259,6 +338,8 @@ where
 		lock_height: output.lock_height,
 		is_coinbase: output.is_coinbase,
 		tx_log_entry: Some(log_id),
+		frozen: false,
+		multisig: false,
 	});
 
 	let max_child_index = found_parents.get(&parent_key_id).unwrap().clone();
@@ -316,13 +397,23 @@ where
 /// Check / repair wallet contents by scanning against chain
 /// assume wallet contents have been freshly updated with contents
 /// of latest block
+///
+/// If given, `progress` is called after each batch of chain outputs checked with the
+/// percentage complete and the height of the last output checked. The scan stops with
+/// `ErrorKind::ScanCancelled` as soon as it returns `false`, which is only possible
+/// before the wallet is changed.
+///
+/// If `account` is given, only the outputs of the account with that parent key id are
+/// restored and repaired, the others found on the chain being left as they are.
 pub fn scan<'a, L, C, K>(
 	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
 	keychain_mask: Option<&SecretKey>,
 	delete_unconfirmed: bool,
 	start_height: u64,
 	end_height: u64,
+	account: Option<&Identifier>,
 	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
 ) -> Result<ScannedBlockInfo, Error>
 where
 	L: WalletLCProvider<'a, C, K>,
@@ -342,12 +433,18 @@ where
 	let pmmr_range = client.height_range_to_pmmr_indices(start_height, Some(end_height))?;
 
 	let (chain_outs, last_index) = collect_chain_outputs(
-		&keychain,
+		&keychain_rewinder(&keychain),
 		client,
 		pmmr_range.0,
 		Some(pmmr_range.1),
 		status_send_channel,
+		progress,
 	)?;
+	let in_account = |parent_key_id: &Identifier| account.map_or(true, |a| a == parent_key_id);
+	let chain_outs: Vec<OutputResult> = chain_outs
+		.into_iter()
+		.filter(|o| in_account(&o.key_id.parent_path()))
+		.collect();
 	let msg = format!(
 		"Identified {} wallet_outputs as belonging to this wallet",
 		chain_outs.len(),
@@ -362,7 +459,7 @@ where
 		wallet_lock!(wallet_inst, w);
 		updater::retrieve_outputs(&mut **w, keychain_mask, true, false, None, None)?
 	};
-	
+
 	let mut missing_outs = vec![];
 	let mut accidental_spend_outs = vec![];
 	let mut locked_outs = vec![];
@@ -447,6 +544,7 @@ where
 		let unconfirmed_outs: Vec<&OutputCommitMapping> = wallet_outputs
 			.iter()
 			.filter(|o| o.output.status == OutputStatus::Unconfirmed)
+			.filter(|o| in_account(&o.output.root_key_id))
 			.collect();
 		// Delete unconfirmed outputs
 		for m in unconfirmed_outs.into_iter() {
@@ -467,7 +565,194 @@ where
 		}
 	}
 
-	// restore labels, account paths and child derivation indices
+	restore_account_paths(
+		wallet_inst.clone(),
+		keychain_mask,
+		&found_parents,
+		status_send_channel,
+	)?;
+
+	// outputs restored under another account than the one already recording them
+	let dups = {
+		wallet_lock!(wallet_inst, w);
+		updater::dedup_outputs(&mut **w, keychain_mask)?
+	};
+	if let Some(ref s) = status_send_channel {
+		for d in dups.iter() {
+			let _ = s.send(StatusMessage::Scanning(updater::dedup_message(d), 99));
+		}
+	}
+
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::ScanningComplete(
+			"Scanning Complete".to_owned(),
+		));
+	}
+	publish_event(WalletEvent::ScanProgress { percent: 100 });
+
+	Ok(ScannedBlockInfo {
+		height: end_height,
+		hash: "".to_owned(),
+		start_pmmr_index: pmmr_range.0,
+		last_pmmr_index: last_index,
+	})
+}
+
+/// Scan of a watch-only wallet, finding the outputs of its account between the given
+/// heights with its view key and restoring those missing from the wallet. The account's
+/// unspent outputs the node doesn't have anymore are marked as spent, as there is no
+/// keychain to tell the wallet's own spends apart.
+pub fn scan_watch_only<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	view_key: &ViewKey,
+	start_height: u64,
+	end_height: u64,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+	progress: Option<&dyn Fn(u8, Option<u64>) -> bool>,
+) -> Result<ScannedBlockInfo, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::Scanning("Starting UTXO scan".to_owned(), 0));
+	}
+	let client = {
+		wallet_lock!(wallet_inst, w);
+		w.w2n_client().clone()
+	};
+
+	let pmmr_range = client.height_range_to_pmmr_indices(start_height, Some(end_height))?;
+	let secp = Secp256k1::with_caps(ContextFlag::Commit);
+	let rewind = |commit: &pedersen::Commitment, proof: &pedersen::RangeProof| {
+		let info: RewoundOutput = proof::rewind(&secp, view_key, *commit, None, *proof)?;
+		Ok(info)
+	};
+	let (chain_outs, last_index) = collect_chain_outputs(
+		&rewind,
+		client.clone(),
+		pmmr_range.0,
+		Some(pmmr_range.1),
+		status_send_channel,
+		progress,
+	)?;
+
+	wallet_lock!(wallet_inst, w);
+	let parent_key_id = view_key.parent_key_id.clone();
+	let wallet_outputs: Vec<OutputData> = w
+		.iter()
+		.filter(|o| o.root_key_id == parent_key_id)
+		.collect();
+	let unspent: Vec<(OutputData, pedersen::Commitment)> = wallet_outputs
+		.iter()
+		.filter(|o| o.status == OutputStatus::Unspent)
+		.filter_map(|o| match o.commit.as_ref().map(|c| from_hex(c.clone())) {
+			Some(Ok(c)) => Some((o.clone(), pedersen::Commitment::from_vec(c))),
+			_ => None,
+		})
+		.collect();
+	let on_chain = match unspent.is_empty() {
+		true => HashMap::new(),
+		false => client.get_outputs_from_node(unspent.iter().map(|o| o.1).collect())?,
+	};
+	let mut next_child = w.current_child_index(&parent_key_id)?;
+
+	let mut batch = w.batch_no_mask()?;
+	for (mut o, commit) in unspent.into_iter() {
+		if !on_chain.contains_key(&commit) {
+			let msg = format!(
+				"Output for {} with ID {} ({:?}) not in UTXO set anymore. Marking spent.",
+				o.value, o.key_id, commit,
+			);
+			if let Some(ref s) = status_send_channel {
+				let _ = s.send(StatusMessage::Scanning(msg, 99));
+			}
+			o.status = OutputStatus::Spent;
+			batch.save(o)?;
+		}
+	}
+	for m in chain_outs.into_iter() {
+		let commit = to_hex(m.commit.0.to_vec());
+		let known = wallet_outputs
+			.iter()
+			.find(|o| o.commit.as_ref() == Some(&commit));
+		if let Some(o) = known {
+			// confirmed against a short-lived fork, for example
+			if o.status == OutputStatus::Spent {
+				let mut o = o.clone();
+				o.status = OutputStatus::Unspent;
+				batch.save(o)?;
+			}
+			continue;
+		}
+		let msg = format!(
+			"Confirmed output for {} with ID {} ({:?}, index {}) exists in UTXO set but not in wallet. \
+			 Restoring.",
+			m.value, m.key_id, m.commit, m.mmr_index
+		);
+		if let Some(ref s) = status_send_channel {
+			let _ = s.send(StatusMessage::Scanning(msg, 99));
+		}
+		let log_id = batch.next_tx_log_id(&parent_key_id)?;
+		let entry_type = match m.is_coinbase {
+			true => TxLogEntryType::ConfirmedCoinbase,
+			false => TxLogEntryType::TxReceived,
+		};
+		let mut t = TxLogEntry::new(parent_key_id.clone(), entry_type, log_id);
+		t.confirmed = true;
+		t.amount_credited = m.value;
+		t.num_outputs = 1;
+		t.update_confirmation_ts();
+		batch.save_tx_log_entry(t, &parent_key_id)?;
+		next_child = cmp::max(next_child, m.n_child + 1);
+		batch.save(OutputData {
+			root_key_id: parent_key_id.clone(),
+			key_id: m.key_id,
+			n_child: m.n_child,
+			mmr_index: Some(m.mmr_index),
+			commit: Some(commit),
+			value: m.value,
+			status: OutputStatus::Unspent,
+			height: m.height,
+			lock_height: m.lock_height,
+			is_coinbase: m.is_coinbase,
+			tx_log_entry: Some(log_id),
+			frozen: false,
+			multisig: false,
+		})?;
+	}
+	batch.save_child_index(&parent_key_id, next_child)?;
+	batch.commit()?;
+
+	if let Some(ref s) = status_send_channel {
+		let _ = s.send(StatusMessage::ScanningComplete(
+			"Scanning Complete".to_owned(),
+		));
+	}
+	publish_event(WalletEvent::ScanProgress { percent: 100 });
+
+	Ok(ScannedBlockInfo {
+		height: end_height,
+		hash: "".to_owned(),
+		start_pmmr_index: pmmr_range.0,
+		last_pmmr_index: last_index,
+	})
+}
+
+/// Restore labels, account paths and child derivation indices of the parents
+/// of restored outputs
+fn restore_account_paths<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	found_parents: &HashMap<Identifier, u32>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<(), Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
 	wallet_lock!(wallet_inst, w);
 	let label_base = "account";
 	let accounts: Vec<Identifier> = w.acct_path_iter().map(|m| m.path).collect();
@@ -491,17 +776,112 @@ where
 			batch.commit()?;
 		}
 	}
+	Ok(())
+}
 
-	if let Some(ref s) = status_send_channel {
-		let _ = s.send(StatusMessage::ScanningComplete(
-			"Scanning Complete".to_owned(),
-		));
+/// Import outputs exported by another wallet along with their key derivation.
+/// Each output's commitment is derived from the wallet's seed and looked up in the
+/// node's UTXO set, so outputs are restored, with a transaction log entry each,
+/// without scanning the chain. Outputs the node doesn't know as unspent, or that
+/// the wallet already holds, are skipped.
+pub fn import_outputs<'a, L, C, K>(
+	wallet_inst: Arc<Mutex<Box<dyn WalletInst<'a, L, C, K>>>>,
+	keychain_mask: Option<&SecretKey>,
+	outputs: Vec<ImportedOutput>,
+	status_send_channel: &Option<Sender<StatusMessage>>,
+) -> Result<OutputImportSummary, Error>
+where
+	L: WalletLCProvider<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (client, keychain) = {
+		wallet_lock!(wallet_inst, w);
+		(w.w2n_client().clone(), w.keychain(keychain_mask)?.clone())
+	};
+	let mut summary = OutputImportSummary::default();
+
+	let mut seen = HashSet::new();
+	let mut candidates = vec![];
+	for o in outputs {
+		let commit = keychain.commit(o.value, &o.key_id, &SwitchCommitmentType::Regular)?;
+		if let Some(c) = o.commit {
+			if c != commit {
+				let msg = format!(
+					"Output {:?} doesn't derive from key {} for {}, skipping.",
+					c, o.key_id, o.value
+				);
+				if let Some(ref s) = status_send_channel {
+					let _ = s.send(StatusMessage::UpdateWarning(msg));
+				}
+				summary.num_mismatched += 1;
+				continue;
+			}
+		}
+		if seen.insert(commit) {
+			candidates.push((commit, o));
+		}
+	}
+	if candidates.is_empty() {
+		return Ok(summary);
 	}
 
-	Ok(ScannedBlockInfo {
-		height: end_height,
-		hash: "".to_owned(),
-		start_pmmr_index: pmmr_range.0,
-		last_pmmr_index: last_index,
-	})
+	let api_outputs = client.get_outputs_from_node(candidates.iter().map(|c| c.0).collect())?;
+	let wallet_outputs = {
+		wallet_lock!(wallet_inst, w);
+		updater::retrieve_outputs(&mut **w, keychain_mask, true, false, None, None)?
+	};
+
+	let mut found_parents: HashMap<Identifier, u32> = HashMap::new();
+	for (commit, o) in candidates {
+		let (height, mmr_index) = match api_outputs.get(&commit) {
+			Some(a) => (a.1, a.2),
+			None => {
+				summary.num_not_found += 1;
+				continue;
+			}
+		};
+		if wallet_outputs.iter().any(|wo| wo.commit == commit) {
+			summary.num_known += 1;
+			continue;
+		}
+		let lock_height = if o.is_coinbase {
+			height + chain_params::coinbase_maturity()
+		} else {
+			height
+		};
+		let msg = format!(
+			"Importing output for {} with ID {} ({:?}, index {})",
+			o.value, o.key_id, commit, mmr_index
+		);
+		if let Some(ref s) = status_send_channel {
+			let _ = s.send(StatusMessage::Scanning(msg, 99));
+		}
+		restore_missing_output(
+			wallet_inst.clone(),
+			keychain_mask,
+			OutputResult {
+				commit,
+				n_child: o.key_id.to_path().last_path_index(),
+				key_id: o.key_id,
+				mmr_index,
+				value: o.value,
+				height,
+				lock_height,
+				is_coinbase: o.is_coinbase,
+			},
+			&mut found_parents,
+			&mut None,
+		)?;
+		summary.num_imported += 1;
+		summary.imported_value += o.value;
+	}
+
+	restore_account_paths(
+		wallet_inst,
+		keychain_mask,
+		&found_parents,
+		status_send_channel,
+	)?;
+	Ok(summary)
 }
@@ -15,6 +15,7 @@
 //! Selection of inputs for building transactions
 
 use crate::address;
+use crate::epic_core::consensus;
 use crate::epic_core::core::amount_to_hr_string;
 use crate::epic_core::libtx::{
 	build,
@@ -23,6 +24,8 @@ use crate::epic_core::libtx::{
 };
 use crate::epic_keychain::{Identifier, Keychain};
 use crate::epic_util::secp::key::SecretKey;
+use crate::epic_util::secp::pedersen::Commitment;
+use crate::epic_util::{from_hex, to_hex};
 use crate::error::{Error, ErrorKind};
 use crate::internal::keys;
 use crate::slate::Slate;
@@ -42,7 +45,9 @@ pub fn build_send_tx<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	change_outputs: usize,
+	decoy_amount: Option<u64>,
 	selection_strategy_is_use_all: bool,
+	inputs: Option<&[Commitment]>,
 	parent_key_id: Identifier,
 	use_test_nonce: bool,
 ) -> Result<Context, Error>
@@ -59,7 +64,9 @@ where
 		minimum_confirmations,
 		max_outputs,
 		change_outputs,
+		decoy_amount,
 		selection_strategy_is_use_all,
+		inputs,
 		&parent_key_id,
 	)?;
 
@@ -78,6 +85,10 @@ where
 	);
 
 	context.fee = fee;
+	// the decoy is the extra change output beyond those requested, when built
+	if change_amounts_derivations.len() > change_outputs {
+		context.decoy_amount = decoy_amount;
+	}
 
 	// Store our private identifiers for each input
 	for input in inputs {
@@ -151,16 +162,36 @@ where
 		t.kernel_lookup_min_height = Some(slate.height);
 
 		let mut amount_debited = 0;
+		let mut coins = vec![];
 		t.num_inputs = lock_inputs.len();
 		for id in lock_inputs {
 			let mut coin = batch.get(&id.0, &id.1).unwrap();
 			coin.tx_log_entry = Some(log_id);
 			amount_debited = amount_debited + coin.value;
 			batch.lock_output(&mut coin)?;
+			coins.push(coin);
+		}
+		let composition = InputComposition::from_outputs(&coins);
+		if composition.is_mixed() {
+			info!(
+				"Send {} spends {} coinbase and {} plain outputs",
+				slate_id, composition.coinbase_inputs, composition.plain_inputs
+			);
 		}
+		t.input_composition = Some(composition);
+		t.kernel_features = Some(TxKernelFeatures::from_lock_height(slate.lock_height));
 
 		t.amount_debited = amount_debited;
 		t.messages = messages;
+		t.decoy_amount = context.decoy_amount;
+		t.approval = context.approval;
+		// the other party adds one output to the transaction
+		t.tx_size = Some(TxSizeInfo::estimate(
+			t.num_inputs,
+			context.get_outputs().len() + 1,
+			slate.tx.body.kernels.len(),
+		));
+		t.slate_version = Some(slate.version_info.orig_version);
 
 		// store extra payment proof info, if required
 		if let Some(ref p) = slate.payment_proof {
@@ -184,6 +215,10 @@ where
 				sender_address,
 				sender_address_path,
 				sender_signature: None,
+				state: match p.receiver_signature {
+					Some(_) => Some(PaymentProofState::Promised),
+					None => Some(PaymentProofState::Requested),
+				},
 			});
 		};
 
@@ -204,6 +239,8 @@ where
 				lock_height: 0,
 				is_coinbase: false,
 				tx_log_entry: Some(log_id),
+				frozen: false,
+				multisig: false,
 			})?;
 		}
 		batch.save_tx_log_entry(t.clone(), &parent_key_id)?;
@@ -271,6 +308,8 @@ where
 		Err(_) => {}
 	}
 	t.kernel_lookup_min_height = Some(slate.height);
+	t.slate_version = Some(slate.version_info.orig_version);
+	t.kernel_features = Some(TxKernelFeatures::from_lock_height(slate.lock_height));
 	batch.save(OutputData {
 		root_key_id: parent_key_id.clone(),
 		key_id: key_id_inner.clone(),
@@ -283,6 +322,8 @@ where
 		lock_height: 0,
 		is_coinbase: false,
 		tx_log_entry: Some(log_id),
+		frozen: false,
+		multisig: false,
 	})?;
 	batch.save_tx_log_entry(t, &parent_key_id)?;
 	batch.commit()?;
@@ -301,7 +342,9 @@ pub fn select_send_tx<'a, T: ?Sized, C, K, B>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	change_outputs: usize,
+	decoy_amount: Option<u64>,
 	selection_strategy_is_use_all: bool,
+	inputs: Option<&[Commitment]>,
 	parent_key_id: &Identifier,
 ) -> Result<
 	(
@@ -318,25 +361,37 @@ where
 	K: Keychain + 'a,
 	B: ProofBuild,
 {
+	// the fee accounts for the decoy output, if any
 	let (coins, _total, amount, fee) = select_coins_and_fee(
 		wallet,
 		amount,
 		current_height,
 		minimum_confirmations,
 		max_outputs,
-		change_outputs,
+		change_outputs + decoy_amount.map_or(0, |_| 1),
 		selection_strategy_is_use_all,
+		inputs,
+		None,
 		&parent_key_id,
 	)?;
 
 	// build transaction skeleton with inputs and change
-	let (parts, change_amounts_derivations) =
-		inputs_and_change(&coins, wallet, keychain_mask, amount, fee, change_outputs)?;
+	let (parts, change_amounts_derivations) = inputs_and_change(
+		&coins,
+		wallet,
+		keychain_mask,
+		amount,
+		fee,
+		change_outputs,
+		decoy_amount,
+	)?;
 
 	Ok((parts, coins, change_amounts_derivations, fee))
 }
 
-/// Select outputs and calculating fee.
+/// Select outputs and calculating fee. If `inputs` is given, exactly those outputs
+/// are spent instead. If `coinbase` is given, only coinbase outputs, or only plain
+/// ones, are selected.
 pub fn select_coins_and_fee<'a, T: ?Sized, C, K>(
 	wallet: &mut T,
 	amount: u64,
@@ -345,6 +400,8 @@ pub fn select_coins_and_fee<'a, T: ?Sized, C, K>(
 	max_outputs: usize,
 	change_outputs: usize,
 	selection_strategy_is_use_all: bool,
+	inputs: Option<&[Commitment]>,
+	coinbase: Option<bool>,
 	parent_key_id: &Identifier,
 ) -> Result<
 	(
@@ -360,16 +417,30 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	// select some spendable coins from the wallet
-	let (max_outputs, mut coins) = select_coins(
-		wallet,
-		amount,
-		current_height,
-		minimum_confirmations,
-		max_outputs,
-		selection_strategy_is_use_all,
-		parent_key_id,
-	);
+	// select some spendable coins from the wallet, unless they're given, in which
+	// case no others can be added
+	let (max_outputs, mut coins) = match inputs {
+		Some(commits) => {
+			let coins = select_given_coins(
+				wallet,
+				commits,
+				current_height,
+				minimum_confirmations,
+				parent_key_id,
+			)?;
+			(coins.len(), coins)
+		}
+		None => select_coins(
+			wallet,
+			amount,
+			current_height,
+			minimum_confirmations,
+			max_outputs,
+			selection_strategy_is_use_all,
+			coinbase,
+			parent_key_id,
+		),
+	};
 
 	// sender is responsible for setting the fee on the partial tx
 	// recipient should double check the fee calculation and not blindly trust the
@@ -430,6 +501,7 @@ where
 				minimum_confirmations,
 				max_outputs,
 				selection_strategy_is_use_all,
+				coinbase,
 				parent_key_id,
 			)
 			.1;
@@ -441,7 +513,149 @@ where
 	Ok((coins, total, amount, fee))
 }
 
-/// Selects inputs and change for a transaction
+/// Inputs of a send spending either coinbase or plain outputs but not both, plain
+/// outputs being preferred. Given inputs are checked not to mix them.
+pub fn select_unmixed_inputs<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	amount: u64,
+	current_height: u64,
+	minimum_confirmations: u64,
+	max_outputs: usize,
+	change_outputs: usize,
+	selection_strategy_is_use_all: bool,
+	inputs: Option<&[Commitment]>,
+	parent_key_id: &Identifier,
+) -> Result<Vec<Commitment>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if let Some(commits) = inputs {
+		let coins = select_given_coins(
+			wallet,
+			commits,
+			current_height,
+			minimum_confirmations,
+			parent_key_id,
+		)?;
+		if InputComposition::from_outputs(&coins).is_mixed() {
+			let msg = "the given inputs include both".to_owned();
+			return Err(ErrorKind::MixedCoinbaseInputs(msg).into());
+		}
+		return Ok(commits.to_vec());
+	}
+	for coinbase in [false, true].iter() {
+		let res = select_coins_and_fee(
+			wallet,
+			amount,
+			current_height,
+			minimum_confirmations,
+			max_outputs,
+			change_outputs,
+			selection_strategy_is_use_all,
+			None,
+			Some(*coinbase),
+			parent_key_id,
+		);
+		let coins = match res {
+			Ok((coins, _, _, _)) => coins,
+			Err(e) => match e.kind() {
+				ErrorKind::NotEnoughFunds { .. } => continue,
+				_ => return Err(e),
+			},
+		};
+		return output_commitments(&coins);
+	}
+	let msg = "neither the coinbase nor the plain outputs cover the amount".to_owned();
+	Err(ErrorKind::MixedCoinbaseInputs(msg).into())
+}
+
+/// Inputs of a send adding, to those covering it, the smallest further spendable
+/// outputs smaller than the amount, so they're consolidated into the change. Outputs
+/// are added while each raises the fee by at most `max_fee_increase`, and is worth
+/// more than what it adds to the fee, up to `max_outputs` inputs. If `inputs` is
+/// given the send spends them, and only outputs of the same kind, coinbase or plain,
+/// as all of them are added.
+pub fn select_consolidating_inputs<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	amount: u64,
+	current_height: u64,
+	minimum_confirmations: u64,
+	max_outputs: usize,
+	change_outputs: usize,
+	selection_strategy_is_use_all: bool,
+	inputs: Option<&[Commitment]>,
+	max_fee_increase: u64,
+	parent_key_id: &Identifier,
+) -> Result<Vec<Commitment>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (mut coins, _, _, mut fee) = select_coins_and_fee(
+		wallet,
+		amount,
+		current_height,
+		minimum_confirmations,
+		max_outputs,
+		change_outputs,
+		selection_strategy_is_use_all,
+		inputs,
+		None,
+		parent_key_id,
+	)?;
+	let coinbase = match (inputs, InputComposition::from_outputs(&coins)) {
+		(Some(_), c) if c.plain_inputs == 0 => Some(true),
+		(Some(_), c) if c.coinbase_inputs == 0 => Some(false),
+		_ => None,
+	};
+	let mut candidates = wallet
+		.iter()
+		.filter(|out| {
+			out.root_key_id == *parent_key_id
+				&& out.value < amount
+				&& out.eligible_to_spend(current_height, minimum_confirmations)
+				&& coinbase.map_or(true, |c| out.is_coinbase == c)
+				&& !coins
+					.iter()
+					.any(|c| c.key_id == out.key_id && c.mmr_index == out.mmr_index)
+		})
+		.collect::<Vec<OutputData>>();
+	candidates.sort_by_key(|out| out.value);
+
+	let num_outputs = change_outputs + 1;
+	let mut consolidated = 0;
+	for out in candidates {
+		if coins.len() >= max_outputs {
+			break;
+		}
+		// the first output added may bring in the change output too
+		let new_fee = tx_fee(coins.len() + 1, num_outputs, 1, None);
+		let increase = new_fee.saturating_sub(fee);
+		if increase > max_fee_increase {
+			break;
+		}
+		if out.value <= increase {
+			continue;
+		}
+		coins.push(out);
+		fee = new_fee;
+		consolidated += 1;
+	}
+	if consolidated > 0 {
+		debug!(
+			"Consolidating {} more outputs into the change, for a fee of {}",
+			consolidated, fee
+		);
+	}
+	output_commitments(&coins)
+}
+
+/// Selects inputs and change for a transaction. If a decoy amount is given and the
+/// change covers it, an extra change output of that value is split off the change,
+/// so the number of outputs doesn't tell which one is the payment.
 pub fn inputs_and_change<'a, T: ?Sized, C, K, B>(
 	coins: &Vec<OutputData>,
 	wallet: &mut T,
@@ -449,6 +663,7 @@ pub fn inputs_and_change<'a, T: ?Sized, C, K, B>(
 	amount: u64,
 	fee: u64,
 	num_change_outputs: usize,
+	decoy_amount: Option<u64>,
 ) -> Result<
 	(
 		Vec<Box<build::Append<K, B>>>,
@@ -470,7 +685,7 @@ where
 	// if we are spending 10,000 coins to send 1,000 then our change will be 9,000
 	// if the fee is 80 then the recipient will receive 1000 and our change will be
 	// 8,920
-	let change = total - amount - fee;
+	let mut change = total - amount - fee;
 
 	// build inputs using the appropriate derived key_ids
 	for coin in coins {
@@ -483,6 +698,18 @@ where
 
 	let mut change_amounts_derivations = vec![];
 
+	match decoy_amount {
+		Some(d) if d > 0 && change > d => {
+			debug!("Building decoy output of {}", d);
+			let decoy_key = wallet.next_child(keychain_mask).unwrap();
+			change_amounts_derivations.push((d, decoy_key.clone(), None));
+			parts.push(build::output(d, decoy_key));
+			change -= d;
+		}
+		Some(d) => debug!("Change of {} doesn't cover decoy output of {}", change, d),
+		None => {}
+	}
+
 	if change == 0 {
 		debug!("No change (sending exactly amount + fee), no change outputs to build");
 	} else {
@@ -512,6 +739,196 @@ where
 	Ok((parts, change_amounts_derivations))
 }
 
+/// Weight of a transaction with the given number of inputs, outputs and
+/// kernels, as counted against the block weight limit
+pub fn tx_weight(num_inputs: usize, num_outputs: usize, num_kernels: usize) -> u64 {
+	(num_inputs * consensus::BLOCK_INPUT_WEIGHT
+		+ num_outputs * consensus::BLOCK_OUTPUT_WEIGHT
+		+ num_kernels * consensus::BLOCK_KERNEL_WEIGHT) as u64
+}
+
+/// Maximum number of inputs a single kernel transaction with `num_outputs`
+/// outputs can spend without exceeding `max_weight`
+pub fn max_inputs_for_weight(max_weight: u64, num_outputs: usize) -> usize {
+	let fixed = tx_weight(0, num_outputs, 1);
+	if max_weight <= fixed {
+		return 0;
+	}
+	((max_weight - fixed) / consensus::BLOCK_INPUT_WEIGHT as u64) as usize
+}
+
+/// Splits `amount` into the amounts of sequential transactions that each stay
+/// within `max_weight`, along with the commitments of the outputs each one spends.
+/// Each transaction spends at most the largest remaining eligible outputs that fit,
+/// so the number of transactions is kept minimal.
+pub fn split_send_amounts<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	amount: u64,
+	current_height: u64,
+	minimum_confirmations: u64,
+	num_change_outputs: usize,
+	max_weight: u64,
+	parent_key_id: &Identifier,
+) -> Result<Vec<(u64, Vec<Commitment>)>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	// the recipient adds one output of their own
+	let num_outputs = num_change_outputs + 1;
+	let max_inputs = max_inputs_for_weight(max_weight, num_outputs);
+	if max_inputs == 0 {
+		return Err(ErrorKind::GenericError(format!(
+			"Maximum transaction weight {} is too low for any transaction",
+			max_weight
+		)))?;
+	}
+
+	let mut eligible = wallet
+		.iter()
+		.filter(|out| {
+			out.root_key_id == *parent_key_id
+				&& out.eligible_to_spend(current_height, minimum_confirmations)
+		})
+		.collect::<Vec<OutputData>>();
+	eligible.sort_by(|a, b| b.value.cmp(&a.value));
+
+	let mut parts = vec![];
+	let mut remaining = amount;
+	let mut available = 0;
+	for chunk in eligible.chunks(max_inputs) {
+		if remaining == 0 {
+			break;
+		}
+		let total: u64 = chunk.iter().map(|c| c.value).sum();
+		let capacity = total.saturating_sub(tx_fee(chunk.len(), num_outputs, 1, None));
+		if capacity == 0 {
+			continue;
+		}
+		available += capacity;
+		let part = std::cmp::min(remaining, capacity);
+		parts.push((part, output_commitments(chunk)?));
+		remaining -= part;
+	}
+
+	if remaining > 0 {
+		return Err(ErrorKind::NotEnoughFunds {
+			available,
+			available_disp: amount_to_hr_string(available, false),
+			needed: amount,
+			needed_disp: amount_to_hr_string(amount, false),
+		})?;
+	}
+	Ok(parts)
+}
+
+/// Commitments of the given outputs, to spend them as inputs
+pub fn output_commitments(coins: &[OutputData]) -> Result<Vec<Commitment>, Error> {
+	coins
+		.iter()
+		.map(|c| match c.commit.clone().map(from_hex) {
+			Some(Ok(bytes)) => Ok(Commitment::from_vec(bytes)),
+			_ => {
+				let msg = format!("no commitment stored for output {}", c.key_id);
+				Err(ErrorKind::GenericError(msg).into())
+			}
+		})
+		.collect()
+}
+
+/// Look up the outputs of the given commitments, to be spent as inputs. Each must
+/// be a spendable output of the account, given once.
+pub fn select_given_coins<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	commits: &[Commitment],
+	current_height: u64,
+	minimum_confirmations: u64,
+	parent_key_id: &Identifier,
+) -> Result<Vec<OutputData>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut outputs: HashMap<String, OutputData> = wallet
+		.iter()
+		.filter_map(|out| out.commit.clone().map(|c| (c, out)))
+		.collect();
+	let mut coins: Vec<OutputData> = vec![];
+	for commit in commits {
+		let commit = to_hex(commit.0.to_vec());
+		let reason = match outputs.remove(&commit) {
+			None if coins.iter().any(|c| c.commit.as_ref() == Some(&commit)) => {
+				"it's given more than once"
+			}
+			None => "it doesn't belong to the wallet",
+			Some(out) if out.root_key_id != *parent_key_id => "it belongs to another account",
+			Some(out) if out.frozen && out.status != OutputStatus::Spent => "it's frozen",
+			Some(out) if !out.eligible_to_spend(current_height, minimum_confirmations) => {
+				match out.status {
+					OutputStatus::Spent => "it's spent already",
+					OutputStatus::Locked => "it's locked by another transaction",
+					_ => "it isn't confirmed or mature yet",
+				}
+			}
+			Some(out) => {
+				coins.push(out);
+				continue;
+			}
+		};
+		return Err(ErrorKind::UnspendableInput(commit, reason.to_owned()).into());
+	}
+	Ok(coins)
+}
+
+/// Select the inputs of one of the sends of a batch from the outputs shared by the
+/// batch, sorted by increasing value. The outputs selected are removed from them, so
+/// no other send of the batch spends them.
+pub fn select_batch_inputs(
+	outputs: &mut Vec<OutputData>,
+	amount: u64,
+	current_height: u64,
+	minimum_confirmations: u64,
+	max_outputs: usize,
+	change_outputs: usize,
+	selection_strategy_is_use_all: bool,
+	coinbase: Option<bool>,
+	parent_key_id: &Identifier,
+) -> Result<Vec<OutputData>, Error> {
+	let num_outputs = change_outputs + 1;
+	let mut selected = vec![];
+	let mut total = 0;
+	for (i, out) in outputs.iter().enumerate() {
+		if out.root_key_id != *parent_key_id
+			|| !out.eligible_to_spend(current_height, minimum_confirmations)
+			|| coinbase.map_or(false, |c| out.is_coinbase != c)
+		{
+			continue;
+		}
+		let covered = total >= amount + tx_fee(selected.len(), num_outputs, 1, None);
+		if selected.len() == max_outputs || (covered && !selection_strategy_is_use_all) {
+			break;
+		}
+		selected.push(i);
+		total += out.value;
+	}
+	let amount_with_fee = amount + tx_fee(selected.len(), num_outputs, 1, None);
+	if total < amount_with_fee {
+		return Err(ErrorKind::NotEnoughFunds {
+			available: total,
+			available_disp: amount_to_hr_string(total, false),
+			needed: amount_with_fee,
+			needed_disp: amount_to_hr_string(amount_with_fee, false),
+		})?;
+	}
+	Ok(selected
+		.into_iter()
+		.rev()
+		.map(|i| outputs.remove(i))
+		.collect())
+}
+
 /// Select spendable coins from a wallet.
 /// Default strategy is to spend the maximum number of outputs (up to
 /// max_outputs). Alternative strategy is to spend smallest outputs first
@@ -526,6 +943,7 @@ pub fn select_coins<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	select_all: bool,
+	coinbase: Option<bool>,
 	parent_key_id: &Identifier,
 ) -> (usize, Vec<OutputData>)
 //    max_outputs_available, Outputs
@@ -534,12 +952,14 @@ where
 	C: NodeClient + 'a,
 	K: Keychain + 'a,
 {
-	// first find all eligible outputs based on number of confirmations
+	// first find all eligible outputs based on number of confirmations, and on
+	// their features if only coinbase or plain outputs are to be spent
 	let mut eligible = wallet
 		.iter()
 		.filter(|out| {
 			out.root_key_id == *parent_key_id
 				&& out.eligible_to_spend(current_height, minimum_confirmations)
+				&& coinbase.map_or(true, |c| out.is_coinbase == c)
 		})
 		.collect::<Vec<OutputData>>();
 
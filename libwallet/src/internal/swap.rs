@@ -0,0 +1,1044 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Atomic swaps of epics for bitcoins or litecoins, neither party trusting the other.
+//!
+//! The seller locks the epics in an output shared with the buyer, once the buyer signed
+//! the seller's refund of the output, only valid from a height. The buyer then locks the
+//! secondary currency in a script the seller redeems with its own key and a secret of the
+//! buyer's, or the buyer refunds once a lock time passed, long after the epic refund.
+//!
+//! The secret is revealed by the transaction redeeming the locked epics to the buyer: the
+//! buyer gives the seller its partial signature of the transaction plus the secret, an
+//! adaptor signature, and the seller signs the transaction for the buyer to post. The
+//! seller then takes the secret out of the posted signature. Each step stores the swap,
+//! the parties exchanging one message per step.
+
+use crate::address;
+use crate::blake2::blake2b::blake2b;
+use crate::epic_core::consensus;
+use crate::epic_core::core::amount_to_hr_string;
+use crate::epic_core::core::transaction::{Input, OutputFeatures, Transaction};
+use crate::epic_core::libtx::{build, proof::ProofBuilder, tx_fee};
+use crate::epic_core::ser;
+use crate::epic_keychain::{BlindSum, BlindingFactor, Identifier, Keychain, SwitchCommitmentType};
+use crate::epic_util::secp::key::{PublicKey, SecretKey};
+use crate::epic_util::secp::pedersen::Commitment;
+use crate::epic_util::secp::{Secp256k1, Signature};
+use crate::epic_util::{from_hex, to_hex};
+use crate::internal::{keys, multisig, selection, tx, updater};
+use crate::slate::Slate;
+use crate::types::{
+	Context, NodeClient, OutputData, OutputStatus, Swap, SwapRole, SwapStatus, TxKernelFeatures,
+	TxLogEntry, TxLogEntryType, WalletBackend,
+};
+use crate::{Error, ErrorKind, SwapMessage, SwapMessageBody, SwapOffer, SwapStartArgs, SwapStep};
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+/// Index of the address the keys of the secondary lock scripts are derived from
+const SWAP_KEY_INDEX: u32 = 0x7377_6170;
+
+/// Keys of the secondary lock script, derived for each swap
+const REDEEM_KEY: u8 = 0;
+const REFUND_KEY: u8 = 1;
+const SECRET_KEY: u8 = 2;
+
+/// Script opcodes of the secondary lock script
+const OP_IF: u8 = 0x63;
+const OP_ELSE: u8 = 0x67;
+const OP_ENDIF: u8 = 0x68;
+const OP_DROP: u8 = 0x75;
+const OP_2: u8 = 0x52;
+const OP_CHECKSIG: u8 = 0xac;
+const OP_CHECKMULTISIG: u8 = 0xae;
+const OP_CHECKLOCKTIMEVERIFY: u8 = 0xb1;
+
+fn swap_error(msg: &str) -> Error {
+	ErrorKind::Swap(msg.to_owned()).into()
+}
+
+/// Fee of the refund and redeem transactions, spending the locked output to one output
+fn spend_fee() -> u64 {
+	tx_fee(1, 1, 1, None)
+}
+
+/// Our key of the secondary lock script of a swap, or the buyer's secret
+fn secondary_key<K>(
+	keychain: &K,
+	parent_key_id: &Identifier,
+	id: &Uuid,
+	key: u8,
+) -> Result<SecretKey, Error>
+where
+	K: Keychain,
+{
+	let base = address::address_from_derivation_path(keychain, parent_key_id, SWAP_KEY_INDEX)?;
+	let mut data = id.as_bytes().to_vec();
+	data.push(key);
+	let hash = blake2b(32, &base.0, &data);
+	Ok(SecretKey::from_slice(keychain.secp(), hash.as_bytes())?)
+}
+
+/// Pushes data onto a script
+fn push_data(script: &mut Vec<u8>, data: &[u8]) {
+	script.push(data.len() as u8);
+	script.extend_from_slice(data);
+}
+
+/// Script the secondary currency is locked with: redeemable by the seller's redeem key
+/// along with the secret, or by the buyer's refund key once the lock time passed
+pub fn secondary_lock_script(
+	secp: &Secp256k1,
+	lock_time: i64,
+	redeem_public: &PublicKey,
+	secret_public: &PublicKey,
+	refund_public: &PublicKey,
+) -> String {
+	// the lock time as a minimally encoded script number
+	let mut lock_time_bytes = vec![];
+	let mut n = lock_time as u64;
+	while n > 0 {
+		lock_time_bytes.push((n & 0xff) as u8);
+		n >>= 8;
+	}
+	if lock_time_bytes.last().map_or(false, |b| b & 0x80 != 0) {
+		lock_time_bytes.push(0);
+	}
+
+	let mut script = vec![OP_IF];
+	push_data(&mut script, &lock_time_bytes);
+	script.extend_from_slice(&[OP_CHECKLOCKTIMEVERIFY, OP_DROP]);
+	push_data(&mut script, &refund_public.serialize_vec(secp, true));
+	script.extend_from_slice(&[OP_CHECKSIG, OP_ELSE, OP_2]);
+	push_data(&mut script, &redeem_public.serialize_vec(secp, true));
+	push_data(&mut script, &secret_public.serialize_vec(secp, true));
+	script.extend_from_slice(&[OP_2, OP_CHECKMULTISIG, OP_ENDIF]);
+	to_hex(script)
+}
+
+/// The scalar of a signature
+fn sig_scalar(secp: &Secp256k1, sig: &Signature) -> Result<SecretKey, Error> {
+	Ok(SecretKey::from_slice(secp, &sig.to_raw_data()[32..])?)
+}
+
+/// The output the epics of a swap are locked in, as stored
+fn locked_output<'a, T: ?Sized, C, K>(wallet: &mut T, swap: &Swap) -> Result<OutputData, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let commit = match swap.lock_commit {
+		Some(c) => to_hex(c.0.to_vec()),
+		None => return Err(swap_error("the swap's epics aren't locked")),
+	};
+	wallet
+		.iter()
+		.find(|o| o.multisig && o.commit.as_ref() == Some(&commit))
+		.ok_or_else(|| swap_error(&format!("no share of locked output {}", commit)))
+}
+
+/// The redeem transaction of a swap, once started
+fn redeem_slate_of(swap: &Swap) -> Result<Slate, Error> {
+	swap.redeem_slate
+		.clone()
+		.ok_or_else(|| swap_error("the swap's redeem transaction wasn't started"))
+}
+
+fn save_swap<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	message: Option<SwapMessageBody>,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	swap.updated_ts = Utc::now();
+	let mut batch = wallet.batch(keychain_mask)?;
+	batch.save_swap(swap.clone())?;
+	batch.commit()?;
+	Ok(SwapStep {
+		message: message.map(|body| SwapMessage { id: swap.id, body }),
+		swap,
+	})
+}
+
+/// Checks the terms of a swap to start, before the epics are locked
+pub fn check_start_args(args: &SwapStartArgs) -> Result<(), Error> {
+	if args.amount <= spend_fee() {
+		return Err(swap_error(&format!(
+			"the swapped amount must be more than the fee of {} of its redeem transaction",
+			amount_to_hr_string(spend_fee(), false)
+		)));
+	}
+	if args.secondary_amount == 0 {
+		return Err(swap_error("the secondary amount can't be 0"));
+	}
+	if args.refund_blocks == 0 {
+		return Err(swap_error("the refund can't be valid right away"));
+	}
+	if args.secondary_lock_hours * 3600 < 2 * args.refund_blocks * consensus::BLOCK_TIME_SEC {
+		return Err(swap_error(
+			"the secondary lock must last at least twice as long as the refund blocks",
+		));
+	}
+	Ok(())
+}
+
+/// Offers a swap of the epics the lock slate sends to a shared output, as the seller:
+/// starts the refund of the shared output to us and stores the swap
+pub fn offer<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	lock_slate: Slate,
+	args: &SwapStartArgs,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let lock_context = wallet.get_private_context(keychain_mask, lock_slate.id.as_bytes(), 0)?;
+	let share_key_id = match lock_context.multisig_key_id {
+		Some(ref k) => k.clone(),
+		None => return Err(swap_error("the lock transaction has no shared output")),
+	};
+	let parent_key_id = lock_context.parent_key_id.clone();
+	let refund_height = lock_slate.height + args.refund_blocks;
+
+	let fee = spend_fee();
+	let mut refund_slate =
+		tx::new_tx_slate(&mut *wallet, args.amount - fee, 2, use_test_rng, None)?;
+	refund_slate.fee = fee;
+	refund_slate.lock_height = refund_height;
+	let key_id = keys::next_available_key(&mut *wallet, keychain_mask)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let blind = refund_slate.add_transaction_elements(
+		&keychain,
+		&ProofBuilder::new(&keychain),
+		vec![build::output(refund_slate.amount, key_id.clone())],
+	)?;
+	// our excess is our output less our share of the locked output
+	let share = multisig::share_blind(&keychain, &share_key_id)?;
+	let sec_key = keychain.blind_sum(
+		&BlindSum::new()
+			.add_blinding_factor(blind)
+			.sub_blinding_factor(BlindingFactor::from_secret_key(share)),
+	)?;
+	let mut refund_context = Context::new(
+		secp,
+		sec_key.secret_key(secp)?,
+		&parent_key_id,
+		use_test_rng,
+		0,
+	);
+	refund_context.add_output(&key_id, &None, refund_slate.amount);
+	refund_slate.fill_round_1(
+		&keychain,
+		&mut refund_context.sec_key,
+		&refund_context.sec_nonce,
+		0,
+		None,
+		use_test_rng,
+	)?;
+
+	let redeem_key = secondary_key(&keychain, &parent_key_id, &lock_slate.id, REDEEM_KEY)?;
+	let redeem_public = PublicKey::from_secret_key(secp, &redeem_key)?;
+	let now = Utc::now();
+	let swap = Swap {
+		id: lock_slate.id,
+		role: SwapRole::Seller,
+		status: SwapStatus::Offered,
+		parent_key_id,
+		amount: args.amount,
+		secondary_currency: args.secondary_currency,
+		secondary_amount: args.secondary_amount,
+		refund_height,
+		secondary_lock_time: now.timestamp() + (args.secondary_lock_hours * 3600) as i64,
+		lock_commit: None,
+		redeem_public: Some(redeem_public),
+		refund_public: None,
+		secret_public: None,
+		secondary_lock_script: None,
+		refund_slate_id: Some(refund_slate.id),
+		refund_key_id: Some(key_id),
+		refund_tx: None,
+		redeem_slate: None,
+		adaptor: None,
+		secondary_key: None,
+		secondary_secret: None,
+		created_ts: now,
+		updated_ts: now,
+	};
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.save_private_context(refund_slate.id.as_bytes(), 0, &refund_context)?;
+		batch.commit()?;
+	}
+	let offer = SwapOffer {
+		amount: swap.amount,
+		secondary_currency: swap.secondary_currency,
+		secondary_amount: swap.secondary_amount,
+		refund_height,
+		secondary_lock_time: swap.secondary_lock_time,
+		redeem_public,
+		lock_slate,
+		refund_slate,
+	};
+	save_swap(
+		wallet,
+		keychain_mask,
+		swap,
+		Some(SwapMessageBody::Offer(offer)),
+	)
+}
+
+/// Accepts a swap offered by the seller, as the buyer: joins the shared output of the
+/// lock transaction and signs the seller's refund of it
+pub fn accept<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	id: &Uuid,
+	offer: SwapOffer,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let SwapOffer {
+		amount,
+		secondary_currency,
+		secondary_amount,
+		refund_height,
+		secondary_lock_time,
+		redeem_public,
+		mut lock_slate,
+		mut refund_slate,
+	} = offer;
+	if lock_slate.id != *id || lock_slate.amount != amount {
+		return Err(swap_error("the lock transaction isn't the offered one"));
+	}
+	let fee = spend_fee();
+	if refund_slate.lock_height != refund_height
+		|| refund_slate.fee < fee
+		|| refund_slate.amount + refund_slate.fee != amount
+		|| !refund_slate.tx.inputs().is_empty()
+		|| refund_slate.tx.outputs().len() != 1
+		|| refund_slate.participant_data.len() != 1
+	{
+		return Err(swap_error(
+			"the refund transaction isn't a refund of the locked epics from the refund height",
+		));
+	}
+	// the seller must be able to redeem the secondary currency well before we can refund
+	// it, once we redeemed the epics just before the seller could refund them
+	let height = wallet.w2n_client().get_chain_tip()?.0;
+	let now = Utc::now().timestamp();
+	if refund_height <= height
+		|| secondary_lock_time - now
+			< (2 * (refund_height - height) * consensus::BLOCK_TIME_SEC) as i64
+	{
+		return Err(swap_error(
+			"the secondary lock must last at least twice as long as the refund takes",
+		));
+	}
+
+	let parent_key_id = wallet.parent_key_id();
+	let lock_context = multisig::join_output(
+		&mut *wallet,
+		keychain_mask,
+		&mut lock_slate,
+		&parent_key_id,
+		use_test_rng,
+	)?;
+	let commit = match lock_slate.multisig_output.as_ref().and_then(|o| o.commit) {
+		Some(c) => c,
+		None => return Err(swap_error("the lock transaction has no shared output")),
+	};
+
+	// our excess of the refund is minus our share of the locked output
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let share_key_id = lock_context.multisig_key_id.clone().unwrap();
+	let share = multisig::share_blind(&keychain, &share_key_id)?;
+	let sec_key = keychain
+		.blind_sum(&BlindSum::new().sub_blinding_factor(BlindingFactor::from_secret_key(share)))?;
+	let mut refund_context = Context::new(
+		secp,
+		sec_key.secret_key(secp)?,
+		&parent_key_id,
+		use_test_rng,
+		1,
+	);
+	refund_slate.tx = refund_slate
+		.tx
+		.clone()
+		.with_input(Input::new(OutputFeatures::Plain, commit));
+	refund_slate.fill_round_1(
+		&keychain,
+		&mut refund_context.sec_key,
+		&refund_context.sec_nonce,
+		1,
+		None,
+		use_test_rng,
+	)?;
+	refund_slate.fill_round_2(
+		&keychain,
+		&refund_context.sec_key,
+		&refund_context.sec_nonce,
+		1,
+	)?;
+
+	let refund_key = secondary_key(&keychain, &parent_key_id, id, REFUND_KEY)?;
+	let refund_public = PublicKey::from_secret_key(secp, &refund_key)?;
+	let secret = secondary_key(&keychain, &parent_key_id, id, SECRET_KEY)?;
+	let secret_public = PublicKey::from_secret_key(secp, &secret)?;
+	let script = secondary_lock_script(
+		secp,
+		secondary_lock_time,
+		&redeem_public,
+		&secret_public,
+		&refund_public,
+	);
+	let now = Utc::now();
+	let swap = Swap {
+		id: *id,
+		role: SwapRole::Buyer,
+		status: SwapStatus::Accepted,
+		parent_key_id,
+		amount,
+		secondary_currency,
+		secondary_amount,
+		refund_height,
+		secondary_lock_time,
+		lock_commit: Some(commit),
+		redeem_public: Some(redeem_public),
+		refund_public: Some(refund_public),
+		secret_public: Some(secret_public),
+		secondary_lock_script: Some(script),
+		refund_slate_id: Some(refund_slate.id),
+		refund_key_id: None,
+		refund_tx: None,
+		redeem_slate: None,
+		adaptor: None,
+		secondary_key: None,
+		secondary_secret: None,
+		created_ts: now,
+		updated_ts: now,
+	};
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.save_private_context(lock_slate.id.as_bytes(), 1, &lock_context)?;
+		batch.commit()?;
+	}
+	let accept = SwapMessageBody::Accept {
+		lock_slate,
+		refund_slate,
+		refund_public,
+		secret_public,
+	};
+	save_swap(wallet, keychain_mask, swap, Some(accept))
+}
+
+/// Completes the refund of the locked epics once the buyer signed it, then signs the lock
+/// transaction, as the seller
+pub fn sign_lock<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	mut lock_slate: Slate,
+	mut refund_slate: Slate,
+	refund_public: PublicKey,
+	secret_public: PublicKey,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if lock_slate.id != swap.id || Some(refund_slate.id) != swap.refund_slate_id {
+		return Err(swap_error("the transactions aren't those of the swap"));
+	}
+	let commit = match lock_slate.multisig_output.as_ref().and_then(|o| o.commit) {
+		Some(c) => c,
+		None => return Err(swap_error("the buyer didn't join the shared output")),
+	};
+
+	// the refund must be complete before the epics are locked
+	let refund_key_id = match swap.refund_key_id {
+		Some(ref k) => k.clone(),
+		None => return Err(swap_error("the swap has no refund output")),
+	};
+	let keychain = wallet.keychain(keychain_mask)?;
+	let refund_commit = keychain.commit(
+		refund_slate.amount,
+		&refund_key_id,
+		&SwitchCommitmentType::Regular,
+	)?;
+	let inputs: Vec<Commitment> = refund_slate
+		.tx
+		.inputs()
+		.iter()
+		.map(|i| i.commitment())
+		.collect();
+	let outputs: Vec<Commitment> = refund_slate
+		.tx
+		.outputs()
+		.iter()
+		.map(|o| o.commitment())
+		.collect();
+	if refund_slate.lock_height != swap.refund_height
+		|| inputs != vec![commit]
+		|| outputs != vec![refund_commit]
+	{
+		return Err(swap_error(
+			"the refund transaction isn't a refund of the locked epics from the refund height",
+		));
+	}
+	let refund_context =
+		wallet.get_private_context(keychain_mask, refund_slate.id.as_bytes(), 0)?;
+	refund_slate.fill_round_2(
+		&keychain,
+		&refund_context.sec_key,
+		&refund_context.sec_nonce,
+		0,
+	)?;
+	refund_slate.finalize(&keychain)?;
+
+	let lock_context = wallet.get_private_context(keychain_mask, lock_slate.id.as_bytes(), 0)?;
+	multisig::add_proof_rounds(&mut *wallet, keychain_mask, &mut lock_slate, &lock_context)?;
+	lock_slate.fill_round_2(&keychain, &lock_context.sec_key, &lock_context.sec_nonce, 0)?;
+	tx::update_message(&mut *wallet, keychain_mask, &lock_slate)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.delete_private_context(lock_slate.id.as_bytes(), 0)?;
+		batch.delete_private_context(refund_slate.id.as_bytes(), 0)?;
+		batch.commit()?;
+	}
+
+	swap.secondary_lock_script = Some(secondary_lock_script(
+		keychain.secp(),
+		swap.secondary_lock_time,
+		swap.redeem_public.as_ref().unwrap(),
+		&secret_public,
+		&refund_public,
+	));
+	swap.status = SwapStatus::Locking;
+	swap.lock_commit = Some(commit);
+	swap.refund_public = Some(refund_public);
+	swap.secret_public = Some(secret_public);
+	swap.refund_tx = Some(to_hex(ser::ser_vec(
+		&refund_slate.tx,
+		ser::ProtocolVersion(1),
+	)?));
+	let locked = SwapMessageBody::LockSigned { lock_slate };
+	save_swap(wallet, keychain_mask, swap, Some(locked))
+}
+
+/// Completes the lock transaction signed by the seller, as the buyer. Returns the
+/// transaction to post.
+pub fn complete_lock<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	mut lock_slate: Slate,
+) -> Result<(SwapStep, Transaction), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if lock_slate.id != swap.id {
+		return Err(swap_error("the lock transaction isn't that of the swap"));
+	}
+	let context = wallet.get_private_context(keychain_mask, lock_slate.id.as_bytes(), 1)?;
+	multisig::complete_output(&mut *wallet, keychain_mask, &mut lock_slate, &context)?;
+	tx::complete_tx(&mut *wallet, keychain_mask, &mut lock_slate, 1, &context)?;
+	tx::update_stored_tx(&mut *wallet, keychain_mask, &context, &lock_slate, true)?;
+	tx::update_message(&mut *wallet, keychain_mask, &lock_slate)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.delete_private_context(lock_slate.id.as_bytes(), 1)?;
+		batch.commit()?;
+	}
+	swap.status = SwapStatus::Locking;
+	let step = save_swap(wallet, keychain_mask, swap, None)?;
+	Ok((step, lock_slate.tx))
+}
+
+/// Starts the transaction redeeming the locked epics to the buyer, as the seller, once
+/// the lock transaction is confirmed
+pub fn start_redeem<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	updater::refresh_outputs(&mut *wallet, keychain_mask, &swap.parent_key_id, false)?;
+	let output = locked_output(&mut *wallet, &swap)?;
+	if output.status != OutputStatus::Unspent {
+		return Err(swap_error(&format!(
+			"the lock transaction of swap {} isn't confirmed yet",
+			swap.id
+		)));
+	}
+	let mut slate = tx::new_tx_slate(
+		&mut *wallet,
+		output.value - spend_fee(),
+		2,
+		use_test_rng,
+		None,
+	)?;
+	let context = multisig::add_shared_input_to_slate(
+		&mut *wallet,
+		keychain_mask,
+		&mut slate,
+		&output,
+		None,
+		use_test_rng,
+	)?;
+	selection::lock_tx_context(&mut *wallet, keychain_mask, &slate, &context)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.save_private_context(slate.id.as_bytes(), 0, &context)?;
+		batch.commit()?;
+	}
+	swap.status = SwapStatus::Redeeming;
+	swap.redeem_slate = Some(slate.clone());
+	let redeem = SwapMessageBody::Redeem {
+		redeem_slate: slate,
+	};
+	save_swap(wallet, keychain_mask, swap, Some(redeem))
+}
+
+/// Adds our output to the redeem transaction started by the seller, as the buyer, with
+/// our adaptor signature of it instead of our partial signature
+pub fn adapt_redeem<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	mut redeem_slate: Slate,
+	use_test_rng: bool,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if redeem_slate.multisig_input != swap.lock_commit
+		|| redeem_slate.amount + redeem_slate.fee != swap.amount
+		|| redeem_slate.lock_height != 0
+		|| redeem_slate.participant_data.len() != 1
+	{
+		return Err(swap_error(
+			"the redeem transaction doesn't send the locked epics to us",
+		));
+	}
+	// our share of the locked output is only spent once it's confirmed
+	updater::refresh_outputs(&mut *wallet, keychain_mask, &swap.parent_key_id, false)?;
+	let parent_key_id = swap.parent_key_id.clone();
+	let context = tx::add_output_to_slate(
+		&mut *wallet,
+		keychain_mask,
+		&mut redeem_slate,
+		&parent_key_id,
+		1,
+		None,
+		true,
+		use_test_rng,
+	)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secret = secondary_key(&keychain, &parent_key_id, &swap.id, SECRET_KEY)?;
+	let adaptor =
+		redeem_slate.adaptor_sig(&keychain, &context.sec_key, &context.sec_nonce, &secret)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.save_private_context(redeem_slate.id.as_bytes(), 1, &context)?;
+		batch.commit()?;
+	}
+	swap.status = SwapStatus::SecondaryLocking;
+	swap.redeem_slate = Some(redeem_slate.clone());
+	let adapted = SwapMessageBody::RedeemAdaptor {
+		redeem_slate,
+		adaptor,
+	};
+	save_swap(wallet, keychain_mask, swap, Some(adapted))
+}
+
+/// Checks the buyer's adaptor signature of the redeem transaction, as the seller, and
+/// keeps it to take the secret out of the posted transaction
+pub fn check_adaptor<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	redeem_slate: Slate,
+	adaptor: Signature,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let started = redeem_slate_of(&swap)?;
+	let ours = |s: &Slate| {
+		s.participant_with_id(0)
+			.map(|p| (p.public_blind_excess, p.public_nonce))
+	};
+	if redeem_slate.id != started.id
+		|| redeem_slate.amount != started.amount
+		|| redeem_slate.fee != started.fee
+		|| redeem_slate.multisig_input != started.multisig_input
+		|| ours(&redeem_slate) != ours(&started)
+	{
+		return Err(swap_error(
+			"the redeem transaction isn't the one we started",
+		));
+	}
+	let secret_public = match swap.secret_public {
+		Some(p) => p,
+		None => return Err(swap_error("the swap has no secret")),
+	};
+	let keychain = wallet.keychain(keychain_mask)?;
+	redeem_slate.verify_adaptor_sig(keychain.secp(), 1, &adaptor, &secret_public)?;
+	swap.status = SwapStatus::SecondaryLocking;
+	swap.redeem_slate = Some(redeem_slate);
+	swap.adaptor = Some(adaptor);
+	save_swap(wallet, keychain_mask, swap, None)
+}
+
+/// Signs the redeem transaction for the buyer to post, as the seller, once the buyer
+/// locked the secondary currency
+pub fn sign_redeem<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut slate = redeem_slate_of(&swap)?;
+	let context = wallet.get_private_context(keychain_mask, slate.id.as_bytes(), 0)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	slate.fill_round_2(&keychain, &context.sec_key, &context.sec_nonce, 0)?;
+
+	// the kernel excess, for the send to be confirmed once the buyer posts it
+	let excess = slate.calc_excess(&keychain)?;
+	let sent = updater::retrieve_txs(
+		&mut *wallet,
+		None,
+		Some(slate.id),
+		Some(&swap.parent_key_id),
+		false,
+	)?
+	.into_iter()
+	.find(|t| t.tx_type == TxLogEntryType::TxSent);
+	tx::update_message(&mut *wallet, keychain_mask, &slate)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		if let Some(mut t) = sent {
+			t.kernel_excess = Some(excess);
+			batch.save_tx_log_entry(t, &swap.parent_key_id)?;
+		}
+		batch.delete_private_context(slate.id.as_bytes(), 0)?;
+		batch.commit()?;
+	}
+	swap.status = SwapStatus::Signed;
+	swap.redeem_slate = Some(slate.clone());
+	let signed = SwapMessageBody::RedeemSigned {
+		redeem_slate: slate,
+	};
+	save_swap(wallet, keychain_mask, swap, Some(signed))
+}
+
+/// Completes the redeem transaction signed by the seller, as the buyer. Returns the
+/// transaction to post, which reveals the secret to the seller.
+pub fn complete_redeem<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+	mut redeem_slate: Slate,
+) -> Result<(SwapStep, Transaction), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	if redeem_slate.id != redeem_slate_of(&swap)?.id {
+		return Err(swap_error("the redeem transaction isn't that of the swap"));
+	}
+	let context = wallet.get_private_context(keychain_mask, redeem_slate.id.as_bytes(), 1)?;
+	tx::complete_tx(&mut *wallet, keychain_mask, &mut redeem_slate, 1, &context)?;
+	tx::update_stored_tx(&mut *wallet, keychain_mask, &context, &redeem_slate, true)?;
+	tx::update_message(&mut *wallet, keychain_mask, &redeem_slate)?;
+	{
+		let mut batch = wallet.batch(keychain_mask)?;
+		batch.delete_private_context(redeem_slate.id.as_bytes(), 1)?;
+		batch.commit()?;
+	}
+	let tx = redeem_slate.tx.clone();
+	swap.status = SwapStatus::Redeemed;
+	swap.redeem_slate = Some(redeem_slate);
+	let step = save_swap(wallet, keychain_mask, swap, None)?;
+	Ok((step, tx))
+}
+
+/// Takes the secret out of the redeem transaction posted by the buyer, as the seller,
+/// completing the swap: the secondary currency can then be redeemed with our redeem key
+/// and the secret
+pub fn reveal_secret<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+) -> Result<SwapStep, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let slate = redeem_slate_of(&swap)?;
+	let keychain = wallet.keychain(keychain_mask)?;
+	let secp = keychain.secp();
+	let excess = slate.calc_excess(&keychain)?;
+	let kernel = match wallet
+		.w2n_client()
+		.get_kernel(&excess, Some(slate.height), None)?
+	{
+		Some((k, _, _)) => k,
+		None => {
+			return Err(swap_error(&format!(
+				"the redeem transaction of swap {} isn't confirmed yet",
+				swap.id
+			)))
+		}
+	};
+	let (adaptor, ours) = match (
+		swap.adaptor.as_ref(),
+		slate.participant_with_id(0).and_then(|p| p.part_sig),
+	) {
+		(Some(a), Some(s)) => (*a, s),
+		_ => return Err(swap_error("the redeem transaction wasn't signed")),
+	};
+	// the buyer's partial signature is the final one less ours, the secret is the
+	// adaptor signature less the buyer's partial signature
+	let secret = keychain.blind_sum(
+		&BlindSum::new()
+			.add_blinding_factor(BlindingFactor::from_secret_key(sig_scalar(secp, &adaptor)?))
+			.sub_blinding_factor(BlindingFactor::from_secret_key(sig_scalar(
+				secp,
+				&kernel.excess_sig,
+			)?))
+			.add_blinding_factor(BlindingFactor::from_secret_key(sig_scalar(secp, &ours)?)),
+	)?;
+	let secret = secret.secret_key(secp)?;
+	if Some(PublicKey::from_secret_key(secp, &secret)?) != swap.secret_public {
+		return Err(swap_error(
+			"the posted redeem transaction doesn't reveal the secret",
+		));
+	}
+	swap.secondary_key = Some(secondary_key(
+		&keychain,
+		&swap.parent_key_id,
+		&swap.id,
+		REDEEM_KEY,
+	)?);
+	swap.secondary_secret = Some(secret);
+	swap.status = SwapStatus::Redeemed;
+	save_swap(wallet, keychain_mask, swap, None)
+}
+
+/// Records the refund of the locked epics to the seller, to be posted
+fn record_refund<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	swap: &Swap,
+) -> Result<Transaction, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let (refund_tx, key_id, slate_id) = match (
+		swap.refund_tx.clone(),
+		swap.refund_key_id.clone(),
+		swap.refund_slate_id,
+	) {
+		(Some(t), Some(k), Some(i)) => (t, k, i),
+		_ => return Err(swap_error("the swap has no refund transaction")),
+	};
+	let tx_bin = from_hex(refund_tx)
+		.map_err(|e| swap_error(&format!("invalid refund transaction: {}", e)))?;
+	let tx: Transaction = ser::deserialize(&mut &tx_bin[..], ser::ProtocolVersion(1))?;
+	let amount = swap.amount - tx.fee();
+	let commit = wallet.calc_commit_for_cache(keychain_mask, amount, &key_id)?;
+	let parent_key_id = swap.parent_key_id.clone();
+
+	let mut batch = wallet.batch(keychain_mask)?;
+	let log_id = batch.next_tx_log_id(&parent_key_id)?;
+	let mut t = TxLogEntry::new(parent_key_id.clone(), TxLogEntryType::TxReceived, log_id);
+	t.tx_slate_id = Some(slate_id);
+	t.stored_tx = Some(format!("{}.epictx", slate_id));
+	t.amount_credited = amount;
+	t.num_outputs = 1;
+	t.kernel_excess = Some(tx.kernels()[0].excess);
+	t.kernel_lookup_min_height = Some(swap.refund_height);
+	t.kernel_features = Some(TxKernelFeatures::HeightLocked {
+		lock_height: swap.refund_height,
+	});
+	batch.save(OutputData {
+		root_key_id: parent_key_id.clone(),
+		key_id: key_id.clone(),
+		n_child: key_id.to_path().last_path_index(),
+		commit,
+		mmr_index: None,
+		value: amount,
+		status: OutputStatus::Unconfirmed,
+		height: swap.refund_height,
+		lock_height: 0,
+		is_coinbase: false,
+		tx_log_entry: Some(log_id),
+		frozen: false,
+		multisig: false,
+	})?;
+	batch.save_tx_log_entry(t, &parent_key_id)?;
+	batch.commit()?;
+	wallet.store_tx(&format!("{}", slate_id), &tx)?;
+	Ok(tx)
+}
+
+/// Cancels a swap. The seller cancels the lock transaction if it wasn't signed yet, or
+/// refunds the locked epics from the refund height, returning the refund transaction to
+/// post. The buyer gives up the swap, once the secondary lock time passed if the
+/// secondary currency was locked, revealing its refund key of the secondary lock script.
+pub fn cancel<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	mut swap: Swap,
+) -> Result<(Swap, Option<Transaction>), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let parent_key_id = swap.parent_key_id.clone();
+	let mut refund = None;
+	match (swap.role, swap.status) {
+		(_, SwapStatus::Redeemed) | (_, SwapStatus::Refunded) | (_, SwapStatus::Cancelled) => {
+			return Err(swap_error(&format!("swap {} is {}", swap.id, swap.status)));
+		}
+		(SwapRole::Seller, SwapStatus::Offered) => {
+			tx::cancel_tx(
+				&mut *wallet,
+				keychain_mask,
+				&parent_key_id,
+				None,
+				Some(swap.id),
+			)?;
+			let mut batch = wallet.batch(keychain_mask)?;
+			batch.delete_private_context(swap.id.as_bytes(), 0)?;
+			if let Some(id) = swap.refund_slate_id {
+				batch.delete_private_context(id.as_bytes(), 0)?;
+			}
+			batch.commit()?;
+			swap.status = SwapStatus::Cancelled;
+		}
+		(SwapRole::Seller, status) => {
+			let height = wallet.w2n_client().get_chain_tip()?.0;
+			if height + 1 < swap.refund_height {
+				return Err(swap_error(&format!(
+					"the locked epics can be refunded from height {}, the chain is at {}",
+					swap.refund_height, height
+				)));
+			}
+			// the redeem transaction spends the locked output too
+			if let Some(ref s) = swap.redeem_slate {
+				tx::cancel_tx(
+					&mut *wallet,
+					keychain_mask,
+					&parent_key_id,
+					None,
+					Some(s.id),
+				)?;
+				if status != SwapStatus::Signed {
+					let mut batch = wallet.batch(keychain_mask)?;
+					batch.delete_private_context(s.id.as_bytes(), 0)?;
+					batch.commit()?;
+				}
+			}
+			refund = Some(record_refund(&mut *wallet, keychain_mask, &swap)?);
+			swap.status = SwapStatus::Refunded;
+		}
+		(SwapRole::Buyer, SwapStatus::Accepted) => {
+			tx::cancel_tx(
+				&mut *wallet,
+				keychain_mask,
+				&parent_key_id,
+				None,
+				Some(swap.id),
+			)?;
+			let mut batch = wallet.batch(keychain_mask)?;
+			batch.delete_private_context(swap.id.as_bytes(), 1)?;
+			batch.commit()?;
+			swap.status = SwapStatus::Cancelled;
+		}
+		(SwapRole::Buyer, SwapStatus::SecondaryLocking) => {
+			let now = Utc::now().timestamp();
+			if now < swap.secondary_lock_time {
+				return Err(swap_error(&format!(
+					"the {} can be refunded from {}",
+					swap.secondary_currency,
+					Utc.timestamp(swap.secondary_lock_time, 0)
+				)));
+			}
+			let s = redeem_slate_of(&swap)?;
+			tx::cancel_tx(
+				&mut *wallet,
+				keychain_mask,
+				&parent_key_id,
+				None,
+				Some(s.id),
+			)?;
+			let keychain = wallet.keychain(keychain_mask)?;
+			swap.secondary_key = Some(secondary_key(
+				&keychain,
+				&parent_key_id,
+				&swap.id,
+				REFUND_KEY,
+			)?);
+			let mut batch = wallet.batch(keychain_mask)?;
+			batch.delete_private_context(s.id.as_bytes(), 1)?;
+			batch.commit()?;
+			swap.status = SwapStatus::Cancelled;
+		}
+		(SwapRole::Buyer, _) => {
+			swap.status = SwapStatus::Cancelled;
+		}
+	}
+	let step = save_swap(wallet, keychain_mask, swap, None)?;
+	Ok((step.swap, refund))
+}
@@ -20,14 +20,17 @@ use uuid::Uuid;
 
 use crate::epic_core::consensus::valid_header_version;
 use crate::epic_core::core::HeaderVersion;
-use crate::epic_keychain::{Identifier, Keychain};
+use crate::epic_keychain::{Identifier, Keychain, SwitchCommitmentType};
 use crate::epic_util::secp::key::SecretKey;
 use crate::epic_util::secp::pedersen;
-use crate::epic_util::Mutex;
-use crate::internal::{selection, updater};
-use crate::slate::Slate;
-use crate::types::{Context, NodeClient, StoredProofInfo, TxLogEntryType, WalletBackend};
-use crate::{address, Error, ErrorKind};
+use crate::epic_util::{from_hex, static_secp_instance, Mutex};
+use crate::internal::{multisig, selection, updater};
+use crate::slate::{ResponseSignature, Slate};
+use crate::types::{
+	Context, NodeClient, PaymentProofState, StoredProofInfo, TxLogEntryType, TxSizeInfo,
+	WalletBackend,
+};
+use crate::{address, Error, ErrorKind, OutputCommitMapping};
 use ed25519_dalek::Keypair as DalekKeypair;
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use ed25519_dalek::SecretKey as DalekSecretKey;
@@ -92,11 +95,13 @@ pub fn estimate_send_tx<'a, T: ?Sized, C, K>(
 	max_outputs: usize,
 	num_change_outputs: usize,
 	selection_strategy_is_use_all: bool,
+	inputs: Option<&[pedersen::Commitment]>,
 	parent_key_id: &Identifier,
 ) -> Result<
 	(
-		u64, // total
-		u64, // fee
+		Vec<OutputCommitMapping>, // inputs
+		u64,                      // total
+		u64,                      // fee
 	),
 	Error,
 >
@@ -117,7 +122,7 @@ where
 	// according to plan
 	// This function is just a big helper to do all of that, in theory
 	// this process can be split up in any way
-	let (_coins, total, _amount, fee) = selection::select_coins_and_fee(
+	let (coins, total, _amount, fee) = selection::select_coins_and_fee(
 		wallet,
 		amount,
 		current_height,
@@ -125,9 +130,31 @@ where
 		max_outputs,
 		num_change_outputs,
 		selection_strategy_is_use_all,
+		inputs,
+		None,
 		parent_key_id,
 	)?;
-	Ok((total, fee))
+
+	// only the commitments of the inputs are computed, no rangeproofs are built
+	let keychain = wallet.keychain(keychain_mask)?;
+	let inputs = coins
+		.into_iter()
+		.map(|output| {
+			let commit = match output.commit.clone() {
+				Some(c) => {
+					let bytes = from_hex(c).map_err(|e| {
+						ErrorKind::GenericError(format!("Invalid stored commitment: {}", e))
+					})?;
+					pedersen::Commitment::from_vec(bytes)
+				}
+				None => {
+					keychain.commit(output.value, &output.key_id, &SwitchCommitmentType::Regular)?
+				}
+			};
+			Ok(OutputCommitMapping::new(output, commit))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	Ok((inputs, total, fee))
 }
 
 /// Add inputs to the slate (effectively becoming the sender)
@@ -138,7 +165,9 @@ pub fn add_inputs_to_slate<'a, T: ?Sized, C, K>(
 	minimum_confirmations: u64,
 	max_outputs: usize,
 	num_change_outputs: usize,
+	decoy_amount: Option<u64>,
 	selection_strategy_is_use_all: bool,
+	inputs: Option<&[pedersen::Commitment]>,
 	parent_key_id: &Identifier,
 	participant_id: usize,
 	message: Option<String>,
@@ -168,7 +197,9 @@ where
 		minimum_confirmations,
 		max_outputs,
 		num_change_outputs,
+		decoy_amount,
 		selection_strategy_is_use_all,
+		inputs,
 		parent_key_id.clone(),
 		use_test_rng,
 	)?;
@@ -215,13 +246,15 @@ where
 	K: Keychain + 'a,
 {
 	// create an output using the amount in the slate
-	let (_, mut context) = selection::build_recipient_output(
+	let (key_id, mut context) = selection::build_recipient_output(
 		wallet,
 		keychain_mask,
 		slate,
 		parent_key_id.clone(),
 		use_test_rng,
 	)?;
+	// along with our share of the shared output sent to us, if any
+	multisig::add_input_share(wallet, keychain_mask, slate, &key_id, &mut context)?;
 
 	// fill public keys
 	let _ = slate.fill_round_1(
@@ -349,6 +382,7 @@ where
 	wallet.store_tx(&format!("{}", tx.tx_slate_id.unwrap()), &slate.tx)?;
 	let parent_key = tx.parent_key_id.clone();
 	tx.kernel_excess = Some(slate.tx.body.kernels[0].excess);
+	tx.tx_size = Some(TxSizeInfo::from_tx(&slate.tx));
 
 	if let Some(ref p) = slate.payment_proof {
 		let derivation_index = match context.payment_proof_derivation_index {
@@ -369,6 +403,10 @@ where
 			sender_address_path: derivation_index,
 			sender_address,
 			sender_signature: Some(sig),
+			state: match p.receiver_signature {
+				Some(_) => Some(PaymentProofState::Promised),
+				None => Some(PaymentProofState::Requested),
+			},
 		})
 	}
 
@@ -541,6 +579,163 @@ where
 	Ok(())
 }
 
+/// Message signed by the recipient of a slate to authenticate its response
+pub fn response_signature_message(
+	slate: &Slate,
+	kernel_commitment: &pedersen::Commitment,
+) -> Result<Vec<u8>, Error> {
+	let mut msg = Vec::new();
+	msg.append(&mut slate.id.as_bytes().to_vec());
+	msg.write_u64::<BigEndian>(slate.amount)?;
+	msg.write_u64::<BigEndian>(slate.fee)?;
+	msg.append(&mut kernel_commitment.0.to_vec());
+	Ok(msg)
+}
+
+/// Sign the response to a slate with the key of the recipient's address
+pub fn create_response_signature(
+	slate: &Slate,
+	kernel_commitment: &pedersen::Commitment,
+	sec_key: SecretKey,
+) -> Result<ResponseSignature, Error> {
+	let msg = response_signature_message(slate, kernel_commitment)?;
+	let (d_skey, pub_key) = address::ed25519_keypair(&sec_key)?;
+	let keypair = DalekKeypair {
+		public: pub_key,
+		secret: d_skey,
+	};
+	Ok(ResponseSignature {
+		address: pub_key,
+		signature: keypair.sign(&msg),
+	})
+}
+
+/// Verify the recipient's signature of its response to the current slate. The
+/// response must be signed with the address given when the transaction was created,
+/// if any, otherwise a signed response must come from the payment proof recipient.
+pub fn verify_response_signature<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	parent_key_id: &Identifier,
+	context: &Context,
+	slate: &Slate,
+) -> Result<(), Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let sig = match slate.response_signature {
+		Some(ref s) => s,
+		None => match context.response_address {
+			Some(_) => {
+				return Err(ErrorKind::ResponseSignature(
+					"Recipient did not sign its response".to_owned(),
+				))?;
+			}
+			None => return Ok(()),
+		},
+	};
+
+	let expected_address = match context.response_address {
+		Some(a) => Some(a),
+		None => updater::retrieve_txs(wallet, None, Some(slate.id), Some(parent_key_id), false)?
+			.into_iter()
+			.find_map(|t| t.payment_proof)
+			.map(|p| p.receiver_address),
+	};
+	if let Some(a) = expected_address {
+		if sig.address != a {
+			return Err(ErrorKind::ResponseSignature(format!(
+				"Response signed by {} rather than the recipient {}",
+				address::onion_v3_from_pubkey(&sig.address)?,
+				address::onion_v3_from_pubkey(&a)?
+			)))?;
+		}
+	}
+
+	let keychain = wallet.keychain(keychain_mask)?;
+	let msg = response_signature_message(slate, &slate.calc_excess(&keychain)?)?;
+	if let Err(_) = sig.address.verify(&msg, &sig.signature) {
+		return Err(ErrorKind::ResponseSignature(
+			"Invalid response signature".to_owned(),
+		))?;
+	}
+	if expected_address.is_none() {
+		debug!(
+			"Response to slate {} signed by {}, no recipient address to check it against",
+			slate.id,
+			address::onion_v3_from_pubkey(&sig.address)?
+		);
+	}
+	Ok(())
+}
+
+/// Message signed by the sender of a slate to prove it controls the sender address
+/// of its payment proof
+pub fn sender_signature_message(slate: &Slate) -> Result<Vec<u8>, Error> {
+	let excess = match slate.participant_with_id(0) {
+		Some(p) => p.public_blind_excess,
+		None => {
+			return Err(ErrorKind::SenderSignature(
+				"Slate is missing the sender's data".to_owned(),
+			))?;
+		}
+	};
+	let secp = static_secp_instance();
+	let secp = secp.lock();
+	let mut msg = Vec::new();
+	msg.append(&mut slate.id.as_bytes().to_vec());
+	msg.write_u64::<BigEndian>(slate.amount)?;
+	msg.write_u64::<BigEndian>(slate.fee)?;
+	msg.append(&mut excess.serialize_vec(&secp, true).to_vec());
+	Ok(msg)
+}
+
+/// Sign a slate with the key of the sender's address
+pub fn create_sender_signature(
+	slate: &Slate,
+	sec_key: SecretKey,
+) -> Result<ResponseSignature, Error> {
+	let msg = sender_signature_message(slate)?;
+	let (d_skey, pub_key) = address::ed25519_keypair(&sec_key)?;
+	let keypair = DalekKeypair {
+		public: pub_key,
+		secret: d_skey,
+	};
+	Ok(ResponseSignature {
+		address: pub_key,
+		signature: keypair.sign(&msg),
+	})
+}
+
+/// Verify the sender's signature of a slate, which must be made with the key of
+/// the sender address of the slate's payment proof
+pub fn verify_sender_signature(slate: &Slate, sig: &ResponseSignature) -> Result<(), Error> {
+	let sender_address = match slate.payment_proof.as_ref() {
+		Some(p) => p.sender_address,
+		None => {
+			return Err(ErrorKind::SenderSignature(
+				"Slate doesn't name its sender with a payment proof".to_owned(),
+			))?;
+		}
+	};
+	if sig.address != sender_address {
+		return Err(ErrorKind::SenderSignature(format!(
+			"Slate signed by {} rather than its sender {}",
+			address::onion_v3_from_pubkey(&sig.address)?,
+			address::onion_v3_from_pubkey(&sender_address)?
+		)))?;
+	}
+	let msg = sender_signature_message(slate)?;
+	if let Err(_) = sig.address.verify(&msg, &sig.signature) {
+		return Err(ErrorKind::SenderSignature(
+			"Invalid sender signature".to_owned(),
+		))?;
+	}
+	Ok(())
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -624,4 +819,51 @@ mod test {
 
 		assert!(address.verify(&msg, &sig).is_ok());
 	}
+
+	#[test]
+	fn sender_signature() {
+		use crate::slate::{ParticipantData, PaymentInfo};
+
+		let (sender_key, other_key, excess) = {
+			let secp_inst = static_secp_instance();
+			let secp = secp_inst.lock();
+			let mut test_rng = StepRng::new(1234567890u64, 1);
+			let excess_key = secp::key::SecretKey::new(&secp, &mut test_rng);
+			(
+				secp::key::SecretKey::new(&secp, &mut test_rng),
+				secp::key::SecretKey::new(&secp, &mut test_rng),
+				secp::key::PublicKey::from_secret_key(&secp, &excess_key).unwrap(),
+			)
+		};
+		let (_, sender_address) = address::ed25519_keypair(&sender_key).unwrap();
+		let (_, receiver_address) = address::ed25519_keypair(&other_key).unwrap();
+
+		let mut slate = Slate::blank(2);
+		slate.amount = 12345678;
+		slate.participant_data.push(ParticipantData {
+			id: 0,
+			public_blind_excess: excess,
+			public_nonce: excess,
+			part_sig: None,
+			message: None,
+			message_sig: None,
+		});
+		slate.payment_proof = Some(PaymentInfo {
+			sender_address,
+			receiver_address,
+			receiver_signature: None,
+		});
+
+		let sig = create_sender_signature(&slate, sender_key).unwrap();
+		assert!(verify_sender_signature(&slate, &sig).is_ok());
+
+		// by the sender named in the payment proof
+		let other = create_sender_signature(&slate, other_key).unwrap();
+		assert!(verify_sender_signature(&slate, &other).is_err());
+
+		// of this slate
+		let mut changed = slate.clone();
+		changed.amount += 1;
+		assert!(verify_sender_signature(&changed, &sig).is_err());
+	}
 }
@@ -15,12 +15,19 @@
 //! Utilities to check the status of all the outputs we have stored in
 //! the wallet storage and update them.
 
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Maximum number of block headers kept in the wallet's header cache
+pub const MAX_CACHED_HEADERS: usize = 10_000;
+
+/// Maximum number of block headers requested from the node in one update
+pub const HEADER_FETCH_WINDOW: u64 = 1_000;
+
+use crate::chain_params;
 use crate::epic_core::consensus::{cumulative_reward_foundation, reward};
 use crate::epic_core::core::{Output, TxKernel};
-use crate::epic_core::global;
 use crate::epic_core::libtx::proof::ProofBuilder;
 use crate::epic_core::libtx::reward;
 use crate::epic_keychain::{Identifier, Keychain, SwitchCommitmentType};
@@ -28,12 +35,16 @@ use crate::epic_util as util;
 use crate::epic_util::secp::key::SecretKey;
 use crate::epic_util::secp::pedersen;
 use crate::epic_util::static_secp_instance;
-use crate::error::Error;
+use crate::error::{Error, ErrorKind};
 use crate::internal::keys;
 use crate::types::{
-	NodeClient, OutputData, OutputStatus, TxLogEntry, TxLogEntryType, WalletBackend, WalletInfo,
+	BlockHeaderInfo, NodeClient, OutputData, OutputStatus, TxLogEntry, TxLogEntryType,
+	WalletBackend, WalletInfo,
+};
+use crate::{
+	publish_event, store_events, BlockFees, CbData, DeduplicatedOutput, OutputCommitMapping,
+	WalletEvent,
 };
-use crate::{BlockFees, CbData, OutputCommitMapping};
 
 /// Retrieve all of the outputs (doesn't attempt to update from node)
 pub fn retrieve_outputs<'a, T: ?Sized, C, K>(
@@ -80,21 +91,35 @@ where
 			.collect();
 	}
 
-	outputs.sort_by_key(|out| (out.n_child, out.tx_log_entry));
-	let keychain = wallet.keychain(keychain_mask)?;
+	// a total order, so the outputs are listed in the same order whatever the order they're
+	// stored in
+	outputs.sort_by(|a, b| {
+		(a.n_child, a.tx_log_entry)
+			.cmp(&(b.n_child, b.tx_log_entry))
+			.then_with(|| a.cmp(b))
+	});
+	// watch-only wallets have no keychain, but store the commitments of all their outputs
+	let keychain = match wallet.keychain(keychain_mask) {
+		Ok(k) => Some(k),
+		Err(e) => match e.kind() {
+			ErrorKind::KeychainDoesntExist => None,
+			_ => return Err(e),
+		},
+	};
 
 	let res = outputs
 		.into_iter()
 		.map(|output| {
-			let commit = match output.commit.clone() {
-				Some(c) => pedersen::Commitment::from_vec(util::from_hex(c).unwrap()),
-				None => keychain
+			let commit = match (output.commit.clone(), keychain.as_ref()) {
+				(Some(c), _) => pedersen::Commitment::from_vec(util::from_hex(c).unwrap()),
+				(None, Some(k)) => k
 					.commit(output.value, &output.key_id, &SwitchCommitmentType::Regular)
 					.unwrap(), // TODO: proper support for different switch commitment schemes
+				(None, None) => return Err(ErrorKind::KeychainDoesntExist.into()),
 			};
-			OutputCommitMapping { output, commit }
+			Ok(OutputCommitMapping::new(output, commit))
 		})
-		.collect();
+		.collect::<Result<Vec<_>, Error>>()?;
 	Ok(res)
 }
 
@@ -138,7 +163,10 @@ where
 			f_pk && f_tx_id && f_txs && f_outstanding
 		})
 		.collect();
-	txs.sort_by_key(|tx| tx.creation_ts);
+	// entries are unique by account and id
+	txs.sort_by(|a, b| {
+		(a.creation_ts, &a.parent_key_id, a.id).cmp(&(b.creation_ts, &b.parent_key_id, b.id))
+	});
 	Ok(txs)
 }
 
@@ -244,6 +272,9 @@ where
 	}
 	if tx.tx_type == TxLogEntryType::TxReceived {
 		tx.tx_type = TxLogEntryType::TxReceivedCancelled;
+		if let Some(id) = tx.tx_slate_id {
+			batch.delete_slate_response(&id)?;
+		}
 	}
 	batch.save_tx_log_entry(tx, parent_key_id)?;
 	batch.commit()?;
@@ -256,6 +287,7 @@ pub fn apply_api_outputs<'a, T: ?Sized, C, K>(
 	keychain_mask: Option<&SecretKey>,
 	wallet_outputs: &HashMap<pedersen::Commitment, (Identifier, Option<u64>)>,
 	api_outputs: &HashMap<pedersen::Commitment, (String, u64, u64)>,
+	block_times: &HashMap<u64, DateTime<Utc>>,
 	height: u64,
 	parent_key_id: &Identifier,
 ) -> Result<(), Error>
@@ -279,6 +311,8 @@ where
 			warn!("Please wait for sync on node to complete or fork to resolve and try again.");
 			return Ok(());
 		}
+		// published once the changes are committed
+		let mut events = vec![];
 		let mut batch = wallet.batch(keychain_mask)?;
 		for (commit, (id, mmr_index)) in wallet_outputs.iter() {
 			if let Ok(mut output) = batch.get(id, mmr_index) {
@@ -306,8 +340,16 @@ where
 								t.kernel_excess = Some(excess);
 								t.kernel_lookup_min_height = Some(height);
 							}
-							t.update_confirmation_ts();
+							match block_times.get(&o.1) {
+								Some(ts) => t.confirmation_ts = Some(*ts),
+								None => t.update_confirmation_ts(),
+							}
 							output.tx_log_entry = Some(log_id);
+							events.push(WalletEvent::TxConfirmed {
+								tx_id: log_id,
+								tx_slate_id: None,
+								height: o.1,
+							});
 							batch.save_tx_log_entry(t, &parent_key_id)?;
 						}
 						// also mark the transaction in which this output is involved as confirmed
@@ -319,15 +361,35 @@ where
 									&& t.parent_key_id == *parent_key_id
 							});
 							if let Some(mut t) = tx {
-								t.update_confirmation_ts();
-								t.confirmed = true;
+								match block_times.get(&o.1) {
+									Some(ts) => t.confirm_at(*ts),
+									None => t.confirm(),
+								}
+								// the exchange is over once on chain
+								if let Some(id) = t.tx_slate_id {
+									batch.delete_slate_response(&id)?;
+								}
+								events.push(WalletEvent::TxConfirmed {
+									tx_id: t.id,
+									tx_slate_id: t.tx_slate_id,
+									height: o.1,
+								});
 								batch.save_tx_log_entry(t, &parent_key_id)?;
 							}
 						}
 						output.height = o.1;
 						output.mark_unspent();
 					}
-					None => output.mark_spent(),
+					None => {
+						let was_spent = output.status == OutputStatus::Spent;
+						output.mark_spent();
+						if !was_spent && output.status == OutputStatus::Spent {
+							events.push(WalletEvent::OutputSpent {
+								commit: util::to_hex(commit.0.to_vec()),
+								value: output.value,
+							});
+						}
+					}
 				};
 				batch.save(output)?;
 			}
@@ -335,7 +397,11 @@ where
 		{
 			batch.save_last_confirmed_height(parent_key_id, height)?;
 		}
+		store_events(&mut *batch, &events)?;
 		batch.commit()?;
+		for e in events {
+			publish_event(e);
+		}
 	}
 	Ok(())
 }
@@ -366,11 +432,25 @@ where
 		.w2n_client()
 		.get_outputs_from_node(wallet_output_keys)?;
 
+	// timestamps of the blocks confirming outputs for the first time
+	let mut confirm_heights = vec![];
+	for (commit, (id, mmr_index)) in wallet_outputs.iter() {
+		if let Some(o) = api_outputs.get(commit) {
+			if let Ok(output) = wallet.get(id, mmr_index) {
+				if output.status == OutputStatus::Unconfirmed {
+					confirm_heights.push(o.1);
+				}
+			}
+		}
+	}
+	let block_times = block_times(wallet, confirm_heights);
+
 	apply_api_outputs(
 		wallet,
 		keychain_mask,
 		&wallet_outputs,
 		&api_outputs,
+		&block_times,
 		height,
 		parent_key_id,
 	)?;
@@ -378,6 +458,52 @@ where
 	Ok(())
 }
 
+/// Timestamps of the blocks at the given heights, read from the header cache
+/// or else fetched from the node in a single range request. Heights whose
+/// block can't be found are left out.
+fn block_times<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	heights: Vec<u64>,
+) -> HashMap<u64, DateTime<Utc>>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let mut times = HashMap::new();
+	let mut missing = vec![];
+	for height in heights {
+		match wallet.get_block_header(height) {
+			Ok(Some(h)) => {
+				times.insert(height, h.timestamp);
+			}
+			_ => missing.push(height),
+		}
+	}
+	let (start, end) = match (missing.iter().min(), missing.iter().max()) {
+		(Some(s), Some(e)) => (*s, *e),
+		_ => return times,
+	};
+	if end - start >= HEADER_FETCH_WINDOW {
+		debug!(
+			"Not fetching {} block headers for confirmation times",
+			end - start + 1
+		);
+		return times;
+	}
+	match wallet.w2n_client().get_headers(start, end) {
+		Ok(headers) => {
+			for h in headers {
+				if missing.contains(&h.height) {
+					times.insert(h.height, h.timestamp);
+				}
+			}
+		}
+		Err(e) => warn!("Unable to retrieve block headers from node: {}", e),
+	}
+	times
+}
+
 fn clean_old_unconfirmed<'a, T: ?Sized, C, K>(
 	wallet: &mut T,
 	keychain_mask: Option<&SecretKey>,
@@ -409,6 +535,189 @@ where
 	Ok(())
 }
 
+/// How the records of an output are ranked to pick the one kept: the record in the
+/// account its key was derived in, then with its MMR index known, then linked to a
+/// transaction, then the furthest along, i.e. locked or spent rather than unspent
+fn dedup_rank(out: &OutputData) -> (bool, bool, bool, u8) {
+	let status = match out.status {
+		OutputStatus::Unconfirmed => 0,
+		OutputStatus::Unspent => 1,
+		OutputStatus::Locked => 2,
+		OutputStatus::Spent => 3,
+		OutputStatus::Deleted => 0,
+	};
+	(
+		out.key_id.parent_path() == out.root_key_id,
+		out.mmr_index.is_some(),
+		out.tx_log_entry.is_some(),
+		status,
+	)
+}
+
+/// Find the outputs recorded more than once with the same commitment, across all
+/// accounts, and remove all their records but the best ranked one, so they aren't
+/// counted twice. The transaction a removed record was linked to is kept on the
+/// record kept if it has none. Returns what was removed.
+pub fn dedup_outputs<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+) -> Result<Vec<DeduplicatedOutput>, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let keychain = wallet.keychain(keychain_mask)?;
+	let mut by_commit: HashMap<String, Vec<OutputData>> = HashMap::new();
+	for out in wallet.iter() {
+		let commit = match out.commit.clone() {
+			Some(c) => c,
+			None => util::to_hex(
+				keychain
+					.commit(out.value, &out.key_id, &SwitchCommitmentType::Regular)?
+					.0
+					.to_vec(),
+			),
+		};
+		by_commit.entry(commit).or_default().push(out);
+	}
+
+	let mut dups = vec![];
+	for (commit, mut outs) in by_commit {
+		if outs.len() < 2 {
+			continue;
+		}
+		outs.sort_by(|a, b| {
+			dedup_rank(b)
+				.cmp(&dedup_rank(a))
+				.then_with(|| a.root_key_id.to_bytes().cmp(&b.root_key_id.to_bytes()))
+		});
+		let kept = outs.remove(0);
+		dups.push(DeduplicatedOutput {
+			commit,
+			kept,
+			removed: outs,
+		});
+	}
+	if dups.is_empty() {
+		return Ok(dups);
+	}
+	dups.sort_by(|a, b| a.commit.cmp(&b.commit));
+
+	let mut batch = wallet.batch(keychain_mask)?;
+	for d in dups.iter_mut() {
+		for r in d.removed.iter() {
+			batch.delete(&r.key_id, &r.mmr_index, &r.tx_log_entry)?;
+		}
+		if d.kept.tx_log_entry.is_none() {
+			if let Some(t) = d.removed.iter().find_map(|r| r.tx_log_entry) {
+				d.kept.tx_log_entry = Some(t);
+				batch.save(d.kept.clone())?;
+			}
+		}
+		warn!(
+			"Output {} recorded {} times, keeping the record of account {}",
+			d.commit,
+			d.removed.len() + 1,
+			d.kept.root_key_id
+		);
+	}
+	batch.commit()?;
+	Ok(dups)
+}
+
+/// Description of the records of an output removed as duplicates, for the status
+/// messages of updates and scans
+pub fn dedup_message(d: &DeduplicatedOutput) -> String {
+	let removed: Vec<String> = d
+		.removed
+		.iter()
+		.map(|o| o.root_key_id.to_string())
+		.collect();
+	format!(
+		"Output {} was recorded {} times, kept the record of account {}, removed those of {}",
+		d.commit,
+		d.removed.len() + 1,
+		d.kept.root_key_id,
+		removed.join(", ")
+	)
+}
+
+/// Height from which headers should be fetched to bring the header cache up
+/// to the given tip: the highest cached height not above the tip, so a reorg
+/// below it is noticed, within `HEADER_FETCH_WINDOW` of the tip.
+pub fn header_fetch_start<'a, T: ?Sized, C, K>(wallet: &mut T, tip_height: u64) -> u64
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let window_start = tip_height.saturating_sub(HEADER_FETCH_WINDOW - 1);
+	wallet
+		.block_headers_iter()
+		.map(|h| h.height)
+		.filter(|h| *h <= tip_height)
+		.max()
+		.map(|h| h.max(window_start))
+		.unwrap_or(tip_height)
+}
+
+/// Store a range of headers of the node's chain, ending at its current tip,
+/// in the header cache. Cached headers that disagree with them, at the same
+/// height with a different hash or above the tip, have been reorganised away
+/// and are removed. The oldest headers are pruned to keep the cache bounded.
+/// Returns whether a reorg was detected.
+pub fn cache_headers<'a, T: ?Sized, C, K>(
+	wallet: &mut T,
+	keychain_mask: Option<&SecretKey>,
+	headers: Vec<BlockHeaderInfo>,
+) -> Result<bool, Error>
+where
+	T: WalletBackend<'a, C, K>,
+	C: NodeClient + 'a,
+	K: Keychain + 'a,
+{
+	let tip_height = match headers.iter().map(|h| h.height).max() {
+		Some(h) => h,
+		None => return Ok(false),
+	};
+	let fetched: HashMap<u64, &str> = headers
+		.iter()
+		.map(|h| (h.height, h.hash.as_str()))
+		.collect();
+	let mut stale = vec![];
+	let mut heights = vec![];
+	for h in wallet.block_headers_iter() {
+		match fetched.get(&h.height) {
+			Some(hash) if *hash != h.hash => stale.push(h.height),
+			Some(_) => {}
+			None if h.height > tip_height => stale.push(h.height),
+			None => heights.push(h.height),
+		}
+	}
+	let reorg = !stale.is_empty();
+	if reorg {
+		warn!(
+			"Chain reorg detected below height {}, dropping {} cached block headers",
+			tip_height,
+			stale.len()
+		);
+	}
+
+	heights.sort();
+	let excess = (heights.len() + fetched.len()).saturating_sub(MAX_CACHED_HEADERS);
+
+	let mut batch = wallet.batch(keychain_mask)?;
+	for height in stale.into_iter().chain(heights.into_iter().take(excess)) {
+		batch.delete_block_header(height)?;
+	}
+	for header in headers {
+		batch.save_block_header(header)?;
+	}
+	batch.commit()?;
+	Ok(reorg)
+}
+
 /// Retrieve summary info about the wallet
 /// caller should refresh first if desired
 pub fn retrieve_info<'a, T: ?Sized, C, K>(
@@ -422,9 +731,10 @@ where
 	K: Keychain + 'a,
 {
 	let current_height = wallet.last_confirmed_height()?;
+	// outputs shared with another wallet aren't the wallet's alone to spend
 	let outputs = wallet
 		.iter()
-		.filter(|out| out.root_key_id == *parent_key_id);
+		.filter(|out| out.root_key_id == *parent_key_id && !out.multisig);
 
 	let mut unspent_total = 0;
 	let mut immature_total = 0;
@@ -537,7 +847,7 @@ where
 	K: Keychain + 'a,
 {
 	let height = block_fees.height;
-	let lock_height = height + global::coinbase_maturity();
+	let lock_height = height + chain_params::coinbase_maturity();
 	let key_id = block_fees.key_id();
 	let parent_key_id = wallet.parent_key_id();
 	let amount = reward(block_fees.fees, block_fees.height);
@@ -566,6 +876,8 @@ where
 			lock_height: lock_height,
 			is_coinbase: true,
 			tx_log_entry: None,
+			frozen: false,
+			multisig: false,
 		})?;
 		batch.commit()?;
 	}
@@ -607,7 +919,7 @@ where
 	K: Keychain + 'a,
 {
 	let height = block_fees.height;
-	let lock_height = height + global::coinbase_maturity();
+	let lock_height = height + chain_params::coinbase_maturity();
 	let key_id = block_fees.key_id();
 	let parent_key_id = wallet.parent_key_id();
 	let amount = cumulative_reward_foundation(block_fees.height);
@@ -636,6 +948,8 @@ where
 			lock_height: lock_height,
 			is_coinbase: true,
 			tx_log_entry: None,
+			frozen: false,
+			multisig: false,
 		})?;
 		batch.commit()?;
 	}
@@ -47,18 +47,29 @@ extern crate strum_macros;
 pub mod address;
 pub mod api_impl;
 mod base58;
+mod chain_params;
 pub mod crypto;
 mod epicbox_address;
 mod error;
+mod events;
 mod internal;
 pub mod message;
 pub mod slate;
 pub mod slate_versions;
 mod tx_proof;
 mod types;
+mod view_key;
 
+pub use crate::chain_params::{check_node_chain, coinbase_maturity, set_chain_params};
 pub use crate::error::{Error, ErrorKind};
-pub use crate::slate::{ParticipantData, ParticipantMessageData, Slate};
+pub use crate::events::{
+	publish_event, record_event, store_events, subscribe_events, StoredEvent, WalletEvent,
+	MAX_STORED_EVENTS,
+};
+pub use crate::slate::{
+	MultisigOutput, MultisigParty, ParticipantData, ParticipantMessageData, ResponseSignature,
+	Slate,
+};
 pub use crate::slate_versions::{
 	SlateVersion, VersionedCoinbase, VersionedSlate, CURRENT_SLATE_VERSION,
 	EPIC_BLOCK_HEADER_VERSION,
@@ -66,20 +77,37 @@ pub use crate::slate_versions::{
 pub use crate::tx_proof::TxProof;
 pub use api_impl::owner_updater::StatusMessage;
 pub use api_impl::types::{
-	BlockFees, InitTxArgs, InitTxSendArgs, IssueInvoiceTxArgs, NodeHeightResult,
-	OutputCommitMapping, PaymentProof, SendTXArgs, VersionInfo,
+	AccountFeeReport, AccountStatement, ApiStatus, ApiVersions, BalanceAlert, BalanceAlertKind,
+	BalanceAlertPolicy, BalanceThreshold, BlockFees, DeduplicatedOutput, FeePeriod,
+	FeePeriodSummary, FeeReport, FeeSummary, ImportedOutput, InitTxArgs, InitTxBatchResult,
+	InitTxSendArgs, IssueInvoiceTxArgs, MethodDeprecation, NodeHeightResult, OutputClaim,
+	OutputClaimVerification, OutputCommitMapping, OutputImportSummary, OutputLineage,
+	OutputMatured, OutputPage, OutputPageQuery, OutputSortField, OwnerApiVersion, PaymentProof,
+	PaymentProofEntry, PaymentRequest, ScanJob, ScanJobStatus, ScanPreview, SeedWord, SendJob,
+	SendJobStatus, SendPriority, SendTXArgs, SigningRequest, StatementFiat, StatementLine,
+	SwapMessage, SwapMessageBody, SwapOffer, SwapStartArgs, SwapStep, SweepPolicy, TxBulkResult,
+	TxEstimate, TxFilter, TxPage, TxPageQuery, VersionInfo, SWEEP_MINIMUM_CONFIRMATIONS,
+	SWEEP_TX_TAG,
 };
 pub use epicbox_address::{
 	version_bytes, Address, AddressType, EpicboxAddress, DEFAULT_EPICBOX_PORT_443,
 	DEFAULT_EPICBOX_PORT_80,
 };
+pub use internal::keys::{check_derivation_config, set_derivation_config};
+pub use internal::password::{check_password_policy, DEFAULT_PASSWORD_MIN_LENGTH};
 pub use internal::scan::scan;
+pub use internal::tx::{create_sender_signature, verify_sender_signature};
 pub use slate_versions::ser as dalek_ser;
 pub use types::{
-	AcctPathMapping, BlockIdentifier, CbData, Context, NodeClient, NodeVersionInfo, OutputData,
-	OutputStatus, ScannedBlockInfo, StoredProofInfo, TxLogEntry, TxLogEntryType, TxWrapper,
-	WalletBackend, WalletInfo, WalletInitStatus, WalletInst, WalletLCProvider, WalletOutputBatch,
+	AcctPathMapping, BalanceAlertHook, BlockHeaderInfo, BlockIdentifier, CbData, Contact, Context,
+	InputComposition, KeyPinCheck, MaturityHook, MetadataEntry, MnemonicLanguage, NodeClient,
+	NodeRequestMetrics, NodeVersionInfo, OutputData, OutputStatus, PaymentProofState, PinnedKey,
+	PriceFeed, ReceiveHook, ScannedBlockInfo, SendTemplate, StoredProofInfo, Swap, SwapCurrency,
+	SwapRole, SwapStatus, TransportEvent, TransportMetrics, TxApproval, TxHook, TxKernelFeatures,
+	TxLogEntry, TxLogEntryType, TxSizeInfo, TxWrapper, WalletBackend, WalletInfo, WalletInitStatus,
+	WalletInst, WalletLCProvider, WalletOutputBatch, CONTACT_TRANSPORTS, SEND_TEMPLATE_METHODS,
 };
+pub use view_key::ViewKey;
 
 /// Helper for taking a lock on the wallet instance
 #[macro_export]
@@ -43,8 +43,9 @@ use uuid::Uuid;
 
 use crate::slate_versions::v2::SlateV2;
 use crate::slate_versions::v3::{
-	CoinbaseV3, InputV3, OutputV3, ParticipantDataV3, PaymentInfoV3, SlateV3, TransactionBodyV3,
-	TransactionV3, TxKernelV3, VersionCompatInfoV3,
+	CoinbaseV3, InputV3, MultisigOutputV3, MultisigPartyV3, OutputV3, ParticipantDataV3,
+	PaymentInfoV3, ResponseSignatureV3, SlateV3, TransactionBodyV3, TransactionV3, TxKernelV3,
+	VersionCompatInfoV3,
 };
 use crate::slate_versions::{CURRENT_SLATE_VERSION, EPIC_BLOCK_HEADER_VERSION};
 use crate::types::CbData;
@@ -63,6 +64,61 @@ pub struct PaymentInfo {
 	pub receiver_signature: Option<DalekSignature>,
 }
 
+/// Signature of the recipient's response to a slate, made with the key of the
+/// recipient wallet's address so the sender can tell it came from the intended
+/// recipient rather than a relay in between
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResponseSignature {
+	/// Address of the wallet which signed the response
+	#[serde(with = "dalek_ser::dalek_pubkey_serde")]
+	pub address: DalekPublicKey,
+	/// Signature of the response
+	#[serde(with = "dalek_ser::dalek_sig_serde")]
+	pub signature: DalekSignature,
+}
+
+/// Output shared by the two parties of a transaction, only spendable by both. Each
+/// party holds a share of its blinding factor and adds its partial commitment to the
+/// slate. The parties then build its range proof together, in two rounds: each party
+/// adds its T1 and T2, then its share of tau_x once all T1 and T2 are known. The last
+/// party to add its share of tau_x builds the proof.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigOutput {
+	/// Nonce of the range proof common to the parties, chosen by the initiator
+	#[serde(
+		serialize_with = "secp_ser::as_hex",
+		deserialize_with = "secp_ser::blind_from_hex"
+	)]
+	pub common_nonce: BlindingFactor,
+	/// Commitment of the output, once all the parties added their share of it
+	#[serde(with = "secp_ser::option_commitment_serde")]
+	pub commit: Option<Commitment>,
+	/// Contribution of each party to the output
+	pub parties: Vec<MultisigParty>,
+}
+
+/// Contribution of a party to a shared output
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigParty {
+	/// Id of the party's participant data in the slate
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub id: u64,
+	/// The party's share of the blinding factor times the generator, it's the public
+	/// blind excess of the party in the slate
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub partial_commit: PublicKey,
+	/// First round of the range proof, once the parties exchanged their partial
+	/// commitments
+	#[serde(with = "dalek_ser::option_pubkey_serde")]
+	pub t_one: Option<PublicKey>,
+	/// First round of the range proof, along with `t_one`
+	#[serde(with = "dalek_ser::option_pubkey_serde")]
+	pub t_two: Option<PublicKey>,
+	/// Second round of the range proof, once the first is complete
+	#[serde(with = "secp_ser::option_seckey_serde")]
+	pub tau_x: Option<SecretKey>,
+}
+
 /// Public data for each participant in the slate
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ParticipantData {
@@ -200,6 +256,16 @@ pub struct Slate {
 	/// Payment Proof
 	#[serde(default = "default_payment_none")]
 	pub payment_proof: Option<PaymentInfo>,
+	/// Signature of the recipient's response, if the recipient signs its responses
+	#[serde(default)]
+	pub response_signature: Option<ResponseSignature>,
+	/// Output shared by the parties the transaction creates, if any
+	#[serde(default)]
+	pub multisig_output: Option<MultisigOutput>,
+	/// Output shared by the parties the transaction spends, if any. The recipient
+	/// adds its share of the output to the transaction.
+	#[serde(default, with = "secp_ser::option_commitment_serde")]
+	pub multisig_input: Option<Commitment>,
 }
 
 fn default_payment_none() -> Option<PaymentInfo> {
@@ -246,6 +312,35 @@ impl Slate {
 		Ok(v3.into())
 	}
 
+	/// Restrict the slate to what wallets reading the given slate version
+	/// understand, removing optional fields introduced by later versions. Fails
+	/// if the slate relies on a feature the version can't carry.
+	pub fn restrict_to_version(&mut self, version: u16) -> Result<(), Error> {
+		if version < 2 || version > CURRENT_SLATE_VERSION {
+			return Err(ErrorKind::SlateVersion(version).into());
+		}
+		if version < 3 {
+			if self.payment_proof.is_some() {
+				return Err(ErrorKind::Compatibility(format!(
+					"payment proofs require slate version 3, but slate version {} was requested",
+					version
+				))
+				.into());
+			}
+			if self.multisig_output.is_some() || self.multisig_input.is_some() {
+				return Err(ErrorKind::Compatibility(format!(
+					"shared outputs require slate version 3, but slate version {} was requested",
+					version
+				))
+				.into());
+			}
+			self.ttl_cutoff_height = None;
+		}
+		self.version_info.version = version;
+		self.version_info.orig_version = version;
+		Ok(())
+	}
+
 	/// Create a new slate
 	pub fn blank(num_participants: usize) -> Slate {
 		Slate {
@@ -264,6 +359,9 @@ impl Slate {
 				block_header_version: EPIC_BLOCK_HEADER_VERSION,
 			},
 			payment_proof: None,
+			response_signature: None,
+			multisig_output: None,
+			multisig_input: None,
 		}
 	}
 
@@ -375,6 +473,71 @@ impl Slate {
 		Ok(())
 	}
 
+	/// Computes caller's partial signature as `fill_round_2` would, plus the secret: an
+	/// adaptor signature, which only completes the transaction's signature once the
+	/// secret is subtracted from it. It isn't added to the slate.
+	pub fn adaptor_sig<K>(
+		&self,
+		keychain: &K,
+		sec_key: &SecretKey,
+		sec_nonce: &SecretKey,
+		secret: &SecretKey,
+	) -> Result<Signature, Error>
+	where
+		K: Keychain,
+	{
+		self.check_fees()?;
+
+		self.verify_part_sigs(keychain.secp())?;
+		let pub_nonce_sum = self.pub_nonce_sum(keychain.secp())?;
+		let sig = secp::aggsig::sign_single(
+			keychain.secp(),
+			&self.msg_to_sign()?,
+			sec_key,
+			Some(sec_nonce),
+			Some(secret),
+			Some(&pub_nonce_sum),
+			Some(&self.pub_blind_sum(keychain.secp())?),
+			Some(&pub_nonce_sum),
+		)?;
+		Ok(sig)
+	}
+
+	/// Verifies the adaptor signature of a participant is its partial signature plus the
+	/// secret of the given public key
+	pub fn verify_adaptor_sig(
+		&self,
+		secp: &secp::Secp256k1,
+		participant_id: usize,
+		adaptor: &Signature,
+		secret_public: &PublicKey,
+	) -> Result<(), Error> {
+		let participant = match self.participant_with_id(participant_id) {
+			Some(p) => p,
+			None => {
+				return Err(ErrorKind::Signature(format!(
+					"No participant {} in the slate",
+					participant_id
+				)))?
+			}
+		};
+		if !secp::aggsig::verify_single(
+			secp,
+			adaptor,
+			&self.msg_to_sign()?,
+			Some(&self.pub_nonce_sum(secp)?),
+			&participant.public_blind_excess,
+			Some(&self.pub_blind_sum(secp)?),
+			Some(secret_public),
+			true,
+		) {
+			return Err(ErrorKind::Signature(
+				"Adaptor signature doesn't verify".to_owned(),
+			))?;
+		}
+		Ok(())
+	}
+
 	/// Creates the final signature, callable by either the sender or recipient
 	/// (after phase 3: sender confirmation)
 	pub fn finalize<K>(&mut self, keychain: &K) -> Result<(), Error>
@@ -788,6 +951,9 @@ impl From<Slate> for SlateV3 {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		} = slate;
 		let participant_data = map_vec!(participant_data, |data| ParticipantDataV3::from(data));
 		let version_info = VersionCompatInfoV3::from(&version_info);
@@ -795,6 +961,8 @@ impl From<Slate> for SlateV3 {
 			Some(p) => Some(PaymentInfoV3::from(&p)),
 			None => None,
 		};
+		let response_signature = response_signature.as_ref().map(ResponseSignatureV3::from);
+		let multisig_output = multisig_output.as_ref().map(MultisigOutputV3::from);
 		let tx = TransactionV3::from(tx);
 		SlateV3 {
 			num_participants,
@@ -808,6 +976,9 @@ impl From<Slate> for SlateV3 {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		}
 	}
 }
@@ -826,6 +997,9 @@ impl From<&Slate> for SlateV3 {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		} = slate;
 		let num_participants = *num_participants;
 		let id = *id;
@@ -841,6 +1015,9 @@ impl From<&Slate> for SlateV3 {
 			Some(p) => Some(PaymentInfoV3::from(p)),
 			None => None,
 		};
+		let response_signature = response_signature.as_ref().map(ResponseSignatureV3::from);
+		let multisig_output = multisig_output.as_ref().map(MultisigOutputV3::from);
+		let multisig_input = *multisig_input;
 		SlateV3 {
 			num_participants,
 			id,
@@ -853,6 +1030,9 @@ impl From<&Slate> for SlateV3 {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		}
 	}
 }
@@ -920,6 +1100,37 @@ impl From<&PaymentInfo> for PaymentInfoV3 {
 	}
 }
 
+impl From<&ResponseSignature> for ResponseSignatureV3 {
+	fn from(data: &ResponseSignature) -> ResponseSignatureV3 {
+		ResponseSignatureV3 {
+			address: data.address,
+			signature: data.signature,
+		}
+	}
+}
+
+impl From<&MultisigOutput> for MultisigOutputV3 {
+	fn from(data: &MultisigOutput) -> MultisigOutputV3 {
+		MultisigOutputV3 {
+			common_nonce: data.common_nonce.clone(),
+			commit: data.commit,
+			parties: map_vec!(data.parties, |p| MultisigPartyV3::from(p)),
+		}
+	}
+}
+
+impl From<&MultisigParty> for MultisigPartyV3 {
+	fn from(data: &MultisigParty) -> MultisigPartyV3 {
+		MultisigPartyV3 {
+			id: data.id,
+			partial_commit: data.partial_commit,
+			t_one: data.t_one,
+			t_two: data.t_two,
+			tau_x: data.tau_x.clone(),
+		}
+	}
+}
+
 impl From<Transaction> for TransactionV3 {
 	fn from(tx: Transaction) -> TransactionV3 {
 		let Transaction { offset, body } = tx;
@@ -1012,6 +1223,9 @@ impl From<SlateV3> for Slate {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		} = slate;
 		let participant_data = map_vec!(participant_data, |data| ParticipantData::from(data));
 		let version_info = VersionCompatInfo::from(&version_info);
@@ -1019,6 +1233,8 @@ impl From<SlateV3> for Slate {
 			Some(p) => Some(PaymentInfo::from(&p)),
 			None => None,
 		};
+		let response_signature = response_signature.as_ref().map(ResponseSignature::from);
+		let multisig_output = multisig_output.as_ref().map(MultisigOutput::from);
 		let tx = Transaction::from(tx);
 		Slate {
 			num_participants,
@@ -1032,6 +1248,9 @@ impl From<SlateV3> for Slate {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		}
 	}
 }
@@ -1099,6 +1318,37 @@ impl From<&PaymentInfoV3> for PaymentInfo {
 	}
 }
 
+impl From<&ResponseSignatureV3> for ResponseSignature {
+	fn from(data: &ResponseSignatureV3) -> ResponseSignature {
+		ResponseSignature {
+			address: data.address,
+			signature: data.signature,
+		}
+	}
+}
+
+impl From<&MultisigOutputV3> for MultisigOutput {
+	fn from(data: &MultisigOutputV3) -> MultisigOutput {
+		MultisigOutput {
+			common_nonce: data.common_nonce.clone(),
+			commit: data.commit,
+			parties: map_vec!(data.parties, |p| MultisigParty::from(p)),
+		}
+	}
+}
+
+impl From<&MultisigPartyV3> for MultisigParty {
+	fn from(data: &MultisigPartyV3) -> MultisigParty {
+		MultisigParty {
+			id: data.id,
+			partial_commit: data.partial_commit,
+			t_one: data.t_one,
+			t_two: data.t_two,
+			tau_x: data.tau_x.clone(),
+		}
+	}
+}
+
 impl From<TransactionV3> for Transaction {
 	fn from(tx: TransactionV3) -> Transaction {
 		let TransactionV3 { offset, body } = tx;
@@ -150,6 +150,90 @@ pub mod option_dalek_sig_serde {
 	}
 }
 
+/// Serializes an Option<Vec<pedersen::Commitment>> to and from a list of hex strings
+pub mod option_commitments_serde {
+	use serde::de::Error;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	use crate::epic_util::secp::constants::PEDERSEN_COMMITMENT_SIZE;
+	use crate::epic_util::secp::pedersen::Commitment;
+	use crate::epic_util::{from_hex, to_hex};
+
+	///
+	pub fn serialize<S>(commits: &Option<Vec<Commitment>>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match commits {
+			Some(c) => serializer.collect_seq(c.iter().map(|c| to_hex(c.0.to_vec()))),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	///
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<Commitment>>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Option::<Vec<String>>::deserialize(deserializer).and_then(|res| match res {
+			Some(strings) => strings
+				.into_iter()
+				.map(|string| {
+					let bytes = from_hex(string).map_err(|err| Error::custom(err.to_string()))?;
+					if bytes.len() != PEDERSEN_COMMITMENT_SIZE {
+						return Err(Error::custom("invalid commitment length"));
+					}
+					Ok(Commitment::from_vec(bytes))
+				})
+				.collect::<Result<Vec<_>, _>>()
+				.map(Some),
+			None => Ok(None),
+		})
+	}
+}
+
+/// Serializes an Option<secp::key::PublicKey> to and from hex
+pub mod option_pubkey_serde {
+	use serde::de::Error;
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	use crate::epic_util::secp::key::PublicKey;
+	use crate::epic_util::{from_hex, static_secp_instance, to_hex};
+
+	///
+	pub fn serialize<S>(key: &Option<PublicKey>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		match key {
+			Some(key) => {
+				let secp = static_secp_instance();
+				let secp = secp.lock();
+				serializer.serialize_str(&to_hex(key.serialize_vec(&secp, true).to_vec()))
+			}
+			None => serializer.serialize_none(),
+		}
+	}
+
+	///
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<PublicKey>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		Option::<String>::deserialize(deserializer).and_then(|res| match res {
+			Some(string) => {
+				let bytes = from_hex(string).map_err(|err| Error::custom(err.to_string()))?;
+				let secp = static_secp_instance();
+				let secp = secp.lock();
+				PublicKey::from_slice(&secp, &bytes)
+					.map(Some)
+					.map_err(|err| Error::custom(err.to_string()))
+			}
+			None => Ok(None),
+		})
+	}
+}
+
 // Test serialization methods of components that are being used
 #[cfg(test)]
 mod test {
@@ -216,4 +300,54 @@ mod test {
 			assert_eq!(s, deserialized);
 		}
 	}
+
+	#[derive(Serialize, Deserialize, PartialEq, Debug)]
+	struct CommitsTest {
+		#[serde(with = "option_commitments_serde")]
+		pub commits: Option<Vec<secp::pedersen::Commitment>>,
+	}
+
+	#[test]
+	fn ser_commitments() {
+		let c = CommitsTest {
+			commits: Some(vec![secp::pedersen::Commitment::from_vec(vec![8u8; 33])]),
+		};
+		let serialized = serde_json::to_string(&c).unwrap();
+		assert_eq!(
+			serialized,
+			format!("{{\"commits\":[\"{}\"]}}", "08".repeat(33))
+		);
+		assert_eq!(serde_json::from_str::<CommitsTest>(&serialized).unwrap(), c);
+
+		let none = CommitsTest { commits: None };
+		let serialized = serde_json::to_string(&none).unwrap();
+		assert_eq!(
+			serde_json::from_str::<CommitsTest>(&serialized).unwrap(),
+			none
+		);
+		assert!(serde_json::from_str::<CommitsTest>("{\"commits\":[\"0808\"]}").is_err());
+	}
+
+	#[derive(Serialize, Deserialize, PartialEq, Debug)]
+	struct PubkeyTest {
+		#[serde(with = "option_pubkey_serde")]
+		pub key: Option<secp::key::PublicKey>,
+	}
+
+	#[test]
+	fn ser_option_pubkey() {
+		let secp_inst = static_secp_instance();
+		let key = {
+			let secp = secp_inst.lock();
+			let mut test_rng = StepRng::new(1234567890u64, 1);
+			let sec_key = secp::key::SecretKey::new(&secp, &mut test_rng);
+			secp::key::PublicKey::from_secret_key(&secp, &sec_key).unwrap()
+		};
+		for k in vec![Some(key), None] {
+			let t = PubkeyTest { key: k };
+			let serialized = serde_json::to_string(&t).unwrap();
+			assert_eq!(serde_json::from_str::<PubkeyTest>(&serialized).unwrap(), t);
+		}
+		assert!(serde_json::from_str::<PubkeyTest>("{\"key\":\"0808\"}").is_err());
+	}
 }
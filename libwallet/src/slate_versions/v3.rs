@@ -16,13 +16,15 @@
 //! Changes from V2:
 //! * Addition of payment_proof (PaymentInfo struct)
 //! * Addition of a u64 ttl_cutoff_height field
+//! * Addition of an optional response_signature (ResponseSignature struct)
+//! * Addition of an optional multisig_output (MultisigOutput struct) and multisig_input
 
 use crate::epic_core::core::transaction::OutputFeatures;
 use crate::epic_core::libtx::secp_ser;
 use crate::epic_core::map_vec;
 use crate::epic_keychain::{BlindingFactor, Identifier};
 use crate::epic_util::secp;
-use crate::epic_util::secp::key::PublicKey;
+use crate::epic_util::secp::key::{PublicKey, SecretKey};
 use crate::epic_util::secp::pedersen::{Commitment, RangeProof};
 use crate::epic_util::secp::Signature;
 use crate::slate::CompatKernelFeatures;
@@ -71,6 +73,19 @@ pub struct SlateV3 {
 	/// Payment Proof
 	#[serde(default = "default_payment_none")]
 	pub payment_proof: Option<PaymentInfoV3>,
+	/// Signature of the recipient's response, if the recipient signs its responses
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub response_signature: Option<ResponseSignatureV3>,
+	/// Output shared by the parties the transaction creates, if any
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub multisig_output: Option<MultisigOutputV3>,
+	/// Output shared by the parties the transaction spends, if any
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "secp_ser::option_commitment_serde"
+	)]
+	pub multisig_input: Option<Commitment>,
 }
 
 fn default_payment_none() -> Option<PaymentInfoV3> {
@@ -118,6 +133,40 @@ pub struct PaymentInfoV3 {
 	pub receiver_signature: Option<DalekSignature>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResponseSignatureV3 {
+	#[serde(with = "dalek_ser::dalek_pubkey_serde")]
+	pub address: DalekPublicKey,
+	#[serde(with = "dalek_ser::dalek_sig_serde")]
+	pub signature: DalekSignature,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigOutputV3 {
+	#[serde(
+		serialize_with = "secp_ser::as_hex",
+		deserialize_with = "secp_ser::blind_from_hex"
+	)]
+	pub common_nonce: BlindingFactor,
+	#[serde(with = "secp_ser::option_commitment_serde")]
+	pub commit: Option<Commitment>,
+	pub parties: Vec<MultisigPartyV3>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigPartyV3 {
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub id: u64,
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub partial_commit: PublicKey,
+	#[serde(with = "dalek_ser::option_pubkey_serde")]
+	pub t_one: Option<PublicKey>,
+	#[serde(with = "dalek_ser::option_pubkey_serde")]
+	pub t_two: Option<PublicKey>,
+	#[serde(with = "secp_ser::option_seckey_serde")]
+	pub tau_x: Option<SecretKey>,
+}
+
 /// A transaction
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransactionV3 {
@@ -238,6 +287,9 @@ impl From<SlateV2> for SlateV3 {
 			participant_data,
 			version_info,
 			payment_proof: None,
+			response_signature: None,
+			multisig_output: None,
+			multisig_input: None,
 		}
 	}
 }
@@ -365,6 +417,9 @@ impl From<&SlateV3> for SlateV2 {
 			participant_data,
 			version_info,
 			payment_proof,
+			response_signature,
+			multisig_output,
+			multisig_input,
 		} = slate;
 		let num_participants = *num_participants;
 		let id = *id;
@@ -15,7 +15,8 @@
 //! Types and traits that should be provided by a wallet
 //! implementation
 
-use crate::config::{EpicboxConfig, TorConfig, WalletConfig};
+use crate::api_impl::types::{BalanceAlert, OutputMatured};
+use crate::config::{DerivationConfig, EpicboxConfig, TorConfig, WalletConfig};
 use crate::epic_core::core::hash::Hash;
 use crate::epic_core::core::{Output, Transaction, TxKernel};
 use crate::epic_core::libtx::{aggsig, secp_ser};
@@ -26,15 +27,18 @@ use crate::epic_util::secp::key::{PublicKey, SecretKey};
 use crate::epic_util::secp::{self, pedersen, Secp256k1};
 use crate::epic_util::ZeroingString;
 use crate::error::{Error, ErrorKind};
-use crate::slate::ParticipantMessages;
+use crate::events::StoredEvent;
+use crate::internal::selection;
+use crate::slate::{ParticipantMessages, Slate};
 use crate::slate_versions::ser as dalek_ser;
+use crate::view_key::ViewKey;
 use chrono::prelude::*;
 use ed25519_dalek::PublicKey as DalekPublicKey;
 use ed25519_dalek::Signature as DalekSignature;
 use failure::ResultExt;
 use serde;
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use uuid::Uuid;
@@ -64,6 +68,14 @@ where
 	/// default is assumed to be ~/.epic/main/wallet_data (or floonet equivalent)
 	fn get_top_level_directory(&self) -> Result<String, Error>;
 
+	/// Sets whether wallets are opened with a read-only store, which can be used
+	/// alongside another process writing to it. Writes through it are refused.
+	fn set_read_only(&mut self, read_only: bool) -> Result<(), Error>;
+
+	/// Sets the address and API secret of the node the open wallet, and the wallets
+	/// opened from now on, talk to
+	fn set_node(&mut self, node_url: &str, node_api_secret: Option<String>) -> Result<(), Error>;
+
 	/// Output a epic-wallet.toml file into the current top-level system wallet directory
 	fn create_config(
 		&self,
@@ -81,6 +93,7 @@ where
 		name: Option<&str>,
 		mnemonic: Option<ZeroingString>,
 		mnemonic_length: usize,
+		language: Option<MnemonicLanguage>,
 		password: ZeroingString,
 		test_mode: bool,
 	) -> Result<(), Error>;
@@ -100,6 +113,34 @@ where
 	/// whether a wallet exists at the given directory
 	fn wallet_exists(&self, name: Option<&str>) -> Result<bool, Error>;
 
+	/// Export the view key of an account of the wallet, from which a watch-only wallet
+	/// can be created
+	fn export_view_key(
+		&self,
+		name: Option<&str>,
+		password: ZeroingString,
+		parent_key_id: &Identifier,
+	) -> Result<ViewKey, Error>;
+
+	/// Create a watch-only wallet tracking the account of the given view key. It has no
+	/// seed nor password and can't sign, and scans the chain on its first update.
+	fn create_watch_only_wallet(
+		&mut self,
+		name: Option<&str>,
+		view_key: ViewKey,
+	) -> Result<(), Error>;
+
+	/// The view key of the wallet, if it's watch-only
+	fn view_key(&self, name: Option<&str>) -> Result<Option<ViewKey>, Error>;
+
+	/// Open a snapshot of another wallet, as written by `db export`, in place of the
+	/// wallet of the top level directory. It has no keys, its store refuses writes
+	/// and it's never updated from the node.
+	fn open_snapshot(&mut self, snapshot_file: &str) -> Result<(), Error>;
+
+	/// The file of the snapshot open, if a snapshot rather than a wallet is open
+	fn snapshot(&self) -> Option<String>;
+
 	/// return mnemonic of given wallet
 	fn get_mnemonic(
 		&self,
@@ -118,6 +159,12 @@ where
 		password: ZeroingString,
 	) -> Result<(), Error>;
 
+	/// whether the user has confirmed a backup of the wallet's recovery phrase
+	fn seed_verified(&self, name: Option<&str>) -> Result<bool, Error>;
+
+	/// record whether the user has confirmed a backup of the wallet's recovery phrase
+	fn set_seed_verified(&self, name: Option<&str>, verified: bool) -> Result<(), Error>;
+
 	/// changes password
 	fn change_password(
 		&self,
@@ -239,6 +286,57 @@ where
 
 	/// Flag whether the wallet needs a full UTXO scan on next update attempt
 	fn init_status<'a>(&mut self) -> Result<WalletInitStatus, Error>;
+
+	/// Layout of the key derivation paths of accounts, stored when the wallet was created
+	fn derivation_config(&self) -> Result<DerivationConfig, Error>;
+
+	/// Iterate over the stored usage counters of all slate transports
+	fn transport_metrics_iter<'a>(&'a self) -> Box<dyn Iterator<Item = TransportMetrics> + 'a>;
+
+	/// Gets the stored usage counters for the given slate transport
+	fn get_transport_metrics(&self, transport: &str) -> Result<Option<TransportMetrics>, Error>;
+
+	/// Iterate over the epicbox keys pinned for known contacts
+	fn pinned_keys_iter<'a>(&'a self) -> Box<dyn Iterator<Item = PinnedKey> + 'a>;
+
+	/// Gets the epicbox key pinned for the given contact
+	fn get_pinned_key(&self, contact: &str) -> Result<Option<PinnedKey>, Error>;
+
+	/// Iterate over the contacts of the address book
+	fn contacts_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Contact> + 'a>;
+
+	/// Gets the address book contact with the given name
+	fn get_contact(&self, name: &str) -> Result<Option<Contact>, Error>;
+
+	/// Iterate over the saved send templates
+	fn send_templates_iter<'a>(&'a self) -> Box<dyn Iterator<Item = SendTemplate> + 'a>;
+
+	/// Gets the send template with the given name
+	fn get_send_template(&self, name: &str) -> Result<Option<SendTemplate>, Error>;
+
+	/// Gets the response this wallet signed for the slate with the given id, if kept
+	fn get_slate_response(&self, slate_id: &Uuid) -> Result<Option<Slate>, Error>;
+
+	/// Gets the metadata entry stored under the given namespace and key
+	fn get_metadata(&self, namespace: &str, key: &str) -> Result<Option<MetadataEntry>, Error>;
+
+	/// Iterate over all stored metadata entries
+	fn metadata_iter<'a>(&'a self) -> Box<dyn Iterator<Item = MetadataEntry> + 'a>;
+
+	/// Gets the cached header of the block at the given height
+	fn get_block_header(&self, height: u64) -> Result<Option<BlockHeaderInfo>, Error>;
+
+	/// Iterate over all cached block headers
+	fn block_headers_iter<'a>(&'a self) -> Box<dyn Iterator<Item = BlockHeaderInfo> + 'a>;
+
+	/// Iterate over the atomic swaps of the wallet
+	fn swaps_iter<'a>(&'a self) -> Box<dyn Iterator<Item = Swap> + 'a>;
+
+	/// Gets the atomic swap with the given id
+	fn get_swap(&self, id: &Uuid) -> Result<Option<Swap>, Error>;
+
+	/// Iterate over the events kept in the store
+	fn events_iter<'a>(&'a self) -> Box<dyn Iterator<Item = StoredEvent> + 'a>;
 }
 
 /// Batch trait to update the output data backend atomically. Trying to use a
@@ -292,6 +390,61 @@ where
 	/// Save flag indicating whether wallet needs a full UTXO scan
 	fn save_init_status<'a>(&mut self, value: WalletInitStatus) -> Result<(), Error>;
 
+	/// Save the layout of the key derivation paths of accounts
+	fn save_derivation_config(&mut self, config: DerivationConfig) -> Result<(), Error>;
+
+	/// Save the usage counters of a slate transport
+	fn save_transport_metrics(&mut self, metrics: TransportMetrics) -> Result<(), Error>;
+
+	/// Save the epicbox key pinned for a contact
+	fn save_pinned_key(&mut self, key: PinnedKey) -> Result<(), Error>;
+
+	/// Forget the epicbox key pinned for a contact
+	fn delete_pinned_key(&mut self, contact: &str) -> Result<(), Error>;
+
+	/// Add or update a contact of the address book
+	fn save_contact(&mut self, contact: Contact) -> Result<(), Error>;
+
+	/// Remove a contact from the address book
+	fn delete_contact(&mut self, name: &str) -> Result<(), Error>;
+
+	/// Add or replace a send template
+	fn save_send_template(&mut self, template: SendTemplate) -> Result<(), Error>;
+
+	/// Remove a send template
+	fn delete_send_template(&mut self, name: &str) -> Result<(), Error>;
+
+	/// Keep the response signed for a received slate, returned again to a sender
+	/// retrying the exchange
+	fn save_slate_response(&mut self, slate: Slate) -> Result<(), Error>;
+
+	/// Forget the response kept for a received slate
+	fn delete_slate_response(&mut self, slate_id: &Uuid) -> Result<(), Error>;
+
+	/// Add or replace a metadata entry
+	fn save_metadata(&mut self, entry: MetadataEntry) -> Result<(), Error>;
+
+	/// Remove the metadata entry stored under the given namespace and key
+	fn delete_metadata(&mut self, namespace: &str, key: &str) -> Result<(), Error>;
+
+	/// Add or replace a cached block header
+	fn save_block_header(&mut self, header: BlockHeaderInfo) -> Result<(), Error>;
+
+	/// Remove the cached header of the block at the given height
+	fn delete_block_header(&mut self, height: u64) -> Result<(), Error>;
+
+	/// Add or update an atomic swap
+	fn save_swap(&mut self, swap: Swap) -> Result<(), Error>;
+
+	/// Iterate over the events kept in the store
+	fn events_iter(&self) -> Box<dyn Iterator<Item = StoredEvent>>;
+
+	/// Keep an event in the store
+	fn save_event(&mut self, event: StoredEvent) -> Result<(), Error>;
+
+	/// Remove the event with the given id from the store
+	fn delete_event(&mut self, id: u64) -> Result<(), Error>;
+
 	/// get next output history table id
 	fn next_output_history_id(&mut self) -> Result<u32, Error>;
 
@@ -400,6 +553,61 @@ pub trait NodeClient: Send + Sync + Clone {
 		start_height: u64,
 		end_height: Option<u64>,
 	) -> Result<(u64, u64), Error>;
+
+	/// Get the header of the block at the given height on the node's chain, if any
+	fn get_header_info(&self, height: u64) -> Result<Option<BlockHeaderInfo>, Error>;
+
+	/// Get the headers of the blocks between the given heights, inclusive, on the
+	/// node's chain, ordered by height. Heights above the node's tip are left out.
+	fn get_headers(
+		&self,
+		start_height: u64,
+		end_height: u64,
+	) -> Result<Vec<BlockHeaderInfo>, Error>;
+
+	/// Latency statistics of the requests made to the node since the client was
+	/// created, for each method of the client used
+	fn request_metrics(&self) -> Vec<NodeRequestMetrics>;
+}
+
+/// Check applied by the wallet's listeners to an incoming slate before signing it
+/// as recipient, allowing a payment to be refused in real time
+pub trait ReceiveHook: Send + Sync {
+	/// Accept the slate, or reject it with `ErrorKind::ReceiveRejected` giving the
+	/// reason returned to the sender
+	fn check(&self, slate: &Slate) -> Result<(), Error>;
+}
+
+/// Hooks run by the Owner API around the transactions it sends, e.g. to have sends
+/// approved by an external workflow or to log them
+pub trait TxHook: Send + Sync {
+	/// Run on a new slate before it's sent. Rejecting it with `ErrorKind::SendRejected`
+	/// vetoes the send.
+	fn pre_send(&self, slate: &Slate) -> Result<(), Error>;
+	/// Run on a slate once it's finalized. Errors are only logged, the transaction
+	/// being complete already.
+	fn post_finalize(&self, slate: &Slate) -> Result<(), Error>;
+}
+
+/// Notified by the updater thread of the balance alerts it raises, e.g. to page
+/// an operator before an account runs dry
+pub trait BalanceAlertHook: Send + Sync {
+	/// Deliver the alert
+	fn alert(&self, alert: &BalanceAlert) -> Result<(), Error>;
+}
+
+/// Notified by the updater thread of the wallet's outputs becoming spendable, e.g.
+/// to tell a miner the moment a reward matures
+pub trait MaturityHook: Send + Sync {
+	/// Deliver the notification
+	fn matured(&self, output: &OutputMatured) -> Result<(), Error>;
+}
+
+/// Source of the exchange rates of EPIC to fiat currencies, e.g. for the fiat value
+/// of a transaction at the time it was made
+pub trait PriceFeed: Send + Sync {
+	/// Current price of one EPIC in the currency, given by its ISO 4217 code, e.g. `USD`
+	fn price(&self, currency: &str) -> Result<f64, Error>;
 }
 
 /// Node version info
@@ -413,6 +621,18 @@ pub struct NodeVersionInfo {
 	pub verified: Option<bool>,
 }
 
+/// Block header details kept in the wallet's header cache
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlockHeaderInfo {
+	/// Height of the block
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub height: u64,
+	/// Hash of the block, hex encoded
+	pub hash: String,
+	/// Time the block was mined
+	pub timestamp: DateTime<Utc>,
+}
+
 /// Information about an output that's being tracked by the wallet. Must be
 /// enough to reconstruct the commitment associated with the ouput when the
 /// root private key is known.
@@ -446,6 +666,13 @@ pub struct OutputData {
 	pub is_coinbase: bool,
 	/// Optional corresponding internal entry in tx entry log
 	pub tx_log_entry: Option<u32>,
+	/// Whether the output is kept out of coin selection, e.g. to hold a reserve
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub frozen: bool,
+	/// Whether the output is shared with another wallet, this wallet only holding its
+	/// share of the blinding factor. Only spendable along with the other wallet.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub multisig: bool,
 }
 
 impl ser::Writeable for OutputData {
@@ -490,6 +717,8 @@ impl OutputData {
 	pub fn eligible_to_spend(&self, current_height: u64, minimum_confirmations: u64) -> bool {
 		if [OutputStatus::Spent, OutputStatus::Locked].contains(&self.status) {
 			return false;
+		} else if self.frozen || self.multisig {
+			return false;
 		} else if self.status == OutputStatus::Unconfirmed && self.is_coinbase {
 			return false;
 		} else if self.lock_height > current_height {
@@ -574,6 +803,20 @@ pub struct Context {
 	pub participant_id: usize,
 	/// Payment proof sender address derivation path, if needed
 	pub payment_proof_derivation_index: Option<u32>,
+	/// Value of the decoy change output, if one was built
+	#[serde(default)]
+	pub decoy_amount: Option<u64>,
+	/// Address the recipient's response must be signed with, if required
+	#[serde(default, with = "dalek_ser::option_dalek_pubkey_serde")]
+	pub response_address: Option<DalekPublicKey>,
+	/// Key of our share of the output shared with the other party, if the
+	/// transaction creates one
+	#[serde(default)]
+	pub multisig_key_id: Option<Identifier>,
+	/// Approval the send requires, given to its log entry when the outputs are
+	/// locked
+	#[serde(default)]
+	pub approval: Option<TxApproval>,
 }
 
 impl Context {
@@ -598,6 +841,10 @@ impl Context {
 			fee: 0,
 			participant_id,
 			payment_proof_derivation_index: None,
+			decoy_amount: None,
+			response_address: None,
+			multisig_key_id: None,
+			approval: None,
 		}
 	}
 }
@@ -765,6 +1012,16 @@ impl fmt::Display for TxLogEntryType {
 	}
 }
 
+/// Approval of a send by a second operator, required before it's finalized
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxApproval {
+	/// Waiting for the approval, the send can't be finalized
+	Pending,
+	/// Approved, the send can be finalized
+	Approved,
+}
+
 /// Optional transaction information, recorded when an event happens
 /// to add or remove funds from a wallet. One Transaction log entry
 /// maps to one or many outputs
@@ -820,6 +1077,45 @@ pub struct TxLogEntry {
 	/// Additional info needed to stored payment proof
 	#[serde(default)]
 	pub payment_proof: Option<StoredProofInfo>,
+	/// Tags categorizing this transaction, e.g. "payroll" or "mining"
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub tags: Vec<String>,
+	/// Name of the address book contact the transaction was made with, matched
+	/// on the payment proof address when the entry is retrieved
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub contact: Option<String>,
+	/// Value of the decoy output sent back to ourselves with the transaction, if any.
+	/// It's one of the change outputs and already counted in `amount_credited`.
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "secp_ser::opt_string_or_u64"
+	)]
+	pub decoy_amount: Option<u64>,
+	/// Weight and size of the whole transaction, estimated when it's created and
+	/// updated once it's finalized
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tx_size: Option<TxSizeInfo>,
+	/// Number and value of the coinbase and plain outputs spent by a send
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub input_composition: Option<InputComposition>,
+	/// Features of the transaction's kernel, plain or locked at a height
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub kernel_features: Option<TxKernelFeatures>,
+	/// Transport the slate was sent to the recipient with, e.g. "epicbox"
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub transport: Option<String>,
+	/// Version of the slate the transaction was exchanged in with the other party
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub slate_version: Option<u16>,
+	/// Approval of the send by a second operator, if sends required one when it
+	/// was initiated
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub approval: Option<TxApproval>,
+	/// Why the send was abandoned before it was completed, e.g. the recipient not
+	/// responding in time
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub failure_reason: Option<String>,
 }
 
 impl ser::Writeable for TxLogEntry {
@@ -857,9 +1153,25 @@ impl TxLogEntry {
 			kernel_excess: None,
 			kernel_lookup_min_height: None,
 			payment_proof: None,
+			tags: vec![],
+			contact: None,
+			decoy_amount: None,
+			tx_size: None,
+			input_composition: None,
+			kernel_features: None,
+			transport: None,
+			slate_version: None,
+			approval: None,
+			failure_reason: None,
 		}
 	}
 
+	/// Whether this entry carries all of the given tags
+	pub fn has_tags(&self, tags: &[String]) -> bool {
+		tags.iter()
+			.all(|t| self.tags.contains(&t.trim().to_lowercase()))
+	}
+
 	/// Given a vec of TX log entries, return credited + debited sums
 	pub fn sum_confirmed(txs: &Vec<TxLogEntry>) -> (u64, u64) {
 		txs.iter().fold((0, 0), |acc, tx| match tx.confirmed {
@@ -872,6 +1184,185 @@ impl TxLogEntry {
 	pub fn update_confirmation_ts(&mut self) {
 		self.confirmation_ts = Some(Utc::now());
 	}
+
+	/// Mark the transaction as confirmed at the given time, e.g. the timestamp of
+	/// the block it was confirmed in
+	pub fn confirm_at(&mut self, ts: DateTime<Utc>) {
+		self.confirm();
+		self.confirmation_ts = Some(ts);
+	}
+
+	/// Mark the transaction as confirmed now, completing a promised payment proof
+	pub fn confirm(&mut self) {
+		self.update_confirmation_ts();
+		self.confirmed = true;
+		if let Some(ref mut p) = self.payment_proof {
+			if p.state == Some(PaymentProofState::Promised) {
+				p.state = Some(PaymentProofState::Received);
+			}
+		}
+	}
+
+	/// Payment proof address of the other party to the transaction, if known
+	pub fn counterparty_address(&self) -> Option<&DalekPublicKey> {
+		self.payment_proof.as_ref().map(|p| match self.tx_type {
+			TxLogEntryType::TxReceived | TxLogEntryType::TxReceivedCancelled => &p.sender_address,
+			_ => &p.receiver_address,
+		})
+	}
+
+	/// State of the payment proof of this transaction, if one was requested
+	pub fn payment_proof_state(&self) -> Option<PaymentProofState> {
+		self.payment_proof.as_ref().map(|p| match p.state {
+			Some(s) => s,
+			// entries stored before the state was tracked
+			None if p.receiver_signature.is_none() => PaymentProofState::Requested,
+			None if !self.confirmed => PaymentProofState::Promised,
+			None => PaymentProofState::Received,
+		})
+	}
+}
+
+/// Weight and serialized size of a transaction, to check it against relay limits
+/// and compare its fee with its weight
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TxSizeInfo {
+	/// Number of inputs of the whole transaction
+	pub num_inputs: usize,
+	/// Number of outputs of the whole transaction
+	pub num_outputs: usize,
+	/// Number of kernels
+	pub num_kernels: usize,
+	/// Weight, as counted against the block weight limit
+	pub weight: u64,
+	/// Serialized size in bytes, only known once the transaction is finalized
+	#[serde(default)]
+	pub size: Option<u64>,
+}
+
+impl TxSizeInfo {
+	/// Estimate for a transaction which isn't complete yet
+	pub fn estimate(num_inputs: usize, num_outputs: usize, num_kernels: usize) -> TxSizeInfo {
+		TxSizeInfo {
+			num_inputs,
+			num_outputs,
+			num_kernels,
+			weight: selection::tx_weight(num_inputs, num_outputs, num_kernels),
+			size: None,
+		}
+	}
+
+	/// Figures of a complete transaction
+	pub fn from_tx(tx: &Transaction) -> TxSizeInfo {
+		let body = &tx.body;
+		let mut info =
+			TxSizeInfo::estimate(body.inputs.len(), body.outputs.len(), body.kernels.len());
+		info.size = ser::ser_vec(tx, ser::ProtocolVersion(1))
+			.ok()
+			.map(|v| v.len() as u64);
+		info
+	}
+}
+
+/// Outputs spent by a transaction by their features, so sends mixing mining rewards
+/// with other funds can be told apart
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct InputComposition {
+	/// Number of coinbase outputs spent
+	pub coinbase_inputs: usize,
+	/// Total value of the coinbase outputs spent
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub coinbase_amount: u64,
+	/// Number of plain outputs spent
+	pub plain_inputs: usize,
+	/// Total value of the plain outputs spent
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub plain_amount: u64,
+}
+
+impl InputComposition {
+	/// Composition of the given outputs of the wallet
+	pub fn from_outputs<'a, I>(outputs: I) -> InputComposition
+	where
+		I: IntoIterator<Item = &'a OutputData>,
+	{
+		let mut retval = InputComposition::default();
+		for out in outputs {
+			match out.is_coinbase {
+				true => {
+					retval.coinbase_inputs += 1;
+					retval.coinbase_amount += out.value;
+				}
+				false => {
+					retval.plain_inputs += 1;
+					retval.plain_amount += out.value;
+				}
+			}
+		}
+		retval
+	}
+
+	/// Whether both coinbase and plain outputs are spent
+	pub fn is_mixed(&self) -> bool {
+		self.coinbase_inputs > 0 && self.plain_inputs > 0
+	}
+}
+
+/// Features of the kernel of a transaction, chosen by its sender
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TxKernelFeatures {
+	/// Kernel valid in any block
+	Plain,
+	/// Kernel not valid in blocks below the lock height, so the transaction can't
+	/// be confirmed before it
+	HeightLocked {
+		/// Height of the first block the kernel is valid in
+		#[serde(with = "secp_ser::string_or_u64")]
+		lock_height: u64,
+	},
+}
+
+impl TxKernelFeatures {
+	/// Features of a kernel locked at the given height, plain if 0
+	pub fn from_lock_height(lock_height: u64) -> TxKernelFeatures {
+		match lock_height {
+			0 => TxKernelFeatures::Plain,
+			_ => TxKernelFeatures::HeightLocked { lock_height },
+		}
+	}
+
+	/// Lock height of the kernel, 0 for a plain kernel
+	pub fn lock_height(&self) -> u64 {
+		match self {
+			TxKernelFeatures::Plain => 0,
+			TxKernelFeatures::HeightLocked { lock_height } => *lock_height,
+		}
+	}
+}
+
+/// Progress of the payment proof a sender requested from the recipient
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PaymentProofState {
+	/// Requested from the recipient, who hasn't signed it yet
+	Requested,
+	/// Signed by the recipient, but the transaction isn't confirmed yet
+	Promised,
+	/// The transaction is confirmed, so the proof can be verified against the chain
+	Received,
+	/// The proof was verified against the chain by this wallet
+	Verified,
+}
+
+impl fmt::Display for PaymentProofState {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			PaymentProofState::Requested => write!(f, "Requested"),
+			PaymentProofState::Promised => write!(f, "Promised"),
+			PaymentProofState::Received => write!(f, "Received"),
+			PaymentProofState::Verified => write!(f, "Verified"),
+		}
+	}
 }
 
 /// Payment proof information. Differs from what is sent via
@@ -892,6 +1383,9 @@ pub struct StoredProofInfo {
 	/// sender signature
 	#[serde(with = "dalek_ser::option_dalek_sig_serde")]
 	pub sender_signature: Option<DalekSignature>,
+	/// Progress of the proof, not stored by older wallets
+	#[serde(default)]
+	pub state: Option<PaymentProofState>,
 }
 
 impl ser::Writeable for StoredProofInfo {
@@ -914,6 +1408,10 @@ pub struct AcctPathMapping {
 	pub label: String,
 	/// Corresponding parent BIP32 derivation path
 	pub path: Identifier,
+	/// Arbitrary key/value metadata attached to the account, such as a
+	/// customer id or department code
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub metadata: BTreeMap<String, String>,
 }
 
 impl ser::Writeable for AcctPathMapping {
@@ -998,3 +1496,460 @@ impl ser::Readable for WalletInitStatus {
 		serde_json::from_slice(&data[..]).map_err(|_| ser::Error::CorruptedData)
 	}
 }
+
+/// Language of the words of a BIP39 recovery phrase, the seed being the same in any
+/// of them
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicLanguage {
+	/// English
+	English,
+	/// Simplified Chinese
+	ChineseSimplified,
+	/// Traditional Chinese
+	ChineseTraditional,
+	/// Czech
+	Czech,
+	/// French
+	French,
+	/// Italian
+	Italian,
+	/// Japanese
+	Japanese,
+	/// Korean
+	Korean,
+	/// Portuguese
+	Portuguese,
+	/// Spanish
+	Spanish,
+}
+
+impl MnemonicLanguage {
+	/// All the languages of the standard BIP39 wordlists
+	pub const ALL: [MnemonicLanguage; 10] = [
+		MnemonicLanguage::English,
+		MnemonicLanguage::ChineseSimplified,
+		MnemonicLanguage::ChineseTraditional,
+		MnemonicLanguage::Czech,
+		MnemonicLanguage::French,
+		MnemonicLanguage::Italian,
+		MnemonicLanguage::Japanese,
+		MnemonicLanguage::Korean,
+		MnemonicLanguage::Portuguese,
+		MnemonicLanguage::Spanish,
+	];
+
+	/// Name of the language in the APIs and on the command line, e.g. `chinese_simplified`
+	pub fn code(&self) -> &'static str {
+		match self {
+			MnemonicLanguage::English => "english",
+			MnemonicLanguage::ChineseSimplified => "chinese_simplified",
+			MnemonicLanguage::ChineseTraditional => "chinese_traditional",
+			MnemonicLanguage::Czech => "czech",
+			MnemonicLanguage::French => "french",
+			MnemonicLanguage::Italian => "italian",
+			MnemonicLanguage::Japanese => "japanese",
+			MnemonicLanguage::Korean => "korean",
+			MnemonicLanguage::Portuguese => "portuguese",
+			MnemonicLanguage::Spanish => "spanish",
+		}
+	}
+
+	/// Language of the given name, as returned by `code`
+	pub fn from_code(code: &str) -> Option<MnemonicLanguage> {
+		MnemonicLanguage::ALL
+			.iter()
+			.find(|l| l.code() == code)
+			.cloned()
+	}
+}
+
+impl Default for MnemonicLanguage {
+	fn default() -> MnemonicLanguage {
+		MnemonicLanguage::English
+	}
+}
+
+impl fmt::Display for MnemonicLanguage {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.code())
+	}
+}
+
+/// Slate transport event recorded in the transport metrics
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransportEvent {
+	/// A slate of the given size in bytes was received
+	Received(u64),
+	/// A slate of the given size in bytes was sent
+	Sent(u64),
+	/// Sending or receiving a slate failed
+	Failure,
+}
+
+impl TransportEvent {
+	/// The given slate was sent, sized by its json serialization
+	pub fn slate_sent<T: serde::Serialize>(slate: &T) -> TransportEvent {
+		TransportEvent::Sent(TransportEvent::slate_size(slate))
+	}
+
+	/// The given slate was received, sized by its json serialization
+	pub fn slate_received<T: serde::Serialize>(slate: &T) -> TransportEvent {
+		TransportEvent::Received(TransportEvent::slate_size(slate))
+	}
+
+	fn slate_size<T: serde::Serialize>(slate: &T) -> u64 {
+		serde_json::to_vec(slate)
+			.map(|v| v.len() as u64)
+			.unwrap_or(0)
+	}
+}
+
+/// Usage counters of a single slate transport (http, tor, epicbox), persisted
+/// across restarts
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TransportMetrics {
+	/// Transport name
+	pub transport: String,
+	/// Number of slates received
+	pub slates_received: u64,
+	/// Number of slates sent
+	pub slates_sent: u64,
+	/// Number of failed sends or receives
+	pub failures: u64,
+	/// Total size of received slates, in bytes
+	pub bytes_received: u64,
+	/// Total size of sent slates, in bytes
+	pub bytes_sent: u64,
+}
+
+impl TransportMetrics {
+	/// Empty counters for the given transport
+	pub fn new(transport: &str) -> TransportMetrics {
+		TransportMetrics {
+			transport: transport.to_owned(),
+			slates_received: 0,
+			slates_sent: 0,
+			failures: 0,
+			bytes_received: 0,
+			bytes_sent: 0,
+		}
+	}
+
+	/// Update the counters with the given event
+	pub fn record(&mut self, event: &TransportEvent) {
+		match event {
+			TransportEvent::Received(bytes) => {
+				self.slates_received += 1;
+				self.bytes_received += bytes;
+			}
+			TransportEvent::Sent(bytes) => {
+				self.slates_sent += 1;
+				self.bytes_sent += bytes;
+			}
+			TransportEvent::Failure => self.failures += 1,
+		}
+	}
+}
+
+/// Requests made to the node with a method of the node client, kept in memory while
+/// the wallet runs. Latencies are over the latest 100 requests.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct NodeRequestMetrics {
+	/// Method of the node client
+	pub method: String,
+	/// Pattern of the node API URLs requested by the method
+	pub url_pattern: String,
+	/// Number of requests made
+	pub requests: u64,
+	/// Number of failed requests
+	pub failures: u64,
+	/// Number of requests slower than the configured threshold
+	pub slow_requests: u64,
+	/// Average duration, in milliseconds
+	pub avg_ms: u64,
+	/// 95th percentile of the durations, in milliseconds
+	pub p95_ms: u64,
+	/// Longest duration, in milliseconds
+	pub max_ms: u64,
+}
+
+/// Epicbox key of a contact, pinned the first time a slate is exchanged with
+/// them so a key substituted on a later payment can be detected
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PinnedKey {
+	/// Contact, identified by the onion v3 payment proof address of their wallet
+	pub contact: String,
+	/// Epicbox public key the contact was first seen with
+	pub epicbox_key: String,
+	/// When the key was pinned
+	pub first_seen: DateTime<Utc>,
+	/// When a slate was last exchanged with the pinned key
+	pub last_seen: DateTime<Utc>,
+	/// Number of slates exchanged with the pinned key
+	pub times_seen: u64,
+}
+
+impl PinnedKey {
+	/// Pin the given key for the contact
+	pub fn new(contact: &str, epicbox_key: &str) -> PinnedKey {
+		let now = Utc::now();
+		PinnedKey {
+			contact: contact.to_owned(),
+			epicbox_key: epicbox_key.to_owned(),
+			first_seen: now,
+			last_seen: now,
+			times_seen: 1,
+		}
+	}
+}
+
+/// Entry of the wallet's address book
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Contact {
+	/// Name the contact is known by, unique within the wallet
+	pub name: String,
+	/// Onion v3 payment proof address of the contact's wallet
+	pub proof_address: String,
+	/// Epicbox address of the contact, if known
+	#[serde(default)]
+	pub epicbox_address: Option<String>,
+	/// HTTP(S) listener address of the contact's wallet, if known
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub http_address: Option<String>,
+	/// Transport to try first when sending to the contact, one of
+	/// `CONTACT_TRANSPORTS`
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub preferred_transport: Option<String>,
+	/// Transport the last slate sent to the contact went through
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_transport: Option<String>,
+}
+
+/// Transports a slate can be sent to a contact with, in the order they're
+/// tried when the contact has no preference
+pub const CONTACT_TRANSPORTS: [&str; 3] = ["epicbox", "tor", "http"];
+
+impl Contact {
+	/// Destination of the given transport for the contact, if its address for
+	/// the transport is known
+	pub fn transport_dest(&self, transport: &str) -> Option<String> {
+		match transport {
+			"epicbox" => self.epicbox_address.clone(),
+			"tor" => Some(self.proof_address.clone()),
+			"http" => self.http_address.clone(),
+			_ => None,
+		}
+	}
+
+	/// Transports to try in turn when sending to the contact with their destinations:
+	/// the preferred one, the one that last worked, then the others in the default
+	/// order, leaving out those the contact has no address for
+	pub fn transports(&self) -> Vec<(String, String)> {
+		let mut order: Vec<&str> = vec![];
+		for t in self
+			.preferred_transport
+			.iter()
+			.chain(self.last_transport.iter())
+			.map(|t| t.as_str())
+			.chain(CONTACT_TRANSPORTS.iter().cloned())
+		{
+			if !order.contains(&t) {
+				order.push(t);
+			}
+		}
+		order
+			.into_iter()
+			.filter_map(|t| self.transport_dest(t).map(|d| (t.to_owned(), d)))
+			.collect()
+	}
+}
+
+/// Send saved under a name, executed again with `send --template` for routine
+/// payments
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SendTemplate {
+	/// Name the template is known by, unique within the wallet
+	pub name: String,
+	/// Method the slate is sent with, one of `SEND_TEMPLATE_METHODS`
+	pub method: String,
+	/// Destination of the slate, as given to `send`, e.g. an address book contact as `@name`
+	pub dest: String,
+	/// Amount sent, in nanoepics
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Account sent from, the active account if not set
+	#[serde(default)]
+	pub src_acct_name: Option<String>,
+	/// Participant message included in the slate
+	#[serde(default)]
+	pub message: Option<String>,
+}
+
+/// Methods a send template can send with, those of `send` which need a destination
+pub const SEND_TEMPLATE_METHODS: [&str; 5] = ["http", "file", "keybase", "epicbox", "lan"];
+
+/// Value stored by an application in the wallet's metadata store, e.g. a user
+/// preference or an annotation kept by a GUI wallet
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MetadataEntry {
+	/// Namespace of the application owning the entry
+	pub namespace: String,
+	/// Key of the entry within its namespace
+	pub key: String,
+	/// Stored value
+	pub value: String,
+	/// Time the value was last set
+	pub updated_ts: DateTime<Utc>,
+}
+
+/// Outcome of checking a contact's epicbox key against the pinned one
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyPinCheck {
+	/// First slate with the contact, the key is now pinned
+	New,
+	/// The key matches the pinned one
+	Known,
+	/// The contact was pinned with a different key, which is left unchanged
+	Changed(PinnedKey),
+}
+
+/// Currency epics are swapped for
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum SwapCurrency {
+	/// Bitcoin, amounts in satoshis
+	Btc,
+	/// Litecoin, amounts in litoshis
+	Ltc,
+}
+
+impl fmt::Display for SwapCurrency {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			SwapCurrency::Btc => write!(f, "BTC"),
+			SwapCurrency::Ltc => write!(f, "LTC"),
+		}
+	}
+}
+
+/// Side the wallet takes in an atomic swap
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum SwapRole {
+	/// Sells epics for the secondary currency
+	Seller,
+	/// Buys epics with the secondary currency
+	Buyer,
+}
+
+/// Progress of an atomic swap
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum SwapStatus {
+	/// Offered by the seller, not accepted yet
+	Offered,
+	/// Accepted by the buyer, the lock transaction of the epics isn't signed yet
+	Accepted,
+	/// The lock transaction is signed, the epics are locked once it's confirmed
+	Locking,
+	/// The seller started the transaction redeeming the locked epics to the buyer
+	Redeeming,
+	/// The buyer's adaptor signature of the redeem transaction was exchanged, the buyer
+	/// locks the secondary currency
+	SecondaryLocking,
+	/// The seller signed the redeem transaction, posting it reveals the secret of the
+	/// secondary currency to the seller
+	Signed,
+	/// Completed, each party can take what it swapped for
+	Redeemed,
+	/// The seller took the locked epics back
+	Refunded,
+	/// Abandoned before the epics were redeemed
+	Cancelled,
+}
+
+impl fmt::Display for SwapStatus {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			SwapStatus::Offered => write!(f, "Offered"),
+			SwapStatus::Accepted => write!(f, "Accepted"),
+			SwapStatus::Locking => write!(f, "Locking"),
+			SwapStatus::Redeeming => write!(f, "Redeeming"),
+			SwapStatus::SecondaryLocking => write!(f, "Secondary locking"),
+			SwapStatus::Signed => write!(f, "Signed"),
+			SwapStatus::Redeemed => write!(f, "Redeemed"),
+			SwapStatus::Refunded => write!(f, "Refunded"),
+			SwapStatus::Cancelled => write!(f, "Cancelled"),
+		}
+	}
+}
+
+/// Atomic swap of epics for another currency, as stored by each party. The epics are
+/// locked in an output shared by both parties, the secondary currency in a script
+/// redeemable by the seller with the secret the buyer's redeem transaction of the epics
+/// reveals, or refundable to the buyer once its lock time passed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Swap {
+	/// Id of the swap, that of its lock transaction
+	pub id: Uuid,
+	/// Our side of the swap
+	pub role: SwapRole,
+	/// Progress of the swap
+	pub status: SwapStatus,
+	/// Account the epics are sent from or received to
+	pub parent_key_id: Identifier,
+	/// Amount of epics swapped, in nanoepics
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub amount: u64,
+	/// Currency the epics are swapped for
+	pub secondary_currency: SwapCurrency,
+	/// Amount of the secondary currency, in its smallest unit
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub secondary_amount: u64,
+	/// Height from which the seller can take the locked epics back
+	#[serde(with = "secp_ser::string_or_u64")]
+	pub refund_height: u64,
+	/// Time from which the buyer can take the secondary currency back, in seconds since
+	/// the epoch
+	pub secondary_lock_time: i64,
+	/// Commitment of the output the epics are locked in
+	#[serde(default, with = "secp_ser::option_commitment_serde")]
+	pub lock_commit: Option<pedersen::Commitment>,
+	/// Seller's key of the redeem branch of the secondary lock script
+	#[serde(default, with = "dalek_ser::option_pubkey_serde")]
+	pub redeem_public: Option<PublicKey>,
+	/// Buyer's key of the refund branch of the secondary lock script
+	#[serde(default, with = "dalek_ser::option_pubkey_serde")]
+	pub refund_public: Option<PublicKey>,
+	/// Public key of the secret the redeem transaction reveals, the second key of the
+	/// redeem branch of the secondary lock script
+	#[serde(default, with = "dalek_ser::option_pubkey_serde")]
+	pub secret_public: Option<PublicKey>,
+	/// Script the secondary currency is locked with, hex encoded
+	#[serde(default)]
+	pub secondary_lock_script: Option<String>,
+	/// Id of the seller's refund transaction of the locked epics
+	#[serde(default)]
+	pub refund_slate_id: Option<Uuid>,
+	/// Key of the seller's output the refund transaction creates
+	#[serde(default)]
+	pub refund_key_id: Option<Identifier>,
+	/// The seller's refund transaction, valid from the refund height, hex encoded
+	#[serde(default)]
+	pub refund_tx: Option<String>,
+	/// Redeem transaction of the locked epics, once started
+	#[serde(default)]
+	pub redeem_slate: Option<Slate>,
+	/// The buyer's adaptor signature of the redeem transaction, as kept by the seller
+	#[serde(default, with = "secp_ser::option_sig_serde")]
+	pub adaptor: Option<secp::Signature>,
+	/// Our private key of the secondary lock script, once needed: the seller's redeem
+	/// key once redeemed, the buyer's refund key once cancelled
+	#[serde(default, with = "secp_ser::option_seckey_serde")]
+	pub secondary_key: Option<SecretKey>,
+	/// The secret revealed by the redeem transaction, the seller's second redeem key
+	#[serde(default, with = "secp_ser::option_seckey_serde")]
+	pub secondary_secret: Option<SecretKey>,
+	/// Time the swap was started
+	pub created_ts: DateTime<Utc>,
+	/// Time the swap last progressed
+	pub updated_ts: DateTime<Utc>,
+}
@@ -0,0 +1,228 @@
+// Copyright 2019 The Epic Developers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! View keys of wallet accounts: the extended public key of an account, along with
+//! what's needed to rewind the range proofs of its outputs. A watch-only wallet finds
+//! and tracks the outputs of the account with it, without any of its private keys.
+
+use crate::blake2::blake2b::blake2b;
+use crate::epic_core::libtx::proof::ProofBuild;
+use crate::epic_core::libtx::{secp_ser, Error as LibTxError, ErrorKind as LibTxErrorKind};
+use crate::epic_keychain::extkey_bip32::{BIP32GrinHasher, BIP32Hasher, ExtendedPrivKey};
+use crate::epic_keychain::{Identifier, SwitchCommitmentType};
+use crate::epic_util::secp::constants::GENERATOR_PUB_J_RAW;
+use crate::epic_util::secp::key::{PublicKey, SecretKey};
+use crate::epic_util::secp::pedersen::{Commitment, ProofMessage};
+use crate::epic_util::secp::{self, ffi, ContextFlag, Secp256k1};
+use crate::{Error, ErrorKind};
+use byteorder::{BigEndian, ByteOrder};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+
+/// View key of a wallet account
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ViewKey {
+	/// Whether the keys are derived for floonet
+	pub is_floo: bool,
+	/// The account
+	pub parent_key_id: Identifier,
+	/// Public key of the account
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub public_key: PublicKey,
+	/// The account's private key times the generator of switch commitments
+	#[serde(with = "secp_ser::pubkey_serde")]
+	pub switch_public_key: PublicKey,
+	/// BIP32 chain code of the account
+	#[serde(with = "hex_bytes")]
+	pub chain_code: Vec<u8>,
+	/// Hash of the wallet's root public key, the nonces of its range proofs are
+	/// derived from
+	#[serde(with = "hex_bytes")]
+	pub rewind_hash: Vec<u8>,
+}
+
+impl ViewKey {
+	/// View key of the account of a wallet seed
+	pub fn from_seed(
+		seed: &[u8],
+		is_floo: bool,
+		parent_key_id: &Identifier,
+	) -> Result<ViewKey, Error> {
+		let secp = Secp256k1::with_caps(ContextFlag::Commit);
+		let mut hasher = BIP32GrinHasher::new(is_floo);
+		let derivation_error =
+			|e| ErrorKind::WatchOnly(format!("Error deriving the account key: {:?}", e));
+		let master =
+			ExtendedPrivKey::new_master(&secp, &mut hasher, seed).map_err(derivation_error)?;
+		let root_public_key = PublicKey::from_secret_key(&secp, &master.secret_key)?;
+		let rewind_hash = blake2b(32, &[], &root_public_key.serialize_vec(&secp, true)[..]);
+
+		let path = parent_key_id.to_path();
+		let mut account = master;
+		for i in 0..path.depth as usize {
+			account = account
+				.ckd_priv(&secp, &mut hasher, path.path[i])
+				.map_err(derivation_error)?;
+		}
+		let mut switch_public_key = generator_j();
+		switch_public_key.mul_assign(&secp, &account.secret_key)?;
+		Ok(ViewKey {
+			is_floo,
+			parent_key_id: parent_key_id.clone(),
+			public_key: PublicKey::from_secret_key(&secp, &account.secret_key)?,
+			switch_public_key,
+			chain_code: account.chain_code[..].to_vec(),
+			rewind_hash: rewind_hash.as_bytes().to_vec(),
+		})
+	}
+
+	/// Public key and switch public key of the non-hardened child of the account with
+	/// the given index
+	fn child_keys(
+		&self,
+		secp: &Secp256k1,
+		index: u32,
+	) -> Result<(PublicKey, PublicKey), secp::Error> {
+		let mut hasher = BIP32GrinHasher::new(self.is_floo);
+		hasher.init_sha512(&self.chain_code[..]);
+		hasher.append_sha512(&self.public_key.serialize_vec(secp, true)[..]);
+		let mut be_n = [0; 4];
+		BigEndian::write_u32(&mut be_n, index);
+		hasher.append_sha512(&be_n);
+		let result = hasher.result_sha512();
+		let tweak = SecretKey::from_slice(secp, &result[..32])?;
+
+		let mut public_key = self.public_key.clone();
+		public_key.add_exp_assign(secp, &tweak)?;
+		let mut switch_tweak = generator_j();
+		switch_tweak.mul_assign(secp, &tweak)?;
+		let switch_public_key =
+			PublicKey::from_combination(secp, vec![&self.switch_public_key, &switch_tweak])?;
+		Ok((public_key, switch_public_key))
+	}
+
+	/// Commitment to the given amount of the child of the account with the given index,
+	/// as the wallet's keychain computes it from the child's private key
+	pub fn commit(
+		&self,
+		secp: &Secp256k1,
+		amount: u64,
+		index: u32,
+		switch: &SwitchCommitmentType,
+	) -> Result<Commitment, secp::Error> {
+		let (public_key, switch_public_key) = self.child_keys(secp, index)?;
+		let value = secp.commit_value(amount)?.to_pubkey(secp)?;
+		let mut commit = PublicKey::from_combination(secp, vec![&public_key, &value])?;
+		if let SwitchCommitmentType::Regular = switch {
+			// the blinding factor is tweaked by the hash of the commitment, serialized
+			// with its 0x08 or 0x09 prefix, and of the child's switch public key
+			let mut commit_ser = commit.serialize_vec(secp, true);
+			commit_ser[0] += 6;
+			let mut hasher = Sha256::new();
+			hasher.update(&commit_ser[..]);
+			hasher.update(&switch_public_key.serialize_vec(secp, true)[..]);
+			let tweak = SecretKey::from_slice(secp, &hasher.finalize())?;
+			commit.add_exp_assign(secp, &tweak)?;
+		}
+		Commitment::from_pubkey(secp, &commit)
+	}
+}
+
+/// Error of building a range proof with a view key
+fn no_private_key() -> LibTxError {
+	LibTxErrorKind::Commitment("view key can't build range proofs".to_owned()).into()
+}
+
+/// Only rewinds the range proofs of the account's outputs, a view key can't build any
+impl ProofBuild for ViewKey {
+	fn rewind_nonce(&self, secp: &Secp256k1, commit: &Commitment) -> Result<SecretKey, LibTxError> {
+		let res = blake2b(32, &commit.0, &self.rewind_hash);
+		Ok(SecretKey::from_slice(secp, res.as_bytes())?)
+	}
+
+	fn private_nonce(
+		&self,
+		_secp: &Secp256k1,
+		_commit: &Commitment,
+	) -> Result<SecretKey, LibTxError> {
+		Err(no_private_key())
+	}
+
+	fn proof_message(
+		&self,
+		_secp: &Secp256k1,
+		_id: &Identifier,
+		_switch: &SwitchCommitmentType,
+	) -> Result<ProofMessage, LibTxError> {
+		Err(no_private_key())
+	}
+
+	fn check_output(
+		&self,
+		secp: &Secp256k1,
+		commit: &Commitment,
+		amount: u64,
+		message: ProofMessage,
+	) -> Result<Option<(Identifier, SwitchCommitmentType)>, LibTxError> {
+		if message.len() != 20 {
+			return Ok(None);
+		}
+		let msg = message.as_bytes();
+		if msg[..2] != [0; 2] {
+			return Ok(None);
+		}
+		let switch = match SwitchCommitmentType::try_from(msg[2]) {
+			Ok(s) => s,
+			Err(_) => return Ok(None),
+		};
+		let depth = u8::min(msg[3], 4);
+		let id = Identifier::from_serialized_path(depth, &msg[4..]);
+		// only the direct children of the account can be derived from its public key
+		if id.parent_path() != self.parent_key_id {
+			return Ok(None);
+		}
+		let index = id.to_path().last_path_index();
+		match self.commit(secp, amount, index, &switch)? == *commit {
+			true => Ok(Some((id, switch))),
+			false => Ok(None),
+		}
+	}
+}
+
+/// Generator of switch commitments
+fn generator_j() -> PublicKey {
+	PublicKey(ffi::PublicKey(GENERATOR_PUB_J_RAW))
+}
+
+/// Serializes bytes as a hex string
+mod hex_bytes {
+	use crate::epic_util::{from_hex, to_hex};
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(&to_hex(bytes.to_vec()))
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		use serde::de::Error;
+		String::deserialize(deserializer)
+			.and_then(|s| from_hex(s).map_err(|err| Error::custom(err.to_string())))
+	}
+}
@@ -15,7 +15,9 @@
 use crate::cmd::wallet_args;
 use crate::config::GlobalWalletConfig;
 use clap::ArgMatches;
-use epic_wallet_impls::HTTPNodeClient;
+use epic_wallet_controller::command::{self, ConfigShowArgs};
+use epic_wallet_controller::{display, ErrorKind};
+use epic_wallet_impls::{HTTPNodeClient, DEFAULT_NODE_CACHE_SIZE, DEFAULT_NODE_CACHE_TTL_SECS};
 use epic_wallet_libwallet::NodeClient;
 use log::debug;
 use semver::Version;
@@ -25,10 +27,33 @@ use std::time::Duration;
 const MIN_COMPAT_NODE_VERSION: &str = "3.0.0";
 
 pub fn wallet_command(wallet_args: &ArgMatches<'_>, config: GlobalWalletConfig) -> i32 {
+	// configuration commands don't need the wallet or the node
+	if let ("config", Some(args)) = wallet_args.subcommand() {
+		let res = match args.subcommand() {
+			("show", Some(args)) => command::config_show(
+				&config,
+				ConfigShowArgs {
+					effective: args.is_present("effective"),
+				},
+			),
+			_ => {
+				println!("{}", args.usage());
+				Ok(())
+			}
+		};
+		return match res {
+			Ok(()) => 0,
+			Err(e) => {
+				eprintln!("Wallet command failed: {}", e);
+				1
+			}
+		};
+	}
+
 	// Get defaults from the global config
 	let wallet_config = config.members.clone().unwrap().wallet;
 	let tor_config = config.members.clone().unwrap().tor;
-	let epicbox_config = config.members.unwrap().epicbox;
+	let epicbox_config = config.members.clone().unwrap().epicbox;
 
 	// Setup node client, check for provided node URL, else use default
 	let mut node_client = match wallet_args.value_of("api_server_address") {
@@ -42,6 +67,20 @@ pub fn wallet_command(wallet_args: &ArgMatches<'_>, config: GlobalWalletConfig)
 		.expect("Can't read configuration file");
 
 	node_client.set_node_api_secret(global_wallet_args.node_api_secret.clone());
+	if let Some(ms) = wallet_config.node_slow_request_ms {
+		node_client.set_slow_request_ms(ms);
+	}
+	if let Some(ms) = wallet_config.rpc_slow_call_ms {
+		epic_wallet_api::set_slow_rpc_ms(ms);
+	}
+	node_client.set_cache(
+		wallet_config
+			.node_cache_size
+			.unwrap_or(DEFAULT_NODE_CACHE_SIZE),
+		wallet_config
+			.node_cache_ttl_secs
+			.unwrap_or(DEFAULT_NODE_CACHE_TTL_SECS),
+	);
 
 	// This will also cache the node version info for calls to foreign API check middleware
 	if let Some(v) = node_client.clone().get_version_info() {
@@ -66,6 +105,7 @@ pub fn wallet_command(wallet_args: &ArgMatches<'_>, config: GlobalWalletConfig)
 		wallet_config,
 		tor_config,
 		epicbox_config,
+		Some(config),
 		node_client,
 		false,
 		|_| {},
@@ -76,6 +116,11 @@ pub fn wallet_command(wallet_args: &ArgMatches<'_>, config: GlobalWalletConfig)
 
 	if let Err(e) = res {
 		eprintln!("Wallet command failed: {}", e);
+		if let ErrorKind::LibWallet(kind, _) = e.kind() {
+			if let Some(hint) = display::error_hint(&kind) {
+				eprintln!("{}", hint);
+			}
+		}
 		1
 	} else {
 		println!(
@@ -15,29 +15,36 @@
 use crate::api::TLSConfig;
 use crate::config::EPIC_WALLET_DIR;
 use crate::util::file::get_first_line;
-use crate::util::{to_hex, Mutex, ZeroingString};
+use crate::util::secp::key::SecretKey;
+use crate::util::secp::pedersen;
+use crate::util::{from_hex, to_hex, Mutex, ZeroingString};
 /// Argument parsing and error handling for wallet commands
 use clap::ArgMatches;
-use epic_wallet_config::{EpicboxConfig, TorConfig, WalletConfig};
-use epic_wallet_controller::command;
+use epic_wallet_config::{
+	EpicboxConfig, GlobalWalletConfig, MessageTemplatesConfig, TorConfig, WalletConfig,
+};
+use epic_wallet_controller::{command, i18n};
 use epic_wallet_controller::{Error, ErrorKind};
 use epic_wallet_impls::tor::config::is_tor_address;
-use epic_wallet_impls::{DefaultLCProvider, DefaultWalletImpl};
+use epic_wallet_impls::{DefaultLCProvider, DefaultWalletImpl, StoreBackend};
 use epic_wallet_impls::{PathToSlate, SlateGetter as _};
+use epic_wallet_libwallet::api_impl::owner;
 use epic_wallet_libwallet::Slate;
 use epic_wallet_libwallet::{
-	address, IssueInvoiceTxArgs, NodeClient, WalletInst, WalletLCProvider,
+	address, check_node_chain, set_chain_params, Contact, FeePeriod, IssueInvoiceTxArgs,
+	MnemonicLanguage, NodeClient, SeedWord, SendTemplate, SwapCurrency, SwapStartArgs, SweepPolicy,
+	TxFilter, TxLogEntryType, WalletInst, WalletLCProvider, SWEEP_MINIMUM_CONFIRMATIONS,
 };
-use epic_wallet_util::epic_core as core;
-use epic_wallet_util::epic_core::core::amount_to_hr_string;
 use epic_wallet_util::epic_core::global;
 use epic_wallet_util::epic_keychain as keychain;
 use failure::Fail;
 use linefeed::terminal::Signal;
 use linefeed::{Interface, ReadResult};
 use rpassword;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
@@ -133,9 +140,53 @@ where
 	}
 }
 
+fn prompt_seed_words<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	password: &ZeroingString,
+	count: usize,
+) -> Result<(Vec<usize>, Vec<SeedWord>), ParseError>
+where
+	DefaultWalletImpl<'static, C>: WalletInst<'static, L, C, K>,
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let positions = {
+		let mut w_lock = wallet.lock();
+		let p = w_lock.lc_provider().unwrap();
+		p.get_mnemonic(None, password.clone())
+			.and_then(|m| owner::seed_quiz(&m, count))
+			.map_err(|e| ParseError::ArgumentError(format!("{}", e)))?
+	};
+	let mut rl = Editor::<()>::new();
+	println!(
+		"To confirm your recovery phrase is backed up, please enter the following words of it:"
+	);
+	let mut words = vec![];
+	for position in positions.iter().cloned() {
+		match rl.readline(&format!("word #{}> ", position)) {
+			Ok(line) => words.push(SeedWord {
+				position,
+				word: line.trim().to_owned(),
+			}),
+			Err(ReadlineError::Interrupted) => {
+				return Err(ParseError::CancelledError);
+			}
+			Err(ReadlineError::Eof) => {
+				return Err(ParseError::CancelledError);
+			}
+			Err(err) => {
+				eprintln!("Error: {:?}", err);
+				return Err(ParseError::CancelledError);
+			}
+		}
+	}
+	Ok((positions, words))
+}
+
 fn prompt_pay_invoice(slate: &Slate, method: &str, dest: &str) -> Result<bool, ParseError> {
 	let interface = Arc::new(Interface::new("pay")?);
-	let amount = amount_to_hr_string(slate.amount, false);
+	let amount = i18n::amount(slate.amount, false);
 	interface.set_report_signal(Signal::Interrupt, true);
 	interface.set_prompt(
 		"To proceed, type the exact amount of the invoice as displayed above (or Q/q to quit) > ",
@@ -147,8 +198,9 @@ fn prompt_pay_invoice(slate: &Slate, method: &str, dest: &str) -> Result<bool, P
 	println!("After you confirm, the following will occur: ");
 	println!();
 	println!(
-		"* {} of your wallet funds will be added to the transaction to pay this invoice.",
-		amount
+		"* {} {} of your wallet funds will be added to the transaction to pay this invoice.",
+		amount,
+		i18n::unit().symbol()
 	);
 	if method == "http" {
 		println!("* The resulting transaction will IMMEDIATELY be sent to the wallet listening at: '{}'.", dest);
@@ -176,7 +228,7 @@ fn prompt_pay_invoice(slate: &Slate, method: &str, dest: &str) -> Result<bool, P
 				match line.trim() {
 					"Q" | "q" => return Err(ParseError::CancelledError),
 					result => {
-						if result == amount {
+						if i18n::parse_amount(result) == Ok(slate.amount) {
 							return Ok(true);
 						} else {
 							println!("Please enter exact amount of the invoice as shown above or Q to quit");
@@ -189,6 +241,75 @@ fn prompt_pay_invoice(slate: &Slate, method: &str, dest: &str) -> Result<bool, P
 	}
 }
 
+fn prompt_large_send(
+	amount: u64,
+	method: &str,
+	dest: &str,
+	dest_chars: usize,
+) -> Result<(), ParseError> {
+	let interface = Arc::new(Interface::new("send")?);
+	interface.set_report_signal(Signal::Interrupt, true);
+	let amount_str = i18n::amount(amount, false);
+	println!();
+	println!(
+		"This command will send {} {} of your wallet funds with method '{}' to '{}'.",
+		amount_str,
+		i18n::unit().symbol(),
+		method,
+		dest
+	);
+	println!("Please review the above information carefully before proceeding");
+	println!();
+
+	// only the characters the destination has can be asked for
+	let dest_prefix: String = dest.chars().take(dest_chars).collect();
+	let mut checks = vec![(
+		"To proceed, type the exact amount of the send as displayed above (or Q/q to quit) > "
+			.to_owned(),
+		"Please enter the exact amount of the send as shown above or Q to quit",
+	)];
+	if !dest_prefix.is_empty() {
+		checks.push((
+			format!(
+				"Type the first {} characters of the destination (or Q/q to quit) > ",
+				dest_prefix.chars().count()
+			),
+			"Please enter the first characters of the destination as shown above or Q to quit",
+		));
+	}
+	for (i, (prompt, retry)) in checks.iter().enumerate() {
+		interface.set_prompt(prompt)?;
+		loop {
+			let res = interface.read_line()?;
+			match res {
+				ReadResult::Eof => return Err(ParseError::CancelledError),
+				ReadResult::Signal(sig) => {
+					if sig == Signal::Interrupt {
+						interface.cancel_read_line()?;
+						return Err(ParseError::CancelledError);
+					}
+				}
+				ReadResult::Input(line) => {
+					let line = line.trim();
+					if line == "Q" || line == "q" {
+						return Err(ParseError::CancelledError);
+					}
+					let confirmed = match i {
+						0 => i18n::parse_amount(line) == Ok(amount),
+						_ => line == dest_prefix,
+					};
+					if confirmed {
+						break;
+					}
+					println!("{}", retry);
+					println!();
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
 // instantiate wallet (needed by most functions)
 
 pub fn inst_wallet<L, C, K>(
@@ -208,6 +329,93 @@ where
 	Ok(Arc::new(Mutex::new(wallet)))
 }
 
+/// Instances of the further wallets the owner API serves, by name, each of its own
+/// data directory (the wallets aren't opened)
+pub fn inst_owner_api_wallets<L, C, K>(
+	config: &WalletConfig,
+	node_client: &C,
+) -> Result<HashMap<String, Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>>, ParseError>
+where
+	DefaultWalletImpl<'static, C>: WalletInst<'static, L, C, K>,
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + Clone + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut wallets = HashMap::new();
+	for (name, dir) in config.owner_api_wallets.iter().flatten() {
+		if *dir == config.data_file_dir {
+			let msg = format!("Wallet {} is the owner API's default wallet", name);
+			return Err(ParseError::ArgumentError(msg));
+		}
+		let mut wallet_config = config.clone();
+		wallet_config.data_file_dir = dir.to_owned();
+		wallets.insert(
+			name.to_owned(),
+			inst_wallet(wallet_config, node_client.clone())?,
+		);
+	}
+	Ok(wallets)
+}
+
+/// The further wallets the foreign API listener serves, by name, each of its own data
+/// directory and opened with the password in its password file, or `password`
+pub fn inst_foreign_api_wallets<L, C, K>(
+	config: &WalletConfig,
+	node_client: &C,
+	password: Option<&ZeroingString>,
+) -> Result<
+	HashMap<
+		String,
+		(
+			Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+			Option<SecretKey>,
+		),
+	>,
+	ParseError,
+>
+where
+	DefaultWalletImpl<'static, C>: WalletInst<'static, L, C, K>,
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + Clone + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let mut wallets = HashMap::new();
+	for (name, c) in config.foreign_api_wallets.iter().flatten() {
+		if c.data_file_dir == config.data_file_dir {
+			let msg = format!("Wallet {} is the listener's wallet", name);
+			return Err(ParseError::ArgumentError(msg));
+		}
+		let password = match c.password_file.as_ref() {
+			Some(f) => match get_first_line(Some(f.to_owned())) {
+				Some(p) => ZeroingString::from(p),
+				None => {
+					let msg = format!("No password in the password file of wallet {}", name);
+					return Err(ParseError::ArgumentError(msg));
+				}
+			},
+			None => match password {
+				Some(p) => p.clone(),
+				None => {
+					let msg = format!("No password to open wallet {} with", name);
+					return Err(ParseError::ArgumentError(msg));
+				}
+			},
+		};
+		let mut wallet_config = config.clone();
+		wallet_config.data_file_dir = c.data_file_dir.to_owned();
+		let wallet = inst_wallet(wallet_config, node_client.clone())?;
+		let mask = wallet
+			.lock()
+			.lc_provider()
+			.and_then(|lc| lc.open_wallet(None, password, false, false))
+			.map_err(|e| {
+				ParseError::ArgumentError(format!("Unable to open wallet {}: {}", name, e))
+			})?;
+		wallets.insert(name.to_owned(), (wallet, mask));
+	}
+	Ok(wallets)
+}
+
 // parses a required value, or throws error with message otherwise
 fn parse_required<'a>(args: &'a ArgMatches, name: &str) -> Result<&'a str, ParseError> {
 	let arg = args.value_of(name);
@@ -220,6 +428,22 @@ fn parse_required<'a>(args: &'a ArgMatches, name: &str) -> Result<&'a str, Parse
 	}
 }
 
+// the configured participant message template picked by `kind`, for the account
+// and reference the command is run with
+fn parse_message_template(
+	config: &WalletConfig,
+	kind: fn(&MessageTemplatesConfig) -> Option<&String>,
+	g_args: &command::GlobalArgs,
+	args: &ArgMatches,
+) -> Option<command::MessageTemplate> {
+	let template = config.message_templates.as_ref().and_then(kind)?;
+	Some(command::MessageTemplate {
+		template: template.to_owned(),
+		account: g_args.account.clone(),
+		invoice_ref: args.value_of("invoice_ref").map(|r| r.to_owned()),
+	})
+}
+
 // parses a number, or throws error with message otherwise
 fn parse_u64(arg: &str, name: &str) -> Result<u64, ParseError> {
 	let val = arg.parse::<u64>();
@@ -309,6 +533,16 @@ where
 		false => 32,
 		true => 16,
 	};
+	let language = match args.value_of("language") {
+		Some(l) => match MnemonicLanguage::from_code(l) {
+			Some(l) => Some(l),
+			None => {
+				let msg = format!("Unknown recovery phrase language: {}", l);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+		None => None,
+	};
 	let recovery_phrase = match args.is_present("recover") {
 		true => Some(prompt_recovery_phrase(wallet)?),
 		false => None,
@@ -330,6 +564,7 @@ where
 		password,
 		config: config.clone(),
 		recovery_phrase,
+		language,
 		restore: false,
 	})
 }
@@ -343,6 +578,27 @@ where
 	Ok(command::RecoverArgs { passphrase })
 }
 
+pub fn parse_verify_seed_args<L, C, K>(
+	wallet: Arc<Mutex<Box<dyn WalletInst<'static, L, C, K>>>>,
+	g_args: &command::GlobalArgs,
+	args: &ArgMatches,
+) -> Result<command::VerifySeedArgs, ParseError>
+where
+	DefaultWalletImpl<'static, C>: WalletInst<'static, L, C, K>,
+	L: WalletLCProvider<'static, C, K>,
+	C: NodeClient + 'static,
+	K: keychain::Keychain + 'static,
+{
+	let count = parse_u64(parse_required(args, "words")?, "words")? as usize;
+	let password = prompt_password(&g_args.password);
+	let (positions, words) = prompt_seed_words(wallet, &password, count)?;
+	Ok(command::VerifySeedArgs {
+		password,
+		positions,
+		words,
+	})
+}
+
 pub fn parse_listen_args(
 	config: &mut WalletConfig,
 	tor_config: &mut TorConfig,
@@ -375,33 +631,121 @@ pub fn parse_owner_api_args(
 	Ok(())
 }
 
+pub fn parse_display_unit(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<i18n::DisplayUnit, ParseError> {
+	let unit = match args.value_of("unit") {
+		Some(u) => u,
+		None => match config.display_unit.as_ref() {
+			Some(u) => u.as_str(),
+			None => return Ok(i18n::DisplayUnit::default()),
+		},
+	};
+	unit.parse().map_err(ParseError::ArgumentError)
+}
+
 pub fn parse_account_args(account_args: &ArgMatches) -> Result<command::AccountArgs, ParseError> {
 	let create = match account_args.value_of("create") {
 		None => None,
 		Some(s) => Some(s.to_owned()),
 	};
-	Ok(command::AccountArgs { create })
+	let set_metadata = match account_args.value_of("set") {
+		None => None,
+		Some(s) => match s.find('=') {
+			Some(i) => Some((s[..i].to_owned(), s[i + 1..].to_owned())),
+			None => {
+				let msg = format!("Metadata entry '{}' must be given as key=value", s);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	let unset_metadata = account_args.value_of("unset").map(|s| s.to_owned());
+	let metadata_label = account_args.value_of("label").map(|s| s.to_owned());
+	let changes_metadata = set_metadata.is_some() || unset_metadata.is_some();
+	if changes_metadata && metadata_label.is_none() {
+		let msg = format!("'label' (-l) is required to set or unset account metadata");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	if changes_metadata && create.is_some() {
+		let msg = format!("Account metadata can't be changed while creating an account");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	Ok(command::AccountArgs {
+		create,
+		metadata_label,
+		set_metadata,
+		unset_metadata,
+	})
 }
 
-pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseError> {
+/// Slate version emitted in strict compatibility mode, the oldest one this wallet writes
+const STRICT_COMPAT_SLATE_VERSION: u16 = 2;
+
+// target slate version to create, the oldest one in strict compatibility mode
+fn parse_target_slate_version(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<Option<u16>, ParseError> {
+	let strict = args.is_present("strict_compat") || config.strict_slate_compat.unwrap_or(false);
+	match args.is_present("slate_version") {
+		true => {
+			let v = parse_required(args, "slate_version")?;
+			let v = parse_u64(v, "slate_version")? as u16;
+			if strict && v != STRICT_COMPAT_SLATE_VERSION {
+				let msg = format!(
+					"Slate version {} can't be used in strict slate compatibility mode, which emits version {}",
+					v, STRICT_COMPAT_SLATE_VERSION
+				);
+				return Err(ParseError::ArgumentError(msg));
+			}
+			Ok(Some(v))
+		}
+		false if strict => Ok(Some(STRICT_COMPAT_SLATE_VERSION)),
+		false => Ok(None),
+	}
+}
+
+pub fn parse_send_args(
+	config: &WalletConfig,
+	g_args: &command::GlobalArgs,
+	args: &ArgMatches,
+	template: Option<&SendTemplate>,
+	prompt: bool,
+) -> Result<command::SendArgs, ParseError> {
+	// a send template gives the amount, method, destination and message of the send
+	if template.is_some() {
+		for a in ["amount", "method", "dest", "message"].iter() {
+			if args.occurrences_of(a) > 0 {
+				let msg = format!("'{}' can't be given with a send template", a);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		}
+	}
+
 	// amount
-	let amount = parse_required(args, "amount")?;
-	let amount = core::core::amount_from_hr_string(amount);
-	let amount = match amount {
-		Ok(a) => a,
-		Err(e) => {
-			let msg = format!(
-				"Could not parse amount as a number with optional decimal point. e={:?}",
-				e
-			);
-			return Err(ParseError::ArgumentError(msg));
+	let amount = match template {
+		Some(t) => t.amount,
+		None => {
+			let amount = parse_required(args, "amount")?;
+			match i18n::parse_amount(amount) {
+				Ok(a) => a,
+				Err(e) => {
+					let msg = format!("Could not parse amount in unit '{}'. e={}", i18n::unit(), e);
+					return Err(ParseError::ArgumentError(msg));
+				}
+			}
 		}
 	};
 
 	// message
-	let message = match args.is_present("message") {
-		true => Some(args.value_of("message").unwrap().to_owned()),
-		false => None,
+	let message = match template.and_then(|t| t.message.as_ref()) {
+		Some(m) => Some(m.clone()),
+		None => match args.is_present("message") {
+			true => Some(args.value_of("message").unwrap().to_owned()),
+			false => parse_message_template(config, |t| t.send.as_ref(), g_args, args)
+				.map(|t| t.render(amount)),
+		},
 	};
 
 	// minimum_confirmations
@@ -415,28 +759,44 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 	let estimate_selection_strategies = args.is_present("estimate_selection_strategies");
 
 	// method
-	let method = parse_required(args, "method")?;
+	let method = match template {
+		Some(t) => t.method.as_str(),
+		None => parse_required(args, "method")?,
+	};
 
 	// dest
-	let dest = {
-		if method == "self" {
-			match args.value_of("dest") {
-				Some(d) => d,
-				None => "default",
-			}
-		} else if method == "emoji" {
-			""
-		} else {
-			if !estimate_selection_strategies {
-				parse_required(args, "dest")?
-			} else {
+	let dest = match template {
+		Some(t) => t.dest.as_str(),
+		None => {
+			if method == "self" {
+				match args.value_of("dest") {
+					Some(d) => d,
+					None => "default",
+				}
+			} else if method == "emoji" {
 				""
+			} else {
+				if !estimate_selection_strategies {
+					parse_required(args, "dest")?
+				} else {
+					""
+				}
 			}
 		}
 	};
 
+	// a contact is sent to with the transports known for them
+	if dest.starts_with('@') && method != "http" && method != "epicbox" {
+		let msg = format!(
+			"A contact is sent to over epicbox, tor or http, not with method '{}'",
+			method,
+		);
+		return Err(ParseError::ArgumentError(msg));
+	}
+
 	if !estimate_selection_strategies
 		&& method == "http"
+		&& !dest.starts_with('@')
 		&& !dest.starts_with("http://")
 		&& !dest.starts_with("https://")
 		&& is_tor_address(&dest).is_err()
@@ -448,6 +808,14 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 		return Err(ParseError::ArgumentError(msg));
 	}
 
+	// a large send is confirmed by typing its amount, and the start of its destination
+	if let Some(c) = config.large_send_confirm.as_ref() {
+		let confirm = prompt && !estimate_selection_strategies && !args.is_present("yes");
+		if confirm && amount >= c.threshold {
+			prompt_large_send(amount, method, dest, c.dest_chars.unwrap_or(0))?;
+		}
+	}
+
 	// change_outputs
 	let change_outputs = parse_required(args, "change_outputs")?;
 	let change_outputs = parse_u64(change_outputs, "change_outputs")? as usize;
@@ -458,27 +826,57 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 	// ttl_blocks
 	let ttl_blocks = parse_u64_or_none(args.value_of("ttl_blocks"));
 
+	// lock_height, for a height-locked kernel
+	let lock_height = parse_u64_or_none(args.value_of("lock_height"));
+
 	// max_outputs
 	let max_outputs = 500;
 
+	// max_tx_weight
+	let max_tx_weight = match args.value_of("max_weight") {
+		Some(w) => Some(parse_u64(w, "max_weight")?),
+		None => config.max_tx_weight,
+	};
+	let auto_split = args.is_present("split") || config.auto_split_tx.unwrap_or(false);
+
+	// inputs, spent by a single transaction
+	let inputs = parse_commitments(args, "inputs")?;
+	if inputs.is_some() && args.is_present("split") {
+		let msg = "A transaction spending the given inputs can't be split".to_owned();
+		return Err(ParseError::ArgumentError(msg));
+	}
+
 	// target slate version to create/send
-	let target_slate_version = {
-		match args.is_present("slate_version") {
-			true => {
-				let v = parse_required(args, "slate_version")?;
-				Some(parse_u64(v, "slate_version")? as u16)
-			}
-			false => None,
+	let target_slate_version = parse_target_slate_version(config, args)?;
+
+	if target_slate_version == Some(STRICT_COMPAT_SLATE_VERSION) {
+		if args.is_present("request_payment_proof") {
+			let msg = "Payment proofs need slate version 3 and can't be requested in strict slate compatibility mode".to_owned();
+			return Err(ParseError::ArgumentError(msg));
 		}
-	};
+		if args.is_present("signed_response") {
+			let msg = "Signed responses need slate version 3 and can't be required in strict slate compatibility mode".to_owned();
+			return Err(ParseError::ArgumentError(msg));
+		}
+		if ttl_blocks.is_some() {
+			let msg =
+				"A TTL needs slate version 3 and can't be set in strict slate compatibility mode"
+					.to_owned();
+			return Err(ParseError::ArgumentError(msg));
+		}
+	}
 
+	// a wallet on the local network is always paid to with a payment proof to, and
+	// signs its response with, the address it advertises
 	let payment_proof_address = {
-		match args.is_present("request_payment_proof") {
+		match args.is_present("request_payment_proof") && method != "lan" {
 			true => {
 				// if the destination address is a TOR address, we don't need the address
-				// separately
+				// separately, and a contact stands for their proof address, filled in once
+				// the contact is looked up
 				match address::pubkey_from_onion_v3(&dest) {
 					Ok(k) => Some(to_hex(k.to_bytes().to_vec())),
+					Err(_) if dest.starts_with('@') => Some(dest.to_owned()),
 					Err(_) => Some(parse_required(args, "proof_address")?.to_owned()),
 				}
 			}
@@ -486,8 +884,19 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 		}
 	};
 
+	// the recipient's response is signed with the key of the same address
+	let response_address = match args.is_present("signed_response") && method != "lan" {
+		true => match address::pubkey_from_onion_v3(&dest) {
+			Ok(k) => Some(to_hex(k.to_bytes().to_vec())),
+			Err(_) if dest.starts_with('@') => Some(dest.to_owned()),
+			Err(_) => Some(parse_required(args, "proof_address")?.to_owned()),
+		},
+		false => None,
+	};
+
 	Ok(command::SendArgs {
 		amount,
+		src_acct_name: template.and_then(|t| t.src_acct_name.clone()),
 		message,
 		minimum_confirmations: min_c,
 		selection_strategy: selection_strategy.to_owned(),
@@ -498,12 +907,28 @@ pub fn parse_send_args(args: &ArgMatches) -> Result<command::SendArgs, ParseErro
 		fluff,
 		max_outputs,
 		payment_proof_address,
+		response_address,
 		ttl_blocks,
 		target_slate_version,
+		max_tx_weight,
+		auto_split: auto_split && inputs.is_none(),
+		inputs,
+		decoy_output: config.decoy_output.clone(),
+		avoid_mixed_coinbase: config.avoid_mixed_coinbase.unwrap_or(false),
+		consolidation_fee_threshold: config.consolidation_fee_threshold,
+		lock_height,
+		tx_hooks: config.tx_hooks.clone(),
+		lan: config.lan.clone(),
+		ignore_node_height: args.is_present("ignore_node_height"),
+		send_timeout: config.send_timeout_secs.map(Duration::from_secs),
 	})
 }
 
-pub fn parse_receive_args(receive_args: &ArgMatches) -> Result<command::ReceiveArgs, ParseError> {
+pub fn parse_receive_args(
+	config: &WalletConfig,
+	g_args: &command::GlobalArgs,
+	receive_args: &ArgMatches,
+) -> Result<command::ReceiveArgs, ParseError> {
 	// message
 	let message = match receive_args.is_present("message") {
 		true => Some(receive_args.value_of("message").unwrap().to_owned()),
@@ -527,11 +952,20 @@ pub fn parse_receive_args(receive_args: &ArgMatches) -> Result<command::ReceiveA
 	Ok(command::ReceiveArgs {
 		input: tx_file.to_owned(),
 		message: message,
+		message_template: parse_message_template(
+			config,
+			|t| t.receive.as_ref(),
+			g_args,
+			receive_args,
+		),
 		method: method.to_string(),
 	})
 }
 
-pub fn parse_finalize_args(args: &ArgMatches) -> Result<command::FinalizeArgs, ParseError> {
+pub fn parse_finalize_args(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<command::FinalizeArgs, ParseError> {
 	let fluff = args.is_present("fluff");
 	let nopost = args.is_present("nopost");
 
@@ -560,39 +994,78 @@ pub fn parse_finalize_args(args: &ArgMatches) -> Result<command::FinalizeArgs, P
 		dest: dest_file.to_owned(),
 		nopost,
 		fluff,
+		tx_hooks: config.tx_hooks.clone(),
+		ignore_node_height: args.is_present("ignore_node_height"),
+	})
+}
+
+pub fn parse_sign_args(args: &ArgMatches) -> Result<command::SignArgs, ParseError> {
+	let input = parse_required(args, "input")?;
+	if !Path::new(&input).is_file() {
+		let msg = format!("File {} not found.", input);
+		return Err(ParseError::ArgumentError(msg));
+	}
+	let dest = match args.value_of("dest") {
+		Some(d) => d.to_owned(),
+		None => format!("{}.tx", input),
+	};
+	Ok(command::SignArgs {
+		input: input.to_owned(),
+		dest,
+	})
+}
+
+pub fn parse_fees_args(args: &ArgMatches) -> Result<command::FeesArgs, ParseError> {
+	let period = match parse_required(args, "period")? {
+		"day" => FeePeriod::Day,
+		"week" => FeePeriod::Week,
+		_ => FeePeriod::Month,
+	};
+	Ok(command::FeesArgs { period })
+}
+
+pub fn parse_watch_args(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<command::WatchArgs, ParseError> {
+	let dir = parse_required(args, "dir")?;
+	if !Path::new(&dir).is_dir() {
+		let msg = format!("Directory {} not found.", dir);
+		return Err(ParseError::ArgumentError(msg));
+	}
+	let interval = parse_required(args, "interval")?;
+	let interval = parse_u64(interval, "interval")?;
+	Ok(command::WatchArgs {
+		dir: dir.to_owned(),
+		interval: Duration::from_secs(interval),
+		fluff: args.is_present("fluff"),
+		nopost: args.is_present("nopost"),
+		tx_hooks: config.tx_hooks.clone(),
+		ignore_node_height: args.is_present("ignore_node_height"),
 	})
 }
 
 pub fn parse_issue_invoice_args(
+	config: &WalletConfig,
+	g_args: &command::GlobalArgs,
 	args: &ArgMatches,
 ) -> Result<command::IssueInvoiceArgs, ParseError> {
 	let amount = parse_required(args, "amount")?;
-	let amount = core::core::amount_from_hr_string(amount);
-	let amount = match amount {
+	let amount = match i18n::parse_amount(amount) {
 		Ok(a) => a,
 		Err(e) => {
-			let msg = format!(
-				"Could not parse amount as a number with optional decimal point. e={:?}",
-				e
-			);
+			let msg = format!("Could not parse amount in unit '{}'. e={}", i18n::unit(), e);
 			return Err(ParseError::ArgumentError(msg));
 		}
 	};
 	// message
 	let message = match args.is_present("message") {
 		true => Some(args.value_of("message").unwrap().to_owned()),
-		false => None,
+		false => parse_message_template(config, |t| t.invoice.as_ref(), g_args, args)
+			.map(|t| t.render(amount)),
 	};
 	// target slate version to create
-	let target_slate_version = {
-		match args.is_present("slate_version") {
-			true => {
-				let v = parse_required(args, "slate_version")?;
-				Some(parse_u64(v, "slate_version")? as u16)
-			}
-			false => None,
-		}
-	};
+	let target_slate_version = parse_target_slate_version(config, args)?;
 	// dest (output file)
 	let dest = parse_required(args, "dest")?;
 	Ok(command::IssueInvoiceArgs {
@@ -607,6 +1080,8 @@ pub fn parse_issue_invoice_args(
 }
 
 pub fn parse_process_invoice_args(
+	config: &WalletConfig,
+	g_args: &command::GlobalArgs,
 	args: &ArgMatches,
 	prompt: bool,
 ) -> Result<command::ProcessInvoiceArgs, ParseError> {
@@ -680,6 +1155,7 @@ pub fn parse_process_invoice_args(
 
 	Ok(command::ProcessInvoiceArgs {
 		message,
+		message_template: parse_message_template(config, |t| t.send.as_ref(), g_args, args),
 		minimum_confirmations: min_c,
 		selection_strategy: selection_strategy.to_owned(),
 		estimate_selection_strategies,
@@ -691,12 +1167,16 @@ pub fn parse_process_invoice_args(
 	})
 }
 
-pub fn parse_info_args(args: &ArgMatches) -> Result<command::InfoArgs, ParseError> {
+pub fn parse_info_args(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<command::InfoArgs, ParseError> {
 	// minimum_confirmations
 	let mc = parse_required(args, "minimum_confirmations")?;
 	let mc = parse_u64(mc, "minimum_confirmations")?;
 	Ok(command::InfoArgs {
 		minimum_confirmations: mc,
+		auto_initial_scan: config.auto_initial_scan.unwrap_or(true),
 	})
 }
 
@@ -707,12 +1187,40 @@ pub fn parse_outputs_args(args: &ArgMatches) -> Result<command::OutputsArgs, Par
 	})
 }
 
-pub fn parse_check_args(args: &ArgMatches) -> Result<command::CheckArgs, ParseError> {
+pub fn parse_check_args(
+	args: &ArgMatches,
+	g_args: &command::GlobalArgs,
+) -> Result<command::CheckArgs, ParseError> {
 	let delete_unconfirmed = args.is_present("delete_unconfirmed");
 	let start_height = parse_u64_or_none(args.value_of("start_height"));
+	let end_height = match args.value_of("end_height") {
+		Some(h) => Some(parse_u64(h, "end_height")?),
+		None => None,
+	};
+	let preview = args.is_present("preview");
+	if end_height.is_some() && !preview {
+		let msg = format!("An end height (-e) can only be given with --preview.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	if preview && delete_unconfirmed {
+		let msg =
+			format!("--preview doesn't change the wallet, so can't delete unconfirmed outputs.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	let account = match args.is_present("only_account") {
+		true if preview => {
+			let msg = format!("--only_account can't be given with --preview.");
+			return Err(ParseError::ArgumentError(msg));
+		}
+		true => Some(g_args.account.clone()),
+		false => None,
+	};
 	Ok(command::CheckArgs {
 		start_height,
+		end_height,
 		delete_unconfirmed,
+		preview,
+		account,
 	})
 }
 
@@ -735,9 +1243,286 @@ pub fn parse_txs_args(args: &ArgMatches) -> Result<command::TxsArgs, ParseError>
 		let msg = format!("At most one of 'id' (-i) or 'txid' (-t) may be provided.");
 		return Err(ParseError::ArgumentError(msg));
 	}
+	let tags = parse_tags(args, "tags");
+	if !tags.is_empty() && (tx_id.is_some() || tx_slate_id.is_some()) {
+		let msg = format!("'tags' (-g) may not be combined with 'id' (-i) or 'txid' (-t).");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	let contact = args.value_of("contact").map(|c| c.trim().to_owned());
+	if contact.is_some() && (tx_id.is_some() || tx_slate_id.is_some() || !tags.is_empty()) {
+		let msg = format!(
+			"'contact' (-c) may not be combined with 'id' (-i), 'txid' (-t) or 'tags' (-g)."
+		);
+		return Err(ParseError::ArgumentError(msg));
+	}
 	Ok(command::TxsArgs {
 		id: tx_id,
 		tx_slate_id,
+		tags,
+		contact,
+		csv_file: args.value_of("csv").map(|f| f.to_owned()),
+		detail: args.is_present("detail"),
+	})
+}
+
+fn parse_commitments(
+	args: &ArgMatches,
+	name: &str,
+) -> Result<Option<Vec<pedersen::Commitment>>, ParseError> {
+	let commits = match args.value_of(name) {
+		None => return Ok(None),
+		Some(c) => c,
+	};
+	let mut res = vec![];
+	for c in commits
+		.split(',')
+		.map(|c| c.trim())
+		.filter(|c| !c.is_empty())
+	{
+		match from_hex(c.to_owned()) {
+			Ok(bytes) if bytes.len() == 33 => res.push(pedersen::Commitment::from_vec(bytes)),
+			_ => {
+				let msg = format!("Invalid output commitment {}", c);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		}
+	}
+	Ok(Some(res))
+}
+
+fn parse_tags(args: &ArgMatches, name: &str) -> Vec<String> {
+	match args.value_of(name) {
+		None => vec![],
+		Some(t) => t
+			.split(',')
+			.map(|t| t.trim().to_owned())
+			.filter(|t| !t.is_empty())
+			.collect(),
+	}
+}
+
+pub fn parse_tag_args(args: &ArgMatches) -> Result<command::TagArgs, ParseError> {
+	let tx_id = match args.value_of("id") {
+		None => None,
+		Some(tx) => Some(parse_u64(tx, "id")? as u32),
+	};
+	let tx_slate_id = match args.value_of("txid") {
+		None => None,
+		Some(tx) => match tx.parse() {
+			Ok(t) => Some(t),
+			Err(e) => {
+				let msg = format!("Could not parse txid parameter. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	if tx_id.is_some() && tx_slate_id.is_some() {
+		let msg = format!("At most one of 'id' (-i) or 'txid' (-t) may be provided.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	let add = parse_tags(args, "add");
+	let remove = parse_tags(args, "remove");
+	if tx_id.is_none() && tx_slate_id.is_none() && !(add.is_empty() && remove.is_empty()) {
+		let msg = format!("'id' (-i) or 'txid' (-t) is required to add or remove tags.");
+		return Err(ParseError::ArgumentError(msg));
+	}
+	Ok(command::TagArgs {
+		id: tx_id,
+		tx_slate_id,
+		add,
+		remove,
+	})
+}
+
+pub fn parse_contacts_args(args: &ArgMatches) -> Result<command::ContactsArgs, ParseError> {
+	let add = match args.value_of("add") {
+		Some(name) => Some(Contact {
+			name: name.to_owned(),
+			proof_address: parse_required(args, "proof_address")?.to_owned(),
+			epicbox_address: args.value_of("epicbox_address").map(|a| a.to_owned()),
+			http_address: args.value_of("http_address").map(|a| a.to_owned()),
+			preferred_transport: args.value_of("preferred_transport").map(|t| t.to_owned()),
+			last_transport: None,
+		}),
+		None => {
+			if [
+				"proof_address",
+				"epicbox_address",
+				"http_address",
+				"preferred_transport",
+			]
+			.iter()
+			.any(|a| args.is_present(a))
+			{
+				let msg = format!("'add' (-a) is required to give the addresses of a contact.");
+				return Err(ParseError::ArgumentError(msg));
+			}
+			None
+		}
+	};
+	Ok(command::ContactsArgs {
+		add,
+		remove: args.value_of("remove").map(|r| r.to_owned()),
+	})
+}
+
+pub fn parse_templates_args(args: &ArgMatches) -> Result<command::TemplatesArgs, ParseError> {
+	let add = match args.value_of("add") {
+		Some(name) => {
+			let amount = parse_required(args, "amount")?;
+			let amount = match i18n::parse_amount(amount) {
+				Ok(a) => a,
+				Err(e) => {
+					let msg = format!("Could not parse amount in unit '{}'. e={}", i18n::unit(), e);
+					return Err(ParseError::ArgumentError(msg));
+				}
+			};
+			Some(SendTemplate {
+				name: name.to_owned(),
+				method: parse_required(args, "method")?.to_owned(),
+				dest: parse_required(args, "dest")?.to_owned(),
+				amount,
+				src_acct_name: args.value_of("src_account").map(|a| a.to_owned()),
+				message: args.value_of("message").map(|m| m.to_owned()),
+			})
+		}
+		None => {
+			if ["method", "dest", "amount", "src_account", "message"]
+				.iter()
+				.any(|a| args.is_present(a))
+			{
+				let msg = format!("'add' (-a) is required to give the send of a template.");
+				return Err(ParseError::ArgumentError(msg));
+			}
+			None
+		}
+	};
+	Ok(command::TemplatesArgs {
+		add,
+		remove: args.value_of("remove").map(|r| r.to_owned()),
+	})
+}
+
+pub fn parse_slate_inspect_args(
+	args: &ArgMatches,
+) -> Result<command::SlateInspectArgs, ParseError> {
+	Ok(command::SlateInspectArgs {
+		input: parse_required(args, "input")?.to_owned(),
+	})
+}
+
+pub fn parse_slate_diff_args(args: &ArgMatches) -> Result<command::SlateDiffArgs, ParseError> {
+	Ok(command::SlateDiffArgs {
+		from: parse_required(args, "from")?.to_owned(),
+		to: parse_required(args, "to")?.to_owned(),
+	})
+}
+
+pub fn parse_db_import_args(args: &ArgMatches) -> Result<command::DbImportArgs, ParseError> {
+	Ok(command::DbImportArgs {
+		input: parse_required(args, "input")?.to_owned(),
+	})
+}
+
+pub fn parse_db_export_args(args: &ArgMatches) -> Result<command::DbExportArgs, ParseError> {
+	Ok(command::DbExportArgs {
+		format: parse_required(args, "format")?.to_owned(),
+		output: parse_required(args, "output")?.to_owned(),
+	})
+}
+
+pub fn parse_swap_start_args(args: &ArgMatches) -> Result<command::SwapOfferArgs, ParseError> {
+	let amount = parse_required(args, "amount")?;
+	let amount = match i18n::parse_amount(amount) {
+		Ok(a) => a,
+		Err(e) => {
+			let msg = format!("Could not parse amount in unit '{}'. e={}", i18n::unit(), e);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	};
+	let secondary_currency = match parse_required(args, "currency")? {
+		"ltc" => SwapCurrency::Ltc,
+		_ => SwapCurrency::Btc,
+	};
+	let secondary_amount = parse_u64(
+		parse_required(args, "secondary_amount")?,
+		"secondary_amount",
+	)?;
+	let minimum_confirmations = parse_u64(
+		parse_required(args, "minimum_confirmations")?,
+		"minimum_confirmations",
+	)?;
+	let refund_blocks = parse_u64(parse_required(args, "refund_blocks")?, "refund_blocks")?;
+	let secondary_lock_hours = parse_u64(parse_required(args, "lock_hours")?, "lock_hours")?;
+	Ok(command::SwapOfferArgs {
+		dest: args.value_of("dest").map(|d| d.to_owned()),
+		start_args: SwapStartArgs {
+			src_acct_name: None,
+			amount,
+			secondary_currency,
+			secondary_amount,
+			minimum_confirmations,
+			refund_blocks,
+			secondary_lock_hours,
+		},
+	})
+}
+
+pub fn parse_swap_process_args(args: &ArgMatches) -> Result<command::SwapProcessArgs, ParseError> {
+	let input = args.value_of("input").map(|i| i.to_owned());
+	if let Some(ref i) = input {
+		if !Path::new(i).is_file() {
+			let msg = format!("File {} not found.", i);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	}
+	let id = match args.value_of("id") {
+		None => None,
+		Some(id) => match id.parse() {
+			Ok(i) => Some(i),
+			Err(e) => {
+				let msg = format!("Could not parse swap id. e={}", e);
+				return Err(ParseError::ArgumentError(msg));
+			}
+		},
+	};
+	if input.is_none() && id.is_none() {
+		let msg =
+			"Either the other party's message (-i) or the swap id (--id) is required".to_owned();
+		return Err(ParseError::ArgumentError(msg));
+	}
+	Ok(command::SwapProcessArgs {
+		input,
+		id,
+		dest: args.value_of("dest").map(|d| d.to_owned()),
+	})
+}
+
+pub fn parse_swap_cancel_args(args: &ArgMatches) -> Result<command::SwapCancelArgs, ParseError> {
+	let id = match parse_required(args, "id")?.parse() {
+		Ok(i) => i,
+		Err(e) => {
+			let msg = format!("Could not parse swap id. e={}", e);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	};
+	Ok(command::SwapCancelArgs { id })
+}
+
+pub fn parse_migrate_args(args: &ArgMatches) -> Result<command::MigrateArgs, ParseError> {
+	let to = match parse_required(args, "to")? {
+		"lmdb" => StoreBackend::Lmdb,
+		_ => StoreBackend::Sqlite,
+	};
+	Ok(command::MigrateArgs { to })
+}
+
+pub fn parse_backup_restore_args(
+	args: &ArgMatches,
+) -> Result<command::BackupRestoreArgs, ParseError> {
+	let name = parse_required(args, "name")?;
+	Ok(command::BackupRestoreArgs {
+		name: name.to_owned(),
 	})
 }
 
@@ -748,6 +1533,7 @@ pub fn parse_post_args(args: &ArgMatches) -> Result<command::PostArgs, ParseErro
 	Ok(command::PostArgs {
 		input: tx_file.to_owned(),
 		fluff,
+		ignore_node_height: args.is_present("ignore_node_height"),
 	})
 }
 
@@ -767,6 +1553,7 @@ pub fn parse_repost_args(args: &ArgMatches) -> Result<command::RepostArgs, Parse
 		id: tx_id.unwrap(),
 		dump_file,
 		fluff,
+		ignore_node_height: args.is_present("ignore_node_height"),
 	})
 }
 
@@ -799,6 +1586,83 @@ pub fn parse_cancel_args(args: &ArgMatches) -> Result<command::CancelArgs, Parse
 		tx_id_string: tx_id_string.to_owned(),
 	})
 }
+fn parse_tx_filter(args: &ArgMatches) -> Result<TxFilter, ParseError> {
+	let tx_type = match args.value_of("type") {
+		Some("sent") => Some(TxLogEntryType::TxSent),
+		Some("received") => Some(TxLogEntryType::TxReceived),
+		_ => None,
+	};
+	let min_age_hours = match args.value_of("older_than") {
+		None => None,
+		Some(h) => Some(parse_u64(h, "older_than")?),
+	};
+	Ok(TxFilter {
+		tx_type,
+		min_age_hours,
+	})
+}
+
+pub fn parse_cancel_txs_args(args: &ArgMatches) -> Result<command::CancelTxsArgs, ParseError> {
+	let filter = parse_tx_filter(args)?;
+	Ok(command::CancelTxsArgs { filter })
+}
+
+pub fn parse_repost_txs_args(args: &ArgMatches) -> Result<command::RepostTxsArgs, ParseError> {
+	let filter = parse_tx_filter(args)?;
+	let fluff = args.is_present("fluff");
+	Ok(command::RepostTxsArgs {
+		filter,
+		fluff,
+		ignore_node_height: args.is_present("ignore_node_height"),
+	})
+}
+
+pub fn parse_sweep_args(
+	config: &WalletConfig,
+	args: &ArgMatches,
+) -> Result<command::SweepArgs, ParseError> {
+	let mut policy = config.sweep.as_ref().map(command::sweep_policy);
+	if let Some(hot) = args.value_of("hot") {
+		let cold = parse_required(args, "cold")?;
+		let max_hot_balance = parse_required(args, "max_hot_balance")?;
+		policy = Some(SweepPolicy {
+			hot_account: hot.to_owned(),
+			cold_account: cold.to_owned(),
+			max_hot_balance: parse_sweep_amount(max_hot_balance)?,
+			minimum_confirmations: SWEEP_MINIMUM_CONFIRMATIONS,
+		});
+	}
+	let mut policy = match policy {
+		Some(p) => p,
+		None => {
+			let msg = format!(
+				"No sweep policy configured. Provide 'hot' (-h), 'cold' (-c) and 'max_hot_balance' (-m)."
+			);
+			return Err(ParseError::ArgumentError(msg));
+		}
+	};
+	if let Some(c) = args.value_of("cold") {
+		policy.cold_account = c.to_owned();
+	}
+	if let Some(m) = args.value_of("max_hot_balance") {
+		policy.max_hot_balance = parse_sweep_amount(m)?;
+	}
+	if let Some(c) = args.value_of("minimum_confirmations") {
+		policy.minimum_confirmations = parse_u64(c, "minimum_confirmations")?;
+	}
+	Ok(command::SweepArgs { policy })
+}
+
+fn parse_sweep_amount(amount: &str) -> Result<u64, ParseError> {
+	match i18n::parse_amount(amount) {
+		Ok(a) => Ok(a),
+		Err(e) => {
+			let msg = format!("Could not parse amount in unit '{}'. e={}", i18n::unit(), e);
+			Err(ParseError::ArgumentError(msg))
+		}
+	}
+}
+
 pub fn parse_export_proof_args(args: &ArgMatches) -> Result<command::ProofExportArgs, ParseError> {
 	let output_file = parse_required(args, "output")?;
 	let tx_id = match args.value_of("id") {
@@ -834,6 +1698,7 @@ pub fn parse_verify_proof_args(args: &ArgMatches) -> Result<command::ProofVerify
 	let input_file = parse_required(args, "input")?;
 	Ok(command::ProofVerifyArgs {
 		input_file: input_file.to_owned(),
+		offline: args.is_present("offline"),
 	})
 }
 
@@ -842,6 +1707,7 @@ pub fn wallet_command<C, F>(
 	mut wallet_config: WalletConfig,
 	tor_config: Option<TorConfig>,
 	epicbox_config: Option<EpicboxConfig>,
+	global_config: Option<GlobalWalletConfig>,
 	node_client: C,
 	test_mode: bool,
 	wallet_inst_cb: F,
@@ -866,6 +1732,8 @@ where
 	if let Some(t) = wallet_config.chain_type.clone() {
 		global::set_mining_mode(t);
 	}
+	let chain_type = global::CHAIN_TYPE.read().clone();
+	set_chain_params(&chain_type, wallet_config.chain_params.as_ref())?;
 
 	if wallet_args.is_present("external") {
 		wallet_config.api_listen_interface = "0.0.0.0".to_string();
@@ -877,6 +1745,17 @@ where
 
 	let global_wallet_args = arg_parse!(parse_global_args(&wallet_config, &wallet_args));
 
+	let display_unit = arg_parse!(parse_display_unit(&wallet_config, &wallet_args));
+	i18n::init(i18n::Localizer::new(
+		wallet_config.locale.as_ref().map(|l| l.as_str()),
+		wallet_config
+			.locale_catalog_path
+			.as_ref()
+			.map(|p| p.as_str()),
+		display_unit,
+		wallet_config.amount_decimals,
+	));
+
 	// legacy hack to avoid the need for changes in existing epic-wallet.toml files
 	// remove `wallet_data` from end of path as
 	// new lifecycle provider assumes epic_wallet.toml is in root of data directory
@@ -903,11 +1782,14 @@ where
 		None => EpicboxConfig::default(),
 	};
 
+	// overridden chain parameters are only used with a node on a custom chain
+	check_node_chain(&node_client)?;
+
 	// Instantiate wallet (doesn't open the wallet)
 	let wallet =
 		inst_wallet::<DefaultLCProvider<C, keychain::ExtKeychain>, C, keychain::ExtKeychain>(
 			wallet_config.clone(),
-			node_client,
+			node_client.clone(),
 		)
 		.unwrap_or_else(|e| {
 			eprintln!("{:?}", e);
@@ -918,6 +1800,7 @@ where
 		let mut wallet_lock = wallet.lock();
 		let lc = wallet_lock.lc_provider().unwrap();
 		let _ = lc.set_top_level_directory(&wallet_config.data_file_dir);
+		let _ = lc.set_read_only(wallet_args.is_present("read_only"));
 	}
 
 	// provide wallet instance back to the caller (handy for testing with local wallet proxy, etc)
@@ -928,6 +1811,14 @@ where
 	match wallet_args.subcommand() {
 		("init", Some(_)) => open_wallet = false,
 		("recover", _) => open_wallet = false,
+		("verify_seed", _) => open_wallet = false,
+		("slate", _) => open_wallet = false,
+		("migrate", _) => open_wallet = false,
+		("backup", _) => open_wallet = false,
+		("view", _) => open_wallet = false,
+		("owner_api", Some(args)) if args.subcommand_name() == Some("rotate-secret") => {
+			open_wallet = false
+		}
 		("owner_api", _) => {
 			// If wallet exists, open it. Otherwise, that's fine too.
 			let mut wallet_lock = wallet.lock();
@@ -937,16 +1828,15 @@ where
 		_ => {}
 	}
 
+	// kept for the listener's scheduled backups, whose key derives from it
+	let mut wallet_password = None;
 	let keychain_mask = match open_wallet {
 		true => {
 			let mut wallet_lock = wallet.lock();
 			let lc = wallet_lock.lc_provider().unwrap();
-			let mask = lc.open_wallet(
-				None,
-				prompt_password(&global_wallet_args.password),
-				false,
-				false,
-			)?;
+			let password = prompt_password(&global_wallet_args.password);
+			let mask = lc.open_wallet(None, password.clone(), false, false)?;
+			wallet_password = Some(password);
 			if let Some(account) = wallet_args.value_of("account") {
 				let wallet_inst = lc.wallet_inst()?;
 				wallet_inst.set_parent_key_id_by_name(account)?;
@@ -960,54 +1850,118 @@ where
 
 	let res = match wallet_args.subcommand() {
 		("init", Some(args)) => {
+			let verify_words = match args.value_of("verify") {
+				Some(v) => arg_parse!(parse_u64(v, "verify")) as usize,
+				None => 0,
+			};
 			let a = arg_parse!(parse_init_args(
 				wallet.clone(),
 				&wallet_config,
 				&global_wallet_args,
 				&args
 			));
-			command::init(wallet, &global_wallet_args, a)
+			let password = a.password.clone();
+			match command::init(wallet.clone(), &global_wallet_args, a) {
+				Ok(()) if verify_words > 0 => {
+					let (positions, words) =
+						arg_parse!(prompt_seed_words(wallet.clone(), &password, verify_words));
+					let args = command::VerifySeedArgs {
+						password,
+						positions,
+						words,
+					};
+					command::verify_seed(wallet, args)
+				}
+				r => r,
+			}
 		}
 		("recover", Some(_)) => {
 			let a = arg_parse!(parse_recover_args(&global_wallet_args,));
 			command::recover(wallet, a)
 		}
+		("verify_seed", Some(args)) => {
+			let a = arg_parse!(parse_verify_seed_args(
+				wallet.clone(),
+				&global_wallet_args,
+				&args
+			));
+			command::verify_seed(wallet, a)
+		}
 		("listen", Some(args)) => {
 			let mut c = wallet_config.clone();
 			let mut t = tor_config.clone();
 			let e = epicbox_config.clone();
 			let a = arg_parse!(parse_listen_args(&mut c, &mut t, &args));
+			let wallets = match a.method.as_str() {
+				"http" => arg_parse!(inst_foreign_api_wallets(
+					&c,
+					&node_client,
+					wallet_password.as_ref()
+				)),
+				_ => HashMap::new(),
+			};
+			let mut g = global_wallet_args.clone();
+			g.password = wallet_password;
 			command::listen(
 				wallet,
 				Arc::new(Mutex::new(keychain_mask)),
+				wallets,
 				&c,
 				&t,
 				&e,
 				&a,
-				&global_wallet_args.clone(),
+				&g,
 			)
 		}
+		("owner_api", Some(args)) if args.subcommand_name() == Some("rotate-secret") => {
+			command::owner_api_rotate_secret(&wallet_config)
+		}
 		("owner_api", Some(args)) => {
 			let mut c = wallet_config.clone();
 			let mut g = global_wallet_args.clone();
 			g.tls_conf = None;
 			arg_parse!(parse_owner_api_args(&mut c, &args));
-			command::owner_api(wallet, keychain_mask, &c, &tor_config, &epicbox_config, &g)
+			let wallets = arg_parse!(inst_owner_api_wallets(&c, &node_client));
+			command::owner_api(
+				wallet,
+				keychain_mask,
+				wallets,
+				&c,
+				&tor_config,
+				&epicbox_config,
+				&g,
+				global_config,
+			)
+		}
+		("web", Some(_)) => {
+			let wallets = arg_parse!(inst_owner_api_wallets(&wallet_config, &node_client));
+			command::owner_api(
+				wallet,
+				keychain_mask,
+				wallets,
+				&wallet_config,
+				&tor_config,
+				&epicbox_config,
+				&global_wallet_args,
+				global_config,
+			)
 		}
-		("web", Some(_)) => command::owner_api(
-			wallet,
-			keychain_mask,
-			&wallet_config,
-			&tor_config,
-			&epicbox_config,
-			&global_wallet_args,
-		),
 		("account", Some(args)) => {
 			let a = arg_parse!(parse_account_args(&args));
 			command::account(wallet, km, a)
 		}
 		("send", Some(args)) => {
-			let a = arg_parse!(parse_send_args(&args));
+			let template = match args.value_of("template") {
+				Some(name) => Some(command::send_template(wallet.clone(), km, name)?),
+				None => None,
+			};
+			let a = arg_parse!(parse_send_args(
+				&wallet_config,
+				&global_wallet_args,
+				&args,
+				template.as_ref(),
+				!test_mode
+			));
 			command::send(
 				wallet,
 				km,
@@ -1018,19 +1972,51 @@ where
 			)
 		}
 		("receive", Some(args)) => {
-			let a = arg_parse!(parse_receive_args(&args));
+			let a = arg_parse!(parse_receive_args(
+				&wallet_config,
+				&global_wallet_args,
+				&args
+			));
 			command::receive(wallet, km, &global_wallet_args, a)
 		}
 		("finalize", Some(args)) => {
-			let a = arg_parse!(parse_finalize_args(&args));
+			let a = arg_parse!(parse_finalize_args(&wallet_config, &args));
 			command::finalize(wallet, km, a)
 		}
+		("sign", Some(args)) => {
+			let a = arg_parse!(parse_sign_args(&args));
+			command::sign(wallet, km, a)
+		}
+		("pending", Some(_)) => command::pending(
+			wallet,
+			km,
+			command::PendingArgs {
+				expiry_hours: wallet_config.pending_receive_expiry_hours,
+			},
+		),
+		("fees", Some(args)) => {
+			let a = arg_parse!(parse_fees_args(&args));
+			command::fees(wallet, km, a)
+		}
+		("watch", Some(args)) => {
+			let a = arg_parse!(parse_watch_args(&wallet_config, &args));
+			command::watch(wallet, km, a)
+		}
 		("invoice", Some(args)) => {
-			let a = arg_parse!(parse_issue_invoice_args(&args));
+			let a = arg_parse!(parse_issue_invoice_args(
+				&wallet_config,
+				&global_wallet_args,
+				&args
+			));
 			command::issue_invoice_tx(wallet, km, a)
 		}
 		("pay", Some(args)) => {
-			let a = arg_parse!(parse_process_invoice_args(&args, !test_mode));
+			let a = arg_parse!(parse_process_invoice_args(
+				&wallet_config,
+				&global_wallet_args,
+				&args,
+				!test_mode
+			));
 			command::process_invoice(
 				wallet,
 				km,
@@ -1040,7 +2026,7 @@ where
 			)
 		}
 		("info", Some(args)) => {
-			let a = arg_parse!(parse_info_args(&args));
+			let a = arg_parse!(parse_info_args(&wallet_config, &args));
 			command::info(
 				wallet,
 				km,
@@ -1069,6 +2055,132 @@ where
 				wallet_config.dark_background_color_scheme.unwrap_or(true),
 			)
 		}
+		("tag", Some(args)) => {
+			let a = arg_parse!(parse_tag_args(&args));
+			command::tag(wallet, km, a)
+		}
+		("contacts", Some(args)) => {
+			let a = arg_parse!(parse_contacts_args(&args));
+			command::contacts(wallet, km, a)
+		}
+		("templates", Some(args)) => {
+			let a = arg_parse!(parse_templates_args(&args));
+			command::templates(wallet, km, a)
+		}
+		("slate", Some(args)) => match args.subcommand() {
+			("inspect", Some(args)) => {
+				let a = arg_parse!(parse_slate_inspect_args(&args));
+				command::slate_inspect(
+					a,
+					wallet_config.dark_background_color_scheme.unwrap_or(true),
+				)
+			}
+			("diff", Some(args)) => {
+				let a = arg_parse!(parse_slate_diff_args(&args));
+				command::slate_diff(a)
+			}
+			_ => {
+				let msg =
+					format!("Unknown slate command, use 'epic-wallet help slate' for details");
+				return Err(ErrorKind::ArgumentError(msg).into());
+			}
+		},
+		("view", Some(args)) => {
+			let snapshot = arg_parse!(parse_required(args, "snapshot"));
+			{
+				let mut wallet_lock = wallet.lock();
+				let lc = wallet_lock.lc_provider()?;
+				lc.open_snapshot(snapshot)?;
+			}
+			let dark_scheme = wallet_config.dark_background_color_scheme.unwrap_or(true);
+			match args.subcommand() {
+				("info", Some(args)) => {
+					let mut a = arg_parse!(parse_info_args(&wallet_config, &args));
+					a.auto_initial_scan = false;
+					command::info(wallet, None, &global_wallet_args, a, dark_scheme)
+				}
+				("outputs", Some(args)) => {
+					let a = arg_parse!(parse_outputs_args(&args));
+					command::outputs(wallet, None, &global_wallet_args, a, dark_scheme)
+				}
+				("txs", Some(args)) => {
+					let a = arg_parse!(parse_txs_args(&args));
+					command::txs(wallet, None, &global_wallet_args, a, dark_scheme)
+				}
+				("account", Some(args)) => {
+					let a = arg_parse!(parse_account_args(&args));
+					command::account(wallet, None, a)
+				}
+				("owner_api", Some(args)) => {
+					let mut c = wallet_config.clone();
+					arg_parse!(parse_owner_api_args(&mut c, &args));
+					command::view_owner_api(wallet, &c, &global_wallet_args)
+				}
+				_ => {
+					let msg =
+						"Unknown view command, use 'epic-wallet help view' for details".to_owned();
+					return Err(ErrorKind::ArgumentError(msg).into());
+				}
+			}
+		}
+		("db", Some(args)) => match args.subcommand() {
+			("export", Some(args)) => {
+				let a = arg_parse!(parse_db_export_args(&args));
+				command::db_export(wallet, a)
+			}
+			("import", Some(args)) => {
+				let a = arg_parse!(parse_db_import_args(&args));
+				command::db_import(wallet, km, a)
+			}
+			_ => {
+				let msg = "Unknown db command, use 'epic-wallet help db' for details".to_owned();
+				return Err(ErrorKind::ArgumentError(msg).into());
+			}
+		},
+		("swap", Some(args)) => match args.subcommand() {
+			("start", Some(args)) => {
+				let a = arg_parse!(parse_swap_start_args(&args));
+				command::swap_start(wallet, km, a)
+			}
+			("process", Some(args)) => {
+				let a = arg_parse!(parse_swap_process_args(&args));
+				command::swap_process(wallet, km, a)
+			}
+			("cancel", Some(args)) => {
+				let a = arg_parse!(parse_swap_cancel_args(&args));
+				command::swap_cancel(wallet, km, a)
+			}
+			_ => {
+				let msg =
+					"Unknown swap command, use 'epic-wallet help swap' for details".to_owned();
+				return Err(ErrorKind::ArgumentError(msg).into());
+			}
+		},
+		("migrate", Some(args)) => {
+			let a = arg_parse!(parse_migrate_args(&args));
+			let data_dir = Path::new(&wallet_config.data_file_dir).join(EPIC_WALLET_DIR);
+			command::migrate(&data_dir.to_string_lossy(), a)
+		}
+		("backup", Some(args)) => {
+			let data_dir = Path::new(&wallet_config.data_file_dir).join(EPIC_WALLET_DIR);
+			let data_dir = data_dir.to_string_lossy();
+			match args.subcommand() {
+				("now", Some(_)) => {
+					let password = prompt_password(&global_wallet_args.password);
+					command::backup_now(&wallet_config, &data_dir, password)
+				}
+				("restore", Some(args)) => {
+					let a = arg_parse!(parse_backup_restore_args(&args));
+					let password = prompt_password(&global_wallet_args.password);
+					command::backup_restore(&wallet_config, &data_dir, password, a)
+				}
+				_ => {
+					let msg = "Unknown backup command, use 'epic-wallet help backup' for details"
+						.to_owned();
+					return Err(ErrorKind::ArgumentError(msg).into());
+				}
+			}
+		}
 		("post", Some(args)) => {
 			let a = arg_parse!(parse_post_args(&args));
 			command::post(wallet, km, a)
@@ -1081,6 +2193,18 @@ where
 			let a = arg_parse!(parse_cancel_args(&args));
 			command::cancel(wallet, km, a)
 		}
+		("cancel_txs", Some(args)) => {
+			let a = arg_parse!(parse_cancel_txs_args(&args));
+			command::cancel_txs(wallet, km, a)
+		}
+		("repost_txs", Some(args)) => {
+			let a = arg_parse!(parse_repost_txs_args(&args));
+			command::repost_txs(wallet, km, a)
+		}
+		("sweep", Some(args)) => {
+			let a = arg_parse!(parse_sweep_args(&wallet_config, &args));
+			command::sweep(wallet, km, a)
+		}
 		("export_proof", Some(args)) => {
 			let a = arg_parse!(parse_export_proof_args(&args));
 			command::proof_export(wallet, km, a)
@@ -1091,7 +2215,7 @@ where
 		}
 		("address", Some(_)) => command::address(wallet, &global_wallet_args, km),
 		("scan", Some(args)) => {
-			let a = arg_parse!(parse_check_args(&args));
+			let a = arg_parse!(parse_check_args(&args, &global_wallet_args));
 			command::scan(wallet, km, a)
 		}
 		_ => {
@@ -23,6 +23,7 @@ extern crate epic_wallet;
 use epic_wallet_impls::test_framework::{self, LocalWalletClient, WalletProxy};
 
 use clap::App;
+use std::path::Path;
 use std::thread;
 use std::time::Duration;
 
@@ -338,6 +339,28 @@ fn command_line_test_impl(test_dir: &str) -> Result<(), epic_wallet_controller::
 		Ok(())
 	})?;
 
+	// Export the wallet's records, one JSON record per line
+	let export_file_name = format!("{}/wallet1_export.jsonl", test_dir);
+	let arg_vec = vec![
+		"epic-wallet",
+		"-p",
+		"password",
+		"db",
+		"export",
+		"-o",
+		&export_file_name,
+	];
+	execute_command(&app, test_dir, "wallet1", &client1, arg_vec)?;
+	let export = std::fs::read_to_string(&export_file_name).unwrap();
+	let records: Vec<serde_json::Value> = export
+		.lines()
+		.map(|l| serde_json::from_str(l).unwrap())
+		.collect();
+	let count = |t: &str| records.iter().filter(|r| r["type"] == t).count();
+	assert_eq!(count("account"), 3);
+	assert!(count("output") > 0);
+	assert!(count("tx") > bh as usize);
+
 	// Try using the self-send method, splitting up outputs for the fun of it
 	let arg_vec = vec![
 		"epic-wallet",
@@ -346,6 +369,7 @@ fn command_line_test_impl(test_dir: &str) -> Result<(), epic_wallet_controller::
 		"-a",
 		"mining",
 		"send",
+		"--yes",
 		"-m",
 		"self",
 		"-d",
@@ -430,6 +454,63 @@ fn command_line_test_impl(test_dir: &str) -> Result<(), epic_wallet_controller::
 	];
 	execute_command(&app, test_dir, "wallet1", &client1, arg_vec)?;
 
+	// save the send as a template, and send it again from the template's account
+	let template_file_name = format!("{}/template_tx.part_tx", test_dir);
+	let arg_vec = vec![
+		"epic-wallet",
+		"-p",
+		"password",
+		"templates",
+		"-a",
+		"not-sending",
+		"-m",
+		"file",
+		"-d",
+		&template_file_name,
+		"-v",
+		"10",
+		"-s",
+		"mining",
+		"-g",
+		"Ain't sending 3",
+	];
+	execute_command(&app, test_dir, "wallet1", &client1, arg_vec)?;
+
+	// the template gives the amount and destination
+	let arg_vec = vec![
+		"epic-wallet",
+		"-p",
+		"password",
+		"send",
+		"--template",
+		"not-sending",
+		"10",
+	];
+	assert!(execute_command(&app, test_dir, "wallet1", &client1, arg_vec).is_err());
+	let arg_vec = vec![
+		"epic-wallet",
+		"-p",
+		"password",
+		"send",
+		"--template",
+		"unknown",
+	];
+	assert!(execute_command(&app, test_dir, "wallet1", &client1, arg_vec).is_err());
+
+	let arg_vec = vec![
+		"epic-wallet",
+		"-p",
+		"password",
+		"send",
+		"--template",
+		"not-sending",
+	];
+	execute_command(&app, test_dir, "wallet1", &client1, arg_vec)?;
+	assert!(Path::new(&template_file_name).is_file());
+
+	let arg_vec = vec!["epic-wallet", "-p", "password", "scan", "-d"];
+	execute_command(&app, test_dir, "wallet1", &client1, arg_vec)?;
+
 	// issue an invoice tx, wallet 2
 	let file_name = format!("{}/invoice.slate", test_dir);
 	let arg_vec = vec![
@@ -284,6 +284,7 @@ pub fn execute_command(
 		wallet_config.clone(),
 		tor_config,
 		epicbox_config,
+		None,
 		client.clone(),
 		true,
 		|_| {},
@@ -331,6 +332,7 @@ where
 		wallet_config,
 		tor_config,
 		epicbox_config,
+		None,
 		client.clone(),
 		true,
 		f,
@@ -454,7 +454,8 @@ fn owner_v3_lifecycle() -> Result<(), epic_wallet_controller::Error> {
 		"method": "open_wallet",
 		"params": {
 			"name": null,
-			"password": "password"
+			"password": "password",
+			"scope": null
 		}
 	});
 	let res = send_request_enc::<String>(
@@ -553,7 +554,7 @@ fn owner_v3_lifecycle() -> Result<(), epic_wallet_controller::Error> {
 	assert!(res.is_ok());
 	println!("RES 23: {:?}", res);
 
-	// 24) Delete the wallet (close first)
+	// 24) Close the wallet
 	let req = include_str!("data/v3_reqs/close_wallet.req.json");
 	let res = send_request_enc::<String>(
 		RpcId::Integer(1),
@@ -564,18 +565,104 @@ fn owner_v3_lifecycle() -> Result<(), epic_wallet_controller::Error> {
 	)?;
 	assert!(res.is_ok());
 
-	let req = include_str!("data/v3_reqs/delete_wallet.req.json");
+	// 25) Recover the seed from its mnemonic, with a new password
+	let req = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "get_mnemonic",
+		"params": {
+			"name": null,
+			"password": "password"
+		}
+	});
 	let res = send_request_enc::<String>(
 		RpcId::Integer(1),
 		1,
 		"http://127.0.0.1:43420/v3/owner",
-		&req,
+		&req.to_string(),
 		&shared_key,
 	)?;
-	println!("RES 24: {:?}", res);
 	assert!(res.is_ok());
+	let mnemonic = res.unwrap();
 
-	// 25) Wallet should be gone
+	let req = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "recover_from_mnemonic",
+		"params": {
+			"name": null,
+			"mnemonic": mnemonic,
+			"password": "recovered"
+		}
+	});
+	let res = send_request_enc::<String>(
+		RpcId::Integer(1),
+		1,
+		"http://127.0.0.1:43420/v3/owner",
+		&req.to_string(),
+		&shared_key,
+	)?;
+	println!("RES 25: {:?}", res);
+	assert!(res.is_ok());
+
+	// 26) Deleting needs a confirmation token
+	let req = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "delete_wallet",
+		"params": {
+			"name": null,
+			"confirmation_token": "not_a_token"
+		}
+	});
+	let res = send_request_enc::<String>(
+		RpcId::Integer(1),
+		1,
+		"http://127.0.0.1:43420/v3/owner",
+		&req.to_string(),
+		&shared_key,
+	)?;
+	assert!(res.is_err());
+
+	let req = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "get_delete_token",
+		"params": {
+			"name": null,
+			"password": "recovered"
+		}
+	});
+	let res = send_request_enc::<String>(
+		RpcId::Integer(1),
+		1,
+		"http://127.0.0.1:43420/v3/owner",
+		&req.to_string(),
+		&shared_key,
+	)?;
+	assert!(res.is_ok());
+	let confirmation_token = res.unwrap();
+
+	let req = serde_json::json!({
+		"jsonrpc": "2.0",
+		"id": 1,
+		"method": "delete_wallet",
+		"params": {
+			"name": null,
+			"confirmation_token": confirmation_token
+		}
+	});
+	let res = send_request_enc::<String>(
+		RpcId::Integer(1),
+		1,
+		"http://127.0.0.1:43420/v3/owner",
+		&req.to_string(),
+		&shared_key,
+	)?;
+	println!("RES 26: {:?}", res);
+	assert!(res.is_ok());
+
+	// 27) Wallet should be gone
 	let req = include_str!("data/v3_reqs/open_wallet.req.json");
 	let res = send_request_enc::<String>(
 		RpcId::Integer(1),
@@ -584,7 +671,7 @@ fn owner_v3_lifecycle() -> Result<(), epic_wallet_controller::Error> {
 		&req,
 		&shared_key,
 	)?;
-	println!("RES 25: {:?}", res);
+	println!("RES 27: {:?}", res);
 	assert!(res.is_err());
 
 	clean_output_dir(test_dir);